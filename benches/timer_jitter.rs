@@ -0,0 +1,97 @@
+//! Compares frame pacing of `every_precise` at 60 FPS with and without the
+//! high precision (spin-wait) timer.
+//!
+//! Run with `cargo bench --bench timer_jitter`.
+
+use bubbletea_rs::{command, Cmd, Model, Msg, Program, QuitMsg};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const TARGET_FPS: f64 = 60.0;
+const FRAMES: usize = 240;
+
+static FRAME_TIMES: Mutex<Vec<Instant>> = Mutex::new(Vec::new());
+
+#[derive(Debug)]
+struct FrameMsg(Instant);
+
+struct FrameModel;
+
+impl Model for FrameModel {
+    fn init() -> (Self, Option<Cmd>) {
+        let cmd = command::every_precise(TARGET_FPS, |at| Box::new(FrameMsg(at)) as Msg);
+        (Self, Some(cmd))
+    }
+
+    #[allow(refining_impl_trait)]
+    fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        if let Some(FrameMsg(at)) = msg.downcast_ref::<FrameMsg>() {
+            let mut times = FRAME_TIMES.lock().unwrap();
+            times.push(*at);
+            if times.len() > FRAMES {
+                return Some(Box::pin(async { Some(Box::new(QuitMsg) as Msg) }));
+            }
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+struct Jitter {
+    mean_us: f64,
+    p99_us: f64,
+    max_us: f64,
+}
+
+fn measure(high_precision: bool, runtime: &tokio::runtime::Runtime) -> Jitter {
+    FRAME_TIMES.lock().unwrap().clear();
+    runtime.block_on(async {
+        let program = Program::<FrameModel>::builder()
+            .without_renderer()
+            .signal_handler(false)
+            .high_precision_timer(high_precision)
+            .build()
+            .expect("program build");
+        program.run().await.expect("program run");
+    });
+
+    let target = Duration::from_secs_f64(1.0 / TARGET_FPS).as_secs_f64() * 1e6;
+    let times = FRAME_TIMES.lock().unwrap();
+    let mut deviations: Vec<f64> = times
+        .windows(2)
+        .map(|w| ((w[1] - w[0]).as_secs_f64() * 1e6 - target).abs())
+        .collect();
+    deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mean_us = deviations.iter().sum::<f64>() / deviations.len() as f64;
+    let p99_us = deviations[(deviations.len() * 99) / 100];
+    let max_us = *deviations.last().unwrap();
+    Jitter {
+        mean_us,
+        p99_us,
+        max_us,
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("tokio runtime");
+
+    println!("every_precise jitter at {TARGET_FPS} FPS over {FRAMES} frames");
+    println!(
+        "{:<16} {:>12} {:>12} {:>12}",
+        "mode", "mean (us)", "p99 (us)", "max (us)"
+    );
+    for (label, high_precision) in [("sleep", false), ("spin-wait", true)] {
+        let j = measure(high_precision, &runtime);
+        println!(
+            "{:<16} {:>12.1} {:>12.1} {:>12.1}",
+            label, j.mean_us, j.p99_us, j.max_us
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Derive macros for `bubbletea-rs`.
+//!
+//! Use these through the `derive` feature of `bubbletea-rs`, which
+//! re-exports them next to the traits they implement.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+/// Derives `bubbletea_rs::message::Message`.
+///
+/// The message is named after the type, and its `Debug` implementation is
+/// used by `as_debug`. Both can be changed with the `message` attribute:
+///
+/// - `#[message(name = "...")]` sets the name.
+/// - `#[message(no_debug)]` makes `as_debug` return `None`, for types that
+///   do not implement `Debug`.
+#[proc_macro_derive(Message, attributes(message))]
+pub fn derive_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_message(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_message(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let mut name = ident.to_string();
+    let mut debug = true;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("message")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("no_debug") {
+                debug = false;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"...\"` or `no_debug`"))
+            }
+        })?;
+    }
+
+    let as_debug = if debug {
+        quote!(::core::option::Option::Some(self))
+    } else {
+        quote!(::core::option::Option::None)
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bubbletea_rs::message::Message for #ident #ty_generics #where_clause {
+            fn name(&self) -> &'static str {
+                #name
+            }
+
+            fn as_debug(&self) -> ::core::option::Option<&dyn ::core::fmt::Debug> {
+                #as_debug
+            }
+        }
+    })
+}
@@ -11,14 +11,6 @@ use bubbletea_rs::{
 use bubbletea_widgets::key::{new_binding, with_help, with_keys_str, Binding};
 use lipgloss_extras::lipgloss::{Color, Style};
 
-// Synthetic message used to trigger the initial render immediately after startup.
-#[derive(Debug)]
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 // Key mappings for the altscreen toggle example
 #[derive(Debug)]
 pub struct KeyBindings {
@@ -63,17 +55,12 @@ impl Model for AltScreenModel {
                 suspending: false,
                 keys: KeyBindings::default(),
             },
-            // Trigger an initial render so the view shows immediately on startup.
-            Some(init_render_cmd()),
+            None,
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Initial render trigger is a no-op beyond causing the first render
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return None;
-        }
-
         // Resume clears suspending state
         if msg.downcast_ref::<ResumeMsg>().is_some() {
             self.suspending = false;
@@ -25,14 +25,6 @@ use serde::Deserialize;
 
 const REPOS_URL: &str = "https://api.github.com/orgs/charmbracelet/repos";
 
-// Triggers an initial render immediately after startup to avoid a blank screen
-// during early async work (e.g., HTTP fetch).
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 #[derive(Debug, Clone, Deserialize)]
 struct Repo {
     #[serde(rename = "name")]
@@ -112,19 +104,12 @@ impl Model for AutocompleteModel {
         let mut model = Self::new();
         // Focus the input so it immediately receives typed characters
         let focus_cmd = model.text_input.focus();
-        // Start HTTP request for repos and trigger an immediate first render
-        (
-            model,
-            Some(batch(vec![fetch_repos(), init_render_cmd(), focus_cmd])),
-        )
+        // Start HTTP request for repos
+        (model, Some(batch(vec![fetch_repos(), focus_cmd])))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Initial render trigger; no state change required.
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return None;
-        }
-
         // HTTP results: set suggestions on textinput
         if let Some(GotReposMsg(repos)) = msg.downcast_ref::<GotReposMsg>().cloned() {
             let suggestions: Vec<String> = repos.into_iter().map(|r| r.name).collect();
@@ -209,6 +209,7 @@ impl Model for CellBufferModel {
         (m, Some(batch(vec![init_cmd(), window_size(), animate()])))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle init message - force initial render
         if msg.downcast_ref::<InitMsg>().is_some() {
@@ -90,6 +90,7 @@ impl Model for ChatModel {
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Window size: update layout and recompute content wrapping
         if let Some(ws) = msg.downcast_ref::<bubbletea_rs::WindowSizeMsg>() {
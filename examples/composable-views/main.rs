@@ -209,6 +209,7 @@ impl Model for MainModel {
         (model, Some(batch(vec![timer_cmd, spinner_cmd])))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         let mut cmds: Vec<Cmd> = Vec::new();
 
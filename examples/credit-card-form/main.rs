@@ -1,10 +1,12 @@
 //! Credit Card Form Example
 //!
-//! Demonstrates a credit card form with validation using bubbletea-widgets
+//! Demonstrates a credit card form with validation using bubbletea-widgets,
+//! and pasting a whole card number into a field in one step
 
-use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, Program};
+use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, PasteMsg, Program};
 use bubbletea_widgets::{key, textinput};
 use lipgloss_extras::lipgloss::{join_horizontal, join_vertical, Color, Style, LEFT};
+use std::time::Duration;
 
 const CCN: usize = 0;
 const EXP: usize = 1;
@@ -55,6 +57,27 @@ impl CreditCardForm {
         self.focused = (self.focused + 1) % self.inputs.len();
     }
 
+    /// Fills the focused field from pasted text in one step.
+    ///
+    /// Typed input is formatted and validated key by key; a paste is
+    /// formatted and validated once, with whitespace removed. Bracketed and
+    /// inferred pastes are handled alike, but an inferred paste may end with
+    /// the Enter of a copied line, which is dropped with the rest of the
+    /// whitespace instead of moving to the next field.
+    fn paste(&mut self, paste: &PasteMsg) {
+        let text: String = paste
+            .sanitized()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let value = match self.focused {
+            CCN => format_ccn(&text),
+            EXP => format_exp(&text),
+            _ => text,
+        };
+        self.inputs[self.focused].set_value(&value);
+    }
+
     fn prev_input(&mut self) {
         if self.focused == 0 {
             self.focused = self.inputs.len() - 1;
@@ -191,7 +214,13 @@ impl Model for CreditCardForm {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        if let Some(paste) = msg.downcast_ref::<PasteMsg>() {
+            self.paste(paste);
+            return None;
+        }
+
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             match key_msg {
                 _ if self.keymap.quit.matches(key_msg) => {
@@ -299,9 +328,13 @@ impl Model for CreditCardForm {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Terminals without bracketed paste deliver a paste as a burst of key
+    // presses; the heuristic turns such bursts into one `PasteMsg` too
     let program = Program::<CreditCardForm>::builder()
         .alt_screen(true)
         .signal_handler(true)
+        .bracketed_paste(true)
+        .paste_heuristic(10, Duration::from_millis(50))
         .build()?;
     let _ = program.run().await?;
     Ok(())
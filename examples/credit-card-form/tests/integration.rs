@@ -1,4 +1,4 @@
-use bubbletea_rs::{KeyMsg, Model};
+use bubbletea_rs::{KeyMsg, Model, PasteMsg};
 use crossterm::event::{KeyCode, KeyModifiers};
 
 // Re-export the main module for testing
@@ -108,3 +108,44 @@ fn test_exp_formatting() {
     // Test input with existing slash (should reformat)
     assert_eq!(format_exp("12/25"), "12/25");
 }
+
+/// Feeds `msg` to a fresh form and returns its view.
+fn view_after(msg: bubbletea_rs::Msg) -> String {
+    let (mut model, _cmd) = CreditCardForm::init();
+    let _cmd = model.update(msg);
+    model.view()
+}
+
+#[test]
+fn test_bracketed_paste_formats_card_number_at_once() {
+    let view = view_after(Box::new(PasteMsg::new("4111 1111 1111 1111", true)));
+    assert!(view.contains("4111 1111 1111 1111"));
+
+    // Whitespace in the pasted text is dropped before formatting
+    let view = view_after(Box::new(PasteMsg::new(" 4111\t111111111111 ", true)));
+    assert!(view.contains("4111 1111 1111 1111"));
+}
+
+#[tokio::test]
+async fn test_inferred_paste_formats_card_number_at_once() {
+    use bubbletea_rs::{InputHandler, InputSource};
+    use std::time::Duration;
+
+    // Without bracketed paste the card number arrives as a burst of keys,
+    // ending with the Enter of the copied line
+    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+    let source = InputSource::Custom(Box::pin(std::io::Cursor::new("4111 1111 1111 1111\n")));
+    InputHandler::with_source(event_tx, source)
+        .with_paste_heuristic(10, Duration::from_millis(50))
+        .run()
+        .await
+        .unwrap();
+
+    let msg = event_rx.recv().await.unwrap();
+    let paste = msg.downcast_ref::<PasteMsg>().expect("expected PasteMsg");
+    assert!(!paste.bracketed);
+    assert!(event_rx.try_recv().is_err(), "the burst is one message");
+
+    let view = view_after(msg);
+    assert!(view.contains("4111 1111 1111 1111"));
+}
@@ -20,14 +20,6 @@ const DEBOUNCE_DURATION: Duration = Duration::from_secs(1);
 #[derive(Debug)]
 pub struct ExitMsg(pub i32);
 
-/// Synthetic message used to trigger the initial render immediately after startup
-#[derive(Debug)]
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 /// The model represents our application state
 #[derive(Debug)]
 pub struct DebounceModel {
@@ -37,9 +29,10 @@ pub struct DebounceModel {
 impl Model for DebounceModel {
     fn init() -> (Self, Option<Cmd>) {
         let model = DebounceModel { tag: 0 };
-        (model, Some(init_render_cmd()))
+        (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle keyboard input
         if let Some(_key_msg) = msg.downcast_ref::<KeyMsg>() {
@@ -65,12 +58,6 @@ impl Model for DebounceModel {
             }
         }
 
-        // Handle initial render message (no-op, just triggers view)
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // This message just triggers the initial render
-            return None;
-        }
-
         None
     }
 
@@ -3,14 +3,6 @@ use std::env;
 use bubbletea_rs::{enter_alt_screen, exit_alt_screen, quit, Cmd, KeyMsg, Model, Msg, Program};
 use bubbletea_widgets::key::{new_binding, with_help, with_keys_str, Binding};
 
-// Synthetic message used to trigger the initial render immediately after startup.
-#[derive(Debug)]
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 #[derive(Debug)]
 struct EditorFinishedMsg {
     err: Option<String>,
@@ -75,15 +67,11 @@ struct ExecModel {
 
 impl Model for ExecModel {
     fn init() -> (Self, Option<Cmd>) {
-        (Self::default(), Some(init_render_cmd()))
+        (Self::default(), None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Initial render trigger is a no-op beyond causing the first render
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return None;
-        }
-
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             if self.keys.toggle_altscreen.matches(key_msg) {
                 self.altscreen_active = !self.altscreen_active;
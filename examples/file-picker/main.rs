@@ -114,6 +114,7 @@ impl Model for FilePickerModel {
         }
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle quit keys
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
@@ -41,6 +41,7 @@ impl Model for AppModel {
         (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if msg.downcast_ref::<FocusMsg>().is_some() {
             self.focused = true;
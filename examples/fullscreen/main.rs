@@ -64,6 +64,7 @@ impl Model for FullscreenModel {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle keyboard input - check if user wants to quit
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
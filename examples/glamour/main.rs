@@ -238,6 +238,7 @@ impl Model for GlamourModel {
         }
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<bubbletea_rs::Cmd> {
         // Handle keyboard input - check for quit keys first
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
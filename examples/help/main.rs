@@ -16,16 +16,8 @@ use bubbletea_widgets::help::{KeyMap as HelpKeyMap, Model as HelpModel};
 use bubbletea_widgets::key::{
     matches_binding, new_binding, with_help, with_keys_str, Binding, KeyMap,
 };
-use crossterm::terminal;
 use lipgloss_extras::lipgloss::{Color, Style};
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 /// KeyMap defines a set of keybindings. To work for help it must satisfy
 /// key::KeyMap. It mirrors the Go example structure.
 #[derive(Debug, Clone)]
@@ -115,17 +107,11 @@ impl HelpKeyMap for Model {
 
 impl BubbleTeaModel for Model {
     fn init() -> (Self, Option<Cmd>) {
-        let mut model = Self::new();
-
-        // Set initial terminal width if available
-        if let Ok((w, _h)) = terminal::size() {
-            model.help.width = w as usize;
-        }
-
-        // Emit a synthetic message immediately to trigger the first render
-        (model, Some(init_render_cmd()))
+        // The help width is set from the WindowSizeMsg sent before the first frame
+        (Self::new(), None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             if matches_binding(key_msg, &self.keys.up) {
@@ -146,8 +132,6 @@ impl BubbleTeaModel for Model {
             // If we set a width on the help menu it can gracefully truncate
             // its view as needed.
             self.help.width = size_msg.width as usize;
-        } else if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // No-op: receiving this message merely triggers the initial render
         }
 
         None
@@ -40,7 +40,7 @@ mod tests {
             "No key should be pressed initially"
         );
         assert!(!model.quitting, "Model should not be quitting initially");
-        assert!(cmd.is_some(), "Init should return an InitRenderMsg command");
+        assert!(cmd.is_none(), "Init should not need a command");
     }
 
     #[test]
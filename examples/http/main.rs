@@ -32,6 +32,7 @@ impl Model for HttpModel {
         (model, Some(check_server()))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             match (key_msg.key, key_msg.modifiers) {
@@ -0,0 +1,78 @@
+//! Inline Preview Example
+//!
+//! An inline program, drawn in a small live region below the shell prompt,
+//! that opens a fullscreen preview on the alternate screen and comes back.
+//! Each return logs a line above the live region.
+//!
+//! Toggle the preview with space, resize the terminal while it is open, and
+//! toggle again: the inline frame is cleared and drawn again where it was,
+//! at the new width, instead of relying on what the terminal restored.
+//!
+//! Usage: cargo run --bin inline-preview
+
+use bubbletea_rs::{command, Cmd, KeyMsg, Model, Msg, Program};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Rows of the live region.
+pub const LIVE_ROWS: u16 = 2;
+
+#[derive(Debug, Default)]
+pub struct PreviewModel {
+    pub previewing: bool,
+    pub previews: u32,
+}
+
+impl Model for PreviewModel {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self::default(), Some(command::window_size()))
+    }
+
+    #[allow(refining_impl_trait)]
+    fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        let key = msg.downcast_ref::<KeyMsg>()?;
+        match key.key {
+            KeyCode::Char('q') | KeyCode::Esc => Some(command::quit()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(command::quit())
+            }
+            KeyCode::Char(' ') if self.previewing => {
+                self.previewing = false;
+                let note = format!("closed preview #{}", self.previews);
+                Some(command::sequence(vec![
+                    command::exit_alt_screen(),
+                    command::println(note),
+                ]))
+            }
+            KeyCode::Char(' ') => {
+                self.previewing = true;
+                self.previews += 1;
+                Some(command::enter_alt_screen())
+            }
+            _ => None,
+        }
+    }
+
+    fn view_sized(&self, width: u16, height: u16) -> String {
+        if self.previewing {
+            format!(
+                "Preview #{} ({width}x{height})\n\nResize the terminal, then press space to go back.",
+                self.previews
+            )
+        } else {
+            format!(
+                "{width}x{height}, {} previews opened\nspace: open preview • q: quit",
+                self.previews
+            )
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let program = Program::<PreviewModel>::builder()
+        .inline_with_log_region(LIVE_ROWS)
+        .build()?;
+    program.run().await?;
+    Ok(())
+}
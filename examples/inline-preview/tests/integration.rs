@@ -0,0 +1,79 @@
+use bubbletea_rs::{
+    KeyMsg, Model, Msg, RenderHint, Terminal, TerminalCapabilities, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[path = "../main.rs"]
+mod inline_preview_main;
+use inline_preview_main::{PreviewModel, LIVE_ROWS};
+
+fn space() -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(' '),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[test]
+fn test_space_toggles_the_preview() {
+    let (mut model, _) = PreviewModel::init();
+    assert!(model.update(space()).is_some());
+    assert!(model.previewing);
+    assert!(model.view_sized(80, 24).starts_with("Preview #1 (80x24)"));
+
+    assert!(model.update(space()).is_some());
+    assert!(!model.previewing);
+    assert!(model.view_sized(80, 24).contains("1 previews opened"));
+}
+
+/// Plays the program's part: draws each view inline or on the alternate
+/// screen as the model asks, and records what returning from the preview
+/// writes.
+#[tokio::test]
+async fn test_repeated_toggles_under_resize_restore_the_inline_frame() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default())
+        .with_log_region(LIVE_ROWS);
+    let (mut model, _) = PreviewModel::init();
+    let mut size = (80, 24);
+
+    let mut returns = Vec::new();
+    for resized in [(60, 20), (100, 30), (40, 10)] {
+        terminal
+            .render(&model.view_sized(size.0, size.1), RenderHint::Full)
+            .await
+            .unwrap();
+
+        model.update(space());
+        terminal.enter_alt_screen().await.unwrap();
+        terminal
+            .render(&model.view_sized(size.0, size.1), RenderHint::Full)
+            .await
+            .unwrap();
+        size = resized;
+        terminal
+            .render(&model.view_sized(size.0, size.1), RenderHint::Full)
+            .await
+            .unwrap();
+
+        model.update(space());
+        output.lock().await.clear();
+        terminal.exit_alt_screen().await.unwrap();
+        returns.push(String::from_utf8(std::mem::take(&mut *output.lock().await)).unwrap());
+    }
+
+    // Each return clears the two-row region and redraws the frame that was
+    // showing before the preview opened
+    assert_eq!(
+        returns,
+        [
+            "\x1b[?1049l\r\x1b[1A\x1b[J80x24, 0 previews opened\r\nspace: open preview • q: quit",
+            "\x1b[?1049l\r\x1b[1A\x1b[J60x20, 1 previews opened\r\nspace: open preview • q: quit",
+            "\x1b[?1049l\r\x1b[1A\x1b[J100x30, 2 previews opened\r\nspace: open preview • q: quit",
+        ]
+    );
+}
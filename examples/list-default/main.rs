@@ -3,22 +3,13 @@
 // This is a minimal implementation that matches the simplicity of the Go version.
 // The rich help text is provided automatically by bubbletea-widgets.
 
-use bubbletea_rs::{
-    window_size, Cmd, KeyMsg, Model as BubbleTeaModel, Msg, Program, WindowSizeMsg,
-};
+use bubbletea_rs::{Cmd, KeyMsg, Model as BubbleTeaModel, Msg, Program, WindowSizeMsg};
 use bubbletea_widgets::list::{DefaultDelegate, DefaultItem, Model as List};
 use bubbletea_widgets::paginator::Type as PaginatorType;
 use crossterm::event::{KeyCode, KeyModifiers};
 use lipgloss_extras::lipgloss::renderer::{self, ColorProfileKind};
 use lipgloss_extras::lipgloss::Style;
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 // Document style with margin(1, 2) matching Go's docStyle.Margin(1, 2)
 // Go's Margin(1, 2) = 1 vertical, 2 horizontal = margin(top=1, right=2, bottom=1, left=2)
 fn doc_style() -> Style {
@@ -65,27 +56,17 @@ impl BubbleTeaModel for Model {
         // Create list with default delegate - simple like Go version
         let delegate = DefaultDelegate::new();
 
-        // Get initial terminal size (like Go version)
-        let (terminal_width, terminal_height) = crossterm::terminal::size().unwrap_or((80, 24));
-        let frame_width = 4; // 2 left + 2 right margin from doc_style
-        let frame_height = 2; // 1 top + 1 bottom margin from doc_style
-
-        let list_width = (terminal_width as usize).saturating_sub(frame_width);
-        let list_height = (terminal_height as usize).saturating_sub(frame_height);
-
-        let list = List::new(items, delegate, list_width, list_height)
+        // Start with no size; the program sends a WindowSizeMsg with the
+        // terminal's size before the first frame is drawn
+        let list = List::new(items, delegate, 0, 0)
             .with_title("My Fave Things")
             .with_pagination_type(PaginatorType::Dots); // Use dots pagination to match Go version
 
-        (Model { list }, Some(init_render_cmd()))
+        (Model { list }, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle initial render message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return Some(window_size());
-        }
-
         // Handle Ctrl+C like the Go version (only custom key handling)
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             if key_msg.key == KeyCode::Char('c')
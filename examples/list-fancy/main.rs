@@ -1,5 +1,5 @@
 use bubbletea_rs::{
-    window_size, Cmd, KeyMsg, Model as BubbleTeaModel, Msg, Program, WindowSizeMsg,
+    announce, batch, Cmd, KeyMsg, Model as BubbleTeaModel, Msg, Program, WindowSizeMsg,
 };
 use bubbletea_widgets::help::{KeyMap as HelpKeyMap, Model as HelpModel};
 use bubbletea_widgets::key::{
@@ -12,13 +12,6 @@ use rand::{seq::SliceRandom, thread_rng};
 use std::fmt::Display;
 use std::sync::{Arc, Mutex};
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 // Status message for the list
 struct StatusMessage(String);
 
@@ -505,15 +498,11 @@ impl HelpKeyMap for Model {
 impl BubbleTeaModel for Model {
     fn init() -> (Self, Option<Cmd>) {
         let model = Self::new();
-        (model, Some(init_render_cmd()))
+        (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle initial render message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return Some(window_size());
-        }
-
         // Handle status messages
         if let Some(status_msg) = msg.downcast_ref::<StatusMessage>() {
             self.status_message = status_msg.0.clone();
@@ -601,7 +590,19 @@ impl BubbleTeaModel for Model {
         }
 
         // Delegate to list widget for other messages
-        self.list.update(msg)
+        let previous = self.list.selected_item().map(|item| item.title.clone());
+        let cmd = self.list.update(msg);
+        // Read out the new selection when running with ACCESSIBLE set
+        if let Some(item) = self.list.selected_item() {
+            if previous.as_ref() != Some(&item.title) {
+                let announcement = announce(format!("Selected {}", item.title));
+                return Some(match cmd {
+                    Some(cmd) => batch(vec![cmd, announcement]),
+                    None => announcement,
+                });
+            }
+        }
+        cmd
     }
 
     fn view(&self) -> String {
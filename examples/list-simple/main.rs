@@ -5,13 +5,6 @@ use bubbletea_widgets::paginator::Type as PaginatorType;
 use lipgloss_extras::lipgloss::{Color, Style};
 use std::fmt::Display;
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 // Simple item type (equivalent to Go's item string)
 #[derive(Debug, Clone)]
 struct FoodItem(String);
@@ -141,16 +134,11 @@ impl Model {
 impl BubbleTeaModel for Model {
     fn init() -> (Self, Option<Cmd>) {
         let model = Self::new();
-        (model, Some(init_render_cmd()))
+        (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle initial render message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // Just trigger a render, no state change needed
-            return None;
-        }
-
         // Handle window size changes
         if let Some(_size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
             // List widget handles resizing internally
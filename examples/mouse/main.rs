@@ -19,6 +19,7 @@ impl Model for MouseModel {
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if let Some(key) = msg.downcast_ref::<KeyMsg>() {
             match (key.key, key.modifiers) {
@@ -3,8 +3,8 @@
 //! A package installer simulation demonstrating advanced bubbletea-rs patterns:
 //!
 //! ## Key Components Demonstrated:
-//! - **Custom Spinner**: Hand-built spinner component with lipgloss styling
-//! - **Animated Progress Bar**: Custom progress with gradient rendering using bubbletea-rs::gradient
+//! - **Custom Spinner**: Spinner component built on `FrameAnimator` with lipgloss styling
+//! - **Animated Progress Bar**: The built-in `progress::AnimatedBar` with its default gradient
 //! - **Dynamic List Building**: Maintaining completed items in model state (not printf)
 //! - **Complex Layout**: Width-aware text truncation and gap calculation
 //! - **Multi-Command Coordination**: Using `batch()` for concurrent commands
@@ -22,7 +22,9 @@
 //! Usage: cargo run
 
 // bubbletea-rs core imports for MVU pattern
-use bubbletea_rs::gradient::gradient_filled_segment; // Built-in gradient helper for progress bars
+use bubbletea_rs::animation::FrameAnimator; // Built-in frame animation driver for the spinner
+use bubbletea_rs::gradient::BRAILLE_FRAMES; // Shared Braille spinner frames
+use bubbletea_rs::progress::{AnimatedBar, ProgressFrameMsg}; // Built-in animated progress bar
 use bubbletea_rs::{batch, quit, tick, Cmd, KeyMsg, Model, Msg, Program, WindowSizeMsg};
 
 // crossterm for keyboard input handling
@@ -48,16 +50,6 @@ use std::time::Duration;
 #[derive(Debug)]
 pub struct InstalledPkgMsg(pub String);
 
-/// Message for spinner animation ticks
-/// Sent periodically to advance the spinner frame
-#[derive(Debug)]
-pub struct SpinnerTickMsg;
-
-/// Message for progress bar animation frames
-/// Sent at ~60fps to create smooth progress bar animations
-#[derive(Debug)]
-pub struct ProgressFrameMsg;
-
 // =============================================================================
 // CUSTOM SPINNER COMPONENT
 // =============================================================================
@@ -70,24 +62,26 @@ pub struct ProgressFrameMsg;
 /// ## bubbletea-rs Pattern: Custom Components
 /// Instead of using a pre-built spinner, this shows how to create your own
 /// reusable component with:
-/// - Internal state management (current_frame)
+/// - Frame state and tick scheduling delegated to `FrameAnimator`
 /// - Styling with lipgloss-extras
-/// - Animation timing with tick() commands
 /// - Clean separation of concerns
 #[derive(Debug)]
 pub struct Spinner {
-    current_frame: usize,
+    animator: FrameAnimator,
 }
 
 impl Spinner {
     pub fn new() -> Self {
-        Self { current_frame: 0 }
+        let frames = Self::frames().iter().map(|f| f.to_string()).collect();
+        Self {
+            animator: FrameAnimator::new(frames, Self::interval()),
+        }
     }
 
     /// Get the dot spinner frames (matching Go bubbles)
     /// These Unicode Braille patterns create a smooth spinning effect
     fn frames() -> &'static [&'static str] {
-        &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"]
+        BRAILLE_FRAMES
     }
 
     /// Get the spinner interval - 100ms for smooth animation
@@ -102,117 +96,30 @@ impl Spinner {
     /// The Style::render() method applies ANSI color codes while
     /// keeping the visual appearance separate from the data.
     pub fn view(&self) -> String {
-        let frames = Self::frames();
-        let frame = frames[self.current_frame % frames.len()];
-
         // Apply color #63 styling to match Go version exactly
         let style = Style::new().foreground(Color::from("63"));
-        style.render(frame)
+        style.render(self.animator.current())
     }
 
-    /// Advance to the next frame
-    /// Called when SpinnerTickMsg is received
-    pub fn advance_frame(&mut self) {
-        let frames = Self::frames();
-        self.current_frame = (self.current_frame + 1) % frames.len();
+    /// Start the animation and return the command for the first tick
+    pub fn start(&mut self) -> Cmd {
+        self.animator.start()
     }
 
-    /// Create spinner tick command
-    ///
-    /// ## bubbletea-rs Pattern: Async Commands with tick()
-    /// tick() creates a one-shot timer that sends a message after a delay.
-    /// This is perfect for animations - each tick advances the frame and
-    /// schedules the next tick, creating a smooth animation loop.
-    pub fn tick_cmd() -> Cmd {
-        tick(Self::interval(), |_| Box::new(SpinnerTickMsg) as Msg)
+    /// Stop the animation; pending ticks are discarded
+    pub fn stop(&mut self) {
+        self.animator.stop();
     }
-}
 
-// =============================================================================
-// CUSTOM PROGRESS BAR COMPONENT
-// =============================================================================
-
-/// Animated progress bar with gradient (matching Go bubbles)
-///
-/// ## bubbletea-rs Pattern: Smooth Animations
-/// This demonstrates smooth percentage animations using:
-/// - target_percent vs current_percent for tweening
-/// - 60fps frame updates with ProgressFrameMsg
-/// - Built-in gradient rendering with gradient_filled_segment()
-/// - Non-blocking animation that doesn't interfere with other updates
-#[derive(Debug)]
-pub struct Progress {
-    width: usize,
-    current_percent: f64, // Currently displayed percentage (animated)
-    target_percent: f64,  // Target percentage (set immediately)
-    animation_speed: f64, // How fast to animate between current and target
-}
-
-impl Progress {
-    pub fn new() -> Self {
-        Self {
-            width: 40,
-            current_percent: 0.0,
-            target_percent: 0.0,
-            animation_speed: 0.15,
-        }
-    }
-
-    /// Set target percentage for animation
-    ///
-    /// ## bubbletea-rs Pattern: Conditional Commands
-    /// This method demonstrates how to conditionally return commands based on state.
-    /// If animation is needed, it returns a tick() command to start the animation loop.
-    /// If no animation is needed, it returns None to avoid unnecessary work.
-    pub fn set_percent(&mut self, percent: f64) -> Option<Cmd> {
-        self.target_percent = percent.clamp(0.0, 1.0);
-
-        // If we need to animate, start frame messages
-        if (self.current_percent - self.target_percent).abs() > 0.001 {
-            Some(tick(Duration::from_millis(16), |_| {
-                Box::new(ProgressFrameMsg) as Msg
-            })) // ~60fps for smooth animation
-        } else {
-            None
-        }
-    }
-
-    /// Update animation frame
-    pub fn update_animation(&mut self) -> Option<Cmd> {
-        if (self.current_percent - self.target_percent).abs() > 0.001 {
-            let diff = self.target_percent - self.current_percent;
-            let step = diff * self.animation_speed;
-
-            if step.abs() < 0.001 {
-                self.current_percent = self.target_percent;
-                None // Animation complete
-            } else {
-                self.current_percent += step;
-                // Continue animation
-                Some(tick(Duration::from_millis(16), |_| {
-                    Box::new(ProgressFrameMsg) as Msg
-                }))
-            }
-        } else {
-            None // No animation needed
-        }
-    }
-
-    /// Render progress bar with gradient (without percentage, matching Go)
+    /// Advance the spinner if `msg` is one of its ticks
     ///
-    /// ## bubbletea-rs Pattern: Built-in Gradient Helper
-    /// Uses bubbletea-rs::gradient::gradient_filled_segment() to create
-    /// the same gradient colors as Charm's default (pink to yellow).
-    /// This ensures visual consistency across different examples.
-    pub fn view(&self) -> String {
-        let percent = self.current_percent.clamp(0.0, 1.0);
-        let filled_width = (self.width as f64 * percent).round() as usize;
-        let empty_width = self.width.saturating_sub(filled_width);
-
-        // Use gradient for filled portion (matching Go's WithDefaultGradient)
-        let filled_str = gradient_filled_segment(filled_width, '█');
-        let empty_str = '░'.to_string().repeat(empty_width);
-        format!("{}{}", filled_str, empty_str)
+    /// ## bubbletea-rs Pattern: Animation Loops with FrameAnimator
+    /// Each tick advances the frame and schedules the next tick, creating a
+    /// self-sustaining loop. `FrameAnimator::handle` recognizes its own tick
+    /// messages by id and returns the command for the next tick, so the
+    /// model just forwards messages to it.
+    pub fn update(&mut self, msg: &Msg) -> Option<Cmd> {
+        self.animator.handle(msg)
     }
 }
 
@@ -242,8 +149,8 @@ pub struct PackageManagerModel {
     done: bool,            // Whether all packages are complete
 
     // UI component state
-    spinner: Spinner,   // Custom spinner component
-    progress: Progress, // Custom progress bar component
+    spinner: Spinner,      // Custom spinner component
+    progress: AnimatedBar, // Built-in animated progress bar
 
     // Display state
     completed_packages: Vec<String>, // Track completed packages for display
@@ -261,7 +168,7 @@ impl PackageManagerModel {
             width: 80,
             height: 24,
             spinner: Spinner::new(),
-            progress: Progress::new(),
+            progress: AnimatedBar::new(40).with_percentage(false), // Bar only, matching Go
             done: false,
             completed_packages: Vec::new(),
         }
@@ -298,11 +205,11 @@ impl Model for PackageManagerModel {
     /// - Start the spinner animation
     /// This demonstrates how to kick off multiple async processes.
     fn init() -> (Self, Option<Cmd>) {
-        let model = Self::new();
+        let mut model = Self::new();
 
         // Start with the first package installation and spinner
         let install_cmd = Self::download_and_install(model.packages[model.index].clone());
-        let spinner_cmd = Spinner::tick_cmd();
+        let spinner_cmd = model.spinner.start();
 
         // batch() runs commands concurrently, not sequentially
         (model, Some(batch(vec![install_cmd, spinner_cmd])))
@@ -314,6 +221,7 @@ impl Model for PackageManagerModel {
     /// Since Msg is a trait object, we use downcast_ref() to check the
     /// concrete message type. This is similar to pattern matching in Go,
     /// but uses Rust's type system for safety.
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle window size changes for responsive layout
         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
@@ -351,6 +259,7 @@ impl Model for PackageManagerModel {
             if self.index >= self.packages.len() - 1 {
                 // Everything's been installed. We're done!
                 self.done = true;
+                self.spinner.stop();
                 return Some(quit());
             }
 
@@ -380,20 +289,17 @@ impl Model for PackageManagerModel {
         // ## bubbletea-rs Pattern: Animation Loops
         // For continuous animations, each tick advances the state and
         // schedules the next tick. This creates a self-sustaining loop.
-        if msg.downcast_ref::<SpinnerTickMsg>().is_some() {
-            if !self.done {
-                self.spinner.advance_frame();
-                return Some(Spinner::tick_cmd()); // Schedule next frame
-            }
+        if let Some(cmd) = self.spinner.update(&msg) {
+            return Some(cmd); // Schedule next frame
         }
 
         // Handle progress bar animation frames
         //
         // ## bubbletea-rs Pattern: Conditional Animation
-        // The progress bar only animates when needed (when current != target).
-        // This saves CPU when no animation is required.
+        // The progress bar only schedules frames while it is easing toward
+        // a new percentage. This saves CPU when no animation is required.
         if msg.downcast_ref::<ProgressFrameMsg>().is_some() {
-            return self.progress.update_animation();
+            return self.progress.handle_frame_msg(&msg);
         }
 
         None
@@ -451,7 +357,7 @@ impl Model for PackageManagerModel {
         // - String length (includes ANSI codes)
         // This calculation uses visual width for layout, string data for rendering.
 
-        let fixed_width = 2 + self.progress.width + pkg_count.len(); // spinner + prog + count
+        let fixed_width = 2 + self.progress.width() + pkg_count.len(); // spinner + prog + count
         let available_width = if self.width > fixed_width + 15 {
             // ensure minimum space
             self.width - fixed_width
@@ -474,7 +380,7 @@ impl Model for PackageManagerModel {
         // ## Critical: Use visual length, not string length for gap calculation
         // The styled string contains ANSI escape codes, so info_text.len() would be wrong
         let info_visual_len = "Installing ".len() + pkg_name.len();
-        let used_width = 2 + info_visual_len + self.progress.width + pkg_count.len();
+        let used_width = 2 + info_visual_len + self.progress.width() + pkg_count.len();
         let gap = if self.width > used_width {
             " ".repeat(self.width - used_width)
         } else {
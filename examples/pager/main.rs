@@ -20,6 +20,7 @@
 //! ### 💻 **Program Configuration**
 //! - **Alternate Screen Buffer**: Full-screen TUI mode with `.alt_screen()`
 //! - **Mouse Support**: Enabling mouse wheel scrolling with `.mouse_motion()`
+//! - **Fast Wheel Scrolling**: Coalesced wheel events scroll by `scroll_delta`
 //! - **File I/O Integration**: Loading external content at startup
 //! - **Error Handling**: Graceful handling of missing files
 //!
@@ -66,20 +67,29 @@
 
 // bubbletea-rs core imports for MVU pattern
 use bubbletea_rs::{
-    quit, window_size, KeyMsg, Model as BubbleTeaModel, MouseMotion, Msg, Program, WindowSizeMsg,
+    batch, quit, watch_path, window_size, ErrorMsg, KeyMsg, Model as BubbleTeaModel, MouseMotion,
+    MouseMsg, Msg, Program, WatchEvent, WindowSizeMsg,
 };
 
 // bubbletea-widgets for viewport component
 use bubbletea_widgets::viewport;
 
 // crossterm for keyboard input handling
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseEventKind};
 
 // lipgloss-extras for terminal styling (borders, colors, layout)
 use lipgloss_extras::lipgloss::{border, join_horizontal, width_visible, Style, CENTER};
 
 // Standard library imports
 use std::fs;
+use std::time::Duration;
+
+/// The document displayed by the pager
+const DOCUMENT_PATH: &str = "artichoke.md";
+
+/// Message sent when the document changes on disk
+#[derive(Debug)]
+struct DocumentChangedMsg(WatchEvent);
 
 // =============================================================================
 // TERMINAL LAYOUT GOTCHAS & PATTERNS
@@ -215,8 +225,8 @@ impl PagerModel {
     /// Create a new pager model with content loaded from file
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         // Load content from artichoke.md file
-        let content = fs::read_to_string("artichoke.md")
-            .map_err(|e| format!("could not load file: {}", e))?;
+        let content =
+            fs::read_to_string(DOCUMENT_PATH).map_err(|e| format!("could not load file: {}", e))?;
 
         // Split content into lines for manual scrolling
         let content_lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
@@ -234,6 +244,27 @@ impl PagerModel {
         })
     }
 
+    /// Reload the document after it changed on disk
+    ///
+    /// ## bubbletea-rs Pattern: Live Reload
+    /// The scroll position is kept where possible so editing the file in
+    /// another window doesn't throw the reader back to the top.
+    fn reload(&mut self) {
+        // A removed or half-written file keeps the last good content on screen
+        let Ok(content) = fs::read_to_string(DOCUMENT_PATH) else {
+            return;
+        };
+        self.content_lines = content.lines().map(|s| s.to_string()).collect();
+        self.viewport.set_content(&content);
+        self.content = content;
+
+        let max_offset = self
+            .content_lines
+            .len()
+            .saturating_sub(self.viewport.height);
+        self.scroll_offset = std::cmp::min(self.scroll_offset, max_offset);
+    }
+
     /// Create the header view with title and horizontal line
     ///
     /// ## bubbletea-rs Pattern: Terminal Layout with Styled Text
@@ -368,21 +399,31 @@ impl PagerModel {
 impl BubbleTeaModel for PagerModel {
     /// Initialize the model by loading content from disk
     ///
-    /// ## bubbletea-rs Pattern: File Loading in Init
-    /// Demonstrates loading external resources during initialization.
-    /// Error handling here uses Result to fail fast if content is missing.
+    /// ## bubbletea-rs Pattern: Fallible Initialization
+    /// Loading external resources can fail, so the setup lives in `try_init`.
+    /// The program calls it before touching the terminal, and an error comes
+    /// back from `run()` instead of exiting the process from inside init.
     ///
     /// ## bubbletea-rs Pattern: Window Size Request
     /// We request the window size immediately so the viewport can be properly initialized.
     /// Without this, the model would stay in "Initializing..." state forever.
+    ///
+    /// ## bubbletea-rs Pattern: Watching Files
+    /// `watch_path` keeps a watcher running for the lifetime of the program and
+    /// debounces bursts of writes (editors often save in several steps) into a
+    /// single message.
+    fn try_init() -> Result<(Self, Option<bubbletea_rs::Cmd>), bubbletea_rs::Error> {
+        let model = PagerModel::new()
+            .map_err(|e| bubbletea_rs::Error::Configuration(format!("initializing pager: {e}")))?;
+        let watch = watch_path(DOCUMENT_PATH, Duration::from_millis(100), |event| {
+            Box::new(DocumentChangedMsg(event)) as Msg
+        });
+        Ok((model, Some(batch(vec![window_size(), watch]))))
+    }
+
+    /// Not called by the program, which uses `try_init` above
     fn init() -> (Self, Option<bubbletea_rs::Cmd>) {
-        match PagerModel::new() {
-            Ok(model) => (model, Some(window_size())),
-            Err(e) => {
-                eprintln!("Error initializing pager: {}", e);
-                std::process::exit(1);
-            }
-        }
+        Self::try_init().expect("pager initialization failed")
     }
 
     /// Handle messages for navigation and window resizing
@@ -390,6 +431,7 @@ impl BubbleTeaModel for PagerModel {
     /// ## bubbletea-rs Pattern: Viewport Message Delegation
     /// The viewport component handles most navigation messages itself.
     /// We only need to intercept quit messages and window size changes.
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<bubbletea_rs::Cmd> {
         // Handle keyboard input for navigation and quitting
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
@@ -440,6 +482,43 @@ impl BubbleTeaModel for PagerModel {
             }
         }
 
+        // Scroll with the mouse wheel
+        //
+        // ## bubbletea-rs Pattern: Wheel Scroll Delta
+        // A wheel message may stand for several notches when the program
+        // coalesces a fast scroll, so move by `scroll_delta` lines instead of
+        // one line per message.
+        if let Some(mouse) = msg.downcast_ref::<MouseMsg>() {
+            let delta = usize::from(mouse.scroll_delta);
+            let max_offset = self
+                .content_lines
+                .len()
+                .saturating_sub(self.viewport.height);
+            match mouse.button {
+                MouseEventKind::ScrollUp => {
+                    self.scroll_offset = self.scroll_offset.saturating_sub(delta);
+                }
+                MouseEventKind::ScrollDown => {
+                    self.scroll_offset = std::cmp::min(self.scroll_offset + delta, max_offset);
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        // Reload the document when it changes on disk
+        if let Some(DocumentChangedMsg(event)) = msg.downcast_ref::<DocumentChangedMsg>() {
+            if !matches!(event, WatchEvent::Removed(_)) {
+                self.reload();
+            }
+            return None;
+        }
+
+        // Watcher failures are not fatal; the pager keeps showing the last content
+        if msg.downcast_ref::<ErrorMsg>().is_some() {
+            return None;
+        }
+
         // Handle window size changes for responsive layout
         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
             // Calculate viewport dimensions accounting for header and footer
@@ -503,10 +582,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // This demonstrates the typical setup for a document viewer:
     // - .alt_screen(true): Use alternate screen buffer (fullscreen)
     // - .mouse_motion(): Enable mouse wheel scrolling
+    // - .coalesce_wheel_events() / .wheel_scroll_lines(): Scroll long
+    //   documents quickly; each notch moves three lines and a fast burst of
+    //   notches arrives as one message
     // This matches the Go version's WithAltScreen() and WithMouseCellMotion()
     let program = Program::<PagerModel>::builder()
         .alt_screen(true) // Enable alternate screen buffer
         .mouse_motion(MouseMotion::Cell) // Enable mouse wheel support
+        .coalesce_wheel_events(true)
+        .wheel_scroll_lines(3)
         .build()?;
 
     // Run the program and handle any errors
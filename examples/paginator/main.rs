@@ -2,20 +2,11 @@
 //
 // A simple program demonstrating the paginator component from the bubbletea-widgets library.
 
-use bubbletea_rs::{
-    window_size, Cmd, KeyMsg, Model as BubbleTeaModel, Msg, Program, WindowSizeMsg,
-};
+use bubbletea_rs::{Cmd, KeyMsg, Model as BubbleTeaModel, Msg, Program, WindowSizeMsg};
 use bubbletea_widgets::paginator::{Model as Paginator, Type};
 use crossterm::event::{KeyCode, KeyModifiers};
 use lipgloss_extras::lipgloss::{renderer, Color, ColorProfileKind, Style};
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 fn new_model() -> Model {
     // Create 100 items matching the Go example
     let mut items = Vec::new();
@@ -56,15 +47,11 @@ pub struct Model {
 impl BubbleTeaModel for Model {
     fn init() -> (Self, Option<Cmd>) {
         let model = new_model();
-        (model, Some(init_render_cmd()))
+        (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle initial render message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return Some(window_size());
-        }
-
         // Handle window size changes
         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
             self.width = size_msg.width;
@@ -0,0 +1,77 @@
+// A picker meant for shell pipelines. The interface is drawn on stderr, so
+// stdout only ever receives the chosen item:
+//
+//     cd "$(cargo run -q -p picker-example)"
+
+use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, Program};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const CHOICES: &[&str] = &["/tmp", "/usr/local", "/var/log", "/etc"];
+
+struct PickerModel {
+    cursor: usize,
+    choice: Option<&'static str>,
+}
+
+impl Model for PickerModel {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            PickerModel {
+                cursor: 0,
+                choice: None,
+            },
+            None,
+        )
+    }
+
+    #[allow(refining_impl_trait)]
+    fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        let key_msg = msg.downcast_ref::<KeyMsg>()?;
+        match key_msg.key {
+            KeyCode::Char('c') if key_msg.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(quit());
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Some(quit()),
+            KeyCode::Enter => {
+                self.choice = Some(CHOICES[self.cursor]);
+                return Some(quit());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.cursor = (self.cursor + 1) % CHOICES.len();
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.cursor = (self.cursor + CHOICES.len() - 1) % CHOICES.len();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        let mut s = String::from("Pick a directory:\n\n");
+        for (i, choice) in CHOICES.iter().enumerate() {
+            let cursor = if self.cursor == i { ">" } else { " " };
+            s.push_str(&format!("{cursor} {choice}\n"));
+        }
+        s.push_str("\n(enter to pick, q to quit)\n");
+        s
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Frames, cursor and mode changes all go to stderr; building fails if
+    // stderr is not a terminal.
+    let program = Program::<PickerModel>::builder()
+        .output_to_stderr(true)
+        .build()?;
+
+    let final_model = program.run().await?;
+
+    match final_model.choice {
+        Some(choice) => println!("{choice}"),
+        None => std::process::exit(1),
+    }
+
+    Ok(())
+}
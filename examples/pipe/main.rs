@@ -65,6 +65,7 @@ impl Model for PipeModel {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle quit keys first
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
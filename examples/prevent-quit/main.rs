@@ -158,6 +158,7 @@ impl Model for AppModel {
         (model, cmd)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if self.quitting {
             self.update_prompt_view(msg)
@@ -1,125 +1,39 @@
+use bubbletea_rs::gradient::gradient_filled_segment;
+use bubbletea_rs::progress::{AnimatedBar, ProgressFrameMsg};
 use bubbletea_rs::{KeyMsg, Model, Msg, WindowSizeMsg};
 use crossterm::event::{KeyCode, KeyModifiers};
 
 #[path = "../main.rs"]
 mod progress_animated_main;
-use progress_animated_main::{
-    AnimatedProgressBar, ProgressAnimatedModel, ProgressFrameMsg, ProgressTickMsg,
-};
+use progress_animated_main::{ProgressAnimatedModel, ProgressTickMsg};
 
-#[test]
-fn test_animated_progress_bar_new() {
-    let progress = AnimatedProgressBar::new();
-
-    assert_eq!(progress.width, 40);
-    assert_eq!(progress.current_percent, 0.0);
-    assert_eq!(progress.target_percent, 0.0);
-    assert_eq!(progress.filled_char, '█');
-    assert_eq!(progress.empty_char, '░');
-    assert_eq!(progress.animation_speed, 0.1);
-}
-
-#[test]
-fn test_animated_progress_bar_set_percent() {
-    let mut progress = AnimatedProgressBar::new();
-
-    // Set target to 50%
-    let cmd = progress.set_percent(0.5);
-    assert_eq!(progress.target_percent, 0.5);
-    assert_eq!(progress.current_percent, 0.0); // Hasn't animated yet
-    assert!(cmd.is_some()); // Should start animation
-
-    // Set to same value - no animation needed
-    let cmd = progress.set_percent(0.0);
-    assert_eq!(progress.target_percent, 0.0);
-    assert!(cmd.is_none()); // No animation needed
-}
-
-#[test]
-fn test_animated_progress_bar_incr_percent() {
-    let mut progress = AnimatedProgressBar::new();
-
-    // Increment by 25%
-    let cmd = progress.incr_percent(0.25);
-    assert_eq!(progress.target_percent, 0.25);
-    assert!(cmd.is_some()); // Should start animation
-
-    // Increment again
-    progress.incr_percent(0.25);
-    assert_eq!(progress.target_percent, 0.5);
+/// Builds the frame message the bar would receive from its own tick
+fn frame_for(progress: &AnimatedBar) -> Msg {
+    Box::new(ProgressFrameMsg { id: progress.id() })
 }
 
-#[test]
-fn test_animated_progress_bar_update_animation() {
-    let mut progress = AnimatedProgressBar::new();
-    progress.target_percent = 1.0;
-    progress.current_percent = 0.0;
-
-    // Should animate toward target
-    let cmd = progress.update_animation();
-    assert!(progress.current_percent > 0.0);
-    assert!(progress.current_percent < 1.0);
-    assert!(cmd.is_some()); // Should continue animation
-
-    // When at target, animation should stop
-    progress.current_percent = 1.0;
-    let cmd = progress.update_animation();
-    assert!(cmd.is_none()); // Animation complete
-}
-
-#[test]
-fn test_animated_progress_bar_percent() {
-    let mut progress = AnimatedProgressBar::new();
-    progress.current_percent = 0.75;
-
-    assert_eq!(progress.percent(), 0.75);
-}
-
-#[test]
-fn test_animated_progress_bar_view() {
-    let mut progress = AnimatedProgressBar::new();
-    progress.current_percent = 0.5;
-
-    let view = progress.view();
-    assert!(view.contains("█")); // Filled portion
-    assert!(view.contains("░")); // Empty portion
-    assert!(view.contains("50.0%"));
-
-    // Test 0%
-    progress.current_percent = 0.0;
-    let view = progress.view();
-    assert!(view.contains("0.0%"));
-    let empty_count = view.chars().filter(|&c| c == '░').count();
-    assert_eq!(empty_count, 40);
-
-    // Test 100%
-    progress.current_percent = 1.0;
-    let view = progress.view();
-    assert!(view.contains("100.0%"));
-    let filled_count = view.chars().filter(|&c| c == '█').count();
-    assert_eq!(filled_count, 40);
+/// Runs animation frames until the bar reaches its target
+fn settle(progress: &mut AnimatedBar) {
+    let mut frames = 0;
+    while progress.handle_frame_msg(&frame_for(progress)).is_some() {
+        frames += 1;
+        assert!(frames < 100, "animation should finish");
+    }
 }
 
-#[test]
-fn test_animated_progress_bar_clamp() {
-    let mut progress = AnimatedProgressBar::new();
-
-    // Test negative value gets clamped
-    progress.set_percent(-0.5);
-    assert_eq!(progress.target_percent, 0.0);
-
-    // Test value > 1.0 gets clamped
-    progress.set_percent(1.5);
-    assert_eq!(progress.target_percent, 1.0);
+/// Moves the bar straight to `percent`
+fn show_percent(progress: &mut AnimatedBar, percent: f64) {
+    progress.set_percent(percent);
+    settle(progress);
 }
 
 #[test]
 fn test_progress_animated_model_new() {
     let model = ProgressAnimatedModel::new();
 
-    assert_eq!(model.progress.current_percent, 0.0);
-    assert_eq!(model.progress.target_percent, 0.0);
-    assert_eq!(model.progress.width, 40);
+    assert_eq!(model.progress.percent(), 0.0);
+    assert_eq!(model.progress.target_percent(), 0.0);
+    assert_eq!(model.progress.width(), 40);
 }
 
 #[test]
@@ -138,27 +52,27 @@ fn test_update_window_size() {
     let mut model = ProgressAnimatedModel::new();
 
     model.update_window_size(100, 30);
-    assert_eq!(model.progress.width, 80); // Max width cap
+    assert_eq!(model.progress.width(), 80); // Max width cap
 
     // Test smaller window
     model.update_window_size(50, 20);
-    assert_eq!(model.progress.width, 42); // 50 - 8 (padding)
+    assert_eq!(model.progress.width(), 42); // 50 - 8 (padding)
 
     // Test very small window
     model.update_window_size(10, 5);
-    assert_eq!(model.progress.width, 2); // 10 - 8 (padding)
+    assert_eq!(model.progress.width(), 2); // 10 - 8 (padding)
 }
 
 #[test]
 fn test_progress_tick_message() {
     let mut model = ProgressAnimatedModel::new();
-    let initial_target = model.progress.target_percent;
+    let initial_target = model.progress.target_percent();
 
     let tick_msg = Box::new(ProgressTickMsg) as Msg;
     let cmd = model.update(tick_msg);
 
     // Target progress should advance by 25%
-    assert_eq!(model.progress.target_percent, initial_target + 0.25);
+    assert_eq!(model.progress.target_percent(), initial_target + 0.25);
 
     // Should return batched commands (tick + animation)
     assert!(cmd.is_some());
@@ -167,8 +81,7 @@ fn test_progress_tick_message() {
 #[test]
 fn test_progress_tick_completion() {
     let mut model = ProgressAnimatedModel::new();
-    model.progress.current_percent = 1.0;
-    model.progress.target_percent = 1.0;
+    show_percent(&mut model.progress, 1.0);
 
     let tick_msg = Box::new(ProgressTickMsg) as Msg;
     let cmd = model.update(tick_msg);
@@ -180,18 +93,34 @@ fn test_progress_tick_completion() {
 #[test]
 fn test_progress_frame_message() {
     let mut model = ProgressAnimatedModel::new();
-    model.progress.target_percent = 0.5;
-    model.progress.current_percent = 0.0;
+    model.progress.set_percent(0.5);
 
-    let frame_msg = Box::new(ProgressFrameMsg) as Msg;
+    let frame_msg = frame_for(&model.progress);
     let cmd = model.update(frame_msg);
 
     // Should animate toward target
-    assert!(model.progress.current_percent > 0.0);
-    assert!(model.progress.current_percent < 0.5);
+    assert!(model.progress.percent() > 0.0);
+    assert!(model.progress.percent() < 0.5);
     assert!(cmd.is_some()); // Should continue animation
 }
 
+#[test]
+fn test_progress_frame_message_quits_at_completion() {
+    let mut model = ProgressAnimatedModel::new();
+    model.progress.set_percent(1.0);
+
+    let mut quit = false;
+    for _ in 0..100 {
+        let frame_msg = frame_for(&model.progress);
+        let cmd = model.update(frame_msg);
+        if model.progress.percent() >= 1.0 {
+            quit = cmd.is_some();
+            break;
+        }
+    }
+    assert!(quit, "reaching 100% should quit");
+}
+
 #[test]
 fn test_window_size_message() {
     let mut model = ProgressAnimatedModel::new();
@@ -203,7 +132,7 @@ fn test_window_size_message() {
 
     let cmd = model.update(size_msg);
 
-    assert_eq!(model.progress.width, 80); // Max width
+    assert_eq!(model.progress.width(), 80); // Max width
     assert!(cmd.is_none());
 }
 
@@ -277,7 +206,7 @@ fn test_view_initial_state() {
 #[test]
 fn test_view_in_progress() {
     let mut model = ProgressAnimatedModel::new();
-    model.progress.current_percent = 0.5;
+    show_percent(&mut model.progress, 0.5);
 
     let view = model.view();
 
@@ -290,7 +219,7 @@ fn test_view_in_progress() {
 #[test]
 fn test_view_completed() {
     let mut model = ProgressAnimatedModel::new();
-    model.progress.current_percent = 1.0;
+    show_percent(&mut model.progress, 1.0);
 
     let view = model.view();
 
@@ -302,90 +231,74 @@ fn test_view_completed() {
     assert_eq!(filled_count, 40);
 }
 
+#[test]
+fn test_view_snapshot() {
+    let mut model = ProgressAnimatedModel::new();
+    show_percent(&mut model.progress, 0.25);
+
+    let bar = format!("{}{}", gradient_filled_segment(10, '█'), "░".repeat(30));
+    assert_eq!(
+        model.view(),
+        format!("\n  {}  25.0%\n\n  Press any key to quit", bar)
+    );
+}
+
 #[test]
 fn test_animation_progression() {
-    let mut progress = AnimatedProgressBar::new();
+    let mut model = ProgressAnimatedModel::new();
 
     // Set target to 100%
-    progress.set_percent(1.0);
-    assert_eq!(progress.target_percent, 1.0);
-    assert_eq!(progress.current_percent, 0.0);
+    model.progress.set_percent(1.0);
+    assert_eq!(model.progress.target_percent(), 1.0);
+    assert_eq!(model.progress.percent(), 0.0);
 
-    // Animate several frames
+    // Animate until the bar stops scheduling frames
     let mut animation_steps = 0;
-    while (progress.current_percent - progress.target_percent).abs() > 0.001
-        && animation_steps < 100
-    {
-        let prev_percent = progress.current_percent;
-        progress.update_animation();
+    loop {
+        let prev_percent = model.progress.percent();
+        let cmd = model.progress.handle_frame_msg(&frame_for(&model.progress));
 
         // Should make progress each frame
-        assert!(progress.current_percent > prev_percent);
-        assert!(progress.current_percent <= 1.0);
+        assert!(model.progress.percent() > prev_percent);
+        assert!(model.progress.percent() <= 1.0);
 
         animation_steps += 1;
+        if cmd.is_none() {
+            break;
+        }
+        assert!(animation_steps < 100);
     }
 
-    // Should eventually reach target
-    assert!((progress.current_percent - 1.0).abs() < 0.001);
-    assert!(animation_steps > 0); // Should have taken some steps
-}
-
-#[test]
-fn test_different_animation_speeds() {
-    let mut progress = AnimatedProgressBar::new();
-    progress.animation_speed = 0.2; // Faster animation
-    progress.set_percent(1.0);
-
-    let initial_percent = progress.current_percent;
-    progress.update_animation();
-    let fast_step = progress.current_percent - initial_percent;
-
-    // Reset and try slower animation
-    let mut progress = AnimatedProgressBar::new();
-    progress.animation_speed = 0.05; // Slower animation
-    progress.set_percent(1.0);
-
-    let initial_percent = progress.current_percent;
-    progress.update_animation();
-    let slow_step = progress.current_percent - initial_percent;
-
-    // Faster should take bigger steps
-    assert!(fast_step > slow_step);
+    // Should land exactly on the target
+    assert_eq!(model.progress.percent(), 1.0);
+    assert!(animation_steps > 1); // Should have taken several steps
 }
 
 #[test]
 fn test_animation_smoothness() {
-    let mut progress = AnimatedProgressBar::new();
-    progress.set_percent(0.5);
+    let mut model = ProgressAnimatedModel::new();
+    model.progress.set_percent(0.5);
 
-    let mut prev_percent = progress.current_percent;
+    let mut prev_percent = model.progress.percent();
     let mut step_sizes = Vec::new();
 
     // Collect several animation steps
     for _ in 0..10 {
-        progress.update_animation();
-        let step = progress.current_percent - prev_percent;
+        model.progress.handle_frame_msg(&frame_for(&model.progress));
+        let step = model.progress.percent() - prev_percent;
         if step > 0.0 {
             step_sizes.push(step);
         }
-        prev_percent = progress.current_percent;
+        prev_percent = model.progress.percent();
 
-        if (progress.current_percent - progress.target_percent).abs() < 0.001 {
+        if model.progress.percent() == model.progress.target_percent() {
             break;
         }
     }
 
-    // Steps should gradually decrease (exponential decay animation)
-    if step_sizes.len() > 1 {
-        // First step should be larger than last step (exponential decay)
-        assert!(step_sizes[0] > step_sizes[step_sizes.len() - 1]);
-
-        // All steps should be positive
-        for step in &step_sizes {
-            assert!(*step > 0.0);
-        }
-    }
+    // Steps should gradually decrease (ease-out animation)
+    assert!(step_sizes.len() > 1);
+    assert!(step_sizes[0] > step_sizes[step_sizes.len() - 1]);
 }
 
 #[test]
@@ -394,14 +307,7 @@ fn test_progress_animated_model_debug() {
     let debug_str = format!("{:?}", model);
 
     assert!(debug_str.contains("ProgressAnimatedModel"));
-}
-
-#[test]
-fn test_animated_progress_bar_debug() {
-    let progress = AnimatedProgressBar::new();
-    let debug_str = format!("{:?}", progress);
-
-    assert!(debug_str.contains("AnimatedProgressBar"));
+    assert!(debug_str.contains("AnimatedBar"));
 }
 
 #[test]
@@ -412,26 +318,18 @@ fn test_progress_tick_msg_debug() {
     assert!(debug_str.contains("ProgressTickMsg"));
 }
 
-#[test]
-fn test_progress_frame_msg_debug() {
-    let msg = ProgressFrameMsg;
-    let debug_str = format!("{:?}", msg);
-
-    assert!(debug_str.contains("ProgressFrameMsg"));
-}
-
 #[test]
 fn test_edge_case_progress_values() {
-    let mut progress = AnimatedProgressBar::new();
+    let mut model = ProgressAnimatedModel::new();
 
     // Test very small increment
-    progress.set_percent(0.001);
-    let view = progress.view();
+    model.progress.set_percent(0.001);
+    let view = model.progress.view();
     assert!(view.contains("0.0%")); // Should round to 0.0%
 
     // Test very close to 1.0
-    progress.set_percent(0.999);
-    assert!((progress.target_percent - 0.999).abs() < 0.001);
+    model.progress.set_percent(0.999);
+    assert!((model.progress.target_percent() - 0.999).abs() < 0.001);
 }
 
 #[test]
@@ -439,20 +337,16 @@ fn test_multiple_increments() {
     let mut model = ProgressAnimatedModel::new();
 
     // Simulate the actual sequence: 0% -> 25% -> 50% -> 75% -> 100%
-    let increments = [0.25, 0.25, 0.25, 0.25];
-
-    for (i, _increment) in increments.iter().enumerate() {
+    for i in 0..4 {
         let tick_msg = Box::new(ProgressTickMsg) as Msg;
         let cmd = model.update(tick_msg);
 
         let expected_target = (i + 1) as f64 * 0.25;
-        assert_eq!(model.progress.target_percent, expected_target);
-
-        if expected_target < 1.0 {
-            assert!(cmd.is_some()); // Should continue
-        }
+        assert_eq!(model.progress.target_percent(), expected_target);
+        assert!(cmd.is_some()); // Should continue
+        settle(&mut model.progress);
     }
 
     // Final state should be 100% target
-    assert_eq!(model.progress.target_percent, 1.0);
+    assert_eq!(model.progress.target_percent(), 1.0);
 }
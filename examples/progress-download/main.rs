@@ -19,7 +19,7 @@
 //!
 //! Usage: cargo run -- --url https://example.com/file.zip
 
-use bubbletea_rs::gradient::gradient_filled_segment;
+use bubbletea_rs::progress::{AnimatedBar, ProgressFrameMsg};
 use bubbletea_rs::{batch, quit, sequence, tick, Cmd, KeyMsg, Model, Msg, Program, WindowSizeMsg};
 use clap::Parser;
 use futures_util::StreamExt;
@@ -44,10 +44,6 @@ struct Args {
 #[derive(Debug, Clone)]
 pub struct ProgressMsg(pub f64);
 
-/// Message for progress bar animation frames
-#[derive(Debug)]
-pub struct ProgressFrameMsg;
-
 /// Message indicating download error
 #[derive(Debug)]
 pub struct ProgressErrMsg {
@@ -113,83 +109,8 @@ impl ProgressWriter {
     }
 }
 
-/// Animated progress bar with gradient (matching progress-animated example)
-#[derive(Debug)]
-pub struct AnimatedProgressBar {
-    pub width: usize,
-    pub current_percent: f64,
-    pub target_percent: f64,
-    pub filled_char: char,
-    pub empty_char: char,
-    pub animation_speed: f64,
-}
-
-impl AnimatedProgressBar {
-    pub fn new() -> Self {
-        Self {
-            width: 40,
-            current_percent: 0.0,
-            target_percent: 0.0,
-            filled_char: '█',
-            empty_char: '░',
-            animation_speed: 0.15,
-        }
-    }
-
-    /// Set target percentage for animation
-    pub fn set_percent(&mut self, percent: f64) -> Option<Cmd> {
-        self.target_percent = percent.clamp(0.0, 1.0);
-
-        // If we need to animate, start frame messages
-        if (self.current_percent - self.target_percent).abs() > 0.001 {
-            Some(tick(Duration::from_millis(16), |_| {
-                Box::new(ProgressFrameMsg) as Msg
-            })) // ~60fps
-        } else {
-            None
-        }
-    }
-
-    /// Update animation frame
-    pub fn update_animation(&mut self) -> Option<Cmd> {
-        if (self.current_percent - self.target_percent).abs() > 0.001 {
-            let diff = self.target_percent - self.current_percent;
-            let step = diff * self.animation_speed;
-
-            if step.abs() < 0.001 {
-                self.current_percent = self.target_percent;
-                None // Animation complete
-            } else {
-                self.current_percent += step;
-                // Continue animation
-                Some(tick(Duration::from_millis(16), |_| {
-                    Box::new(ProgressFrameMsg) as Msg
-                }))
-            }
-        } else {
-            None // No animation needed
-        }
-    }
-
-    /// Get current animated percentage
-    pub fn percent(&self) -> f64 {
-        self.current_percent
-    }
-
-    /// Render animated progress bar with gradient
-    pub fn view(&self) -> String {
-        let percent = self.current_percent.clamp(0.0, 1.0);
-        let filled_width = (self.width as f64 * percent).round() as usize;
-        let empty_width = self.width.saturating_sub(filled_width);
-
-        // Use gradient for filled portion
-        let filled_str = gradient_filled_segment(filled_width, self.filled_char);
-        let empty_str = self.empty_char.to_string().repeat(empty_width);
-        let bar = format!("{}{}", filled_str, empty_str);
-
-        format!("{} {:5.1}%", bar, percent * 100.0)
-    }
-}
+/// Width of the progress bar before the first window size arrives
+const DEFAULT_WIDTH: usize = 40;
 
 /// Static channel for communicating with the model
 static PROGRESS_CHANNEL: std::sync::OnceLock<mpsc::UnboundedSender<Msg>> =
@@ -198,7 +119,7 @@ static PROGRESS_CHANNEL: std::sync::OnceLock<mpsc::UnboundedSender<Msg>> =
 /// The application state
 #[derive(Debug)]
 pub struct ProgressDownloadModel {
-    pub progress: AnimatedProgressBar,
+    pub progress: AnimatedBar,
     pub error: Option<String>,
     pub progress_receiver: mpsc::UnboundedReceiver<Msg>,
 }
@@ -209,7 +130,7 @@ impl ProgressDownloadModel {
         let _ = PROGRESS_CHANNEL.set(sender);
 
         Self {
-            progress: AnimatedProgressBar::new(),
+            progress: AnimatedBar::new(DEFAULT_WIDTH),
             error: None,
             progress_receiver: receiver,
         }
@@ -221,7 +142,7 @@ impl ProgressDownloadModel {
         const MAX_WIDTH: usize = 80;
 
         let available_width = width.saturating_sub(PADDING * 2).saturating_sub(4) as usize;
-        self.progress.width = available_width.min(MAX_WIDTH);
+        self.progress.set_width(available_width.min(MAX_WIDTH));
     }
 
     /// Create final pause command before quitting (matching Go's 750ms)
@@ -242,6 +163,7 @@ impl Model for ProgressDownloadModel {
         (model, Some(channel_cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle channel message processing
         if msg.downcast_ref::<ProcessChannelMsg>().is_some() {
@@ -295,7 +217,7 @@ impl Model for ProgressDownloadModel {
 
         // Handle animation frame messages
         if msg.downcast_ref::<ProgressFrameMsg>().is_some() {
-            return self.progress.update_animation();
+            return self.progress.handle_frame_msg(&msg);
         }
 
         // Handle download errors
@@ -96,6 +96,7 @@ impl Model for ProgressStaticModel {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle progress tick messages
         if msg.downcast_ref::<ProgressTickMsg>().is_some() {
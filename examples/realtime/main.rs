@@ -93,6 +93,7 @@ impl Model for RealtimeModel {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle any key press - quit immediately (matching Go behavior)
         if msg.downcast_ref::<KeyMsg>().is_some() {
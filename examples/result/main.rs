@@ -6,14 +6,6 @@ use bubbletea_widgets::key::{matches_binding, new_binding, with_help, with_keys_
 
 const CHOICES: &[&str] = &["Taro", "Coffee", "Lychee"];
 
-// Synthetic message used to trigger the initial render immediately after startup.
-#[derive(Debug, Clone)]
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 struct AppModel {
     cursor: usize,
     choice: String,
@@ -55,17 +47,12 @@ impl Model for AppModel {
                 choice: String::new(),
                 keymap: KeyMap::new(),
             },
-            Some(init_render_cmd()),
+            None,
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle the initial render trigger message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // No-op: receiving this message merely triggers the initial render
-            return None;
-        }
-
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             if matches_binding(key_msg, &self.keymap.quit) {
                 return Some(quit());
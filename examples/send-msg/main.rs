@@ -48,14 +48,6 @@ impl ResultMsg {
 #[derive(Debug)]
 struct SpinnerTickMsg;
 
-/// Synthetic message used to trigger the initial render immediately after startup
-#[derive(Debug, Clone)]
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 /// The application model
 #[derive(Debug)]
 struct SendMsgModel {
@@ -105,22 +97,16 @@ impl SendMsgModel {
 impl Model for SendMsgModel {
     fn init() -> (Self, Option<Cmd>) {
         let model = SendMsgModel::new();
-        // Start both initial render and spinner animation
-        let init_cmd = init_render_cmd();
-        (model, Some(init_cmd))
+        // Start spinner animation and food simulation
+        let spinner_cmd = tick(Duration::from_millis(100), |_| {
+            Box::new(SpinnerTickMsg) as Msg
+        });
+        let food_cmd = simulate_food_eating();
+        (model, Some(batch(vec![spinner_cmd, food_cmd])))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle the initial render trigger message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // Start spinner animation and food simulation
-            let spinner_cmd = tick(Duration::from_millis(100), |_| {
-                Box::new(SpinnerTickMsg) as Msg
-            });
-            let food_cmd = simulate_food_eating();
-            return Some(batch(vec![spinner_cmd, food_cmd]));
-        }
-
         // Handle spinner tick messages
         if msg.downcast_ref::<SpinnerTickMsg>().is_some() {
             if !self.quitting {
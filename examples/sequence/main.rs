@@ -35,6 +35,7 @@ impl Model for SequenceModel {
         (model, Some(sequence_cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle keyboard input - any key quits (matching Go version)
         if let Some(_key_msg) = msg.downcast_ref::<KeyMsg>() {
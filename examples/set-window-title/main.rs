@@ -34,6 +34,7 @@ impl Model for SetWindowTitleModel {
         (model, Some(set_title_cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle title update confirmation
         if msg.downcast_ref::<TitleUpdateMsg>().is_some() {
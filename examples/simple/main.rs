@@ -28,6 +28,7 @@ impl Model for SimpleModel {
     // Update is called when messages are received. The idea is that you inspect the
     // message and send back an updated model accordingly. You can also return
     // a command, which is a function that performs I/O and returns a message.
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             match key_msg.key {
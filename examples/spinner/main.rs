@@ -78,6 +78,7 @@ impl Model for SpinnerModel {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle spinner tick messages
         if msg.downcast_ref::<SpinnerTickMsg>().is_some() {
@@ -18,13 +18,12 @@
 //! - Ctrl+W: Remove current editor (minimum 1)
 //! - Esc/Ctrl+C: Quit
 
-use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, Program, WindowSizeMsg};
+use bubbletea_rs::{quit, Cmd, IntoCmd, KeyMsg, Model, Msg, Program, WindowSizeMsg};
 use bubbletea_widgets::help::{KeyMap as HelpKeyMap, Model as HelpModel};
 use bubbletea_widgets::key::{
     matches_binding, new_binding, with_help, with_keys_str, Binding, KeyMap,
 };
 use crossterm::event::KeyCode;
-use crossterm::terminal;
 use lipgloss_extras::lipgloss::{
     hidden_border, join_horizontal, rounded_border, Color, Style, TOP,
 };
@@ -35,13 +34,6 @@ const MAX_INPUTS: usize = 6;
 const MIN_INPUTS: usize = 1;
 const HELP_HEIGHT: i32 = 5; // Space for help text at bottom
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 // ================================================================================================
 // STYLING FUNCTIONS - Understanding Terminal Colors and ANSI Escape Codes
 // ================================================================================================
@@ -720,22 +712,12 @@ impl HelpKeyMap for SplitEditorsModel {
 
 impl Model for SplitEditorsModel {
     fn init() -> (Self, Option<Cmd>) {
-        let mut model = Self::new();
-
-        // Set initial terminal size if available
-        if let Ok((w, h)) = terminal::size() {
-            model.width = w as i32;
-            model.height = h as i32;
-            model.help.width = w as usize;
-            model.size_inputs();
-        }
-
-        // Trigger initial render
-        (model, Some(init_render_cmd()))
+        // The editors are sized from the WindowSizeMsg sent before the first frame
+        (Self::new(), None)
     }
 
-    fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        let cmds = Vec::new();
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let cmds: Vec<Cmd> = Vec::new();
 
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             if matches_binding(key_msg, &self.keymap.quit) {
@@ -743,7 +725,7 @@ impl Model for SplitEditorsModel {
                 for input in &mut self.inputs {
                     input.blur();
                 }
-                return Some(quit());
+                return vec![quit()];
             } else if matches_binding(key_msg, &self.keymap.next) {
                 // Move to next textarea
                 self.inputs[self.focus].blur();
@@ -782,18 +764,12 @@ impl Model for SplitEditorsModel {
             self.height = size_msg.height as i32;
             self.help.width = size_msg.width as usize;
             self.size_inputs();
-        } else if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // No-op: receiving this message merely triggers the initial render
         }
 
         self.update_keybindings();
 
-        // Return batch command if we have any commands
-        if cmds.is_empty() {
-            None
-        } else {
-            Some(bubbletea_rs::batch(cmds))
-        }
+        // An empty Vec means no command; several commands are batched
+        cmds
     }
 
     fn view(&self) -> String {
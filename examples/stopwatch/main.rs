@@ -128,6 +128,7 @@ impl BubbleTeaModel for Model {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle key messages first (matching Go's switch order)
         if let Some(key) = msg.downcast_ref::<KeyMsg>() {
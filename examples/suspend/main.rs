@@ -7,13 +7,6 @@ struct SuspendModel {
     suspending: bool,
 }
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 impl Model for SuspendModel {
     fn init() -> (Self, Option<Cmd>) {
         (
@@ -21,15 +14,12 @@ impl Model for SuspendModel {
                 quitting: false,
                 suspending: false,
             },
-            Some(init_render_cmd()),
+            None,
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return None;
-        }
-
         if msg.downcast_ref::<ResumeMsg>().is_some() {
             self.suspending = false;
             return None;
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
-use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, Program, WindowSizeMsg};
-use crossterm::event::{KeyCode, KeyModifiers};
+use bubbletea_rs::{
+    quit, Cmd, Error, KeyMsg, Model, MouseMotion, MouseMsg, Msg, Program, WindowSizeMsg,
+};
+use crossterm::event::{KeyCode, KeyModifiers, MouseEventKind};
 use lipgloss_extras::lipgloss::{thick_border, Color, Style};
 use lipgloss_extras::table::{Table, HEADER_ROW};
 
@@ -14,6 +16,43 @@ struct AppModel {
     width: i32,
     height: i32,
     scroll_offset: usize,
+    /// Number of data columns scrolled off to the left. The `#` column stays pinned.
+    col_offset: usize,
+}
+
+impl AppModel {
+    fn max_visible_rows(&self) -> usize {
+        let available_height = (self.height - 4).max(3) as usize;
+        available_height.saturating_sub(1)
+    }
+
+    fn scroll_up(&mut self) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(1);
+    }
+
+    fn scroll_down(&mut self) {
+        if self.scroll_offset + self.max_visible_rows() < self.rows.len() {
+            self.scroll_offset += 1;
+        }
+    }
+
+    fn scroll_left(&mut self) {
+        self.col_offset = self.col_offset.saturating_sub(1);
+    }
+
+    fn scroll_right(&mut self) {
+        // Always keep at least one data column next to the pinned `#` column
+        if self.col_offset + 2 < self.headers.len() {
+            self.col_offset += 1;
+        }
+    }
+
+    /// Indices of the columns currently shown, in display order.
+    fn visible_columns(&self) -> Vec<usize> {
+        std::iter::once(0)
+            .chain(1 + self.col_offset..self.headers.len())
+            .collect()
+    }
 }
 
 impl Model for AppModel {
@@ -284,11 +323,13 @@ impl Model for AppModel {
             width: 80,  // Default width
             height: 24, // Default height
             scroll_offset: 0,
+            col_offset: 0,
         };
 
         (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             match key_msg.key {
@@ -296,30 +337,31 @@ impl Model for AppModel {
                 KeyCode::Char('c') if key_msg.modifiers.contains(KeyModifiers::CONTROL) => {
                     return Some(quit())
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.scroll_offset > 0 {
-                        self.scroll_offset -= 1;
-                    }
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    let available_height = (self.height - 4).max(3) as usize;
-                    let max_visible_rows = available_height.saturating_sub(1);
-                    if self.scroll_offset + max_visible_rows < self.rows.len() {
-                        self.scroll_offset += 1;
-                    }
-                }
+                KeyCode::Up | KeyCode::Char('k') => self.scroll_up(),
+                KeyCode::Down | KeyCode::Char('j') => self.scroll_down(),
+                KeyCode::Left | KeyCode::Char('h') => self.scroll_left(),
+                KeyCode::Right | KeyCode::Char('l') => self.scroll_right(),
                 KeyCode::Home => {
                     self.scroll_offset = 0;
                 }
                 KeyCode::End => {
-                    let available_height = (self.height - 4).max(3) as usize;
-                    let max_visible_rows = available_height.saturating_sub(1);
-                    self.scroll_offset = self.rows.len().saturating_sub(max_visible_rows);
+                    self.scroll_offset = self.rows.len().saturating_sub(self.max_visible_rows());
                 }
                 _ => {}
             }
         }
 
+        // Mouse wheel scrolls rows; a horizontal wheel or trackpad swipe scrolls columns
+        if let Some(mouse_msg) = msg.downcast_ref::<MouseMsg>() {
+            match mouse_msg.button {
+                MouseEventKind::ScrollUp => self.scroll_up(),
+                MouseEventKind::ScrollDown => self.scroll_down(),
+                MouseEventKind::ScrollLeft => self.scroll_left(),
+                MouseEventKind::ScrollRight => self.scroll_right(),
+                _ => {}
+            }
+        }
+
         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
             self.width = size_msg.width as i32;
             self.height = size_msg.height as i32;
@@ -338,8 +380,8 @@ impl Model for AppModel {
 
         // Calculate how many rows can fit in the terminal
         // Account for header, borders, and some padding
-        let available_height = (self.height - 4).max(3) as usize; // Reserve space for borders
-        let max_visible_rows = available_height.saturating_sub(1); // Reserve space for header
+        let max_visible_rows = self.max_visible_rows();
+        let columns = self.visible_columns();
 
         // Determine which rows to show based on scroll offset
         let visible_rows: Vec<Vec<String>> = if self.rows.len() <= max_visible_rows {
@@ -352,13 +394,18 @@ impl Model for AppModel {
             self.rows[start_idx..end_idx].to_vec()
         };
 
-        // Build table with styling
+        // Build table with styling, keeping only the horizontally visible columns
         let mut table = Table::new()
-            .headers(self.headers.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+            .headers(
+                columns
+                    .iter()
+                    .map(|&c| self.headers[c].as_str())
+                    .collect::<Vec<_>>(),
+            )
             .rows(
                 visible_rows
                     .iter()
-                    .map(|row| row.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+                    .map(|row| columns.iter().map(|&c| row[c].as_str()).collect::<Vec<_>>())
                     .collect::<Vec<_>>(),
             )
             .border(thick_border())
@@ -367,8 +414,12 @@ impl Model for AppModel {
                 let rows = visible_rows.clone();
                 let type_colors = self.type_colors.clone();
                 let dim_type_colors = self.dim_type_colors.clone();
+                let columns = columns.clone();
+
+                Box::new(move |row: i32, col: usize| {
+                    // Map the display column back to the data column
+                    let col = columns.get(col).copied().unwrap_or(col);
 
-                Box::new(move |row, col| {
                     if row == HEADER_ROW {
                         return header_style.clone();
                     }
@@ -387,7 +438,7 @@ impl Model for AppModel {
 
                     // Handle type columns (TYPE 1 and TYPE 2)
                     if col == 2 || col == 3 {
-                        let col_index = col as usize;
+                        let col_index = col;
                         if col_index < rows[row_index].len() {
                             let type_name = &rows[row_index][col_index];
                             if !type_name.is_empty() {
@@ -417,11 +468,11 @@ impl Model for AppModel {
 
         let help_text = if total_rows > max_visible_rows {
             format!(
-                "Showing rows {}-{} of {} | ↑/k: up, ↓/j: down, Home: top, End: bottom, q: quit",
+                "Showing rows {}-{} of {} | ↑/k: up, ↓/j: down, ←/h →/l: columns, Home: top, End: bottom, q: quit",
                 showing_start, showing_end, total_rows
             )
         } else {
-            "All rows visible | q: quit".to_string()
+            "All rows visible | ←/h →/l: columns, q: quit".to_string()
         };
 
         format!("{}\n{}", table_output, help_text)
@@ -433,20 +484,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create and run the program
     let program = Program::<AppModel>::builder()
         .alt_screen(true) // Use alternate screen for cleaner display
+        .mouse_motion(MouseMotion::Cell) // Enable mouse wheel and trackpad scrolling
         .signal_handler(true) // Enable Ctrl+C handling
+        .on_error(|err| {
+            if !matches!(err, Error::Interrupted | Error::ProgramKilled(_)) {
+                eprintln!("Error: {}", err);
+            }
+        })
         .build()?;
 
     // Run the program and handle errors
     if let Err(err) = program.run().await {
         match err {
-            bubbletea_rs::Error::Interrupted => {
+            Error::Interrupted => {
                 std::process::exit(130);
             }
-            bubbletea_rs::Error::ProgramKilled => {
-                std::process::exit(1);
+            Error::ProgramKilled(code) => {
+                std::process::exit(code);
             }
             _ => {
-                eprintln!("Error: {}", err);
                 std::process::exit(1);
             }
         }
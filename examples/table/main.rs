@@ -1,16 +1,8 @@
-use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, Program, WindowSizeMsg};
+use bubbletea_rs::{announce, quit, Cmd, Error, KeyMsg, Model, Msg, Program, WindowSizeMsg};
 use crossterm::event::{KeyCode, KeyModifiers};
 use lipgloss_extras::lipgloss::{normal_border, Color, Style};
 use lipgloss_extras::table::{Table, HEADER_ROW};
 
-// Synthetic message used to trigger the initial render immediately after startup.
-#[derive(Debug)]
-struct InitRenderMsg;
-
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 #[derive(Debug)]
 struct AppModel {
     headers: Vec<String>,
@@ -643,20 +635,20 @@ impl Model for AppModel {
             focused: true,
             message: None,
         };
-        (model, Some(init_render_cmd()))
+        (model, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
-        // Handle initial render message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            return None; // Just trigger a render, no command needed
-        }
-
-        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+        // Held arrow keys arrive as one `KeyRepeatMsg` once they outpace
+        // rendering; `presses` says how many rows to move
+        if let Some((key_msg, presses)) = KeyMsg::presses(&msg) {
+            let presses = usize::from(presses);
             // If a message is showing, any key should quit
             if self.message.is_some() {
                 return Some(quit());
             }
+            let previous = self.cursor;
 
             match key_msg.key {
                 KeyCode::Char('q') => return Some(quit()),
@@ -676,13 +668,14 @@ impl Model for AppModel {
                     }
                 }
                 KeyCode::Up | KeyCode::Char('k') => {
-                    if self.focused && self.cursor > 0 {
-                        self.cursor -= 1;
+                    if self.focused {
+                        self.cursor = self.cursor.saturating_sub(presses);
                     }
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
-                    if self.focused && self.cursor < self.rows.len().saturating_sub(1) {
-                        self.cursor += 1;
+                    if self.focused {
+                        let last = self.rows.len().saturating_sub(1);
+                        self.cursor = (self.cursor + presses).min(last);
                     }
                 }
                 KeyCode::Home => {
@@ -697,6 +690,16 @@ impl Model for AppModel {
                 }
                 _ => {}
             }
+            // Read out the new row when running with ACCESSIBLE set
+            if self.cursor != previous {
+                let row = &self.rows[self.cursor];
+                return Some(announce(format!(
+                    "Selected row {}: {}, {}",
+                    self.cursor + 1,
+                    row[1],
+                    row[2]
+                )));
+            }
         }
 
         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
@@ -784,18 +787,25 @@ impl Model for AppModel {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let program = Program::<AppModel>::builder().alt_screen(true).build()?;
+    let program = Program::<AppModel>::builder()
+        .alt_screen(true)
+        .coalesce_key_repeats(true)
+        .on_error(|err| {
+            if !matches!(err, Error::Interrupted | Error::ProgramKilled(_)) {
+                eprintln!("Error: {}", err);
+            }
+        })
+        .build()?;
 
     if let Err(err) = program.run().await {
         match err {
-            bubbletea_rs::Error::Interrupted => {
+            Error::Interrupted => {
                 std::process::exit(130);
             }
-            bubbletea_rs::Error::ProgramKilled => {
-                std::process::exit(1);
+            Error::ProgramKilled(code) => {
+                std::process::exit(code);
             }
             _ => {
-                eprintln!("Error: {}", err);
                 std::process::exit(1);
             }
         }
@@ -14,7 +14,8 @@
 //! between, with each tab displaying unique content in a connected window below.
 
 // Core bubbletea-rs imports for the Model-View-Update architecture
-use bubbletea_rs::{quit, Cmd, KeyMsg, Model, Msg, Program};
+use bubbletea_rs::model::common::CommonHandlers;
+use bubbletea_rs::{Cmd, IntoCmd, KeyMsg, KeySpec, Model, Msg, Program};
 
 // Crossterm for keyboard input handling
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -37,22 +38,6 @@ use std::cmp::{max, min};
 // INITIAL RENDER TRIGGER
 // ============================================================================
 
-/// Synthetic message used to trigger the initial render immediately after startup.
-///
-/// Without this, the interface would remain blank until the user presses a key.
-/// This pattern ensures users see the tabbed interface immediately when the
-/// program starts, creating a better user experience.
-#[derive(Debug)]
-struct InitRenderMsg;
-
-/// Creates a command that triggers the initial render.
-///
-/// Returns a boxed async future that immediately resolves with an InitRenderMsg.
-/// This is called from init() to force an immediate UI update on program start.
-fn init_render_cmd() -> Cmd {
-    Box::pin(async { Some(Box::new(InitRenderMsg) as Msg) })
-}
-
 // ============================================================================
 // APPLICATION MODEL
 // ============================================================================
@@ -164,6 +149,21 @@ fn tab_border_with_bottom(left: &'static str, middle: &'static str, right: &'sta
     border
 }
 
+// ============================================================================
+// COMMON MESSAGE HANDLING
+// ============================================================================
+
+/// 'q' quits, a common quick-quit key in terminal applications.
+const QUIT_KEYS: &[KeySpec] = &[KeySpec::new(KeyCode::Char('q'), KeyModifiers::NONE)];
+
+/// Ctrl+C and the quit keys are handled by `handle_common` before the
+/// tab navigation in update() sees them.
+impl CommonHandlers for TabModel {
+    fn quit_keys(&self) -> &[KeySpec] {
+        QUIT_KEYS
+    }
+}
+
 // ============================================================================
 // MODEL IMPLEMENTATION
 // ============================================================================
@@ -173,11 +173,8 @@ impl Model for TabModel {
     ///
     /// This method is called once when the program starts. It returns the
     /// initial model state and optionally a command to execute immediately.
-    ///
-    /// The init_render_cmd() ensures the interface appears immediately rather
-    /// than waiting for user input to trigger the first render.
     fn init() -> (Self, Option<Cmd>) {
-        (TabModel::default(), Some(init_render_cmd()))
+        (TabModel::default(), None)
     }
 
     /// Processes messages and updates the application state.
@@ -187,23 +184,16 @@ impl Model for TabModel {
     /// events, custom messages, etc.).
     ///
     /// Returns an optional command to execute after the state update.
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        // Ctrl+C and 'q' quit the program
+        if let Some(handled) = self.handle_common(&msg) {
+            return handled.into_cmd();
+        }
+
         // Handle keyboard input messages
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             match key_msg.key {
-                // ============================================================
-                // EXIT COMMANDS
-                // ============================================================
-                // Ctrl+C - Standard terminal interrupt signal
-                KeyCode::Char('c') if key_msg.modifiers.contains(KeyModifiers::CONTROL) => {
-                    return Some(quit());
-                }
-
-                // 'q' - Quick quit key, common in terminal applications
-                KeyCode::Char('q') => {
-                    return Some(quit());
-                }
-
                 // ============================================================
                 // NAVIGATION - NEXT TAB
                 // ============================================================
@@ -237,12 +227,6 @@ impl Model for TabModel {
             }
         }
 
-        // Handle the synthetic initial render message
-        if let Some(_init_msg) = msg.downcast_ref::<InitRenderMsg>() {
-            // No action needed - this just triggers the initial view() call
-            // to display the interface when the program starts
-        }
-
         // No additional commands needed after processing these messages
         None
     }
@@ -470,7 +454,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // - .signal_handler(true) for Ctrl+C/Ctrl+Z support
     // - .mouse_support(true) for mouse input
     // - .alt_screen(true) for full-screen applications
-    let program = Program::<TabModel>::builder().build()?;
+    //
+    // .on_error reports unexpected errors (I/O failures, terminal issues,
+    // etc.) once the terminal is back to normal, so the message isn't lost
+    // on the alternate screen or mixed into a half-drawn frame.
+    let program = Program::<TabModel>::builder()
+        .on_error(|err| {
+            if !matches!(
+                err,
+                bubbletea_rs::Error::Interrupted | bubbletea_rs::Error::ProgramKilled(_)
+            ) {
+                eprintln!("Error: {}", err);
+            }
+        })
+        .build()?;
 
     // ========================================================================
     // PROGRAM EXECUTION WITH ERROR HANDLING
@@ -481,7 +478,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // program.run() returns a Result that indicates how the program ended:
     // - Ok(model) - Normal termination via quit() command
     // - Err(Error::Interrupted) - Ctrl+C or signal termination
-    // - Err(Error::ProgramKilled) - Force kill via kill() command
+    // - Err(Error::ProgramKilled(code)) - Force kill via kill() command
     // - Err(other) - Unexpected errors (I/O, terminal issues, etc.)
     if let Err(err) = program.run().await {
         match err {
@@ -492,17 +489,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 std::process::exit(130);
             }
 
-            // Exit code 1 for force kill - indicates abnormal termination
-            // This is used when the program needs to exit immediately
-            // without normal cleanup procedures
-            bubbletea_rs::Error::ProgramKilled => {
-                std::process::exit(1);
+            // Force kill - exit with the code carried by the KillMsg
+            // (1 for the kill() command) to indicate abnormal termination
+            bubbletea_rs::Error::ProgramKilled(code) => {
+                std::process::exit(code);
             }
 
-            // Handle unexpected errors (I/O failures, terminal issues, etc.)
-            // Print the error message and exit with code 1 to indicate failure
+            // Unexpected errors were already printed by on_error; exit
+            // with code 1 to indicate failure
             _ => {
-                eprintln!("Error: {}", err);
                 std::process::exit(1);
             }
         }
@@ -204,6 +204,7 @@ impl Model for TextAreaModel {
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle cursor blink messages
         if msg.downcast_ref::<BlinkMsg>().is_some() {
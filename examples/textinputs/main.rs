@@ -88,6 +88,7 @@ impl Model for ModelTextInputs {
         (m, Some(cmd_focus))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
             // Quit
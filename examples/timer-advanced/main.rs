@@ -22,9 +22,6 @@ use bubbletea_widgets::timer::{
 use lipgloss_extras::lipgloss::{Color, Style};
 use std::time::Duration;
 
-// Synthetic message used to trigger the initial render immediately after startup.
-struct InitRenderMsg;
-
 /// Timer preset types for different use cases
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum TimerType {
@@ -336,12 +333,8 @@ impl TimerApp {
 
 impl Model for TimerApp {
     fn init() -> (Self, Option<Cmd>) {
-        let mut app = Self::new();
-
-        // Set initial terminal width
-        if let Ok((w, _h)) = crossterm::terminal::size() {
-            app.terminal_width = w;
-        }
+        // The real width arrives in a WindowSizeMsg before the first frame
+        let app = Self::new();
 
         // Initialize the first timer like the basic timer example
         let init_cmd = app.current_timer().init();
@@ -349,6 +342,7 @@ impl Model for TimerApp {
         (app, Some(init_cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle timer-specific messages first
         if let Some(timeout) = msg.downcast_ref::<TimeoutMsg>() {
@@ -400,11 +394,6 @@ impl Model for TimerApp {
             self.terminal_width = size.width;
         }
 
-        // Handle init render message
-        if msg.downcast_ref::<InitRenderMsg>().is_some() {
-            // No-op: just triggers render
-        }
-
         None
     }
 
@@ -154,6 +154,7 @@ impl BubbleTeaModel for Model {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle timer messages (matching Go's switch cases)
 
@@ -113,6 +113,7 @@ impl Model for TuiDaemonModel {
         (model, Some(batch(cmds)))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle key messages - quit on any key
         if msg.downcast_ref::<KeyMsg>().is_some() {
@@ -14,7 +14,8 @@
 //! similar to how the original Go example was written before the Bubbles
 //! progress component was available.
 
-use bubbletea_rs::{quit, tick, Cmd, KeyMsg, Model, Msg, Program};
+use bubbletea_rs::easing::ease_out_bounce;
+use bubbletea_rs::{gradient, quit, tick, Cmd, KeyMsg, Model, Msg, Program};
 use crossterm::event::{KeyCode, KeyModifiers};
 use lipgloss_extras::lipgloss::{Color, Style};
 use std::time::Duration;
@@ -68,6 +69,7 @@ impl Model for ViewsModel {
         )
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle keyboard input - always check for quit keys first
         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
@@ -299,28 +301,14 @@ fn make_gradient_ramp(color_a: &str, color_b: &str, steps: usize) -> Vec<String>
     let start_color = hex_to_rgb(color_a);
     let end_color = hex_to_rgb(color_b);
 
-    let mut ramp = Vec::with_capacity(steps);
-
-    for i in 0..steps {
-        let t = if steps <= 1 {
-            0.0
-        } else {
-            i as f64 / (steps - 1) as f64
-        };
-
-        let r =
-            (start_color.0 as f64 + (end_color.0 as f64 - start_color.0 as f64) * t).round() as u8;
-        let g =
-            (start_color.1 as f64 + (end_color.1 as f64 - start_color.1 as f64) * t).round() as u8;
-        let b =
-            (start_color.2 as f64 + (end_color.2 as f64 - start_color.2 as f64) * t).round() as u8;
-
-        let hex_color = format!("#{:02x}{:02x}{:02x}", r, g, b);
-        let style = Style::new().foreground(Color::from(hex_color.as_str()));
-        ramp.push(style.render(&PROGRESS_FULL_CHAR.to_string()));
-    }
-
-    ramp
+    gradient::sample(start_color, end_color, steps)
+        .into_iter()
+        .map(|(r, g, b)| {
+            let hex_color = format!("#{:02x}{:02x}{:02x}", r, g, b);
+            let style = Style::new().foreground(Color::from(hex_color.as_str()));
+            style.render(&PROGRESS_FULL_CHAR.to_string())
+        })
+        .collect()
 }
 
 /// Convert hex color string to RGB tuple
@@ -332,24 +320,6 @@ fn hex_to_rgb(hex: &str) -> (u8, u8, u8) {
     (r, g, b)
 }
 
-/// OutBounce easing function - matches the fogleman/ease OutBounce
-fn ease_out_bounce(t: f64) -> f64 {
-    let t = t.clamp(0.0, 1.0);
-
-    if t < (1.0 / 2.75) {
-        7.5625 * t * t
-    } else if t < (2.0 / 2.75) {
-        let t = t - (1.5 / 2.75);
-        7.5625 * t * t + 0.75
-    } else if t < (2.5 / 2.75) {
-        let t = t - (2.25 / 2.75);
-        7.5625 * t * t + 0.9375
-    } else {
-        let t = t - (2.625 / 2.75);
-        7.5625 * t * t + 0.984375
-    }
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let program = Program::<ViewsModel>::builder()
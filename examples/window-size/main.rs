@@ -53,6 +53,7 @@ impl Model for WindowSizeModel {
         (model, Some(cmd))
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, msg: Msg) -> Option<Cmd> {
         // Handle keyboard input - any key quits
         if let Some(_key_msg) = msg.downcast_ref::<KeyMsg>() {
@@ -0,0 +1,210 @@
+//! Frame-based animation driver for spinners and other looping animations.
+//!
+//! Many components animate by cycling through a fixed list of frames on a
+//! timer: spinners, loading dots, pulsing indicators. [`FrameAnimator`]
+//! holds the frame state and the tick plumbing so models don't have to
+//! define their own tick message type and rescheduling logic.
+//!
+//! Each animator has a unique id, and its tick messages carry that id, so
+//! several animators can run in the same model without interfering. Passing
+//! every message to [`FrameAnimator::handle`] is enough to keep an animator
+//! running: it ignores messages that are not its own.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::animation::FrameAnimator;
+//! use bubbletea_rs::{Cmd, IntoCmd, Model, Msg};
+//! use std::time::Duration;
+//!
+//! struct Loading {
+//!     spinner: FrameAnimator,
+//! }
+//!
+//! impl Model for Loading {
+//!     fn init() -> (Self, Option<Cmd>) {
+//!         let mut spinner = FrameAnimator::new(
+//!             ["|", "/", "-", "\\"].map(String::from).to_vec(),
+//!             Duration::from_millis(100),
+//!         );
+//!         let cmd = spinner.start();
+//!         (Self { spinner }, Some(cmd))
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+//!         // Advances the frame and schedules the next tick for our own ticks
+//!         self.spinner.handle(&msg)
+//!     }
+//!
+//!     fn view(&self) -> String {
+//!         format!("{} Loading...", self.spinner.current())
+//!     }
+//! }
+//! ```
+
+use crate::command::{tick, Cmd};
+use crate::event::{next_timer_id, BlurMsg, FocusMsg, Msg};
+use std::time::Duration;
+
+/// A tick message produced by a [`FrameAnimator`].
+///
+/// Models normally don't need to inspect this message; pass it to
+/// [`FrameAnimator::handle`] instead.
+#[derive(Debug, Clone)]
+pub struct AnimationTickMsg {
+    /// The id of the animator that scheduled this tick.
+    pub id: u64,
+    /// Generation of the tick loop, used to discard ticks scheduled before
+    /// the animator was stopped, paused, or restarted.
+    generation: u64,
+}
+
+/// Cycles through a list of frames at a fixed interval.
+///
+/// The animator is driven by one-shot [`tick`] commands: each tick advances
+/// to the next frame and schedules the following tick. Calling
+/// [`stop`](Self::stop) ends the loop; ticks that were already scheduled are
+/// discarded when they arrive.
+///
+/// # Pausing on blur
+///
+/// When the program reports focus changes (see
+/// `ProgramBuilder::report_focus`), [`handle`](Self::handle) pauses the
+/// animation on `BlurMsg` and resumes it on `FocusMsg`, so hidden terminals
+/// don't keep re-rendering. Without focus reporting the animation simply
+/// runs until stopped.
+#[derive(Debug)]
+pub struct FrameAnimator {
+    frames: Vec<String>,
+    interval: Duration,
+    index: usize,
+    id: u64,
+    generation: u64,
+    running: bool,
+    paused: bool,
+}
+
+impl FrameAnimator {
+    /// Creates a new animator with the given frames and frame interval.
+    ///
+    /// The animator starts out stopped on the first frame; call
+    /// [`start`](Self::start) to begin animating.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frames to cycle through, in order
+    /// * `interval` - How long each frame is shown
+    pub fn new(frames: Vec<String>, interval: Duration) -> Self {
+        Self {
+            frames,
+            interval,
+            index: 0,
+            id: next_timer_id(),
+            generation: 0,
+            running: false,
+            paused: false,
+        }
+    }
+
+    /// Returns the unique id carried by this animator's tick messages.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the interval between frames.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the current frame, or an empty string if there are no frames.
+    pub fn current(&self) -> &str {
+        self.frames.get(self.index).map_or("", String::as_str)
+    }
+
+    /// Advances to the next frame, wrapping around after the last one.
+    pub fn advance(&mut self) {
+        if !self.frames.is_empty() {
+            self.index = (self.index + 1) % self.frames.len();
+        }
+    }
+
+    /// Returns whether the animation is running (it may still be paused).
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns whether the animation is paused because the terminal lost focus.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Starts (or restarts) the animation.
+    ///
+    /// # Returns
+    ///
+    /// The command that schedules the first tick. Any tick still pending
+    /// from a previous run is discarded, so restarting never speeds the
+    /// animation up.
+    pub fn start(&mut self) -> Cmd {
+        self.running = true;
+        self.paused = false;
+        self.generation += 1;
+        self.tick_cmd()
+    }
+
+    /// Stops the animation.
+    ///
+    /// The current frame is kept, and no further ticks are scheduled.
+    pub fn stop(&mut self) {
+        self.running = false;
+        self.generation += 1;
+    }
+
+    /// Creates a command that delivers this animator's next tick after one
+    /// interval.
+    ///
+    /// [`start`](Self::start) and [`handle`](Self::handle) call this for
+    /// you; it is only needed when scheduling ticks manually.
+    pub fn tick_cmd(&self) -> Cmd {
+        let id = self.id;
+        let generation = self.generation;
+        tick(self.interval, move |_| {
+            Box::new(AnimationTickMsg { id, generation }) as Msg
+        })
+    }
+
+    /// Updates the animator from a message.
+    ///
+    /// - A current tick for this animator advances the frame and returns the
+    ///   command for the next tick.
+    /// - `BlurMsg` pauses a running animation; `FocusMsg` resumes it and
+    ///   returns the command for the next tick.
+    /// - Everything else, including ticks from other animators, is ignored.
+    ///
+    /// # Returns
+    ///
+    /// A command to schedule the next tick, or `None`.
+    pub fn handle(&mut self, msg: &Msg) -> Option<Cmd> {
+        if let Some(tick_msg) = msg.downcast_ref::<AnimationTickMsg>() {
+            if tick_msg.id != self.id
+                || tick_msg.generation != self.generation
+                || !self.running
+                || self.paused
+            {
+                return None;
+            }
+            self.advance();
+            return Some(self.tick_cmd());
+        }
+        if msg.is::<BlurMsg>() && self.running && !self.paused {
+            self.paused = true;
+            self.generation += 1;
+        } else if msg.is::<FocusMsg>() && self.paused {
+            self.paused = false;
+            if self.running {
+                return Some(self.tick_cmd());
+            }
+        }
+        None
+    }
+}
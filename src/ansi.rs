@@ -0,0 +1,320 @@
+//! Helpers for measuring and wrapping text that contains ANSI escape
+//! sequences.
+//!
+//! Styled strings, such as the output of lipgloss or the gradient helpers,
+//! carry escape sequences that take up bytes but no cells on screen. These
+//! helpers treat each escape sequence as a single zero-width unit, so widths
+//! are measured in visible characters and sequences are never split.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::ansi;
+//!
+//! let styled = "\x1b[32m✓\x1b[0m installed";
+//! assert_eq!(ansi::visible_width(styled), 11);
+//!
+//! let lines = ansi::wrap("\x1b[1mbold text\x1b[0m", 5);
+//! assert_eq!(lines, ["\x1b[1mbold \x1b[0m", "\x1b[1mtext\x1b[0m"]);
+//! ```
+
+/// The SGR sequence that resets all styling.
+pub const RESET: &str = "\x1b[0m";
+
+/// Returns the byte length of the escape sequence at the start of `s`.
+///
+/// `s` must start with `ESC`. CSI sequences run until their final byte in
+/// `'@'..='~'`, OSC sequences until BEL or ST (`ESC \`), and anything else
+/// is `ESC` plus one character. An unterminated sequence runs to the end of
+/// `s`.
+fn escape_len(s: &str) -> usize {
+    let mut chars = s.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '[')) => {
+            for (i, c) in chars {
+                if ('@'..='~').contains(&c) {
+                    return i + c.len_utf8();
+                }
+            }
+            s.len()
+        }
+        Some((_, ']')) => {
+            let mut chars = chars.peekable();
+            while let Some((i, c)) = chars.next() {
+                if c == '\x07' {
+                    return i + 1;
+                }
+                if c == '\x1b' && matches!(chars.peek(), Some((_, '\\'))) {
+                    return i + 2;
+                }
+            }
+            s.len()
+        }
+        Some((i, c)) => i + c.len_utf8(),
+        None => s.len(),
+    }
+}
+
+/// A piece of styled text: either an escape sequence or a visible character.
+enum Token<'a> {
+    Escape(&'a str),
+    Char(char),
+}
+
+/// Splits `s` into escape sequences and visible characters.
+fn tokens(s: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        let ch = rest.chars().next()?;
+        if ch == '\x1b' {
+            let len = escape_len(rest);
+            let (escape, tail) = rest.split_at(len);
+            rest = tail;
+            Some(Token::Escape(escape))
+        } else {
+            rest = &rest[ch.len_utf8()..];
+            Some(Token::Char(ch))
+        }
+    })
+}
+
+/// Returns the parameters of `escape` if it is an SGR (`ESC [ … m`) sequence.
+fn sgr_params(escape: &str) -> Option<&str> {
+    escape.strip_prefix("\x1b[")?.strip_suffix('m')
+}
+
+/// Counts the characters of `s` that occupy a cell, skipping ANSI CSI and
+/// OSC escape sequences.
+///
+/// Every other character counts as one cell.
+pub fn visible_width(s: &str) -> usize {
+    tokens(s)
+        .filter(|token| matches!(token, Token::Char(_)))
+        .count()
+}
+
+/// Removes every ANSI escape sequence from `s`, keeping the visible text.
+pub fn strip(s: &str) -> String {
+    tokens(s)
+        .filter_map(|token| match token {
+            Token::Char(ch) => Some(ch),
+            Token::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// Splits styled text into lines of at most `width` visible characters.
+///
+/// The text is split at newlines first, and each line is then broken every
+/// `width` visible characters. A `width` of zero disables wrapping. Escape
+/// sequences are never split.
+///
+/// Every returned line is self-contained: styling that is still active at
+/// the end of a line is closed with [`RESET`], and reopened at the start of
+/// the next non-empty line. Printing the lines one after another therefore
+/// looks the same as printing the original text, but nothing after the last
+/// line inherits its colors.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    // SGR sequences applied since the last reset
+    let mut active: Vec<&str> = Vec::new();
+
+    for source_line in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0;
+        let mut fresh = true;
+
+        for token in tokens(source_line) {
+            if let Token::Char(_) = token {
+                if width > 0 && line_width == width {
+                    if !active.is_empty() {
+                        line.push_str(RESET);
+                    }
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                    fresh = true;
+                }
+            }
+            if fresh {
+                line.extend(active.iter().copied());
+                fresh = false;
+            }
+            match token {
+                Token::Char(ch) => {
+                    line.push(ch);
+                    line_width += 1;
+                }
+                Token::Escape(escape) => {
+                    line.push_str(escape);
+                    track_sgr(&mut active, escape);
+                }
+            }
+        }
+        if !fresh && !active.is_empty() {
+            line.push_str(RESET);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Returns how many rows `text` takes up on a terminal `width` columns
+/// wide.
+///
+/// Lines longer than `width` visible characters wrap onto further rows, as
+/// with [`wrap`]. A trailing newline does not start another row, and empty
+/// text takes none. A `width` of zero disables wrapping.
+pub fn height(text: &str, width: usize) -> usize {
+    if text.is_empty() {
+        return 0;
+    }
+    let text = text.strip_suffix('\n').unwrap_or(text);
+    let lines = text.split('\n');
+    if width == 0 {
+        return lines.count();
+    }
+    lines
+        .map(|line| visible_width(line).div_ceil(width).max(1))
+        .sum()
+}
+
+/// Returns the visible columns `start..start + width` of a single line of
+/// styled text.
+///
+/// Like the lines from [`wrap`], the slice is self-contained: styling
+/// applied before `start` is reopened at the beginning, and styling still
+/// active at the end is closed with [`RESET`]. Columns past the end of the
+/// line are simply missing, so the result may be narrower than `width`.
+pub fn slice(line: &str, start: usize, width: usize) -> String {
+    let end = start.saturating_add(width);
+    let mut out = String::new();
+    // SGR sequences applied since the last reset
+    let mut active: Vec<&str> = Vec::new();
+    let mut column = 0;
+    let mut opened = false;
+
+    for token in tokens(line) {
+        match token {
+            Token::Char(ch) => {
+                if column >= end {
+                    break;
+                }
+                if column >= start {
+                    if !opened {
+                        out.extend(active.iter().copied());
+                        opened = true;
+                    }
+                    out.push(ch);
+                }
+                column += 1;
+            }
+            Token::Escape(escape) => {
+                if opened {
+                    out.push_str(escape);
+                }
+                track_sgr(&mut active, escape);
+            }
+        }
+    }
+    if opened && !active.is_empty() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Updates the list of active SGR sequences after `escape`.
+fn track_sgr<'a>(active: &mut Vec<&'a str>, escape: &'a str) {
+    if let Some(params) = sgr_params(escape) {
+        if params.is_empty() || params == "0" {
+            active.clear();
+        } else {
+            if params.starts_with("0;") {
+                active.clear();
+            }
+            active.push(escape);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_width_skips_escape_sequences() {
+        assert_eq!(visible_width("plain"), 5);
+        assert_eq!(visible_width("\x1b[38;2;255;0;0m█\x1b[0m"), 1);
+        assert_eq!(visible_width("\x1b]0;title\x07ab"), 2);
+        assert_eq!(visible_width("\x1b]8;;http://x\x1b\\link"), 4);
+        assert_eq!(visible_width("\x1b[31"), 0);
+    }
+
+    #[test]
+    fn test_strip_removes_escape_sequences() {
+        assert_eq!(
+            strip("\x1b[1;31mred\x1b[0m and \x1b]0;t\x07plain"),
+            "red and plain"
+        );
+        assert_eq!(strip("no escapes"), "no escapes");
+    }
+
+    #[test]
+    fn test_slice_keeps_styling_self_contained() {
+        assert_eq!(slice("abcdef", 2, 3), "cde");
+        assert_eq!(slice("abc", 2, 5), "c");
+        assert_eq!(slice("abc", 5, 2), "");
+        assert_eq!(
+            slice("\x1b[31mred\x1b[0m plain", 1, 4),
+            "\x1b[31med\x1b[0m p"
+        );
+        assert_eq!(slice("\x1b[1mbold", 2, 1), "\x1b[1ml\x1b[0m");
+    }
+
+    #[test]
+    fn test_height_counts_wrapped_rows() {
+        assert_eq!(height("", 10), 0);
+        assert_eq!(height("one\ntwo\n", 10), 2);
+        assert_eq!(height("0123456789", 10), 1);
+        assert_eq!(height("0123456789a\n\nb", 10), 4);
+        assert_eq!(height("\x1b[31m0123456789\x1b[0m", 10), 1);
+        assert_eq!(height("0123456789a", 0), 1);
+        for text in ["abcdefg\nhi", "\x1b[1mbold text\x1b[0m", "a\n\nb"] {
+            assert_eq!(height(text, 3), wrap(text, 3).len(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_wrap_without_width_only_splits_lines() {
+        assert_eq!(wrap("one\ntwo", 0), ["one", "two"]);
+        assert_eq!(wrap("trailing\n", 0), ["trailing", ""]);
+    }
+
+    #[test]
+    fn test_wrap_counts_visible_characters_only() {
+        let lines = wrap("\x1b[38;2;1;2;3mab\x1b[0mcd", 3);
+        assert_eq!(lines, ["\x1b[38;2;1;2;3mab\x1b[0mc", "d"]);
+    }
+
+    #[test]
+    fn test_wrap_reopens_style_on_continuation_lines() {
+        let lines = wrap("\x1b[1m\x1b[31mabcdef\x1b[0m", 4);
+        assert_eq!(
+            lines,
+            ["\x1b[1m\x1b[31mabcd\x1b[0m", "\x1b[1m\x1b[31mef\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_closes_style_left_open_at_line_end() {
+        let lines = wrap("\x1b[32mgreen\nstill green", 0);
+        assert_eq!(
+            lines,
+            ["\x1b[32mgreen\x1b[0m", "\x1b[32mstill green\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_leaves_empty_lines_unstyled() {
+        assert_eq!(wrap("\x1b[32mok\n", 0), ["\x1b[32mok\x1b[0m", ""]);
+    }
+}
@@ -0,0 +1,357 @@
+//! Injectable time source for timers.
+//!
+//! A [`Program`](crate::Program) built with
+//! [`ProgramBuilder::clock`](crate::ProgramBuilder::clock) runs its timers
+//! against that [`Clock`] instead of the runtime's timer. With a [`MockClock`],
+//! a test decides exactly when time passes, so timer-driven behaviour is
+//! deterministic without sleeping.
+//!
+//! Every timer the program runs waits on its clock: one-shot commands such
+//! as [`command::tick`](crate::command::tick) and
+//! [`command::debounce`](crate::command::debounce), recurring timers from
+//! [`command::every`](crate::command::every) and
+//! [`command::every_precise`](crate::command::every_precise), the visual
+//! bell, [`ProgramBuilder::idle_after`](crate::ProgramBuilder::idle_after),
+//! interval flushing and the frame budget. Only commands that sleep on
+//! tokio's timer themselves are outside its control.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::clock::{Clock, MockClock};
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let clock = MockClock::new();
+//! let start = clock.now();
+//! let sleep = clock.sleep(Duration::from_secs(1));
+//! clock.advance(Duration::from_secs(1));
+//! sleep.await;
+//! assert_eq!(clock.now() - start, Duration::from_secs(1));
+//! # }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+use tokio::time::MissedTickBehavior;
+
+/// A source of the current time and of sleeps.
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+
+    /// Returns a future that completes once `duration` has passed on this
+    /// clock.
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+}
+
+/// The real clock, backed by tokio's timer.
+///
+/// Used when no clock is set on the builder and the program runs on tokio.
+#[cfg(feature = "tokio-runtime")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+#[cfg(feature = "tokio-runtime")]
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// A clock that only moves when [`advance`](Self::advance) is called.
+///
+/// Clones share the same time, so a test keeps one clone and gives another
+/// to the program.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: Instant,
+    /// The id the next sleep gets
+    next_id: u64,
+    /// Each pending sleep's deadline and the waker of its latest poll, by id
+    sleepers: BTreeMap<u64, (Instant, Waker)>,
+}
+
+impl MockClock {
+    /// Creates a clock that starts at the current real time and stands
+    /// still.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                now: Instant::now(),
+                next_id: 0,
+                sleepers: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Moves the clock forward by `duration`, completing every sleep whose
+    /// deadline has been reached.
+    pub fn advance(&self, duration: Duration) {
+        let mut due = {
+            let mut state = self.state.lock().unwrap();
+            state.now += duration;
+            let now = state.now;
+            let ids: Vec<u64> = state
+                .sleepers
+                .iter()
+                .filter(|(_, (deadline, _))| *deadline <= now)
+                .map(|(id, _)| *id)
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| state.sleepers.remove(&id))
+                .collect::<Vec<_>>()
+        };
+        // Earliest deadline first; ties in the order the sleeps were made
+        due.sort_by_key(|(deadline, _)| *deadline);
+        for (_, waker) in due {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        Box::pin(MockSleep {
+            id,
+            deadline: state.now + duration,
+            state: Arc::clone(&self.state),
+        })
+    }
+}
+
+/// A sleep on a [`MockClock`].
+struct MockSleep {
+    id: u64,
+    deadline: Instant,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.now >= self.deadline {
+            state.sleepers.remove(&self.id);
+            return Poll::Ready(());
+        }
+        // Only the latest waker is kept, however often the sleep is polled
+        match state.sleepers.get_mut(&self.id) {
+            Some((_, waker)) => waker.clone_from(cx.waker()),
+            None => {
+                state
+                    .sleepers
+                    .insert(self.id, (self.deadline, cx.waker().clone()));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for MockSleep {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.sleepers.remove(&self.id);
+        }
+    }
+}
+
+/// The real clock of whichever runtime the caller is on.
+struct RuntimeClock;
+
+impl Clock for RuntimeClock {
+    fn now(&self) -> Instant {
+        crate::runtime::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(crate::runtime::sleep(duration))
+    }
+}
+
+thread_local! {
+    /// The clock of the future [`scope`] is polling on this thread
+    static CURRENT: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Returns the clock of the program running the current command, if it set
+/// one.
+pub(crate) fn current() -> Option<Arc<dyn Clock>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Sleeps for `duration` on the [`current`] clock, or on the runtime's
+/// timer if there is none.
+pub(crate) async fn sleep(duration: Duration) {
+    match current() {
+        Some(clock) => clock.sleep(duration).await,
+        None => crate::runtime::sleep(duration).await,
+    }
+}
+
+/// Returns the current time on the [`current`] clock, or on the runtime's
+/// timer if there is none.
+pub(crate) fn now() -> Instant {
+    match current() {
+        Some(clock) => clock.now(),
+        None => crate::runtime::now(),
+    }
+}
+
+/// Sleeps until `deadline` on the [`current`] clock, or on the runtime's
+/// timer if there is none.
+pub(crate) async fn sleep_until(deadline: Instant) {
+    match current() {
+        Some(clock) => {
+            let duration = deadline.saturating_duration_since(clock.now());
+            clock.sleep(duration).await;
+        }
+        None => crate::runtime::sleep_until(deadline).await,
+    }
+}
+
+/// A recurring timer on the [`current`] clock, falling back to a
+/// `tokio::time::Interval` on tokio and the built-in timer elsewhere.
+pub(crate) enum Ticker {
+    #[cfg(feature = "tokio-runtime")]
+    Tokio(tokio::time::Interval),
+    Clock {
+        clock: Arc<dyn Clock>,
+        period: Duration,
+        behavior: MissedTickBehavior,
+        /// When the next tick is due
+        next: Instant,
+    },
+}
+
+impl Ticker {
+    /// Creates a ticker whose first tick is one `period` from now. Ticks
+    /// that are missed are handled as `behavior` says.
+    pub(crate) fn new(period: Duration, behavior: MissedTickBehavior) -> Self {
+        let current = current();
+        #[cfg(feature = "tokio-runtime")]
+        if current.is_none() && crate::runtime::on_tokio() {
+            let start = tokio::time::Instant::now() + period;
+            let mut interval = tokio::time::interval_at(start, period);
+            interval.set_missed_tick_behavior(behavior);
+            return Self::Tokio(interval);
+        }
+        let clock = current.unwrap_or_else(|| Arc::new(RuntimeClock));
+        Self::Clock {
+            next: clock.now() + period,
+            clock,
+            period,
+            behavior,
+        }
+    }
+
+    /// Waits for the next tick.
+    pub(crate) async fn tick(&mut self) {
+        match self {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(interval) => {
+                interval.tick().await;
+            }
+            Self::Clock {
+                clock,
+                period,
+                behavior,
+                next,
+            } => {
+                clock
+                    .sleep(next.saturating_duration_since(clock.now()))
+                    .await;
+                let now = clock.now();
+                *next = match behavior {
+                    MissedTickBehavior::Burst => *next + *period,
+                    MissedTickBehavior::Delay => now + *period,
+                    _ => {
+                        let mut next = *next + *period;
+                        while next <= now {
+                            next += *period;
+                        }
+                        next
+                    }
+                };
+            }
+        }
+    }
+
+    /// Starts a fresh period, so the next tick is one `period` from now.
+    pub(crate) fn reset(&mut self) {
+        match self {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(interval) => interval.reset(),
+            Self::Clock {
+                clock,
+                period,
+                next,
+                ..
+            } => *next = clock.now() + *period,
+        }
+    }
+}
+
+/// Runs `future` with `clock` as its [`current`] clock.
+pub(crate) fn scope<F: Future>(
+    clock: Arc<dyn Clock>,
+    future: F,
+) -> impl Future<Output = F::Output> {
+    Scoped { clock, future }
+}
+
+/// A future that makes its clock [`current`] while it is polled.
+#[pin_project::pin_project]
+struct Scoped<F> {
+    clock: Arc<dyn Clock>,
+    #[pin]
+    future: F,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        /// Puts back the clock that was current before, even on a panic.
+        struct Restore(Option<Arc<dyn Clock>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT.with(|current| *current.borrow_mut() = previous);
+            }
+        }
+
+        let this = self.project();
+        let previous = CURRENT.with(|current| current.replace(Some(Arc::clone(this.clock))));
+        let _restore = Restore(previous);
+        this.future.poll(cx)
+    }
+}
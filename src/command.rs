@@ -2,19 +2,22 @@
 //! Commands are asynchronous operations that can produce messages to update the model.
 
 use crate::event::{
-    next_timer_id, BatchCmdMsg, ClearScreenMsg, DisableBracketedPasteMsg, DisableMouseMsg,
-    DisableReportFocusMsg, EnableBracketedPasteMsg, EnableMouseAllMotionMsg,
-    EnableMouseCellMotionMsg, EnableReportFocusMsg, EnterAltScreenMsg, ExitAltScreenMsg,
-    HideCursorMsg, InterruptMsg, KillMsg, Msg, PrintMsg, PrintfMsg, QuitMsg, RequestWindowSizeMsg,
-    ShowCursorMsg, SuspendMsg,
+    next_timer_id, AnnounceMsg, BatchCmdMsg, BatchLimitedMsg, BeginMouseCaptureMsg,
+    ClearFromStartOfScreenMsg, ClearLineMsg, ClearScreenMsg, ClearToEndOfScreenMsg,
+    DisableBracketedPasteMsg, DisableMouseMsg, DisableReportFocusMsg, EnableBracketedPasteMsg,
+    EnableMouseAllMotionMsg, EnableMouseCellMotionMsg, EnableReportFocusMsg, EndMouseCaptureMsg,
+    EnterAltScreenMsg, ExitAltScreenMsg, HideCursorMsg, InterruptMsg, KillMsg, Msg, PauseRenderMsg,
+    PrintMsg, PrintfMsg, QueryKeyboardEnhancementsMsg, QuitMsg, QuitWithMsg, RequestWindowSizeMsg,
+    RestoreCursorMsg, ResumeRenderMsg, SaveCursorMsg, ShowCursorMsg, SuspendMsg,
 };
+use std::any::Any;
 use std::future::Future;
 use std::pin::Pin;
 use std::process::Command as StdCommand;
 use std::sync::OnceLock;
-use std::time::Duration;
-use tokio::process::Command as TokioCommand;
-use tokio::time::interval;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::time::MissedTickBehavior;
 use tokio_util::sync::CancellationToken;
 
 /// A command represents an asynchronous operation that may produce a message.
@@ -28,6 +31,89 @@ use tokio_util::sync::CancellationToken;
 /// to be processed by the `update` method.
 pub type Cmd = Pin<Box<dyn Future<Output = Option<Msg>> + Send>>;
 
+/// Conversion into an optional command, used for the return value of
+/// `Model::update`.
+///
+/// This lets `update` return whichever shape is most natural for the
+/// message being handled instead of always building an `Option<Cmd>`:
+///
+/// - `Option<Cmd>`: returned as-is
+/// - `Cmd`: a single command
+/// - `Vec<Cmd>`: several commands, run concurrently like [`batch`]. An empty
+///   vector produces no command, and a single command is not wrapped.
+/// - `Option<Vec<Cmd>>`: like `Vec<Cmd>`, with `None` meaning no command
+/// - `()`: no command
+///
+/// When `update` is declared as `-> impl IntoCmd`, every return path must
+/// have the same type, and a bare `None` has no type on its own. Models that
+/// never issue commands can return `()` (an empty body); otherwise write
+/// `None::<Cmd>` if no other return path fixes the type.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg, WindowSizeMsg};
+///
+/// struct MyModel;
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<Cmd>) {
+///         (Self, None)
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         let mut cmds = Vec::new();
+///         if msg.downcast_ref::<WindowSizeMsg>().is_some() {
+///             cmds.push(command::clear_screen());
+///             cmds.push(command::set_window_title("resized".to_string()));
+///         }
+///         cmds // No `batch` and no empty check needed
+///     }
+///
+///     fn view(&self) -> String {
+///         String::new()
+///     }
+/// }
+/// ```
+pub trait IntoCmd {
+    /// Converts `self` into an optional command.
+    fn into_cmd(self) -> Option<Cmd>;
+}
+
+impl IntoCmd for Option<Cmd> {
+    fn into_cmd(self) -> Option<Cmd> {
+        self
+    }
+}
+
+impl IntoCmd for Cmd {
+    fn into_cmd(self) -> Option<Cmd> {
+        Some(self)
+    }
+}
+
+impl IntoCmd for Vec<Cmd> {
+    fn into_cmd(mut self) -> Option<Cmd> {
+        match self.len() {
+            0 => None,
+            1 => self.pop(),
+            _ => Some(batch(self)),
+        }
+    }
+}
+
+impl IntoCmd for Option<Vec<Cmd>> {
+    fn into_cmd(self) -> Option<Cmd> {
+        self.and_then(IntoCmd::into_cmd)
+    }
+}
+
+impl IntoCmd for () {
+    fn into_cmd(self) -> Option<Cmd> {
+        None
+    }
+}
+
 /// A batch command that executes multiple commands concurrently.
 ///
 /// This struct is used internally by the `batch` function to group multiple
@@ -93,10 +179,101 @@ pub fn quit() -> Cmd {
     Box::pin(async { Some(Box::new(QuitMsg) as Msg) })
 }
 
+/// Creates a command that quits the application and hands `value` back to
+/// the caller.
+///
+/// The model receives a plain `QuitMsg`, exactly as with [`quit`], and the
+/// value can be read from the `ProgramResult` returned by
+/// [`Program::run_with_result`](crate::Program::run_with_result). If several
+/// `quit_with` messages are handled before the program stops, the last value
+/// wins; a plain [`quit`] leaves the value unset.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, KeyMsg, Model, Msg};
+/// use crossterm::event::KeyCode;
+///
+/// struct Menu;
+///
+/// impl Model for Menu {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         (Self, None)
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+///             match key_msg.key {
+///                 KeyCode::Char('o') => return Some(command::quit_with("open")),
+///                 KeyCode::Char('d') => return Some(command::quit_with("delete")),
+///                 KeyCode::Esc => return Some(command::quit()),
+///                 _ => {}
+///             }
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         "(o)pen, (d)elete or Esc to cancel".to_string()
+///     }
+/// }
+/// ```
+pub fn quit_with<T: Send + 'static>(value: T) -> Cmd {
+    Box::pin(async move {
+        Some(Box::new(QuitWithMsg {
+            value: Box::new(value),
+        }) as Msg)
+    })
+}
+
+/// Creates a command that quits the application only if an async check
+/// passes.
+///
+/// The command awaits `check`. If it resolves to `true`, the command
+/// quits like [`quit`]; otherwise it delivers the message from
+/// `on_blocked` instead, so the model can, for example, ask whether to
+/// discard unsaved changes.
+///
+/// # Arguments
+///
+/// * `check` - Resolves to whether quitting is allowed
+/// * `on_blocked` - Creates the message delivered when it is not
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, Msg};
+///
+/// #[derive(Debug)]
+/// struct ConfirmDiscardMsg;
+///
+/// async fn all_changes_synced() -> bool {
+///     // Ask the server whether anything is still pending
+///     true
+/// }
+///
+/// fn request_quit() -> Cmd {
+///     command::quit_if(all_changes_synced(), || Box::new(ConfirmDiscardMsg) as Msg)
+/// }
+/// ```
+pub fn quit_if<F, B>(check: F, on_blocked: B) -> Cmd
+where
+    F: Future<Output = bool> + Send + 'static,
+    B: FnOnce() -> Msg + Send + 'static,
+{
+    Box::pin(async move {
+        if check.await {
+            quit().await
+        } else {
+            Some(on_blocked())
+        }
+    })
+}
+
 /// Creates a command that kills the application immediately.
 ///
 /// This command sends a `KillMsg` to the program, which will cause the event loop
-/// to terminate as soon as possible with `Error::ProgramKilled`.
+/// to terminate as soon as possible with `Error::ProgramKilled(1)`.
 ///
 /// # Examples
 ///
@@ -126,7 +303,7 @@ pub fn quit() -> Cmd {
 /// }
 /// ```
 pub fn kill() -> Cmd {
-    Box::pin(async { Some(Box::new(KillMsg) as Msg) })
+    Box::pin(async { Some(Box::new(KillMsg::by_user("kill command".to_string())) as Msg) })
 }
 
 /// Creates a command that interrupts the application.
@@ -198,6 +375,47 @@ pub fn batch(cmds: Vec<Cmd>) -> Cmd {
     })
 }
 
+/// Creates a command that executes commands concurrently, at most
+/// `concurrency` at a time.
+///
+/// Like [`batch`], but when `concurrency` commands are running the rest
+/// wait their turn; the next one starts as soon as a running one finishes.
+/// Commands are started in order, and each message is delivered as soon
+/// as its command completes rather than when the whole set is done. A
+/// `concurrency` of zero is treated as one. Commands still waiting when the
+/// program shuts down are never started.
+///
+/// Use [`after_all_limited`] to get one message once all of them are done.
+///
+/// # Arguments
+///
+/// * `concurrency` - How many commands may run at once
+/// * `cmds` - The commands to execute
+///
+/// # Returns
+///
+/// A command that hands the commands to the program for limited concurrent
+/// execution
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, Msg};
+///
+/// #[derive(Debug)]
+/// struct FetchedMsg(u32);
+///
+/// fn fetch(page: u32) -> Cmd {
+///     Box::pin(async move { Some(Box::new(FetchedMsg(page)) as Msg) })
+/// }
+///
+/// // Fetch 200 pages without opening 200 connections at once
+/// let cmd = command::batch_limited(8, (0..200).map(fetch).collect());
+/// ```
+pub fn batch_limited(concurrency: usize, cmds: Vec<Cmd>) -> Cmd {
+    Box::pin(async move { Some(Box::new(BatchLimitedMsg { concurrency, cmds }) as Msg) })
+}
+
 /// Creates a command that executes a sequence of commands sequentially.
 ///
 /// The commands in the sequence will be executed one after another in order.
@@ -257,189 +475,548 @@ pub fn sequence(cmds: Vec<Cmd>) -> Cmd {
     })
 }
 
-/// Creates a command that produces a single message after a delay.
-///
-/// This command will send a message produced by the provided closure `f`
-/// after the specified `duration`. Unlike `every()`, this produces only
-/// one message and then completes. It's commonly used for one-shot timers
-/// that can be re-armed in the update method.
+/// Creates a command that executes commands sequentially until one of them
+/// produces a cancelling message.
 ///
-/// Note: Due to tokio's interval implementation, the first tick is consumed
-/// to ensure the message is sent after a full duration, not immediately.
+/// This behaves like [`sequence`], except that every step's message is
+/// checked with `cancel` as soon as the step completes. When `cancel`
+/// returns `true`, the remaining steps are skipped and the message is
+/// replaced by a [`CancelledMsg`](crate::event::CancelledMsg) carrying the
+/// step's index. Messages from earlier steps are still delivered, followed
+/// by the `CancelledMsg`.
 ///
 /// # Arguments
 ///
-/// * `duration` - The duration to wait before sending the message
-/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+/// * `cmds` - A vector of commands to execute sequentially
+/// * `cancel` - Returns `true` for messages that should abort the sequence
 ///
 /// # Returns
 ///
-/// A command that will produce a single message after the specified duration
+/// A command that executes the commands in sequence until cancelled
 ///
 /// # Examples
 ///
 /// ```
-/// use bubbletea_rs::{command, Model, Msg};
-/// use std::time::Duration;
+/// use bubbletea_rs::{command, Msg};
+/// use bubbletea_rs::event::CancelledMsg;
 ///
 /// #[derive(Debug)]
-/// struct TickMsg;
+/// struct StepFailedMsg;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let cmd = command::sequence_until(
+///     vec![
+///         Box::pin(async { Some(Box::new(StepFailedMsg) as Msg) }),
+///         command::quit(), // never runs
+///     ],
+///     |msg| msg.is::<StepFailedMsg>(),
+/// );
+/// let batch = cmd.await.unwrap();
+/// let batch = batch.downcast_ref::<bubbletea_rs::BatchMsgInternal>().unwrap();
+/// assert_eq!(batch.messages.len(), 1);
+/// assert!(batch.messages[0].is::<CancelledMsg>());
+/// # }
+/// ```
+pub fn sequence_until<F>(cmds: Vec<Cmd>, cancel: F) -> Cmd
+where
+    F: Fn(&Msg) -> bool + Send + 'static,
+{
+    Box::pin(async move {
+        let mut results = Vec::new();
+        for (step, cmd) in cmds.into_iter().enumerate() {
+            if let Some(msg) = cmd.await {
+                if cancel(&msg) {
+                    results.push(Box::new(crate::event::CancelledMsg { step }) as Msg);
+                    break;
+                }
+                results.push(msg);
+            }
+        }
+        if results.is_empty() {
+            None
+        } else {
+            Some(Box::new(crate::event::BatchMsgInternal { messages: results }) as Msg)
+        }
+    })
+}
+
+/// Creates a command that executes commands sequentially until one of them
+/// produces a message of type `T`.
 ///
-/// struct MyModel {
-///     counter: u32,
+/// This is [`sequence_until`] with a type check as the predicate. For
+/// example, `sequence_until_msg::<QuitMsg>(steps)` stops a multi-step flow
+/// as soon as a step asks to quit, instead of running the remaining steps
+/// while the program shuts down.
+///
+/// # Arguments
+///
+/// * `cmds` - A vector of commands to execute sequentially
+///
+/// # Returns
+///
+/// A command that executes the commands in sequence until cancelled
+pub fn sequence_until_msg<T: Any + Send>(cmds: Vec<Cmd>) -> Cmd {
+    sequence_until(cmds, |msg| msg.is::<T>())
+}
+
+/// Creates a command that runs commands in order and reports progress.
+///
+/// Like [`sequence`], each command only starts after the previous one has
+/// completed. Before each step starts, the message returned by
+/// `on_step(index, total)` is delivered, with a zero-based `index`. Unlike
+/// `sequence`, every step's message is delivered as soon as that step
+/// completes rather than all at once at the end, so progress and results
+/// arrive interleaved: progress 0, result 0, progress 1, result 1, and so on.
+///
+/// # Arguments
+///
+/// * `cmds` - The commands to execute sequentially
+/// * `on_step` - Builds the progress message from `(index, total)`
+///
+/// # Returns
+///
+/// A command that runs the steps in a background task
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg};
+///
+/// struct StepMsg(usize, usize);
+///
+/// struct Wizard {
+///     status: String,
 /// }
 ///
-/// impl Model for MyModel {
-///     fn init() -> (Self, Option<command::Cmd>) {
-///         let model = Self { counter: 0 };
-///         // Start a timer that fires after 1 second
-///         let cmd = command::tick(Duration::from_secs(1), |_| {
-///             Box::new(TickMsg) as Msg
-///         });
-///         (model, Some(cmd))
+/// impl Model for Wizard {
+///     fn init() -> (Self, Option<Cmd>) {
+///         let cmd = command::sequence_with_progress(
+///             vec![command::clear_screen(), command::hide_cursor()],
+///             |index, total| Box::new(StepMsg(index, total)) as Msg,
+///         );
+///         (Self { status: String::new() }, Some(cmd))
 ///     }
 ///
-///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
-///         if msg.downcast_ref::<TickMsg>().is_some() {
-///             self.counter += 1;
-///             // Re-arm the timer for another tick
-///             return Some(command::tick(Duration::from_secs(1), |_| {
-///                 Box::new(TickMsg) as Msg
-///             }));
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(StepMsg(index, total)) = msg.downcast_ref::<StepMsg>() {
+///             self.status = format!("Step {} of {}", index + 1, total);
 ///         }
-///         None
 ///     }
-///     
+///
 ///     fn view(&self) -> String {
-///         format!("Counter: {}", self.counter)
+///         self.status.clone()
 ///     }
 /// }
 /// ```
-pub fn tick<F>(duration: Duration, f: F) -> Cmd
+pub fn sequence_with_progress<F>(cmds: Vec<Cmd>, on_step: F) -> Cmd
 where
-    F: Fn(Duration) -> Msg + Send + 'static,
+    F: Fn(usize, usize) -> Msg + Send + 'static,
 {
     Box::pin(async move {
-        let mut ticker = interval(duration);
-        // The first tick completes immediately; advance once to move to the start
-        ticker.tick().await; // consume the immediate tick
-                             // Now wait for one full duration before emitting
-        ticker.tick().await;
-        Some(f(duration))
+        Some(Box::new(crate::event::SequenceMsgInternal {
+            steps: cmds,
+            on_step: Box::new(on_step),
+        }) as Msg)
     })
 }
 
-/// Creates a command that produces messages repeatedly at a regular interval.
+/// Creates a command that runs commands concurrently and delivers one
+/// message once all of them have completed.
 ///
-/// This command will continuously send messages produced by the provided closure `f`
-/// after every `duration` until the program exits or the timer is cancelled.
-/// Unlike `tick()`, this creates a persistent timer that keeps firing.
+/// This is the join to [`batch`]'s fork: where `batch` delivers each
+/// command's message on its own, `after_all` collects them into a `Vec<Msg>`
+/// in the order of `cmds`, whatever order they finish in, and delivers the
+/// message returned by `on_complete`. A command that produces no message
+/// contributes a [`NoMsg`](crate::NoMsg) so positions still line up.
 ///
-/// Warning: Be careful not to call `every()` repeatedly for the same timer,
-/// as this will create multiple concurrent timers that can overwhelm the
-/// event loop. Instead, call it once and use `cancel_timer()` if needed.
+/// The collected messages go to `on_complete` instead of the program, so
+/// control messages such as [`quit`] inside the set have no effect.
 ///
 /// # Arguments
 ///
-/// * `duration` - The duration between messages
-/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+/// * `cmds` - The commands to execute concurrently
+/// * `on_complete` - Builds the aggregated message from the collected ones
 ///
 /// # Returns
 ///
-/// A command that will produce messages repeatedly at the specified interval
+/// A command that completes when the slowest of `cmds` does
 ///
 /// # Examples
 ///
 /// ```
-/// use bubbletea_rs::{command, Model, Msg};
-/// use std::time::Duration;
+/// use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg};
 ///
 /// #[derive(Debug)]
-/// struct ClockTickMsg;
+/// struct LoadedMsg(&'static str);
 ///
-/// struct MyModel {
-///     time_elapsed: Duration,
+/// #[derive(Debug)]
+/// struct AllLoadedMsg(Vec<Msg>);
+///
+/// fn load(name: &'static str) -> Cmd {
+///     Box::pin(async move { Some(Box::new(LoadedMsg(name)) as Msg) })
 /// }
 ///
-/// impl Model for MyModel {
-///     fn init() -> (Self, Option<command::Cmd>) {
-///         let model = Self { time_elapsed: Duration::from_secs(0) };
-///         // Start a timer that fires every second
-///         let cmd = command::every(Duration::from_secs(1), |_| {
-///             Box::new(ClockTickMsg) as Msg
+/// struct Dashboard {
+///     ready: bool,
+/// }
+///
+/// impl Model for Dashboard {
+///     fn init() -> (Self, Option<Cmd>) {
+///         let cmd = command::after_all(vec![load("users"), load("orders")], |msgs| {
+///             Box::new(AllLoadedMsg(msgs)) as Msg
 ///         });
-///         (model, Some(cmd))
+///         (Self { ready: false }, Some(cmd))
 ///     }
 ///
-///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
-///         if msg.downcast_ref::<ClockTickMsg>().is_some() {
-///             self.time_elapsed += Duration::from_secs(1);
-///             // No need to re-arm - it keeps firing automatically
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if msg.is::<AllLoadedMsg>() {
+///             self.ready = true;
 ///         }
-///         None
 ///     }
-///     
+///
 ///     fn view(&self) -> String {
-///         format!("Time elapsed: {:?}", self.time_elapsed)
+///         if self.ready { "Ready".into() } else { "Loading...".into() }
 ///     }
 /// }
 /// ```
-pub fn every<F>(duration: Duration, f: F) -> Cmd
+pub fn after_all<F>(cmds: Vec<Cmd>, on_complete: F) -> Cmd
 where
-    F: Fn(Duration) -> Msg + Send + 'static,
+    F: Fn(Vec<Msg>) -> Msg + Send + 'static,
 {
-    let timer_id = next_timer_id();
-    let cancellation_token = CancellationToken::new();
-
     Box::pin(async move {
-        Some(Box::new(crate::event::EveryMsgInternal {
-            duration,
-            func: Box::new(f),
-            cancellation_token,
-            timer_id,
-        }) as Msg)
+        let msgs = futures::future::join_all(cmds)
+            .await
+            .into_iter()
+            .map(|msg| msg.unwrap_or_else(|| Box::new(crate::event::NoMsg)))
+            .collect();
+        Some(on_complete(msgs))
     })
 }
 
-/// Creates a command that produces messages repeatedly at a regular interval with cancellation support.
+/// Creates a command that runs commands at most `concurrency` at a time and
+/// delivers one message once all of them have completed.
 ///
-/// This command will continuously send messages produced by the provided closure `f`
-/// after every `duration` until the program exits or the timer is cancelled.
-/// The returned timer ID can be used with `cancel_timer()` to stop the timer.
+/// Combines [`after_all`] with the limit of [`batch_limited`]: the messages
+/// are collected in the order of `cmds` and passed to `on_complete`, but
+/// no more than `concurrency` commands run at once. A `concurrency` of zero
+/// is treated as one.
 ///
 /// # Arguments
 ///
-/// * `duration` - The duration between messages
-/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+/// * `concurrency` - How many commands may run at once
+/// * `cmds` - The commands to execute
+/// * `on_complete` - Builds the aggregated message from the collected ones
 ///
 /// # Returns
 ///
-/// Returns a tuple containing:
-/// - The command to start the timer
-/// - A timer ID that can be used with `cancel_timer()`
+/// A command that completes when the last of `cmds` does
 ///
 /// # Examples
 ///
 /// ```
-/// use bubbletea_rs::{command, Model, Msg};
-/// use std::time::Duration;
+/// use bubbletea_rs::{command, Cmd, Msg};
 ///
 /// #[derive(Debug)]
-/// struct AnimationFrameMsg;
-///
-/// #[derive(Debug)]
-/// struct StartAnimationMsg(u64); // Contains timer ID
+/// struct AllFetchedMsg(Vec<Msg>);
 ///
-/// struct MyModel {
-///     animation_timer_id: Option<u64>,
-///     is_animating: bool,
+/// fn fetch(page: u32) -> Cmd {
+///     Box::pin(async move { Some(Box::new(page) as Msg) })
 /// }
 ///
-/// impl Model for MyModel {
-///     fn init() -> (Self, Option<command::Cmd>) {
-///         let model = Self {
-///             animation_timer_id: None,
-///             is_animating: false,
+/// let cmd = command::after_all_limited(8, (0..200).map(fetch).collect(), |msgs| {
+///     Box::new(AllFetchedMsg(msgs)) as Msg
+/// });
+/// ```
+pub fn after_all_limited<F>(concurrency: usize, cmds: Vec<Cmd>, on_complete: F) -> Cmd
+where
+    F: Fn(Vec<Msg>) -> Msg + Send + 'static,
+{
+    use futures::StreamExt;
+
+    Box::pin(async move {
+        let msgs = futures::stream::iter(cmds)
+            .buffered(concurrency.max(1))
+            .map(|msg| msg.unwrap_or_else(|| Box::new(crate::event::NoMsg)))
+            .collect()
+            .await;
+        Some(on_complete(msgs))
+    })
+}
+
+/// Creates a command that builds the real command only when it runs.
+///
+/// `factory` is not called until the program first polls the command, so
+/// the command it returns sees the state as it is then rather than when
+/// `lazy` was called. Inside a [`sequence`] that is when the step's turn
+/// comes, which can be long after the sequence was created. The state must
+/// be shared with the factory, for example through an `Arc`.
+///
+/// # Arguments
+///
+/// * `factory` - Creates the command to run
+///
+/// # Returns
+///
+/// A command that produces whatever message the created command does
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, Msg};
+/// use std::sync::atomic::{AtomicU16, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct BarWidthMsg(u16);
+///
+/// fn redraw_bar(width: Arc<AtomicU16>) -> Cmd {
+///     command::sequence(vec![
+///         command::tick(Duration::from_secs(1), |_| Box::new(()) as Msg),
+///         // Reads the width after the tick, not when the sequence is built
+///         command::lazy(move || {
+///             let width = width.load(Ordering::Relaxed);
+///             Box::pin(async move { Some(Box::new(BarWidthMsg(width)) as Msg) })
+///         }),
+///     ])
+/// }
+/// ```
+pub fn lazy<F>(factory: F) -> Cmd
+where
+    F: FnOnce() -> Cmd + Send + 'static,
+{
+    Box::pin(async move { factory().await })
+}
+
+/// Creates a command that produces a single message after a delay.
+///
+/// This command will send a message produced by the provided closure `f`
+/// after the specified `duration`. Unlike `every()`, this produces only
+/// one message and then completes. It's commonly used for one-shot timers
+/// that can be re-armed in the update method.
+///
+/// The delay is measured on the program's
+/// [`clock`](crate::ProgramBuilder::clock), if one is set.
+///
+/// Note: Due to tokio's interval implementation, the first tick is consumed
+/// to ensure the message is sent after a full duration, not immediately.
+///
+/// # Arguments
+///
+/// * `duration` - The duration to wait before sending the message
+/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+///
+/// # Returns
+///
+/// A command that will produce a single message after the specified duration
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct TickMsg;
+///
+/// struct MyModel {
+///     counter: u32,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let model = Self { counter: 0 };
+///         // Start a timer that fires after 1 second
+///         let cmd = command::tick(Duration::from_secs(1), |_| {
+///             Box::new(TickMsg) as Msg
+///         });
+///         (model, Some(cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if msg.downcast_ref::<TickMsg>().is_some() {
+///             self.counter += 1;
+///             // Re-arm the timer for another tick
+///             return Some(command::tick(Duration::from_secs(1), |_| {
+///                 Box::new(TickMsg) as Msg
+///             }));
+///         }
+///         None
+///     }
+///     
+///     fn view(&self) -> String {
+///         format!("Counter: {}", self.counter)
+///     }
+/// }
+/// ```
+pub fn tick<F>(duration: Duration, f: F) -> Cmd
+where
+    F: Fn(Duration) -> Msg + Send + 'static,
+{
+    Box::pin(async move {
+        crate::clock::sleep(duration).await;
+        Some(f(duration))
+    })
+}
+
+/// Creates a command that produces messages repeatedly at a regular interval.
+///
+/// This command will continuously send messages produced by the provided closure `f`
+/// after every `duration` until the program exits or the timer is cancelled.
+/// Unlike `tick()`, this creates a persistent timer that keeps firing.
+///
+/// Warning: Be careful not to call `every()` repeatedly for the same timer,
+/// as this will create multiple concurrent timers that can overwhelm the
+/// event loop. Instead, call it once and use `cancel_timer()` if needed.
+///
+/// # Arguments
+///
+/// * `duration` - The duration between messages
+/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+///
+/// # Returns
+///
+/// A command that will produce messages repeatedly at the specified interval
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct ClockTickMsg;
+///
+/// struct MyModel {
+///     time_elapsed: Duration,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let model = Self { time_elapsed: Duration::from_secs(0) };
+///         // Start a timer that fires every second
+///         let cmd = command::every(Duration::from_secs(1), |_| {
+///             Box::new(ClockTickMsg) as Msg
+///         });
+///         (model, Some(cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if msg.downcast_ref::<ClockTickMsg>().is_some() {
+///             self.time_elapsed += Duration::from_secs(1);
+///             // No need to re-arm - it keeps firing automatically
+///         }
+///         None
+///     }
+///     
+///     fn view(&self) -> String {
+///         format!("Time elapsed: {:?}", self.time_elapsed)
+///     }
+/// }
+/// ```
+pub fn every<F>(duration: Duration, f: F) -> Cmd
+where
+    F: Fn(Duration) -> Msg + Send + 'static,
+{
+    every_with_behavior(duration, MissedTickBehavior::Skip, f)
+}
+
+/// Creates a recurring timer with an explicit missed-tick behavior.
+///
+/// `every()` uses `MissedTickBehavior::Skip`: if the event loop falls behind
+/// (for example because `update()` blocked for several intervals), the
+/// missed ticks are dropped and a single message is delivered before the
+/// timer realigns to its schedule. Use this function to opt into
+/// `MissedTickBehavior::Burst`, which catches up by firing every missed tick
+/// back to back, or `MissedTickBehavior::Delay`, which restarts the interval
+/// from the moment the late tick fired.
+///
+/// # Arguments
+///
+/// * `duration` - The duration between messages
+/// * `behavior` - How the timer reacts when ticks are missed
+/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+///
+/// # Returns
+///
+/// A command that will produce messages repeatedly at the specified interval
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Msg};
+/// use std::time::Duration;
+/// use tokio::time::MissedTickBehavior;
+///
+/// #[derive(Debug)]
+/// struct SampleMsg;
+///
+/// // Every sample matters, so deliver missed ticks instead of dropping them
+/// let cmd = command::every_with_behavior(
+///     Duration::from_millis(100),
+///     MissedTickBehavior::Burst,
+///     |_| Box::new(SampleMsg) as Msg,
+/// );
+/// ```
+pub fn every_with_behavior<F>(duration: Duration, behavior: MissedTickBehavior, f: F) -> Cmd
+where
+    F: Fn(Duration) -> Msg + Send + 'static,
+{
+    let timer_id = next_timer_id();
+    let cancellation_token = CancellationToken::new();
+
+    Box::pin(async move {
+        Some(Box::new(crate::event::EveryMsgInternal {
+            duration,
+            func: Box::new(f),
+            cancellation_token,
+            timer_id,
+            missed_tick_behavior: behavior,
+            group: None,
+        }) as Msg)
+    })
+}
+
+/// Creates a command that produces messages repeatedly at a regular interval with cancellation support.
+///
+/// This command will continuously send messages produced by the provided closure `f`
+/// after every `duration` until the program exits or the timer is cancelled.
+/// The returned timer ID can be used with `cancel_timer()` to stop the timer.
+///
+/// # Arguments
+///
+/// * `duration` - The duration between messages
+/// * `f` - A closure that takes a `Duration` and returns a `Msg`
+///
+/// # Returns
+///
+/// Returns a tuple containing:
+/// - The command to start the timer
+/// - A timer ID that can be used with `cancel_timer()`
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct AnimationFrameMsg;
+///
+/// #[derive(Debug)]
+/// struct StartAnimationMsg(u64); // Contains timer ID
+///
+/// struct MyModel {
+///     animation_timer_id: Option<u64>,
+///     is_animating: bool,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let model = Self {
+///             animation_timer_id: None,
+///             is_animating: false,
 ///         };
 ///         // Start animation timer and get its ID
 ///         let (cmd, timer_id) = command::every_with_id(
@@ -475,98 +1052,696 @@ where
 /// ```
 pub fn every_with_id<F>(duration: Duration, f: F) -> (Cmd, u64)
 where
-    F: Fn(Duration) -> Msg + Send + 'static,
+    F: Fn(Duration) -> Msg + Send + 'static,
+{
+    let timer_id = next_timer_id();
+    let cancellation_token = CancellationToken::new();
+
+    let cmd = Box::pin(async move {
+        Some(Box::new(crate::event::EveryMsgInternal {
+            duration,
+            func: Box::new(f),
+            cancellation_token,
+            timer_id,
+            missed_tick_behavior: MissedTickBehavior::Skip,
+            group: None,
+        }) as Msg)
+    });
+
+    (cmd, timer_id)
+}
+
+/// Creates a one-shot timer like [`tick`] that belongs to a timer group.
+///
+/// While the group is paused with [`pause_timer_group`], a tick that fires
+/// holds its message; it is delivered when the group is resumed with
+/// [`resume_timer_group`]. Group ids are chosen by the application, and a
+/// group exists as soon as a timer or command names it.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, BlurMsg, Cmd, FocusMsg, IntoCmd, Model, Msg};
+/// use std::time::Duration;
+///
+/// const ANIMATIONS: u64 = 1;
+///
+/// #[derive(Debug)]
+/// struct FrameMsg;
+///
+/// fn frame() -> Cmd {
+///     command::tick_in_group(ANIMATIONS, Duration::from_millis(16), |_| {
+///         Box::new(FrameMsg) as Msg
+///     })
+/// }
+///
+/// struct Spinner {
+///     frame: usize,
+/// }
+///
+/// impl Model for Spinner {
+///     fn init() -> (Self, Option<Cmd>) {
+///         (Self { frame: 0 }, Some(frame()))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if msg.is::<BlurMsg>() {
+///             return Some(command::pause_timer_group(ANIMATIONS));
+///         }
+///         if msg.is::<FocusMsg>() {
+///             return Some(command::resume_timer_group(ANIMATIONS));
+///         }
+///         if msg.is::<FrameMsg>() {
+///             self.frame += 1;
+///             return Some(frame());
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         ["|", "/", "-", "\\"][self.frame % 4].to_string()
+///     }
+/// }
+/// ```
+pub fn tick_in_group<F>(group: u64, duration: Duration, f: F) -> Cmd
+where
+    F: Fn(Duration) -> Msg + Send + 'static,
+{
+    let tick = tick(duration, f);
+    Box::pin(async move {
+        let msg = tick.await?;
+        Some(Box::new(crate::event::GroupedTickMsg { group, msg }) as Msg)
+    })
+}
+
+/// Creates a recurring timer like [`every`] that belongs to a timer group.
+///
+/// While the group is paused with [`pause_timer_group`] the timer does not
+/// fire, and ticks missed during the pause are not made up: after
+/// [`resume_timer_group`] the next tick comes one full `duration` later.
+/// Returns the command and the timer's id, for [`cancel_timer`].
+pub fn every_in_group<F>(group: u64, duration: Duration, f: F) -> (Cmd, u64)
+where
+    F: Fn(Duration) -> Msg + Send + 'static,
+{
+    let timer_id = next_timer_id();
+    let cancellation_token = CancellationToken::new();
+
+    let cmd = Box::pin(async move {
+        Some(Box::new(crate::event::EveryMsgInternal {
+            duration,
+            func: Box::new(f),
+            cancellation_token,
+            timer_id,
+            missed_tick_behavior: MissedTickBehavior::Skip,
+            group: Some(group),
+        }) as Msg)
+    });
+
+    (cmd, timer_id)
+}
+
+/// Creates a command that produces messages at a target frame rate.
+///
+/// This is a frame-rate oriented variant of `every()`. The interval is
+/// derived from `target_fps` (for example, `60.0` gives a 16.67ms interval)
+/// and each frame is scheduled against an absolute deadline, so fractional
+/// intervals do not drift over time. The closure receives the `Instant` at
+/// which the frame fired.
+///
+/// Tokio timers have roughly 1ms resolution on Linux and can be much coarser
+/// on other platforms. When `ProgramBuilder::high_precision_timer(true)` is
+/// set, the timer sleeps until shortly before each deadline and then
+/// spin-waits for the remainder, giving sub-millisecond accuracy at the cost
+/// of keeping a CPU core busy for about a millisecond per frame. Without it,
+/// frames are scheduled with regular timers.
+///
+/// Use `cancel_all_timers()` or `every_precise_with_id()` with
+/// `cancel_timer()` to stop the timer.
+///
+/// # Arguments
+///
+/// * `target_fps` - The desired number of frames per second. Must be positive.
+/// * `f` - A closure that takes the frame `Instant` and returns a `Msg`
+///
+/// # Panics
+///
+/// Panics if `target_fps` is not a positive, finite number.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+/// use std::time::Instant;
+///
+/// #[derive(Debug)]
+/// struct FrameMsg(Instant);
+///
+/// struct MyModel {
+///     last_frame: Option<Instant>,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let cmd = command::every_precise(60.0, |at| Box::new(FrameMsg(at)) as Msg);
+///         (Self { last_frame: None }, Some(cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(FrameMsg(at)) = msg.downcast_ref::<FrameMsg>() {
+///             self.last_frame = Some(*at);
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         format!("Last frame: {:?}", self.last_frame)
+///     }
+/// }
+/// ```
+pub fn every_precise<F>(target_fps: f64, f: F) -> Cmd
+where
+    F: Fn(Instant) -> Msg + Send + 'static,
+{
+    every_precise_with_id(target_fps, f).0
+}
+
+/// Creates a command that produces messages at a target frame rate, with
+/// cancellation support.
+///
+/// This behaves like `every_precise()` but also returns a timer ID that can
+/// be passed to `cancel_timer()`.
+///
+/// # Arguments
+///
+/// * `target_fps` - The desired number of frames per second. Must be positive.
+/// * `f` - A closure that takes the frame `Instant` and returns a `Msg`
+///
+/// # Returns
+///
+/// Returns a tuple containing:
+/// - The command to start the timer
+/// - A timer ID that can be used with `cancel_timer()`
+///
+/// # Panics
+///
+/// Panics if `target_fps` is not a positive, finite number.
+pub fn every_precise_with_id<F>(target_fps: f64, f: F) -> (Cmd, u64)
+where
+    F: Fn(Instant) -> Msg + Send + 'static,
+{
+    assert!(
+        target_fps.is_finite() && target_fps > 0.0,
+        "target_fps must be a positive, finite number"
+    );
+    let interval = Duration::from_secs_f64(1.0 / target_fps);
+    let timer_id = next_timer_id();
+    let cancellation_token = CancellationToken::new();
+
+    let cmd = Box::pin(async move {
+        Some(Box::new(crate::event::EveryPreciseMsgInternal {
+            interval,
+            func: Box::new(f),
+            cancellation_token,
+            timer_id,
+        }) as Msg)
+    });
+
+    (cmd, timer_id)
+}
+
+/// Spin threshold used by `sleep_until_precise` in high precision mode.
+///
+/// Tokio rounds sleeps up to its 1ms timer tick, so the coarse sleep has to
+/// end one tick earlier than the 1ms we actually want to spin for.
+const PRECISE_SPIN_WINDOW: Duration = Duration::from_millis(2);
+
+/// Waits until `deadline`, optionally spin-waiting for the final millisecond.
+///
+/// With `spin` disabled this is equivalent to `tokio::time::sleep_until`.
+/// With `spin` enabled the task sleeps until `PRECISE_SPIN_WINDOW` before the
+/// deadline and spins for the rest, trading CPU time for accuracy. The spin
+/// yields to the runtime between checks, so it never holds a worker thread,
+/// and it stops early once `shutdown` is cancelled.
+///
+/// Deadlines are on the program's clock, and a clock set on the program is
+/// never spun on.
+pub(crate) async fn sleep_until_precise(
+    deadline: Instant,
+    spin: bool,
+    shutdown: &CancellationToken,
+) {
+    if !spin || crate::clock::current().is_some() {
+        crate::clock::sleep_until(deadline).await;
+        return;
+    }
+    if let Some(coarse) = deadline.checked_sub(PRECISE_SPIN_WINDOW) {
+        crate::runtime::sleep_until(coarse).await;
+    }
+    while crate::clock::now() < deadline && !shutdown.is_cancelled() {
+        crate::runtime::yield_now().await;
+    }
+}
+
+/// Drives a timer started with `every()` until it is cancelled.
+///
+/// The first message arrives one full `duration` after start.
+pub(crate) async fn run_every_timer(
+    duration: Duration,
+    behavior: MissedTickBehavior,
+    func: Box<dyn Fn(Duration) -> Msg + Send>,
+    cancellation_token: CancellationToken,
+    event_tx: crate::event::EventSender,
+    mut paused: watch::Receiver<bool>,
+    mut group: Option<watch::Receiver<bool>>,
+) {
+    let mut ticker = crate::clock::Ticker::new(duration, behavior);
+
+    loop {
+        tokio::select! {
+            _ = cancellation_token.cancelled() => {
+                // Timer was cancelled
+                break;
+            }
+            _ = ticker.tick() => {
+                let group_paused = group.as_ref().is_some_and(|group| *group.borrow());
+                if *paused.borrow() || group_paused {
+                    if !wait_until_running(&mut paused, group.as_mut(), &cancellation_token).await {
+                        break;
+                    }
+                    // Start a fresh period instead of firing right away
+                    ticker.reset();
+                    continue;
+                }
+                let msg = func(duration);
+                if event_tx.send(msg).is_err() {
+                    break; // Receiver dropped
+                }
+            }
+        }
+    }
+}
+
+/// Waits while the program, or the timer's group, has timers paused.
+///
+/// Returns `false` if the timer was cancelled or the program is gone.
+pub(crate) async fn wait_until_running(
+    paused: &mut watch::Receiver<bool>,
+    mut group: Option<&mut watch::Receiver<bool>>,
+    cancellation_token: &CancellationToken,
+) -> bool {
+    loop {
+        if !wait_until_resumed(paused, cancellation_token).await {
+            return false;
+        }
+        match group.as_deref_mut() {
+            Some(group) if *group.borrow() => {
+                if !wait_until_resumed(group, cancellation_token).await {
+                    return false;
+                }
+            }
+            _ => return true,
+        }
+    }
+}
+
+/// Waits while the program has recurring timers paused.
+///
+/// Returns `false` if the timer was cancelled or the program is gone.
+pub(crate) async fn wait_until_resumed(
+    paused: &mut watch::Receiver<bool>,
+    cancellation_token: &CancellationToken,
+) -> bool {
+    tokio::select! {
+        _ = cancellation_token.cancelled() => false,
+        resumed = paused.wait_for(|paused| !*paused) => resumed.is_ok(),
+    }
+}
+
+/// Asks the running program to release the terminal, returning once it has.
+///
+/// Returns `false` if no program is running to take part.
+async fn release_terminal_for_exec() -> bool {
+    let Some(sender) = crate::event::EVENT_SENDER.get() else {
+        return false;
+    };
+    if crate::event::RUNNING_EVENT_LOOPS.load(std::sync::atomic::Ordering::SeqCst) == 0 {
+        return false;
+    }
+    let (released, reply) = tokio::sync::oneshot::channel();
+    let msg = crate::event::ExecStartMsg { released };
+    sender.send(Box::new(msg)).is_ok() && reply.await.is_ok()
+}
+
+/// Gives the terminal back to the program after `release_terminal_for_exec`,
+/// returning once it has redrawn.
+async fn restore_terminal_after_exec() {
+    if let Some(sender) = crate::event::EVENT_SENDER.get() {
+        let (restored, reply) = tokio::sync::oneshot::channel();
+        let msg = crate::event::ExecEndMsg { restored };
+        if sender.send(Box::new(msg)).is_ok() {
+            let _ = reply.await;
+        }
+    }
+}
+
+/// Applies the configured environment variables, if any, to `cmd`.
+pub(crate) fn apply_command_env(cmd: &mut StdCommand) {
+    if let Some(env) = COMMAND_ENV.get() {
+        for (k, v) in env.iter() {
+            cmd.env(k, v);
+        }
+    }
+}
+
+/// Creates a command that executes an external process.
+///
+/// This command spawns an external process asynchronously and returns a message
+/// produced by the provided closure with the process's output. The process runs
+/// in the background and doesn't block the UI.
+///
+/// While the process runs, the program leaves the terminal alone: it
+/// releases the terminal as [`Program::release_terminal`](crate::Program::release_terminal)
+/// does, draws nothing, and pauses recurring timers such as those started by
+/// [`every`]. Messages that arrive in the meantime still reach the model.
+/// When the process exits, the program restores the terminal and resumes
+/// the timers, then delivers the closure's message and redraws the view in
+/// full. To decide what to run inside a command of your own, return an
+/// [`ExecRequestMsg`](crate::ExecRequestMsg) instead.
+///
+/// # Arguments
+///
+/// * `cmd` - The `std::process::Command` to execute
+/// * `f` - A closure that processes the command output and returns a `Msg`
+///
+/// # Returns
+///
+/// A command that executes the external process
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+/// use std::process::Command;
+///
+/// #[derive(Debug)]
+/// struct GitStatusMsg(String);
+///
+/// struct MyModel {
+///     git_status: String,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let model = Self { git_status: String::new() };
+///         // Run git status command
+///         let mut cmd = Command::new("git");
+///         cmd.arg("status").arg("--short");
+///         
+///         let exec_cmd = command::exec_process(cmd, |result| {
+///             match result {
+///                 Ok(output) => {
+///                     let status = String::from_utf8_lossy(&output.stdout).to_string();
+///                     Box::new(GitStatusMsg(status)) as Msg
+///                 }
+///                 Err(e) => {
+///                     Box::new(GitStatusMsg(format!("Error: {}", e))) as Msg
+///                 }
+///             }
+///         });
+///         (model, Some(exec_cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(GitStatusMsg(status)) = msg.downcast_ref::<GitStatusMsg>() {
+///             self.git_status = status.clone();
+///         }
+///         None
+///     }
+///     
+///     fn view(&self) -> String {
+///         format!("Git status:\n{}", self.git_status)
+///     }
+/// }
+/// ```
+pub fn exec_process<F>(cmd: StdCommand, f: F) -> Cmd
+where
+    F: Fn(Result<std::process::Output, std::io::Error>) -> Msg + Send + 'static,
+{
+    Box::pin(async move {
+        let mut cmd = cmd;
+        apply_command_env(&mut cmd);
+        let released = release_terminal_for_exec().await;
+        let output = crate::runtime::output(cmd).await;
+        if released {
+            restore_terminal_after_exec().await;
+        }
+        Some(f(output))
+    })
+}
+
+/// Creates a command that runs CPU-bound work on a blocking thread.
+///
+/// Ordinary commands run on the async runtime's worker threads, so a heavy
+/// synchronous computation inside one (syntax highlighting a large file,
+/// fuzzy-matching thousands of entries) stalls input handling and rendering.
+/// This command runs `f` with `tokio::task::spawn_blocking` instead and
+/// delivers the message it returns.
+///
+/// # Arguments
+///
+/// * `f` - Closure that performs the work and produces a message
+///
+/// # Panics
+///
+/// A panic inside `f` does not take down the program. The model receives an
+/// `ErrorMsg` holding `Error::ProgramPanic` with the panic message instead,
+/// whether or not `catch_panics` is enabled. The program's panic hook skips
+/// these panics, so each one is reported exactly once.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+///
+/// #[derive(Debug)]
+/// struct PrimesMsg(usize);
+///
+/// struct MyModel;
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let cmd = command::blocking(|| {
+///             let count = (2..50_000u32)
+///                 .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+///                 .count();
+///             Box::new(PrimesMsg(count)) as Msg
+///         });
+///         (Self, Some(cmd))
+///     }
+///
+///     fn update(&mut self, _msg: Msg) -> Option<command::Cmd> {
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         "Counting primes...".to_string()
+///     }
+/// }
+/// ```
+pub fn blocking<F>(f: F) -> Cmd
+where
+    F: FnOnce() -> Msg + Send + 'static,
+{
+    Box::pin(async move {
+        let msg = match spawn_blocking_command(f).await? {
+            Ok(msg) => msg,
+            Err(panic) => Box::new(crate::event::ErrorMsg(panic)) as Msg,
+        };
+        Some(msg)
+    })
+}
+
+/// Creates a command that runs fallible CPU-bound work on a blocking thread.
+///
+/// This is the fallible counterpart of [`blocking`]. If `f` returns
+/// `Ok(msg)`, `msg` is delivered to the model. If it returns an error, or
+/// panics, the model receives an `ErrorMsg` instead: errors are wrapped in
+/// `Error::CommandExecution` and panics in `Error::ProgramPanic`. As with
+/// [`blocking`], a panic is reported only through this `ErrorMsg`.
+///
+/// # Arguments
+///
+/// * `f` - Closure that performs the work and produces a message or an error
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, ErrorMsg, Model, Msg};
+///
+/// #[derive(Debug)]
+/// struct ParsedMsg(Vec<i64>);
+///
+/// struct MyModel {
+///     error: Option<String>,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let cmd = command::try_blocking(|| {
+///             let numbers = "1 2 x"
+///                 .split_whitespace()
+///                 .map(str::parse)
+///                 .collect::<Result<Vec<i64>, _>>()?;
+///             Ok::<_, std::num::ParseIntError>(Box::new(ParsedMsg(numbers)) as Msg)
+///         });
+///         (Self { error: None }, Some(cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(ErrorMsg(err)) = msg.downcast_ref::<ErrorMsg>() {
+///             self.error = Some(err.to_string());
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         self.error.clone().unwrap_or_default()
+///     }
+/// }
+/// ```
+pub fn try_blocking<F, E>(f: F) -> Cmd
+where
+    F: FnOnce() -> Result<Msg, E> + Send + 'static,
+    E: std::fmt::Display + Send + 'static,
+{
+    Box::pin(async move {
+        let error = match spawn_blocking_command(f).await? {
+            Ok(Ok(msg)) => return Some(msg),
+            Ok(Err(e)) => crate::Error::CommandExecution(e.to_string()),
+            Err(panic) => panic,
+        };
+        Some(Box::new(crate::event::ErrorMsg(error)) as Msg)
+    })
+}
+
+thread_local! {
+    /// Whether this thread is running the closure of a blocking command.
+    static IN_BLOCKING_COMMAND: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Returns whether the current thread is running the closure of a
+/// [`blocking`] or [`try_blocking`] command.
+///
+/// The program's panic hook skips these panics: the command reports them
+/// itself.
+pub(crate) fn in_blocking_command() -> bool {
+    IN_BLOCKING_COMMAND.with(|flag| flag.get())
+}
+
+/// Returns the message a panic was raised with.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<unknown panic>".to_string()
+    }
+}
+
+/// Runs `f` on a blocking thread, turning a panic into `Error::ProgramPanic`.
+///
+/// Returns `None` if the task was cancelled during runtime shutdown.
+async fn spawn_blocking_command<F, T>(f: F) -> Option<Result<T, crate::Error>>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
 {
-    let timer_id = next_timer_id();
-    let cancellation_token = CancellationToken::new();
-
-    let cmd = Box::pin(async move {
-        Some(Box::new(crate::event::EveryMsgInternal {
-            duration,
-            func: Box::new(f),
-            cancellation_token,
-            timer_id,
-        }) as Msg)
-    });
+    /// Clears the flag when `f` returns or unwinds; blocking threads are reused.
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IN_BLOCKING_COMMAND.with(|flag| flag.set(false));
+        }
+    }
 
-    (cmd, timer_id)
+    let result = crate::runtime::spawn_blocking(move || {
+        IN_BLOCKING_COMMAND.with(|flag| flag.set(true));
+        let _reset = Reset;
+        f()
+    })
+    .await?;
+    match result {
+        Ok(value) => Some(Ok(value)),
+        Err(payload) => Some(Err(crate::Error::ProgramPanic(panic_message(&*payload)))),
+    }
 }
 
-/// Creates a command that executes an external process.
+/// Creates a command that writes a file without blocking the event loop.
 ///
-/// This command spawns an external process asynchronously and returns a message
-/// produced by the provided closure with the process's output. The process runs
-/// in the background and doesn't block the UI.
+/// `contents` is written to `path` off the event loop, creating the
+/// file if it does not exist and replacing its contents if it does. When
+/// the write finishes, `on_done` is called with the result and the message
+/// it returns is delivered to the model, so the UI stays responsive while a
+/// large buffer is saved.
 ///
 /// # Arguments
 ///
-/// * `cmd` - The `std::process::Command` to execute
-/// * `f` - A closure that processes the command output and returns a `Msg`
-///
-/// # Returns
-///
-/// A command that executes the external process
+/// * `path` - The file to write
+/// * `contents` - The bytes to write
+/// * `on_done` - Turns the result of the write into a message
 ///
 /// # Examples
 ///
 /// ```
 /// use bubbletea_rs::{command, Model, Msg};
-/// use std::process::Command;
 ///
 /// #[derive(Debug)]
-/// struct GitStatusMsg(String);
+/// struct SavedMsg(Result<(), String>);
 ///
-/// struct MyModel {
-///     git_status: String,
+/// struct Editor {
+///     buffer: String,
+///     status: String,
 /// }
 ///
-/// impl Model for MyModel {
+/// impl Model for Editor {
 ///     fn init() -> (Self, Option<command::Cmd>) {
-///         let model = Self { git_status: String::new() };
-///         // Run git status command
-///         let mut cmd = Command::new("git");
-///         cmd.arg("status").arg("--short");
-///         
-///         let exec_cmd = command::exec_process(cmd, |result| {
-///             match result {
-///                 Ok(output) => {
-///                     let status = String::from_utf8_lossy(&output.stdout).to_string();
-///                     Box::new(GitStatusMsg(status)) as Msg
-///                 }
-///                 Err(e) => {
-///                     Box::new(GitStatusMsg(format!("Error: {}", e))) as Msg
-///                 }
-///             }
-///         });
-///         (model, Some(exec_cmd))
+///         let editor = Self {
+///             buffer: "hello".to_string(),
+///             status: String::new(),
+///         };
+///         let save = command::write_file(
+///             std::env::temp_dir().join("buffer.txt"),
+///             editor.buffer.clone().into_bytes(),
+///             |result| Box::new(SavedMsg(result.map_err(|e| e.to_string()))) as Msg,
+///         );
+///         (editor, Some(save))
 ///     }
 ///
 ///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
-///         if let Some(GitStatusMsg(status)) = msg.downcast_ref::<GitStatusMsg>() {
-///             self.git_status = status.clone();
+///         if let Some(SavedMsg(result)) = msg.downcast_ref::<SavedMsg>() {
+///             self.status = match result {
+///                 Ok(()) => "Saved".to_string(),
+///                 Err(e) => format!("Save failed: {e}"),
+///             };
 ///         }
 ///         None
 ///     }
-///     
+///
 ///     fn view(&self) -> String {
-///         format!("Git status:\n{}", self.git_status)
+///         format!("{}\n{}", self.buffer, self.status)
 ///     }
 /// }
 /// ```
-pub fn exec_process<F>(cmd: StdCommand, f: F) -> Cmd
+pub fn write_file<P, F>(path: P, contents: Vec<u8>, on_done: F) -> Cmd
 where
-    F: Fn(Result<std::process::Output, std::io::Error>) -> Msg + Send + 'static,
+    P: AsRef<std::path::Path> + Send + 'static,
+    F: FnOnce(std::io::Result<()>) -> Msg + Send + 'static,
 {
     Box::pin(async move {
-        // Apply configured environment variables, if any
-        let mut cmd = cmd;
-        if let Some(env) = crate::command::COMMAND_ENV.get() {
-            for (k, v) in env.iter() {
-                cmd.env(k, v);
-            }
-        }
-        let output = TokioCommand::from(cmd).output().await;
-        Some(f(output))
+        let path = path.as_ref().to_path_buf();
+        Some(on_done(crate::runtime::write_file(path, contents).await))
     })
 }
 
@@ -640,6 +1815,38 @@ pub fn disable_mouse() -> Cmd {
     Box::pin(async { Some(Box::new(DisableMouseMsg) as Msg) })
 }
 
+/// Creates a command that reports all mouse motion for the length of a drag.
+///
+/// All-motion tracking is enabled until the next button release arrives,
+/// then the mouse mode that was enabled before is restored, so motion isn't
+/// reported outside the drag. The release itself is still delivered.
+/// Starting a capture while one is active does nothing.
+///
+/// # Example
+///
+/// ```rust
+/// use bubbletea_rs::{command, Cmd, MouseMsg};
+/// use crossterm::event::MouseEventKind;
+///
+/// fn on_mouse(mouse: &MouseMsg) -> Option<Cmd> {
+///     match mouse.button {
+///         MouseEventKind::Down(_) => Some(command::begin_mouse_capture()),
+///         _ => None,
+///     }
+/// }
+/// ```
+pub fn begin_mouse_capture() -> Cmd {
+    Box::pin(async { Some(Box::new(BeginMouseCaptureMsg) as Msg) })
+}
+
+/// Creates a command that ends a mouse capture before the button is
+/// released, restoring the mouse mode that was enabled before it began.
+///
+/// Does nothing if no capture is active.
+pub fn end_mouse_capture() -> Cmd {
+    Box::pin(async { Some(Box::new(EndMouseCaptureMsg) as Msg) })
+}
+
 /// Creates a command that enables focus reporting.
 ///
 /// This command sends an `EnableReportFocusMsg` to the program, which will
@@ -683,65 +1890,406 @@ pub fn show_cursor() -> Cmd {
 
 /// Creates a command that hides the terminal cursor.
 ///
-/// This command sends a `HideCursorMsg` to the program, which will make the
-/// terminal cursor invisible.
-pub fn hide_cursor() -> Cmd {
-    Box::pin(async { Some(Box::new(HideCursorMsg) as Msg) })
+/// This command sends a `HideCursorMsg` to the program, which will make the
+/// terminal cursor invisible.
+pub fn hide_cursor() -> Cmd {
+    Box::pin(async { Some(Box::new(HideCursorMsg) as Msg) })
+}
+
+/// Creates a command that saves the terminal cursor position.
+///
+/// This command sends a `SaveCursorMsg` to the program, which writes DECSC
+/// (`\x1b7`). Restore the position with [`restore_cursor`]. The program
+/// already saves and restores the cursor around [`println`] and [`printf`];
+/// this is for apps that move the cursor themselves.
+///
+/// # Note
+///
+/// Most terminals keep a single saved position rather than a stack, so a
+/// second save overwrites the first. Always restore before saving again.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::command;
+///
+/// let cmd = command::sequence(vec![
+///     command::save_cursor(),
+///     command::printf("\x1b[1;1Hstatus: ok".to_string()),
+///     command::restore_cursor(),
+/// ]);
+/// ```
+pub fn save_cursor() -> Cmd {
+    Box::pin(async { Some(Box::new(SaveCursorMsg) as Msg) })
+}
+
+/// Creates a command that restores the cursor position saved by
+/// [`save_cursor`].
+///
+/// This command sends a `RestoreCursorMsg` to the program, which writes
+/// DECRC (`\x1b8`).
+pub fn restore_cursor() -> Cmd {
+    Box::pin(async { Some(Box::new(RestoreCursorMsg) as Msg) })
+}
+
+/// Creates a command that clears the terminal screen.
+///
+/// This command sends a `ClearScreenMsg` to the program, which will clear
+/// all content from the terminal screen.
+pub fn clear_screen() -> Cmd {
+    Box::pin(async { Some(Box::new(ClearScreenMsg) as Msg) })
+}
+
+/// Creates a command that clears from the cursor to the end of its row.
+///
+/// This command sends a `ClearLineMsg` to the program, which writes
+/// `\x1b[K`. The next frame is drawn in full.
+pub fn clear_line() -> Cmd {
+    Box::pin(async { Some(Box::new(ClearLineMsg) as Msg) })
+}
+
+/// Creates a command that clears from the cursor to the end of the screen.
+///
+/// This command sends a `ClearToEndOfScreenMsg` to the program, which
+/// writes `\x1b[J`. The next frame is drawn in full.
+pub fn clear_to_end() -> Cmd {
+    Box::pin(async { Some(Box::new(ClearToEndOfScreenMsg) as Msg) })
+}
+
+/// Creates a command that clears from the top of the screen to the cursor.
+///
+/// This command sends a `ClearFromStartOfScreenMsg` to the program, which
+/// writes `\x1b[1J`. The next frame is drawn in full.
+pub fn clear_from_start() -> Cmd {
+    Box::pin(async { Some(Box::new(ClearFromStartOfScreenMsg) as Msg) })
+}
+
+/// Creates a command that stops the program from rendering.
+///
+/// This command sends a `PauseRenderMsg` to the program. Until
+/// [`resume_render`] is issued, messages are still delivered to
+/// `Model::update` and the model keeps its state, but the view is not drawn
+/// to the terminal. Use it to hand the screen to something else, such as a
+/// full-screen child process, without releasing the terminal as
+/// [`suspend`] does.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::command;
+///
+/// // Leave the screen alone while the editor runs, then draw the
+/// // last frame again
+/// let cmd = command::sequence(vec![
+///     command::pause_render(),
+///     command::exec_process(std::process::Command::new("vim"), |_| {
+///         Box::new(()) as bubbletea_rs::Msg
+///     }),
+///     command::resume_render(),
+/// ]);
+/// ```
+pub fn pause_render() -> Cmd {
+    Box::pin(async { Some(Box::new(PauseRenderMsg) as Msg) })
+}
+
+/// Creates a command that asks the terminal which keyboard enhancement
+/// flags it supports.
+///
+/// The program delivers the answer as a `KeyboardEnhancementsMsg`. The
+/// terminal's reply is taken off the input stream rather than being read as
+/// key presses. A terminal that does not answer within two seconds is
+/// reported as not supporting the protocol.
+///
+/// If the program enabled the protocol with
+/// [`ProgramBuilder::keyboard_enhancement`](crate::ProgramBuilder::keyboard_enhancement)
+/// and the terminal reports that the flags were not applied, the program
+/// turns the protocol off again and decodes keys the legacy way.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, IntoCmd, KeyboardEnhancementsMsg, Model, Msg};
+///
+/// struct App {
+///     kitty_keys: bool,
+/// }
+///
+/// impl Model for App {
+///     fn init() -> (Self, Option<Cmd>) {
+///         (Self { kitty_keys: false }, Some(command::query_keyboard_enhancements()))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(reply) = msg.downcast_ref::<KeyboardEnhancementsMsg>() {
+///             self.kitty_keys = reply.is_supported();
+///         }
+///         None::<Cmd>
+///     }
+///
+///     fn view(&self) -> String {
+///         String::new()
+///     }
+/// }
+/// ```
+pub fn query_keyboard_enhancements() -> Cmd {
+    Box::pin(async { Some(Box::new(QueryKeyboardEnhancementsMsg) as Msg) })
+}
+
+/// Creates a command that resumes rendering after [`pause_render`].
+///
+/// This command sends a `ResumeRenderMsg` to the program, which redraws the
+/// whole view, ignoring `Model::damage`, since the screen may have been
+/// changed while rendering was paused.
+pub fn resume_render() -> Cmd {
+    Box::pin(async { Some(Box::new(ResumeRenderMsg) as Msg) })
+}
+
+/// Creates a command that rings the terminal bell.
+///
+/// This command sends a `BellMsg` to the program, which writes the BEL
+/// control character (`\x07`) to the terminal. Use it to alert the user, for
+/// example after an invalid key press or when a long operation completes.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, KeyMsg, Model, Msg};
+/// use crossterm::event::KeyCode;
+///
+/// struct MyModel;
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         (Self, None)
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(key) = msg.downcast_ref::<KeyMsg>() {
+///             if !matches!(key.key, KeyCode::Char('y') | KeyCode::Char('n')) {
+///                 // Only y/n are accepted here
+///                 return Some(command::bell());
+///             }
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         "Continue? (y/n)".to_string()
+///     }
+/// }
+/// ```
+pub fn bell() -> Cmd {
+    Box::pin(async { Some(Box::new(crate::event::BellMsg) as Msg) })
+}
+
+/// Creates a command that flashes the terminal screen as a visual bell.
+///
+/// This command sends a `VisualBellMsg` to the program, which switches the
+/// terminal to reverse video for about 100ms. This is useful for terminals
+/// where the audible bell is disabled.
+///
+/// # Note
+///
+/// The flash is implemented by toggling reverse video, so the program
+/// re-renders the view when the flash starts and again when it ends. Apps
+/// that use `without_renderer()` will not see any effect.
+pub fn visual_bell() -> Cmd {
+    Box::pin(async { Some(Box::new(crate::event::VisualBellMsg) as Msg) })
+}
+
+/// Creates a command that requests the current window size.
+///
+/// This command sends a `RequestWindowSizeMsg` to the program. The terminal
+/// will respond with a `WindowSizeMsg` containing its current dimensions.
+/// This is useful for responsive layouts that adapt to terminal size.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg, WindowSizeMsg};
+///
+/// struct MyModel {
+///     width: u16,
+///     height: u16,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let model = Self { width: 0, height: 0 };
+///         // Get initial window size
+///         (model, Some(command::window_size()))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
+///             self.width = size_msg.width;
+///             self.height = size_msg.height;
+///         }
+///         None
+///     }
+///     
+///     fn view(&self) -> String {
+///         format!("Window size: {}x{}", self.width, self.height)
+///     }
+/// }
+/// ```
+pub fn window_size() -> Cmd {
+    Box::pin(async { Some(Box::new(RequestWindowSizeMsg) as Msg) })
+}
+
+/// Creates a command that sets the size the program assumes for its
+/// terminal.
+///
+/// The model receives a `WindowSizeMsg` with the given dimensions, and
+/// from then on [`window_size`] reports them instead of the terminal's
+/// real size. Useful when the program is embedded in a host that manages
+/// the layout, such as a multiplexer, or in tests that need a fixed size
+/// without a real terminal resize.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg, WindowSizeMsg};
+///
+/// struct Pane {
+///     width: u16,
+/// }
+///
+/// impl Model for Pane {
+///     fn init() -> (Self, Option<Cmd>) {
+///         // The host gives this pane 100 columns and 30 rows
+///         (Self { width: 0 }, Some(command::set_viewport_size(100, 30)))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(size) = msg.downcast_ref::<WindowSizeMsg>() {
+///             self.width = size.width;
+///         }
+///     }
+///
+///     fn view(&self) -> String {
+///         "-".repeat(usize::from(self.width))
+///     }
+/// }
+/// ```
+pub fn set_viewport_size(width: u16, height: u16) -> Cmd {
+    Box::pin(
+        async move { Some(Box::new(crate::event::SetViewportSizeMsg { width, height }) as Msg) },
+    )
+}
+
+/// Creates a command that requests the size of the area the view is drawn
+/// in.
+///
+/// The program answers with a [`ViewportSizeMsg`](crate::ViewportSizeMsg)
+/// holding both the terminal's size and the content area left to the view
+/// once the program's own chrome is accounted for, such as an inline log
+/// region or the debug input overlay. After the first request, a new
+/// `ViewportSizeMsg` is sent whenever either size changes, so a model can
+/// size its layout from the content area without knowing which features
+/// are on.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg, ViewportSizeMsg};
+///
+/// struct Pager {
+///     rows: u16,
+/// }
+///
+/// impl Model for Pager {
+///     fn init() -> (Self, Option<Cmd>) {
+///         (Self { rows: 0 }, Some(command::viewport_size()))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(size) = msg.downcast_ref::<ViewportSizeMsg>() {
+///             // One row for the status line
+///             self.rows = size.content_height.saturating_sub(1);
+///         }
+///     }
+///
+///     fn view(&self) -> String {
+///         format!("{} rows of text\nstatus", self.rows)
+///     }
+/// }
+/// ```
+pub fn viewport_size() -> Cmd {
+    Box::pin(async { Some(Box::new(crate::event::RequestViewportSizeMsg) as Msg) })
 }
 
-/// Creates a command that clears the terminal screen.
-///
-/// This command sends a `ClearScreenMsg` to the program, which will clear
-/// all content from the terminal screen.
-pub fn clear_screen() -> Cmd {
-    Box::pin(async { Some(Box::new(ClearScreenMsg) as Msg) })
+/// Creates a command that shows or hides the debug input overlay.
+///
+/// Does at run time what
+/// [`ProgramBuilder::debug_input_overlay`](crate::ProgramBuilder::debug_input_overlay)
+/// does at startup. Models that asked for [`viewport_size`] are sent a new
+/// `ViewportSizeMsg`, since the overlay covers the last row.
+pub fn set_debug_input_overlay(enabled: bool) -> Cmd {
+    Box::pin(async move { Some(Box::new(crate::event::SetDebugInputOverlayMsg(enabled)) as Msg) })
 }
 
-/// Creates a command that requests the current window size.
+/// Creates a command that captures the frame currently on screen.
 ///
-/// This command sends a `RequestWindowSizeMsg` to the program. The terminal
-/// will respond with a `WindowSizeMsg` containing its current dimensions.
-/// This is useful for responsive layouts that adapt to terminal size.
+/// The program passes the last frame it drew to `on_done` and delivers the
+/// resulting message, so an application can offer to save a screenshot for
+/// a bug report. The frame is the text the terminal was sent, styling
+/// escape sequences included, not a rasterized image: viewing it with
+/// `cat` in a terminal of the same width shows what was on screen. It is
+/// the full view even when only part of the screen was redrawn, including
+/// the letterbox margin of
+/// [`ProgramBuilder::max_width`](crate::ProgramBuilder::max_width) and the
+/// debug input overlay.
 ///
-/// # Examples
+/// Before the first frame, and without a renderer, the frame is empty.
 ///
-/// ```
-/// use bubbletea_rs::{command, Model, Msg, WindowSizeMsg};
+/// # Example
 ///
-/// struct MyModel {
-///     width: u16,
-///     height: u16,
+/// ```rust
+/// use bubbletea_rs::{command, Cmd, IntoCmd, KeyMsg, Model, Msg};
+///
+/// struct ScreenshotMsg(String);
+///
+/// struct App {
+///     saved: Option<String>,
 /// }
 ///
-/// impl Model for MyModel {
-///     fn init() -> (Self, Option<command::Cmd>) {
-///         let model = Self { width: 0, height: 0 };
-///         // Get initial window size
-///         (model, Some(command::window_size()))
+/// impl Model for App {
+///     fn init() -> (Self, Option<Cmd>) {
+///         (App { saved: None }, None)
 ///     }
 ///
-///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
-///         if let Some(size_msg) = msg.downcast_ref::<WindowSizeMsg>() {
-///             self.width = size_msg.width;
-///             self.height = size_msg.height;
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(ScreenshotMsg(frame)) = msg.downcast_ref::<ScreenshotMsg>() {
+///             self.saved = Some(frame.clone());
+///         } else if msg.is::<KeyMsg>() {
+///             return Some(command::screenshot(|frame| Box::new(ScreenshotMsg(frame)) as Msg));
 ///         }
 ///         None
 ///     }
-///     
+///
 ///     fn view(&self) -> String {
-///         format!("Window size: {}x{}", self.width, self.height)
+///         "press any key to take a screenshot".to_string()
 ///     }
 /// }
 /// ```
-pub fn window_size() -> Cmd {
-    Box::pin(async { Some(Box::new(RequestWindowSizeMsg) as Msg) })
+pub fn screenshot<F>(on_done: F) -> Cmd
+where
+    F: FnOnce(String) -> Msg + Send + 'static,
+{
+    Box::pin(async move {
+        Some(Box::new(crate::event::ScreenshotMsg {
+            on_done: Box::new(on_done),
+        }) as Msg)
+    })
 }
 
 /// Creates a command that prints a line to the terminal.
 ///
 /// This command sends a `PrintMsg` to the program, which will print the
 /// provided string to the terminal. This is useful for debugging or
-/// outputting information that should appear outside the normal UI.
+/// outputting information that should appear outside the normal UI. The
+/// cursor position is saved before printing and restored afterwards.
 ///
 /// # Arguments
 ///
@@ -784,11 +2332,70 @@ pub fn println(s: String) -> Cmd {
 /// Creates a command that prints formatted text to the terminal.
 ///
 /// This command sends a `PrintfMsg` to the program, which will print the
-/// provided formatted string to the terminal.
+/// provided formatted string to the terminal. The cursor position is saved
+/// before printing and restored afterwards, so cursor movements in `s` do
+/// not affect where the next frame is drawn.
 pub fn printf(s: String) -> Cmd {
     Box::pin(async move { Some(Box::new(PrintfMsg(s)) as Msg) })
 }
 
+/// Creates a command that prints several lines to the terminal at once.
+///
+/// The lines are joined and sent as a single `PrintMsg`, so they are
+/// written in one go with a single cursor save and restore, and can't be
+/// interleaved with a frame or another print. Each line may carry its own
+/// ANSI styling; styling left open at the end of a line is closed before
+/// the next frame is drawn.
+///
+/// # Arguments
+///
+/// * `lines` - The lines to print, without trailing newlines
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, PrintMsg};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let cmd = command::print_lines(vec![
+///     "\x1b[32m✓\x1b[0m fetched".to_string(),
+///     "\x1b[32m✓\x1b[0m built".to_string(),
+/// ]);
+/// let msg = cmd.await.unwrap();
+/// assert_eq!(
+///     msg.downcast_ref::<PrintMsg>().unwrap().0,
+///     "\x1b[32m✓\x1b[0m fetched\n\x1b[32m✓\x1b[0m built"
+/// );
+/// # }
+/// ```
+pub fn print_lines(lines: Vec<String>) -> Cmd {
+    println(lines.join("\n"))
+}
+
+/// Creates a command that announces a short status line to screen readers.
+///
+/// This command sends an `AnnounceMsg`. In accessible mode the text is
+/// printed as a plain line below the output so far, where a screen reader
+/// reads it out; otherwise it is dropped, since the view already shows the
+/// change. Keep announcements concise and about what just changed.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, AnnounceMsg};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let msg = command::announce("Selected row 5: Tokyo").await.unwrap();
+/// assert_eq!(msg.downcast_ref::<AnnounceMsg>().unwrap().0, "Selected row 5: Tokyo");
+/// # }
+/// ```
+pub fn announce(text: impl Into<String>) -> Cmd {
+    let text = text.into();
+    Box::pin(async move { Some(Box::new(AnnounceMsg(text)) as Msg) })
+}
+
 /// Creates a command that sets the terminal window title.
 ///
 /// This command sends a `SetWindowTitleMsg` to the program, which will update
@@ -841,6 +2448,71 @@ pub fn set_window_title(title: String) -> Cmd {
     Box::pin(async move { Some(Box::new(crate::event::SetWindowTitleMsg(title)) as Msg) })
 }
 
+/// Creates a command that sets a temporary window title.
+///
+/// This command sends a `PushWindowTitleMsg` to the program, which saves the
+/// current title and sets `title`. Restore the saved title with
+/// [`pop_window_title`]. Pushes nest: each pop restores the title from
+/// before the matching push, and any titles still pushed when the program
+/// exits are popped automatically.
+///
+/// On terminals with a title stack (`CSI 22;0 t` / `CSI 23;0 t`) the title
+/// the user had before the program started is restored. Elsewhere the
+/// program can only restore titles it set itself, so popping the outermost
+/// push leaves the temporary title in place unless the program had set a
+/// title before pushing.
+///
+/// # Arguments
+///
+/// * `title` - The temporary window title
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{command, Model, Msg};
+///
+/// #[derive(Debug)]
+/// struct DownloadProgress(u8);
+///
+/// #[derive(Debug)]
+/// struct DownloadDone;
+///
+/// struct Downloader;
+///
+/// impl Model for Downloader {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         (Self, Some(command::push_window_title("Downloading…".to_string())))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(DownloadProgress(percent)) = msg.downcast_ref::<DownloadProgress>() {
+///             let title = format!("Downloading… {percent}%");
+///             return Some(command::set_window_title(title));
+///         }
+///         if msg.is::<DownloadDone>() {
+///             return Some(command::pop_window_title());
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         String::new()
+///     }
+/// }
+/// ```
+pub fn push_window_title(title: String) -> Cmd {
+    Box::pin(async move { Some(Box::new(crate::event::PushWindowTitleMsg(title)) as Msg) })
+}
+
+/// Creates a command that restores the window title saved by the last
+/// [`push_window_title`].
+///
+/// This command sends a `PopWindowTitleMsg` to the program. It does nothing
+/// if no title is pushed.
+pub fn pop_window_title() -> Cmd {
+    Box::pin(async { Some(Box::new(crate::event::PopWindowTitleMsg) as Msg) })
+}
+
 /// Creates a command that cancels a specific timer.
 ///
 /// This command sends a `CancelTimerMsg` to the program, which will stop
@@ -904,3 +2576,348 @@ pub fn cancel_timer(timer_id: u64) -> Cmd {
 pub fn cancel_all_timers() -> Cmd {
     Box::pin(async move { Some(Box::new(crate::event::CancelAllTimersMsg) as Msg) })
 }
+
+/// Creates a command that pauses the timers of a group.
+///
+/// Timers started with [`every_in_group`] stop firing, and ticks started
+/// with [`tick_in_group`] hold their messages, until the group is resumed.
+/// Unlike [`cancel_all_timers`], nothing has to be recreated afterwards.
+/// Pausing a paused group does nothing.
+pub fn pause_timer_group(group: u64) -> Cmd {
+    Box::pin(async move { Some(Box::new(crate::event::PauseTimerGroupMsg { group }) as Msg) })
+}
+
+/// Creates a command that resumes the timers of a group paused with
+/// [`pause_timer_group`].
+///
+/// Ticks held during the pause are delivered in the order they fired.
+pub fn resume_timer_group(group: u64) -> Cmd {
+    Box::pin(async move { Some(Box::new(crate::event::ResumeTimerGroupMsg { group }) as Msg) })
+}
+
+/// Creates a command that delivers `msg` once `key` has been quiet for
+/// `delay`.
+///
+/// Each call for the same `key` replaces the message still waiting and
+/// starts the delay over, so a burst of calls delivers only the last
+/// message, `delay` after the burst ends. Different keys are independent.
+/// This is the search-as-you-type pattern: debounce a query on every
+/// keystroke, and run it only when the user pauses.
+///
+/// # Arguments
+///
+/// * `key` - Names the action being debounced
+/// * `delay` - The quiet period before the message is delivered
+/// * `msg` - The message to deliver
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::{command, Cmd, KeyMsg, Model, Msg};
+/// use crossterm::event::KeyCode;
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct SearchMsg(String);
+///
+/// struct Search {
+///     query: String,
+/// }
+///
+/// impl Model for Search {
+///     fn init() -> (Self, Option<Cmd>) {
+///         (Self { query: String::new() }, None)
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<Cmd> {
+///         if let Some(KeyMsg { key: KeyCode::Char(c), .. }) = msg.downcast_ref::<KeyMsg>() {
+///             self.query.push(*c);
+///             let search = Box::new(SearchMsg(self.query.clone()));
+///             return Some(command::debounce("search", Duration::from_millis(300), search));
+///         }
+///         if let Some(SearchMsg(query)) = msg.downcast_ref::<SearchMsg>() {
+///             // Run the query for `query`
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         format!("Search: {}", self.query)
+///     }
+/// }
+/// ```
+pub fn debounce(key: &'static str, delay: Duration, msg: Msg) -> Cmd {
+    Box::pin(async move { Some(Box::new(crate::event::DebounceMsg { key, delay, msg }) as Msg) })
+}
+
+/// Creates a command that watches a path for file system changes.
+///
+/// Once processed by the program, a background watcher observes `path`
+/// (recursively, if it is a directory) and sends a message produced by `f`
+/// for every create, modify, or remove event. Rapid bursts of events are
+/// debounced: an event is only delivered after `debounce` has passed without
+/// further activity, and duplicate events within a burst are collapsed.
+///
+/// The watcher runs until the program shuts down. Use `watch_path_with_id()`
+/// if you need to stop it earlier. Errors from the underlying watcher are
+/// delivered as [`ErrorMsg`](crate::event::ErrorMsg).
+///
+/// Requires the `watch` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file or directory to watch
+/// * `debounce` - The quiet period used to coalesce bursts of events
+/// * `f` - A closure that takes a `WatchEvent` and returns a `Msg`
+///
+/// # Examples
+///
+/// ```no_run
+/// use bubbletea_rs::{command, Model, Msg};
+/// use bubbletea_rs::watch::WatchEvent;
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct ReloadMsg;
+///
+/// struct MyModel {
+///     content: String,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let content = std::fs::read_to_string("notes.md").unwrap_or_default();
+///         let cmd = command::watch_path("notes.md", Duration::from_millis(100), |_event| {
+///             Box::new(ReloadMsg) as Msg
+///         });
+///         (Self { content }, Some(cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if msg.downcast_ref::<ReloadMsg>().is_some() {
+///             self.content = std::fs::read_to_string("notes.md").unwrap_or_default();
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         self.content.clone()
+///     }
+/// }
+/// ```
+#[cfg(feature = "watch")]
+pub fn watch_path<P, F>(path: P, debounce: Duration, f: F) -> Cmd
+where
+    P: Into<std::path::PathBuf>,
+    F: Fn(crate::watch::WatchEvent) -> Msg + Send + 'static,
+{
+    watch_path_with_id(path, debounce, f).0
+}
+
+/// Creates a command that watches a path for file system changes, with
+/// cancellation support.
+///
+/// This behaves like `watch_path()` but also returns a watch ID that can be
+/// passed to `cancel_watch()` to stop the watcher.
+///
+/// Requires the `watch` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file or directory to watch
+/// * `debounce` - The quiet period used to coalesce bursts of events
+/// * `f` - A closure that takes a `WatchEvent` and returns a `Msg`
+///
+/// # Returns
+///
+/// Returns a tuple containing:
+/// - The command to start the watcher
+/// - A watch ID that can be used with `cancel_watch()`
+#[cfg(feature = "watch")]
+pub fn watch_path_with_id<P, F>(path: P, debounce: Duration, f: F) -> (Cmd, u64)
+where
+    P: Into<std::path::PathBuf>,
+    F: Fn(crate::watch::WatchEvent) -> Msg + Send + 'static,
+{
+    let watch_id = next_timer_id();
+    let cancellation_token = CancellationToken::new();
+    let path = path.into();
+
+    let cmd = Box::pin(async move {
+        Some(Box::new(crate::watch::WatchMsgInternal {
+            path,
+            debounce,
+            func: Box::new(f),
+            cancellation_token,
+            watch_id,
+        }) as Msg)
+    });
+
+    (cmd, watch_id)
+}
+
+/// Creates a command that stops a path watcher.
+///
+/// This command sends a `CancelWatchMsg` to the program, which will stop
+/// the watcher with the given ID. Use this with watch IDs returned by
+/// `watch_path_with_id()`. If the watcher has already stopped, this has
+/// no effect.
+///
+/// # Arguments
+///
+/// * `watch_id` - The ID of the watcher to cancel
+pub fn cancel_watch(watch_id: u64) -> Cmd {
+    Box::pin(async move { Some(Box::new(crate::event::CancelWatchMsg { watch_id }) as Msg) })
+}
+
+/// Creates a command that performs an HTTP GET request and delivers the
+/// response body as a message.
+///
+/// `on_result` receives the body bytes on success, or a description of the
+/// error if the request fails or the server answers with a non-success
+/// status. Dropping the command before it completes aborts the request; use
+/// [`fetch_with_timeout`] to give up after a fixed time.
+///
+/// Requires the `fetch` feature.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch
+/// * `on_result` - A closure that turns the body or error into a `Msg`
+///
+/// # Examples
+///
+/// ```no_run
+/// use bubbletea_rs::{command, Model, Msg};
+///
+/// #[derive(Debug)]
+/// struct FetchedMsg(Result<Vec<u8>, String>);
+///
+/// struct MyModel {
+///     status: String,
+/// }
+///
+/// impl Model for MyModel {
+///     fn init() -> (Self, Option<command::Cmd>) {
+///         let cmd = command::fetch("https://example.com", |result| {
+///             Box::new(FetchedMsg(result)) as Msg
+///         });
+///         (Self { status: "Loading...".to_string() }, Some(cmd))
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Option<command::Cmd> {
+///         if let Some(FetchedMsg(result)) = msg.downcast_ref::<FetchedMsg>() {
+///             self.status = match result {
+///                 Ok(body) => format!("Fetched {} bytes", body.len()),
+///                 Err(error) => format!("Error: {error}"),
+///             };
+///         }
+///         None
+///     }
+///
+///     fn view(&self) -> String {
+///         self.status.clone()
+///     }
+/// }
+/// ```
+#[cfg(feature = "fetch")]
+pub fn fetch<U, F>(url: U, on_result: F) -> Cmd
+where
+    U: Into<String>,
+    F: Fn(Result<Vec<u8>, String>) -> Msg + Send + 'static,
+{
+    let url = url.into();
+    Box::pin(async move {
+        let result = get_body(&url).await;
+        Some(on_result(result))
+    })
+}
+
+/// Creates a command that performs an HTTP GET request, giving up after
+/// `timeout`.
+///
+/// This behaves like [`fetch`], but if the whole request, including reading
+/// the body, takes longer than `timeout` it is cancelled and `on_result`
+/// receives an error instead.
+///
+/// Requires the `fetch` feature.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch
+/// * `timeout` - How long to wait before cancelling the request
+/// * `on_result` - A closure that turns the body or error into a `Msg`
+#[cfg(feature = "fetch")]
+pub fn fetch_with_timeout<U, F>(url: U, timeout: Duration, on_result: F) -> Cmd
+where
+    U: Into<String>,
+    F: Fn(Result<Vec<u8>, String>) -> Msg + Send + 'static,
+{
+    let url = url.into();
+    Box::pin(async move {
+        let result = tokio::time::timeout(timeout, get_body(&url))
+            .await
+            .unwrap_or_else(|_| Err(format!("request timed out after {timeout:?}")));
+        Some(on_result(result))
+    })
+}
+
+/// Fetches `url` and reads the whole body, treating error statuses as
+/// failures.
+#[cfg(feature = "fetch")]
+async fn get_body(url: &str) -> Result<Vec<u8>, String> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    Ok(body.to_vec())
+}
+
+#[cfg(all(test, feature = "tokio-runtime"))]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TickMsg;
+
+    /// Starts a timer on a paused clock, stalls it for five intervals and
+    /// returns how many messages were delivered for the stall.
+    async fn ticks_after_stall(behavior: MissedTickBehavior) -> usize {
+        let duration = Duration::from_millis(10);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        tokio::spawn(run_every_timer(
+            duration,
+            behavior,
+            Box::new(|_| Box::new(TickMsg) as Msg),
+            token.clone(),
+            crate::event::EventSender::from_unbounded(tx),
+            watch::channel(false).1,
+            None,
+        ));
+        tokio::task::yield_now().await;
+
+        tokio::time::advance(duration * 5).await;
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        token.cancel();
+
+        let mut count = 0;
+        while rx.try_recv().is_ok() {
+            count += 1;
+        }
+        count
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_every_skips_missed_ticks() {
+        assert_eq!(ticks_after_stall(MissedTickBehavior::Skip).await, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_every_with_burst_delivers_missed_ticks() {
+        assert_eq!(ticks_after_stall(MissedTickBehavior::Burst).await, 5);
+    }
+}
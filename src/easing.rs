@@ -0,0 +1,410 @@
+//! Easing functions and tweening for frame-based animations.
+//!
+//! Easing functions map linear progress `t` in `[0.0, 1.0]` to eased
+//! progress. Every function returns exactly `0.0` at `t = 0.0` and exactly
+//! `1.0` at `t = 1.0`; inputs outside that range are clamped. The bounce and
+//! elastic families may leave `[0.0, 1.0]` between the endpoints.
+//!
+//! [`Tween`] combines an easing function with a start value, a target value,
+//! and a duration. Advance it on every animation frame and it produces the
+//! interpolated value, landing exactly on the target when the duration has
+//! elapsed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::easing::{self, Tween};
+//! use std::time::Duration;
+//!
+//! let mut tween = Tween::new(0.0, 10.0, Duration::from_millis(100))
+//!     .with_easing(easing::ease_out_cubic);
+//!
+//! while !tween.is_finished() {
+//!     let value = tween.advance(Duration::from_millis(16));
+//!     assert!(value <= 10.0);
+//! }
+//! assert_eq!(tween.value(), 10.0);
+//! ```
+
+use std::f64::consts::PI;
+use std::time::Duration;
+
+/// An easing function mapping linear progress in `[0.0, 1.0]` to eased progress.
+pub type EasingFn = fn(f64) -> f64;
+
+/// No easing: progress advances at a constant rate.
+pub fn linear(t: f64) -> f64 {
+    t.clamp(0.0, 1.0)
+}
+
+/// Quadratic ease-in: starts slowly and accelerates.
+pub fn ease_in_quad(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t
+}
+
+/// Quadratic ease-out: starts quickly and decelerates.
+pub fn ease_out_quad(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Quadratic ease-in-out: accelerates to the midpoint, then decelerates.
+pub fn ease_in_out_quad(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+    }
+}
+
+/// Cubic ease-in: starts slowly and accelerates.
+pub fn ease_in_cubic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * t
+}
+
+/// Cubic ease-out: starts quickly and decelerates.
+pub fn ease_out_cubic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Cubic ease-in-out: accelerates to the midpoint, then decelerates.
+pub fn ease_in_out_cubic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// Exponential ease-in: nearly still at first, then very fast.
+pub fn ease_in_expo(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t == 0.0 {
+        0.0
+    } else {
+        2f64.powf(10.0 * t - 10.0)
+    }
+}
+
+/// Exponential ease-out: very fast at first, then settles slowly.
+pub fn ease_out_expo(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t == 1.0 {
+        1.0
+    } else {
+        1.0 - 2f64.powf(-10.0 * t)
+    }
+}
+
+/// Exponential ease-in-out.
+pub fn ease_in_out_expo(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t == 0.0 || t == 1.0 {
+        t
+    } else if t < 0.5 {
+        2f64.powf(20.0 * t - 10.0) / 2.0
+    } else {
+        (2.0 - 2f64.powf(-20.0 * t + 10.0)) / 2.0
+    }
+}
+
+/// Bounce ease-in: the mirror image of [`ease_out_bounce`].
+pub fn ease_in_bounce(t: f64) -> f64 {
+    1.0 - ease_out_bounce(1.0 - t.clamp(0.0, 1.0))
+}
+
+/// Bounce ease-out: overshoots the floor and bounces to rest at the target.
+pub fn ease_out_bounce(t: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+
+    let t = t.clamp(0.0, 1.0);
+    if t == 1.0 {
+        1.0
+    } else if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Bounce ease-in-out.
+pub fn ease_in_out_bounce(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        (1.0 - ease_out_bounce(1.0 - 2.0 * t)) / 2.0
+    } else {
+        (1.0 + ease_out_bounce(2.0 * t - 1.0)) / 2.0
+    }
+}
+
+/// Elastic ease-in: winds up with growing oscillations before the start.
+pub fn ease_in_elastic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t == 0.0 || t == 1.0 {
+        t
+    } else {
+        -(2f64.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * (2.0 * PI / 3.0)).sin()
+    }
+}
+
+/// Elastic ease-out: overshoots the target and oscillates to rest.
+pub fn ease_out_elastic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t == 0.0 || t == 1.0 {
+        t
+    } else {
+        2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+    }
+}
+
+/// Elastic ease-in-out.
+pub fn ease_in_out_elastic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    let c = 2.0 * PI / 4.5;
+    if t == 0.0 || t == 1.0 {
+        t
+    } else if t < 0.5 {
+        -(2f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * c).sin()) / 2.0
+    } else {
+        2f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * c).sin() / 2.0 + 1.0
+    }
+}
+
+/// Interpolates from a start value to a target value over a fixed duration.
+///
+/// A tween does not schedule anything by itself: call
+/// [`advance`](Self::advance) with the frame interval whenever a frame
+/// message arrives, and keep scheduling frames until
+/// [`is_finished`](Self::is_finished) returns `true`. Once the duration has
+/// elapsed the value is exactly the target, so callers can compare against
+/// it without a tolerance.
+#[derive(Debug, Clone)]
+pub struct Tween {
+    start: f64,
+    target: f64,
+    duration: Duration,
+    elapsed: Duration,
+    easing: EasingFn,
+}
+
+impl Tween {
+    /// Creates a linear tween from `start` to `target` over `duration`.
+    ///
+    /// A zero `duration` produces a tween that is already finished at
+    /// `target`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The initial value
+    /// * `target` - The value to land on
+    /// * `duration` - How long the transition takes
+    pub fn new(start: f64, target: f64, duration: Duration) -> Self {
+        Self {
+            start,
+            target,
+            duration,
+            elapsed: Duration::ZERO,
+            easing: linear,
+        }
+    }
+
+    /// Sets the easing function, such as [`ease_out_cubic`].
+    pub fn with_easing(mut self, easing: EasingFn) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns the value the tween started from.
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Returns the value the tween lands on.
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    /// Returns the total duration of the transition.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns whether the full duration has elapsed.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Returns linear progress through the duration, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f64 {
+        if self.is_finished() {
+            1.0
+        } else {
+            self.elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        }
+    }
+
+    /// Returns the current interpolated value.
+    ///
+    /// This is exactly [`target`](Self::target) once the tween has finished.
+    pub fn value(&self) -> f64 {
+        if self.is_finished() {
+            self.target
+        } else {
+            self.start + (self.target - self.start) * (self.easing)(self.progress())
+        }
+    }
+
+    /// Advances the tween by one frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - Time elapsed since the previous frame
+    ///
+    /// # Returns
+    ///
+    /// The interpolated value after advancing.
+    pub fn advance(&mut self, dt: Duration) -> f64 {
+        self.elapsed = self.elapsed.saturating_add(dt).min(self.duration);
+        self.value()
+    }
+
+    /// Starts a new transition from the current value to `target`.
+    ///
+    /// The duration and easing function are kept, and the elapsed time is
+    /// reset, so retargeting mid-animation continues smoothly from wherever
+    /// the value currently is.
+    pub fn retarget(&mut self, target: f64) {
+        self.start = self.value();
+        self.target = target;
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [(&str, EasingFn); 13] = [
+        ("linear", linear),
+        ("ease_in_quad", ease_in_quad),
+        ("ease_out_quad", ease_out_quad),
+        ("ease_in_out_quad", ease_in_out_quad),
+        ("ease_in_cubic", ease_in_cubic),
+        ("ease_out_cubic", ease_out_cubic),
+        ("ease_in_out_cubic", ease_in_out_cubic),
+        ("ease_in_expo", ease_in_expo),
+        ("ease_out_expo", ease_out_expo),
+        ("ease_in_out_expo", ease_in_out_expo),
+        ("ease_in_bounce", ease_in_bounce),
+        ("ease_out_bounce", ease_out_bounce),
+        ("ease_in_out_bounce", ease_in_out_bounce),
+    ];
+
+    const ELASTIC: [(&str, EasingFn); 3] = [
+        ("ease_in_elastic", ease_in_elastic),
+        ("ease_out_elastic", ease_out_elastic),
+        ("ease_in_out_elastic", ease_in_out_elastic),
+    ];
+
+    #[test]
+    fn endpoints_are_exact() {
+        for (name, f) in ALL.iter().chain(ELASTIC.iter()) {
+            assert_eq!(f(0.0), 0.0, "{name}(0)");
+            assert_eq!(f(1.0), 1.0, "{name}(1)");
+            assert_eq!(f(-0.5), 0.0, "{name} clamps below");
+            assert_eq!(f(1.5), 1.0, "{name} clamps above");
+        }
+    }
+
+    #[test]
+    fn polynomial_and_expo_are_monotonic() {
+        for (name, f) in ALL.iter().filter(|(name, _)| !name.contains("bounce")) {
+            let mut prev = f(0.0);
+            for i in 1..=1000 {
+                let v = f(i as f64 / 1000.0);
+                assert!(v >= prev, "{name} decreased at step {i}");
+                prev = v;
+            }
+        }
+    }
+
+    #[test]
+    fn bounce_stays_in_range() {
+        for (name, f) in ALL.iter().filter(|(name, _)| name.contains("bounce")) {
+            for i in 0..=1000 {
+                let v = f(i as f64 / 1000.0);
+                assert!((0.0..=1.0).contains(&v), "{name} out of range: {v}");
+            }
+        }
+    }
+
+    #[test]
+    fn bounce_out_matches_reference_points() {
+        assert!((ease_out_bounce(0.5) - 0.765625).abs() < 1e-12);
+        assert!((ease_in_bounce(0.5) - 0.234375).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tween_lands_exactly_on_target() {
+        for (name, f) in ALL.iter().chain(ELASTIC.iter()) {
+            let mut tween = Tween::new(0.1, 0.7, Duration::from_millis(250)).with_easing(*f);
+            let mut frames = 0;
+            while !tween.is_finished() {
+                tween.advance(Duration::from_millis(16));
+                frames += 1;
+            }
+            assert_eq!(frames, 16, "{name}");
+            assert_eq!(tween.value(), 0.7, "{name}");
+        }
+    }
+
+    #[test]
+    fn tween_is_monotonic_toward_target() {
+        let mut tween =
+            Tween::new(1.0, 0.0, Duration::from_millis(300)).with_easing(ease_in_out_cubic);
+        let mut prev = tween.value();
+        assert_eq!(prev, 1.0);
+        while !tween.is_finished() {
+            let v = tween.advance(Duration::from_millis(7));
+            assert!(v <= prev);
+            prev = v;
+        }
+        assert_eq!(prev, 0.0);
+    }
+
+    #[test]
+    fn zero_duration_tween_is_finished() {
+        let tween = Tween::new(3.0, 5.0, Duration::ZERO);
+        assert!(tween.is_finished());
+        assert_eq!(tween.value(), 5.0);
+        assert_eq!(tween.progress(), 1.0);
+    }
+
+    #[test]
+    fn retarget_continues_from_current_value() {
+        let mut tween = Tween::new(0.0, 1.0, Duration::from_millis(100));
+        let mid = tween.advance(Duration::from_millis(50));
+        assert!((mid - 0.5).abs() < 1e-12);
+
+        tween.retarget(0.0);
+        assert_eq!(tween.start(), mid);
+        assert_eq!(tween.value(), mid);
+        assert!(!tween.is_finished());
+
+        tween.advance(Duration::from_secs(1));
+        assert_eq!(tween.value(), 0.0);
+    }
+}
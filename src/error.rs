@@ -21,7 +21,7 @@
 //! # struct MyModel;
 //! # impl Model for MyModel {
 //! #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
-//! #     fn update(&mut self, _msg: Msg) -> Option<Cmd> { None }
+//! #     fn update(&mut self, _msg: Msg) -> impl bubbletea_rs::IntoCmd {}
 //! #     fn view(&self) -> String { String::new() }
 //! # }
 //! async fn run_program() -> Result<(), Error> {
@@ -41,8 +41,9 @@
 //!         Error::Interrupted => {
 //!             println!("Program was interrupted by user");
 //!         }
-//!         Error::ProgramKilled => {
+//!         Error::ProgramKilled(code) => {
 //!             println!("Program was explicitly killed");
+//!             std::process::exit(code);
 //!         }
 //!         Error::Io(io_err) => {
 //!             eprintln!("I/O error occurred: {}", io_err);
@@ -98,7 +99,7 @@ use thiserror::Error;
 /// # struct MyModel;
 /// # impl Model for MyModel {
 /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
-/// #     fn update(&mut self, _msg: Msg) -> Option<Cmd> { None }
+/// #     fn update(&mut self, _msg: Msg) -> impl bubbletea_rs::IntoCmd {}
 /// #     fn view(&self) -> String { String::new() }
 /// # }
 /// fn create_program() -> Result<Program<MyModel>, Error> {
@@ -114,15 +115,29 @@ pub enum Error {
     ProgramPanic(String),
 
     /// Indicates that the program was explicitly killed, similar to Go's `ErrProgramKilled`.
-    /// This can happen if the `kill()` method is called on the `Program`.
-    #[error("Program was killed")]
-    ProgramKilled,
+    /// This can happen if the `kill()` method is called on the `Program`. Carries the
+    /// exit code from the `KillMsg`, for passing to `std::process::exit`.
+    #[error("Program was killed (exit code {0})")]
+    ProgramKilled(i32),
 
     /// Indicates that the program was interrupted, similar to Go's `ErrInterrupted`.
     /// This typically occurs when an interrupt signal (e.g., Ctrl+C) is received.
     #[error("Program was interrupted")]
     Interrupted,
 
+    /// Indicates that the terminal went away while the program was running,
+    /// for example because the terminal emulator closed or an SSH connection
+    /// dropped. Writes to it failed for good, or the process received
+    /// `SIGHUP`. Carries the underlying I/O error as its source.
+    #[error("Terminal lost: {0}")]
+    TerminalLost(#[source] std::io::Error),
+
+    /// Indicates that another `Program` is already running on the same
+    /// terminal. Returned by `run` before the terminal is touched, so the
+    /// running program keeps working undisturbed.
+    #[error("Another program is already running on this terminal")]
+    AlreadyRunning,
+
     /// Represents an I/O error, wrapping `std::io::Error`.
     /// This can occur during terminal operations, file access, or network communication.
     #[error("I/O error: {0}")]
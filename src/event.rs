@@ -2,9 +2,11 @@
 //! Messages are events that trigger updates in your application's model.
 //! They are typically sent by commands or the input handler.
 
+use crossterm::event::KeyboardEnhancementFlags;
 use std::any::Any;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::OnceLock;
+use std::time::Instant;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
@@ -113,6 +115,33 @@ impl EventSender {
         }
     }
 
+    /// Returns how many messages are waiting in the channel.
+    ///
+    /// Only bounded channels can tell from the sending side, as the
+    /// difference between their capacity and the free slots. Unbounded
+    /// channels return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bubbletea_rs::event::{EventSender, Msg};
+    /// use tokio::sync::mpsc;
+    ///
+    /// let (tx, _rx) = mpsc::channel::<Msg>(10);
+    /// let sender = EventSender::from_bounded(tx);
+    /// sender.send(Box::new(1)).unwrap();
+    /// assert_eq!(sender.queued(), Some(1));
+    ///
+    /// let (tx, _rx) = mpsc::unbounded_channel::<Msg>();
+    /// assert_eq!(EventSender::from_unbounded(tx).queued(), None);
+    /// ```
+    pub fn queued(&self) -> Option<usize> {
+        match self {
+            EventSender::Unbounded(_) => None,
+            EventSender::Bounded(tx) => Some(tx.max_capacity() - tx.capacity()),
+        }
+    }
+
     /// Create an EventSender from an UnboundedSender (for backward compatibility).
     ///
     /// This method creates an `EventSender` wrapping an unbounded channel sender.
@@ -252,6 +281,24 @@ impl EventReceiver {
             EventReceiver::Bounded(rx) => rx.recv().await,
         }
     }
+
+    /// Receives a message that is already waiting, without blocking.
+    ///
+    /// Returns `None` if no message is waiting or the channel is closed.
+    pub fn try_recv(&mut self) -> Option<Msg> {
+        match self {
+            EventReceiver::Unbounded(rx) => rx.try_recv().ok(),
+            EventReceiver::Bounded(rx) => rx.try_recv().ok(),
+        }
+    }
+
+    /// Returns `true` if no message is waiting, so `recv` would block.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            EventReceiver::Unbounded(rx) => rx.is_empty(),
+            EventReceiver::Bounded(rx) => rx.is_empty(),
+        }
+    }
 }
 
 /// Global event sender set by Program on startup so commands can emit messages
@@ -280,6 +327,10 @@ impl EventReceiver {
 /// read from it, never write to it.
 pub static EVENT_SENDER: OnceLock<EventSender> = OnceLock::new();
 
+/// How many programs are running their event loop, so commands can tell
+/// whether a message sent through `EVENT_SENDER` will be handled.
+pub(crate) static RUNNING_EVENT_LOOPS: AtomicUsize = AtomicUsize::new(0);
+
 /// Global timer ID generator for unique timer identification.
 ///
 /// This atomic counter ensures that each timer created in the application
@@ -311,6 +362,20 @@ pub fn next_timer_id() -> u64 {
 }
 
 /// A message indicating a keyboard input event.
+///
+/// The time the key was decoded is available from [`input_timestamp`]
+/// while `update` handles it.
+///
+/// # Enter, Tab and their Ctrl counterparts
+///
+/// Legacy terminals send the same byte for Enter and Ctrl+M (`\r`), and for
+/// Tab and Ctrl+I (`\t`). Those bytes are always delivered as `Enter` and
+/// `Tab`, so an application cannot tell which key was pressed, and
+/// [`input_is_ambiguous`] returns `true` while `update` handles them. With
+/// the keyboard enhancement protocol active (see
+/// [`ProgramBuilder::keyboard_enhancement`](crate::ProgramBuilder::keyboard_enhancement)),
+/// Ctrl+M and Ctrl+I arrive as `Char('m')` and `Char('i')` with
+/// `CONTROL`, and Enter and Tab are reliable.
 #[derive(Debug, Clone)]
 pub struct KeyMsg {
     /// The `crossterm::event::KeyCode` representing the key pressed.
@@ -319,24 +384,147 @@ pub struct KeyMsg {
     pub modifiers: crossterm::event::KeyModifiers,
 }
 
+impl KeyMsg {
+    /// Returns the key in `msg` and how many presses it stands for.
+    ///
+    /// A `KeyMsg` is one press and a [`KeyRepeatMsg`] carries its
+    /// `repeat_count`. Returns `None` for any other message. Models that
+    /// enable [`ProgramBuilder::coalesce_key_repeats`](crate::ProgramBuilder::coalesce_key_repeats)
+    /// can use this to handle both messages in one place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bubbletea_rs::{KeyMsg, KeyRepeatMsg, Msg};
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// let down = KeyMsg {
+    ///     key: KeyCode::Down,
+    ///     modifiers: KeyModifiers::NONE,
+    /// };
+    /// let repeated: Msg = Box::new(KeyRepeatMsg {
+    ///     key: down.clone(),
+    ///     repeat_count: 12,
+    /// });
+    /// let (key, presses) = KeyMsg::presses(&repeated).unwrap();
+    /// assert_eq!((key.key, presses), (KeyCode::Down, 12));
+    ///
+    /// let single: Msg = Box::new(down);
+    /// assert_eq!(KeyMsg::presses(&single).unwrap().1, 1);
+    /// ```
+    pub fn presses(msg: &Msg) -> Option<(&KeyMsg, u16)> {
+        if let Some(key) = msg.downcast_ref::<KeyMsg>() {
+            Some((key, 1))
+        } else {
+            msg.downcast_ref::<KeyRepeatMsg>()
+                .map(|repeat| (&repeat.key, repeat.repeat_count))
+        }
+    }
+
+    /// Returns `true` if `other` is the same key with the same modifiers.
+    pub(crate) fn same_key(&self, other: &KeyMsg) -> bool {
+        self.key == other.key && self.modifiers == other.modifiers
+    }
+}
+
+/// A key that was pressed several times in a row before the program could
+/// handle it.
+///
+/// Holding a key down makes the operating system repeat it many times a
+/// second, faster than a slow `update` or `view` can keep up with. With
+/// [`ProgramBuilder::coalesce_key_repeats`](crate::ProgramBuilder::coalesce_key_repeats)
+/// enabled, consecutive identical `KeyMsg`s that are already queued are
+/// delivered as one `KeyRepeatMsg`, so the model catches up in a single
+/// update and stops as soon as the key is released. A key press that is not
+/// followed by identical ones still arrives as a plain `KeyMsg`.
+///
+/// Use [`KeyMsg::presses`] to handle both messages alike.
+#[derive(Debug, Clone)]
+pub struct KeyRepeatMsg {
+    /// The repeated key.
+    pub key: KeyMsg,
+    /// How many identical presses this message replaces, at least 2.
+    pub repeat_count: u16,
+}
+
 /// A message indicating a mouse input event.
+///
+/// Coordinates are 0-based: the top-left cell of the terminal is `(0, 0)`.
+/// Terminals report mouse positions 1-based, so the origin is subtracted
+/// before the message is delivered, matching crossterm's convention. This
+/// holds for events from crossterm and for SGR reports read from a custom
+/// input source alike, so `x` and `y` can index straight into the lines of
+/// a view.
+///
+/// The time the event was decoded is available from [`input_timestamp`]
+/// while `update` handles it.
 #[derive(Debug, Clone)]
 pub struct MouseMsg {
-    /// The column coordinate of the mouse event.
+    /// The 0-based column of the mouse event.
     pub x: u16,
-    /// The row coordinate of the mouse event.
+    /// The 0-based row of the mouse event.
     pub y: u16,
     /// The `crossterm::event::MouseEventKind` representing the type of mouse event.
+    ///
+    /// Wheel events are reported as `ScrollUp`/`ScrollDown`, and horizontal
+    /// wheels or trackpad swipes as `ScrollLeft`/`ScrollRight`.
     pub button: crossterm::event::MouseEventKind,
     /// The `crossterm::event::KeyModifiers` active during the mouse event.
     pub modifiers: crossterm::event::KeyModifiers,
+    /// How many lines a wheel event scrolls; `0` for other events.
+    ///
+    /// A single wheel notch scrolls
+    /// [`ProgramBuilder::wheel_scroll_lines`](crate::ProgramBuilder::wheel_scroll_lines)
+    /// lines, one by default. With
+    /// [`ProgramBuilder::coalesce_wheel_events`](crate::ProgramBuilder::coalesce_wheel_events)
+    /// enabled, a queued run of wheel events in the same direction arrives
+    /// as one message whose delta adds them up, so fast scrolling does not
+    /// fall behind.
+    pub scroll_delta: u16,
+}
+
+impl MouseMsg {
+    /// Returns the 0-based column of the event. Same as `x`.
+    pub fn column(&self) -> u16 {
+        self.x
+    }
+
+    /// Returns the 0-based row of the event. Same as `y`.
+    pub fn row(&self) -> u16 {
+        self.y
+    }
+
+    /// Returns `true` for mouse wheel and trackpad scroll events.
+    pub fn is_wheel(&self) -> bool {
+        use crossterm::event::MouseEventKind::*;
+        matches!(
+            self.button,
+            ScrollUp | ScrollDown | ScrollLeft | ScrollRight
+        )
+    }
+
+    /// Returns the scroll delta one event of `kind` starts with: one notch
+    /// for a wheel event, nothing otherwise.
+    pub(crate) fn initial_delta(kind: crossterm::event::MouseEventKind) -> u16 {
+        use crossterm::event::MouseEventKind::*;
+        matches!(kind, ScrollUp | ScrollDown | ScrollLeft | ScrollRight).into()
+    }
 }
 
-/// A message indicating that text was pasted into the terminal (bracketed paste).
+/// A message indicating that text was pasted into the terminal.
+///
+/// The pasted content is captured as a single string, preserving newlines
+/// and special characters. It arrives in one of two ways:
+///
+/// - With bracketed paste enabled, the terminal marks pasted text and the
+///   message has `bracketed` set.
+/// - Without it, a paste reaches the program as a fast burst of key presses.
+///   With [`ProgramBuilder::paste_heuristic`](crate::ProgramBuilder::paste_heuristic)
+///   the burst is coalesced into one message with `bracketed` unset; without
+///   it, the model sees individual `KeyMsg`s.
 ///
-/// This message is generated when bracketed paste mode is enabled and the user
-/// pastes text into the terminal. The pasted content is captured as a single
-/// string, preserving newlines and special characters.
+/// [`Program::paste_supported`](crate::Program::paste_supported) reports
+/// whether the terminal supports bracketed paste.
 ///
 /// # Examples
 ///
@@ -344,17 +532,90 @@ pub struct MouseMsg {
 /// use bubbletea_rs::event::PasteMsg;
 ///
 /// // Handling a paste event in your model's update method
-/// let paste_msg = PasteMsg("Hello\nWorld".to_string());
+/// let paste_msg = PasteMsg::new("Hello\nWorld", true);
 /// // The text contains the exact pasted content
-/// assert_eq!(paste_msg.0, "Hello\nWorld");
+/// assert_eq!(paste_msg.text, "Hello\nWorld");
+/// assert!(paste_msg.bracketed);
 /// ```
 ///
 /// # Note
 ///
-/// Bracketed paste mode must be enabled with `EnableBracketedPasteMsg` for
-/// these messages to be generated.
+/// Pasted text comes from outside the application and may contain escape
+/// sequences or control characters, whether by accident or on purpose. The
+/// raw text is delivered unchanged; use [`sanitized`](PasteMsg::sanitized)
+/// before inserting it into an input field.
 #[derive(Debug, Clone)]
-pub struct PasteMsg(pub String);
+pub struct PasteMsg {
+    /// The pasted text, exactly as it was received.
+    pub text: String,
+    /// Whether the terminal marked the text as pasted. `false` when the
+    /// paste was inferred from a burst of key presses.
+    pub bracketed: bool,
+}
+
+impl PasteMsg {
+    /// Creates a paste message for `text`.
+    pub fn new(text: impl Into<String>, bracketed: bool) -> Self {
+        Self {
+            text: text.into(),
+            bracketed,
+        }
+    }
+
+    /// Returns the pasted text exactly as it was received.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the pasted text with escape sequences and control characters
+    /// removed.
+    ///
+    /// Tabs and newlines are kept. Carriage returns, which many terminals
+    /// send for line breaks in pasted text, are turned into newlines
+    /// (`\r\n` becomes a single `\n`). Every other control character,
+    /// including the C1 range, is dropped, and complete ANSI escape
+    /// sequences are removed rather than leaving their parameters behind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bubbletea_rs::PasteMsg;
+    ///
+    /// let paste = PasteMsg::new("\x1b[31mred\x1b[0m\r\nnext\x07", true);
+    /// assert_eq!(paste.sanitized(), "red\nnext");
+    /// ```
+    pub fn sanitized(&self) -> String {
+        crate::ansi::strip(&self.text.replace("\r\n", "\n"))
+            .chars()
+            .filter_map(|ch| match ch {
+                '\r' => Some('\n'),
+                '\t' | '\n' => Some(ch),
+                _ if ch.is_control() => None,
+                _ => Some(ch),
+            })
+            .collect()
+    }
+}
+
+/// A piece of a bracketed paste, delivered while the rest is still being
+/// read.
+///
+/// With [`ProgramBuilder::paste_chunk_size`](crate::ProgramBuilder::paste_chunk_size),
+/// a bracketed paste arrives as `PasteChunkMsg`s, in order, followed by one
+/// [`PasteEndMsg`], instead of as a single [`PasteMsg`]. Chunks are split at
+/// character boundaries, so each holds at most the chunk size in bytes
+/// unless one character is longer. Appending their text gives the pasted
+/// text.
+#[derive(Debug, Clone)]
+pub struct PasteChunkMsg {
+    /// The next piece of the pasted text, exactly as it was received.
+    pub text: String,
+}
+
+/// A message indicating that a paste delivered as [`PasteChunkMsg`]s is
+/// complete.
+#[derive(Debug, Clone, Copy)]
+pub struct PasteEndMsg;
 
 /// A message indicating a change in the terminal window size.
 #[derive(Debug, Clone)]
@@ -371,13 +632,76 @@ pub struct WindowSizeMsg {
 #[derive(Debug, Clone)]
 pub struct QuitMsg;
 
+/// A message to quit the application and hand a value back to the caller.
+///
+/// Produced by [`quit_with`](crate::command::quit_with). The program keeps
+/// `value` and passes a plain [`QuitMsg`] to the model in its place, so
+/// models handle both kinds of quit the same way. The value is returned by
+/// [`Program::run_with_result`](crate::Program::run_with_result).
+pub struct QuitWithMsg {
+    /// The value to return from the program.
+    pub value: Box<dyn Any + Send>,
+}
+
+impl std::fmt::Debug for QuitWithMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuitWithMsg").finish_non_exhaustive()
+    }
+}
+
+/// Stands in for a command that produced no message.
+///
+/// [`after_all`](crate::command::after_all) puts one in its collected
+/// messages for each such command, so every command keeps its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoMsg;
+
 /// A message to forcefully kill the application immediately.
 ///
 /// Sending this message to the `Program` will cause it to terminate as soon as
 /// possible. The event loop will stop without invoking the model's `update` and
-/// will return an `Error::ProgramKilled`.
-#[derive(Debug, Clone)]
-pub struct KillMsg;
+/// will return an `Error::ProgramKilled` carrying `exit_code`.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::KillMsg;
+///
+/// let by_signal = KillMsg::by_signal(15);
+/// assert_eq!(by_signal.exit_code, 143);
+///
+/// let by_user = KillMsg::by_user("deployment cancelled".to_string());
+/// assert_eq!(by_user.exit_code, 1);
+/// assert_eq!(by_user.reason, "deployment cancelled");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillMsg {
+    /// Why the program is being terminated.
+    pub reason: String,
+    /// The process exit code the caller should use.
+    pub exit_code: i32,
+}
+
+impl KillMsg {
+    /// Creates a kill message for termination by the signal `signum`.
+    ///
+    /// The exit code follows the shell convention of `128 + signum`.
+    pub fn by_signal(signum: i32) -> Self {
+        Self {
+            reason: format!("killed by signal {signum}"),
+            exit_code: 128 + signum,
+        }
+    }
+
+    /// Creates a kill message for a termination requested by the user or
+    /// application, with exit code 1.
+    pub fn by_user(reason: String) -> Self {
+        Self {
+            reason,
+            exit_code: 1,
+        }
+    }
+}
 
 /// A message to signal an application interruption.
 ///
@@ -385,6 +709,21 @@ pub struct KillMsg;
 #[derive(Debug, Clone)]
 pub struct InterruptMsg;
 
+/// A message sent to the model when the terminal has gone away.
+///
+/// Delivered once, after output to the terminal failed for good (for
+/// example with a broken pipe when the terminal emulator closed) or the
+/// process received `SIGHUP`. Nothing is rendered afterwards. The command
+/// returned from `update` is still run, for a short while, so the model can
+/// persist its state; the program then stops with `Error::TerminalLost`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalLostMsg {
+    /// The kind of the I/O error that ended the output.
+    pub kind: std::io::ErrorKind,
+    /// A description of the failure.
+    pub reason: String,
+}
+
 /// A message to signal the application to suspend.
 ///
 /// This can be used to temporarily pause the application, for example, when
@@ -397,13 +736,93 @@ pub struct SuspendMsg;
 pub struct ResumeMsg;
 
 /// A message indicating that the terminal gained focus.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FocusMsg;
 
 /// A message indicating that the terminal lost focus.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BlurMsg;
 
+/// A message delivered when no input has arrived for the period set with
+/// [`ProgramBuilder::idle_after`](crate::ProgramBuilder::idle_after).
+///
+/// Sent once per quiet spell: the next key press, mouse event or paste
+/// starts a new one. Messages that aren't input, such as ticks, don't
+/// count as activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdleMsg {
+    /// How long it has been since the last input, or since the program
+    /// started if there was none.
+    pub idle_for: std::time::Duration,
+}
+
+/// Focus state of one pane in a model composed of several panes.
+///
+/// The terminal reports focus for the whole window, so a composed model
+/// decides which pane a `FocusMsg` or `BlurMsg` belongs to and hands it to
+/// that pane's `PaneFocus`.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{BlurMsg, FocusMsg, Msg, PaneFocus};
+///
+/// let mut editor = PaneFocus::default();
+/// assert!(editor.update(&(Box::new(FocusMsg) as Msg)));
+/// assert!(editor.focused);
+///
+/// editor.update(&(Box::new(BlurMsg) as Msg));
+/// assert!(!editor.focused);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PaneFocus {
+    /// Whether the pane has focus.
+    pub focused: bool,
+}
+
+impl PaneFocus {
+    /// Creates a pane focus state.
+    pub fn new(focused: bool) -> Self {
+        Self { focused }
+    }
+
+    /// Applies a `FocusMsg` or `BlurMsg`.
+    ///
+    /// Returns `true` if `msg` was one of them, and `false`, leaving the
+    /// state unchanged, for any other message.
+    pub fn update(&mut self, msg: &Msg) -> bool {
+        if msg.is::<FocusMsg>() {
+            self.focused = true;
+        } else if msg.is::<BlurMsg>() {
+            self.focused = false;
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+/// A message carrying an error produced by a command or background task.
+///
+/// Long-running commands (such as file watchers) cannot return a `Result`
+/// to the model, so failures are delivered through the normal message flow
+/// instead. Models that care about these errors can downcast to `ErrorMsg`
+/// in their `update` method.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{Error, Msg};
+/// use bubbletea_rs::event::ErrorMsg;
+///
+/// let msg: Msg = Box::new(ErrorMsg(Error::CommandExecution("boom".to_string())));
+/// if let Some(ErrorMsg(err)) = msg.downcast_ref::<ErrorMsg>() {
+///     assert_eq!(err.to_string(), "Command execution error: boom");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ErrorMsg(pub crate::Error);
+
 /// An internal message type used to batch multiple messages together.
 /// This is not exposed as a public API.
 #[derive(Debug)]
@@ -416,6 +835,73 @@ pub struct BatchMsgInternal {
 /// This enables non-blocking batch operations that spawn commands immediately.
 pub struct BatchCmdMsg(pub Vec<crate::Cmd>);
 
+/// A message containing commands to run with limited concurrency.
+///
+/// Produced by `batch_limited()`. The `Program` runs at most `concurrency`
+/// of the commands at a time, starting the next as each one finishes, and
+/// delivers each message as soon as its command completes. Commands still
+/// queued when the program shuts down are never started.
+pub struct BatchLimitedMsg {
+    /// How many commands may run at once; zero is treated as one.
+    pub concurrency: usize,
+    /// The commands to run, started in order.
+    pub cmds: Vec<crate::Cmd>,
+}
+
+impl std::fmt::Debug for BatchLimitedMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchLimitedMsg")
+            .field("concurrency", &self.concurrency)
+            .field("cmds", &self.cmds.len())
+            .finish()
+    }
+}
+
+/// A message delivered when a cancellable sequence stops early.
+///
+/// Produced by `sequence_until()` and `sequence_until_msg()` in place of the
+/// message that triggered the cancellation. Steps after `step` are not run.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::event::CancelledMsg;
+///
+/// let msg = CancelledMsg { step: 2 };
+/// assert_eq!(msg.step, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelledMsg {
+    /// Zero-based index of the step whose message cancelled the sequence.
+    pub step: usize,
+}
+
+/// An internal message carrying the steps of a `sequence_with_progress()`.
+///
+/// The `Program` runs the steps one after another in a background task,
+/// sending the progress message for each step before running it and the
+/// step's own message as soon as it completes.
+///
+/// # Note
+///
+/// This is not exposed as a public API and should not be used directly
+/// by application code. Use the `sequence_with_progress()` command instead.
+pub struct SequenceMsgInternal {
+    /// The commands to run, in order.
+    pub steps: Vec<crate::Cmd>,
+    /// Builds the progress message from `(step_index, total_steps)`.
+    pub on_step: Box<dyn Fn(usize, usize) -> Msg + Send>,
+}
+
+impl std::fmt::Debug for SequenceMsgInternal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SequenceMsgInternal")
+            .field("steps", &self.steps.len())
+            .field("on_step", &"<closure>")
+            .finish()
+    }
+}
+
 /// A message to signal the terminal to enter the alternate screen buffer.
 #[derive(Debug, Clone)]
 pub struct EnterAltScreenMsg;
@@ -436,6 +922,16 @@ pub struct EnableMouseAllMotionMsg;
 #[derive(Debug, Clone)]
 pub struct DisableMouseMsg;
 
+/// A message to start a mouse capture: all mouse motion is reported until
+/// the next button release, then the previous mouse mode comes back.
+#[derive(Debug, Clone)]
+pub struct BeginMouseCaptureMsg;
+
+/// A message to end a mouse capture early, restoring the mouse mode that
+/// was enabled before it began.
+#[derive(Debug, Clone)]
+pub struct EndMouseCaptureMsg;
+
 /// A message to signal the terminal to enable bracketed paste mode.
 #[derive(Debug, Clone)]
 pub struct EnableBracketedPasteMsg;
@@ -460,16 +956,575 @@ pub struct ShowCursorMsg;
 #[derive(Debug, Clone)]
 pub struct HideCursorMsg;
 
+/// A message to signal the terminal to save the cursor position.
+///
+/// Most terminals keep only one saved position, so saves do not nest.
+#[derive(Debug, Clone)]
+pub struct SaveCursorMsg;
+
+/// A message to signal the terminal to restore the saved cursor position.
+#[derive(Debug, Clone)]
+pub struct RestoreCursorMsg;
+
 /// A message to signal the terminal to clear the screen.
 #[derive(Debug, Clone)]
 pub struct ClearScreenMsg;
 
+/// A message to signal the terminal to clear from the cursor to the end of
+/// its row.
+#[derive(Debug, Clone)]
+pub struct ClearLineMsg;
+
+/// A message to signal the terminal to clear from the cursor to the end of
+/// the screen.
+#[derive(Debug, Clone)]
+pub struct ClearToEndOfScreenMsg;
+
+/// A message to signal the terminal to clear from the top of the screen to
+/// the cursor.
+#[derive(Debug, Clone)]
+pub struct ClearFromStartOfScreenMsg;
+
+/// A message to stop drawing the view to the terminal.
+///
+/// Messages are still processed while rendering is paused. Send a
+/// `ResumeRenderMsg` to draw again.
+#[derive(Debug, Clone)]
+pub struct PauseRenderMsg;
+
+/// A message to resume drawing after a `PauseRenderMsg`.
+///
+/// The next frame is drawn in full.
+#[derive(Debug, Clone)]
+pub struct ResumeRenderMsg;
+
+/// A message asking the program to query the terminal's keyboard
+/// enhancement support.
+///
+/// The program answers with a [`KeyboardEnhancementsMsg`]. See
+/// [`query_keyboard_enhancements`](crate::command::query_keyboard_enhancements).
+#[derive(Debug, Clone)]
+pub struct QueryKeyboardEnhancementsMsg;
+
+/// A message reporting the terminal's keyboard enhancement support, in reply
+/// to [`query_keyboard_enhancements`](crate::command::query_keyboard_enhancements).
+///
+/// The terminal's reply is taken off the input stream, so it never reaches
+/// the model as key presses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyboardEnhancementsMsg {
+    /// The keyboard enhancement flags the terminal reported as active, or
+    /// `None` if it does not support the protocol or did not answer in time.
+    pub supported_flags: Option<KeyboardEnhancementFlags>,
+}
+
+impl KeyboardEnhancementsMsg {
+    /// Returns `true` if the terminal supports the keyboard enhancement
+    /// protocol.
+    pub fn is_supported(&self) -> bool {
+        self.supported_flags.is_some()
+    }
+}
+
+/// The input modes currently enabled on the terminal.
+///
+/// The program starts with the modes set on its
+/// [`ProgramBuilder`](crate::ProgramBuilder) and updates them as commands
+/// such as [`enable_bracketed_paste`](crate::command::enable_bracketed_paste)
+/// run. The same modes are restored when the program takes the terminal
+/// back after [`exec_process`](crate::command::exec_process) or a suspend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputModes {
+    /// Whether bracketed paste is enabled, so pastes arrive as one
+    /// `PasteMsg`.
+    pub bracketed_paste: bool,
+    /// Which mouse events are reported.
+    pub mouse_motion: crate::MouseMotion,
+    /// Whether focus changes are reported as `FocusMsg` and `BlurMsg`.
+    pub focus_reporting: bool,
+}
+
+impl InputModes {
+    /// Updates the modes for a terminal mode message, returning `true` if
+    /// they changed.
+    pub(crate) fn apply(&mut self, msg: &Msg) -> bool {
+        let before = *self;
+        if msg.is::<EnableBracketedPasteMsg>() {
+            self.bracketed_paste = true;
+        } else if msg.is::<DisableBracketedPasteMsg>() {
+            self.bracketed_paste = false;
+        } else if msg.is::<EnableMouseCellMotionMsg>() {
+            self.mouse_motion = crate::MouseMotion::Cell;
+        } else if msg.is::<EnableMouseAllMotionMsg>() {
+            self.mouse_motion = crate::MouseMotion::All;
+        } else if msg.is::<DisableMouseMsg>() {
+            self.mouse_motion = crate::MouseMotion::None;
+        } else if msg.is::<EnableReportFocusMsg>() {
+            self.focus_reporting = true;
+        } else if msg.is::<DisableReportFocusMsg>() {
+            self.focus_reporting = false;
+        }
+        *self != before
+    }
+}
+
+/// A message delivered to the model whenever the enabled [`InputModes`]
+/// change.
+///
+/// Lets a model know, for example, whether to expect `PasteMsg`s without
+/// keeping its own copy of the mode state, which could fall out of sync.
+/// Enabling a mode that is already enabled sends nothing. No message is
+/// sent for the modes the program starts with; those are the ones set on
+/// the builder, less any reported in a [`ModesDegradedMsg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModesMsg(pub InputModes);
+
+/// A message delivered at startup when terminal modes set on the
+/// [`ProgramBuilder`](crate::ProgramBuilder) could not be enabled.
+///
+/// The program runs without them instead of failing, unless
+/// [`strict_modes`](crate::ProgramBuilder::strict_modes) is set. Only sent
+/// if at least one mode failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModesDegradedMsg {
+    /// The input modes actually enabled.
+    pub modes: InputModes,
+    /// Each mode that could not be enabled, with the error it failed with.
+    pub failures: Vec<(crate::TerminalMode, String)>,
+}
+
+/// What the program's input handler recorded about a key or mouse event
+/// when it decoded it. See [`input_timestamp`] and [`input_is_ambiguous`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InputStamp {
+    /// When the event was decoded
+    pub(crate) at: Instant,
+    /// Whether the key may have been another key press encoded the same way
+    pub(crate) ambiguous: bool,
+}
+
+/// An internal wrapper carrying the [`InputStamp`] of a decoded key or
+/// mouse event.
+///
+/// The program unwraps it as soon as it is received, so nothing else ever
+/// sees it.
+pub(crate) struct TimestampedMsg {
+    pub(crate) msg: Msg,
+    pub(crate) stamp: InputStamp,
+}
+
+impl TimestampedMsg {
+    /// Splits a received message into the message and its stamp, if it was
+    /// wrapped.
+    pub(crate) fn split(msg: Msg) -> (Msg, Option<InputStamp>) {
+        match msg.downcast::<TimestampedMsg>() {
+            Ok(stamped) => (stamped.msg, Some(stamped.stamp)),
+            Err(msg) => (msg, None),
+        }
+    }
+
+    /// Wraps `msg` with its stamp, if there is one.
+    pub(crate) fn join(msg: Msg, stamp: Option<InputStamp>) -> Msg {
+        match stamp {
+            Some(stamp) => Box::new(TimestampedMsg { msg, stamp }),
+            None => msg,
+        }
+    }
+}
+
+thread_local! {
+    static INPUT_STAMP: std::cell::Cell<Option<InputStamp>> = const { std::cell::Cell::new(None) };
+}
+
+/// Returns when the input event behind the message `Model::update` is
+/// handling was decoded.
+///
+/// `KeyMsg`, `KeyRepeatMsg` and `MouseMsg` from the terminal or a custom
+/// input source are stamped as soon as they are decoded, so the time does
+/// not include however long the message waited in the queue. That makes it
+/// suitable for double-click detection or measuring typing speed, where
+/// sampling `Instant::now()` in `update` would be skewed by queueing. A
+/// `KeyRepeatMsg` reports its last press.
+///
+/// A key or mouse message sent rather than decoded, such as one injected
+/// through [`Program::sender`](crate::Program::sender), reports when the
+/// program received it. Returns `None` outside `update` and for other
+/// messages.
+///
+/// The time belongs to the message being handled, not to the message
+/// value: a model that keeps a `KeyMsg` or `MouseMsg` to look at later
+/// should keep its timestamp alongside it.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{event, IntoCmd, Model, MouseMsg, Msg};
+/// use std::time::{Duration, Instant};
+///
+/// struct Canvas {
+///     last_click: Option<Instant>,
+///     double_clicked: bool,
+/// }
+///
+/// impl Model for Canvas {
+///     fn init() -> (Self, Option<bubbletea_rs::Cmd>) {
+///         (Self { last_click: None, double_clicked: false }, None)
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if msg.is::<MouseMsg>() {
+///             if let Some(at) = event::input_timestamp() {
+///                 self.double_clicked = self
+///                     .last_click
+///                     .is_some_and(|last| at - last < Duration::from_millis(400));
+///                 self.last_click = Some(at);
+///             }
+///         }
+///     }
+///
+///     fn view(&self) -> String {
+///         String::new()
+///     }
+/// }
+/// ```
+pub fn input_timestamp() -> Option<Instant> {
+    INPUT_STAMP.with(|stamp| stamp.get()).map(|stamp| stamp.at)
+}
+
+/// Returns `true` if the key `Model::update` is handling may have been a
+/// different key press that the terminal encodes the same way.
+///
+/// The program's input handler decides this when it decodes the key: an
+/// unmodified `Enter` (which may have been Ctrl+M) or `Tab` (which may
+/// have been Ctrl+I) is ambiguous unless the keyboard enhancement protocol
+/// was active on the program's terminal at the time. Applications should
+/// not bind different actions to such pairs while this returns `true`. A
+/// `KeyRepeatMsg` of such keys is ambiguous the same way.
+///
+/// Returns `false` outside `update`, for other messages, and for keys sent
+/// rather than decoded, such as those injected through
+/// [`Program::sender`](crate::Program::sender). Like [`input_timestamp`],
+/// the answer belongs to the message being handled.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{event, IntoCmd, KeyMsg, Model, Msg};
+/// use crossterm::event::KeyCode;
+///
+/// struct Editor {
+///     submitted: bool,
+/// }
+///
+/// impl Model for Editor {
+///     fn init() -> (Self, Option<bubbletea_rs::Cmd>) {
+///         (Self { submitted: false }, None)
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(key) = msg.downcast_ref::<KeyMsg>() {
+///             // Ctrl+M inserts a line break where it can be told apart
+///             if key.key == KeyCode::Enter && !event::input_is_ambiguous() {
+///                 self.submitted = true;
+///             }
+///         }
+///     }
+///
+///     fn view(&self) -> String {
+///         String::new()
+///     }
+/// }
+/// ```
+pub fn input_is_ambiguous() -> bool {
+    INPUT_STAMP
+        .with(|stamp| stamp.get())
+        .is_some_and(|stamp| stamp.ambiguous)
+}
+
+/// Runs `f` with [`input_timestamp`] and [`input_is_ambiguous`] reporting
+/// `stamp`.
+pub(crate) fn with_input_stamp<R>(stamp: Option<InputStamp>, f: impl FnOnce() -> R) -> R {
+    let previous = INPUT_STAMP.with(|current| current.replace(stamp));
+    let result = f();
+    INPUT_STAMP.with(|current| current.set(previous));
+    result
+}
+
+/// What [`ChordMatcher::feed`] made of a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChordMatch<A> {
+    /// The key completed the chord bound to this action.
+    Matched(A),
+    /// The key started or continued a chord; more keys are needed.
+    Pending,
+    /// The key is not part of any chord; handle it as a plain key.
+    NoMatch,
+}
+
+/// Detects multi-key sequences such as Vim's `g g` or `d d`.
+///
+/// Bind each sequence to an action, keep the matcher in the model, and
+/// feed it every `KeyMsg`. It reports [`ChordMatch::Matched`] when a bound
+/// sequence completes with no more than `timeout` between consecutive
+/// keys. A key that does not continue the sequence so far, or arrives too
+/// late, starts over: it is tried as the first key of a new sequence.
+///
+/// If one bound sequence is a prefix of another, the shorter one wins.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::event::{ChordMatch, ChordMatcher};
+/// use bubbletea_rs::KeyMsg;
+/// use crossterm::event::{KeyCode, KeyModifiers};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Action {
+///     Top,
+///     DeleteLine,
+/// }
+///
+/// let mut chords = ChordMatcher::new(Duration::from_millis(500))
+///     .bind("g g", Action::Top)
+///     .bind("d d", Action::DeleteLine);
+///
+/// let g = KeyMsg { key: KeyCode::Char('g'), modifiers: KeyModifiers::NONE };
+/// assert_eq!(chords.feed(&g), ChordMatch::Pending);
+/// assert_eq!(chords.feed(&g), ChordMatch::Matched(Action::Top));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChordMatcher<A> {
+    chords: Vec<(Vec<crate::KeySpec>, A)>,
+    timeout: std::time::Duration,
+    pending: Vec<KeyMsg>,
+    last_key_at: Option<Instant>,
+}
+
+impl<A: Clone> ChordMatcher<A> {
+    /// Creates a matcher with no chords that allows at most `timeout`
+    /// between the keys of a chord.
+    pub fn new(timeout: std::time::Duration) -> Self {
+        Self {
+            chords: Vec::new(),
+            timeout,
+            pending: Vec::new(),
+            last_key_at: None,
+        }
+    }
+
+    /// Binds the whitespace-separated key specs in `chord`, such as
+    /// `"g g"` or `"ctrl+w j"`, to `action`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chord` is empty or a key spec in it is invalid. Use
+    /// [`try_bind`](Self::try_bind) for chords from user configuration.
+    pub fn bind(self, chord: &str, action: A) -> Self {
+        match self.try_bind(chord, action) {
+            Ok(matcher) => matcher,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Like [`bind`](Self::bind), but returns an error for an empty chord
+    /// or an invalid key spec.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Configuration` if `chord` has no keys or a key spec
+    /// cannot be parsed.
+    pub fn try_bind(mut self, chord: &str, action: A) -> Result<Self, crate::Error> {
+        let keys = chord
+            .split_whitespace()
+            .map(crate::KeySpec::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err(crate::Error::Configuration("empty key chord".to_string()));
+        }
+        self.chords.push((keys, action));
+        Ok(self)
+    }
+
+    /// Feeds a key, timed by [`input_timestamp`] when it is known and by
+    /// the current time otherwise.
+    pub fn feed(&mut self, key: &KeyMsg) -> ChordMatch<A> {
+        let at = input_timestamp().unwrap_or_else(Instant::now);
+        self.feed_at(key, at)
+    }
+
+    /// Feeds a key pressed at `at`.
+    pub fn feed_at(&mut self, key: &KeyMsg, at: Instant) -> ChordMatch<A> {
+        let late = self
+            .last_key_at
+            .is_some_and(|last| at.saturating_duration_since(last) > self.timeout);
+        if late {
+            self.pending.clear();
+        }
+        let continuing = !self.pending.is_empty();
+        self.pending.push(key.clone());
+        let mut result = self.check();
+        if matches!(result, ChordMatch::NoMatch) && continuing {
+            // The key may start a chord of its own
+            self.pending.clear();
+            self.pending.push(key.clone());
+            result = self.check();
+        }
+        match result {
+            ChordMatch::Pending => self.last_key_at = Some(at),
+            _ => self.reset(),
+        }
+        result
+    }
+
+    /// Forgets the keys of a chord in progress.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_key_at = None;
+    }
+
+    /// Returns `true` while a chord has been started but not completed.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Matches the pending keys against the bound chords.
+    fn check(&self) -> ChordMatch<A> {
+        let mut longer = false;
+        for (keys, action) in &self.chords {
+            let prefix = keys.len() >= self.pending.len()
+                && keys
+                    .iter()
+                    .zip(&self.pending)
+                    .all(|(spec, key)| spec.matches(key));
+            if !prefix {
+                continue;
+            }
+            if keys.len() == self.pending.len() {
+                return ChordMatch::Matched(action.clone());
+            }
+            longer = true;
+        }
+        if longer {
+            ChordMatch::Pending
+        } else {
+            ChordMatch::NoMatch
+        }
+    }
+}
+
+/// An internal message that ends a keyboard enhancement query the terminal
+/// has not answered.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KeyboardEnhancementsTimeoutMsg {
+    /// The query this timeout belongs to
+    pub(crate) query: u64,
+}
+
+/// A message to ring the terminal bell.
+///
+/// The program writes the BEL control character (`\x07`) to the terminal.
+/// Whether this produces a sound, a notification, or nothing at all depends
+/// on the terminal emulator's settings.
+#[derive(Debug, Clone)]
+pub struct BellMsg;
+
+/// A message to flash the terminal screen as a visual bell.
+///
+/// The program switches the terminal to reverse video, re-renders the view,
+/// and switches back shortly afterwards by sending itself an
+/// `EndVisualBellMsg`.
+#[derive(Debug, Clone)]
+pub struct VisualBellMsg;
+
+/// An internal message that ends a visual bell flash.
+///
+/// # Note
+///
+/// This is sent by the program itself after a `VisualBellMsg` and should not
+/// be needed by application code.
+#[derive(Debug, Clone)]
+pub struct EndVisualBellMsg;
+
 /// A message to signal the terminal to request its current window size.
 ///
 /// The terminal will respond with a `WindowSizeMsg` containing its dimensions.
 #[derive(Debug, Clone)]
 pub struct RequestWindowSizeMsg;
 
+/// A message that sets the size the program assumes for its terminal.
+///
+/// Produced by [`command::set_viewport_size`](crate::command::set_viewport_size).
+/// The program delivers a `WindowSizeMsg` with these dimensions to the
+/// model, and answers later window size requests with them instead of
+/// querying the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetViewportSizeMsg {
+    /// The width in columns.
+    pub width: u16,
+    /// The height in rows.
+    pub height: u16,
+}
+
+/// A message delivered when the terminal's capabilities change while the
+/// program runs.
+///
+/// Only sent with
+/// [`requery_capabilities_on_resize`](crate::ProgramBuilder::requery_capabilities_on_resize):
+/// the program detects capabilities again on each resize, and sends the new
+/// ones if they differ, for example when a tmux session is reattached from
+/// a terminal with truecolor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapabilitiesMsg(pub crate::TerminalCapabilities);
+
+/// A message asking the program for a [`ViewportSizeMsg`].
+///
+/// Produced by [`command::viewport_size`](crate::command::viewport_size).
+#[derive(Debug, Clone)]
+pub struct RequestViewportSizeMsg;
+
+/// The terminal's size, and the area of it the program gives to the view.
+///
+/// Sent in answer to [`command::viewport_size`](crate::command::viewport_size),
+/// and again whenever either size changes from then on. The content area
+/// leaves out what the program itself draws or reserves: columns beyond
+/// `max_width`, the rows above an inline log region's live rows, and the
+/// row the debug input overlay covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewportSizeMsg {
+    /// The terminal's width in columns, or the width set with
+    /// `set_viewport_size`.
+    pub terminal_width: u16,
+    /// The terminal's height in rows, or the height set with
+    /// `set_viewport_size`.
+    pub terminal_height: u16,
+    /// Columns available to the view.
+    pub content_width: u16,
+    /// Rows available to the view.
+    pub content_height: u16,
+}
+
+/// A message that shows or hides the debug input overlay.
+///
+/// Produced by
+/// [`command::set_debug_input_overlay`](crate::command::set_debug_input_overlay).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetDebugInputOverlayMsg(pub bool);
+
+/// An internal message asking the program for the last frame it drew.
+///
+/// Produced by [`command::screenshot`](crate::command::screenshot). The
+/// program answers with the message `on_done` builds from the frame.
+pub(crate) struct ScreenshotMsg {
+    pub(crate) on_done: Box<dyn FnOnce(String) -> Msg + Send>,
+}
+
+impl std::fmt::Debug for ScreenshotMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScreenshotMsg").finish_non_exhaustive()
+    }
+}
+
 /// A message to print a line to the terminal.
 ///
 /// This message causes the program to print text to the terminal output.
@@ -487,7 +1542,9 @@ pub struct RequestWindowSizeMsg;
 /// # Note
 ///
 /// This bypasses the normal view rendering and directly outputs to the terminal.
-/// Use sparingly as it can interfere with the TUI display.
+/// The cursor position is saved before printing and restored afterwards, so
+/// the next frame is still drawn where the renderer expects. Use sparingly
+/// as it can interfere with the TUI display.
 #[derive(Debug, Clone)]
 pub struct PrintMsg(pub String);
 
@@ -507,12 +1564,22 @@ pub struct PrintMsg(pub String);
 ///
 /// # Note
 ///
-/// This bypasses the normal view rendering and directly outputs to the terminal.
+/// This bypasses the normal view rendering and directly outputs to the terminal,
+/// saving and restoring the cursor position around the output.
 /// Useful for progress indicators or custom formatting that requires precise
 /// control over newlines and carriage returns.
 #[derive(Debug, Clone)]
 pub struct PrintfMsg(pub String);
 
+/// A message carrying a short status line for screen readers, such as
+/// "Selected row 5: Tokyo".
+///
+/// Printed as a plain line in accessible mode (see
+/// [`ProgramBuilder::accessible`](crate::ProgramBuilder::accessible)) and
+/// dropped otherwise, so models can announce changes unconditionally.
+#[derive(Debug, Clone)]
+pub struct AnnounceMsg(pub String);
+
 /// A message to set the terminal window title.
 ///
 /// This message updates the terminal window's title bar with the provided string.
@@ -535,6 +1602,109 @@ pub struct PrintfMsg(pub String);
 #[derive(Debug, Clone)]
 pub struct SetWindowTitleMsg(pub String);
 
+/// A message to set the window title, saving the current one.
+///
+/// The saved title is restored by a matching `PopWindowTitleMsg`, or when the
+/// program exits.
+#[derive(Debug, Clone)]
+pub struct PushWindowTitleMsg(pub String);
+
+/// A message to restore the window title saved by the last
+/// `PushWindowTitleMsg`.
+#[derive(Debug, Clone)]
+pub struct PopWindowTitleMsg;
+
+/// A message asking the program to hand the terminal to an external process.
+///
+/// [`exec_process`](crate::command::exec_process) covers the common case. A
+/// command returns this message itself when it has to decide what to run
+/// asynchronously, for example picking an editor after probing the system.
+/// The program intercepts it (it never reaches the model), releases the
+/// terminal, runs `command` with the terminal's stdin, stdout and stderr,
+/// restores the terminal, and then delivers `callback`'s message with the
+/// process's exit status. Variables set with
+/// [`ProgramBuilder::with_environment`](crate::ProgramBuilder::with_environment)
+/// are applied to `command`.
+///
+/// # Ordering
+///
+/// - Messages queued before the request are handled before the terminal is
+///   released.
+/// - While the process runs, messages still reach the model, but nothing is
+///   drawn and recurring timers are paused, as with `exec_process`.
+/// - The callback's message is queued only after the terminal has been
+///   restored, behind any messages that arrived while the process ran. The
+///   frame drawn after it is a full redraw.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{Cmd, ExecRequestMsg, Msg};
+/// use std::process::Command;
+///
+/// #[derive(Debug)]
+/// struct EditorClosedMsg(bool);
+///
+/// fn open_editor(path: String) -> Cmd {
+///     Box::pin(async move {
+///         let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+///         let mut command = Command::new(editor);
+///         command.arg(path);
+///         let request = ExecRequestMsg::new(command, |status| {
+///             let ok = status.map(|status| status.success()).unwrap_or(false);
+///             Box::new(EditorClosedMsg(ok)) as Msg
+///         });
+///         Some(Box::new(request) as Msg)
+///     })
+/// }
+/// ```
+pub struct ExecRequestMsg {
+    /// The process to run.
+    pub command: std::process::Command,
+    /// Turns the process's exit status into the message delivered afterwards.
+    pub callback: Box<dyn FnOnce(std::io::Result<std::process::ExitStatus>) -> Msg + Send>,
+}
+
+impl ExecRequestMsg {
+    /// Creates a request to run `command`, delivering `callback`'s message
+    /// when it exits.
+    pub fn new<F>(command: std::process::Command, callback: F) -> Self
+    where
+        F: FnOnce(std::io::Result<std::process::ExitStatus>) -> Msg + Send + 'static,
+    {
+        Self {
+            command,
+            callback: Box::new(callback),
+        }
+    }
+}
+
+impl std::fmt::Debug for ExecRequestMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecRequestMsg")
+            .field("command", &self.command)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An internal message asking the program to release the terminal to an
+/// external process.
+///
+/// The program stops drawing, pauses recurring timers and then answers on
+/// `released`.
+pub(crate) struct ExecStartMsg {
+    pub(crate) released: tokio::sync::oneshot::Sender<()>,
+}
+
+/// An internal message telling the program that an external process started
+/// with an `ExecStartMsg` has finished.
+///
+/// The program takes the terminal back, resumes recurring timers and then
+/// answers on `restored`. The next frame is drawn in full.
+pub(crate) struct ExecEndMsg {
+    pub(crate) restored: tokio::sync::oneshot::Sender<()>,
+}
+
 /// An internal message used to start a recurring timer.
 ///
 /// This structure is used internally by the framework to manage recurring
@@ -554,6 +1724,10 @@ pub struct EveryMsgInternal {
     pub cancellation_token: CancellationToken,
     /// Unique identifier for this timer instance.
     pub timer_id: u64,
+    /// How the timer reacts when the event loop falls behind.
+    pub missed_tick_behavior: tokio::time::MissedTickBehavior,
+    /// Timer group the timer belongs to, if any.
+    pub group: Option<u64>,
 }
 
 impl std::fmt::Debug for EveryMsgInternal {
@@ -561,6 +1735,40 @@ impl std::fmt::Debug for EveryMsgInternal {
         f.debug_struct("EveryMsgInternal")
             .field("duration", &self.duration)
             .field("timer_id", &self.timer_id)
+            .field("missed_tick_behavior", &self.missed_tick_behavior)
+            .field("group", &self.group)
+            .field("func", &"<closure>")
+            .finish()
+    }
+}
+
+/// An internal message used to start a frame-rate timer.
+///
+/// This structure is used internally by the framework to manage timers
+/// created with the `every_precise()` command. Unlike `EveryMsgInternal`,
+/// ticks are scheduled against absolute deadlines so that rounding errors
+/// do not accumulate across frames.
+///
+/// # Note
+///
+/// This is not exposed as a public API and should not be used directly
+/// by application code. Use the `every_precise()` command function instead.
+pub struct EveryPreciseMsgInternal {
+    /// Interval between frames, derived from the target frames per second.
+    pub interval: std::time::Duration,
+    /// Function invoked on each frame with the instant the frame fired.
+    pub func: Box<dyn Fn(std::time::Instant) -> Msg + Send>,
+    /// Token used to cancel the running timer.
+    pub cancellation_token: CancellationToken,
+    /// Unique identifier for this timer instance.
+    pub timer_id: u64,
+}
+
+impl std::fmt::Debug for EveryPreciseMsgInternal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EveryPreciseMsgInternal")
+            .field("interval", &self.interval)
+            .field("timer_id", &self.timer_id)
             .field("func", &"<closure>")
             .finish()
     }
@@ -613,3 +1821,68 @@ pub struct CancelTimerMsg {
 /// - Error recovery scenarios
 #[derive(Debug, Clone)]
 pub struct CancelAllTimersMsg;
+
+/// A message that pauses the timers of a group.
+///
+/// Produced by [`command::pause_timer_group`](crate::command::pause_timer_group).
+/// Recurring timers in the group stop firing, and one-shot ticks that fire
+/// hold their messages, until a [`ResumeTimerGroupMsg`] for the group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PauseTimerGroupMsg {
+    /// The group to pause.
+    pub group: u64,
+}
+
+/// A message that resumes the timers of a paused group.
+///
+/// Produced by
+/// [`command::resume_timer_group`](crate::command::resume_timer_group).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeTimerGroupMsg {
+    /// The group to resume.
+    pub group: u64,
+}
+
+/// An internal message carrying the message of a grouped one-shot tick, so
+/// the program can hold it while the group is paused.
+#[derive(Debug)]
+pub(crate) struct GroupedTickMsg {
+    pub(crate) group: u64,
+    pub(crate) msg: Msg,
+}
+
+/// An internal message asking the program to deliver `msg` once `key` has
+/// been quiet for `delay`.
+#[derive(Debug)]
+pub(crate) struct DebounceMsg {
+    pub(crate) key: &'static str,
+    pub(crate) delay: std::time::Duration,
+    pub(crate) msg: Msg,
+}
+
+/// An internal message sent when a debounce delay has passed; `msg` is
+/// delivered only if no later call for `key` replaced it.
+#[derive(Debug)]
+pub(crate) struct DebounceFiredMsg {
+    pub(crate) key: &'static str,
+    pub(crate) generation: u64,
+    pub(crate) msg: Msg,
+}
+
+/// A message to stop a path watcher.
+///
+/// This message stops a running watcher started with `watch_path_with_id()`.
+/// If the watcher has already stopped, this message has no effect.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::event::CancelWatchMsg;
+///
+/// let cancel_msg = CancelWatchMsg { watch_id: 42 };
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancelWatchMsg {
+    /// The unique identifier of the watcher to cancel.
+    pub watch_id: u64,
+}
@@ -11,9 +11,15 @@
 //! ## Features
 //!
 //! - Fast RGB color interpolation for smooth gradients
+//! - Raw RGB samples ([`sample`], [`sample_at`]) for feeding other renderers
 //! - Optimized ANSI escape sequence generation
 //! - Buffer reuse support for high-frequency rendering
 //! - Charm Bubble Tea compatible default gradient colors
+//! - Multi-stop gradients and temperature/health presets for metric dashboards
+//! - Gradient-colored Braille spinner frames
+//! - [`ColorWheel`] for rainbow effects that keep rotating through the hues
+//! - Compile-time gradient arrays with [`gradient_const!`](crate::gradient_const),
+//!   behind the `const-gradients` feature
 //!
 //! ## Example
 //!
@@ -166,16 +172,204 @@ pub fn charm_default_gradient() -> ((u8, u8, u8), (u8, u8, u8)) {
 ///
 /// # Performance Notes
 ///
-/// This function uses floating-point arithmetic for interpolation and rounds the final
-/// results. It's optimized for gradient generation where smooth color transitions are
-/// more important than absolute performance.
+/// `t` is converted to a 16-bit fixed-point fraction and each channel is
+/// interpolated with integer arithmetic, rounding to the nearest value. This
+/// keeps the function `const`, so gradients can be computed at compile time.
 #[inline]
-pub fn lerp_rgb(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
-    let t = t.clamp(0.0, 1.0);
-    let r = (start.0 as f64 + (end.0 as f64 - start.0 as f64) * t).round() as u8;
-    let g = (start.1 as f64 + (end.1 as f64 - start.1 as f64) * t).round() as u8;
-    let b = (start.2 as f64 + (end.2 as f64 - start.2 as f64) * t).round() as u8;
-    (r, g, b)
+pub const fn lerp_rgb(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    // NaN clamps to NaN, which casts to 0
+    let t = (t.clamp(0.0, 1.0) * LERP_ONE as f64 + 0.5) as i32;
+    lerp_rgb_fixed(start, end, t)
+}
+
+/// Returns the color at position `t` between `start` and `end`.
+///
+/// The raw-RGB counterpart of the styled helpers in this module: no ANSI
+/// sequences are produced, so the result can be fed into another renderer,
+/// for example as a lipgloss `Color`. `t` is clamped to \[0,1\], exactly as
+/// in [`lerp_rgb`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::sample_at;
+///
+/// let (r, g, b) = sample_at((255, 0, 0), (0, 0, 255), 0.5);
+/// assert_eq!((r, g, b), (128, 0, 128));
+/// let hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+/// assert_eq!(hex, "#800080");
+/// ```
+#[inline]
+pub fn sample_at(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    lerp_rgb(start, end, t)
+}
+
+/// Returns `steps` colors spread evenly from `start` to `end`, inclusive.
+///
+/// These are the same colors [`gradient_filled_segment`] draws for a segment
+/// `steps` cells wide, without the ANSI styling. A single step yields
+/// `start`, and zero steps yield an empty vector.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::sample;
+///
+/// let ramp = sample((0, 0, 0), (200, 100, 0), 3);
+/// assert_eq!(ramp, [(0, 0, 0), (100, 50, 0), (200, 100, 0)]);
+/// ```
+pub fn sample(start: (u8, u8, u8), end: (u8, u8, u8), steps: usize) -> Vec<(u8, u8, u8)> {
+    (0..steps)
+        .map(|i| {
+            let t = if steps <= 1 {
+                0.0
+            } else {
+                i as f64 / (steps - 1) as f64
+            };
+            lerp_rgb(start, end, t)
+        })
+        .collect()
+}
+
+/// Fixed-point 1.0 for [`lerp_rgb_fixed`].
+const LERP_ONE: i32 = 1 << 16;
+
+/// Interpolates between two colors at `t / LERP_ONE`, rounding half up.
+#[inline]
+const fn lerp_rgb_fixed(start: (u8, u8, u8), end: (u8, u8, u8), t: i32) -> (u8, u8, u8) {
+    const fn channel(a: u8, b: u8, t: i32) -> u8 {
+        let delta = (b as i32 - a as i32) * t;
+        (a as i32 + (delta + LERP_ONE / 2).div_euclid(LERP_ONE)) as u8
+    }
+    (
+        channel(start.0, end.0, t),
+        channel(start.1, end.1, t),
+        channel(start.2, end.2, t),
+    )
+}
+
+/// Interpolates between two colors at `t_256 / 256`.
+///
+/// A `const fn` counterpart of [`lerp_rgb`] for compile-time gradients:
+/// `0` returns `start` and `255` returns a color just short of `end`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::lerp_rgb_const;
+///
+/// const PURPLE: (u8, u8, u8) = lerp_rgb_const((255, 0, 0), (0, 0, 255), 128);
+/// assert_eq!(PURPLE, (128, 0, 128));
+/// ```
+#[cfg(feature = "const-gradients")]
+#[inline]
+pub const fn lerp_rgb_const(start: (u8, u8, u8), end: (u8, u8, u8), t_256: u8) -> (u8, u8, u8) {
+    lerp_rgb_fixed(start, end, (t_256 as i32) << 8)
+}
+
+/// Parses a `#RRGGBB` (or `RRGGBB`) hex color.
+///
+/// Used by [`gradient_const!`](crate::gradient_const) to read its colors at
+/// compile time.
+///
+/// # Panics
+///
+/// Panics if `hex` is not six hex digits with an optional leading `#`. In a
+/// `const` context this is a compile error.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::hex_rgb;
+///
+/// const PINK: (u8, u8, u8) = hex_rgb("#FF7CCB");
+/// assert_eq!(PINK, (0xFF, 0x7C, 0xCB));
+/// ```
+#[cfg(feature = "const-gradients")]
+pub const fn hex_rgb(hex: &str) -> (u8, u8, u8) {
+    const fn digit(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => panic!("invalid hex digit in color"),
+        }
+    }
+    const fn byte(bytes: &[u8], i: usize) -> u8 {
+        digit(bytes[i]) << 4 | digit(bytes[i + 1])
+    }
+
+    let bytes = hex.as_bytes();
+    let offset = if !bytes.is_empty() && bytes[0] == b'#' {
+        1
+    } else {
+        0
+    };
+    assert!(
+        bytes.len() - offset == 6,
+        "hex color must have six digits, like \"#FF7CCB\""
+    );
+    (
+        byte(bytes, offset),
+        byte(bytes, offset + 2),
+        byte(bytes, offset + 4),
+    )
+}
+
+/// Returns `N` colors spread evenly from `start` to `end`, inclusive.
+///
+/// Color `i` is `lerp_rgb(start, end, i / (N - 1))`, the same colors
+/// [`gradient_filled_segment`] uses for a segment `N` cells wide, but
+/// computed at compile time when used in a `const`. See also
+/// [`gradient_const!`](crate::gradient_const).
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::gradient_array;
+///
+/// const FADE: [(u8, u8, u8); 3] = gradient_array((0, 0, 0), (200, 100, 0));
+/// assert_eq!(FADE, [(0, 0, 0), (100, 50, 0), (200, 100, 0)]);
+/// ```
+#[cfg(feature = "const-gradients")]
+pub const fn gradient_array<const N: usize>(
+    start: (u8, u8, u8),
+    end: (u8, u8, u8),
+) -> [(u8, u8, u8); N] {
+    let mut colors = [start; N];
+    let mut i = 1;
+    while i < N {
+        colors[i] = lerp_rgb(start, end, i as f64 / (N - 1) as f64);
+        i += 1;
+    }
+    colors
+}
+
+/// Builds a gradient of `steps` colors between two hex colors at compile time.
+///
+/// Expands to a call to [`gradient::gradient_array`](crate::gradient::gradient_array),
+/// so it can initialize a `const` or `static`. Invalid hex colors are a
+/// compile error when used in a `const`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient_const;
+///
+/// const HEADER_GRADIENT: [(u8, u8, u8); 80] =
+///     gradient_const!(from: "#FF0000", to: "#00FF00", steps: 80);
+/// assert_eq!(HEADER_GRADIENT[0], (255, 0, 0));
+/// assert_eq!(HEADER_GRADIENT[79], (0, 255, 0));
+/// ```
+#[cfg(feature = "const-gradients")]
+#[macro_export]
+macro_rules! gradient_const {
+    (from: $from:expr, to: $to:expr, steps: $steps:expr $(,)?) => {
+        $crate::gradient::gradient_array::<{ $steps }>(
+            $crate::gradient::hex_rgb($from),
+            $crate::gradient::hex_rgb($to),
+        )
+    };
 }
 
 /// Creates a gradient-colored text segment for terminal display.
@@ -388,3 +582,628 @@ pub fn gradient_filled_segment_with_buffer(
     }
     buffer
 }
+
+/// Color stops for [`temperature_gradient`]: cool blue, warm yellow, hot red.
+pub const TEMPERATURE_STOPS: [(u8, u8, u8); 3] =
+    [(0x3B, 0x82, 0xF6), (0xFA, 0xCC, 0x15), (0xEF, 0x44, 0x44)];
+
+/// Color stops for [`health_gradient`]: good green, warning yellow, critical red.
+pub const HEALTH_STOPS: [(u8, u8, u8); 3] =
+    [(0x22, 0xC5, 0x5E), (0xFA, 0xCC, 0x15), (0xEF, 0x44, 0x44)];
+
+/// Describes a multi-stop gradient for [`gradient_filled_segment_with_spec`].
+///
+/// The color stops are spaced evenly across the gradient. By default the
+/// gradient is stretched over the filled segment, exactly like
+/// [`gradient_filled_segment`]. Setting a `span` fixes the gradient to a
+/// number of cells instead, so a partially filled bar only shows the colors
+/// up to its fill level. This is what metric meters usually want: a bar at
+/// 20% stays in the "cool" colors.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::{GradientSpec, TEMPERATURE_STOPS};
+///
+/// let spec = GradientSpec::new(&TEMPERATURE_STOPS).with_span(20);
+/// assert_eq!(spec.color_at(0.0), TEMPERATURE_STOPS[0]);
+/// assert_eq!(spec.color_at(0.5), TEMPERATURE_STOPS[1]);
+/// assert_eq!(spec.color_at(1.0), TEMPERATURE_STOPS[2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradientSpec<'a> {
+    /// Evenly spaced color stops, from left to right.
+    pub stops: &'a [(u8, u8, u8)],
+    /// Number of cells the full gradient covers. `0` stretches the gradient
+    /// over the filled width.
+    pub span: usize,
+}
+
+impl<'a> GradientSpec<'a> {
+    /// Creates a gradient spec from evenly spaced color stops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stops` is empty.
+    pub const fn new(stops: &'a [(u8, u8, u8)]) -> Self {
+        assert!(
+            !stops.is_empty(),
+            "GradientSpec needs at least one color stop"
+        );
+        Self { stops, span: 0 }
+    }
+
+    /// Fixes the gradient to `span` cells instead of the filled width.
+    pub const fn with_span(mut self, span: usize) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Returns the color at position `t` along the gradient.
+    ///
+    /// `t` is clamped to \[0,1\]. Colors between two stops are interpolated
+    /// with [`lerp_rgb`].
+    pub fn color_at(&self, t: f64) -> (u8, u8, u8) {
+        let segments = self.stops.len() - 1;
+        if segments == 0 {
+            return self.stops[0];
+        }
+        let scaled = t.clamp(0.0, 1.0) * segments as f64;
+        let index = (scaled.floor() as usize).min(segments - 1);
+        lerp_rgb(
+            self.stops[index],
+            self.stops[index + 1],
+            scaled - index as f64,
+        )
+    }
+}
+
+/// Creates a gradient-colored text segment from a [`GradientSpec`].
+///
+/// This is the general form of [`gradient_filled_segment`]: the colors come
+/// from the spec's stops rather than Charm's default two-color gradient, and
+/// the gradient can span more cells than are filled.
+///
+/// # Arguments
+///
+/// * `filled_width` - The number of characters to render. If 0, returns an empty string.
+/// * `ch` - The character to repeat for each position in the gradient
+/// * `spec` - The gradient colors and span to use
+///
+/// # Returns
+///
+/// A `String` containing the gradient-colored characters, using the same
+/// ANSI escape sequence format as [`gradient_filled_segment`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::{gradient_filled_segment_with_spec, GradientSpec};
+///
+/// let spec = GradientSpec::new(&[(255, 0, 0), (0, 255, 0), (0, 0, 255)]);
+/// let segment = gradient_filled_segment_with_spec(3, '█', &spec);
+/// assert!(segment.starts_with("\x1b[38;2;255;0;0m█"));
+/// assert!(segment.ends_with("\x1b[38;2;0;0;255m█\x1b[0m"));
+/// ```
+pub fn gradient_filled_segment_with_spec(
+    filled_width: usize,
+    ch: char,
+    spec: &GradientSpec<'_>,
+) -> String {
+    if filled_width == 0 {
+        return String::new();
+    }
+
+    let span = if spec.span == 0 {
+        filled_width
+    } else {
+        spec.span
+    };
+    let mut s = String::with_capacity(filled_width * 25);
+
+    for i in 0..filled_width {
+        let t = if span <= 1 {
+            0.0
+        } else {
+            i as f64 / (span - 1) as f64
+        };
+        let (r, g, b) = spec.color_at(t);
+
+        s.push_str("\x1b[38;2;");
+        write_u8_to_string(&mut s, r);
+        s.push(';');
+        write_u8_to_string(&mut s, g);
+        s.push(';');
+        write_u8_to_string(&mut s, b);
+        s.push('m');
+        s.push(ch);
+        s.push_str("\x1b[0m");
+    }
+    s
+}
+
+/// Maps `value` from `[min, max]` to `[0, 1]`, clamping out-of-range values.
+///
+/// An empty or inverted range and NaN values map to 0.
+fn normalize(value: f64, min: f64, max: f64) -> f64 {
+    let t = (value - min) / (max - min);
+    if t.is_nan() || max <= min {
+        0.0
+    } else {
+        t.clamp(0.0, 1.0)
+    }
+}
+
+/// Renders a temperature meter for `value` within `[min, max]`.
+///
+/// The bar is `width` cells wide when `value` is at `max`, and the gradient
+/// runs from cool blue through warm yellow to hot red ([`TEMPERATURE_STOPS`])
+/// across the full width. Lower values fill proportionally fewer cells, so
+/// they only show the cooler colors. Values outside the range are clamped.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::temperature_gradient;
+///
+/// // CPU temperature of 65°C on a 30–90°C scale
+/// let meter = temperature_gradient(65.0, 30.0, 90.0, 20);
+/// println!("CPU [{meter}]");
+/// ```
+pub fn temperature_gradient(value: f64, min: f64, max: f64, width: usize) -> String {
+    metric_gradient(&TEMPERATURE_STOPS, value, min, max, width)
+}
+
+/// Renders a health meter for `value` within `[min, max]`.
+///
+/// Works like [`temperature_gradient`] with a green, yellow, red palette
+/// ([`HEALTH_STOPS`]). Low values are healthy and high values are critical,
+/// which suits usage metrics such as CPU load, memory, or error rate.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::health_gradient;
+///
+/// // 7.5 GiB of 8 GiB memory in use
+/// let meter = health_gradient(7.5, 0.0, 8.0, 20);
+/// println!("MEM [{meter}]");
+/// ```
+pub fn health_gradient(value: f64, min: f64, max: f64, width: usize) -> String {
+    metric_gradient(&HEALTH_STOPS, value, min, max, width)
+}
+
+fn metric_gradient(stops: &[(u8, u8, u8)], value: f64, min: f64, max: f64, width: usize) -> String {
+    let filled = (normalize(value, min, max) * width as f64).round() as usize;
+    let spec = GradientSpec::new(stops).with_span(width);
+    gradient_filled_segment_with_spec(filled, '█', &spec)
+}
+
+/// Braille spinner frames, as used by Charm's "dot" spinner.
+pub const BRAILLE_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Generates `count` Braille spinner frames colored along a gradient.
+///
+/// Frame `i` shows `BRAILLE_FRAMES[i % BRAILLE_FRAMES.len()]` in the color at
+/// position `i / (count - 1)` of `spec`, so the color sweeps through the
+/// gradient as the spinner turns. For a seamless loop, use a gradient whose
+/// first and last stops are the same color. The spec's `span` is ignored.
+///
+/// The frames can be passed straight to
+/// [`FrameAnimator::new`](crate::animation::FrameAnimator::new).
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::{spinner_frames_gradient, GradientSpec, BRAILLE_FRAMES};
+///
+/// let spec = GradientSpec::new(&[(255, 0, 0), (0, 0, 255)]);
+/// let frames = spinner_frames_gradient(20, &spec);
+/// assert_eq!(frames.len(), 20);
+/// assert_eq!(frames[0], format!("\x1b[38;2;255;0;0m{}\x1b[0m", BRAILLE_FRAMES[0]));
+/// assert_eq!(frames[19], format!("\x1b[38;2;0;0;255m{}\x1b[0m", BRAILLE_FRAMES[9]));
+/// ```
+pub fn spinner_frames_gradient(count: usize, spec: &GradientSpec<'_>) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            let t = if count <= 1 {
+                0.0
+            } else {
+                i as f64 / (count - 1) as f64
+            };
+            colored_frame(BRAILLE_FRAMES[i % BRAILLE_FRAMES.len()], spec.color_at(t))
+        })
+        .collect()
+}
+
+/// Wraps `frame` in a 24-bit foreground color sequence.
+fn colored_frame(frame: &str, (r, g, b): (u8, u8, u8)) -> String {
+    let mut s = String::with_capacity(frame.len() + 24);
+    s.push_str("\x1b[38;2;");
+    write_u8_to_string(&mut s, r);
+    s.push(';');
+    write_u8_to_string(&mut s, g);
+    s.push(';');
+    write_u8_to_string(&mut s, b);
+    s.push('m');
+    s.push_str(frame);
+    s.push_str("\x1b[0m");
+    s
+}
+
+/// How the frames of a Braille spinner are colored.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::animation::FrameAnimator;
+/// use bubbletea_rs::gradient::{GradientSpec, SpinnerStyle, TEMPERATURE_STOPS};
+/// use std::time::Duration;
+///
+/// let style = SpinnerStyle::Gradient(GradientSpec::new(&TEMPERATURE_STOPS));
+/// let spinner = FrameAnimator::new(style.frames(30), Duration::from_millis(80));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpinnerStyle<'a> {
+    /// Uncolored frames.
+    Plain,
+    /// Every frame in one foreground color.
+    Solid((u8, u8, u8)),
+    /// Each frame in the color at its position along the gradient, see
+    /// [`spinner_frames_gradient`].
+    Gradient(GradientSpec<'a>),
+}
+
+impl SpinnerStyle<'_> {
+    /// Generates `count` Braille spinner frames in this style.
+    pub fn frames(&self, count: usize) -> Vec<String> {
+        match self {
+            SpinnerStyle::Plain => (0..count)
+                .map(|i| BRAILLE_FRAMES[i % BRAILLE_FRAMES.len()].to_string())
+                .collect(),
+            SpinnerStyle::Solid(color) => (0..count)
+                .map(|i| colored_frame(BRAILLE_FRAMES[i % BRAILLE_FRAMES.len()], *color))
+                .collect(),
+            SpinnerStyle::Gradient(spec) => spinner_frames_gradient(count, spec),
+        }
+    }
+}
+
+/// An endless iterator over colors that rotate through the hues.
+///
+/// Each item is the RGB color of the next hue in HSL space, with fixed
+/// saturation and lightness. The hue starts at red (0°) and advances by the
+/// step, one degree per item unless set with [`with_step`](Self::with_step).
+/// The hue of any item is computed from its position, so skipping ahead with
+/// [`Iterator::nth`] or [`Iterator::skip`] takes constant time.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::gradient::ColorWheel;
+///
+/// let mut wheel = ColorWheel::new(1.0, 0.5).with_step(120.0);
+/// assert_eq!(wheel.next(), Some((255, 0, 0)));
+/// assert_eq!(wheel.next(), Some((0, 255, 0)));
+/// assert_eq!(wheel.next(), Some((0, 0, 255)));
+/// assert_eq!(wheel.next(), Some((255, 0, 0)));
+///
+/// // Pick a color by its position around the wheel
+/// assert_eq!(ColorWheel::new(1.0, 0.5).sample_at(0.5), (0, 255, 255));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorWheel {
+    saturation: f64,
+    lightness: f64,
+    step: f64,
+    /// Position of the next item
+    index: u64,
+}
+
+impl ColorWheel {
+    /// Creates a color wheel with the given HSL saturation and lightness.
+    ///
+    /// Both are clamped to \[0,1\]. A saturation of `1.0` and lightness of
+    /// `0.5` give the most vivid colors.
+    pub fn new(saturation: f64, lightness: f64) -> Self {
+        Self {
+            saturation: saturation.clamp(0.0, 1.0),
+            lightness: lightness.clamp(0.0, 1.0),
+            step: 1.0,
+            index: 0,
+        }
+    }
+
+    /// Sets how many degrees the hue advances per item.
+    ///
+    /// Larger steps rotate faster. A negative step rotates backwards.
+    pub fn with_step(mut self, degrees_per_item: f64) -> Self {
+        self.step = degrees_per_item;
+        self
+    }
+
+    /// Returns the color at position `t` around the wheel.
+    ///
+    /// `t` is the fraction of a full turn: `0.0` is red, `1.0 / 3.0` green
+    /// and `2.0 / 3.0` blue. Values outside \[0,1) wrap around.
+    pub fn sample_at(&self, t: f64) -> (u8, u8, u8) {
+        hsl_to_rgb(t.rem_euclid(1.0) * 360.0, self.saturation, self.lightness)
+    }
+
+    /// Generates `count` Braille spinner frames, each in the next color of
+    /// this wheel.
+    ///
+    /// Like [`spinner_frames_gradient`], but the colors keep rotating
+    /// instead of following a gradient. With a step that divides 360 by
+    /// `count`, the colors loop seamlessly.
+    pub fn spinner_frames(&self, count: usize) -> Vec<String> {
+        self.clone()
+            .take(count)
+            .enumerate()
+            .map(|(i, color)| colored_frame(BRAILLE_FRAMES[i % BRAILLE_FRAMES.len()], color))
+            .collect()
+    }
+}
+
+impl Iterator for ColorWheel {
+    type Item = (u8, u8, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hue = (self.index as f64 * self.step).rem_euclid(360.0);
+        self.index = self.index.wrapping_add(1);
+        Some(hsl_to_rgb(hue, self.saturation, self.lightness))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.wrapping_add(n as u64);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Converts an HSL color to RGB.
+///
+/// `hue` is in degrees within \[0,360), `saturation` and `lightness` within
+/// \[0,1\].
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(color: (u8, u8, u8)) -> String {
+        format!("\x1b[38;2;{};{};{}m█\x1b[0m", color.0, color.1, color.2)
+    }
+
+    #[test]
+    fn test_lerp_rgb_stays_within_one_of_float_interpolation() {
+        // Fixed-point rounding may break exact .5 ties the other way
+        let float_lerp = |a: u8, b: u8, t: f64| a as f64 + (b as f64 - a as f64) * t;
+        let (start, end) = charm_default_gradient();
+        for width in [2usize, 3, 7, 20, 80, 255] {
+            for i in 0..width {
+                let t = i as f64 / (width - 1) as f64;
+                let (r, g, b) = lerp_rgb(start, end, t);
+                for (actual, a, b) in [
+                    (r, start.0, end.0),
+                    (g, start.1, end.1),
+                    (b, start.2, end.2),
+                ] {
+                    let expected = float_lerp(a, b, t);
+                    assert!((actual as f64 - expected).abs() <= 0.5 + 1e-3, "t = {t}");
+                }
+            }
+        }
+        assert_eq!(lerp_rgb(start, end, 0.0), start);
+        assert_eq!(lerp_rgb(start, end, 1.0), end);
+        assert_eq!(lerp_rgb(start, end, f64::NAN), start);
+    }
+
+    #[test]
+    fn test_sample_endpoints_and_midpoints() {
+        let ramp = sample((255, 0, 0), (0, 0, 255), 5);
+        assert_eq!(
+            ramp,
+            [
+                (255, 0, 0),
+                (191, 0, 64),
+                (128, 0, 128),
+                (64, 0, 191),
+                (0, 0, 255)
+            ]
+        );
+        assert_eq!(sample((255, 0, 0), (0, 0, 255), 1), [(255, 0, 0)]);
+        assert!(sample((255, 0, 0), (0, 0, 255), 0).is_empty());
+
+        let (start, end) = charm_default_gradient();
+        assert_eq!(sample_at(start, end, 0.0), start);
+        assert_eq!(sample_at(start, end, 1.0), end);
+        assert_eq!(sample_at(start, end, 0.5), (0xFE, 0xBE, 0xAC));
+        assert_eq!(sample_at(start, end, -1.0), start);
+
+        // The styled segment draws exactly the sampled colors
+        let colors = sample(start, end, 4);
+        let segment: String = colors.iter().map(|&c| cell(c)).collect();
+        assert_eq!(gradient_filled_segment(4, '█'), segment);
+    }
+
+    #[cfg(feature = "const-gradients")]
+    #[test]
+    fn test_const_gradients() {
+        const HEADER: [(u8, u8, u8); 20] =
+            crate::gradient_const!(from: "#FF7CCB", to: "fdff8c", steps: 20);
+        let (start, end) = charm_default_gradient();
+        let expected: Vec<String> = HEADER.iter().map(|&color| cell(color)).collect();
+        assert_eq!(expected.concat(), gradient_filled_segment(20, '█'));
+        assert_eq!(HEADER[0], start);
+        assert_eq!(HEADER[19], end);
+
+        const SINGLE: [(u8, u8, u8); 1] = gradient_array((1, 2, 3), (4, 5, 6));
+        assert_eq!(SINGLE, [(1, 2, 3)]);
+        assert_eq!(gradient_array::<0>(start, end), []);
+
+        assert_eq!(lerp_rgb_const(start, end, 0), start);
+        assert_eq!(
+            lerp_rgb_const((0, 0, 0), (255, 255, 255), 255),
+            (254, 254, 254)
+        );
+    }
+
+    #[cfg(feature = "const-gradients")]
+    #[test]
+    #[should_panic(expected = "six digits")]
+    fn test_hex_rgb_rejects_short_colors() {
+        hex_rgb("#FFF");
+    }
+
+    #[test]
+    fn test_metric_gradients_at_boundaries() {
+        for (render, stops) in [
+            (
+                temperature_gradient as fn(f64, f64, f64, usize) -> String,
+                TEMPERATURE_STOPS,
+            ),
+            (health_gradient, HEALTH_STOPS),
+        ] {
+            // At min nothing is filled
+            assert_eq!(render(0.0, 0.0, 100.0, 11), "");
+
+            // At max the whole bar is filled and spans all three stops
+            let full = render(100.0, 0.0, 100.0, 11);
+            assert!(full.starts_with(&cell(stops[0])));
+            assert!(full.contains(&cell(stops[1])));
+            assert!(full.ends_with(&cell(stops[2])));
+            assert_eq!(full.matches('█').count(), 11);
+
+            // At the midpoint half the bar is filled and ends on the middle stop
+            let half = render(50.0, 0.0, 100.0, 11);
+            assert_eq!(half.matches('█').count(), 6);
+            assert!(half.starts_with(&cell(stops[0])));
+            assert!(half.ends_with(&cell(stops[1])));
+
+            // Out-of-range values are clamped
+            assert_eq!(render(-10.0, 0.0, 100.0, 11), "");
+            assert_eq!(render(150.0, 0.0, 100.0, 11), full);
+        }
+    }
+
+    #[test]
+    fn test_metric_gradient_degenerate_range() {
+        assert_eq!(temperature_gradient(5.0, 10.0, 10.0, 8), "");
+        assert_eq!(health_gradient(f64::NAN, 0.0, 1.0, 8), "");
+    }
+
+    #[test]
+    fn test_spinner_frames_gradient_sweeps_colors() {
+        let spec = GradientSpec::new(&[(0, 0, 0), (200, 100, 0)]);
+        let frames = spinner_frames_gradient(3, &spec);
+        assert_eq!(
+            frames,
+            [
+                "\x1b[38;2;0;0;0m⠋\x1b[0m",
+                "\x1b[38;2;100;50;0m⠙\x1b[0m",
+                "\x1b[38;2;200;100;0m⠹\x1b[0m",
+            ]
+        );
+
+        // More frames than Braille patterns cycle through the patterns
+        let frames = spinner_frames_gradient(12, &spec);
+        assert!(frames[10].contains(BRAILLE_FRAMES[0]));
+        assert!(frames[11].contains(BRAILLE_FRAMES[1]));
+
+        assert!(spinner_frames_gradient(0, &spec).is_empty());
+        assert_eq!(
+            spinner_frames_gradient(1, &spec),
+            ["\x1b[38;2;0;0;0m⠋\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn test_spinner_style_frames() {
+        assert_eq!(SpinnerStyle::Plain.frames(2), ["⠋", "⠙"]);
+        assert_eq!(
+            SpinnerStyle::Solid((1, 2, 3)).frames(1),
+            ["\x1b[38;2;1;2;3m⠋\x1b[0m"]
+        );
+        let spec = GradientSpec::new(&HEALTH_STOPS);
+        assert_eq!(
+            SpinnerStyle::Gradient(spec).frames(5),
+            spinner_frames_gradient(5, &spec)
+        );
+    }
+
+    #[test]
+    fn test_color_wheel_rotates_through_hues() {
+        let wheel = ColorWheel::new(1.0, 0.5).with_step(60.0);
+        assert_eq!(
+            wheel.take(7).collect::<Vec<_>>(),
+            [
+                (255, 0, 0),
+                (255, 255, 0),
+                (0, 255, 0),
+                (0, 255, 255),
+                (0, 0, 255),
+                (255, 0, 255),
+                (255, 0, 0),
+            ]
+        );
+
+        // Gray when unsaturated, regardless of hue
+        let gray = ColorWheel::new(0.0, 0.5).with_step(45.0);
+        assert!(gray.take(8).all(|color| color == (128, 128, 128)));
+
+        // Backwards rotation
+        let mut backwards = ColorWheel::new(1.0, 0.5).with_step(-120.0);
+        backwards.next();
+        assert_eq!(backwards.next(), Some((0, 0, 255)));
+    }
+
+    #[test]
+    fn test_color_wheel_skips_in_constant_time() {
+        let wheel = ColorWheel::new(0.8, 0.6).with_step(7.0);
+        assert_eq!(wheel.clone().nth(5), wheel.clone().take(6).last());
+        let mut skipped = wheel.clone().skip(5);
+        assert_eq!(skipped.next(), wheel.clone().nth(5));
+
+        // Far enough that stepping through would take noticeably long
+        let mut skipped = wheel.clone().skip(4_000_000_000);
+        let far = skipped.next().unwrap();
+        let hue = (4_000_000_000f64 * 7.0).rem_euclid(360.0);
+        assert_eq!(far, wheel.sample_at(hue / 360.0));
+    }
+
+    #[test]
+    fn test_color_wheel_sample_at_wraps() {
+        let wheel = ColorWheel::new(1.0, 0.5);
+        assert_eq!(wheel.sample_at(0.0), (255, 0, 0));
+        assert_eq!(wheel.sample_at(1.0 / 3.0), (0, 255, 0));
+        assert_eq!(wheel.sample_at(-1.0 / 3.0), (0, 0, 255));
+        assert_eq!(wheel.sample_at(1.25), wheel.sample_at(0.25));
+        // Out-of-range saturation and lightness are clamped
+        assert_eq!(ColorWheel::new(2.0, 1.5).sample_at(0.5), (255, 255, 255));
+
+        let frames = wheel.with_step(120.0).spinner_frames(3);
+        assert_eq!(
+            frames[1],
+            format!("\x1b[38;2;0;255;0m{}\x1b[0m", BRAILLE_FRAMES[1])
+        );
+    }
+}
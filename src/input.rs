@@ -9,6 +9,11 @@
 //!
 //! - [`InputHandler`] - The main event processor that runs the input loop
 //! - [`InputSource`] - Enum defining different input sources (terminal or custom)
+//! - [`InputHandler::with_paste_heuristic`] - Turns bursts of key presses into `PasteMsg`s
+//! - [`InputHandler::with_paste_chunks`] - Streams large bracketed pastes in pieces
+//! - [`InputHandler::with_focus_debounce`] - Collapses flapping focus changes
+//! - [`parse_sgr_mouse`] - Decoder for SGR (1006) mouse reports
+//! - [`parse_csi_u_key`] - Decoder for keys reported by the keyboard enhancement protocol
 //!
 //! # Examples
 //!
@@ -49,11 +54,23 @@
 //! # }
 //! ```
 
-use crate::{Error, KeyMsg, MouseMsg, WindowSizeMsg};
-use crossterm::event::{Event, EventStream, KeyCode, KeyModifiers};
+use crate::event::{
+    EventSender, InputStamp, KeyboardEnhancementsMsg, PasteChunkMsg, PasteEndMsg, PasteMsg,
+    TimestampedMsg,
+};
+use crate::{BlurMsg, Error, FocusMsg, KeyMsg, MouseMsg, Msg, WindowSizeMsg};
+use crossterm::event::{
+    Event, EventStream, KeyCode, KeyModifiers, KeyboardEnhancementFlags, MouseButton,
+    MouseEventKind,
+};
 use futures::StreamExt;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
 
 /// Represents different input sources that the `InputHandler` can read from.
 ///
@@ -82,6 +99,58 @@ pub struct InputHandler {
 
     /// The input source to read from.
     pub input_source: InputSource,
+
+    /// The burst detection used to recognize unbracketed pastes, if enabled.
+    /// See [`with_paste_heuristic`](Self::with_paste_heuristic).
+    pub paste_heuristic: Option<PasteHeuristic>,
+
+    /// The size in bytes of the pieces bracketed pastes are delivered in, if
+    /// they are streamed. See [`with_paste_chunks`](Self::with_paste_chunks).
+    pub paste_chunk_size: Option<usize>,
+
+    /// Requests to query the terminal's keyboard enhancement support.
+    /// See [`with_keyboard_enhancement_queries`](Self::with_keyboard_enhancement_queries).
+    pub keyboard_enhancement_queries: Option<mpsc::UnboundedReceiver<()>>,
+
+    /// How long focus changes are held back to collapse flapping, or zero
+    /// to send each one right away.
+    /// See [`with_focus_debounce`](Self::with_focus_debounce).
+    pub focus_debounce: Duration,
+
+    /// Whether key, mouse and focus messages are stamped with their decode
+    /// time for [`input_timestamp`](crate::event::input_timestamp). Only a
+    /// `Program` unwraps the stamps, so only it turns them on.
+    pub(crate) timestamps: bool,
+
+    /// Whether the keyboard enhancement protocol is active on the terminal
+    /// being read, shared with the `Program` that turns it on and off. Keys
+    /// decoded while it is off are stamped as
+    /// [`input_is_ambiguous`](crate::event::input_is_ambiguous).
+    pub(crate) keyboard_enhancement: Arc<AtomicBool>,
+}
+
+/// Settings for recognizing pastes from the speed at which keys arrive.
+///
+/// Without bracketed paste, a terminal delivers pasted text as a rapid burst
+/// of key presses. A burst of at least `burst_threshold` characters arriving
+/// within `burst_window` is treated as a paste.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasteHeuristic {
+    /// The number of characters that make a burst a paste.
+    pub burst_threshold: usize,
+    /// The time in which `burst_threshold` characters must arrive, and the
+    /// longest gap between two characters of the same paste.
+    pub burst_window: Duration,
+}
+
+impl Default for PasteHeuristic {
+    /// 10 characters within 50 ms.
+    fn default() -> Self {
+        Self {
+            burst_threshold: 10,
+            burst_window: Duration::from_millis(50),
+        }
+    }
 }
 
 impl InputHandler {
@@ -110,6 +179,12 @@ impl InputHandler {
         Self {
             event_tx: event_tx.into(),
             input_source: InputSource::Terminal,
+            paste_heuristic: None,
+            paste_chunk_size: None,
+            keyboard_enhancement_queries: None,
+            focus_debounce: Duration::ZERO,
+            timestamps: false,
+            keyboard_enhancement: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -148,9 +223,141 @@ impl InputHandler {
         Self {
             event_tx: event_tx.into(),
             input_source,
+            paste_heuristic: None,
+            paste_chunk_size: None,
+            keyboard_enhancement_queries: None,
+            focus_debounce: Duration::ZERO,
+            timestamps: false,
+            keyboard_enhancement: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Coalesces fast bursts of key presses into a single `PasteMsg`.
+    ///
+    /// When at least `burst_threshold` characters arrive within
+    /// `burst_window`, the handler sends them, and every character that
+    /// follows within `burst_window` of the previous one, as one `PasteMsg`
+    /// instead of individual `KeyMsg`s. Enter and Tab become `\n` and `\t` in
+    /// the pasted text. This keeps unbracketed pastes from flooding the
+    /// model's `update` with hundreds of key messages.
+    ///
+    /// Only unmodified (or shifted) characters, Enter and Tab take part in
+    /// bursts; any other event ends the burst. To tell bursts from typing,
+    /// plain key presses are held back for up to `burst_window` before they
+    /// are sent as `KeyMsg`s. [`PasteHeuristic::default`] uses 10 characters
+    /// within 50 ms. A `burst_threshold` below 2 disables the heuristic.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bubbletea_rs::input::InputHandler;
+    /// use std::time::Duration;
+    /// use tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::unbounded_channel();
+    /// let input_handler = InputHandler::new(tx).with_paste_heuristic(10, Duration::from_millis(50));
+    /// ```
+    pub fn with_paste_heuristic(mut self, burst_threshold: usize, burst_window: Duration) -> Self {
+        self.paste_heuristic = (burst_threshold >= 2).then_some(PasteHeuristic {
+            burst_threshold,
+            burst_window,
+        });
+        self
+    }
+
+    /// Delivers bracketed pastes in pieces of `chunk_size` bytes.
+    ///
+    /// By default a bracketed paste arrives as one `PasteMsg` once all of it
+    /// has been read. With chunks, the text is sent as `PasteChunkMsg`s as
+    /// soon as each piece is read, followed by a `PasteEndMsg`, so a model
+    /// can show progress through a paste of several megabytes and the
+    /// program keeps handling other messages in between. A `chunk_size` of
+    /// zero sends the whole paste as one `PasteMsg` again.
+    ///
+    /// Either way, pastes from a custom input source are read in bounded
+    /// pieces rather than a line at a time, yielding to other tasks between
+    /// pieces.
+    ///
+    /// Only custom input sources are streamed as they are read. On the real
+    /// terminal, crossterm hands over a bracketed paste only once all of it
+    /// has arrived, so the chunks are cut from text that is already in
+    /// memory: the model still gets the paste in pieces, with other messages
+    /// handled in between, but the first chunk arrives no sooner and memory
+    /// use is not bounded by the chunk size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bubbletea_rs::input::InputHandler;
+    /// use tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::unbounded_channel();
+    /// let input_handler = InputHandler::new(tx).with_paste_chunks(64 * 1024);
+    /// ```
+    pub fn with_paste_chunks(mut self, chunk_size: usize) -> Self {
+        self.paste_chunk_size = (chunk_size > 0).then_some(chunk_size);
+        self
+    }
+
+    /// Collapses focus changes that arrive in quick succession.
+    ///
+    /// Terminal multiplexers such as tmux can report spurious focus out/in
+    /// pairs when switching panes. With a non-zero `window`, a focus change
+    /// is held back until no other arrives for `window`. Then only the last
+    /// one is sent, as a `FocusMsg` or `BlurMsg`, and only if it differs
+    /// from the last one sent, so changes that cancel out are dropped. A
+    /// program reads when the sent change arrived from
+    /// [`input_timestamp`](crate::event::input_timestamp). Other input is
+    /// not held back, so it may overtake a pending focus change.
+    ///
+    /// A zero `window`, the default, sends every focus change as it
+    /// arrives.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bubbletea_rs::input::InputHandler;
+    /// use std::time::Duration;
+    /// use tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::unbounded_channel();
+    /// let input_handler = InputHandler::new(tx).with_focus_debounce(Duration::from_millis(50));
+    /// ```
+    pub fn with_focus_debounce(mut self, window: Duration) -> Self {
+        self.focus_debounce = window;
+        self
+    }
+
+    /// Answers keyboard enhancement queries for terminal input.
+    ///
+    /// crossterm keeps the terminal's reply to a keyboard enhancement query
+    /// to itself, so the reply never shows up in the event stream. For each
+    /// `()` received on `queries`, the handler pauses its event stream while
+    /// crossterm asks the terminal, then sends the answer as a
+    /// `KeyboardEnhancementsMsg`.
+    ///
+    /// Custom input sources do not need this: they decode replies written to
+    /// them into `KeyboardEnhancementsMsg`s directly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bubbletea_rs::input::InputHandler;
+    /// use tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::unbounded_channel();
+    /// let (query_tx, query_rx) = mpsc::unbounded_channel();
+    /// let input_handler = InputHandler::new(tx).with_keyboard_enhancement_queries(query_rx);
+    /// let _ = query_tx.send(());
+    /// ```
+    pub fn with_keyboard_enhancement_queries(
+        mut self,
+        queries: mpsc::UnboundedReceiver<()>,
+    ) -> Self {
+        self.keyboard_enhancement_queries = Some(queries);
+        self
+    }
+
     /// Runs the input handler loop asynchronously.
     ///
     /// This method continuously reads events from the configured input source
@@ -196,13 +403,38 @@ impl InputHandler {
     /// # }
     /// ```
     pub async fn run(self) -> Result<(), Error> {
-        let event_tx = self.event_tx;
+        let input = Input {
+            event_tx: self.event_tx,
+            timestamps: self.timestamps,
+            keyboard_enhancement: self.keyboard_enhancement,
+            paste_chunk_size: self.paste_chunk_size,
+        };
+        let bursts = self.paste_heuristic.map(BurstDetector::new);
+        let focus = FocusDebouncer::new(self.focus_debounce);
         match self.input_source {
-            InputSource::Terminal => Self::run_terminal_input(event_tx).await,
-            InputSource::Custom(reader) => Self::run_custom_input(event_tx, reader).await,
+            InputSource::Terminal => {
+                Self::run_terminal_input(input, bursts, focus, self.keyboard_enhancement_queries)
+                    .await
+            }
+            InputSource::Custom(reader) => {
+                Self::run_custom_input(input, reader, bursts, focus).await
+            }
         }
     }
 
+    /// Stamps key, mouse and focus messages with their decode time.
+    pub(crate) fn with_timestamps(mut self) -> Self {
+        self.timestamps = true;
+        self
+    }
+
+    /// Reads whether keys are decoded with the keyboard enhancement
+    /// protocol from `active`, which the program keeps up to date.
+    pub(crate) fn with_keyboard_enhancement_state(mut self, active: Arc<AtomicBool>) -> Self {
+        self.keyboard_enhancement = active;
+        self
+    }
+
     /// Runs the terminal input handler using crossterm's event stream.
     ///
     /// This method processes standard terminal events including:
@@ -215,6 +447,9 @@ impl InputHandler {
     /// # Arguments
     ///
     /// * `event_tx` - Channel sender for dispatching processed events
+    /// * `bursts` - Burst detection for the paste heuristic, if enabled
+    /// * `focus` - Debouncing of focus changes
+    /// * `queries` - Keyboard enhancement queries to answer, if any
     ///
     /// # Returns
     ///
@@ -224,75 +459,104 @@ impl InputHandler {
     /// # Errors
     ///
     /// Returns an error if crossterm's event stream encounters an I/O error.
-    async fn run_terminal_input(event_tx: crate::event::EventSender) -> Result<(), Error> {
+    async fn run_terminal_input(
+        input: Input,
+        mut bursts: Option<BurstDetector>,
+        mut focus: FocusDebouncer,
+        mut queries: Option<mpsc::UnboundedReceiver<()>>,
+    ) -> Result<(), Error> {
         let mut event_stream = EventStream::new();
 
-        while let Some(event) = event_stream.next().await {
-            match event {
+        loop {
+            let deadline = next_deadline(&bursts, &focus);
+            let event = tokio::select! {
+                Some(()) = next_query(&mut queries) => {
+                    // crossterm can only read the reply while no event
+                    // stream is holding its reader
+                    drop(event_stream);
+                    let supported_flags =
+                        query_terminal_keyboard_enhancements(&input.keyboard_enhancement).await;
+                    event_stream = EventStream::new();
+                    let msg = Box::new(KeyboardEnhancementsMsg { supported_flags });
+                    if !flush_bursts(&input, &mut bursts) || !input.send(msg) {
+                        break;
+                    }
+                    continue;
+                }
+                event = next_event(&mut event_stream, deadline) => event,
+            };
+            let event = match event {
+                Some(event) => event,
+                None => {
+                    // Nothing arrived within the window: the burst or the
+                    // focus change is over
+                    if !flush_due(&input, &mut bursts, &mut focus) {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            let Some(event) = event else {
+                break;
+            };
+
+            let msg: Msg = match event {
                 Ok(Event::Key(key_event)) => {
+                    // Skip key_event.is_release() on Windows to prevent double keys
+                    if cfg!(target_os = "windows") && !key_event.is_press() {
+                        continue;
+                    }
                     let msg = KeyMsg {
                         key: key_event.code,
                         modifiers: key_event.modifiers,
                     };
-
-                    // Skip key_event.is_release() on Windows to prevent double keys
-                    #[cfg(target_os = "windows")]
-                    {
-                        if key_event.is_press() {
-                            if event_tx.send(Box::new(msg)).is_err() {
-                                break;
-                            }
-                        }
-                    }
-
-                    #[cfg(not(target_os = "windows"))]
-                    {
-                        if event_tx.send(Box::new(msg)).is_err() {
-                            break;
-                        }
+                    if !send_key(&input, &mut bursts, msg) {
+                        break;
                     }
+                    continue;
                 }
-                Ok(Event::Mouse(mouse_event)) => {
-                    let msg = MouseMsg {
+                Ok(Event::Mouse(mouse_event)) => input.stamp(
+                    Box::new(MouseMsg {
                         x: mouse_event.column,
                         y: mouse_event.row,
                         button: mouse_event.kind,
                         modifiers: mouse_event.modifiers,
-                    };
-                    if event_tx.send(Box::new(msg)).is_err() {
-                        break;
-                    }
-                }
-                Ok(Event::Resize(width, height)) => {
-                    let msg = WindowSizeMsg { width, height };
-                    if event_tx.send(Box::new(msg)).is_err() {
-                        break;
-                    }
-                }
-                Ok(Event::FocusGained) => {
-                    let msg = crate::FocusMsg;
-                    if event_tx.send(Box::new(msg)).is_err() {
-                        break;
-                    }
-                }
-                Ok(Event::FocusLost) => {
-                    let msg = crate::BlurMsg;
-                    if event_tx.send(Box::new(msg)).is_err() {
+                        scroll_delta: MouseMsg::initial_delta(mouse_event.kind),
+                    }),
+                    Instant::now(),
+                ),
+                // A terminal that does not know its size reports zero
+                Ok(Event::Resize(width, height)) if width == 0 || height == 0 => continue,
+                Ok(Event::Resize(width, height)) => Box::new(WindowSizeMsg { width, height }),
+                Ok(Event::FocusGained | Event::FocusLost) => {
+                    let focused = matches!(event, Ok(Event::FocusGained));
+                    if !flush_bursts(&input, &mut bursts) || !focus.push(&input, focused) {
                         break;
                     }
+                    continue;
                 }
                 Ok(Event::Paste(pasted_text)) => {
-                    let msg = crate::event::PasteMsg(pasted_text);
-                    if event_tx.send(Box::new(msg)).is_err() {
+                    // crossterm has already read the whole paste, so this
+                    // only splits it up for delivery
+                    let paste = PasteAssembler::with_text(input.paste_chunk_size, pasted_text);
+                    if !flush_bursts(&input, &mut bursts) || !paste.finish(&input) {
                         break;
                     }
+                    continue;
                 }
                 Err(e) => {
                     return Err(Error::Io(e));
                 }
+            };
+            // Anything but a key press ends a burst, and must not overtake it
+            if !flush_bursts(&input, &mut bursts) || !input.send(msg) {
+                break;
             }
         }
 
+        if flush_bursts(&input, &mut bursts) {
+            focus.flush(&input);
+        }
         Ok(())
     }
 
@@ -301,6 +565,14 @@ impl InputHandler {
     /// This method reads line-based input from a custom async reader and converts
     /// each line into individual `KeyMsg` events. Each character in a line becomes
     /// a separate key event, and the newline is converted to an `Enter` key event.
+    /// SGR mouse reports embedded in a line (see [`parse_sgr_mouse`]) are
+    /// delivered as `MouseMsg` events instead, and focus reports (`ESC [ I`
+    /// and `ESC [ O`) as `FocusMsg` and `BlurMsg`.
+    ///
+    /// With a paste heuristic, the characters of lines that arrive together
+    /// count as one burst, so a long line is delivered as a `PasteMsg`.
+    /// Text between `ESC [ 200 ~` and `ESC [ 201 ~` is a bracketed paste,
+    /// which may span lines and is read in pieces until its end marker.
     ///
     /// This is primarily intended for testing and scenarios where you need to
     /// simulate keyboard input from a file or other source.
@@ -309,6 +581,8 @@ impl InputHandler {
     ///
     /// * `event_tx` - Channel sender for dispatching processed events
     /// * `reader` - The async reader to read input from
+    /// * `bursts` - Burst detection for the paste heuristic, if enabled
+    /// * `focus` - Debouncing of focus changes
     ///
     /// # Returns
     ///
@@ -329,35 +603,162 @@ impl InputHandler {
     /// - `KeyMsg { key: KeyCode::Char('o'), modifiers: KeyModifiers::NONE }`
     /// - `KeyMsg { key: KeyCode::Enter, modifiers: KeyModifiers::NONE }`
     async fn run_custom_input(
-        event_tx: crate::event::EventSender,
+        input: Input,
         reader: Pin<Box<dyn AsyncRead + Send + Unpin>>,
+        mut bursts: Option<BurstDetector>,
+        mut focus: FocusDebouncer,
     ) -> Result<(), Error> {
         let mut buf_reader = BufReader::new(reader);
         let mut line = String::new();
+        // The bracketed paste being read, if its end marker is still to come
+        let mut paste: Option<PasteAssembler> = None;
 
         loop {
+            // Wait for more input only as long as a held-back burst or focus
+            // change allows. `fill_buf` does not consume anything, so timing
+            // it out is safe.
+            if let Some(deadline) = next_deadline(&bursts, &focus) {
+                match crate::runtime::timeout_at(deadline.into_std(), buf_reader.fill_buf()).await {
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(Error::Io(e)),
+                    None => {
+                        if !flush_due(&input, &mut bursts, &mut focus) {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(assembler) = paste.as_mut() {
+                // Read the paste in pieces rather than a line at a time, so a
+                // huge paste without line breaks is never buffered whole
+                let buf = buf_reader.fill_buf().await.map_err(Error::Io)?;
+                if buf.is_empty() {
+                    break; // EOF
+                }
+                let read = buf.len();
+                let end = assembler.feed(buf);
+                buf_reader.consume(end.unwrap_or(read));
+                let sent = match end {
+                    Some(_) => paste.take().is_some_and(|paste| paste.finish(&input)),
+                    None => assembler.send_ready(&input),
+                };
+                if !sent {
+                    return Ok(());
+                }
+                // Let the program handle other messages between pieces
+                crate::runtime::yield_now().await;
+                continue;
+            }
+
             line.clear();
-            match buf_reader.read_line(&mut line).await {
+            // A paste may be far longer than a line, so read a line only up
+            // to the start of a paste in it
+            let buffered = buf_reader.fill_buf().await.map_err(Error::Io)?;
+            let read = match split_before_paste(buffered) {
+                Some(len) => {
+                    line.push_str(&String::from_utf8_lossy(&buffered[..len]));
+                    buf_reader.consume(len);
+                    Ok(len)
+                }
+                None => buf_reader.read_line(&mut line).await,
+            };
+            match read {
                 Ok(0) => break, // EOF
                 Ok(_) => {
-                    // Process each character in the line as a separate key event
-                    for ch in line.trim().chars() {
+                    // Process each character in the line as a separate key event,
+                    // except for embedded SGR mouse reports which become mouse events,
+                    // focus reports, and keyboard enhancement reports and cursor key
+                    // sequences which become their keys. Replies to keyboard
+                    // enhancement queries are reported as such.
+                    let body = line.trim_end_matches(['\r', '\n']).trim_matches(' ');
+                    let lead = line.len() - line.trim_start_matches(' ').len();
+                    let mut rest = body;
+                    while let Some(ch) = rest.chars().next() {
+                        if rest.starts_with(PASTE_START) {
+                            if !flush_bursts(&input, &mut bursts) {
+                                return Ok(());
+                            }
+                            // The paste runs to its end marker, line breaks
+                            // and all
+                            let start = body.len() - rest.len() + PASTE_START.len();
+                            let mut assembler = PasteAssembler::new(input.paste_chunk_size);
+                            match assembler.feed(&line.as_bytes()[lead + start..]) {
+                                Some(len) => {
+                                    if !assembler.finish(&input) {
+                                        return Ok(());
+                                    }
+                                    rest = &body[start + len..];
+                                }
+                                None => {
+                                    if !assembler.send_ready(&input) {
+                                        return Ok(());
+                                    }
+                                    paste = Some(assembler);
+                                    break;
+                                }
+                            }
+                            continue;
+                        }
+                        if let Some(focused) = split_focus_report(rest) {
+                            rest = &rest[3..];
+                            if !flush_bursts(&input, &mut bursts) || !focus.push(&input, focused) {
+                                return Ok(());
+                            }
+                            continue;
+                        }
+                        if let Some((msg, len)) = split_keyboard_enhancement_reply(rest) {
+                            rest = &rest[len..];
+                            if !flush_bursts(&input, &mut bursts) || !input.send(Box::new(msg)) {
+                                return Ok(());
+                            }
+                            continue;
+                        }
+                        if let Some((msg, len)) = split_sgr_mouse(rest) {
+                            rest = &rest[len..];
+                            if let Some(msg) = msg {
+                                if !flush_bursts(&input, &mut bursts)
+                                    || !input.send_stamped(Box::new(msg), Instant::now())
+                                {
+                                    return Ok(());
+                                }
+                            }
+                            continue;
+                        }
+                        if let Some((msg, len)) = split_csi_u_key(rest) {
+                            rest = &rest[len..];
+                            if let Some(msg) = msg {
+                                if !send_key(&input, &mut bursts, msg) {
+                                    return Ok(());
+                                }
+                            }
+                            continue;
+                        }
+                        if let Some((msg, len)) = split_cursor_key(rest) {
+                            rest = &rest[len..];
+                            if !send_key(&input, &mut bursts, msg) {
+                                return Ok(());
+                            }
+                            continue;
+                        }
+                        rest = &rest[ch.len_utf8()..];
                         let msg = KeyMsg {
-                            key: KeyCode::Char(ch),
+                            key: legacy_key(ch),
                             modifiers: KeyModifiers::NONE,
                         };
-                        if event_tx.send(Box::new(msg)).is_err() {
+                        if !send_key(&input, &mut bursts, msg) {
                             return Ok(());
                         }
                     }
 
-                    // Send Enter key for the newline
-                    if line.ends_with('\n') {
+                    // Send Enter key for the newline, unless it was pasted
+                    if line.ends_with('\n') && paste.is_none() {
                         let msg = KeyMsg {
                             key: KeyCode::Enter,
                             modifiers: KeyModifiers::NONE,
                         };
-                        if event_tx.send(Box::new(msg)).is_err() {
+                        if !send_key(&input, &mut bursts, msg) {
                             return Ok(());
                         }
                     }
@@ -366,6 +767,763 @@ impl InputHandler {
             }
         }
 
+        // Deliver a paste cut short by the end of input as it is
+        if paste.is_some_and(|paste| !paste.finish(&input)) {
+            return Ok(());
+        }
+        if flush_bursts(&input, &mut bursts) {
+            focus.flush(&input);
+        }
         Ok(())
     }
 }
+
+/// Returns whether `input` starts with a focus report, `ESC [ I` when the
+/// terminal gained focus or `ESC [ O` when it lost it.
+fn split_focus_report(input: &str) -> Option<bool> {
+    match input.as_bytes().get(..3)? {
+        b"\x1b[I" => Some(true),
+        b"\x1b[O" => Some(false),
+        _ => None,
+    }
+}
+
+/// Returns when the held-back burst or focus change that is due first must
+/// be sent, if anything is held back.
+fn next_deadline(bursts: &Option<BurstDetector>, focus: &FocusDebouncer) -> Option<Instant> {
+    let burst = bursts.as_ref().and_then(BurstDetector::deadline);
+    match (burst, focus.deadline()) {
+        (Some(burst), Some(focus)) => Some(burst.min(focus)),
+        (burst, focus) => burst.or(focus),
+    }
+}
+
+/// Sends the held-back burst and focus change whose deadlines have passed.
+/// Returns `false` once the channel is closed.
+fn flush_due(
+    input: &Input,
+    bursts: &mut Option<BurstDetector>,
+    focus: &mut FocusDebouncer,
+) -> bool {
+    let now = Instant::now();
+    let burst_due = bursts
+        .as_ref()
+        .and_then(BurstDetector::deadline)
+        .is_some_and(|deadline| deadline <= now);
+    if burst_due && !flush_bursts(input, bursts) {
+        return false;
+    }
+    if focus.deadline().is_some_and(|deadline| deadline <= now) {
+        return focus.flush(input);
+    }
+    true
+}
+
+/// Waits for the next keyboard enhancement query, forever if there are none.
+async fn next_query(queries: &mut Option<mpsc::UnboundedReceiver<()>>) -> Option<()> {
+    match queries {
+        Some(queries) => queries.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Waits for the next terminal event, giving up at `deadline` if one is set.
+async fn next_event(
+    event_stream: &mut EventStream,
+    deadline: Option<Instant>,
+) -> Option<Option<std::io::Result<Event>>> {
+    match deadline {
+        Some(deadline) => {
+            crate::runtime::timeout_at(deadline.into_std(), event_stream.next()).await
+        }
+        None => Some(event_stream.next().await),
+    }
+}
+
+/// Asks the terminal for its keyboard enhancement support through crossterm.
+///
+/// crossterm only reports whether the protocol is supported. The flags the
+/// terminal would report are the ones this program pushed, if any.
+async fn query_terminal_keyboard_enhancements(
+    active: &AtomicBool,
+) -> Option<KeyboardEnhancementFlags> {
+    let supported =
+        crate::runtime::spawn_blocking(crossterm::terminal::supports_keyboard_enhancement).await;
+    match supported {
+        Some(Ok(Ok(true))) if active.load(Ordering::SeqCst) => {
+            Some(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        }
+        Some(Ok(Ok(true))) => Some(KeyboardEnhancementFlags::empty()),
+        _ => None,
+    }
+}
+
+/// Splits a leading reply to a keyboard enhancement query off `input`.
+///
+/// Terminals that support the protocol answer the query `ESC [ ? u` with
+/// their active flags as `ESC [ ? flags u`. The query is followed by a
+/// primary device attributes request (`ESC [ c`), which every terminal
+/// answers with `ESC [ ? ... c`; that answer alone means the protocol is not
+/// supported. Returns the reply and its byte length, or `None` if `input`
+/// does not start with either reply.
+fn split_keyboard_enhancement_reply(input: &str) -> Option<(KeyboardEnhancementsMsg, usize)> {
+    let body = input.strip_prefix("\x1b[?")?;
+    let end = body.find(|c: char| !(c.is_ascii_digit() || c == ';'))?;
+    let supported_flags = match body.as_bytes()[end] {
+        b'u' => {
+            let bits = body[..end].parse::<u8>().ok()?;
+            Some(KeyboardEnhancementFlags::from_bits_truncate(bits))
+        }
+        b'c' => None,
+        _ => return None,
+    };
+    Some((KeyboardEnhancementsMsg { supported_flags }, 2 + 1 + end + 1))
+}
+
+/// Maps a character read from a custom input source to its key.
+///
+/// Like a legacy terminal, `\r` is always Enter and `\t` is always Tab, even
+/// though Ctrl+M and Ctrl+I send the same bytes. See
+/// [`input_is_ambiguous`](crate::event::input_is_ambiguous).
+fn legacy_key(ch: char) -> KeyCode {
+    match ch {
+        '\r' => KeyCode::Enter,
+        '\t' => KeyCode::Tab,
+        _ => KeyCode::Char(ch),
+    }
+}
+
+/// Sends a key press, or hands it to the burst detector when the paste
+/// heuristic is enabled. Returns `false` once the channel is closed.
+fn send_key(input: &Input, bursts: &mut Option<BurstDetector>, msg: KeyMsg) -> bool {
+    let now = Instant::now();
+    match bursts {
+        Some(bursts) => send_all(input, bursts.push(msg, now)),
+        None => input.send_stamped(Box::new(msg), now),
+    }
+}
+
+/// Sends the key presses the burst detector is holding back, if any.
+/// Returns `false` once the channel is closed.
+fn flush_bursts(input: &Input, bursts: &mut Option<BurstDetector>) -> bool {
+    match bursts {
+        Some(bursts) => send_all(input, bursts.flush()),
+        None => true,
+    }
+}
+
+/// Sends `msgs` in order, stamping those decoded at a known time. Returns
+/// `false` once the channel is closed.
+fn send_all(input: &Input, msgs: Vec<(Msg, Option<Instant>)>) -> bool {
+    msgs.into_iter().all(|(msg, at)| match at {
+        Some(at) => input.send_stamped(msg, at),
+        None => input.send(msg),
+    })
+}
+
+/// Where an `InputHandler` delivers decoded input.
+struct Input {
+    event_tx: EventSender,
+    timestamps: bool,
+    /// Whether the keyboard enhancement protocol is active
+    keyboard_enhancement: Arc<AtomicBool>,
+    /// The size of the pieces bracketed pastes are sent in, if streamed
+    paste_chunk_size: Option<usize>,
+}
+
+impl Input {
+    /// Sends a message. Returns `false` once the channel is closed.
+    fn send(&self, msg: Msg) -> bool {
+        self.event_tx.send(msg).is_ok()
+    }
+
+    /// Sends a key, mouse or focus message decoded at `at`.
+    fn send_stamped(&self, msg: Msg, at: Instant) -> bool {
+        self.send(self.stamp(msg, at))
+    }
+
+    /// Stamps a key, mouse or focus message with its decode time and
+    /// whether it is ambiguous, if enabled.
+    fn stamp(&self, msg: Msg, at: Instant) -> Msg {
+        let stamp = self.timestamps.then(|| InputStamp {
+            at: at.into_std(),
+            ambiguous: self.is_ambiguous(&msg),
+        });
+        TimestampedMsg::join(msg, stamp)
+    }
+
+    /// Returns whether `msg` is an Enter or Tab that may have been Ctrl+M
+    /// or Ctrl+I, because the keyboard enhancement protocol is off.
+    fn is_ambiguous(&self, msg: &Msg) -> bool {
+        msg.downcast_ref::<KeyMsg>().is_some_and(|key| {
+            matches!(key.key, KeyCode::Enter | KeyCode::Tab) && key.modifiers.is_empty()
+        }) && !self.keyboard_enhancement.load(Ordering::SeqCst)
+    }
+}
+
+/// Holds back focus changes to collapse those that arrive in quick
+/// succession. See [`InputHandler::with_focus_debounce`].
+struct FocusDebouncer {
+    window: Duration,
+    /// The latest focus change not sent yet, and when it arrived
+    pending: Option<(bool, Instant)>,
+    /// The last focus change sent, if any
+    sent: Option<bool>,
+}
+
+impl FocusDebouncer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: None,
+            sent: None,
+        }
+    }
+
+    /// When the pending focus change is sent if no other arrives.
+    fn deadline(&self) -> Option<Instant> {
+        self.pending.map(|(_, at)| at + self.window)
+    }
+
+    /// Adds a focus change that arrived now, sending it right away when
+    /// debouncing is off. Returns `false` once the channel is closed.
+    fn push(&mut self, input: &Input, focused: bool) -> bool {
+        let now = Instant::now();
+        if self.window.is_zero() {
+            return input.send_stamped(Self::msg(focused), now);
+        }
+        self.pending = Some((focused, now));
+        true
+    }
+
+    /// Sends the pending focus change, unless it leaves focus where the
+    /// last one sent did. Returns `false` once the channel is closed.
+    fn flush(&mut self, input: &Input) -> bool {
+        let Some((focused, at)) = self.pending.take() else {
+            return true;
+        };
+        if self.sent == Some(focused) {
+            return true;
+        }
+        self.sent = Some(focused);
+        input.send_stamped(Self::msg(focused), at)
+    }
+
+    fn msg(focused: bool) -> Msg {
+        if focused {
+            Box::new(FocusMsg)
+        } else {
+            Box::new(BlurMsg)
+        }
+    }
+}
+
+/// The sequence a terminal sends before bracketed pasted text.
+const PASTE_START: &str = "\x1b[200~";
+
+/// The sequence a terminal sends after bracketed pasted text.
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Returns the length of `buffered` up to and including the start of a
+/// bracketed paste, if one starts before the first line break.
+fn split_before_paste(buffered: &[u8]) -> Option<usize> {
+    let start = PASTE_START.as_bytes();
+    let line_end = buffered
+        .iter()
+        .position(|&byte| byte == b'\n')
+        .unwrap_or(buffered.len());
+    let at = buffered[..line_end]
+        .windows(start.len())
+        .position(|window| window == start)?;
+    Some(at + start.len())
+}
+
+/// Collects the text of a bracketed paste as it is read, and delivers it as
+/// one `PasteMsg` or in chunks. See [`InputHandler::with_paste_chunks`].
+struct PasteAssembler {
+    chunk_size: Option<usize>,
+    /// Text read but not sent yet
+    text: String,
+    /// Bytes read but not decoded yet: what may be the start of the end
+    /// marker, or of a character that is not complete yet
+    pending: Vec<u8>,
+}
+
+impl PasteAssembler {
+    fn new(chunk_size: Option<usize>) -> Self {
+        Self::with_text(chunk_size, String::new())
+    }
+
+    /// Starts from a paste that has been read completely.
+    fn with_text(chunk_size: Option<usize>, text: String) -> Self {
+        Self {
+            chunk_size,
+            text,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Takes in bytes read after the start of the paste. Once the end marker
+    /// is among them, returns how many of them make up the rest of the
+    /// paste, the end marker included.
+    fn feed(&mut self, bytes: &[u8]) -> Option<usize> {
+        let start = self.pending.len();
+        self.pending.extend_from_slice(bytes);
+        // The end marker may have started in an earlier read
+        let from = start.saturating_sub(PASTE_END.len() - 1);
+        let found = self.pending[from..]
+            .windows(PASTE_END.len())
+            .position(|window| window == PASTE_END);
+        if let Some(at) = found {
+            let end = from + at;
+            self.pending.truncate(end);
+            return Some(end + PASTE_END.len() - start);
+        }
+        let len = self.pending.len().saturating_sub(PASTE_END.len() - 1);
+        let complete = match std::str::from_utf8(&self.pending[..len]) {
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            _ => len,
+        };
+        self.text
+            .push_str(&String::from_utf8_lossy(&self.pending[..complete]));
+        self.pending.drain(..complete);
+        None
+    }
+
+    /// Sends the text read so far as `PasteChunkMsg`s, if the paste is
+    /// streamed, keeping back a last piece shorter than a chunk. Returns
+    /// `false` once the channel is closed.
+    fn send_ready(&mut self, input: &Input) -> bool {
+        let Some(chunk_size) = self.chunk_size else {
+            return true;
+        };
+        let mut sent = 0;
+        while self.text.len() - sent >= chunk_size {
+            let mut end = sent + chunk_size;
+            while !self.text.is_char_boundary(end) {
+                end -= 1;
+            }
+            if end == sent {
+                // A single character longer than a chunk
+                end += self.text[sent..].chars().next().map_or(0, char::len_utf8);
+            }
+            let text = self.text[sent..end].to_string();
+            if !input.send(Box::new(PasteChunkMsg { text })) {
+                return false;
+            }
+            sent = end;
+        }
+        self.text.drain(..sent);
+        true
+    }
+
+    /// Sends the rest of the paste: all of it as one `PasteMsg`, or its last
+    /// chunks followed by `PasteEndMsg`. Returns `false` once the channel is
+    /// closed.
+    fn finish(mut self, input: &Input) -> bool {
+        self.text.push_str(&String::from_utf8_lossy(&self.pending));
+        if self.chunk_size.is_none() {
+            return input.send(Box::new(PasteMsg::new(self.text, true)));
+        }
+        if !self.send_ready(input) {
+            return false;
+        }
+        let last = (!self.text.is_empty()).then_some(PasteChunkMsg { text: self.text });
+        last.is_none_or(|last| input.send(Box::new(last))) && input.send(Box::new(PasteEndMsg))
+    }
+}
+
+/// Holds back bursts of key presses to tell pastes from typing.
+///
+/// Keys are buffered from the first pasteable key on. Once
+/// `burst_threshold` of them arrived within `burst_window`, the burst is a
+/// paste that lasts until no key arrives for `burst_window`, and is sent as
+/// one `PasteMsg`. Otherwise the keys are sent unchanged when the window
+/// closes.
+struct BurstDetector {
+    heuristic: PasteHeuristic,
+    keys: Vec<(KeyMsg, Instant)>,
+    text: String,
+    started: Instant,
+    last: Instant,
+}
+
+impl BurstDetector {
+    fn new(heuristic: PasteHeuristic) -> Self {
+        let now = Instant::now();
+        Self {
+            heuristic,
+            keys: Vec::new(),
+            text: String::new(),
+            started: now,
+            last: now,
+        }
+    }
+
+    /// Returns the character a key contributes to pasted text, if it can be
+    /// part of a paste at all.
+    fn pasted_char(key: &KeyMsg) -> Option<char> {
+        if !(key.modifiers - KeyModifiers::SHIFT).is_empty() {
+            return None;
+        }
+        match key.key {
+            KeyCode::Char(ch) => Some(ch),
+            KeyCode::Enter => Some('\n'),
+            KeyCode::Tab => Some('\t'),
+            _ => None,
+        }
+    }
+
+    fn is_paste(&self) -> bool {
+        self.keys.len() >= self.heuristic.burst_threshold
+    }
+
+    /// When the current burst ends if no further key arrives.
+    fn deadline(&self) -> Option<Instant> {
+        if self.keys.is_empty() {
+            None
+        } else if self.is_paste() {
+            Some(self.last + self.heuristic.burst_window)
+        } else {
+            Some(self.started + self.heuristic.burst_window)
+        }
+    }
+
+    /// Adds a key that arrived at `now`, returning the messages that are
+    /// ready to be sent with the times their keys arrived.
+    fn push(&mut self, key: KeyMsg, now: Instant) -> Vec<(Msg, Option<Instant>)> {
+        let Some(ch) = Self::pasted_char(&key) else {
+            let mut msgs = self.flush();
+            msgs.push((Box::new(key), Some(now)));
+            return msgs;
+        };
+
+        let mut msgs = Vec::new();
+        if self.deadline().is_some_and(|deadline| now > deadline) {
+            msgs = self.flush();
+        }
+        if self.keys.is_empty() {
+            self.started = now;
+        }
+        self.last = now;
+        self.keys.push((key, now));
+        self.text.push(ch);
+        msgs
+    }
+
+    /// Ends the current burst, returning it as one `PasteMsg` if it was long
+    /// enough and as the original key presses otherwise.
+    fn flush(&mut self) -> Vec<(Msg, Option<Instant>)> {
+        let text = std::mem::take(&mut self.text);
+        if self.is_paste() {
+            self.keys.clear();
+            vec![(Box::new(PasteMsg::new(text, false)), None)]
+        } else {
+            self.keys
+                .drain(..)
+                .map(|(key, at)| (Box::new(key) as Msg, Some(at)))
+                .collect()
+        }
+    }
+}
+
+/// Parses an SGR (mode 1006) mouse report into a `MouseMsg`.
+///
+/// SGR reports have the form `ESC [ < Cb ; Cx ; Cy M` for presses and
+/// `ESC [ < Cb ; Cx ; Cy m` for releases. Crossterm decodes these for the
+/// regular terminal input stream; this function exposes the same decoding for
+/// custom input sources, recorded sessions, and tests.
+///
+/// The low bits of `Cb` select the button. When the wheel flag (64) is set,
+/// they select the wheel direction instead: up, down, left, right. Horizontal
+/// wheels and trackpads report left/right as X11 buttons 6 and 7, which
+/// become `MouseEventKind::ScrollLeft` and `MouseEventKind::ScrollRight`.
+/// Coordinates are converted from SGR's 1-based values to the 0-based values
+/// used by `MouseMsg`.
+///
+/// # Arguments
+///
+/// * `seq` - A complete SGR mouse report, including the leading escape
+///
+/// # Returns
+///
+/// The decoded `MouseMsg`, or `None` if `seq` is not a well-formed SGR
+/// mouse report or reports a button this crate does not model (8 and up).
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::input::parse_sgr_mouse;
+/// use crossterm::event::MouseEventKind;
+///
+/// // Horizontal wheel to the right at column 10, row 5
+/// let msg = parse_sgr_mouse("\x1b[<67;10;5M").unwrap();
+/// assert_eq!(msg.button, MouseEventKind::ScrollRight);
+/// assert_eq!((msg.x, msg.y), (9, 4));
+/// ```
+pub fn parse_sgr_mouse(seq: &str) -> Option<MouseMsg> {
+    match split_sgr_mouse(seq) {
+        Some((msg, len)) if len == seq.len() => msg,
+        _ => None,
+    }
+}
+
+/// Splits a leading SGR mouse report off `input`.
+///
+/// Returns the decoded message (if the report is valid) and the byte length
+/// of the report, or `None` if `input` does not start with a terminated
+/// `ESC [ <` sequence.
+fn split_sgr_mouse(input: &str) -> Option<(Option<MouseMsg>, usize)> {
+    let body = input.strip_prefix("\x1b[<")?;
+    let end = body.find(|c: char| !(c.is_ascii_digit() || c == ';'))?;
+    let pressed = match body.as_bytes()[end] {
+        b'M' => true,
+        b'm' => false,
+        _ => return None,
+    };
+    let len = 3 + end + 1;
+
+    let mut params = body[..end].split(';').map(|p| p.parse::<u16>().ok());
+    let (Some(Some(cb)), Some(Some(cx)), Some(Some(cy)), None) =
+        (params.next(), params.next(), params.next(), params.next())
+    else {
+        return Some((None, len));
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if cb & 4 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if cb & 8 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if cb & 16 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+
+    let button = match cb & 0b11 {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        _ => MouseButton::Right,
+    };
+    let kind = if cb & 128 != 0 {
+        // Buttons 8-11 (back/forward and friends) have no MouseEventKind
+        return Some((None, len));
+    } else if cb & 64 != 0 {
+        match cb & 0b11 {
+            0 => MouseEventKind::ScrollUp,
+            1 => MouseEventKind::ScrollDown,
+            2 => MouseEventKind::ScrollLeft,
+            _ => MouseEventKind::ScrollRight,
+        }
+    } else if cb & 32 != 0 {
+        if cb & 0b11 == 3 {
+            MouseEventKind::Moved
+        } else {
+            MouseEventKind::Drag(button)
+        }
+    } else if pressed && cb & 0b11 != 3 {
+        MouseEventKind::Down(button)
+    } else {
+        MouseEventKind::Up(button)
+    };
+
+    // SGR reports are 1-based; `MouseMsg` is 0-based like crossterm. A
+    // (malformed) zero coordinate saturates to the first cell.
+    let msg = MouseMsg {
+        x: cx.saturating_sub(1),
+        y: cy.saturating_sub(1),
+        button: kind,
+        modifiers,
+        scroll_delta: MouseMsg::initial_delta(kind),
+    };
+    Some((Some(msg), len))
+}
+
+/// Parses a key report from the keyboard enhancement ("CSI u") protocol.
+///
+/// With the protocol active, terminals report keys that are ambiguous in
+/// legacy mode as `ESC [ code ; modifiers u`, so Ctrl+M (`\x1b[109;5u`)
+/// is distinct from Enter (`\r` or `\x1b[13u`). Custom input sources
+/// decode such reports into `KeyMsg` events automatically.
+///
+/// # Arguments
+///
+/// * `seq` - A complete key report, including the leading escape
+///
+/// # Returns
+///
+/// The decoded `KeyMsg`, or `None` if `seq` is not a well-formed key report,
+/// reports a key release, or reports a functional key this decoder does not
+/// model.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::input::parse_csi_u_key;
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// let ctrl_m = parse_csi_u_key("\x1b[109;5u").unwrap();
+/// assert_eq!(ctrl_m.key, KeyCode::Char('m'));
+/// assert_eq!(ctrl_m.modifiers, KeyModifiers::CONTROL);
+///
+/// let enter = parse_csi_u_key("\x1b[13u").unwrap();
+/// assert_eq!(enter.key, KeyCode::Enter);
+/// ```
+pub fn parse_csi_u_key(seq: &str) -> Option<KeyMsg> {
+    match split_csi_u_key(seq) {
+        Some((msg, len)) if len == seq.len() => msg,
+        _ => None,
+    }
+}
+
+/// Splits a leading keyboard enhancement key report off `input`.
+///
+/// Returns the decoded message (if the report is a key press this crate
+/// models) and the byte length of the report, or `None` if `input` does not
+/// start with a terminated `ESC [ <digits> ... u` sequence.
+fn split_csi_u_key(input: &str) -> Option<(Option<KeyMsg>, usize)> {
+    let body = input.strip_prefix("\x1b[")?;
+    if !body.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let end = body.find(|c: char| !(c.is_ascii_digit() || c == ';' || c == ':'))?;
+    if body.as_bytes()[end] != b'u' {
+        return None;
+    }
+    let len = 2 + end + 1;
+
+    // code[:alternates][;modifiers[:event]][;text]
+    let mut params = body[..end].split(';');
+    let code = params
+        .next()
+        .and_then(|p| p.split(':').next()?.parse::<u32>().ok());
+    let mut mods = params.next().unwrap_or("1").split(':');
+    let modifier_bits = mods.next().map_or(Some(1), |m| {
+        if m.is_empty() {
+            Some(1)
+        } else {
+            m.parse::<u8>().ok()
+        }
+    });
+    let event = mods.next().unwrap_or("1");
+    let (Some(code), Some(modifier_bits)) = (code, modifier_bits) else {
+        return Some((None, len));
+    };
+    if event == "3" {
+        // Key release
+        return Some((None, len));
+    }
+
+    let modifiers = csi_modifiers(modifier_bits);
+    let key = match code {
+        9 if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        9 => KeyCode::Tab,
+        13 => KeyCode::Enter,
+        27 => KeyCode::Esc,
+        127 => KeyCode::Backspace,
+        // Functional keys live in the Unicode private use area
+        0xE000..=0xF8FF => return Some((None, len)),
+        _ => match char::from_u32(code) {
+            Some(ch) => KeyCode::Char(ch),
+            None => return Some((None, len)),
+        },
+    };
+    Some((Some(KeyMsg { key, modifiers }), len))
+}
+
+/// Decodes the modifier parameter of a CSI key report, which is one more
+/// than a bit set of Shift, Alt, Control and Super.
+fn csi_modifiers(param: u8) -> KeyModifiers {
+    let bits = param.saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+    if bits & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if bits & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if bits & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if bits & 8 != 0 {
+        modifiers |= KeyModifiers::SUPER;
+    }
+    modifiers
+}
+
+/// Parses a single arrow, Home or End key sequence.
+///
+/// Terminals send these keys as CSI sequences (`ESC [ A`) in normal
+/// cursor-key mode and as SS3 sequences (`ESC O A`) in application mode,
+/// which a previously run program may have left on. Both are accepted, as
+/// are modified keys (`ESC [ 1 ; 5 A` for Ctrl+Up) and the `ESC [ 1 ~` /
+/// `ESC [ 4 ~` forms of Home and End. Custom input sources are decoded with
+/// the same rules.
+///
+/// Returns `None` if `seq` is not exactly one such sequence.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::input::parse_cursor_key;
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// assert_eq!(parse_cursor_key("\x1b[A").unwrap().key, KeyCode::Up);
+/// assert_eq!(parse_cursor_key("\x1bOA").unwrap().key, KeyCode::Up);
+///
+/// let ctrl_end = parse_cursor_key("\x1b[1;5F").unwrap();
+/// assert_eq!(ctrl_end.key, KeyCode::End);
+/// assert_eq!(ctrl_end.modifiers, KeyModifiers::CONTROL);
+/// ```
+pub fn parse_cursor_key(seq: &str) -> Option<KeyMsg> {
+    match split_cursor_key(seq) {
+        Some((msg, len)) if len == seq.len() => Some(msg),
+        _ => None,
+    }
+}
+
+/// Splits a leading arrow, Home or End key sequence off `input`.
+///
+/// Returns the key and the byte length of the sequence, or `None` if
+/// `input` does not start with one.
+fn split_cursor_key(input: &str) -> Option<(KeyMsg, usize)> {
+    let final_key = |byte: u8| match byte {
+        b'A' => Some(KeyCode::Up),
+        b'B' => Some(KeyCode::Down),
+        b'C' => Some(KeyCode::Right),
+        b'D' => Some(KeyCode::Left),
+        b'H' => Some(KeyCode::Home),
+        b'F' => Some(KeyCode::End),
+        _ => None,
+    };
+    let bytes = input.as_bytes();
+    match bytes.get(..2)? {
+        b"\x1bO" => {
+            let key = final_key(*bytes.get(2)?)?;
+            let modifiers = KeyModifiers::NONE;
+            Some((KeyMsg { key, modifiers }, 3))
+        }
+        b"\x1b[" => {
+            let body = &input[2..];
+            let end = body.find(|c: char| !(c.is_ascii_digit() || c == ';'))?;
+            let mut params = body[..end].split(';');
+            let first = params.next().unwrap_or("");
+            let modifiers = match params.next() {
+                Some(m) => csi_modifiers(m.parse().ok()?),
+                None => KeyModifiers::NONE,
+            };
+            if params.next().is_some() {
+                return None;
+            }
+            let key = match (first, body.as_bytes()[end]) {
+                ("1" | "7", b'~') => KeyCode::Home,
+                ("4" | "8", b'~') => KeyCode::End,
+                ("" | "1", byte) => final_key(byte)?,
+                _ => return None,
+            };
+            Some((KeyMsg { key, modifiers }, 2 + end + 1))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,233 @@
+//! Key descriptions for matching keyboard input.
+//!
+//! A [`KeySpec`] describes a single key press, such as `q` or `ctrl+c`, and
+//! can be compared against incoming [`KeyMsg`]s. It is used by
+//! `ProgramBuilder::quit_keys` and `CommonHandlers::quit_keys` to configure
+//! the keys that end the program.
+
+use crate::event::KeyMsg;
+use crate::{Error, Msg};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fmt;
+use std::str::FromStr;
+
+/// A key press to match against incoming [`KeyMsg`]s.
+///
+/// Key specs can be built from a `KeyCode` (with or without modifiers) or
+/// parsed from a string such as `"q"`, `"esc"`, `"ctrl+c"` or `"alt+enter"`.
+///
+/// # Matching
+///
+/// The key code and modifiers must both match. For character keys the shift
+/// modifier is ignored, because terminals report shifted characters
+/// inconsistently; use an uppercase character (`"Q"`) to match a shifted
+/// letter.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::{KeyMsg, KeySpec};
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// let spec: KeySpec = "ctrl+c".parse().unwrap();
+/// assert!(spec.matches(&KeyMsg {
+///     key: KeyCode::Char('c'),
+///     modifiers: KeyModifiers::CONTROL,
+/// }));
+/// assert!(!spec.matches(&KeyMsg {
+///     key: KeyCode::Char('c'),
+///     modifiers: KeyModifiers::NONE,
+/// }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeySpec {
+    /// The key code to match.
+    pub key: KeyCode,
+    /// The modifiers that must be held.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeySpec {
+    /// Creates a key spec from a key code and modifiers.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key code to match
+    /// * `modifiers` - The modifiers that must be held
+    pub const fn new(key: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Parses a key spec from a string such as `"q"` or `"ctrl+shift+up"`.
+    ///
+    /// The string is a `+`-separated list of modifiers (`ctrl`, `alt`,
+    /// `shift`) followed by a key: a single character, a named key (`enter`,
+    /// `esc`, `tab`, `backspace`, `space`, `up`, `down`, `left`, `right`,
+    /// `home`, `end`, `pgup`, `pgdown`, `delete`, `insert`) or a function
+    /// key (`f1`..`f24`). Modifiers and key names are case-insensitive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Configuration` if the string is empty, names an unknown
+    /// modifier or key, or has more than one key.
+    pub fn parse(spec: &str) -> Result<Self, Error> {
+        let invalid =
+            |reason: &str| Error::Configuration(format!("invalid key '{spec}': {reason}"));
+
+        // A trailing "+" means the plus key itself, as in "ctrl++"
+        let (mods, key) = match spec.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None if spec == "+" => ("", "+"),
+            None => match spec.rsplit_once('+') {
+                Some((rest, key)) => (rest, key),
+                None => ("", spec),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        if !mods.is_empty() {
+            for part in mods.split('+') {
+                modifiers |= match part.to_ascii_lowercase().as_str() {
+                    "ctrl" | "control" => KeyModifiers::CONTROL,
+                    "alt" | "option" => KeyModifiers::ALT,
+                    "shift" => KeyModifiers::SHIFT,
+                    "" => return Err(invalid("empty modifier")),
+                    other => return Err(invalid(&format!("unknown modifier '{other}'"))),
+                };
+            }
+        }
+
+        let mut chars = key.chars();
+        let key = match (chars.next(), chars.next()) {
+            (None, _) => return Err(invalid("missing key")),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdown" | "pagedown" => KeyCode::PageDown,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(invalid(&format!("unknown key '{key}'"))),
+                },
+            },
+        };
+
+        Ok(Self { key, modifiers })
+    }
+
+    /// Returns whether `msg` is a press of this key.
+    pub fn matches(&self, msg: &KeyMsg) -> bool {
+        if msg.key != self.key {
+            return false;
+        }
+        if matches!(self.key, KeyCode::Char(_)) {
+            let ignore = KeyModifiers::SHIFT;
+            msg.modifiers.difference(ignore) == self.modifiers.difference(ignore)
+        } else {
+            msg.modifiers == self.modifiers
+        }
+    }
+}
+
+/// `Ctrl+C`, which quits unless the program or model opts out.
+const CTRL_C: KeySpec = KeySpec::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+/// Returns whether `msg` presses one of the quit `keys`, or `Ctrl+C` when
+/// `ctrl_c` is set.
+///
+/// This is the one definition of a quit key, used both by the program for
+/// `ProgramBuilder::quit_keys` and by `CommonHandlers::handle_common`. A
+/// `KeyRepeatMsg` counts like the key it repeats.
+pub(crate) fn is_quit_key(msg: &Msg, keys: &[KeySpec], ctrl_c: bool) -> bool {
+    KeyMsg::presses(msg).is_some_and(|(key_msg, _)| {
+        (ctrl_c && CTRL_C.matches(key_msg)) || keys.iter().any(|spec| spec.matches(key_msg))
+    })
+}
+
+impl FromStr for KeySpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<KeyCode> for KeySpec {
+    fn from(key: KeyCode) -> Self {
+        Self::new(key, KeyModifiers::NONE)
+    }
+}
+
+impl From<(KeyCode, KeyModifiers)> for KeySpec {
+    fn from((key, modifiers): (KeyCode, KeyModifiers)) -> Self {
+        Self::new(key, modifiers)
+    }
+}
+
+impl From<char> for KeySpec {
+    fn from(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+}
+
+impl From<&str> for KeySpec {
+    /// Parses a key spec from a string literal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid key spec. Use [`KeySpec::parse`]
+    /// for strings that are not known to be valid, such as user
+    /// configuration.
+    fn from(spec: &str) -> Self {
+        match Self::parse(spec) {
+            Ok(spec) => spec,
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+impl fmt::Display for KeySpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("shift+")?;
+        }
+        match self.key {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pgup"),
+            KeyCode::PageDown => f.write_str("pgdown"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
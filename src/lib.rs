@@ -11,13 +11,14 @@
 //! - **Terminal Interface Abstraction**: Works with real terminals and test environments
 //! - **Comprehensive Event Handling**: Keyboard, mouse, window resize, and focus events
 //! - **Memory Monitoring**: Built-in memory usage tracking and leak detection
+//! - **Frame Animations**: Timer-driven spinners and looping animations without boilerplate
 //! - **Gradient Rendering**: Rich color gradients for progress bars and visual elements
 //! - **Flexible Input Sources**: Support for different input mechanisms and testing
 //!
 //! ## Quick Start
 //!
 //! ```rust,no_run
-//! use bubbletea_rs::{Model, Program, Msg, Cmd};
+//! use bubbletea_rs::{Model, Program, Msg, Cmd, IntoCmd};
 //!
 //! struct MyModel {
 //!     counter: i32,
@@ -28,9 +29,7 @@
 //!         (Self { counter: 0 }, None)
 //!     }
 //!
-//!     fn update(&mut self, _msg: Msg) -> Option<Cmd> {
-//!         None
-//!     }
+//!     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
 //!
 //!     fn view(&self) -> String {
 //!         format!("Counter: {}", self.counter)
@@ -64,11 +63,30 @@
 //!
 //! Testing is supported through the `DummyTerminal` which allows you to test your
 //! applications without requiring an actual terminal interface.
+//!
+//! ## Runtimes
+//!
+//! With the default `tokio-runtime` feature, `Program::run` is awaited on a
+//! tokio runtime. The `sync-runtime` feature adds `Program::run_blocking`,
+//! which runs the program on a small built-in executor instead, so a CLI
+//! that has no other use for async needs neither `#[tokio::main]` nor
+//! tokio's runtime. At least one of the two features must be enabled.
 
 #![warn(missing_docs)]
 
+#[cfg(not(any(feature = "tokio-runtime", feature = "sync-runtime")))]
+compile_error!("bubbletea-rs needs the `tokio-runtime` or `sync-runtime` feature");
+
+/// Frame-based animation driver for spinners and other looping animations.
+pub mod animation;
+/// Measuring and wrapping text that contains ANSI escape sequences.
+pub mod ansi;
+/// Injectable time source for deterministic timer tests.
+pub mod clock;
 /// Commands for async operations that produce messages.
 pub mod command;
+/// Easing functions and tweening for frame-based animations.
+pub mod easing;
 /// Error types and handling.
 pub mod error;
 /// Event types and message passing system.
@@ -77,41 +95,101 @@ pub mod event;
 pub mod gradient;
 /// Input handling abstraction for different sources.
 pub mod input;
+/// Key descriptions for matching keyboard input.
+pub mod key;
+/// Fixed-height live region with log lines scrolling above it.
+pub mod log_region;
 /// Logging utilities for debugging and monitoring.
 pub mod logging;
 /// Memory monitoring and leak detection.
 pub mod memory;
+/// Message names, `Debug` output and consuming downcasts.
+pub mod message;
 /// The core Model trait defining application behavior.
 pub mod model;
+/// Composable program-level behaviours.
+pub mod plugin;
 /// Program runtime and builder for TUI applications.
 pub mod program;
+/// Animated progress bar component.
+pub mod progress;
+/// Spawning, timers and blocking work on tokio or the built-in executor.
+mod runtime;
 /// Terminal interface abstraction and implementations.
 pub mod terminal;
+/// File system watching for live-reloading content.
+#[cfg(feature = "watch")]
+pub mod watch;
 
+pub use animation::{AnimationTickMsg, FrameAnimator};
+#[cfg(feature = "tokio-runtime")]
+pub use clock::TokioClock;
+pub use clock::{Clock, MockClock};
 pub use command::{
-    batch, cancel_all_timers, cancel_timer, clear_screen, disable_bracketed_paste, disable_mouse,
-    disable_report_focus, enable_bracketed_paste, enable_mouse_all_motion,
-    enable_mouse_cell_motion, enable_report_focus, enter_alt_screen, every, every_with_id,
-    exec_process, exit_alt_screen, hide_cursor, interrupt, printf, println, quit, sequence,
-    set_window_title, show_cursor, suspend, tick, window_size, Batch, Cmd,
+    after_all, after_all_limited, announce, batch, batch_limited, bell, blocking,
+    cancel_all_timers, cancel_timer, cancel_watch, clear_from_start, clear_line, clear_screen,
+    clear_to_end, disable_bracketed_paste, disable_mouse, disable_report_focus,
+    enable_bracketed_paste, enable_mouse_all_motion, enable_mouse_cell_motion, enable_report_focus,
+    enter_alt_screen, every, every_in_group, every_precise, every_precise_with_id,
+    every_with_behavior, every_with_id, exec_process, exit_alt_screen, hide_cursor, interrupt,
+    lazy, pause_render, pause_timer_group, pop_window_title, print_lines, printf, println,
+    push_window_title, query_keyboard_enhancements, quit, quit_if, quit_with, restore_cursor,
+    resume_render, resume_timer_group, save_cursor, screenshot, sequence, sequence_until,
+    sequence_until_msg, sequence_with_progress, set_debug_input_overlay, set_viewport_size,
+    set_window_title, show_cursor, suspend, tick, tick_in_group, try_blocking, viewport_size,
+    visual_bell, window_size, write_file, Batch, Cmd, IntoCmd,
 };
+pub use easing::Tween;
 pub use error::Error;
 pub use event::{
-    BatchMsgInternal, BlurMsg, CancelAllTimersMsg, CancelTimerMsg, ClearScreenMsg,
+    AnnounceMsg, BatchLimitedMsg, BatchMsgInternal, BeginMouseCaptureMsg, BellMsg, BlurMsg,
+    CancelAllTimersMsg, CancelTimerMsg, CancelWatchMsg, CancelledMsg, CapabilitiesMsg, ChordMatch,
+    ChordMatcher, ClearFromStartOfScreenMsg, ClearLineMsg, ClearScreenMsg, ClearToEndOfScreenMsg,
     DisableBracketedPasteMsg, DisableMouseMsg, DisableReportFocusMsg, EnableBracketedPasteMsg,
-    EnableMouseAllMotionMsg, EnableMouseCellMotionMsg, EnableReportFocusMsg, EnterAltScreenMsg,
-    EventReceiver, EventSender, ExitAltScreenMsg, FocusMsg, HideCursorMsg, InterruptMsg, KeyMsg,
-    KillMsg, MouseMsg, Msg, PasteMsg, PrintMsg, PrintfMsg, QuitMsg, RequestWindowSizeMsg,
-    ResumeMsg, SetWindowTitleMsg, ShowCursorMsg, SuspendMsg, WindowSizeMsg,
+    EnableMouseAllMotionMsg, EnableMouseCellMotionMsg, EnableReportFocusMsg, EndMouseCaptureMsg,
+    EndVisualBellMsg, EnterAltScreenMsg, ErrorMsg, EventReceiver, EventSender, ExecRequestMsg,
+    ExitAltScreenMsg, FocusMsg, HideCursorMsg, IdleMsg, InputModes, InterruptMsg, KeyMsg,
+    KeyRepeatMsg, KeyboardEnhancementsMsg, KillMsg, ModesDegradedMsg, ModesMsg, MouseMsg, Msg,
+    NoMsg, PaneFocus, PasteChunkMsg, PasteEndMsg, PasteMsg, PauseRenderMsg, PauseTimerGroupMsg,
+    PopWindowTitleMsg, PrintMsg, PrintfMsg, PushWindowTitleMsg, QueryKeyboardEnhancementsMsg,
+    QuitMsg, QuitWithMsg, RequestViewportSizeMsg, RequestWindowSizeMsg, RestoreCursorMsg,
+    ResumeMsg, ResumeRenderMsg, ResumeTimerGroupMsg, SaveCursorMsg, SequenceMsgInternal,
+    SetDebugInputOverlayMsg, SetViewportSizeMsg, SetWindowTitleMsg, ShowCursorMsg, SuspendMsg,
+    TerminalLostMsg, ViewportSizeMsg, VisualBellMsg, WindowSizeMsg,
 };
 pub use gradient::{
-    charm_default_gradient, gradient_filled_segment, gradient_filled_segment_with_buffer, lerp_rgb,
+    charm_default_gradient, gradient_filled_segment, gradient_filled_segment_with_buffer,
+    gradient_filled_segment_with_spec, health_gradient, lerp_rgb, sample, sample_at,
+    spinner_frames_gradient, temperature_gradient, ColorWheel, GradientSpec, SpinnerStyle,
+};
+pub use input::{
+    parse_csi_u_key, parse_cursor_key, parse_sgr_mouse, InputHandler, InputSource, PasteHeuristic,
 };
-pub use input::{InputHandler, InputSource};
-pub use memory::{MemoryHealth, MemoryMonitor, MemorySnapshot};
+pub use key::KeySpec;
+pub use memory::{
+    FrameChurn, LineChurn, MemoryHealth, MemoryMonitor, MemorySnapshot, MessageRateExceededMsg,
+    MessageStats, MessageTypeStats, ViewStats, ViewTimings,
+};
+pub use message::{Message, MsgExt};
 pub use model::Model;
-pub use program::{MouseMotion, Program, ProgramBuilder, ProgramConfig};
-pub use terminal::{DummyTerminal, Terminal, TerminalInterface};
+pub use plugin::{Plugin, PluginRegistry};
+pub use program::{
+    FlushStrategy, HeadlessRunner, MouseMotion, ProcessResult, Program, ProgramBuilder,
+    ProgramConfig, ProgramResult, RunState,
+};
+pub use progress::{AnimatedBar, BarColor, ProgressFrameMsg};
+pub use terminal::{
+    resolve_size, ColorProfile, CursorKeyMode, DummyTerminal, DummyTerminalEvent,
+    DummyTerminalPool, OutputStream, RenderHint, SizeSource, Terminal, TerminalCapabilities,
+    TerminalInterface, TerminalMode, DEFAULT_SIZE,
+};
+
+#[cfg(feature = "fetch")]
+pub use command::{fetch, fetch_with_timeout};
+#[cfg(feature = "watch")]
+pub use command::{watch_path, watch_path_with_id};
+#[cfg(feature = "watch")]
+pub use watch::WatchEvent;
 
 #[cfg(feature = "logging")]
 pub use logging::log_to_file;
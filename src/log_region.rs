@@ -0,0 +1,256 @@
+//! A fixed-height live region for inline programs, with log lines scrolling
+//! into the terminal's scrollback above it.
+//!
+//! Build tools like cargo print each finished step as a permanent line and
+//! keep a short status area pinned below them. [`LogRegion`] produces the
+//! escape sequences for that layout: [`repaint`](LogRegion::repaint) redraws
+//! the live region in place, and [`log`](LogRegion::log) erases it and
+//! writes lines that stay behind in the scrollback. The region is only ever
+//! erased from its own top row down, so logged lines are never overwritten.
+//!
+//! The `Program` uses this when built with
+//! [`ProgramBuilder::inline_with_log_region`](crate::ProgramBuilder::inline_with_log_region).
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::log_region::LogRegion;
+//!
+//! let mut region = LogRegion::new(1);
+//! let size = (80, 24);
+//!
+//! assert_eq!(region.repaint("building...", size), "building...");
+//! let out = region.log("compiled foo\n", size) + &region.repaint("building bar...", size);
+//! assert_eq!(out, "\r\x1b[Jcompiled foo\r\nbuilding bar...");
+//! ```
+
+use crate::ansi;
+
+/// The live region at the bottom of an inline program.
+///
+/// Tracks how many rows the region currently occupies, so the next repaint
+/// or log can move back to its top. Sizes are `(columns, rows)` as returned
+/// by [`TerminalInterface::size`](crate::TerminalInterface::size); a
+/// dimension of zero means unknown and disables clamping to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRegion {
+    live_rows: u16,
+    drawn_rows: u16,
+    /// The lines last drawn, as cut to the terminal width
+    drawn: String,
+}
+
+impl LogRegion {
+    /// Creates a region of at most `live_rows` rows. A value of zero is
+    /// treated as one row.
+    pub fn new(live_rows: u16) -> Self {
+        Self {
+            live_rows: live_rows.max(1),
+            drawn_rows: 0,
+            drawn: String::new(),
+        }
+    }
+
+    /// Returns the configured maximum height of the region.
+    pub fn live_rows(&self) -> u16 {
+        self.live_rows
+    }
+
+    /// Returns how many rows the region occupies on screen right now.
+    pub fn drawn_rows(&self) -> u16 {
+        self.drawn_rows
+    }
+
+    /// Returns the height of the region on a terminal with `terminal_rows`
+    /// rows.
+    ///
+    /// The region is clamped to leave at least one row above it, so it
+    /// never covers the whole screen and logged lines always stay visible
+    /// as they scroll away.
+    pub fn height(&self, terminal_rows: u16) -> u16 {
+        if terminal_rows == 0 {
+            return self.live_rows;
+        }
+        self.live_rows.min(terminal_rows.saturating_sub(1)).max(1)
+    }
+
+    /// Returns the sequence that redraws the region with `view`.
+    ///
+    /// Only the last lines of `view` that fit in the region are drawn, each
+    /// cut to the terminal width so it takes exactly one row. The cursor is
+    /// left at the end of the last line.
+    pub fn repaint(&mut self, view: &str, size: (u16, u16)) -> String {
+        let (columns, rows) = size;
+        let lines: Vec<&str> = view
+            .strip_suffix('\n')
+            .unwrap_or(view)
+            .split('\n')
+            .collect();
+        let visible = &lines[lines.len().saturating_sub(usize::from(self.height(rows)))..];
+
+        let mut out = self.erase(rows);
+        for (i, line) in visible.iter().enumerate() {
+            if i > 0 {
+                self.drawn.push('\n');
+            }
+            self.drawn.push_str(&truncate(line, usize::from(columns)));
+        }
+        out.push_str(&self.drawn.replace('\n', "\r\n"));
+        self.drawn_rows = visible.len() as u16;
+        out
+    }
+
+    /// Accounts for the terminal re-wrapping the drawn region to `columns`
+    /// columns.
+    ///
+    /// Every drawn line fit the width it was drawn at, but terminals that
+    /// reflow their screen on resize wrap those lines onto more rows when
+    /// the terminal narrows. Call this before the next repaint or log when
+    /// the width may have changed since the last repaint, such as after
+    /// returning from the alternate screen, so it erases exactly the rows
+    /// the region now covers.
+    pub fn reflow(&mut self, columns: u16) {
+        if self.drawn_rows > 0 {
+            let rows = ansi::height(&self.drawn, usize::from(columns));
+            self.drawn_rows = u16::try_from(rows).unwrap_or(u16::MAX);
+        }
+    }
+
+    /// Returns the sequence that erases the region and writes `text` as
+    /// permanent lines in its place.
+    ///
+    /// Long lines are wrapped to the terminal width. A trailing newline is
+    /// ignored, and text without one still ends its line. The region is
+    /// left empty; call [`repaint`](Self::repaint) to draw it again below
+    /// the new lines.
+    pub fn log(&mut self, text: &str, size: (u16, u16)) -> String {
+        let (columns, rows) = size;
+        let mut out = self.erase(rows);
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        for line in ansi::wrap(text, usize::from(columns)) {
+            out.push_str(&line);
+            out.push_str("\r\n");
+        }
+        out
+    }
+
+    /// Returns the sequence that moves to the top of the region and clears
+    /// everything from there down.
+    ///
+    /// After a resize the region may span fewer rows than were drawn; the
+    /// move is clamped to the screen so it never reaches above the region.
+    fn erase(&mut self, terminal_rows: u16) -> String {
+        if self.drawn_rows == 0 {
+            return String::new();
+        }
+        let mut up = self.drawn_rows - 1;
+        if terminal_rows > 0 {
+            up = up.min(terminal_rows - 1);
+        }
+        self.drawn_rows = 0;
+        self.drawn.clear();
+        if up == 0 {
+            "\r\x1b[J".to_string()
+        } else {
+            format!("\r\x1b[{up}A\x1b[J")
+        }
+    }
+}
+
+/// Cuts `line` to `width` visible characters, keeping escape sequences
+/// intact. A width of zero leaves the line unchanged.
+fn truncate(line: &str, width: usize) -> String {
+    if width == 0 || ansi::visible_width(line) <= width {
+        return line.to_string();
+    }
+    ansi::wrap(line, width).swap_remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repaint_draws_in_place() {
+        let mut region = LogRegion::new(3);
+        assert_eq!(region.repaint("a\nb", (80, 24)), "a\r\nb");
+        assert_eq!(region.drawn_rows(), 2);
+        assert_eq!(
+            region.repaint("c\nd\ne", (80, 24)),
+            "\r\x1b[1A\x1b[Jc\r\nd\r\ne"
+        );
+        assert_eq!(region.drawn_rows(), 3);
+    }
+
+    #[test]
+    fn test_repaint_keeps_last_lines_and_cuts_to_width() {
+        let mut region = LogRegion::new(2);
+        assert_eq!(region.repaint("1\n2\n3\n", (80, 24)), "2\r\n3");
+        assert_eq!(region.repaint("abcdef", (4, 24)), "\r\x1b[1A\x1b[Jabcd");
+    }
+
+    #[test]
+    fn test_resize_reclamps_region() {
+        let mut region = LogRegion::new(5);
+        region.repaint("1\n2\n3\n4\n5", (80, 24));
+        assert_eq!(region.drawn_rows(), 5);
+
+        // The terminal shrank to 3 rows: never move above its top row, and
+        // keep a row free above the region
+        let out = region.repaint("1\n2\n3\n4\n5", (80, 3));
+        assert_eq!(out, "\r\x1b[2A\x1b[J4\r\n5");
+        assert_eq!(region.height(3), 2);
+
+        // Growing again restores the configured height
+        assert_eq!(region.height(24), 5);
+        assert_eq!(region.height(0), 5);
+    }
+
+    #[test]
+    fn test_log_burst_writes_lines_above_region() {
+        let mut region = LogRegion::new(2);
+        region.repaint("status\nprogress", (80, 24));
+
+        let mut out = String::new();
+        for step in 0..3 {
+            out.push_str(&region.log(&format!("step {step}\n"), (80, 24)));
+        }
+        out.push_str(&region.repaint("status\nprogress", (80, 24)));
+
+        // Only the first log moves up, and only over the region's own rows
+        assert_eq!(
+            out,
+            "\r\x1b[1A\x1b[Jstep 0\r\nstep 1\r\nstep 2\r\nstatus\r\nprogress"
+        );
+    }
+
+    #[test]
+    fn test_reflow_counts_rows_wrapped_by_a_narrower_terminal() {
+        let mut region = LogRegion::new(3);
+        region.repaint("status\n0123456789", (10, 24));
+        assert_eq!(region.drawn_rows(), 2);
+
+        // Narrowed to 4 columns, "status" takes 2 rows and the second line 3
+        region.reflow(4);
+        assert_eq!(region.drawn_rows(), 5);
+        assert_eq!(region.repaint("ok", (4, 24)), "\r\x1b[4A\x1b[Jok");
+
+        // Widening again never needs more rows than were drawn
+        region.reflow(80);
+        assert_eq!(region.drawn_rows(), 1);
+    }
+
+    #[test]
+    fn test_reflow_of_an_empty_region_draws_nothing() {
+        let mut region = LogRegion::new(2);
+        region.reflow(4);
+        assert_eq!(region.drawn_rows(), 0);
+        assert_eq!(region.repaint("a", (4, 24)), "a");
+    }
+
+    #[test]
+    fn test_log_wraps_long_lines() {
+        let mut region = LogRegion::new(1);
+        assert_eq!(region.log("abcdef", (3, 24)), "abc\r\ndef\r\n");
+    }
+}
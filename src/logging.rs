@@ -8,6 +8,7 @@
 //!
 //! - File-based logging with automatic file creation
 //! - Append-only logging to preserve existing log data
+//! - Rotation by size or by date with [`LogConfig`]
 //! - Graceful degradation when logging feature is disabled
 //!
 //! # Usage
@@ -28,11 +29,18 @@
 use crate::Error;
 use std::path::Path;
 
+#[cfg(feature = "logging")]
+mod rolling;
+
+#[cfg(feature = "logging")]
+pub use rolling::{flush, LogConfig};
+
 /// Set up file logging for the application.
 ///
 /// This function initializes file-based logging by creating or opening the specified
 /// log file in append mode. The file will be created if it doesn't exist, and new
-/// log entries will be appended to preserve existing log data.
+/// log entries will be appended to preserve existing log data. The file
+/// grows without bound; use [`LogConfig`] to rotate it.
 ///
 /// # Arguments
 ///
@@ -0,0 +1,432 @@
+//! Rolling file logging, rotated by size or by date.
+
+use crate::{Cmd, Error};
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::Mutex;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Configures logging to a file that is rotated by size or by date.
+///
+/// When the file is rotated it is renamed with a UTC timestamp suffix, such
+/// as `app.log.20261017T093000`, and a new file is opened in its place.
+/// [`build`](Self::build) installs the logger for the `log` macros.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use bubbletea_rs::logging::LogConfig;
+///
+/// LogConfig::new("app.log")
+///     .max_size(1024 * 1024)
+///     .max_files(5)
+///     .rotate_daily(true)
+///     .build()?;
+///
+/// log::info!("Application started");
+/// # Ok::<(), bubbletea_rs::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    path: PathBuf,
+    max_size: Option<u64>,
+    max_files: Option<usize>,
+    rotate_daily: bool,
+    level: LevelFilter,
+}
+
+impl LogConfig {
+    /// Logs to `path`, appending to it if it exists.
+    ///
+    /// Without further settings the file is never rotated and records at
+    /// `Info` level and above are written.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            max_size: None,
+            max_files: None,
+            rotate_daily: false,
+            level: LevelFilter::Info,
+        }
+    }
+
+    /// Rotates the file before a record would grow it past `bytes`.
+    ///
+    /// A single record larger than `bytes` is still written, to a file of
+    /// its own.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Keeps at most `count` rotated files besides the current one,
+    /// deleting the oldest. By default rotated files are never deleted.
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count);
+        self
+    }
+
+    /// Rotates the file at the first record of each new UTC day.
+    pub fn rotate_daily(mut self, rotate: bool) -> Self {
+        self.rotate_daily = rotate;
+        self
+    }
+
+    /// Writes only records at `level` and above. Defaults to `Info`.
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Opens the log file and installs the logger for the `log` macros.
+    ///
+    /// Records are buffered; use [`flush`] to write them out, for example
+    /// right before quitting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Io` if the file or its directory cannot be created,
+    /// and `Error::Configuration` if a logger is already installed.
+    pub fn build(self) -> Result<(), Error> {
+        let file = RollingFile::open(&self, SystemTime::now())?;
+        let logger = FileLogger {
+            file: Mutex::new(file),
+            level: self.level,
+        };
+        log::set_boxed_logger(Box::new(logger))
+            .map_err(|_| Error::Configuration("a logger is already installed".to_string()))?;
+        log::set_max_level(self.level);
+        Ok(())
+    }
+}
+
+/// Creates a command that writes out buffered log records.
+///
+/// Flushes whichever logger is installed for the `log` macros. Run it
+/// before quitting, for example with
+/// `sequence(vec![logging::flush(), quit()])`, so the last records are not
+/// lost. The command produces no message.
+pub fn flush() -> Cmd {
+    Box::pin(async {
+        log::logger().flush();
+        None
+    })
+}
+
+/// Writes log records through a [`RollingFile`].
+struct FileLogger {
+    file: Mutex<RollingFile>,
+    level: LevelFilter,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let now = SystemTime::now();
+        let line = format!(
+            "{} {:<5} {}: {}\n",
+            Utc::from(now),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        // Logging must never take the program down
+        let _ = self.file.lock().write_at(line.as_bytes(), now);
+    }
+
+    fn flush(&self) {
+        let _ = self.file.lock().file.flush();
+    }
+}
+
+/// A log file that renames itself and starts over when it gets too big or
+/// a new day starts.
+struct RollingFile {
+    path: PathBuf,
+    file: BufWriter<File>,
+    size: u64,
+    /// The UTC day, counted from the Unix epoch, the file was started on.
+    day: u64,
+    max_size: Option<u64>,
+    max_files: Option<usize>,
+    rotate_daily: bool,
+}
+
+impl RollingFile {
+    fn open(config: &LogConfig, now: SystemTime) -> io::Result<Self> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&config.path)?;
+        let metadata = file.metadata()?;
+        // An existing file belongs to the day it was last written
+        let started = Some(metadata.modified())
+            .filter(|_| metadata.len() > 0)
+            .and_then(Result::ok)
+            .unwrap_or(now);
+        Ok(Self {
+            path: config.path.clone(),
+            file: BufWriter::new(file),
+            size: metadata.len(),
+            day: day_of(started),
+            max_size: config.max_size,
+            max_files: config.max_files,
+            rotate_daily: config.rotate_daily,
+        })
+    }
+
+    /// Writes `record`, rotating first if it is due at `now`.
+    fn write_at(&mut self, record: &[u8], now: SystemTime) -> io::Result<()> {
+        let len = record.len() as u64;
+        let too_big = self
+            .max_size
+            .is_some_and(|max| self.size.saturating_add(len) > max);
+        let new_day = self.rotate_daily && day_of(now) != self.day;
+        if self.size > 0 && (too_big || new_day) {
+            self.rotate(now)?;
+        }
+        self.file.write_all(record)?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn rotate(&mut self, now: SystemTime) -> io::Result<()> {
+        self.file.flush()?;
+        fs::rename(&self.path, self.rotated_path(now))?;
+        self.file = BufWriter::new(open_append(&self.path)?);
+        self.size = 0;
+        self.day = day_of(now);
+        self.prune()
+    }
+
+    /// The name for the current file when rotated at `now`, numbered if a
+    /// file of that name already exists.
+    fn rotated_path(&self, now: SystemTime) -> PathBuf {
+        let mut base = self.path.as_os_str().to_owned();
+        base.push(format!(".{}", Utc::from(now).compact()));
+        let mut candidate = PathBuf::from(&base);
+        let mut n = 1;
+        while candidate.exists() {
+            let mut numbered = base.clone();
+            numbered.push(format!(".{n}"));
+            candidate = PathBuf::from(numbered);
+            n += 1;
+        }
+        candidate
+    }
+
+    /// Deletes the oldest rotated files beyond `max_files`.
+    fn prune(&self) -> io::Result<()> {
+        let Some(max_files) = self.max_files else {
+            return Ok(());
+        };
+        let Some(name) = self.path.file_name() else {
+            return Ok(());
+        };
+        let mut prefix = OsString::from(name);
+        prefix.push(".");
+        let prefix = prefix.to_string_lossy().into_owned();
+        let dir = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        let mut rotated: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .strip_prefix(&prefix)
+                    .is_some_and(|suffix| suffix.starts_with(|c: char| c.is_ascii_digit()))
+            })
+            .map(|entry| entry.path())
+            .collect();
+        // Timestamp suffixes sort oldest first
+        rotated.sort();
+        let excess = rotated.len().saturating_sub(max_files);
+        for path in &rotated[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn seconds_of(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn day_of(time: SystemTime) -> u64 {
+    seconds_of(time) / SECONDS_PER_DAY
+}
+
+/// A UTC date and time, to the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Utc {
+    year: i64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+}
+
+impl Utc {
+    /// Formats as `20261017T093000`, for file names.
+    fn compact(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+impl From<SystemTime> for Utc {
+    fn from(time: SystemTime) -> Self {
+        let seconds = seconds_of(time);
+        let time_of_day = seconds % SECONDS_PER_DAY;
+        // Civil date from days since the epoch, per Howard Hinnant's
+        // `civil_from_days`
+        let days = (seconds / SECONDS_PER_DAY) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u64;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u64;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year,
+            month,
+            day,
+            hour: time_of_day / 3_600,
+            minute: time_of_day % 3_600 / 60,
+            second: time_of_day % 60,
+        }
+    }
+}
+
+impl fmt::Display for Utc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// 2026-10-17T09:30:00Z
+    fn morning() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_792_229_400)
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_utc_formats_calendar_dates() {
+        assert_eq!(Utc::from(UNIX_EPOCH).to_string(), "1970-01-01T00:00:00Z");
+        assert_eq!(Utc::from(morning()).to_string(), "2026-10-17T09:30:00Z");
+        assert_eq!(Utc::from(morning()).compact(), "20261017T093000");
+        // A leap day
+        let leap = UNIX_EPOCH + Duration::from_secs(1_709_164_800);
+        assert_eq!(Utc::from(leap).to_string(), "2024-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn test_rotates_before_exceeding_max_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let config = LogConfig::new(&path).max_size(10);
+        let mut file = RollingFile::open(&config, morning()).unwrap();
+
+        file.write_at(b"123456\n", morning()).unwrap();
+        file.write_at(b"abc\n", morning()).unwrap();
+        file.file.flush().unwrap();
+
+        assert_eq!(names(dir.path()), ["app.log", "app.log.20261017T093000"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "abc\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("app.log.20261017T093000")).unwrap(),
+            "123456\n"
+        );
+    }
+
+    #[test]
+    fn test_rotated_names_are_unique_and_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let config = LogConfig::new(&path).max_size(4).max_files(2);
+        let mut file = RollingFile::open(&config, morning()).unwrap();
+
+        for record in [b"one\n", b"two\n", b"thr\n", b"fou\n"] {
+            file.write_at(record, morning()).unwrap();
+        }
+        file.file.flush().unwrap();
+
+        // Three rotations in the same second; the oldest was deleted
+        assert_eq!(
+            names(dir.path()),
+            [
+                "app.log",
+                "app.log.20261017T093000.1",
+                "app.log.20261017T093000.2"
+            ]
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fou\n");
+    }
+
+    #[test]
+    fn test_rotates_daily_on_first_record_of_a_new_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let config = LogConfig::new(&path).rotate_daily(true);
+        let mut file = RollingFile::open(&config, morning()).unwrap();
+
+        file.write_at(b"morning\n", morning()).unwrap();
+        file.write_at(b"evening\n", morning() + Duration::from_secs(12 * 3_600))
+            .unwrap();
+        let tomorrow = morning() + Duration::from_secs(SECONDS_PER_DAY);
+        file.write_at(b"tomorrow\n", tomorrow).unwrap();
+        file.file.flush().unwrap();
+
+        assert_eq!(names(dir.path()), ["app.log", "app.log.20261018T093000"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "tomorrow\n");
+    }
+}
@@ -1,10 +1,26 @@
 //! Memory usage monitoring utilities.
 //!
 //! This module provides optional memory monitoring features that can be enabled
-//! to track memory usage patterns and identify potential issues.
-
+//! to track memory usage patterns and identify potential issues, including
+//! per-type message counts for finding runaway message loops, timings of
+//! `Model::view` for finding slow frames, and per-line churn for finding
+//! view output that changes every frame.
+
+use parking_lot::Mutex;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the recent rate of each message type is recomputed.
+const RATE_WINDOW: Duration = Duration::from_millis(100);
+
+/// How many of the most recent `view` calls the 95th percentile covers.
+const RECENT_VIEW_CALLS: usize = 1000;
+
+/// How many of the most recent frames a line's churn covers.
+const CHURN_FRAMES: u32 = u64::BITS;
 
 /// Memory usage statistics and monitoring.
 #[derive(Debug, Clone)]
@@ -13,12 +29,17 @@ pub struct MemoryMonitor {
     pub active_timers: Arc<AtomicU64>,
     /// Number of spawned tasks
     pub active_tasks: Arc<AtomicU64>,
+    /// Number of command futures the program is awaiting, if task tracking
+    /// is enabled
+    pub in_flight_commands: Arc<AtomicU64>,
     /// Current channel buffer depth
     pub channel_depth: Arc<AtomicU64>,
     /// Total messages processed
     pub messages_processed: Arc<AtomicU64>,
     /// Peak memory usage (if available)
     pub peak_memory_bytes: Arc<AtomicU64>,
+    /// Per-type message counts, if enabled on the program
+    pub message_stats: Option<MessageStats>,
 }
 
 impl Default for MemoryMonitor {
@@ -33,12 +54,20 @@ impl MemoryMonitor {
         Self {
             active_timers: Arc::new(AtomicU64::new(0)),
             active_tasks: Arc::new(AtomicU64::new(0)),
+            in_flight_commands: Arc::new(AtomicU64::new(0)),
             channel_depth: Arc::new(AtomicU64::new(0)),
             messages_processed: Arc::new(AtomicU64::new(0)),
             peak_memory_bytes: Arc::new(AtomicU64::new(0)),
+            message_stats: None,
         }
     }
 
+    /// Includes per-type message counts from `stats` in snapshots.
+    pub fn with_message_stats(mut self, stats: MessageStats) -> Self {
+        self.message_stats = Some(stats);
+        self
+    }
+
     /// Increment the timer count.
     pub fn timer_added(&self) {
         self.active_timers.fetch_add(1, Ordering::Relaxed);
@@ -69,6 +98,28 @@ impl MemoryMonitor {
         self.active_tasks.load(Ordering::Relaxed)
     }
 
+    /// Increment the in-flight command count.
+    pub fn command_started(&self) {
+        self.in_flight_commands.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrement the in-flight command count.
+    pub fn command_finished(&self) {
+        self.in_flight_commands.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Get the number of command futures the program is awaiting.
+    ///
+    /// Only counted with
+    /// [`ProgramBuilder::track_tasks`](crate::ProgramBuilder::track_tasks).
+    /// A command counts from when it is started until it produces its
+    /// message, or is dropped on shutdown. A count that keeps climbing
+    /// points at commands that start more commands than they finish, such
+    /// as a `tick` re-armed twice per fire.
+    pub fn get_in_flight_commands(&self) -> u64 {
+        self.in_flight_commands.load(Ordering::Relaxed)
+    }
+
     /// Update the channel depth.
     pub fn set_channel_depth(&self, depth: u64) {
         self.channel_depth.store(depth, Ordering::Relaxed);
@@ -116,9 +167,15 @@ impl MemoryMonitor {
         MemorySnapshot {
             active_timers: self.get_active_timers(),
             active_tasks: self.get_active_tasks(),
+            in_flight_commands: self.get_in_flight_commands(),
             channel_depth: self.get_channel_depth(),
             messages_processed: self.get_messages_processed(),
             peak_memory_bytes: self.get_peak_memory_bytes(),
+            message_types: self
+                .message_stats
+                .as_ref()
+                .map(MessageStats::snapshot)
+                .unwrap_or_default(),
         }
     }
 
@@ -126,6 +183,7 @@ impl MemoryMonitor {
     pub fn reset(&self) {
         self.active_timers.store(0, Ordering::Relaxed);
         self.active_tasks.store(0, Ordering::Relaxed);
+        self.in_flight_commands.store(0, Ordering::Relaxed);
         self.channel_depth.store(0, Ordering::Relaxed);
         self.messages_processed.store(0, Ordering::Relaxed);
         self.peak_memory_bytes.store(0, Ordering::Relaxed);
@@ -166,12 +224,18 @@ pub struct MemorySnapshot {
     pub active_timers: u64,
     /// Number of currently active async tasks
     pub active_tasks: u64,
+    /// Number of command futures the program is awaiting; zero unless task
+    /// tracking is enabled
+    pub in_flight_commands: u64,
     /// Current message channel buffer depth
     pub channel_depth: u64,
     /// Total number of messages processed since startup
     pub messages_processed: u64,
     /// Peak memory usage recorded in bytes
     pub peak_memory_bytes: u64,
+    /// Per-type message counts, busiest first; empty unless message
+    /// statistics are enabled
+    pub message_types: Vec<MessageTypeStats>,
 }
 
 /// Health check result for memory usage.
@@ -189,9 +253,10 @@ impl std::fmt::Display for MemorySnapshot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Memory Snapshot - Timers: {}, Tasks: {}, Channel: {}, Messages: {}, Peak Memory: {} bytes",
+            "Memory Snapshot - Timers: {}, Tasks: {}, Commands: {}, Channel: {}, Messages: {}, Peak Memory: {} bytes",
             self.active_timers,
             self.active_tasks,
+            self.in_flight_commands,
             self.channel_depth,
             self.messages_processed,
             self.peak_memory_bytes
@@ -214,6 +279,556 @@ impl std::fmt::Display for MemoryHealth {
     }
 }
 
+/// Counts of one message type seen by the event loop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageTypeStats {
+    /// The registered name of the type, or its `TypeId` if it has none
+    pub type_name: String,
+    /// Messages of this type received by the event loop
+    pub processed: u64,
+    /// Messages of this type discarded by the message filter or a plugin
+    pub dropped: u64,
+    /// Average rate since statistics started, in messages per second
+    pub rate_per_sec: f64,
+    /// Rate over the most recent measurement window, in messages per second
+    pub recent_rate_per_sec: f64,
+}
+
+impl std::fmt::Display for MessageTypeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} processed, {} dropped, {:.1}/s average, {:.1}/s recent",
+            self.type_name,
+            self.processed,
+            self.dropped,
+            self.rate_per_sec,
+            self.recent_rate_per_sec
+        )
+    }
+}
+
+/// A message type that kept arriving faster than the configured limit.
+///
+/// Reported once per type by [`MessageStats::record_processed`], and
+/// delivered to the model by a program built with
+/// [`ProgramBuilder::warn_on_message_rate`](crate::ProgramBuilder::warn_on_message_rate).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageRateExceededMsg {
+    /// The registered name of the type, or its `TypeId` if it has none
+    pub type_name: String,
+    /// The rate that exceeded the limit, in messages per second
+    pub rate_per_sec: f64,
+}
+
+/// The limit a message type's rate is checked against.
+#[derive(Debug, Clone, Copy)]
+struct RateLimit {
+    max_per_second: u64,
+    sustained_for: Duration,
+}
+
+/// Running counts for one message type.
+#[derive(Debug)]
+struct TypeCounter {
+    processed: u64,
+    dropped: u64,
+    window_start: Instant,
+    window_count: u64,
+    recent_rate: f64,
+    /// Start of the current run of windows over the rate limit
+    over_limit_since: Option<Instant>,
+    reported: bool,
+}
+
+#[derive(Debug)]
+struct MessageStatsInner {
+    started: Instant,
+    counters: HashMap<TypeId, TypeCounter>,
+    names: HashMap<TypeId, &'static str>,
+    limit: Option<RateLimit>,
+}
+
+/// Per-type counts of the messages handled by a program, for finding
+/// message types that are produced far more often than intended.
+///
+/// `Msg` is a `Box<dyn Any>`, which identifies its type only by `TypeId`.
+/// The built-in message types are named automatically; register your own
+/// with [`register`](Self::register) to see their names instead of a
+/// `TypeId` in snapshots and warnings. Types registered with
+/// [`message::register`](crate::message::register) are shown by their
+/// [`Message::name`](crate::message::Message::name).
+///
+/// `MessageStats` is a cheap handle: clones share the same counts, so a
+/// clone taken before `Program::run` can be read while the program runs.
+///
+/// # Example
+///
+/// ```rust
+/// use bubbletea_rs::memory::MessageStats;
+/// use bubbletea_rs::Msg;
+///
+/// struct TickMsg;
+///
+/// let stats = MessageStats::new();
+/// stats.register::<TickMsg>();
+///
+/// let msg: Msg = Box::new(TickMsg);
+/// stats.record_processed(&msg);
+///
+/// let snapshot = stats.snapshot();
+/// assert!(snapshot[0].type_name.ends_with("TickMsg"));
+/// assert_eq!(snapshot[0].processed, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageStats {
+    inner: Arc<Mutex<MessageStatsInner>>,
+}
+
+impl Default for MessageStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageStats {
+    /// Creates empty statistics with the built-in message types named.
+    pub fn new() -> Self {
+        let stats = Self {
+            inner: Arc::new(Mutex::new(MessageStatsInner {
+                started: Instant::now(),
+                counters: HashMap::new(),
+                names: HashMap::new(),
+                limit: None,
+            })),
+        };
+        stats.register::<crate::KeyMsg>();
+        stats.register::<crate::KeyRepeatMsg>();
+        stats.register::<crate::KeyboardEnhancementsMsg>();
+        stats.register::<crate::MouseMsg>();
+        stats.register::<crate::PasteMsg>();
+        stats.register::<crate::PasteChunkMsg>();
+        stats.register::<crate::PasteEndMsg>();
+        stats.register::<crate::WindowSizeMsg>();
+        stats.register::<crate::QuitMsg>();
+        stats.register::<crate::FocusMsg>();
+        stats.register::<crate::BlurMsg>();
+        stats.register::<crate::AnimationTickMsg>();
+        stats.register::<crate::ProgressFrameMsg>();
+        stats.register::<crate::event::BatchMsgInternal>();
+        stats.register::<crate::event::BatchCmdMsg>();
+        stats.register::<crate::event::BatchLimitedMsg>();
+        stats.register::<crate::event::EveryMsgInternal>();
+        stats.register::<crate::event::PrintMsg>();
+        stats
+    }
+
+    /// Reports a message type that stays above `max_per_second` for at
+    /// least `sustained_for`.
+    ///
+    /// Rates are measured over short windows, so brief bursts don't
+    /// trigger it. Each type is reported once.
+    pub fn with_rate_limit(self, max_per_second: u64, sustained_for: Duration) -> Self {
+        self.inner.lock().limit = Some(RateLimit {
+            max_per_second,
+            sustained_for,
+        });
+        self
+    }
+
+    /// Names `T` in snapshots and warnings.
+    pub fn register<T: Any>(&self) {
+        self.inner
+            .lock()
+            .names
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
+    }
+
+    /// Counts `msg` as received, returning a warning the first time its
+    /// type exceeds the rate limit.
+    pub fn record_processed(&self, msg: &crate::Msg) -> Option<MessageRateExceededMsg> {
+        let type_id = msg.as_ref().type_id();
+        let now = Instant::now();
+        let mut inner = self.inner.lock();
+        if !inner.counters.contains_key(&type_id) {
+            // Prefer the name a `Message` type gives itself
+            if let Some(message) = crate::message::describe(msg) {
+                inner.names.insert(type_id, message.name());
+            }
+        }
+        let limit = inner.limit;
+        let counter = inner
+            .counters
+            .entry(type_id)
+            .or_insert_with(|| TypeCounter {
+                processed: 0,
+                dropped: 0,
+                window_start: now,
+                window_count: 0,
+                recent_rate: 0.0,
+                over_limit_since: None,
+                reported: false,
+            });
+        counter.processed += 1;
+        counter.window_count += 1;
+
+        let elapsed = now.duration_since(counter.window_start);
+        if elapsed < RATE_WINDOW {
+            return None;
+        }
+        counter.recent_rate = counter.window_count as f64 / elapsed.as_secs_f64();
+        let window_start = counter.window_start;
+        counter.window_start = now;
+        counter.window_count = 0;
+
+        let limit = limit?;
+        if counter.recent_rate <= limit.max_per_second as f64 {
+            counter.over_limit_since = None;
+            return None;
+        }
+        let since = *counter.over_limit_since.get_or_insert(window_start);
+        if counter.reported || now.duration_since(since) < limit.sustained_for {
+            return None;
+        }
+        counter.reported = true;
+        let rate_per_sec = counter.recent_rate;
+        Some(MessageRateExceededMsg {
+            type_name: type_label(&inner.names, type_id),
+            rate_per_sec,
+        })
+    }
+
+    /// Counts a message of type `type_id` as discarded before it reached
+    /// the model.
+    pub fn record_dropped(&self, type_id: TypeId) {
+        if let Some(counter) = self.inner.lock().counters.get_mut(&type_id) {
+            counter.dropped += 1;
+        }
+    }
+
+    /// Returns the counts for every type seen so far, busiest first.
+    pub fn snapshot(&self) -> Vec<MessageTypeStats> {
+        let inner = self.inner.lock();
+        let seconds = inner.started.elapsed().as_secs_f64().max(f64::EPSILON);
+        let mut stats: Vec<MessageTypeStats> = inner
+            .counters
+            .iter()
+            .map(|(type_id, counter)| MessageTypeStats {
+                type_name: type_label(&inner.names, *type_id),
+                processed: counter.processed,
+                dropped: counter.dropped,
+                rate_per_sec: counter.processed as f64 / seconds,
+                recent_rate_per_sec: counter.recent_rate,
+            })
+            .collect();
+        stats.sort_by(|a, b| {
+            b.processed
+                .cmp(&a.processed)
+                .then_with(|| a.type_name.cmp(&b.type_name))
+        });
+        stats
+    }
+
+    /// Returns the snapshot as text, one type per line, for logging or
+    /// printing when debugging.
+    pub fn dump(&self) -> String {
+        self.snapshot()
+            .iter()
+            .map(|stats| format!("{stats}\n"))
+            .collect()
+    }
+
+    /// Clears all counts and restarts the rate measurements.
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock();
+        inner.counters.clear();
+        inner.started = Instant::now();
+    }
+}
+
+/// A summary of how long `Model::view` takes.
+///
+/// Returned by [`Program::view_stats`](crate::Program::view_stats) and
+/// [`ViewTimings::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ViewStats {
+    /// How many times `view` has been called.
+    pub call_count: u64,
+    /// The time spent in all `view` calls.
+    pub total_duration: Duration,
+    /// The 95th percentile of the most recent 1000 `view` calls.
+    pub p95_duration: Duration,
+    /// The slowest `view` call.
+    pub slowest_duration: Duration,
+    /// How many frames were skipped because the terminal could not keep
+    /// up; `view` is not called for them.
+    pub frames_skipped: u64,
+}
+
+/// Execution times of `Model::view`, recorded by the program for every
+/// frame it draws.
+///
+/// Clones share the same timings, so a handle taken with
+/// [`Program::view_timings`](crate::Program::view_timings) before `run`
+/// can be read while or after the program runs.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::memory::ViewTimings;
+/// use std::time::Duration;
+///
+/// let timings = ViewTimings::new();
+/// timings.record(Duration::from_millis(2));
+/// timings.record(Duration::from_millis(8));
+///
+/// let stats = timings.stats();
+/// assert_eq!(stats.call_count, 2);
+/// assert_eq!(stats.total_duration, Duration::from_millis(10));
+/// assert_eq!(stats.slowest_duration, Duration::from_millis(8));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ViewTimings {
+    inner: Arc<Mutex<ViewTimingsInner>>,
+}
+
+#[derive(Debug, Default)]
+struct ViewTimingsInner {
+    call_count: u64,
+    total: Duration,
+    slowest: Duration,
+    frames_skipped: u64,
+    /// The most recent call times, oldest first
+    recent: VecDeque<Duration>,
+}
+
+impl ViewTimings {
+    /// Creates empty timings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one `view` call that took `elapsed`.
+    pub fn record(&self, elapsed: Duration) {
+        let mut inner = self.inner.lock();
+        inner.call_count += 1;
+        inner.total += elapsed;
+        inner.slowest = inner.slowest.max(elapsed);
+        if inner.recent.len() == RECENT_VIEW_CALLS {
+            inner.recent.pop_front();
+        }
+        inner.recent.push_back(elapsed);
+    }
+
+    /// Records one frame skipped without calling `view`.
+    pub fn record_skipped(&self) {
+        self.inner.lock().frames_skipped += 1;
+    }
+
+    /// Returns a summary of the recorded calls.
+    pub fn stats(&self) -> ViewStats {
+        let inner = self.inner.lock();
+        let mut recent: Vec<Duration> = inner.recent.iter().copied().collect();
+        recent.sort_unstable();
+        // Nearest rank: the smallest time at least 95% of calls finished in
+        let p95_duration = match recent.len() {
+            0 => Duration::ZERO,
+            len => recent[(len * 95).div_ceil(100) - 1],
+        };
+        ViewStats {
+            call_count: inner.call_count,
+            total_duration: inner.total,
+            p95_duration,
+            slowest_duration: inner.slowest,
+            frames_skipped: inner.frames_skipped,
+        }
+    }
+
+    /// Forgets all recorded calls.
+    pub fn reset(&self) {
+        *self.inner.lock() = ViewTimingsInner::default();
+    }
+}
+
+/// How often one line of the view changed over the most recent frames.
+///
+/// Returned by [`FrameChurn::report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineChurn {
+    /// The zero-based line of the view
+    pub line: usize,
+    /// In how many of the recent frames the line differed from the frame
+    /// before
+    pub changes: u32,
+    /// How many recent frames were compared, at most 64
+    pub frames: u32,
+}
+
+impl LineChurn {
+    /// The share of the recent frames in which the line changed, from 0.0
+    /// to 1.0.
+    pub fn churn(&self) -> f64 {
+        if self.frames == 0 {
+            return 0.0;
+        }
+        f64::from(self.changes) / f64::from(self.frames)
+    }
+}
+
+impl std::fmt::Display for LineChurn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: changed in {} of the last {} frames",
+            self.line, self.changes, self.frames
+        )
+    }
+}
+
+/// Which lines of the view change from one frame to the next, recorded by
+/// the program for every frame it draws when enabled with
+/// [`ProgramBuilder::frame_churn`](crate::ProgramBuilder::frame_churn).
+///
+/// A line that changes in nearly every frame, such as a timestamp rendered
+/// anew each time, keeps the renderer from skipping identical frames and
+/// shows up here with a churn close to 1.0. Lines are compared as the view
+/// returned them, before the program adds overlays or margins.
+///
+/// Clones share the same records, so a handle taken with
+/// [`Program::frame_churn`](crate::Program::frame_churn) before `run` can
+/// be read while or after the program runs.
+///
+/// # Examples
+///
+/// ```
+/// use bubbletea_rs::memory::FrameChurn;
+///
+/// let churn = FrameChurn::new();
+/// for tick in 0..10 {
+///     churn.record(&format!("Dashboard\nuptime: {tick}s\nq: quit"));
+/// }
+///
+/// assert_eq!(churn.changed_lines(), vec![1]);
+/// let report = churn.report();
+/// assert_eq!(report.len(), 1);
+/// assert_eq!((report[0].line, report[0].changes, report[0].frames), (1, 9, 9));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FrameChurn {
+    inner: Arc<Mutex<FrameChurnInner>>,
+}
+
+#[derive(Debug, Default)]
+struct FrameChurnInner {
+    /// The lines of the last frame recorded
+    previous: Option<Vec<String>>,
+    /// Lines that differed between the last two frames
+    changed: Vec<usize>,
+    /// One bit per recent frame for each line, set when the line changed;
+    /// the lowest bit is the latest frame
+    history: Vec<u64>,
+    /// Frames compared so far, up to `CHURN_FRAMES`
+    frames: u32,
+}
+
+impl FrameChurn {
+    /// Creates empty records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a frame, comparing each line of `view` with the same line of
+    /// the frame recorded before it. A line that only one of the two frames
+    /// has counts as changed.
+    pub fn record(&self, view: &str) {
+        let lines: Vec<String> = view.lines().map(str::to_string).collect();
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        if let Some(previous) = &inner.previous {
+            let len = previous.len().max(lines.len());
+            if inner.history.len() < len {
+                inner.history.resize(len, 0);
+            }
+            inner.changed.clear();
+            for (line, bits) in inner.history.iter_mut().enumerate() {
+                let changed = previous.get(line) != lines.get(line);
+                *bits = (*bits << 1) | u64::from(changed);
+                if changed {
+                    inner.changed.push(line);
+                }
+            }
+            inner.frames = (inner.frames + 1).min(CHURN_FRAMES);
+        }
+        inner.previous = Some(lines);
+    }
+
+    /// Returns the lines that differed between the last two frames recorded,
+    /// in order.
+    pub fn changed_lines(&self) -> Vec<usize> {
+        self.inner.lock().changed.clone()
+    }
+
+    /// Returns the churn of every line that changed in the recent frames,
+    /// most changes first.
+    pub fn report(&self) -> Vec<LineChurn> {
+        let inner = self.inner.lock();
+        let mut report: Vec<LineChurn> = inner
+            .history
+            .iter()
+            .enumerate()
+            .filter(|(_, bits)| **bits != 0)
+            .map(|(line, bits)| LineChurn {
+                line,
+                changes: bits.count_ones(),
+                frames: inner.frames,
+            })
+            .collect();
+        report.sort_by(|a, b| b.changes.cmp(&a.changes).then(a.line.cmp(&b.line)));
+        report
+    }
+
+    /// Returns the lines whose churn is at least `min_churn`, in order.
+    pub fn hot_lines(&self, min_churn: f64) -> Vec<usize> {
+        let mut lines: Vec<usize> = self
+            .report()
+            .iter()
+            .filter(|churn| churn.churn() >= min_churn)
+            .map(|churn| churn.line)
+            .collect();
+        lines.sort_unstable();
+        lines
+    }
+
+    /// Returns the lines changed in the last frame and the report as text,
+    /// one line each, for logging or printing when debugging.
+    pub fn dump(&self) -> String {
+        let changed = self.changed_lines();
+        let mut dump = if changed.is_empty() {
+            "last frame: no lines changed\n".to_string()
+        } else {
+            let lines: Vec<String> = changed.iter().map(usize::to_string).collect();
+            format!("last frame: lines {} changed\n", lines.join(", "))
+        };
+        for churn in self.report() {
+            dump.push_str(&format!("{churn}\n"));
+        }
+        dump
+    }
+
+    /// Forgets all recorded frames.
+    pub fn reset(&self) {
+        *self.inner.lock() = FrameChurnInner::default();
+    }
+}
+
+/// Returns the registered name of `type_id`, or its debug form.
+fn type_label(names: &HashMap<TypeId, &'static str>, type_id: TypeId) -> String {
+    match names.get(&type_id) {
+        Some(name) => (*name).to_string(),
+        None => format!("{type_id:?}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,4 +884,95 @@ mod tests {
         monitor.update_peak_memory(2000); // Should update
         assert_eq!(monitor.get_peak_memory_bytes(), 2000);
     }
+
+    #[test]
+    fn test_message_stats_counts_by_type() {
+        struct Custom;
+        let stats = MessageStats::new();
+        let key: crate::Msg = Box::new(crate::QuitMsg);
+        let custom: crate::Msg = Box::new(Custom);
+        stats.record_processed(&key);
+        stats.record_processed(&custom);
+        stats.record_processed(&custom);
+        stats.record_dropped(custom.as_ref().type_id());
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        // Unregistered types fall back to their TypeId
+        assert!(snapshot[0].type_name.starts_with("TypeId"));
+        assert_eq!((snapshot[0].processed, snapshot[0].dropped), (2, 1));
+        assert!(snapshot[1].type_name.ends_with("QuitMsg"));
+
+        let monitor = MemoryMonitor::new().with_message_stats(stats.clone());
+        let types = monitor.snapshot().message_types;
+        assert_eq!(types.len(), 2);
+        assert_eq!(types[0].processed, 2);
+        assert_eq!(stats.dump().lines().count(), 2);
+    }
+
+    #[test]
+    fn test_view_timings_p95_covers_recent_calls() {
+        let timings = ViewTimings::new();
+        for ms in 1..=100 {
+            timings.record(Duration::from_millis(ms));
+        }
+        let stats = timings.stats();
+        assert_eq!(stats.call_count, 100);
+        assert_eq!(stats.p95_duration, Duration::from_millis(95));
+        assert_eq!(stats.slowest_duration, Duration::from_millis(100));
+
+        // Old calls age out of the percentile but not the totals
+        for _ in 0..RECENT_VIEW_CALLS {
+            timings.record(Duration::from_millis(1));
+        }
+        let stats = timings.stats();
+        assert_eq!(stats.call_count, 100 + RECENT_VIEW_CALLS as u64);
+        assert_eq!(stats.p95_duration, Duration::from_millis(1));
+        assert_eq!(stats.slowest_duration, Duration::from_millis(100));
+
+        timings.reset();
+        assert_eq!(timings.stats(), ViewStats::default());
+    }
+
+    #[test]
+    fn test_frame_churn_fingers_the_changing_line() {
+        let churn = FrameChurn::new();
+        for tick in 0..100 {
+            churn.record(&format!("header\n{tick:>3} ms\nfooter\n"));
+        }
+
+        assert_eq!(churn.changed_lines(), vec![1]);
+        let report = churn.report();
+        assert_eq!(report.len(), 1);
+        // Only the most recent frames count
+        assert_eq!(report[0].line, 1);
+        assert_eq!((report[0].changes, report[0].frames), (64, 64));
+        assert_eq!(churn.hot_lines(0.5), vec![1]);
+        assert_eq!(
+            churn.dump(),
+            "last frame: lines 1 changed\nline 1: changed in 64 of the last 64 frames\n"
+        );
+    }
+
+    #[test]
+    fn test_frame_churn_counts_added_and_removed_lines() {
+        let churn = FrameChurn::new();
+        churn.record("a\nb");
+        churn.record("a\nb\nc");
+        churn.record("a\nb");
+        churn.record("a\nb");
+
+        assert!(churn.changed_lines().is_empty());
+        let report = churn.report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(
+            (report[0].line, report[0].changes, report[0].frames),
+            (2, 2, 3)
+        );
+        assert!(churn.hot_lines(0.7).is_empty());
+
+        churn.reset();
+        assert!(churn.report().is_empty());
+        assert_eq!(churn.dump(), "last frame: no lines changed\n");
+    }
 }
@@ -0,0 +1,173 @@
+//! Names and `Debug` output for messages, and consuming downcasts.
+//!
+//! A [`Msg`] is a `Box<dyn Any + Send>`, which on its own says nothing about
+//! what it holds beyond a `TypeId`. Implementing [`Message`] for a message
+//! type gives it a name and access to its `Debug` output, and
+//! [`register`]ing the type lets code that only has a `Msg`, such as
+//! [`MessageStats`](crate::memory::MessageStats), use them. The built-in
+//! message types are registered already.
+//!
+//! With the `derive` feature, `#[derive(Message)]` implements the trait.
+//!
+//! [`MsgExt`] adds helpers to `Msg` itself, such as the consuming downcast
+//! [`take`](MsgExt::take).
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::message::{self, Message, MsgExt};
+//! use bubbletea_rs::Msg;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct TickMsg(u32);
+//!
+//! impl Message for TickMsg {
+//!     fn name(&self) -> &'static str {
+//!         "TickMsg"
+//!     }
+//!
+//!     fn as_debug(&self) -> Option<&dyn std::fmt::Debug> {
+//!         Some(self)
+//!     }
+//! }
+//!
+//! message::register::<TickMsg>();
+//!
+//! let msg: Msg = Box::new(TickMsg(3));
+//! assert_eq!(msg.message_name(), Some("TickMsg"));
+//! assert_eq!(msg.take::<TickMsg>(), Some(TickMsg(3)));
+//! ```
+
+use crate::Msg;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{OnceLock, RwLock};
+
+#[cfg(feature = "derive")]
+pub use bubbletea_rs_derive::Message;
+
+/// A message type with a name and, optionally, `Debug` output.
+///
+/// Implement it, or derive it with the `derive` feature, for message types
+/// that should show up by name in statistics and logs. Call [`register`]
+/// once so the name can be found from a [`Msg`].
+pub trait Message: Any + Send {
+    /// A short, human-readable name for the message type.
+    fn name(&self) -> &'static str;
+
+    /// The message's `Debug` output, if it has one.
+    fn as_debug(&self) -> Option<&dyn Debug>;
+}
+
+/// Looks up a message of a registered type behind `&dyn Any`.
+type Describe = fn(&(dyn Any + Send)) -> Option<&dyn Message>;
+
+fn describe_as<T: Message>(msg: &(dyn Any + Send)) -> Option<&dyn Message> {
+    msg.downcast_ref::<T>().map(|msg| msg as &dyn Message)
+}
+
+fn registry() -> &'static RwLock<HashMap<TypeId, Describe>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<TypeId, Describe>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(builtin_messages()))
+}
+
+/// Registers `T`, so [`describe`] and [`MsgExt::message_name`] find it
+/// behind a [`Msg`].
+///
+/// Registering a type more than once has no further effect.
+pub fn register<T: Message>() {
+    if let Ok(mut types) = registry().write() {
+        types.insert(TypeId::of::<T>(), describe_as::<T>);
+    }
+}
+
+/// Returns the message in `msg` as a [`Message`], if its type has been
+/// [`register`]ed.
+pub fn describe(msg: &Msg) -> Option<&dyn Message> {
+    let describe = *registry().read().ok()?.get(&msg.as_ref().type_id())?;
+    describe(msg.as_ref())
+}
+
+/// Helpers for [`Msg`].
+pub trait MsgExt {
+    /// Returns `true` if the message is a `T`.
+    fn is<T: Any>(&self) -> bool;
+
+    /// Takes the message out if it is a `T`.
+    ///
+    /// This consumes the message either way; use `downcast` to get the
+    /// message back when it is something else.
+    fn take<T: Any>(self) -> Option<T>;
+
+    /// Returns the [`Message::name`] of the message, if its type has been
+    /// [`register`]ed.
+    fn message_name(&self) -> Option<&'static str>;
+}
+
+impl MsgExt for Msg {
+    fn is<T: Any>(&self) -> bool {
+        <dyn Any>::is::<T>(&**self)
+    }
+
+    fn take<T: Any>(self) -> Option<T> {
+        self.downcast::<T>().ok().map(|msg| *msg)
+    }
+
+    fn message_name(&self) -> Option<&'static str> {
+        describe(self).map(Message::name)
+    }
+}
+
+/// Implements [`Message`] for built-in message types, and lists them for
+/// the registry.
+macro_rules! builtin_messages {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl Message for crate::$ty {
+                fn name(&self) -> &'static str {
+                    stringify!($ty)
+                }
+
+                fn as_debug(&self) -> Option<&dyn Debug> {
+                    Some(self)
+                }
+            }
+        )*
+
+        fn builtin_messages() -> HashMap<TypeId, Describe> {
+            HashMap::from([$((TypeId::of::<crate::$ty>(), describe_as::<crate::$ty> as Describe)),*])
+        }
+    };
+}
+
+builtin_messages!(
+    KeyMsg,
+    KeyRepeatMsg,
+    KeyboardEnhancementsMsg,
+    MouseMsg,
+    PasteMsg,
+    PasteChunkMsg,
+    PasteEndMsg,
+    WindowSizeMsg,
+    QuitMsg,
+    NoMsg,
+    KillMsg,
+    InterruptMsg,
+    TerminalLostMsg,
+    SuspendMsg,
+    ResumeMsg,
+    FocusMsg,
+    BlurMsg,
+    ErrorMsg,
+    CancelledMsg,
+    ModesMsg,
+    BellMsg,
+    VisualBellMsg,
+    PrintMsg,
+    PrintfMsg,
+    SetWindowTitleMsg,
+    AnimationTickMsg,
+    ProgressFrameMsg,
+    MessageRateExceededMsg,
+);
@@ -7,7 +7,13 @@
 //! `Model` interface, facilitating migration and understanding for developers
 //! familiar with the Go version.
 
-use crate::{Cmd, Msg};
+use crate::{Cmd, Error, IntoCmd, Msg, RenderHint};
+use std::ops::Range;
+
+/// Standard handling for quit keys and window size messages.
+pub mod common;
+/// Undo and redo history for editing models.
+pub mod history;
 
 /// The Model trait defines the core interface for bubbletea-rs applications.
 ///
@@ -26,7 +32,7 @@ use crate::{Cmd, Msg};
 /// # Example
 ///
 /// ```rust
-/// use bubbletea_rs::{Model, Msg, Cmd, KeyMsg};
+/// use bubbletea_rs::{Model, Msg, Cmd, IntoCmd, KeyMsg};
 ///
 /// struct Counter {
 ///     value: i32,
@@ -37,7 +43,7 @@ use crate::{Cmd, Msg};
 ///         (Self { value: 0 }, None)
 ///     }
 ///     
-///     fn update(&mut self, msg: Msg) -> Option<Cmd> {
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
 ///         if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
 ///             match key_msg.key {
 ///                 crossterm::event::KeyCode::Up => self.value += 1,
@@ -45,7 +51,6 @@ use crate::{Cmd, Msg};
 ///                 _ => {}
 ///             }
 ///         }
-///         None
 ///     }
 ///     
 ///     fn view(&self) -> String {
@@ -76,12 +81,43 @@ pub trait Model: Send + Sized + 'static {
     ///     // Start with a count of 0 and no initial command
     ///     (MyModel { count: 0 }, None)
     /// }
-    /// # fn update(&mut self, msg: bubbletea_rs::Msg) -> Option<Cmd> { None }
+    /// # fn update(&mut self, msg: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
     /// # fn view(&self) -> String { String::new() }
     /// # }
     /// ```
     fn init() -> (Self, Option<Cmd>);
 
+    /// Initialize the model, reporting setup failures as an error.
+    ///
+    /// The program calls this instead of [`init`](Model::init), before it
+    /// touches the terminal. If it returns `Err`, `Program::run` returns that
+    /// error right away, so fallible setup (opening files, parsing
+    /// configuration) doesn't have to exit the process from inside `init`.
+    ///
+    /// The default implementation calls `init` and never fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, Error, Model};
+    /// # struct Viewer { text: String }
+    /// # impl Model for Viewer {
+    /// fn init() -> (Self, Option<Cmd>) {
+    ///     (Viewer { text: String::new() }, None)
+    /// }
+    ///
+    /// fn try_init() -> Result<(Self, Option<Cmd>), Error> {
+    ///     let text = std::fs::read_to_string("document.md")?;
+    ///     Ok((Viewer { text }, None))
+    /// }
+    /// # fn update(&mut self, msg: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// # fn view(&self) -> String { self.text.clone() }
+    /// # }
+    /// ```
+    fn try_init() -> Result<(Self, Option<Cmd>), Error> {
+        Ok(Self::init())
+    }
+
     /// Update the model in response to a received message.
     ///
     /// This method is called whenever a message is received by your application.
@@ -95,17 +131,42 @@ pub trait Model: Send + Sized + 'static {
     ///
     /// # Returns
     ///
-    /// An optional command to execute after the update. Return `None` if no
-    /// side effects are needed.
+    /// Anything implementing [`IntoCmd`]: usually an `Option<Cmd>`, with
+    /// `None` if no side effects are needed. Returning a `Vec<Cmd>` runs
+    /// several commands concurrently without calling `batch` yourself.
+    ///
+    /// Returning `()` means "no command". When calling `update` directly
+    /// (for example in tests), convert the result with
+    /// [`IntoCmd::into_cmd`] right away; it borrows the model until then.
+    ///
+    /// Existing implementations that spell out `-> Option<Cmd>` still
+    /// compile, and callers that name the concrete model still get an
+    /// `Option<Cmd>` back, but rustc now warns on each of them through its
+    /// `refining_impl_trait` lint, which fails builds that deny warnings.
+    /// Either change the signature to `-> impl IntoCmd` or keep it and put
+    /// `#[allow(refining_impl_trait)]` on the method:
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, Model, Msg};
+    /// # struct Legacy;
+    /// # impl Model for Legacy {
+    /// # fn init() -> (Self, Option<Cmd>) { (Legacy, None) }
+    /// #[allow(refining_impl_trait)]
+    /// fn update(&mut self, msg: Msg) -> Option<Cmd> {
+    ///     None
+    /// }
+    /// # fn view(&self) -> String { String::new() }
+    /// # }
+    /// ```
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use bubbletea_rs::{Model, Msg, Cmd, KeyMsg};
+    /// # use bubbletea_rs::{command, Model, Msg, Cmd, IntoCmd, KeyMsg};
     /// # struct MyModel { count: i32 }
     /// # impl Model for MyModel {
     /// # fn init() -> (Self, Option<Cmd>) { (MyModel { count: 0 }, None) }
-    /// fn update(&mut self, msg: Msg) -> Option<Cmd> {
+    /// fn update(&mut self, msg: Msg) -> impl IntoCmd {
     ///     if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
     ///         match key_msg.key {
     ///             crossterm::event::KeyCode::Up => {
@@ -113,6 +174,7 @@ pub trait Model: Send + Sized + 'static {
     ///                 // No command needed for this update
     ///                 None
     ///             }
+    ///             crossterm::event::KeyCode::Char('q') => Some(command::quit()),
     ///             _ => None,
     ///         }
     ///     } else {
@@ -122,7 +184,21 @@ pub trait Model: Send + Sized + 'static {
     /// # fn view(&self) -> String { String::new() }
     /// # }
     /// ```
-    fn update(&mut self, msg: Msg) -> Option<Cmd>;
+    ///
+    /// Returning several commands:
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// # fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// fn update(&mut self, _msg: Msg) -> impl IntoCmd {
+    ///     vec![command::hide_cursor(), command::clear_screen()]
+    /// }
+    /// # fn view(&self) -> String { String::new() }
+    /// # }
+    /// ```
+    fn update(&mut self, msg: Msg) -> impl IntoCmd;
 
     /// Render the current model state as a string for terminal display.
     ///
@@ -130,6 +206,9 @@ pub trait Model: Send + Sized + 'static {
     /// It should return a string representation of the current model state
     /// that will be displayed to the user.
     ///
+    /// Models that implement [`view_sized`](Self::view_sized) instead don't
+    /// need to implement this; the default renders nothing.
+    ///
     /// # Returns
     ///
     /// A `String` containing the rendered view. This can include:
@@ -150,7 +229,7 @@ pub trait Model: Send + Sized + 'static {
     /// # struct MyModel { count: i32, name: String }
     /// # impl Model for MyModel {
     /// # fn init() -> (Self, Option<Cmd>) { (MyModel { count: 0, name: "App".to_string() }, None) }
-    /// # fn update(&mut self, msg: Msg) -> Option<Cmd> { None }
+    /// # fn update(&mut self, msg: Msg) -> impl bubbletea_rs::IntoCmd {}
     /// fn view(&self) -> String {
     ///     format!(
     ///         "Welcome to {}!\n\nCount: {}\n\nPress ↑/↓ to change",
@@ -160,7 +239,131 @@ pub trait Model: Send + Sized + 'static {
     /// }
     /// # }
     /// ```
-    fn view(&self) -> String;
+    fn view(&self) -> String {
+        String::new()
+    }
+
+    /// Render the current model state for a terminal of the given size.
+    ///
+    /// The program calls this instead of [`view`](Self::view), passing the
+    /// size from the last `WindowSizeMsg` the model received (both zero
+    /// until the first one arrives), so a model doesn't have to store the
+    /// terminal's dimensions just to lay out its view. Implement either
+    /// this or `view`; the default calls `view`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Msg, Cmd};
+    /// # struct StatusBar { status: String }
+    /// # impl Model for StatusBar {
+    /// # fn init() -> (Self, Option<Cmd>) { (StatusBar { status: String::new() }, None) }
+    /// # fn update(&mut self, msg: Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// fn view_sized(&self, width: u16, height: u16) -> String {
+    ///     // Push the status line to the bottom of the screen
+    ///     let padding = "\n".repeat(usize::from(height.saturating_sub(1)));
+    ///     let status: String = self.status.chars().take(usize::from(width)).collect();
+    ///     format!("{padding}{status}")
+    /// }
+    /// # }
+    /// ```
+    fn view_sized(&self, width: u16, height: u16) -> String {
+        let _ = (width, height);
+        self.view()
+    }
+
+    /// Report which rows of the view changed since the last frame.
+    ///
+    /// This is an opt-in optimization for large views. When it returns
+    /// `Some`, the renderer only rewrites the listed row ranges (zero-based,
+    /// end exclusive) of the new view and leaves the rest of the screen
+    /// untouched. Rows past the end of the view are cleared. When it returns
+    /// `None`, the default, the whole view is redrawn.
+    ///
+    /// The ranges must cover every row that differs from the previous
+    /// frame; anything left out keeps its old content. The program ignores
+    /// damage and redraws everything for the first frame and after events
+    /// that disturb the screen, such as resizes, clearing the screen,
+    /// switching screens and printed lines.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Msg, Cmd, KeyMsg};
+    /// # use std::ops::Range;
+    /// # struct Table { rows: Vec<String>, changed: Option<u16> }
+    /// # impl Model for Table {
+    /// # fn init() -> (Self, Option<Cmd>) { (Table { rows: Vec::new(), changed: None }, None) }
+    /// # fn update(&mut self, msg: Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// # fn view(&self) -> String { self.rows.join("\n") }
+    /// fn damage(&self) -> Option<Vec<Range<u16>>> {
+    ///     // Only the edited row needs to be rewritten
+    ///     self.changed.map(|row| vec![row..row + 1])
+    /// }
+    /// # }
+    /// ```
+    fn damage(&self) -> Option<Vec<Range<u16>>> {
+        None
+    }
+
+    /// Report which rectangle of the screen changed since the last frame.
+    ///
+    /// Like [`damage`](Self::damage), this is an opt-in optimization: a
+    /// model that tracks which of its panels changed can return
+    /// [`RenderHint::Region`] for that panel, and only that part of the new
+    /// view is written. The rectangle must cover every cell that differs
+    /// from the previous frame. The default, [`RenderHint::Full`], redraws
+    /// the whole view. The hint is ignored when [`damage`](Self::damage)
+    /// returns `Some`, and in the same situations where damage is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Msg, Cmd, RenderHint};
+    /// # struct Dashboard { clock: String, clock_changed: bool }
+    /// # impl Model for Dashboard {
+    /// # fn init() -> (Self, Option<Cmd>) { (Dashboard { clock: String::new(), clock_changed: false }, None) }
+    /// # fn update(&mut self, msg: Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// # fn view(&self) -> String { format!("Dashboard {}", self.clock) }
+    /// fn render_hint(&self) -> RenderHint {
+    ///     // Only the clock in the top-right corner ticked
+    ///     if self.clock_changed {
+    ///         RenderHint::Region(10, 0, 8, 1)
+    ///     } else {
+    ///         RenderHint::Full
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    fn render_hint(&self) -> RenderHint {
+        RenderHint::Full
+    }
+
+    /// The window title for the current state.
+    ///
+    /// The program sets this title on startup and whenever the window
+    /// regains focus. When it returns `None`, the default, the title from
+    /// [`ProgramBuilder::with_title`](crate::ProgramBuilder::with_title) is
+    /// used instead. Use [`command::set_window_title`](crate::command::set_window_title)
+    /// to change the title immediately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Msg, Cmd};
+    /// # struct Editor { file: String }
+    /// # impl Model for Editor {
+    /// # fn init() -> (Self, Option<Cmd>) { (Editor { file: String::new() }, None) }
+    /// # fn update(&mut self, msg: Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// # fn view(&self) -> String { String::new() }
+    /// fn title(&self) -> Option<String> {
+    ///     Some(format!("editor - {}", self.file))
+    /// }
+    /// # }
+    /// ```
+    fn title(&self) -> Option<String> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -180,7 +383,7 @@ mod tests {
             (Self { count: 0, step: 1 }, None)
         }
 
-        fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        fn update(&mut self, msg: Msg) -> impl IntoCmd {
             if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
                 match key_msg.key {
                     KeyCode::Up | KeyCode::Char('+') => {
@@ -196,7 +399,7 @@ mod tests {
                         self.step = if self.step == 1 { 10 } else { 1 };
                     }
                     KeyCode::Char('q') => {
-                        return Some(Box::pin(async { Some(Box::new(QuitMsg) as Msg) }));
+                        return Some(Box::pin(async { Some(Box::new(QuitMsg) as Msg) }) as Cmd);
                     }
                     _ => {}
                 }
@@ -231,7 +434,7 @@ mod tests {
             )
         }
 
-        fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        fn update(&mut self, msg: Msg) -> impl IntoCmd {
             if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
                 match key_msg.key {
                     KeyCode::Char(c) if self.content.len() < self.max_length => {
@@ -258,7 +461,7 @@ mod tests {
                         self.cursor = self.content.len();
                     }
                     KeyCode::Esc => {
-                        return Some(Box::pin(async { Some(Box::new(QuitMsg) as Msg) }));
+                        return Some(Box::pin(async { Some(Box::new(QuitMsg) as Msg) }) as Cmd);
                     }
                     _ => {}
                 }
@@ -295,7 +498,7 @@ mod tests {
             key: KeyCode::Up,
             modifiers: KeyModifiers::empty(),
         };
-        let cmd = model.update(Box::new(key_msg));
+        let cmd = model.update(Box::new(key_msg)).into_cmd();
         assert_eq!(model.count, 1);
         assert!(cmd.is_none());
 
@@ -0,0 +1,136 @@
+//! Standard handling for the framework messages most models treat alike.
+//!
+//! Nearly every `update` starts the same way: quit on `Ctrl+C` and a few
+//! other keys, and remember the size from the latest `WindowSizeMsg`.
+//! Implementing [`CommonHandlers`] for a model provides that behavior as
+//! [`handle_common`](CommonHandlers::handle_common), configured by
+//! overriding the other methods. The trait is opt-in: a model that does not
+//! implement it pays nothing.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::model::common::{CommonHandlers, ViewportSize};
+//! use bubbletea_rs::{Cmd, IntoCmd, KeySpec, Model, Msg};
+//! use crossterm::event::{KeyCode, KeyModifiers};
+//!
+//! const QUIT_KEYS: &[KeySpec] = &[
+//!     KeySpec::new(KeyCode::Char('q'), KeyModifiers::NONE),
+//!     KeySpec::new(KeyCode::Esc, KeyModifiers::NONE),
+//! ];
+//!
+//! struct Viewer {
+//!     size: ViewportSize,
+//! }
+//!
+//! impl CommonHandlers for Viewer {
+//!     fn quit_keys(&self) -> &[KeySpec] {
+//!         QUIT_KEYS
+//!     }
+//!
+//!     fn viewport_size(&mut self) -> Option<&mut ViewportSize> {
+//!         Some(&mut self.size)
+//!     }
+//! }
+//!
+//! impl Model for Viewer {
+//!     fn init() -> (Self, Option<Cmd>) {
+//!         (Viewer { size: ViewportSize::default() }, None)
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+//!         if let Some(handled) = self.handle_common(&msg) {
+//!             return handled.into_cmd();
+//!         }
+//!         // Messages specific to this model
+//!         None
+//!     }
+//!
+//!     fn view(&self) -> String {
+//!         format!("{}x{}", self.size.width, self.size.height)
+//!     }
+//! }
+//! ```
+
+use crate::{Cmd, IntoCmd, KeyMsg, KeySpec, Model, Msg, WindowSizeMsg};
+
+/// The terminal size captured from the latest `WindowSizeMsg`.
+///
+/// Both dimensions are zero until the first `WindowSizeMsg` arrives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ViewportSize {
+    /// Width in columns.
+    pub width: u16,
+    /// Height in rows.
+    pub height: u16,
+}
+
+/// What [`CommonHandlers::handle_common`] did with a message.
+///
+/// Converts into the command `update` should return, so a handled message
+/// can be passed straight back with [`IntoCmd::into_cmd`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handled {
+    /// A quit key was pressed; the command quits the program.
+    Quit,
+    /// The new size was stored in the model's [`ViewportSize`].
+    Resized,
+}
+
+impl IntoCmd for Handled {
+    fn into_cmd(self) -> Option<Cmd> {
+        match self {
+            Handled::Quit => Some(crate::quit()),
+            Handled::Resized => None,
+        }
+    }
+}
+
+/// Standard behavior for quit keys and window size messages.
+///
+/// Every method has a default, so an empty `impl` gives a model that quits
+/// on `Ctrl+C` and does nothing else. Call
+/// [`handle_common`](Self::handle_common) first thing in `update`. See the
+/// [module documentation](self) for an example.
+pub trait CommonHandlers: Model {
+    /// Keys that quit the program, besides `Ctrl+C`.
+    ///
+    /// Defaults to none. Matched like
+    /// [`ProgramBuilder::quit_keys`](crate::ProgramBuilder::quit_keys).
+    fn quit_keys(&self) -> &[KeySpec] {
+        &[]
+    }
+
+    /// Whether `Ctrl+C` quits the program.
+    ///
+    /// Defaults to `true`. Return `false` to handle `Ctrl+C` in `update`,
+    /// for example to cancel a running operation instead. The program-wide
+    /// counterpart is [`ProgramBuilder::quit_on_ctrl_c`](crate::ProgramBuilder::quit_on_ctrl_c).
+    fn quit_on_ctrl_c(&self) -> bool {
+        true
+    }
+
+    /// Where to store the size from each `WindowSizeMsg`.
+    ///
+    /// Defaults to `None`, which leaves `WindowSizeMsg` to `update`.
+    fn viewport_size(&mut self) -> Option<&mut ViewportSize> {
+        None
+    }
+
+    /// Handles `msg` if it is one of the common messages.
+    ///
+    /// Returns `None` for any other message, which `update` then handles
+    /// itself.
+    fn handle_common(&mut self, msg: &Msg) -> Option<Handled> {
+        if KeyMsg::presses(msg).is_some() {
+            let quits = crate::key::is_quit_key(msg, self.quit_keys(), self.quit_on_ctrl_c());
+            return quits.then_some(Handled::Quit);
+        }
+        if let Some(&WindowSizeMsg { width, height }) = msg.downcast_ref::<WindowSizeMsg>() {
+            let size = self.viewport_size()?;
+            *size = ViewportSize { width, height };
+            return Some(Handled::Resized);
+        }
+        None
+    }
+}
@@ -0,0 +1,190 @@
+//! Undo and redo for models that can be cloned.
+//!
+//! [`UndoStack`] records snapshots of a model and steps back and forth
+//! between them. Use it directly from `update`, or let the program manage
+//! one with [`ProgramBuilder::with_undo`](crate::ProgramBuilder::with_undo),
+//! which records the model around every key press and paste and handles
+//! `Ctrl+Z` and `Ctrl+Y` before the model sees them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::model::history::UndoStack;
+//!
+//! let mut history = UndoStack::new(10);
+//! let mut text = String::from("a");
+//! history.push(&text);
+//! text.push('b');
+//! history.push(&text);
+//!
+//! assert_eq!(history.undo(), Some("a".to_string()));
+//! assert_eq!(history.undo(), None);
+//! assert_eq!(history.redo(), Some("ab".to_string()));
+//! ```
+
+use std::collections::VecDeque;
+
+/// A bounded history of model snapshots with undo and redo.
+///
+/// The stack holds a timeline of recorded states and a cursor pointing at
+/// the current one. [`undo`](Self::undo) and [`redo`](Self::redo) move the
+/// cursor and return the state it lands on. Recording a new state after an
+/// undo discards the states that could have been redone, like in a text
+/// editor.
+#[derive(Debug, Clone)]
+pub struct UndoStack<M: Clone> {
+    states: VecDeque<M>,
+    cursor: usize,
+    max_depth: usize,
+}
+
+impl<M: Clone> UndoStack<M> {
+    /// Creates an empty stack that keeps at most `max_depth` undo steps.
+    ///
+    /// The oldest state is dropped once the limit is reached. A depth of
+    /// zero is treated as one.
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            states: VecDeque::new(),
+            cursor: 0,
+            max_depth: max_depth.max(1),
+        }
+    }
+
+    /// Records `state` as the current state.
+    ///
+    /// Any states that could have been redone are discarded.
+    pub fn push(&mut self, state: &M) {
+        self.states.truncate(self.cursor + 1);
+        self.states.push_back(state.clone());
+        if self.states.len() > self.max_depth + 1 {
+            self.states.pop_front();
+        }
+        self.cursor = self.states.len() - 1;
+    }
+
+    /// Steps back to the previously recorded state and returns it, or
+    /// returns `None` if there is nothing to undo.
+    pub fn undo(&mut self) -> Option<M> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.cursor -= 1;
+        self.states.get(self.cursor).cloned()
+    }
+
+    /// Steps forward to the state that was last undone and returns it, or
+    /// returns `None` if there is nothing to redo.
+    pub fn redo(&mut self) -> Option<M> {
+        if !self.can_redo() {
+            return None;
+        }
+        self.cursor += 1;
+        self.states.get(self.cursor).cloned()
+    }
+
+    /// Removes every recorded state.
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.cursor = 0;
+    }
+
+    /// Returns `true` if [`undo`](Self::undo) would return a state.
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// Returns `true` if [`redo`](Self::redo) would return a state.
+    pub fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.states.len()
+    }
+
+    /// Returns `true` if no state has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Returns the maximum number of undo steps kept.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+/// Type-erased access to an [`UndoStack`], so the program can manage undo
+/// for models without requiring `Clone` on every `Model`.
+pub(crate) trait History<M>: Send {
+    fn push(&mut self, state: &M);
+    fn undo(&mut self) -> Option<M>;
+    fn redo(&mut self) -> Option<M>;
+    fn is_empty(&self) -> bool;
+}
+
+impl<M: Clone + Send> History<M> for UndoStack<M> {
+    fn push(&mut self, state: &M) {
+        UndoStack::push(self, state);
+    }
+
+    fn undo(&mut self) -> Option<M> {
+        UndoStack::undo(self)
+    }
+
+    fn redo(&mut self) -> Option<M> {
+        UndoStack::redo(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        UndoStack::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_and_redo_walk_the_timeline() {
+        let mut history = UndoStack::new(10);
+        for value in 0..3 {
+            history.push(&value);
+        }
+        assert_eq!(history.undo(), Some(1));
+        assert_eq!(history.undo(), Some(0));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(1));
+        assert_eq!(history.redo(), Some(2));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn test_push_after_undo_discards_redo_states() {
+        let mut history = UndoStack::new(10);
+        history.push(&"a");
+        history.push(&"b");
+        assert_eq!(history.undo(), Some("a"));
+        history.push(&"c");
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(), Some("a"));
+    }
+
+    #[test]
+    fn test_max_depth_drops_oldest_states() {
+        let mut history = UndoStack::new(2);
+        for value in 0..5 {
+            history.push(&value);
+        }
+        assert_eq!(history.undo(), Some(3));
+        assert_eq!(history.undo(), Some(2));
+        assert_eq!(history.undo(), None);
+    }
+
+    #[test]
+    fn test_clear_empties_history() {
+        let mut history = UndoStack::new(5);
+        history.push(&1);
+        history.push(&2);
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), None);
+    }
+}
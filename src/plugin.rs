@@ -0,0 +1,295 @@
+//! Composable program-level behaviours.
+//!
+//! Many programs turn on the same terminal features and apply the same
+//! message tweaks: focus reporting, bracketed paste, mouse support, resize
+//! debouncing. Instead of issuing enable/disable commands from `init()` and
+//! `update()`, these can be registered declaratively on the builder with
+//! `ProgramBuilder::with_plugin`.
+//!
+//! A [`Plugin`] can issue commands when the program starts and stops, and
+//! sees every message before the model does. Plugins run in registration
+//! order, after the program's message filter.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::plugin::{BracketedPastePlugin, FocusPlugin, MousePlugin, ResizeDebouncePlugin};
+//! use bubbletea_rs::{Cmd, IntoCmd, Model, MouseMotion, Msg, Program};
+//! use std::time::Duration;
+//!
+//! struct App;
+//!
+//! impl Model for App {
+//!     fn init() -> (Self, Option<Cmd>) {
+//!         (App, None)
+//!     }
+//!
+//!     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+//!
+//!     fn view(&self) -> String {
+//!         String::new()
+//!     }
+//! }
+//!
+//! let program = Program::<App>::builder()
+//!     .with_plugin(Box::new(FocusPlugin))
+//!     .with_plugin(Box::new(BracketedPastePlugin))
+//!     .with_plugin(Box::new(MousePlugin(MouseMotion::Cell)))
+//!     .with_plugin(Box::new(ResizeDebouncePlugin::new(Duration::from_millis(50))))
+//!     .build();
+//! ```
+
+use crate::command::{self, Cmd};
+use crate::event::{next_timer_id, Msg, WindowSizeMsg};
+use crate::program::MouseMotion;
+use std::time::Duration;
+
+/// A program-level behaviour registered with `ProgramBuilder::with_plugin`.
+///
+/// All methods have default implementations that do nothing, so a plugin
+/// only implements the hooks it needs.
+pub trait Plugin: Send {
+    /// Returns a short name identifying the plugin, used for debugging.
+    fn name(&self) -> &str;
+
+    /// Returns commands to run when the program starts.
+    ///
+    /// They run concurrently with the command returned by `Model::init`.
+    fn on_startup(&mut self) -> Vec<Cmd> {
+        Vec::new()
+    }
+
+    /// Returns commands to run when the program stops.
+    ///
+    /// Shutdown commands are awaited before the terminal is restored. Only
+    /// terminal mode messages they produce (such as `DisableMouseMsg`) are
+    /// applied; the model has already stopped, so other messages are
+    /// discarded.
+    fn on_shutdown(&mut self) -> Vec<Cmd> {
+        Vec::new()
+    }
+
+    /// Inspects a message before it reaches the model.
+    ///
+    /// Return `Some(msg)` to pass the message on (possibly replaced with a
+    /// different one), or `None` to swallow it. Commands pushed onto `cmds`
+    /// are run by the program, which lets a plugin deliver messages later.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The incoming message
+    /// * `cmds` - Commands to run alongside this message
+    fn intercept(&mut self, msg: Msg, cmds: &mut Vec<Cmd>) -> Option<Msg> {
+        let _ = cmds;
+        Some(msg)
+    }
+}
+
+/// An ordered collection of [`Plugin`]s.
+///
+/// The program owns a registry built from `ProgramBuilder::with_plugin`
+/// calls; it is public so plugin sets can be assembled and tested on their
+/// own.
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl PluginRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a plugin after all previously registered plugins.
+    pub fn register(&mut self, plugin: Box<dyn Plugin>) {
+        self.plugins.push(plugin);
+    }
+
+    /// Returns the number of registered plugins.
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    /// Returns whether no plugins are registered.
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Returns the names of the registered plugins, in order.
+    pub fn names(&self) -> Vec<&str> {
+        self.plugins.iter().map(|p| p.name()).collect()
+    }
+
+    /// Collects the startup commands of all plugins, in registration order.
+    pub fn startup(&mut self) -> Vec<Cmd> {
+        self.plugins
+            .iter_mut()
+            .flat_map(|p| p.on_startup())
+            .collect()
+    }
+
+    /// Collects the shutdown commands of all plugins, in reverse
+    /// registration order so later plugins are torn down first.
+    pub fn shutdown(&mut self) -> Vec<Cmd> {
+        self.plugins
+            .iter_mut()
+            .rev()
+            .flat_map(|p| p.on_shutdown())
+            .collect()
+    }
+
+    /// Passes a message through every plugin in registration order.
+    ///
+    /// # Returns
+    ///
+    /// The message to deliver to the model, or `None` if a plugin swallowed
+    /// it. Later plugins never see a swallowed message.
+    pub fn intercept(&mut self, msg: Msg, cmds: &mut Vec<Cmd>) -> Option<Msg> {
+        self.plugins
+            .iter_mut()
+            .try_fold(msg, |msg, plugin| plugin.intercept(msg, cmds))
+    }
+}
+
+impl std::fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("plugins", &self.names())
+            .finish()
+    }
+}
+
+/// Enables focus reporting, so the model receives `FocusMsg` and `BlurMsg`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn name(&self) -> &str {
+        "focus"
+    }
+
+    fn on_startup(&mut self) -> Vec<Cmd> {
+        vec![command::enable_report_focus()]
+    }
+
+    fn on_shutdown(&mut self) -> Vec<Cmd> {
+        vec![command::disable_report_focus()]
+    }
+}
+
+/// Enables bracketed paste, so pasted text arrives as a single `PasteMsg`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BracketedPastePlugin;
+
+impl Plugin for BracketedPastePlugin {
+    fn name(&self) -> &str {
+        "bracketed-paste"
+    }
+
+    fn on_startup(&mut self) -> Vec<Cmd> {
+        vec![command::enable_bracketed_paste()]
+    }
+
+    fn on_shutdown(&mut self) -> Vec<Cmd> {
+        vec![command::disable_bracketed_paste()]
+    }
+}
+
+/// Enables mouse reporting with the given motion mode.
+///
+/// With `MouseMotion::None` the plugin leaves mouse reporting off and only
+/// disables it again on shutdown.
+#[derive(Debug, Clone, Copy)]
+pub struct MousePlugin(pub MouseMotion);
+
+impl Plugin for MousePlugin {
+    fn name(&self) -> &str {
+        "mouse"
+    }
+
+    fn on_startup(&mut self) -> Vec<Cmd> {
+        match self.0 {
+            MouseMotion::All => vec![command::enable_mouse_all_motion()],
+            MouseMotion::Cell => vec![command::enable_mouse_cell_motion()],
+            MouseMotion::None => Vec::new(),
+        }
+    }
+
+    fn on_shutdown(&mut self) -> Vec<Cmd> {
+        vec![command::disable_mouse()]
+    }
+}
+
+/// Delivers the timer tick of a [`ResizeDebouncePlugin`].
+#[derive(Debug)]
+struct ResizeSettledMsg {
+    plugin_id: u64,
+    generation: u64,
+}
+
+/// Coalesces bursts of `WindowSizeMsg`s while the terminal is being resized.
+///
+/// Dragging a window edge produces many resize events in quick succession,
+/// and re-laying out for each one is wasteful. This plugin holds resize
+/// messages back until none has arrived for the configured quiet period,
+/// then delivers only the latest size.
+#[derive(Debug)]
+pub struct ResizeDebouncePlugin {
+    quiet_period: Duration,
+    id: u64,
+    generation: u64,
+    pending: Option<(u16, u16)>,
+}
+
+impl ResizeDebouncePlugin {
+    /// Creates a plugin that waits for `quiet_period` without resizes before
+    /// delivering the latest `WindowSizeMsg`.
+    pub fn new(quiet_period: Duration) -> Self {
+        Self {
+            quiet_period,
+            id: next_timer_id(),
+            generation: 0,
+            pending: None,
+        }
+    }
+
+    /// Returns the quiet period.
+    pub fn quiet_period(&self) -> Duration {
+        self.quiet_period
+    }
+}
+
+impl Plugin for ResizeDebouncePlugin {
+    fn name(&self) -> &str {
+        "resize-debounce"
+    }
+
+    fn intercept(&mut self, msg: Msg, cmds: &mut Vec<Cmd>) -> Option<Msg> {
+        if let Some(size) = msg.downcast_ref::<WindowSizeMsg>() {
+            self.pending = Some((size.width, size.height));
+            self.generation += 1;
+            let (plugin_id, generation) = (self.id, self.generation);
+            cmds.push(command::tick(self.quiet_period, move |_| {
+                Box::new(ResizeSettledMsg {
+                    plugin_id,
+                    generation,
+                }) as Msg
+            }));
+            return None;
+        }
+        if let Some(settled) = msg.downcast_ref::<ResizeSettledMsg>() {
+            if settled.plugin_id != self.id {
+                return Some(msg);
+            }
+            if settled.generation != self.generation {
+                return None; // A newer resize restarted the quiet period
+            }
+            return self
+                .pending
+                .take()
+                .map(|(width, height)| Box::new(WindowSizeMsg { width, height }) as Msg);
+        }
+        Some(msg)
+    }
+}
@@ -3,12 +3,14 @@
 //! The `Program` sets up the terminal, handles input, executes commands, and renders
 //! the model's view.
 
-use crate::event::{KillMsg, RequestWindowSizeMsg};
+use crate::event::{KillMsg, RequestWindowSizeMsg, SetViewportSizeMsg};
+pub use crate::plugin::{Plugin, PluginRegistry};
 use crate::{
-    Error, InputHandler, InputSource, Model, Msg, QuitMsg, Terminal, TerminalInterface,
-    WindowSizeMsg,
+    Error, InputHandler, InputSource, IntoCmd, KeyMsg, KeySpec, Model, Msg, OutputStream,
+    PasteHeuristic, QuitMsg, RenderHint, Terminal, TerminalInterface, TerminalMode, WindowSizeMsg,
 };
 use futures::{future::FutureExt, select};
+use std::any::Any;
 use std::marker::PhantomData;
 use std::panic;
 use std::sync::OnceLock;
@@ -17,10 +19,351 @@ use tokio::sync::mpsc;
 type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Send + Sync + 'static>;
 static ORIGINAL_PANIC_HOOK: OnceLock<PanicHook> = OnceLock::new();
 
+/// How long the screen stays in reverse video for a visual bell.
+const VISUAL_BELL_DURATION: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// How long the terminal has to answer a keyboard enhancement query.
+const KEYBOARD_ENHANCEMENT_QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How long each plugin shutdown command may take before it is abandoned.
+const PLUGIN_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Terminal devices driven by a running `Program`.
+static DEVICES_IN_USE: parking_lot::Mutex<Vec<String>> = parking_lot::Mutex::new(Vec::new());
+
+/// Claims a terminal device for a running program, releasing it on drop.
+struct DeviceGuard(String);
+
+impl DeviceGuard {
+    /// Claims `device`, or returns `Error::AlreadyRunning` if another
+    /// program holds it.
+    fn acquire(device: &str) -> Result<Self, Error> {
+        let mut devices = DEVICES_IN_USE.lock();
+        if devices.iter().any(|held| held == device) {
+            return Err(Error::AlreadyRunning);
+        }
+        devices.push(device.to_string());
+        Ok(Self(device.to_string()))
+    }
+}
+
+impl Drop for DeviceGuard {
+    fn drop(&mut self) {
+        DEVICES_IN_USE.lock().retain(|held| *held != self.0);
+    }
+}
+
+/// Counts a command as in flight with the memory monitor until dropped, so
+/// commands cancelled on shutdown are counted out too.
+struct InFlight(Option<crate::memory::MemoryMonitor>);
+
+impl InFlight {
+    fn start(monitor: Option<crate::memory::MemoryMonitor>) -> Self {
+        if let Some(monitor) = &monitor {
+            monitor.command_started();
+        }
+        Self(monitor)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if let Some(monitor) = &self.0 {
+            monitor.command_finished();
+        }
+    }
+}
+
+/// Reports a run as finished when dropped, so every way out of
+/// `run_with_result` ends in `RunState::Finished`.
+struct FinishOnDrop(tokio::sync::watch::Sender<RunState>);
+
+impl Drop for FinishOnDrop {
+    fn drop(&mut self) {
+        // Errors during setup skip the normal shutdown, but still report it
+        self.0.send_if_modified(|state| {
+            let changed = *state != RunState::ShuttingDown;
+            *state = RunState::ShuttingDown;
+            changed
+        });
+        self.0.send_replace(RunState::Finished);
+    }
+}
+
+/// How long the command returned for a `TerminalLostMsg` may run before the
+/// program shuts down anyway.
+const TERMINAL_LOST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// How many writes to the terminal may fail in a row before it is
+/// considered lost, for errors that do not say so outright.
+const MAX_OUTPUT_FAILURES: u32 = 3;
+
+/// Churn from which the debug overlay names a line: changed in at least
+/// half of the recent frames.
+const HOT_LINE_CHURN: f64 = 0.5;
+
+/// Returns the text the debug input overlay draws: the last input event
+/// and, if frame churn is recorded, the lines that change in most frames.
+fn debug_overlay(
+    last_input: Option<&str>,
+    churn: Option<&crate::memory::FrameChurn>,
+) -> Option<String> {
+    let hot = churn.map_or_else(Vec::new, |churn| churn.hot_lines(HOT_LINE_CHURN));
+    if hot.is_empty() {
+        return last_input.map(str::to_string);
+    }
+    let lines: Vec<String> = hot.iter().map(usize::to_string).collect();
+    let churn = format!("churn: lines {}", lines.join(", "));
+    Some(match last_input {
+        Some(input) => format!("{input} | {churn}"),
+        None => churn,
+    })
+}
+
+/// Replaces the last line of `view` with `overlay`, keeping a trailing
+/// newline.
+fn overlay_last_line(view: &str, overlay: &str) -> String {
+    let body = view.strip_suffix('\n').unwrap_or(view);
+    let start = body.rfind('\n').map_or(0, |i| i + 1);
+    let mut composited = String::with_capacity(view.len() + overlay.len());
+    composited.push_str(&view[..start]);
+    composited.push_str(overlay);
+    composited.push_str(&view[body.len()..]);
+    composited
+}
+
+/// Fits `view` into the column `area` describes, as a left margin and a
+/// width: every non-empty line is indented by the margin, and lines wider
+/// than the column are cut off at its edge.
+fn letterbox(view: &str, area: Option<(u16, u16)>) -> String {
+    let Some((margin, width)) = area else {
+        return view.to_string();
+    };
+    let padding = " ".repeat(usize::from(margin));
+    let width = usize::from(width);
+    view.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else if crate::ansi::visible_width(line) > width {
+                format!("{padding}{}", crate::ansi::slice(line, 0, width))
+            } else {
+                format!("{padding}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Calls `model.view_sized()` with `size`, recording how long it took and
+/// warning if that exceeds `threshold`.
+fn timed_view<M: Model>(
+    model: &M,
+    size: (u16, u16),
+    timings: &crate::memory::ViewTimings,
+    threshold: Option<std::time::Duration>,
+) -> String {
+    let start = std::time::Instant::now();
+    let view = model.view_sized(size.0, size.1);
+    let elapsed = start.elapsed();
+    timings.record(elapsed);
+    if threshold.is_some_and(|threshold| elapsed > threshold) {
+        #[cfg(feature = "logging")]
+        log::warn!("Model::view took {elapsed:?}");
+    }
+    view
+}
+
+/// Appends `messages` to `out`, replacing every nested `BatchMsgInternal`
+/// with the messages it contains, in order.
+///
+/// A sequence inside a sequence delivers the inner results as one batch
+/// message; flattening makes it behave like a single flat sequence.
+fn flatten_batch(messages: Vec<Msg>, out: &mut Vec<Msg>) {
+    for msg in messages {
+        match msg.downcast::<crate::event::BatchMsgInternal>() {
+            Ok(inner) => flatten_batch(inner.messages, out),
+            Err(msg) => out.push(msg),
+        }
+    }
+}
+
+/// Receives the next message, merging a queued run of identical key presses
+/// into one `KeyRepeatMsg` when `coalesce_key_repeats` is set, and a queued
+/// run of wheel events into one `MouseMsg` when `coalesce_wheel_events` is.
+///
+/// The message that ends the run is kept in `held` and returned by the next
+/// call. Only messages that are already queued are merged, so a key the
+/// program keeps up with is never delayed.
+///
+/// Input messages are returned with the stamp their decoder gave them, or
+/// stamped with the time they were received if they were sent rather than
+/// decoded. A merged run reports its last press.
+async fn recv_coalesced(
+    rx: &mut crate::event::EventReceiver,
+    held: &mut Option<Msg>,
+    config: &ProgramConfig,
+) -> Option<(Msg, Option<crate::event::InputStamp>)> {
+    use crate::event::{InputStamp, MouseMsg, TimestampedMsg};
+
+    let msg = match held.take() {
+        Some(msg) => msg,
+        None => rx.recv().await?,
+    };
+    let (mut msg, stamp) = TimestampedMsg::split(msg);
+    // Input that was sent rather than decoded is stamped on arrival
+    let mut stamp = stamp.or_else(|| {
+        (msg.is::<KeyMsg>() || msg.is::<crate::event::KeyRepeatMsg>() || msg.is::<MouseMsg>()).then(
+            || InputStamp {
+                at: crate::runtime::now(),
+                ambiguous: false,
+            },
+        )
+    });
+    if let Some(mouse) = msg.downcast_mut::<MouseMsg>().filter(|m| m.is_wheel()) {
+        if config.coalesce_wheel_events {
+            while let Some(next) = rx.try_recv() {
+                let (next, next_stamp) = TimestampedMsg::split(next);
+                match next.downcast_ref::<MouseMsg>() {
+                    Some(next)
+                        if next.button == mouse.button && next.modifiers == mouse.modifiers =>
+                    {
+                        mouse.scroll_delta = mouse.scroll_delta.saturating_add(next.scroll_delta);
+                        mouse.x = next.x;
+                        mouse.y = next.y;
+                        stamp = next_stamp.or(stamp);
+                    }
+                    _ => {
+                        *held = Some(TimestampedMsg::join(next, next_stamp));
+                        break;
+                    }
+                }
+            }
+        }
+        mouse.scroll_delta = mouse.scroll_delta.saturating_mul(config.wheel_scroll_lines);
+        return Some((msg, stamp));
+    }
+    let Some(key) = msg
+        .downcast_ref::<KeyMsg>()
+        .filter(|_| config.coalesce_key_repeats)
+    else {
+        return Some((msg, stamp));
+    };
+    let mut repeat_count: u16 = 1;
+    while repeat_count < u16::MAX {
+        let Some(next) = rx.try_recv() else {
+            break;
+        };
+        let (next, next_stamp) = TimestampedMsg::split(next);
+        if next
+            .downcast_ref::<KeyMsg>()
+            .is_some_and(|next| next.same_key(key))
+        {
+            repeat_count += 1;
+            stamp = next_stamp.or(stamp);
+        } else {
+            *held = Some(TimestampedMsg::join(next, next_stamp));
+            break;
+        }
+    }
+    if repeat_count == 1 {
+        return Some((msg, stamp));
+    }
+    let key = key.clone();
+    let msg = Box::new(crate::event::KeyRepeatMsg { key, repeat_count });
+    Some((msg, stamp))
+}
+
+/// Runs the process of an `ExecRequestMsg` on a terminal already released
+/// by `begin_exec`, then gives the terminal back and delivers the callback's
+/// message.
+async fn run_exec_request(
+    request: crate::event::ExecRequestMsg,
+    event_tx: crate::event::EventSender,
+) {
+    let crate::event::ExecRequestMsg {
+        mut command,
+        callback,
+    } = request;
+    crate::command::apply_command_env(&mut command);
+    let status = crate::runtime::status(command).await;
+
+    let (restored, reply) = tokio::sync::oneshot::channel();
+    let end = crate::event::ExecEndMsg { restored };
+    if event_tx.send(Box::new(end) as Msg).is_ok() && reply.await.is_ok() {
+        let _ = event_tx.send(callback(status));
+    }
+}
+
+/// Returns whether `err` means the terminal's output is gone for good.
+///
+/// A closed pipe or dropped connection shows up as `EPIPE`, a reset
+/// connection, or `EIO` from a hung-up pty.
+fn is_output_gone(err: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        err.kind(),
+        ErrorKind::BrokenPipe
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::WriteZero
+    ) || (cfg!(unix) && err.raw_os_error() == Some(5))
+}
+
+/// Applies a terminal mode message (mouse, focus reporting, bracketed paste,
+/// cursor visibility and position, window title) to the terminal.
+///
+/// Returns `true` if `msg` was a terminal mode message.
+async fn apply_terminal_mode(terminal: &mut (dyn TerminalInterface + Send), msg: &Msg) -> bool {
+    use crate::event::{
+        DisableBracketedPasteMsg, DisableMouseMsg, DisableReportFocusMsg, EnableBracketedPasteMsg,
+        EnableMouseAllMotionMsg, EnableMouseCellMotionMsg, EnableReportFocusMsg, HideCursorMsg,
+        PopWindowTitleMsg, PushWindowTitleMsg, RestoreCursorMsg, SaveCursorMsg, SetWindowTitleMsg,
+        ShowCursorMsg,
+    };
+
+    let result = if msg.is::<EnableMouseCellMotionMsg>() {
+        terminal.enable_mouse_cell_motion().await
+    } else if msg.is::<EnableMouseAllMotionMsg>() {
+        terminal.enable_mouse_all_motion().await
+    } else if msg.is::<DisableMouseMsg>() {
+        terminal.disable_mouse().await
+    } else if msg.is::<EnableReportFocusMsg>() {
+        terminal.enable_focus_reporting().await
+    } else if msg.is::<DisableReportFocusMsg>() {
+        terminal.disable_focus_reporting().await
+    } else if msg.is::<EnableBracketedPasteMsg>() {
+        terminal.enable_bracketed_paste().await
+    } else if msg.is::<DisableBracketedPasteMsg>() {
+        terminal.disable_bracketed_paste().await
+    } else if msg.is::<ShowCursorMsg>() {
+        terminal.show_cursor().await
+    } else if msg.is::<HideCursorMsg>() {
+        terminal.hide_cursor().await
+    } else if msg.is::<SaveCursorMsg>() {
+        terminal.save_cursor().await
+    } else if msg.is::<RestoreCursorMsg>() {
+        terminal.restore_cursor().await
+    } else if let Some(SetWindowTitleMsg(title)) = msg.downcast_ref() {
+        terminal.set_window_title(title).await
+    } else if let Some(PushWindowTitleMsg(title)) = msg.downcast_ref() {
+        terminal.push_window_title(title).await
+    } else if msg.is::<PopWindowTitleMsg>() {
+        terminal.pop_window_title().await
+    } else {
+        return false;
+    };
+    // Mode changes are best effort, like the other terminal control messages
+    let _ = result;
+    true
+}
+
 /// Defines the different modes for mouse motion reporting.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MouseMotion {
     /// No mouse motion events are reported.
+    #[default]
     None,
     /// Mouse motion events are reported when the mouse moves over a different cell.
     Cell,
@@ -28,11 +371,74 @@ pub enum MouseMotion {
     All,
 }
 
+impl MouseMotion {
+    /// Whether this mode reports a mouse event of `kind`. Motion without a
+    /// button needs `All`, and drags need `Cell` or `All`.
+    pub(crate) fn reports(self, kind: crossterm::event::MouseEventKind) -> bool {
+        use crossterm::event::MouseEventKind;
+        match kind {
+            MouseEventKind::Moved => self == MouseMotion::All,
+            MouseEventKind::Drag(_) => self != MouseMotion::None,
+            _ => true,
+        }
+    }
+}
+
+/// Where a [`Program`] is in its lifecycle, as reported by
+/// [`Program::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunState {
+    /// The program hasn't started, or is running the model's `init` and
+    /// setting up the terminal.
+    #[default]
+    Initializing,
+    /// The event loop is handling messages.
+    Running,
+    /// A [`SuspendMsg`](crate::SuspendMsg) was handled and no
+    /// [`ResumeMsg`](crate::ResumeMsg) has followed yet.
+    Suspended,
+    /// An external process started with
+    /// [`exec_process`](crate::command::exec_process) holds the terminal.
+    ReleasedTerminal,
+    /// The event loop has stopped, after a quit or an error, and the
+    /// terminal is being restored.
+    ShuttingDown,
+    /// The run is over. Reported on every exit, errors included.
+    Finished,
+}
+
+/// Whether `msg` is input from the user: a key press, mouse event or paste.
+fn is_user_input(msg: &Msg) -> bool {
+    msg.is::<KeyMsg>()
+        || msg.is::<crate::KeyRepeatMsg>()
+        || msg.is::<crate::MouseMsg>()
+        || msg.is::<crate::PasteMsg>()
+        || msg.is::<crate::PasteChunkMsg>()
+        || msg.is::<crate::PasteEndMsg>()
+}
+
+/// Controls when buffered terminal output is flushed.
+///
+/// Flushing after every frame keeps latency lowest, but on slow terminals
+/// or remote connections many small flushes can hurt throughput. The other
+/// strategies let the terminal buffer several frames and flush them at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushStrategy {
+    /// Flush after every frame.
+    #[default]
+    EveryFrame,
+    /// Flush when the event loop has no more messages waiting, so a burst
+    /// of messages ends in a single flush.
+    OnIdle,
+    /// Flush at most once per interval. Output written in between is
+    /// flushed when the interval has passed.
+    Interval(std::time::Duration),
+}
+
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::io::AsyncWrite;
 use tokio::sync::Mutex;
-use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
 /// Alias for a model-aware message filter function used throughout Program.
@@ -40,6 +446,13 @@ use tokio_util::sync::CancellationToken;
 /// This reduces repeated complex type signatures and improves readability.
 type MessageFilter<M> = Box<dyn Fn(&M, Msg) -> Option<Msg> + Send>;
 
+/// Alias for the function that reports the error a run failed with.
+type ErrorHandler = Box<dyn Fn(&Error) + Send>;
+
+/// Alias for the undo history the program keeps for models built with
+/// `with_undo`.
+type UndoHistory<M> = Box<dyn crate::model::history::History<M>>;
+
 /// Configuration options for a `Program`.
 ///
 /// This struct holds various settings that control the behavior of the `Program`,
@@ -51,6 +464,9 @@ pub struct ProgramConfig {
     pub mouse_motion: MouseMotion,
     /// Whether to report focus events.
     pub report_focus: bool,
+    /// How long focus changes are held back to collapse flapping, or zero
+    /// to deliver each one right away.
+    pub focus_debounce: std::time::Duration,
     /// The target frames per second for rendering.
     pub fps: u32,
     /// Whether to disable the renderer entirely.
@@ -61,6 +477,22 @@ pub struct ProgramConfig {
     pub signal_handler: bool,
     /// Whether to enable bracketed paste mode.
     pub bracketed_paste: bool,
+    /// Whether to enable the keyboard enhancement protocol when supported.
+    pub keyboard_enhancement: bool,
+    /// Whether startup fails when a requested terminal mode cannot be
+    /// enabled, instead of running without it.
+    pub strict_modes: bool,
+    /// Cursor key mode set at startup, if any; normal mode is restored on
+    /// exit.
+    pub cursor_key_mode: Option<crate::terminal::CursorKeyMode>,
+    /// Whether queued runs of identical key presses are delivered as one
+    /// `KeyRepeatMsg`.
+    pub coalesce_key_repeats: bool,
+    /// Whether queued runs of wheel events in one direction are delivered as
+    /// one `MouseMsg`.
+    pub coalesce_wheel_events: bool,
+    /// Lines one mouse wheel notch scrolls.
+    pub wheel_scroll_lines: u16,
     /// Optional custom output writer.
     pub output_writer: Option<Arc<Mutex<dyn AsyncWrite + Send + Unpin>>>,
     /// Optional cancellation token for external control.
@@ -74,6 +506,68 @@ pub struct ProgramConfig {
     pub memory_monitoring: bool,
     /// Optional environment variables to apply to external process commands.
     pub environment: Option<HashMap<String, String>>,
+    /// Whether `every_precise` timers spin-wait for sub-millisecond accuracy.
+    pub high_precision_timer: bool,
+    /// Keys that quit the program; the model sees a `QuitMsg` in their place.
+    pub quit_keys: Vec<KeySpec>,
+    /// Whether a `Ctrl+C` key press quits the program like a quit key.
+    pub quit_on_ctrl_c: bool,
+    /// Whether the terminal output goes to stderr instead of stdout.
+    pub output_to_stderr: bool,
+    /// Burst detection that turns unbracketed pastes into `PasteMsg`s, if enabled.
+    pub paste_heuristic: Option<PasteHeuristic>,
+    /// The size in bytes of the `PasteChunkMsg`s bracketed pastes are
+    /// streamed in, or `None` to deliver each as one `PasteMsg`.
+    pub paste_chunk_size: Option<usize>,
+    /// When buffered terminal output is flushed.
+    pub flush_strategy: FlushStrategy,
+    /// How long without input before the model gets an `IdleMsg`, if set.
+    pub idle_after: Option<std::time::Duration>,
+    /// Whether frames are skipped while the terminal can't keep up.
+    pub frame_coalescing: bool,
+    /// Custom terminal to use instead of the crossterm terminal.
+    pub terminal: Option<Box<dyn TerminalInterface + Send>>,
+    /// Height of the live region for inline rendering with log lines above
+    /// it, if enabled.
+    pub log_region: Option<u16>,
+    /// Whether the program runs in accessible mode, printing plain lines
+    /// for screen readers.
+    pub accessible: bool,
+    /// Whether to count handled messages by type.
+    pub message_stats: bool,
+    /// Rate in messages per second, and how long it must be sustained,
+    /// above which a message type is reported as runaway.
+    pub message_rate_limit: Option<(u64, std::time::Duration)>,
+    /// Whether the memory monitor counts command futures in flight.
+    pub track_tasks: bool,
+    /// Clock that commands wait on, if not tokio's timer.
+    pub clock: Option<Arc<dyn crate::Clock>>,
+    /// Called with the error when `run` fails, after the terminal is
+    /// restored.
+    pub on_error: Option<ErrorHandler>,
+    /// Whether terminal capabilities are detected again on each resize.
+    pub requery_capabilities_on_resize: bool,
+    /// Whether an inline program clears from the cursor to the end of the
+    /// screen before its first frame.
+    pub clear_on_start: bool,
+    /// Whether entering the alternate screen clears it before the first
+    /// frame is drawn there.
+    pub clear_on_alt_screen_enter: bool,
+    /// How long a `Model::view` call may take before the program logs a
+    /// warning, if set.
+    pub view_warning_threshold: Option<std::time::Duration>,
+    /// Whether the last key or mouse event is drawn over the view's last
+    /// line.
+    pub debug_input_overlay: bool,
+    /// Whether to record which lines of the view change between frames.
+    pub frame_churn: bool,
+    /// Window title set on startup and whenever the window regains focus,
+    /// if any.
+    pub title: Option<String>,
+    /// Commands run at startup alongside the command returned by `init`.
+    pub initial_commands: Vec<crate::Cmd>,
+    /// Widest the model's view may be; wider terminals center the frame.
+    pub max_width: Option<u16>,
 }
 
 impl std::fmt::Debug for ProgramConfig {
@@ -82,13 +576,49 @@ impl std::fmt::Debug for ProgramConfig {
             .field("alt_screen", &self.alt_screen)
             .field("mouse_motion", &self.mouse_motion)
             .field("report_focus", &self.report_focus)
+            .field("focus_debounce", &self.focus_debounce)
             .field("fps", &self.fps)
             .field("without_renderer", &self.without_renderer)
             .field("catch_panics", &self.catch_panics)
             .field("signal_handler", &self.signal_handler)
             .field("bracketed_paste", &self.bracketed_paste)
+            .field("keyboard_enhancement", &self.keyboard_enhancement)
+            .field("strict_modes", &self.strict_modes)
+            .field("cursor_key_mode", &self.cursor_key_mode)
+            .field("coalesce_key_repeats", &self.coalesce_key_repeats)
+            .field("coalesce_wheel_events", &self.coalesce_wheel_events)
+            .field("wheel_scroll_lines", &self.wheel_scroll_lines)
             .field("cancellation_token", &self.cancellation_token)
             .field("environment", &self.environment.as_ref().map(|m| m.len()))
+            .field("high_precision_timer", &self.high_precision_timer)
+            .field("quit_keys", &self.quit_keys)
+            .field("quit_on_ctrl_c", &self.quit_on_ctrl_c)
+            .field("output_to_stderr", &self.output_to_stderr)
+            .field("paste_heuristic", &self.paste_heuristic)
+            .field("paste_chunk_size", &self.paste_chunk_size)
+            .field("flush_strategy", &self.flush_strategy)
+            .field("idle_after", &self.idle_after)
+            .field("frame_coalescing", &self.frame_coalescing)
+            .field("terminal", &self.terminal.is_some())
+            .field("log_region", &self.log_region)
+            .field("accessible", &self.accessible)
+            .field("message_stats", &self.message_stats)
+            .field("message_rate_limit", &self.message_rate_limit)
+            .field("track_tasks", &self.track_tasks)
+            .field("clock", &self.clock.is_some())
+            .field("on_error", &self.on_error.is_some())
+            .field(
+                "requery_capabilities_on_resize",
+                &self.requery_capabilities_on_resize,
+            )
+            .field("clear_on_start", &self.clear_on_start)
+            .field("clear_on_alt_screen_enter", &self.clear_on_alt_screen_enter)
+            .field("view_warning_threshold", &self.view_warning_threshold)
+            .field("debug_input_overlay", &self.debug_input_overlay)
+            .field("frame_churn", &self.frame_churn)
+            .field("title", &self.title)
+            .field("initial_commands", &self.initial_commands.len())
+            .field("max_width", &self.max_width)
             .finish()
     }
 }
@@ -98,23 +628,62 @@ impl Default for ProgramConfig {
     ///
     /// By default, the program does not use the alternate screen, has no mouse
     /// motion reporting, does not report focus, targets 60 FPS, enables rendering,
-    /// catches panics, handles signals, and disables bracketed paste.
+    /// catches panics, handles signals, disables bracketed paste and keyboard
+    /// enhancement, delivers every key press separately, uses
+    /// regular (non-spinning) timers, has no quit keys, writes to stdout,
+    /// does not detect unbracketed pastes, flushes after every frame,
+    /// redraws the whole screen for every frame, does not count messages
+    /// by type, does not clear leftover output on start, does not warn
+    /// about slow views, and does not show decoded input.
     fn default() -> Self {
         Self {
             alt_screen: false,
             mouse_motion: MouseMotion::None,
             report_focus: false,
+            focus_debounce: std::time::Duration::from_millis(50),
             fps: 60,
             without_renderer: false,
             catch_panics: true,
             signal_handler: true,
             bracketed_paste: false,
+            keyboard_enhancement: false,
+            strict_modes: false,
+            cursor_key_mode: None,
+            coalesce_key_repeats: false,
+            coalesce_wheel_events: false,
+            wheel_scroll_lines: 1,
             output_writer: None,
             cancellation_token: None,
             input_source: None,
             event_channel_buffer: Some(1000), // Default to bounded channel with 1000 message buffer
             memory_monitoring: false,         // Disabled by default
             environment: None,
+            high_precision_timer: false,
+            quit_keys: Vec::new(),
+            quit_on_ctrl_c: false,
+            output_to_stderr: false,
+            paste_heuristic: None,
+            paste_chunk_size: None,
+            flush_strategy: FlushStrategy::EveryFrame,
+            idle_after: None,
+            frame_coalescing: true,
+            terminal: None,
+            log_region: None,
+            accessible: false,
+            message_stats: false,
+            message_rate_limit: None,
+            track_tasks: false,
+            clock: None,
+            on_error: None,
+            requery_capabilities_on_resize: false,
+            clear_on_start: false,
+            clear_on_alt_screen_enter: true,
+            view_warning_threshold: None,
+            debug_input_overlay: false,
+            frame_churn: false,
+            title: None,
+            initial_commands: Vec::new(),
+            max_width: None,
         }
     }
 }
@@ -128,6 +697,10 @@ pub struct ProgramBuilder<M: Model> {
     _phantom: PhantomData<M>,
     /// Optional model-aware message filter
     message_filter: Option<MessageFilter<M>>,
+    /// Registered program-level plugins
+    plugins: PluginRegistry,
+    /// Undo history managed by the program
+    undo: Option<UndoHistory<M>>,
 }
 
 impl<M: Model> ProgramBuilder<M> {
@@ -144,6 +717,8 @@ impl<M: Model> ProgramBuilder<M> {
             config: ProgramConfig::default(),
             _phantom: PhantomData,
             message_filter: None,
+            plugins: PluginRegistry::new(),
+            undo: None,
         }
     }
 
@@ -166,7 +741,7 @@ impl<M: Model> ProgramBuilder<M> {
     /// # struct MyModel;
     /// # impl Model for MyModel {
     /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
-    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
     /// #     fn view(&self) -> String { String::new() }
     /// # }
     ///
@@ -191,6 +766,65 @@ impl<M: Model> ProgramBuilder<M> {
         self
     }
 
+    /// Sets whether an inline program clears stale output before its first
+    /// frame.
+    ///
+    /// When enabled and the alternate screen is not used, everything from
+    /// the cursor to the end of the screen is cleared before the first
+    /// frame, so output left below the prompt by an earlier command cannot
+    /// show through it. Has no effect with
+    /// [`alt_screen`](Self::alt_screen), which starts from an empty screen
+    /// anyway.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct M;
+    /// # impl Model for M {
+    /// #     fn init() -> (Self, Option<Cmd>) { (M, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<M>::builder()
+    ///     .clear_on_start(true)
+    ///     .build();
+    /// ```
+    pub fn clear_on_start(mut self, enabled: bool) -> Self {
+        self.config.clear_on_start = enabled;
+        self
+    }
+
+    /// Sets whether entering the alternate screen clears it.
+    ///
+    /// Enabled by default. Either way, an `EnterAltScreenMsg` switches
+    /// screens in the same write as the first frame drawn on the alternate
+    /// screen, inside a synchronized output block when the terminal
+    /// supports one, so an empty screen is never shown between the two.
+    /// Since that frame repaints the whole screen, the clear can be turned
+    /// off, which helps programs that toggle between inline and fullscreen
+    /// views often. Leaving the alternate screen puts the cursor back where
+    /// the inline view resumes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct M;
+    /// # impl Model for M {
+    /// #     fn init() -> (Self, Option<Cmd>) { (M, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<M>::builder()
+    ///     .clear_on_alt_screen_enter(false)
+    ///     .build();
+    /// ```
+    pub fn clear_on_alt_screen_enter(mut self, enabled: bool) -> Self {
+        self.config.clear_on_alt_screen_enter = enabled;
+        self
+    }
+
     /// Sets the mouse motion reporting mode.
     ///
     /// # Arguments
@@ -210,6 +844,40 @@ impl<M: Model> ProgramBuilder<M> {
         self
     }
 
+    /// Sets how long focus changes are held back to collapse flapping.
+    ///
+    /// Terminal multiplexers such as tmux can report spurious blur/focus
+    /// pairs when switching panes. A focus change is delivered once no
+    /// other arrives for `window`, and only if it changes the focus the
+    /// model last saw; changes that cancel out are dropped. The default is
+    /// 50 ms, and `Duration::ZERO` delivers every change as it arrives. See
+    /// [`InputHandler::with_focus_debounce`] for details.
+    ///
+    /// During `update`, [`input_timestamp`](crate::event::input_timestamp)
+    /// reports when the delivered `FocusMsg` or `BlurMsg` arrived, for
+    /// models that add their own hysteresis.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # use std::time::Duration;
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .report_focus(true)
+    ///     .focus_debounce(Duration::from_millis(100))
+    ///     .build();
+    /// ```
+    pub fn focus_debounce(mut self, window: std::time::Duration) -> Self {
+        self.config.focus_debounce = window;
+        self
+    }
+
     /// Sets the target frames per second for rendering.
     ///
     /// This controls how often the `view` method of the model is called and
@@ -260,6 +928,94 @@ impl<M: Model> ProgramBuilder<M> {
         self
     }
 
+    /// Sets whether to enable the keyboard enhancement protocol.
+    ///
+    /// When enabled and the terminal supports it, keys that legacy terminals
+    /// cannot tell apart are reported distinctly: Ctrl+M arrives as
+    /// `Char('m')` with `CONTROL` instead of `Enter`, and Ctrl+I as
+    /// `Char('i')` with `CONTROL` instead of `Tab`. On other terminals this
+    /// has no effect, and [`input_is_ambiguous`](crate::event::input_is_ambiguous)
+    /// keeps reporting such keys as ambiguous.
+    pub fn keyboard_enhancement(mut self, enabled: bool) -> Self {
+        self.config.keyboard_enhancement = enabled;
+        self
+    }
+
+    /// Sets whether `run` fails when a requested terminal mode cannot be
+    /// enabled.
+    ///
+    /// By default only failing to enter raw mode stops the program. The
+    /// alternate screen, mouse reporting, focus reporting, bracketed paste
+    /// and the keyboard enhancement protocol are each tried on their own;
+    /// any that fail are logged (with the `logging` feature) and reported
+    /// to the model in a [`ModesDegradedMsg`](crate::ModesDegradedMsg), and
+    /// the program runs without them. With `strict_modes(true)`, the first
+    /// failure is returned from `run` instead.
+    pub fn strict_modes(mut self, enabled: bool) -> Self {
+        self.config.strict_modes = enabled;
+        self
+    }
+
+    /// Puts the terminal's cursor keys into `mode` for the program's run.
+    ///
+    /// A program that exits without resetting application cursor-key mode
+    /// (DECCKM) leaves arrow keys sending SS3 sequences, so what the next
+    /// program receives depends on what ran before. Setting a mode makes it
+    /// deterministic. Terminals offer no portable way to read the mode back,
+    /// so on exit, and while an external process has the terminal, it is
+    /// put back into [`CursorKeyMode::Normal`](crate::CursorKeyMode::Normal),
+    /// the mode terminals and shells start in.
+    ///
+    /// Both encodings are decoded either way; see
+    /// [`parse_cursor_key`](crate::parse_cursor_key). By default the mode is
+    /// left alone.
+    pub fn cursor_key_mode(mut self, mode: crate::terminal::CursorKeyMode) -> Self {
+        self.config.cursor_key_mode = Some(mode);
+        self
+    }
+
+    /// Sets whether queued runs of identical key presses are merged.
+    ///
+    /// When enabled, a key press that is followed in the event queue by
+    /// identical presses, as happens when a held key auto-repeats faster
+    /// than the model updates, is delivered as one
+    /// [`KeyRepeatMsg`](crate::KeyRepeatMsg) carrying the number of presses.
+    /// Held-key navigation then stops as soon as the key is released instead
+    /// of working through a backlog. Keys the program is keeping up with
+    /// still arrive as `KeyMsg`.
+    ///
+    /// The model must handle `KeyRepeatMsg`, for example with
+    /// [`KeyMsg::presses`](crate::KeyMsg::presses), or repeated keys are
+    /// lost.
+    pub fn coalesce_key_repeats(mut self, enabled: bool) -> Self {
+        self.config.coalesce_key_repeats = enabled;
+        self
+    }
+
+    /// Merges queued runs of mouse wheel events into a single `MouseMsg`.
+    ///
+    /// Terminals report a wheel one notch at a time and never accelerate,
+    /// so a fast scroll through a long document queues many events. When
+    /// enabled, consecutive wheel events in the same direction that are
+    /// already queued arrive as one [`MouseMsg`](crate::MouseMsg) whose
+    /// [`scroll_delta`](crate::MouseMsg::scroll_delta) is their sum, at the
+    /// position of the last one. The model must scroll by `scroll_delta`
+    /// rather than by one line per message.
+    pub fn coalesce_wheel_events(mut self, enabled: bool) -> Self {
+        self.config.coalesce_wheel_events = enabled;
+        self
+    }
+
+    /// Sets how many lines one mouse wheel notch scrolls.
+    ///
+    /// Wheel events carry this many lines in their
+    /// [`scroll_delta`](crate::MouseMsg::scroll_delta). Defaults to 1; values
+    /// below 1 are raised to 1.
+    pub fn wheel_scroll_lines(mut self, lines: u16) -> Self {
+        self.config.wheel_scroll_lines = lines.max(1);
+        self
+    }
+
     /// Configures the program to use the default terminal input (stdin).
     ///
     /// This is the default behavior, so calling this method is optional.
@@ -293,6 +1049,22 @@ impl<M: Model> ProgramBuilder<M> {
         self
     }
 
+    /// Sets whether to write the terminal output to stderr instead of stdout.
+    ///
+    /// When enabled, frames and all terminal control sequences go to stderr,
+    /// and nothing is written to stdout. This lets the program print its
+    /// result to stdout after `run()` returns, so it can be captured by the
+    /// shell (`FILE=$(my-picker)`). Raw mode and input still use the
+    /// controlling terminal. Has no effect when a custom output writer is set
+    /// with [`output`](Self::output).
+    ///
+    /// `build()` fails with `Error::Configuration` if stderr is not a
+    /// terminal.
+    pub fn output_to_stderr(mut self, enabled: bool) -> Self {
+        self.config.output_to_stderr = enabled;
+        self
+    }
+
     /// Sets an external cancellation token for the program.
     ///
     /// When the token is cancelled, the program's event loop will gracefully shut down.
@@ -341,96 +1113,947 @@ impl<M: Model> ProgramBuilder<M> {
         self
     }
 
-    /// Builds the `Program` instance with the configured options.
+    /// Enables per-type message statistics.
     ///
-    /// # Returns
+    /// The program counts every message its event loop receives, and every
+    /// message the filter or a plugin discards, by type. Read them with
+    /// [`Program::message_stats`]; with memory monitoring enabled they are
+    /// also part of each [`MemorySnapshot`](crate::MemorySnapshot). When
+    /// disabled, no counting is done at all.
     ///
-    /// A `Result` containing the `Program` instance or an `Error` if building fails.
-    pub fn build(self) -> Result<Program<M>, Error> {
-        Program::new(self.config, self.message_filter)
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder().message_stats(true).build().unwrap();
+    /// let stats = program.message_stats().unwrap().clone();
+    /// // ... run the program, then:
+    /// eprint!("{}", stats.dump());
+    /// ```
+    pub fn message_stats(mut self, enabled: bool) -> Self {
+        self.config.message_stats = enabled;
+        self
     }
-}
-
-/// The main `Program` struct that coordinates the application.
-///
-/// The `Program` is responsible for setting up the terminal, managing the
-/// event loop, executing commands, and rendering the model's view.
-pub struct Program<M: Model> {
-    /// The configuration for this `Program` instance.
-    pub config: ProgramConfig,
-    event_tx: crate::event::EventSender,
-    event_rx: crate::event::EventReceiver,
-    terminal: Option<Box<dyn TerminalInterface + Send>>,
-    /// Active timer handles for cancellation
-    active_timers: HashMap<u64, CancellationToken>,
-    /// Set of spawned tasks that can be cancelled on shutdown
-    task_set: JoinSet<()>,
-    /// Cancellation token for coordinated shutdown
-    shutdown_token: CancellationToken,
-    /// Memory usage monitor (optional)
-    memory_monitor: Option<crate::memory::MemoryMonitor>,
-    /// Optional model-aware message filter
-    message_filter: Option<MessageFilter<M>>,
-    _phantom: PhantomData<M>,
-}
 
-impl<M: Model> Program<M> {
-    /// Creates a new `ProgramBuilder` for configuring and building a `Program`.
-    pub fn builder() -> ProgramBuilder<M> {
-        ProgramBuilder::new()
+    /// Reports message types that arrive faster than `max_per_second` for
+    /// at least `sustained_for`, such as a tick loop that duplicates itself.
+    ///
+    /// Enables [`message_stats`](Self::message_stats). The first time a type
+    /// exceeds the limit, the program logs a warning (with the `logging`
+    /// feature) and sends a
+    /// [`MessageRateExceededMsg`](crate::memory::MessageRateExceededMsg) to
+    /// the model, which may ignore it.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_per_second` - The highest acceptable sustained rate
+    /// * `sustained_for` - How long the rate must stay above the limit
+    pub fn warn_on_message_rate(
+        mut self,
+        max_per_second: u64,
+        sustained_for: std::time::Duration,
+    ) -> Self {
+        self.config.message_stats = true;
+        self.config.message_rate_limit = Some((max_per_second, sustained_for));
+        self
     }
 
-    /// Creates a new `Program` instance with the given configuration.
+    /// Counts the command futures the program is awaiting.
     ///
-    /// This method is called internally by `ProgramBuilder::build()` and should not
-    /// be called directly. Use `Program::builder()` followed by `build()` instead.
+    /// Enables [`memory_monitoring`](Self::memory_monitoring); the count is
+    /// [`MemorySnapshot::in_flight_commands`](crate::MemorySnapshot::in_flight_commands).
+    /// A command is counted while it runs, from when it is spawned until it
+    /// produces its message or is dropped on shutdown; commands that are
+    /// ready right away and queued `batch_limited` commands are not. A
+    /// count that keeps growing usually means a timer is re-armed more than
+    /// once per fire.
+    pub fn track_tasks(mut self, enabled: bool) -> Self {
+        self.config.track_tasks = enabled;
+        self
+    }
+
+    /// Sets the clock the program's timers wait on.
     ///
-    /// # Arguments
+    /// Timer commands such as [`tick`](crate::command::tick) and
+    /// [`every`](crate::command::every), and the program's own timers such
+    /// as [`idle_after`](Self::idle_after), measure their delays on `clock`
+    /// instead of tokio's timer. Tests pass a
+    /// [`MockClock`](crate::MockClock) and advance it by hand, so timers
+    /// fire exactly when the test says. See [`clock`](crate::clock) for
+    /// the full list.
     ///
-    /// * `config` - The `ProgramConfig` to use for this program.
-    /// * `message_filter` - Optional model-aware message filter function.
+    /// # Example
     ///
-    /// # Returns
+    /// ```rust
+    /// # use bubbletea_rs::{Model, MockClock, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let clock = MockClock::new();
+    /// let program = Program::<MyModel>::builder()
+    ///     .clock(Box::new(clock.clone()))
+    ///     .build()
+    ///     .unwrap();
+    /// // ... run the program, and meanwhile:
+    /// clock.advance(std::time::Duration::from_secs(1));
+    /// ```
+    pub fn clock(mut self, clock: Box<dyn crate::Clock>) -> Self {
+        self.config.clock = Some(Arc::from(clock));
+        self
+    }
+
+    /// Sets a function to report the error when the program fails.
     ///
-    /// A `Result` containing the `Program` instance or an `Error` if initialization fails.
+    /// `handler` is called once the terminal has been restored, just
+    /// before [`run`](Program::run) returns the error, so whatever it
+    /// prints lands on a normal screen. The error is still returned, so
+    /// `main` can pick the exit code.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// Returns an `Error` if:
-    /// - Terminal initialization fails
-    /// - Event channel setup fails
-    /// - Global state initialization fails
-    fn new(config: ProgramConfig, message_filter: Option<MessageFilter<M>>) -> Result<Self, Error> {
-        let (event_tx, event_rx) = if let Some(buffer_size) = config.event_channel_buffer {
-            let (tx, rx) = mpsc::channel(buffer_size);
-            (
-                crate::event::EventSender::Bounded(tx),
-                crate::event::EventReceiver::Bounded(rx),
-            )
-        } else {
-            let (tx, rx) = mpsc::unbounded_channel();
-            (
-                crate::event::EventSender::Unbounded(tx),
-                crate::event::EventReceiver::Unbounded(rx),
-            )
-        };
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .on_error(|err| eprintln!("Error: {err}"))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_error(mut self, handler: impl Fn(&Error) + Send + 'static) -> Self {
+        self.config.on_error = Some(Box::new(handler));
+        self
+    }
 
-        let terminal = if config.without_renderer {
+    /// Sets whether the terminal's capabilities are detected again whenever
+    /// it is resized.
+    ///
+    /// A resize often means the session moved, such as a tmux session
+    /// reattached from another terminal, and the new terminal may support
+    /// different features. When the capabilities differ from before, the
+    /// model receives a [`CapabilitiesMsg`](crate::CapabilitiesMsg) after the
+    /// `WindowSizeMsg`. Disabled by default.
+    pub fn requery_capabilities_on_resize(mut self, enabled: bool) -> Self {
+        self.config.requery_capabilities_on_resize = enabled;
+        self
+    }
+
+    /// Logs a warning (with the `logging` feature) whenever a `Model::view`
+    /// call takes longer than `threshold`.
+    ///
+    /// The program times every `view` call regardless; see
+    /// [`Program::view_stats`].
+    pub fn with_view_warning_threshold(mut self, threshold: std::time::Duration) -> Self {
+        self.config.view_warning_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets whether to show the most recently decoded input event.
+    ///
+    /// When enabled, the `Debug` form of the last `KeyMsg`, `KeyRepeatMsg`
+    /// or `MouseMsg` the program received is drawn over the last line of
+    /// each frame, after `view()` has produced it. The model is not
+    /// involved, so this works for checking key bindings in any program.
+    /// Frames are drawn in full while the overlay is enabled.
+    pub fn debug_input_overlay(mut self, enabled: bool) -> Self {
+        self.config.debug_input_overlay = enabled;
+        self
+    }
+
+    /// Records which lines of the view change from frame to frame.
+    ///
+    /// For finding view output that is never stable, such as a timestamp
+    /// rendered anew every frame, which defeats skipping identical frames
+    /// and rewriting only changed lines. Each frame's view is compared line
+    /// by line with the previous one; read the changed lines and each
+    /// line's churn over the recent frames with [`Program::frame_churn`].
+    /// With [`debug_input_overlay`](Self::debug_input_overlay) also enabled,
+    /// the overlay names the lines that changed in at least half of them.
+    /// When disabled, the default, nothing is compared.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder().frame_churn(true).build().unwrap();
+    /// let churn = program.frame_churn().unwrap().clone();
+    /// // ... run the program, then:
+    /// eprint!("{}", churn.dump());
+    /// ```
+    pub fn frame_churn(mut self, enabled: bool) -> Self {
+        self.config.frame_churn = enabled;
+        self
+    }
+
+    /// Sets a static title for the program's window.
+    ///
+    /// The title is set when the program starts and again whenever the
+    /// window regains focus, in case another program changed it meanwhile.
+    /// Screen readers announce it as the window's label. A model that
+    /// overrides [`Model::title`](crate::Model::title) takes precedence.
+    ///
+    /// With the `proctitle` feature, the title also becomes the process
+    /// title shown by tools such as `ps` and `top`.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.config.title = Some(title.into());
+        self
+    }
+
+    /// Adds commands to run at startup, without changing the model's
+    /// `init`.
+    ///
+    /// The commands are batched with the command `init` returns, after it,
+    /// so embedders can inject startup effects such as an initial data
+    /// fetch. Like `init`'s command, they start once the first frame has
+    /// been drawn, so their messages always arrive after the initial
+    /// render. Calling this more than once adds to the commands already
+    /// given.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bubbletea_rs::{command, Model, Msg, Program};
+    /// # struct Dashboard;
+    /// # impl Model for Dashboard {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (Self, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// # fn main() -> Result<(), bubbletea_rs::Error> {
+    /// let program = Program::<Dashboard>::builder()
+    ///     .initial_commands(vec![command::window_size(), command::hide_cursor()])
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn initial_commands(mut self, cmds: Vec<crate::Cmd>) -> Self {
+        self.config.initial_commands.extend(cmds);
+        self
+    }
+
+    /// Caps the width the model renders for at `width` columns.
+    ///
+    /// In a wider terminal, every `WindowSizeMsg` reports `width` instead
+    /// of the terminal's width, and each frame is centered with blank
+    /// margins on both sides. Lines wider than `width` are cut off. In a
+    /// terminal no wider than `width`, nothing changes.
+    pub fn max_width(mut self, width: u16) -> Self {
+        self.config.max_width = Some(width);
+        self
+    }
+
+    /// Sets whether `every_precise` timers use high precision scheduling.
+    ///
+    /// When enabled, frame timers created with `command::every_precise` sleep
+    /// until shortly before each deadline and spin-wait for the remainder,
+    /// yielding to other tasks between checks. This gives sub-millisecond
+    /// frame pacing but burns CPU, so it is disabled by default. Other timers
+    /// are unaffected.
+    pub fn high_precision_timer(mut self, enabled: bool) -> Self {
+        self.config.high_precision_timer = enabled;
+        self
+    }
+
+    /// Adds a key that quits the program.
+    ///
+    /// When a matching `KeyMsg` arrives, the program handles a `QuitMsg` in
+    /// its place: the model is updated with the `QuitMsg` instead of the
+    /// key, and the program exits.
+    /// All other keys reach the model as usual. Call this several times to
+    /// register more than one quit key. No quit keys are configured by
+    /// default.
+    ///
+    /// Keys pass through the message filter first, so a filter can still
+    /// remap or swallow them.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to quit on, such as `"q"`, `"ctrl+c"` or
+    ///   `KeyCode::Esc`. See [`KeySpec`] for the accepted forms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is a string that is not a valid key spec. Parse
+    /// untrusted strings with `KeySpec::parse` first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .quit_on("q")
+    ///     .quit_on("ctrl+c")
+    ///     .build();
+    /// ```
+    pub fn quit_on(mut self, key: impl Into<KeySpec>) -> Self {
+        self.config.quit_keys.push(key.into());
+        self
+    }
+
+    /// Adds several keys that quit the program.
+    ///
+    /// The same as calling [`quit_on`](Self::quit_on) for each key, so
+    /// small utilities can exit on the usual keys without a branch in
+    /// `update`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key is a string that is not a valid key spec.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .quit_keys(&["q", "esc", "ctrl+c"])
+    ///     .build();
+    /// ```
+    pub fn quit_keys<K: Clone + Into<KeySpec>>(self, keys: &[K]) -> Self {
+        keys.iter().cloned().fold(self, Self::quit_on)
+    }
+
+    /// Sets whether a `Ctrl+C` key press quits the program.
+    ///
+    /// In raw mode the terminal delivers `Ctrl+C` as a `KeyMsg` rather than
+    /// a signal. When enabled, it is handled like a key added with
+    /// [`quit_keys`](Self::quit_keys); the model sees a `QuitMsg` in its
+    /// place. Disabled by default, so `Ctrl+C` reaches the model like any
+    /// other key unless it is listed among the quit keys. Models using
+    /// [`CommonHandlers`](crate::model::common::CommonHandlers) make the
+    /// same choice with `quit_on_ctrl_c`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .quit_keys(&["q", "esc"])
+    ///     .quit_on_ctrl_c(true)
+    ///     .build();
+    /// ```
+    pub fn quit_on_ctrl_c(mut self, enabled: bool) -> Self {
+        self.config.quit_on_ctrl_c = enabled;
+        self
+    }
+
+    /// Detects pastes from bursts of key presses, for terminals without
+    /// bracketed paste.
+    ///
+    /// When `burst_threshold` characters arrive within `burst_window`, the
+    /// model receives them as one `PasteMsg` instead of individual `KeyMsg`s.
+    /// Plain key presses are held back for up to `burst_window` to tell the
+    /// two apart. See [`InputHandler::with_paste_heuristic`] for details;
+    /// [`PasteHeuristic::default`] uses 10 characters within 50 ms.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # use std::time::Duration;
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .paste_heuristic(10, Duration::from_millis(50))
+    ///     .build();
+    /// ```
+    pub fn paste_heuristic(
+        mut self,
+        burst_threshold: usize,
+        burst_window: std::time::Duration,
+    ) -> Self {
+        self.config.paste_heuristic = Some(PasteHeuristic {
+            burst_threshold,
+            burst_window,
+        });
+        self
+    }
+
+    /// Streams bracketed pastes to the model in pieces of `chunk_size` bytes.
+    ///
+    /// Instead of one `PasteMsg` once the whole paste has been read, the
+    /// model receives `PasteChunkMsg`s as the text arrives, followed by a
+    /// `PasteEndMsg`. Other messages are handled in between, so the program
+    /// stays responsive while a paste of several megabytes is read. A
+    /// `chunk_size` of zero, the default, delivers each paste as one
+    /// `PasteMsg`. On the real terminal the whole paste has already been
+    /// read by the time the first chunk is sent; see
+    /// [`InputHandler::with_paste_chunks`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .bracketed_paste(true)
+    ///     .paste_chunk_size(64 * 1024)
+    ///     .build();
+    /// ```
+    pub fn paste_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.config.paste_chunk_size = (chunk_size > 0).then_some(chunk_size);
+        self
+    }
+
+    /// Sets when buffered terminal output is flushed.
+    ///
+    /// [`FlushStrategy::EveryFrame`] (the default) flushes after every
+    /// frame. [`FlushStrategy::OnIdle`] flushes once the event loop has
+    /// handled every waiting message, and [`FlushStrategy::Interval`]
+    /// flushes at most once per interval. All buffered output is flushed
+    /// before the terminal is restored on exit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, FlushStrategy, IntoCmd, Model, Msg, Program};
+    /// # use std::time::Duration;
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .flush_strategy(FlushStrategy::Interval(Duration::from_millis(16)))
+    ///     .build();
+    /// ```
+    pub fn flush_strategy(mut self, strategy: FlushStrategy) -> Self {
+        self.config.flush_strategy = strategy;
+        self
+    }
+
+    /// Delivers an [`IdleMsg`](crate::IdleMsg) once no input has arrived
+    /// for `period`.
+    ///
+    /// Key presses, mouse events and pastes count as input. One `IdleMsg`
+    /// is sent per quiet spell, however long it lasts, and the next input
+    /// starts a new one. Useful for putting off expensive work, such as
+    /// re-sorting a long list, until the user pauses. Off by default.
+    pub fn idle_after(mut self, period: std::time::Duration) -> Self {
+        self.config.idle_after = Some(period);
+        self
+    }
+
+    /// Sets whether frames are skipped while the terminal can't keep up.
+    ///
+    /// Enabled by default. When drawing a frame takes longer than the frame
+    /// budget set with [`with_fps`](Self::with_fps), as over a slow SSH
+    /// link, the program stops drawing a frame after each message while
+    /// more messages are waiting. Once they are handled, only the latest
+    /// view is drawn, in full. Whole frames are skipped, so synchronized
+    /// output blocks are never left open. Skipped frames are counted in
+    /// [`ViewStats::frames_skipped`](crate::memory::ViewStats::frames_skipped).
+    ///
+    /// When disabled, a frame is drawn after every message.
+    pub fn frame_coalescing(mut self, enabled: bool) -> Self {
+        self.config.frame_coalescing = enabled;
+        self
+    }
+
+    /// Renders inline, in a live region of at most `live_rows` rows, with
+    /// printed lines scrolling into the terminal's scrollback above it.
+    ///
+    /// Instead of redrawing the whole screen, each frame repaints the live
+    /// region in place. Lines from [`println`](crate::println) and
+    /// [`printf`](crate::printf) are written above the region and stay in
+    /// the scrollback, like the finished steps of a build tool, while the
+    /// region is redrawn below them. Only the last `live_rows` lines of the
+    /// view are shown; the region shrinks to leave a row free on small
+    /// terminals and never moves up over logged lines. Meant for programs
+    /// that do not use the alternate screen.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    /// # struct MyModel;
+    /// # impl Model for MyModel {
+    /// #     fn init() -> (Self, Option<Cmd>) { (MyModel, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let program = Program::<MyModel>::builder()
+    ///     .inline_with_log_region(2)
+    ///     .build();
+    /// ```
+    pub fn inline_with_log_region(mut self, live_rows: u16) -> Self {
+        self.config.log_region = Some(live_rows);
+        self
+    }
+
+    /// Sets whether to run in accessible mode, for screen readers.
+    ///
+    /// Full-screen repaints are hard to follow with a screen reader, so in
+    /// accessible mode the terminal works as a line printer (see
+    /// [`TerminalInterface::set_line_printer`]): each changed frame is
+    /// printed as plain text below the last, and announcements from
+    /// [`announce`](crate::command::announce) are printed as plain lines.
+    /// The alternate screen, mouse reporting and the live region of
+    /// [`inline_with_log_region`](Self::inline_with_log_region) are turned
+    /// off. Setting the `ACCESSIBLE` environment variable to any non-empty
+    /// value enables accessible mode too. Off by default.
+    pub fn accessible(mut self, enabled: bool) -> Self {
+        self.config.accessible = enabled;
+        self
+    }
+
+    /// Uses a custom terminal instead of the crossterm terminal.
+    ///
+    /// This is mainly useful in tests, with a
+    /// [`DummyTerminal`](crate::DummyTerminal) that records what the program
+    /// does. The output options ([`output`](Self::output) and
+    /// [`output_to_stderr`](Self::output_to_stderr)) do not apply to a
+    /// custom terminal, and keyboard input is only read from a custom
+    /// [`input`](Self::input). [`without_renderer`](Self::without_renderer)
+    /// still disables the terminal.
+    pub fn terminal(mut self, terminal: impl TerminalInterface + Send + 'static) -> Self {
+        self.config.terminal = Some(Box::new(terminal));
+        self
+    }
+
+    /// Registers a program-level plugin.
+    ///
+    /// Plugins issue commands when the program starts and stops, and see
+    /// every message (after the message filter) before the model does. They
+    /// run in the order they are registered. See the [`plugin`](crate::plugin)
+    /// module for the built-in plugins.
+    ///
+    /// # Arguments
+    ///
+    /// * `plugin` - The plugin to register
+    pub fn with_plugin(mut self, plugin: Box<dyn Plugin>) -> Self {
+        self.plugins.register(plugin);
+        self
+    }
+
+    /// Builds the `Program` instance with the configured options.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Program` instance or an `Error` if building fails.
+    pub fn build(self) -> Result<Program<M>, Error> {
+        Program::new(self.config, self.message_filter, self.plugins, self.undo)
+    }
+}
+
+impl<M: Model + Clone> ProgramBuilder<M> {
+    /// Adds undo and redo to the model, keeping up to `max_depth` steps.
+    ///
+    /// The program records the model's state around every key press and
+    /// paste it passes to `update`, in an
+    /// [`UndoStack`](crate::model::history::UndoStack). `Ctrl+Z` restores the
+    /// previous state and `Ctrl+Y` the state that was last undone; both are
+    /// handled before `update` and never reach the model. Other messages,
+    /// such as timer ticks, are not recorded, so they don't flood the
+    /// history; undo still restores the whole model, including changes they
+    /// made since the restored state was recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bubbletea_rs::{Model, Program};
+    /// #[derive(Clone)]
+    /// struct Editor {
+    ///     text: String,
+    /// }
+    /// # impl Model for Editor {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (Editor { text: String::new() }, None) }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
+    /// #     fn view(&self) -> String { self.text.clone() }
+    /// # }
+    ///
+    /// let program = Program::<Editor>::builder().with_undo(50).build();
+    /// ```
+    pub fn with_undo(mut self, max_depth: usize) -> Self {
+        self.undo = Some(Box::new(crate::model::history::UndoStack::<M>::new(
+            max_depth,
+        )));
+        self
+    }
+}
+
+/// The outcome of a program that quit normally.
+///
+/// Returned by [`Program::run_with_result`]. Besides the final model, it
+/// carries the value passed to [`quit_with`](crate::command::quit_with), if
+/// the program was ended that way.
+pub struct ProgramResult<M> {
+    /// The final state of the model.
+    pub model: M,
+    exit_value: Option<Box<dyn Any + Send>>,
+    view_height: usize,
+}
+
+impl<M> ProgramResult<M> {
+    /// Returns the exit value if one was set and it has type `T`.
+    ///
+    /// Returns `None` after a plain `quit()`, or if the value has a
+    /// different type.
+    pub fn exit_value<T: 'static>(&self) -> Option<&T> {
+        self.exit_value.as_ref()?.downcast_ref::<T>()
+    }
+
+    /// Returns `true` if the program was ended with `quit_with`.
+    pub fn has_exit_value(&self) -> bool {
+        self.exit_value.is_some()
+    }
+
+    /// Returns how many rows the last frame drawn took up on the terminal.
+    ///
+    /// Lines wider than the terminal are counted once per row they wrap
+    /// onto, as measured by [`ansi::height`](crate::ansi::height). An inline
+    /// program can use this to know how much of the screen its final view
+    /// covers. Zero if no frame was drawn.
+    pub fn view_height(&self) -> usize {
+        self.view_height
+    }
+
+    /// Consumes the result, returning the model and the exit value if it
+    /// has type `T`.
+    pub fn into_parts<T: 'static>(self) -> (M, Option<T>) {
+        let value = self
+            .exit_value
+            .and_then(|value| value.downcast::<T>().ok())
+            .map(|value| *value);
+        (self.model, value)
+    }
+}
+
+impl<M: std::fmt::Debug> std::fmt::Debug for ProgramResult<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgramResult")
+            .field("model", &self.model)
+            .field("has_exit_value", &self.has_exit_value())
+            .field("view_height", &self.view_height)
+            .finish()
+    }
+}
+
+/// The main `Program` struct that coordinates the application.
+///
+/// The `Program` is responsible for setting up the terminal, managing the
+/// event loop, executing commands, and rendering the model's view.
+pub struct Program<M: Model> {
+    /// The configuration for this `Program` instance.
+    pub config: ProgramConfig,
+    event_tx: crate::event::EventSender,
+    event_rx: crate::event::EventReceiver,
+    terminal: Option<Box<dyn TerminalInterface + Send>>,
+    /// Whether keyboard input is read from the terminal
+    reads_terminal_input: bool,
+    /// Active timer handles for cancellation
+    active_timers: HashMap<u64, CancellationToken>,
+    /// Active path watcher handles for cancellation
+    active_watchers: HashMap<u64, CancellationToken>,
+    /// Set of spawned tasks that can be cancelled on shutdown
+    task_set: crate::runtime::TaskSet,
+    /// Cancellation token for coordinated shutdown
+    shutdown_token: CancellationToken,
+    /// Memory usage monitor (optional)
+    memory_monitor: Option<crate::memory::MemoryMonitor>,
+    /// Per-type message counts (optional)
+    message_stats: Option<crate::memory::MessageStats>,
+    /// Per-line changes between frames (optional)
+    frame_churn: Option<crate::memory::FrameChurn>,
+    /// Optional model-aware message filter
+    message_filter: Option<MessageFilter<M>>,
+    /// Registered program-level plugins
+    plugins: PluginRegistry,
+    /// Undo history, if enabled with `with_undo`
+    undo: Option<UndoHistory<M>>,
+    /// Value from the last `quit_with` message
+    exit_value: Option<Box<dyn Any + Send>>,
+    /// Writes to the terminal that failed in a row
+    output_failures: u32,
+    /// Size set with `set_viewport_size`, overriding the terminal's size
+    viewport_size: Option<(u16, u16)>,
+    /// The last `ViewportSizeMsg` sent, once the model has asked for one
+    viewport_report: Option<crate::event::ViewportSizeMsg>,
+    /// Whether the terminal is showing the alternate screen
+    in_alt_screen: bool,
+    /// Message received while merging key repeats, to be handled next
+    held_msg: Option<Msg>,
+    /// Asks the input handler to query the terminal, for terminal input
+    keyboard_enhancement_queries: Option<mpsc::UnboundedSender<()>>,
+    /// The keyboard enhancement query awaiting an answer, if any
+    pending_keyboard_enhancement_query: Option<u64>,
+    /// Keyboard enhancement queries sent so far
+    keyboard_enhancement_query_count: u64,
+    /// Whether the terminal has the keyboard enhancement protocol active,
+    /// shared with the input handler so it can tell ambiguous keys apart
+    keyboard_enhancement_active: Arc<std::sync::atomic::AtomicBool>,
+    /// Execution times of `Model::view`
+    view_timings: crate::memory::ViewTimings,
+    /// `Debug` form of the last input event, for the debug input overlay
+    last_input: Option<String>,
+    /// The full view last sent to the terminal, for screenshots
+    last_frame: String,
+    /// Rows `last_frame` takes up at the terminal's width, wrapping included
+    view_height: usize,
+    /// The size in the last `WindowSizeMsg` the model received, for
+    /// `Model::view_sized`
+    model_size: (u16, u16),
+    /// Whether recurring timers hold their ticks, while an external process
+    /// has the terminal
+    timers_paused: tokio::sync::watch::Sender<bool>,
+    /// Whether each timer group is paused
+    timer_groups: HashMap<u64, tokio::sync::watch::Sender<bool>>,
+    /// Messages of grouped ticks that fired while their group was paused
+    held_ticks: Vec<crate::event::GroupedTickMsg>,
+    /// The number of the latest call of each debounce key
+    debounces: HashMap<&'static str, u64>,
+    /// The terminal while an external process has it; nothing is drawn
+    released_terminal: Option<Box<dyn TerminalInterface + Send>>,
+    /// External processes currently holding the terminal
+    exec_depth: usize,
+    /// Input modes currently enabled, restored after the terminal is
+    /// released
+    input_modes: crate::event::InputModes,
+    /// The mouse mode to restore when the active mouse capture ends
+    mouse_capture: Option<MouseMotion>,
+    /// Where the program is in its lifecycle, for `state` subscribers
+    run_state: tokio::sync::watch::Sender<RunState>,
+    /// Whether `run_session` put the terminal in raw mode, so there is
+    /// setup to undo on exit
+    terminal_set_up: bool,
+    _phantom: PhantomData<M>,
+}
+
+impl<M: Model> Program<M> {
+    /// Creates a new `ProgramBuilder` for configuring and building a `Program`.
+    pub fn builder() -> ProgramBuilder<M> {
+        ProgramBuilder::new()
+    }
+
+    /// Returns whether a program is currently running on the process's
+    /// terminal.
+    ///
+    /// While this is `true`, running another program on the terminal fails
+    /// with `Error::AlreadyRunning`. Programs using a custom terminal that
+    /// reports no [`device`](crate::TerminalInterface::device), such as a
+    /// `DummyTerminal`, do not count.
+    pub fn is_running() -> bool {
+        DEVICES_IN_USE
+            .lock()
+            .iter()
+            .any(|held| held == crate::terminal::CONTROLLING_TERMINAL)
+    }
+
+    /// Returns whether the program's terminal supports bracketed paste.
+    ///
+    /// When it does and bracketed paste is enabled (with
+    /// [`ProgramBuilder::bracketed_paste`], `command::enable_bracketed_paste`
+    /// or the `BracketedPastePlugin`), pastes arrive as one `PasteMsg` with
+    /// `bracketed` set. Otherwise they arrive as key presses, which
+    /// [`ProgramBuilder::paste_heuristic`] can coalesce into a `PasteMsg`
+    /// with `bracketed` unset. Returns `false` without a renderer.
+    pub fn paste_supported(&self) -> bool {
+        self.terminal
+            .as_ref()
+            .is_some_and(|terminal| terminal.capabilities().bracketed_paste)
+    }
+
+    /// Renders `model` to the text a full redraw writes after clearing the
+    /// screen, without a terminal or a running program.
+    ///
+    /// This is `model.view_sized(0, 0)`, the view before any size is known,
+    /// with the same processing as the renderer: lines are separated by
+    /// `\r\n`, exactly as they reach the terminal. Useful for asserting on a
+    /// component's output in unit tests.
+    ///
+    /// Only available in tests and with the `testing` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program};
+    ///
+    /// struct Greeting;
+    ///
+    /// impl Model for Greeting {
+    ///     fn init() -> (Self, Option<Cmd>) {
+    ///         (Self, None)
+    ///     }
+    ///     fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+    ///     fn view(&self) -> String {
+    ///         "hello\nworld".to_string()
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Program::<Greeting>::render_to_string(&Greeting), "hello\r\nworld");
+    /// # }
+    /// ```
+    #[cfg(any(test, feature = "testing"))]
+    pub fn render_to_string(model: &M) -> String {
+        let view = model.view_sized(0, 0);
+        let mut frame = String::with_capacity(view.len());
+        crate::terminal::push_frame(&mut frame, &view);
+        frame
+    }
+
+    /// Creates a new `Program` instance with the given configuration.
+    ///
+    /// This method is called internally by `ProgramBuilder::build()` and should not
+    /// be called directly. Use `Program::builder()` followed by `build()` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The `ProgramConfig` to use for this program.
+    /// * `message_filter` - Optional model-aware message filter function.
+    /// * `plugins` - Program-level plugins registered on the builder.
+    /// * `undo` - Undo history enabled with `with_undo`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Program` instance or an `Error` if initialization fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if:
+    /// - Terminal initialization fails
+    /// - `output_to_stderr` is set but stderr is not a terminal
+    /// - Event channel setup fails
+    /// - Global state initialization fails
+    fn new(
+        mut config: ProgramConfig,
+        message_filter: Option<MessageFilter<M>>,
+        plugins: PluginRegistry,
+        undo: Option<UndoHistory<M>>,
+    ) -> Result<Self, Error> {
+        config.accessible |= std::env::var_os("ACCESSIBLE").is_some_and(|value| !value.is_empty());
+        if config.accessible {
+            // Nothing a line printer can't show
+            config.alt_screen = false;
+            config.mouse_motion = MouseMotion::None;
+            config.log_region = None;
+        }
+        let input_modes = crate::event::InputModes {
+            bracketed_paste: config.bracketed_paste,
+            mouse_motion: config.mouse_motion,
+            focus_reporting: config.report_focus,
+        };
+        let (event_tx, event_rx) = if let Some(buffer_size) = config.event_channel_buffer {
+            let (tx, rx) = mpsc::channel(buffer_size);
+            (
+                crate::event::EventSender::Bounded(tx),
+                crate::event::EventReceiver::Bounded(rx),
+            )
+        } else {
+            let (tx, rx) = mpsc::unbounded_channel();
+            (
+                crate::event::EventSender::Unbounded(tx),
+                crate::event::EventReceiver::Unbounded(rx),
+            )
+        };
+
+        let custom_terminal = config.terminal.take();
+        let reads_terminal_input = !config.without_renderer && custom_terminal.is_none();
+        let mut terminal = if config.without_renderer {
             None
+        } else if let Some(terminal) = custom_terminal {
+            Some(terminal)
         } else {
             let output_writer_for_terminal = config.output_writer.clone();
-            Some(Box::new(Terminal::new(output_writer_for_terminal)?)
-                as Box<dyn TerminalInterface + Send>)
+            let stream = if config.output_to_stderr && output_writer_for_terminal.is_none() {
+                if !OutputStream::Stderr.is_terminal() {
+                    return Err(Error::Configuration(
+                        "output_to_stderr requires stderr to be a terminal".to_string(),
+                    ));
+                }
+                OutputStream::Stderr
+            } else {
+                OutputStream::Stdout
+            };
+            let mut terminal =
+                Terminal::new(output_writer_for_terminal)?.with_output_stream(stream);
+            if let Some(live_rows) = config.log_region {
+                terminal = terminal.with_log_region(live_rows);
+            }
+            Some(Box::new(terminal) as Box<dyn TerminalInterface + Send>)
         };
 
+        if let Some(terminal) = &mut terminal {
+            if config.flush_strategy != FlushStrategy::EveryFrame {
+                terminal.set_auto_flush(false);
+            }
+            terminal.set_clear_on_alt_screen_enter(config.clear_on_alt_screen_enter);
+            if config.accessible {
+                terminal.set_line_printer(true);
+            }
+        }
+
         // Expose the event sender globally for command helpers
         let _ = crate::event::EVENT_SENDER.set(event_tx.clone());
 
         // Expose command environment globally for exec_process
         let _ = crate::command::COMMAND_ENV.set(config.environment.clone().unwrap_or_default());
 
-        let memory_monitor = if config.memory_monitoring {
-            Some(crate::memory::MemoryMonitor::new())
+        let message_stats = config.message_stats.then(|| {
+            let stats = crate::memory::MessageStats::new();
+            match config.message_rate_limit {
+                Some((max_per_second, sustained_for)) => {
+                    stats.with_rate_limit(max_per_second, sustained_for)
+                }
+                None => stats,
+            }
+        });
+
+        let frame_churn = config.frame_churn.then(crate::memory::FrameChurn::new);
+
+        let memory_monitor = if config.memory_monitoring || config.track_tasks {
+            let monitor = crate::memory::MemoryMonitor::new();
+            Some(match &message_stats {
+                Some(stats) => monitor.with_message_stats(stats.clone()),
+                None => monitor,
+            })
         } else {
             None
         };
@@ -440,11 +2063,42 @@ impl<M: Model> Program<M> {
             event_tx,
             event_rx,
             terminal,
+            reads_terminal_input,
             active_timers: HashMap::new(),
-            task_set: JoinSet::new(),
+            active_watchers: HashMap::new(),
+            task_set: crate::runtime::TaskSet::new(),
             shutdown_token: CancellationToken::new(),
             memory_monitor,
+            message_stats,
+            frame_churn,
             message_filter,
+            plugins,
+            undo,
+            exit_value: None,
+            output_failures: 0,
+            viewport_size: None,
+            viewport_report: None,
+            in_alt_screen: false,
+            held_msg: None,
+            keyboard_enhancement_queries: None,
+            pending_keyboard_enhancement_query: None,
+            keyboard_enhancement_query_count: 0,
+            keyboard_enhancement_active: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            view_timings: crate::memory::ViewTimings::new(),
+            last_input: None,
+            last_frame: String::new(),
+            view_height: 0,
+            model_size: (0, 0),
+            timers_paused: tokio::sync::watch::channel(false).0,
+            timer_groups: HashMap::new(),
+            held_ticks: Vec::new(),
+            debounces: HashMap::new(),
+            released_terminal: None,
+            exec_depth: 0,
+            input_modes,
+            mouse_capture: None,
+            run_state: tokio::sync::watch::channel(RunState::Initializing).0,
+            terminal_set_up: false,
             _phantom: PhantomData,
         })
     }
@@ -458,21 +2112,173 @@ impl<M: Model> Program<M> {
     /// # Returns
     ///
     /// A `Result` containing the final `Model` state or an `Error` if the program
-    /// terminates abnormally.
-    pub async fn run(mut self) -> Result<M, Error> {
+    /// terminates abnormally. Errors from `Model::try_init` are returned before
+    /// the terminal is set up.
+    ///
+    /// When the terminal knows its size, the model receives a
+    /// `WindowSizeMsg` right after `init` and before the first frame, so
+    /// `view` never has to guess its dimensions. The first frame is then
+    /// rendered right away, before any other message is handled, so `init`
+    /// does not need to return a command just to get the view on screen.
+    ///
+    /// Only one program can run on a terminal at a time. If another program
+    /// is still running on the same terminal, `Error::AlreadyRunning` is
+    /// returned right away, without touching the terminal. Programs that
+    /// run one after another are fine. See [`is_running`](Self::is_running).
+    ///
+    /// # Message ordering
+    ///
+    /// Messages are handled one at a time, in the order they reach the
+    /// program's queue:
+    ///
+    /// - A command returned by `update` or `init` that is ready right away,
+    ///   like `Box::pin(async { Some(msg) })` or [`quit`](crate::quit), has
+    ///   its message queued before the next message is handled. It lands
+    ///   behind every message already received, input included, and ahead
+    ///   of anything received later. Several such commands, for example in a
+    ///   [`batch`](crate::batch), are queued in the order they were given.
+    ///   Every command takes its first step on the event loop, so heavy
+    ///   synchronous work belongs in [`blocking`](crate::blocking). A
+    ///   command that panics in that step is caught and, like one that
+    ///   panics later on its task, reported to the model as an
+    ///   `Error::ProgramPanic` message when `catch_panics` is on; the
+    ///   program keeps running either way.
+    /// - Commands that have to wait deliver their message whenever they
+    ///   complete, so they have no fixed order relative to input.
+    /// - A `QuitMsg` takes effect when it is handled, after every message
+    ///   queued before it. Messages queued after it are dropped.
+    pub async fn run(self) -> Result<M, Error> {
+        self.run_with_result().await.map(|result| result.model)
+    }
+
+    /// Runs the application like [`run`](Self::run), blocking the calling
+    /// thread until it exits.
+    ///
+    /// For small CLIs that don't otherwise use async: no `#[tokio::main]` is
+    /// needed, and no tokio runtime is started. The event loop runs on the
+    /// calling thread, while commands, timers and
+    /// [`blocking`](crate::blocking) work run on a small built-in executor.
+    ///
+    /// Commands from this crate work the same on it. Commands that need
+    /// tokio's runtime themselves, such as ones that call `tokio::spawn`,
+    /// `tokio::time::sleep` or `command::fetch`, do not;
+    /// use `run` on a tokio runtime for those. OS signals are not handled,
+    /// so `signal_handler` has no effect, though Ctrl+C still reaches the
+    /// program as a key in raw mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Configuration` when called from inside an async
+    /// runtime, where blocking would stall it; use `run` there instead.
+    /// Otherwise fails as `run` does.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bubbletea_rs::{Model, Msg, Program};
+    /// # struct Menu;
+    /// # impl Model for Menu {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (Self, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// fn main() -> Result<(), bubbletea_rs::Error> {
+    ///     let model = Program::<Menu>::builder().build()?.run_blocking()?;
+    ///     # let _ = model;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "sync-runtime")]
+    pub fn run_blocking(self) -> Result<M, Error> {
+        #[cfg(feature = "tokio-runtime")]
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(Error::Configuration(
+                "run_blocking cannot be called from inside an async runtime; use run".to_string(),
+            ));
+        }
+        crate::runtime::block_on(self.run())
+    }
+
+    /// Runs the application like [`run`](Self::run), also returning the
+    /// value passed to [`quit_with`](crate::command::quit_with).
+    ///
+    /// The exit value is only available when the program quits normally.
+    /// Interrupts, kills and other errors return an `Error` as with `run`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bubbletea_rs::{Model, Msg, Program};
+    /// # struct Menu;
+    /// # impl Model for Menu {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (Self, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// # async fn example() -> Result<(), bubbletea_rs::Error> {
+    /// let result = Program::<Menu>::builder().build()?.run_with_result().await?;
+    /// match result.exit_value::<&str>() {
+    ///     Some(action) => println!("chose {action}"),
+    ///     None => println!("cancelled"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_with_result(mut self) -> Result<ProgramResult<M>, Error> {
+        let _finished = FinishOnDrop(self.run_state.clone());
+        // The event loop's own timers run on the program's clock too
+        let result = match self.config.clock.clone() {
+            Some(clock) => crate::clock::scope(clock, self.run_to_completion()).await,
+            None => self.run_to_completion().await,
+        };
+        if let (Err(err), Some(on_error)) = (&result, &self.config.on_error) {
+            on_error(err);
+        }
+        result
+    }
+
+    /// Runs the program, then restores the terminal however the run ended.
+    async fn run_to_completion(&mut self) -> Result<ProgramResult<M>, Error> {
+        // Only one program may drive a terminal at a time
+        let _device = match self.terminal.as_ref().and_then(|t| t.device()) {
+            Some(device) => Some(DeviceGuard::acquire(device)?),
+            None => None,
+        };
+
+        let result = self.run_session().await;
+        self.run_state.send_replace(RunState::ShuttingDown);
+
+        // An external process may still hold the terminal; restore it anyway
+        if let Some(terminal) = self.released_terminal.take() {
+            self.terminal = Some(terminal);
+        }
+        if self.terminal_set_up {
+            self.restore_terminal_on_exit().await;
+        }
+
+        // Cleanup: cancel all tasks and wait for them to complete
+        self.cleanup_tasks().await;
+
+        result.map(|model| ProgramResult {
+            model,
+            exit_value: self.exit_value.take(),
+            view_height: self.view_height,
+        })
+    }
+
+    /// Sets up the terminal and handles messages until the program quits or
+    /// fails. The terminal is left for `run_to_completion` to restore.
+    async fn run_session(&mut self) -> Result<M, Error> {
         // Set up panic hook
         if self.config.catch_panics {
             let event_tx = self.event_tx.clone();
             ORIGINAL_PANIC_HOOK.get_or_init(|| panic::take_hook());
 
             panic::set_hook(Box::new(move |panic_info| {
-                let payload = if let Some(s) = panic_info.payload().downcast_ref::<&str>() {
-                    s.to_string()
-                } else if let Some(s) = panic_info.payload().downcast_ref::<String>() {
-                    s.clone()
-                } else {
-                    "<unknown panic>".to_string()
-                };
+                if crate::command::in_blocking_command() {
+                    return; // The blocking command reports it as an `ErrorMsg`
+                }
+                let payload = crate::command::panic_message(panic_info.payload());
                 let _ = event_tx.send(Box::new(crate::Error::ProgramPanic(payload)) as Msg);
 
                 // Call the original hook if it exists
@@ -482,36 +2288,118 @@ impl<M: Model> Program<M> {
             }));
         }
 
+        // Fallible setup runs before the terminal is touched
+        let (mut model, mut cmd) = M::try_init()?;
+
         // Setup terminal
+        self.in_alt_screen = self.config.alt_screen;
+        // Each mode is optional: the program can run without any of them
+        let mut failures = Vec::new();
         if let Some(terminal) = &mut self.terminal {
             terminal.enter_raw_mode().await?;
+            self.terminal_set_up = true;
             if self.config.alt_screen {
-                terminal.enter_alt_screen().await?;
+                if let Err(err) = terminal.enter_alt_screen().await {
+                    failures.push((TerminalMode::AltScreen, err));
+                }
             }
-            match self.config.mouse_motion {
-                MouseMotion::Cell => terminal.enable_mouse_cell_motion().await?,
-                MouseMotion::All => terminal.enable_mouse_all_motion().await?,
-                MouseMotion::None => (),
+            let mouse = match self.config.mouse_motion {
+                MouseMotion::Cell => terminal.enable_mouse_cell_motion().await,
+                MouseMotion::All => terminal.enable_mouse_all_motion().await,
+                MouseMotion::None => Ok(()),
+            };
+            if let Err(err) = mouse {
+                failures.push((TerminalMode::Mouse, err));
             }
             if self.config.report_focus {
-                terminal.enable_focus_reporting().await?;
+                if let Err(err) = terminal.enable_focus_reporting().await {
+                    failures.push((TerminalMode::FocusReporting, err));
+                }
             }
             if self.config.bracketed_paste {
-                terminal.enable_bracketed_paste().await?;
+                if let Err(err) = terminal.enable_bracketed_paste().await {
+                    failures.push((TerminalMode::BracketedPaste, err));
+                }
+            }
+            if self.config.keyboard_enhancement {
+                match terminal.enable_keyboard_enhancement().await {
+                    Ok(active) => self
+                        .keyboard_enhancement_active
+                        .store(active, std::sync::atomic::Ordering::SeqCst),
+                    Err(err) => failures.push((TerminalMode::KeyboardEnhancement, err)),
+                }
+            }
+        }
+        if self.config.strict_modes {
+            if let Some((_, err)) = failures.into_iter().next() {
+                return Err(err);
+            }
+        } else if !failures.is_empty() {
+            self.degrade_modes(failures);
+        }
+        if let Some(terminal) = &mut self.terminal {
+            if let Some(mode) = self.config.cursor_key_mode {
+                terminal.set_cursor_key_mode(mode).await?;
             }
             terminal.hide_cursor().await?;
+            if self.config.clear_on_start && !self.config.alt_screen {
+                terminal.clear_to_end_of_screen().await?;
+            }
+            if let Some(title) = model.title().or_else(|| self.config.title.clone()) {
+                terminal.set_window_title(&title).await?;
+            }
+        }
+        #[cfg(feature = "proctitle")]
+        if let Some(title) = &self.config.title {
+            proctitle::set_title(title);
+        }
+
+        // Tell the model its size before the first frame is drawn
+        if let Some((width, height)) = self.window_size() {
+            self.model_size = (width, height);
+            let size_cmd = model
+                .update(Box::new(WindowSizeMsg { width, height }))
+                .into_cmd();
+            if let Some(size_cmd) = size_cmd {
+                cmd = Some(match cmd.take() {
+                    Some(init_cmd) => crate::command::batch(vec![init_cmd, size_cmd]),
+                    None => size_cmd,
+                });
+            }
         }
 
-        let (mut model, mut cmd) = M::init();
+        // Plugin startup commands and those given to the builder run
+        // alongside the model's init command
+        let mut startup_cmds = self.plugins.startup();
+        let initial_cmds = std::mem::take(&mut self.config.initial_commands);
+        if !startup_cmds.is_empty() || !initial_cmds.is_empty() {
+            startup_cmds.extend(cmd.take());
+            startup_cmds.extend(initial_cmds);
+            cmd = Some(crate::command::batch(startup_cmds));
+        }
 
         // Setup input handling - either terminal input or custom input source
-        if self.terminal.is_some() || self.config.input_source.is_some() {
+        if self.reads_terminal_input || self.config.input_source.is_some() {
             let input_source = self.config.input_source.take();
             let input_handler = if let Some(source) = input_source {
                 InputHandler::with_source(self.event_tx.clone(), source)
             } else {
-                InputHandler::new(self.event_tx.clone())
+                // crossterm swallows query replies, so the handler asks for us
+                let (query_tx, query_rx) = mpsc::unbounded_channel();
+                self.keyboard_enhancement_queries = Some(query_tx);
+                InputHandler::new(self.event_tx.clone()).with_keyboard_enhancement_queries(query_rx)
             };
+            let mut input_handler = input_handler
+                .with_timestamps()
+                .with_keyboard_enhancement_state(Arc::clone(&self.keyboard_enhancement_active))
+                .with_focus_debounce(self.config.focus_debounce);
+            if let Some(heuristic) = self.config.paste_heuristic {
+                input_handler = input_handler
+                    .with_paste_heuristic(heuristic.burst_threshold, heuristic.burst_window);
+            }
+            if let Some(chunk_size) = self.config.paste_chunk_size {
+                input_handler = input_handler.with_paste_chunks(chunk_size);
+            }
             let shutdown_token = self.shutdown_token.clone();
 
             // Update memory monitoring
@@ -531,70 +2419,368 @@ impl<M: Model> Program<M> {
             });
         }
 
-        let result = 'main_loop: loop {
-            if let Some(c) = cmd.take() {
-                let event_tx = self.event_tx.clone();
-                let shutdown_token = self.shutdown_token.clone();
+        // Whether messages were handled since the last flush, for the
+        // deferred flush strategies
+        let mut unflushed = false;
+        let mut last_flush = crate::clock::now();
+        // Whether the next frame must be drawn in full, ignoring model damage
+        let mut full_redraw = true;
+        // The initial view is painted before any message is handled
+        let mut initial_render = true;
+        // Set by `PauseRenderMsg`; frames are skipped until `ResumeRenderMsg`
+        let mut render_paused = false;
+        // Whether the last frame took longer than the frame budget
+        let frame_budget = std::time::Duration::from_secs(1) / self.config.fps.max(1);
+        let mut behind = false;
+        // Whether a frame was skipped and the latest view is not drawn yet
+        let mut frame_skipped = false;
+        // When input last arrived, and whether an `IdleMsg` was sent since
+        let mut last_input_at = crate::clock::now();
+        let mut idle_sent = false;
+        #[cfg(unix)]
+        let mut hangup = if self.config.signal_handler {
+            crate::runtime::Hangup::new()
+        } else {
+            None
+        };
 
-                // Update memory monitoring
-                if let Some(ref monitor) = self.memory_monitor {
-                    monitor.task_spawned();
+        crate::event::RUNNING_EVENT_LOOPS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.run_state.send_replace(RunState::Running);
+        let result = 'main_loop: loop {
+            // Draw the first frame, or catch up on skipped ones once every
+            // waiting message has been handled
+            let caught_up = frame_skipped
+                && !render_paused
+                && self.event_rx.is_empty()
+                && self.held_msg.is_none();
+            if std::mem::take(&mut initial_render) || caught_up {
+                frame_skipped = false;
+                match self.draw_full_frame(&model).await {
+                    Ok(elapsed) => behind = elapsed > frame_budget,
+                    Err(err) => break Err(self.lose_terminal(&mut model, err).await),
                 }
-
-                self.task_set.spawn(async move {
-                    tokio::select! {
-                        _ = shutdown_token.cancelled() => {
-                            // Shutdown requested, don't process command
-                        }
-                        result = c => {
-                            if let Some(msg) = result {
-                                let _ = event_tx.send(msg);
-                            }
+                unflushed |= self.config.flush_strategy != FlushStrategy::EveryFrame;
+                full_redraw = false;
+            }
+            if unflushed {
+                let due = match self.config.flush_strategy {
+                    FlushStrategy::EveryFrame => false,
+                    FlushStrategy::OnIdle => self.event_rx.is_empty() && self.held_msg.is_none(),
+                    FlushStrategy::Interval(interval) => {
+                        crate::clock::now() - last_flush >= interval
+                    }
+                };
+                if due {
+                    if let Some(terminal) = &mut self.terminal {
+                        let flushed = terminal.flush().await;
+                        if let Err(err) = self.check_output(flushed) {
+                            break Err(self.lose_terminal(&mut model, err).await);
                         }
                     }
-                });
+                    unflushed = false;
+                    last_flush = crate::clock::now();
+                }
+            }
+            let flush_deadline = match self.config.flush_strategy {
+                FlushStrategy::Interval(interval) if unflushed => Some(last_flush + interval),
+                _ => None,
+            };
+            let idle_deadline = self
+                .config
+                .idle_after
+                .filter(|_| !idle_sent)
+                .map(|period| last_input_at + period);
+
+            if let Some(c) = cmd.take() {
+                self.spawn_command(c);
             }
 
             select! {
                 _ = self.config.cancellation_token.as_ref().map_or(futures::future::pending().left_future(), |token| token.cancelled().right_future()).fuse() => {
                     break Ok(model); // External cancellation
                 }
-                event = self.event_rx.recv().fuse() => {
-                    if let Some(mut msg) = event {
+                event = recv_coalesced(&mut self.event_rx, &mut self.held_msg, &self.config).fuse() => {
+                    if let Some((mut msg, input_stamp)) = event {
+                        unflushed = true;
+                        let msg_type = match &self.message_stats {
+                            Some(stats) => {
+                                if let Some(warning) = stats.record_processed(&msg) {
+                                    #[cfg(feature = "logging")]
+                                    log::warn!(
+                                        "{} messages arriving at {:.0}/s",
+                                        warning.type_name,
+                                        warning.rate_per_sec
+                                    );
+                                    let _ = self.event_tx.send(Box::new(warning) as Msg);
+                                }
+                                Some(msg.as_ref().type_id())
+                            }
+                            None => None,
+                        };
                         // KillMsg triggers immediate termination without touching the model
-                        if msg.downcast_ref::<KillMsg>().is_some() {
-                            break Err(Error::ProgramKilled);
+                        if let Some(kill) = msg.downcast_ref::<KillMsg>() {
+                            break Err(Error::ProgramKilled(kill.exit_code));
+                        }
+                        if self.config.debug_input_overlay {
+                            self.record_input(&msg);
+                        }
+                        if is_user_input(&msg) {
+                            last_input_at = crate::clock::now();
+                            idle_sent = false;
                         }
                         if let Some(filter_fn) = &self.message_filter {
                             if let Some(filtered_msg) = filter_fn(&model, msg) {
                                 msg = filtered_msg;
                             } else {
+                                if let (Some(stats), Some(msg_type)) = (&self.message_stats, msg_type) {
+                                    stats.record_dropped(msg_type);
+                                }
                                 continue; // Message was filtered out
                             }
                         }
                         // If the filter produced a KillMsg, terminate immediately
-                        if msg.downcast_ref::<KillMsg>().is_some() {
-                            break Err(Error::ProgramKilled);
+                        if let Some(kill) = msg.downcast_ref::<KillMsg>() {
+                            break Err(Error::ProgramKilled(kill.exit_code));
+                        }
+                        // Plugins see every message before the model and may swallow it
+                        if !self.plugins.is_empty() {
+                            let mut plugin_cmds = Vec::new();
+                            let intercepted = self.plugins.intercept(msg, &mut plugin_cmds);
+                            for plugin_cmd in plugin_cmds {
+                                self.spawn_command(plugin_cmd);
+                            }
+                            match intercepted {
+                                Some(m) => msg = m,
+                                None => {
+                                    if let (Some(stats), Some(msg_type)) = (&self.message_stats, msg_type) {
+                                        stats.record_dropped(msg_type);
+                                    }
+                                    continue;
+                                }
+                            }
+                        }
+                        // Configured quit keys reach the model as a plain quit
+                        if crate::key::is_quit_key(&msg, &self.config.quit_keys, self.config.quit_on_ctrl_c) {
+                            msg = Box::new(QuitMsg);
+                        }
+                        // Start path watchers; the program owns their cancellation tokens
+                        #[cfg(feature = "watch")]
+                        if msg.is::<crate::watch::WatchMsgInternal>() {
+                            if let Ok(watch_msg) = msg.downcast::<crate::watch::WatchMsgInternal>() {
+                                self.active_watchers
+                                    .insert(watch_msg.watch_id, watch_msg.cancellation_token.clone());
+                                crate::runtime::spawn(crate::watch::run_watcher(*watch_msg, self.event_tx.clone()));
+                            }
+                            continue; // Don't pass this to the model
+                        }
+                        // Keyboard enhancement queries end with the terminal's reply or a timeout
+                        if msg.is::<crate::event::QueryKeyboardEnhancementsMsg>() {
+                            self.query_keyboard_enhancements().await;
+                            continue; // handled; don't pass to the model
+                        }
+                        if let Some(timeout) = msg.downcast_ref::<crate::event::KeyboardEnhancementsTimeoutMsg>() {
+                            if self.pending_keyboard_enhancement_query != Some(timeout.query) {
+                                continue; // answered in time
+                            }
+                            msg = Box::new(crate::event::KeyboardEnhancementsMsg { supported_flags: None });
+                        }
+                        if let Some(reply) = msg.downcast_ref::<crate::event::KeyboardEnhancementsMsg>() {
+                            if self.pending_keyboard_enhancement_query.take().is_none() {
+                                continue; // nobody asked, or the query already timed out
+                            }
+                            self.downgrade_keyboard_enhancement(reply.supported_flags).await;
+                        }
+                        let mut msg = self.keep_exit_value(msg);
+                        if let Some(size) = msg.downcast_mut::<WindowSizeMsg>() {
+                            size.width = self.capped_width(size.width);
+                            self.model_size = (size.width, size.height);
+                            if self.config.requery_capabilities_on_resize {
+                                self.requery_capabilities();
+                            }
+                        }
+                        if msg.is::<crate::event::SuspendMsg>() {
+                            self.run_state.send_replace(RunState::Suspended);
+                        } else if msg.is::<crate::event::ResumeMsg>() {
+                            self.run_state.send_if_modified(|state| {
+                                let resumed = *state == RunState::Suspended;
+                                if resumed {
+                                    *state = RunState::Running;
+                                }
+                                resumed
+                            });
+                        }
+                        // Focus reporting doesn't survive leaving raw mode,
+                        // and the terminal still thinks it is on
+                        if msg.is::<crate::event::ResumeMsg>() && self.input_modes.focus_reporting {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = terminal.disable_focus_reporting().await;
+                                let _ = terminal.enable_focus_reporting().await;
+                            }
+                        }
+                        if let Some(mouse) = msg.downcast_ref::<crate::MouseMsg>() {
+                            // Left over from a mode that reported more, such
+                            // as a capture that just ended
+                            if !self.input_modes.mouse_motion.reports(mouse.button) {
+                                continue;
+                            }
+                            if matches!(mouse.button, crossterm::event::MouseEventKind::Up(_)) {
+                                self.end_mouse_capture().await;
+                            }
+                        }
+                        // These disturb the screen, so the next frame can't rely on damage
+                        if msg.is::<WindowSizeMsg>()
+                            || msg.is::<crate::event::ClearScreenMsg>()
+                            || msg.is::<crate::event::ClearLineMsg>()
+                            || msg.is::<crate::event::ClearToEndOfScreenMsg>()
+                            || msg.is::<crate::event::ClearFromStartOfScreenMsg>()
+                            || msg.is::<crate::event::EnterAltScreenMsg>()
+                            || msg.is::<crate::event::ExitAltScreenMsg>()
+                            || msg.is::<crate::event::PrintMsg>()
+                            || msg.is::<crate::event::PrintfMsg>()
+                            || msg.is::<crate::event::ResumeMsg>()
+                            || msg.is::<crate::event::ResumeRenderMsg>()
+                        {
+                            full_redraw = true;
                         }
                         // Check for special internal messages
+                        let mut entering_alt_screen = false;
                         let mut should_quit = false;
                         let mut should_interrupt = false;
 
                         // Handle special internal messages that need to consume the message
-                        if msg.is::<crate::event::ClearScreenMsg>() {
+                        if let Some(terminal) = &mut self.terminal {
+                            if apply_terminal_mode(terminal.as_mut(), &msg).await {
+                                let mouse_mode = self.input_modes.mouse_motion;
+                                if self.input_modes.apply(&msg) {
+                                    // The model chose a mouse mode; keep it
+                                    // when the capture ends
+                                    if self.input_modes.mouse_motion != mouse_mode {
+                                        self.mouse_capture = None;
+                                    }
+                                    let modes = crate::event::ModesMsg(self.input_modes);
+                                    let _ = self.event_tx.send(Box::new(modes) as Msg);
+                                }
+                                continue; // handled; don't pass to the model
+                            }
+                        }
+                        if msg.is::<crate::event::ExecRequestMsg>() {
+                            if let Ok(request) = msg.downcast::<crate::event::ExecRequestMsg>() {
+                                self.begin_exec().await;
+                                crate::runtime::spawn(run_exec_request(*request, self.event_tx.clone()));
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::ExecStartMsg>() {
+                            if let Ok(exec) = msg.downcast::<crate::event::ExecStartMsg>() {
+                                self.begin_exec().await;
+                                let _ = exec.released.send(());
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::ExecEndMsg>() {
+                            if let Ok(exec) = msg.downcast::<crate::event::ExecEndMsg>() {
+                                // Repainted with the process's completion message
+                                full_redraw |= self.end_exec().await;
+                                let _ = exec.restored.send(());
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::BeginMouseCaptureMsg>() {
+                            if self.mouse_capture.is_none() {
+                                self.mouse_capture = Some(self.input_modes.mouse_motion);
+                                self.set_mouse_motion(MouseMotion::All).await;
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::EndMouseCaptureMsg>() {
+                            self.end_mouse_capture().await;
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::PauseRenderMsg>() {
+                            render_paused = true;
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::ResumeRenderMsg>() {
+                            render_paused = false;
+                            // Intentionally do not continue; allow render below to redraw view
+                        } else if msg.is::<crate::event::ClearScreenMsg>() {
                             if let Some(terminal) = &mut self.terminal {
                                 let _ = terminal.clear().await;
                             }
                             continue; // handled; don't pass to the model
-                        } else if msg.is::<crate::event::EnterAltScreenMsg>() {
+                        } else if msg.is::<crate::event::ClearLineMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = terminal.clear_line().await;
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::ClearToEndOfScreenMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = terminal.clear_to_end_of_screen().await;
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::ClearFromStartOfScreenMsg>() {
                             if let Some(terminal) = &mut self.terminal {
-                                let _ = terminal.enter_alt_screen().await;
+                                let _ = terminal.clear_from_start_of_screen().await;
                             }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::EnterAltScreenMsg>() {
+                            // Switched with the frame below
+                            entering_alt_screen = true;
+                            self.in_alt_screen = true;
                             // Intentionally do not continue; allow render below to redraw view
                         } else if msg.is::<crate::event::ExitAltScreenMsg>() {
                             if let Some(terminal) = &mut self.terminal {
                                 let _ = terminal.exit_alt_screen().await;
                             }
+                            self.in_alt_screen = false;
+                            // Intentionally do not continue; allow render below to redraw view
+                        } else if let Some(&crate::event::SetDebugInputOverlayMsg(enabled)) =
+                            msg.downcast_ref::<crate::event::SetDebugInputOverlayMsg>()
+                        {
+                            self.config.debug_input_overlay = enabled;
+                            if !enabled {
+                                self.last_input = None;
+                            }
+                            full_redraw = true;
+                            // Intentionally do not continue; allow render below to redraw view
+                        } else if let Some(print_msg) = msg.downcast_ref::<crate::event::PrintMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let text = format!("{}\n", print_msg.0);
+                                // Logged lines move the live region, so there is no cursor to keep
+                                let _ = if self.config.log_region.is_some() {
+                                    terminal.print(&text).await
+                                } else {
+                                    terminal.print_preserving_cursor(&text).await
+                                };
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if let Some(printf_msg) = msg.downcast_ref::<crate::event::PrintfMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = if self.config.log_region.is_some() {
+                                    terminal.print(&printf_msg.0).await
+                                } else {
+                                    terminal.print_preserving_cursor(&printf_msg.0).await
+                                };
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if let Some(announce) = msg.downcast_ref::<crate::event::AnnounceMsg>() {
+                            if let Some(terminal) = self.terminal.as_mut().filter(|_| self.config.accessible) {
+                                let _ = terminal.print(&format!("{}\n", announce.0)).await;
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::BellMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = terminal.bell().await;
+                            }
+                            continue; // handled; don't pass to the model
+                        } else if msg.is::<crate::event::VisualBellMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = terminal.set_reverse_video(true).await;
+                                let event_tx = self.event_tx.clone();
+                                crate::runtime::spawn(self.with_clock(async move {
+                                    crate::clock::sleep(VISUAL_BELL_DURATION).await;
+                                    let _ = event_tx.send(Box::new(crate::event::EndVisualBellMsg) as Msg);
+                                }));
+                            }
+                            // Intentionally do not continue; allow render below to redraw view
+                        } else if msg.is::<crate::event::EndVisualBellMsg>() {
+                            if let Some(terminal) = &mut self.terminal {
+                                let _ = terminal.set_reverse_video(false).await;
+                            }
                             // Intentionally do not continue; allow render below to redraw view
                         } else if msg.is::<crate::event::EveryMsgInternal>() {
                             // We need to consume the message to get ownership of the function
@@ -603,7 +2789,9 @@ impl<M: Model> Program<M> {
                                 let func = every_msg.func;
                                 let cancellation_token = every_msg.cancellation_token.clone();
                                 let timer_id = every_msg.timer_id;
+                                let behavior = every_msg.missed_tick_behavior;
                                 let event_tx = self.event_tx.clone();
+                                let group = every_msg.group.map(|group| self.timer_group(group).subscribe());
 
                                 // Store the cancellation token for this timer
                                 self.active_timers.insert(timer_id, cancellation_token.clone());
@@ -613,59 +2801,152 @@ impl<M: Model> Program<M> {
                                     monitor.timer_added();
                                 }
 
-                                tokio::spawn(async move {
-                                    let mut ticker = tokio::time::interval(duration);
-                                    ticker.tick().await; // First tick completes immediately
+                                crate::runtime::spawn(self.with_clock(crate::command::run_every_timer(
+                                    duration,
+                                    behavior,
+                                    func,
+                                    cancellation_token,
+                                    event_tx,
+                                    self.timers_paused.subscribe(),
+                                    group,
+                                )));
+                                continue; // Don't pass this to the model
+                            }
+                        } else if msg.is::<crate::event::EveryPreciseMsgInternal>() {
+                            if let Ok(every_msg) = msg.downcast::<crate::event::EveryPreciseMsgInternal>() {
+                                let interval = every_msg.interval;
+                                let func = every_msg.func;
+                                let cancellation_token = every_msg.cancellation_token.clone();
+                                let spin = self.config.high_precision_timer;
+                                let event_tx = self.event_tx.clone();
+                                let mut paused = self.timers_paused.subscribe();
+                                let shutdown_token = self.shutdown_token.clone();
+
+                                self.active_timers.insert(every_msg.timer_id, cancellation_token.clone());
+
+                                // Update memory monitoring
+                                if let Some(ref monitor) = self.memory_monitor {
+                                    monitor.timer_added();
+                                }
 
+                                crate::runtime::spawn(self.with_clock(async move {
+                                    // Absolute deadlines keep fractional intervals from drifting
+                                    let mut deadline = crate::clock::now() + interval;
                                     loop {
                                         tokio::select! {
                                             _ = cancellation_token.cancelled() => {
-                                                // Timer was cancelled
                                                 break;
                                             }
-                                            _ = ticker.tick() => {
-                                                let msg = func(duration);
+                                            _ = shutdown_token.cancelled() => {
+                                                break;
+                                            }
+                                            _ = crate::command::sleep_until_precise(deadline, spin, &shutdown_token) => {
+                                                if shutdown_token.is_cancelled() {
+                                                    break; // The spin was cut short
+                                                }
+                                                if *paused.borrow() {
+                                                    if !crate::command::wait_until_resumed(&mut paused, &cancellation_token).await {
+                                                        break;
+                                                    }
+                                                    deadline = crate::clock::now() + interval;
+                                                    continue;
+                                                }
+                                                let msg = func(crate::clock::now());
                                                 if event_tx.send(msg).is_err() {
                                                     break; // Receiver dropped
                                                 }
+                                                deadline += interval;
+                                                // Skip frames we fell behind on instead of bursting
+                                                let now = crate::clock::now();
+                                                while deadline <= now {
+                                                    deadline += interval;
+                                                }
                                             }
                                         }
                                     }
-                                });
-                                continue; // Don't pass this to the model
+                                }));
                             }
-                        } else if msg.is::<crate::event::BatchCmdMsg>() {
-                            // Handle BatchCmdMsg: spawn all commands concurrently without waiting
-                            if let Ok(batch_cmd_msg) = msg.downcast::<crate::event::BatchCmdMsg>() {
-                                for c in batch_cmd_msg.0 {
-                                    let event_tx = self.event_tx.clone();
-                                    let shutdown_token = self.shutdown_token.clone();
-                                    if let Some(ref monitor) = self.memory_monitor {
-                                        monitor.task_spawned();
-                                    }
-                                    self.task_set.spawn(async move {
+                            continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::CancelWatchMsg>() {
+                            if let Ok(cancel_msg) = msg.downcast::<crate::event::CancelWatchMsg>() {
+                                if let Some(token) = self.active_watchers.remove(&cancel_msg.watch_id) {
+                                    token.cancel();
+                                }
+                            }
+                            continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::SequenceMsgInternal>() {
+                            // Run sequence steps in order, reporting progress before each one
+                            if let Ok(sequence_msg) = msg.downcast::<crate::event::SequenceMsgInternal>() {
+                                let event_tx = self.event_tx.clone();
+                                let shutdown_token = self.shutdown_token.clone();
+                                let tracker = self.command_tracker();
+                                let crate::event::SequenceMsgInternal { steps, on_step } = *sequence_msg;
+                                let steps: Vec<_> = steps.into_iter().map(|step| self.with_clock(step)).collect();
+                                if let Some(ref monitor) = self.memory_monitor {
+                                    monitor.task_spawned();
+                                }
+                                self.task_set.spawn(async move {
+                                    let total = steps.len();
+                                    for (index, step) in steps.into_iter().enumerate() {
+                                        if event_tx.send(on_step(index, total)).is_err() {
+                                            return; // Receiver dropped
+                                        }
+                                        let _in_flight = InFlight::start(tracker.clone());
                                         tokio::select! {
-                                            _ = shutdown_token.cancelled() => {
-                                                // Shutdown requested, don't process command
-                                            }
-                                            result = c => {
+                                            _ = shutdown_token.cancelled() => return,
+                                            result = step => {
                                                 if let Some(msg) = result {
-                                                    let _ = event_tx.send(msg);
+                                                    if event_tx.send(msg).is_err() {
+                                                        return;
+                                                    }
                                                 }
                                             }
                                         }
-                                    });
+                                    }
+                                });
+                            }
+                            continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::BatchCmdMsg>() {
+                            // Handle BatchCmdMsg: spawn all commands concurrently without waiting
+                            if let Ok(batch_cmd_msg) = msg.downcast::<crate::event::BatchCmdMsg>() {
+                                for c in batch_cmd_msg.0 {
+                                    self.spawn_command(c);
                                 }
                             }
                             continue; // We've handled the batch, don't pass it to the model
+                        } else if msg.is::<crate::event::BatchLimitedMsg>() {
+                            if let Ok(batch) = msg.downcast::<crate::event::BatchLimitedMsg>() {
+                                self.spawn_batch_limited(*batch);
+                            }
+                            continue; // Don't pass this to the model
                         } else if msg.is::<crate::event::BatchMsgInternal>() {
                             if let Ok(batch_msg) = msg.downcast::<crate::event::BatchMsgInternal>() {
                                 // Process each message in the batch and accumulate resulting cmds
                                 let mut next_cmds: Vec<crate::command::Cmd> = Vec::new();
-                                for batch_item in batch_msg.messages {
-                                    if batch_item.downcast_ref::<KillMsg>().is_some() {
+                                let mut batch_items = Vec::with_capacity(batch_msg.messages.len());
+                                flatten_batch(batch_msg.messages, &mut batch_items);
+                                for batch_item in batch_items {
+                                    // A batch inside a sequence starts its commands as usual
+                                    let batch_item = match batch_item.downcast::<crate::event::BatchCmdMsg>() {
+                                        Ok(batch_cmd_msg) => {
+                                            for c in batch_cmd_msg.0 {
+                                                self.spawn_command(c);
+                                            }
+                                            continue;
+                                        }
+                                        Err(batch_item) => batch_item,
+                                    };
+                                    let batch_item = match batch_item.downcast::<crate::event::BatchLimitedMsg>() {
+                                        Ok(batch) => {
+                                            self.spawn_batch_limited(*batch);
+                                            continue;
+                                        }
+                                        Err(batch_item) => batch_item,
+                                    };
+                                    let batch_item = self.keep_exit_value(batch_item);
+                                    if let Some(kill) = batch_item.downcast_ref::<KillMsg>() {
                                         // Immediate termination
-                                        break 'main_loop Err(Error::ProgramKilled);
+                                        break 'main_loop Err(Error::ProgramKilled(kill.exit_code));
                                     }
                                     if batch_item.downcast_ref::<QuitMsg>().is_some() {
                                         should_quit = true;
@@ -673,7 +2954,7 @@ impl<M: Model> Program<M> {
                                     if batch_item.downcast_ref::<crate::InterruptMsg>().is_some() {
                                         should_interrupt = true;
                                     }
-                                    if let Some(new_cmd) = model.update(batch_item) {
+                                    if let Some(new_cmd) = model.update(batch_item).into_cmd() {
                                         next_cmds.push(new_cmd);
                                     }
                                 }
@@ -692,6 +2973,53 @@ impl<M: Model> Program<M> {
                                 }
                                 continue; // Don't pass this to the model
                             }
+                        } else if let Some(&crate::event::PauseTimerGroupMsg { group }) =
+                            msg.downcast_ref::<crate::event::PauseTimerGroupMsg>()
+                        {
+                            self.timer_group(group).send_replace(true);
+                            continue; // Don't pass this to the model
+                        } else if let Some(&crate::event::ResumeTimerGroupMsg { group }) =
+                            msg.downcast_ref::<crate::event::ResumeTimerGroupMsg>()
+                        {
+                            self.timer_group(group).send_replace(false);
+                            let (released, held) = std::mem::take(&mut self.held_ticks)
+                                .into_iter()
+                                .partition(|tick| tick.group == group);
+                            self.held_ticks = held;
+                            for tick in released {
+                                let _ = self.event_tx.send(tick.msg);
+                            }
+                            continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::GroupedTickMsg>() {
+                            if let Ok(tick) = msg.downcast::<crate::event::GroupedTickMsg>() {
+                                if *self.timer_group(tick.group).borrow() {
+                                    self.held_ticks.push(*tick);
+                                } else {
+                                    let _ = self.event_tx.send(tick.msg);
+                                }
+                            }
+                            continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::DebounceMsg>() {
+                            if let Ok(debounce) = msg.downcast::<crate::event::DebounceMsg>() {
+                                let crate::event::DebounceMsg { key, delay, msg } = *debounce;
+                                let generation = self.debounces.entry(key).or_default();
+                                *generation += 1;
+                                let generation = *generation;
+                                self.spawn_command(Box::pin(async move {
+                                    crate::clock::sleep(delay).await;
+                                    let fired = crate::event::DebounceFiredMsg { key, generation, msg };
+                                    Some(Box::new(fired) as Msg)
+                                }));
+                            }
+                            continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::DebounceFiredMsg>() {
+                            if let Ok(fired) = msg.downcast::<crate::event::DebounceFiredMsg>() {
+                                // A later call for the key replaces this message
+                                if self.debounces.get(fired.key) == Some(&fired.generation) {
+                                    let _ = self.event_tx.send(fired.msg);
+                                }
+                            }
+                            continue; // Don't pass this to the model
                         } else if msg.is::<crate::event::CancelAllTimersMsg>() {
                             // Cancel all active timers
                             let timer_count = self.active_timers.len();
@@ -705,28 +3033,82 @@ impl<M: Model> Program<M> {
                                 }
                             }
                             continue; // Don't pass this to the model
+                        } else if msg.is::<crate::event::ScreenshotMsg>() {
+                            if let Ok(request) = msg.downcast::<crate::event::ScreenshotMsg>() {
+                                let reply = (request.on_done)(self.last_frame.clone());
+                                let _ = self.event_tx.send(reply);
+                            }
+                            continue; // Don't pass this to the model
                         } else if msg.is::<RequestWindowSizeMsg>() {
-                            if let Some((width, height)) = self
-                                .terminal
-                                .as_ref()
-                                .and_then(|terminal| terminal.size().ok())
-                            {
+                            if let Some((width, height)) = self.window_size() {
                                 let _ = self
                                     .event_tx
                                     .send(Box::new(WindowSizeMsg { width, height }) as Msg);
                             }
                             continue;
+                        } else if msg.is::<crate::event::RequestViewportSizeMsg>() {
+                            if let Some(size) = self.viewport_size_msg() {
+                                self.viewport_report = Some(size);
+                                let _ = self.event_tx.send(Box::new(size) as Msg);
+                            }
+                            continue;
+                        } else if let Some(&SetViewportSizeMsg { width, height }) =
+                            msg.downcast_ref::<SetViewportSizeMsg>()
+                        {
+                            self.viewport_size = Some((width, height));
+                            let _ = self
+                                .event_tx
+                                .send(Box::new(WindowSizeMsg { width, height }) as Msg);
+                            continue;
                         } else {
                             // Handle regular messages
                             let is_quit = msg.downcast_ref::<QuitMsg>().is_some();
                             let is_interrupt = msg.downcast_ref::<crate::InterruptMsg>().is_some();
-                            cmd = model.update(msg);
+                            let is_focus = msg.is::<crate::FocusMsg>();
+                            let undo_step = match (&mut self.undo, msg.downcast_ref::<KeyMsg>()) {
+                                (Some(history), Some(key_msg)) if KeySpec::from("ctrl+z").matches(key_msg) => {
+                                    Some(history.undo())
+                                }
+                                (Some(history), Some(key_msg)) if KeySpec::from("ctrl+y").matches(key_msg) => {
+                                    Some(history.redo())
+                                }
+                                _ => None,
+                            };
+                            if let Some(state) = undo_step {
+                                // Undo and redo replace the model instead of updating it
+                                if let Some(state) = state {
+                                    model = state;
+                                    full_redraw = true;
+                                }
+                            } else {
+                                let record = self.undo.is_some()
+                                    && (KeyMsg::presses(&msg).is_some() || msg.is::<crate::PasteMsg>());
+                                if let Some(history) = self.undo.as_mut().filter(|_| record) {
+                                    if history.is_empty() {
+                                        history.push(&model);
+                                    }
+                                }
+                                cmd = crate::event::with_input_stamp(input_stamp, || {
+                                    model.update(msg).into_cmd()
+                                });
+                                if let Some(history) = self.undo.as_mut().filter(|_| record) {
+                                    history.push(&model);
+                                }
+                            }
                             if is_quit {
                                 should_quit = true;
                             }
                             if is_interrupt {
                                 should_interrupt = true;
                             }
+                            if is_focus {
+                                // Another program may have changed the title
+                                // while the window was in the background
+                                let title = model.title().or_else(|| self.config.title.clone());
+                                if let (Some(terminal), Some(title)) = (&mut self.terminal, title) {
+                                    let _ = terminal.set_window_title(&title).await;
+                                }
+                            }
 
                             // Update memory monitoring
                             if let Some(ref monitor) = self.memory_monitor {
@@ -734,46 +3116,544 @@ impl<M: Model> Program<M> {
                             }
                         }
                         if should_quit {
+                            // Show the state the model quit in, even if its
+                            // frame was skipped
+                            if frame_skipped && !render_paused {
+                                if let Err(err) = self.draw_full_frame(&model).await {
+                                    break Err(self.lose_terminal(&mut model, err).await);
+                                }
+                            }
                             break Ok(model);
                         }
                         if should_interrupt {
                             break Err(Error::Interrupted);
                         }
-                        if let Some(terminal) = &mut self.terminal {
-                            let view = model.view();
-                            terminal.render(&view).await?;
+                        self.report_viewport_size();
+                        // While the terminal lags, only the latest view is drawn
+                        let skip_frame = self.config.frame_coalescing
+                            && behind
+                            && self.terminal.is_some()
+                            && !render_paused
+                            && (!self.event_rx.is_empty() || self.held_msg.is_some());
+                        if skip_frame {
+                            frame_skipped = true;
+                            // The next frame can't rely on damage
+                            full_redraw = true;
+                            self.view_timings.record_skipped();
+                        }
+                        let draw = !render_paused && !skip_frame;
+                        if let Some(terminal) = self.terminal.as_mut().filter(|_| entering_alt_screen) {
+                            if draw {
+                                // Switch in the same write as the frame, and
+                                // inside its synchronized output block, so
+                                // the empty screen is never shown
+                                terminal.set_auto_flush(false);
+                                if terminal.capabilities().synchronized_output {
+                                    let _ = terminal.enter_synchronized_output().await;
+                                }
+                            }
+                            let _ = terminal.enter_alt_screen().await;
+                        }
+                        let area = self.letterbox_area();
+                        let columns = self.screen_columns();
+                        if let Some(terminal) = self.terminal.as_mut().filter(|_| draw) {
+                            frame_skipped = false;
+                            let started = crate::clock::now();
+                            let mut view = timed_view(&model, self.model_size, &self.view_timings, self.config.view_warning_threshold);
+                            if let Some(churn) = &self.frame_churn {
+                                churn.record(&view);
+                            }
+                            let overlay = self.config.debug_input_overlay
+                                .then(|| debug_overlay(self.last_input.as_deref(), self.frame_churn.as_ref()))
+                                .flatten();
+                            if let Some(overlay) = overlay {
+                                view = overlay_last_line(&view, &overlay);
+                            }
+                            let view = letterbox(&view, area);
+                            // The overlay is not part of the model's damage
+                            let damage = model
+                                .damage()
+                                .filter(|_| !full_redraw && !self.config.debug_input_overlay);
+                            let mut rendered = match damage {
+                                Some(rows) => terminal.render_frame_rows(&view, &rows).await,
+                                None => {
+                                    let hint = if full_redraw || self.config.debug_input_overlay {
+                                        RenderHint::Full
+                                    } else {
+                                        match model.render_hint() {
+                                            RenderHint::Region(x, y, width, height) => {
+                                                RenderHint::Region(x.saturating_add(area.map_or(0, |(margin, _)| margin)), y, width, height)
+                                            }
+                                            RenderHint::Full => RenderHint::Full,
+                                        }
+                                    };
+                                    terminal.render_frame(&view, hint).await
+                                }
+                            };
+                            self.view_height = crate::ansi::height(&view, columns);
+                            self.last_frame = view;
+                            full_redraw = false;
+                            if self.config.flush_strategy == FlushStrategy::EveryFrame {
+                                if entering_alt_screen {
+                                    terminal.set_auto_flush(true);
+                                }
+                                if rendered.is_ok() {
+                                    rendered = terminal.flush().await;
+                                }
+                            }
+                            behind = crate::clock::now() - started > frame_budget;
+                            if let Err(err) = self.check_output(rendered) {
+                                break Err(self.lose_terminal(&mut model, err).await);
+                            }
                         }
                     } else {
                         break Err(Error::ChannelReceive);
                     }
                 }
+                _ = async {
+                    match flush_deadline {
+                        Some(deadline) => crate::clock::sleep_until(deadline).await,
+                        None => futures::future::pending::<()>().await,
+                    }
+                }.fuse() => {
+                    // The interval passed without another message
+                    if let Some(terminal) = &mut self.terminal {
+                        let flushed = terminal.flush().await;
+                        if let Err(err) = self.check_output(flushed) {
+                            break Err(self.lose_terminal(&mut model, err).await);
+                        }
+                    }
+                    unflushed = false;
+                    last_flush = crate::clock::now();
+                }
+                _ = async {
+                    match idle_deadline {
+                        Some(deadline) => crate::clock::sleep_until(deadline).await,
+                        None => futures::future::pending::<()>().await,
+                    }
+                }.fuse() => {
+                    idle_sent = true;
+                    let idle = crate::event::IdleMsg { idle_for: crate::clock::now() - last_input_at };
+                    let _ = self.event_tx.send(Box::new(idle) as Msg);
+                }
                 _ = async {
                     if self.config.signal_handler {
-                        tokio::signal::ctrl_c().await.ok();
+                        crate::runtime::ctrl_c().await;
                     } else {
                         futures::future::pending::<()>().await;
                     }
                 }.fuse() => {
                     let _ = self.event_tx.send(Box::new(crate::InterruptMsg));
                 }
+                _ = async {
+                    #[cfg(unix)]
+                    if let Some(hangup) = hangup.as_mut() {
+                        hangup.recv().await;
+                        return;
+                    }
+                    futures::future::pending::<()>().await
+                }.fuse() => {
+                    // A hangup means the terminal is gone, like a failed write
+                    let err = Error::TerminalLost(std::io::Error::new(
+                        std::io::ErrorKind::BrokenPipe,
+                        "received SIGHUP",
+                    ));
+                    break Err(self.lose_terminal(&mut model, err).await);
+                }
             }
         };
+        crate::event::RUNNING_EVENT_LOOPS.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        result
+    }
+
+    /// Undoes the terminal setup of `run_session`: flushes deferred output,
+    /// runs plugin shutdown commands and turns off every mode it enabled.
+    async fn restore_terminal_on_exit(&mut self) {
+        // Write out deferred output, then restore the terminal unbuffered
+        if self.config.flush_strategy != FlushStrategy::EveryFrame {
+            if let Some(terminal) = &mut self.terminal {
+                terminal.set_auto_flush(true);
+                let _ = terminal.flush().await;
+            }
+        }
+
+        // Let plugins undo what they set up before the terminal is restored
+        for shutdown_cmd in self.plugins.shutdown() {
+            let msg = crate::runtime::timeout(PLUGIN_SHUTDOWN_TIMEOUT, shutdown_cmd).await;
+            if let (Some(Some(msg)), Some(terminal)) = (msg, &mut self.terminal) {
+                apply_terminal_mode(terminal.as_mut(), &msg).await;
+            }
+        }
 
         // Restore terminal state on exit
         if let Some(terminal) = &mut self.terminal {
+            // Don't leave temporary titles behind
+            while terminal.window_title_depth() > 0 {
+                let _ = terminal.pop_window_title().await;
+            }
             let _ = terminal.show_cursor().await;
+            let _ = terminal.set_reverse_video(false).await;
             let _ = terminal.disable_mouse().await;
             let _ = terminal.disable_focus_reporting().await;
+            let _ = terminal.disable_bracketed_paste().await;
+            let _ = terminal.disable_keyboard_enhancement().await;
+            self.keyboard_enhancement_active
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+            if self.config.cursor_key_mode.is_some() {
+                let _ = terminal
+                    .set_cursor_key_mode(crate::terminal::CursorKeyMode::Normal)
+                    .await;
+            }
             if self.config.alt_screen {
                 let _ = terminal.exit_alt_screen().await;
             }
             let _ = terminal.exit_raw_mode().await;
         }
+    }
 
-        // Cleanup: cancel all tasks and wait for them to complete
-        self.cleanup_tasks().await;
+    /// Draws the whole view of `model`, returning how long that took.
+    ///
+    /// The frame is flushed with [`FlushStrategy::EveryFrame`] and left
+    /// buffered otherwise. Without a terminal nothing is drawn.
+    async fn draw_full_frame(&mut self, model: &M) -> Result<std::time::Duration, Error> {
+        let area = self.letterbox_area();
+        let columns = self.screen_columns();
+        let Some(terminal) = &mut self.terminal else {
+            return Ok(std::time::Duration::ZERO);
+        };
+        let started = crate::clock::now();
+        let mut view = timed_view(
+            model,
+            self.model_size,
+            &self.view_timings,
+            self.config.view_warning_threshold,
+        );
+        if let Some(churn) = &self.frame_churn {
+            churn.record(&view);
+        }
+        let overlay = self
+            .config
+            .debug_input_overlay
+            .then(|| debug_overlay(self.last_input.as_deref(), self.frame_churn.as_ref()))
+            .flatten();
+        if let Some(overlay) = overlay {
+            view = overlay_last_line(&view, &overlay);
+        }
+        let view = letterbox(&view, area);
+        let mut rendered = terminal.render_frame(&view, RenderHint::Full).await;
+        self.view_height = crate::ansi::height(&view, columns);
+        self.last_frame = view;
+        if rendered.is_ok() && self.config.flush_strategy == FlushStrategy::EveryFrame {
+            rendered = terminal.flush().await;
+        }
+        let elapsed = crate::clock::now() - started;
+        self.check_output(rendered).map(|()| elapsed)
+    }
 
-        result
+    /// Returns the size the model renders for: the
+    /// [`screen_size`](Self::screen_size), with the width capped at
+    /// `max_width`.
+    fn window_size(&self) -> Option<(u16, u16)> {
+        self.screen_size()
+            .map(|(width, height)| (self.capped_width(width), height))
+    }
+
+    /// Returns the terminal's size together with the content area the
+    /// view is drawn in.
+    fn viewport_size_msg(&self) -> Option<crate::event::ViewportSizeMsg> {
+        let (terminal_width, terminal_height) = self.screen_size()?;
+        let mut content_height = match self.config.log_region {
+            // Only the live rows are repainted; the alternate screen has no log
+            Some(live_rows) if !self.in_alt_screen => terminal_height.min(live_rows),
+            _ => terminal_height,
+        };
+        if self.config.debug_input_overlay {
+            content_height = content_height.saturating_sub(1);
+        }
+        Some(crate::event::ViewportSizeMsg {
+            terminal_width,
+            terminal_height,
+            content_width: self.capped_width(terminal_width),
+            content_height,
+        })
+    }
+
+    /// Sends a new `ViewportSizeMsg` if the model has asked for one and
+    /// either size changed since the last.
+    fn report_viewport_size(&mut self) {
+        let Some(last) = self.viewport_report else {
+            return;
+        };
+        if let Some(size) = self.viewport_size_msg().filter(|size| *size != last) {
+            self.viewport_report = Some(size);
+            let _ = self.event_tx.send(Box::new(size) as Msg);
+        }
+    }
+
+    fn capped_width(&self, width: u16) -> u16 {
+        self.config.max_width.map_or(width, |max| width.min(max))
+    }
+
+    /// The blank margin left of each frame and the width it is cut off at,
+    /// which center it when `max_width` is narrower than the screen.
+    fn letterbox_area(&self) -> Option<(u16, u16)> {
+        let max_width = self.config.max_width?;
+        let (width, _) = self.screen_size().filter(|(width, _)| *width > max_width)?;
+        Some(((width - max_width) / 2, max_width))
+    }
+
+    /// Returns the size the program assumes for its terminal: the size set
+    /// with `set_viewport_size` if any, or else the terminal's own size.
+    /// A size with a zero dimension is never reported.
+    fn screen_size(&self) -> Option<(u16, u16)> {
+        self.viewport_size
+            .or_else(|| {
+                self.terminal
+                    .as_ref()
+                    .and_then(|terminal| terminal.size().ok())
+            })
+            .filter(|&(width, height)| width > 0 && height > 0)
+    }
+
+    /// Returns the width frames wrap at, or zero if it is unknown.
+    fn screen_columns(&self) -> usize {
+        self.screen_size()
+            .map_or(0, |(columns, _)| usize::from(columns))
+    }
+
+    /// Checks the result of a write to the terminal.
+    ///
+    /// A transient I/O error only costs the current frame and returns
+    /// `Ok`. An error that means the output is gone, or
+    /// `MAX_OUTPUT_FAILURES` failures in a row, becomes
+    /// `Error::TerminalLost`. Other errors are returned unchanged.
+    fn check_output(&mut self, result: Result<(), Error>) -> Result<(), Error> {
+        match result {
+            Ok(()) => {
+                self.output_failures = 0;
+                Ok(())
+            }
+            Err(Error::Io(err)) => {
+                self.output_failures += 1;
+                if is_output_gone(&err) || self.output_failures >= MAX_OUTPUT_FAILURES {
+                    return Err(Error::TerminalLost(err));
+                }
+                #[cfg(feature = "logging")]
+                log::warn!("Skipped a frame after a failed terminal write: {err}");
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Lets the model react to `Error::TerminalLost` before shutting down.
+    ///
+    /// The model receives a `TerminalLostMsg`, and the command it returns
+    /// runs for up to `TERMINAL_LOST_TIMEOUT` so it can persist state; its
+    /// message is dropped since nothing more is rendered. Other errors are
+    /// returned unchanged.
+    async fn lose_terminal(&mut self, model: &mut M, err: Error) -> Error {
+        if let Error::TerminalLost(source) = &err {
+            let msg = crate::event::TerminalLostMsg {
+                kind: source.kind(),
+                reason: source.to_string(),
+            };
+            if let Some(cmd) = model.update(Box::new(msg)).into_cmd() {
+                let _ = crate::runtime::timeout(TERMINAL_LOST_TIMEOUT, cmd).await;
+            }
+        }
+        err
+    }
+
+    /// Stores the value of a `QuitWithMsg` and replaces it with a plain
+    /// `QuitMsg`; other messages are returned unchanged.
+    fn keep_exit_value(&mut self, msg: Msg) -> Msg {
+        match msg.downcast::<crate::event::QuitWithMsg>() {
+            Ok(quit_with) => {
+                self.exit_value = Some(quit_with.value);
+                Box::new(QuitMsg)
+            }
+            Err(msg) => msg,
+        }
+    }
+
+    /// Spawns a command on the program's task set, delivering its message to
+    /// the event loop when it completes.
+    ///
+    /// A command that is ready on its first poll, like `async { Some(msg) }`,
+    /// is not spawned: its message is queued right away, so it lands behind
+    /// everything already received and in the order commands were issued.
+    ///
+    /// That first poll runs on the event loop. A panic in it is caught so it
+    /// cannot unwind out of `run`; the panic hook reports it just as it
+    /// does for a command that panics on its task.
+    fn spawn_command(&mut self, cmd: crate::command::Cmd) {
+        let mut cmd = std::panic::AssertUnwindSafe(self.with_clock(cmd)).catch_unwind();
+        if let Some(result) = (&mut cmd).now_or_never() {
+            if let Ok(Some(msg)) = result {
+                let _ = self.event_tx.send(msg);
+            }
+            return;
+        }
+        let event_tx = self.event_tx.clone();
+        let shutdown_token = self.shutdown_token.clone();
+        if let Some(ref monitor) = self.memory_monitor {
+            monitor.task_spawned();
+        }
+        let in_flight = InFlight::start(self.command_tracker());
+        self.task_set.spawn(async move {
+            let _in_flight = in_flight;
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {}
+                result = cmd => {
+                    if let Ok(Some(msg)) = result {
+                        let _ = event_tx.send(msg);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Forgets the startup modes in `failures`, so they are not restored or
+    /// turned off later, and tells the model which ones it runs without.
+    fn degrade_modes(&mut self, failures: Vec<(TerminalMode, Error)>) {
+        for (mode, _err) in &failures {
+            #[cfg(feature = "logging")]
+            log::warn!("Running without {mode:?}: {_err}");
+            match mode {
+                TerminalMode::AltScreen => {
+                    self.config.alt_screen = false;
+                    self.in_alt_screen = false;
+                }
+                TerminalMode::Mouse => self.input_modes.mouse_motion = MouseMotion::None,
+                TerminalMode::FocusReporting => self.input_modes.focus_reporting = false,
+                TerminalMode::BracketedPaste => self.input_modes.bracketed_paste = false,
+                TerminalMode::KeyboardEnhancement => self.config.keyboard_enhancement = false,
+            }
+        }
+        let degraded = crate::event::ModesDegradedMsg {
+            modes: self.input_modes,
+            failures: failures
+                .into_iter()
+                .map(|(mode, err)| (mode, err.to_string()))
+                .collect(),
+        };
+        let _ = self.event_tx.send(Box::new(degraded) as Msg);
+    }
+
+    /// Returns the pause state of a timer group, creating it running if the
+    /// group is new.
+    fn timer_group(&mut self, group: u64) -> &tokio::sync::watch::Sender<bool> {
+        self.timer_groups
+            .entry(group)
+            .or_insert_with(|| tokio::sync::watch::channel(false).0)
+    }
+
+    /// Detects the terminal's capabilities again, and tells the model if
+    /// they changed.
+    fn requery_capabilities(&mut self) {
+        if let Some(terminal) = &mut self.terminal {
+            let before = terminal.capabilities();
+            terminal.refresh_capabilities();
+            let after = terminal.capabilities();
+            if after != before {
+                let _ = self
+                    .event_tx
+                    .send(Box::new(crate::event::CapabilitiesMsg(after)) as Msg);
+            }
+        }
+    }
+
+    /// Switches mouse reporting to `motion`, and tells the model if the
+    /// input modes changed.
+    async fn set_mouse_motion(&mut self, motion: MouseMotion) {
+        use crate::event::{DisableMouseMsg, EnableMouseAllMotionMsg, EnableMouseCellMotionMsg};
+        let msg: Msg = match motion {
+            MouseMotion::All => Box::new(EnableMouseAllMotionMsg),
+            MouseMotion::Cell => Box::new(EnableMouseCellMotionMsg),
+            MouseMotion::None => Box::new(DisableMouseMsg),
+        };
+        if let Some(terminal) = &mut self.terminal {
+            apply_terminal_mode(terminal.as_mut(), &msg).await;
+        }
+        if self.input_modes.apply(&msg) {
+            let modes = crate::event::ModesMsg(self.input_modes);
+            let _ = self.event_tx.send(Box::new(modes) as Msg);
+        }
+    }
+
+    /// Restores the mouse mode from before the active capture, if any.
+    async fn end_mouse_capture(&mut self) {
+        if let Some(motion) = self.mouse_capture.take() {
+            self.set_mouse_motion(motion).await;
+        }
+    }
+
+    /// Makes `future`, a command or a timer task, wait on the program's
+    /// clock, if one is set.
+    fn with_clock<F>(&self, future: F) -> futures::future::BoxFuture<'static, F::Output>
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        match &self.config.clock {
+            Some(clock) => Box::pin(crate::clock::scope(Arc::clone(clock), future)),
+            None => Box::pin(future),
+        }
+    }
+
+    /// Returns the monitor that counts in-flight commands, if task tracking
+    /// is enabled.
+    fn command_tracker(&self) -> Option<crate::memory::MemoryMonitor> {
+        self.memory_monitor
+            .clone()
+            .filter(|_| self.config.track_tasks)
+    }
+
+    /// Spawns a task that runs the commands of a `batch_limited`, at most
+    /// `concurrency` at a time.
+    ///
+    /// Each message is sent as soon as its command completes. On shutdown
+    /// the task stops, dropping the running commands and the queued ones.
+    fn spawn_batch_limited(&mut self, batch: crate::event::BatchLimitedMsg) {
+        use futures::StreamExt;
+
+        let event_tx = self.event_tx.clone();
+        let shutdown_token = self.shutdown_token.clone();
+        if let Some(ref monitor) = self.memory_monitor {
+            monitor.task_spawned();
+        }
+        let tracker = self.command_tracker();
+        // Queued commands are only counted once they start
+        let cmds: Vec<crate::command::Cmd> = batch
+            .cmds
+            .into_iter()
+            .map(|cmd| {
+                let tracker = tracker.clone();
+                let cmd = self.with_clock(cmd);
+                Box::pin(async move {
+                    let _in_flight = InFlight::start(tracker);
+                    cmd.await
+                }) as crate::command::Cmd
+            })
+            .collect();
+        self.task_set.spawn(async move {
+            let mut results =
+                futures::stream::iter(cmds).buffer_unordered(batch.concurrency.max(1));
+            loop {
+                tokio::select! {
+                    _ = shutdown_token.cancelled() => return,
+                    result = results.next() => match result {
+                        None => return,
+                        Some(Some(msg)) => {
+                            if event_tx.send(msg).is_err() {
+                                return; // Receiver dropped
+                            }
+                        }
+                        Some(None) => {}
+                    }
+                }
+            }
+        });
     }
 
     /// Clean up all spawned tasks on program shutdown.
@@ -790,14 +3670,17 @@ impl<M: Model> Program<M> {
         // Cancel the shutdown token to signal all tasks to stop
         self.shutdown_token.cancel();
 
-        // Cancel all active timers
+        // Cancel all active timers and watchers
         for (_, token) in self.active_timers.drain() {
             token.cancel();
         }
+        for (_, token) in self.active_watchers.drain() {
+            token.cancel();
+        }
 
         // Wait for all tasks to complete, with a timeout to avoid hanging
         let timeout = std::time::Duration::from_millis(500);
-        let _ = tokio::time::timeout(timeout, async {
+        let _ = crate::runtime::timeout(timeout, async {
             while (self.task_set.join_next().await).is_some() {
                 // Task completed
             }
@@ -820,6 +3703,40 @@ impl<M: Model> Program<M> {
         self.event_tx.clone()
     }
 
+    /// Returns a receiver that follows the program through its lifecycle.
+    ///
+    /// Subscribe before calling [`run`](Self::run), which consumes the
+    /// program. The receiver starts at [`RunState::Initializing`] and ends
+    /// at [`RunState::Finished`], which is reported however the run ends.
+    /// Like any `watch` receiver it only holds the latest state, so a slow
+    /// reader can miss states that were quickly left.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bubbletea_rs::{Model, Msg, Program, RunState};
+    /// # struct Menu;
+    /// # impl Model for Menu {
+    /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (Self, None) }
+    /// #     fn update(&mut self, _msg: Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// # async fn example() -> Result<(), bubbletea_rs::Error> {
+    /// let program = Program::<Menu>::builder().build()?;
+    /// let mut state = program.state();
+    /// tokio::spawn(async move {
+    ///     while state.changed().await.is_ok() {
+    ///         println!("program is {:?}", *state.borrow_and_update());
+    ///     }
+    /// });
+    /// program.run().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn state(&self) -> tokio::sync::watch::Receiver<RunState> {
+        self.run_state.subscribe()
+    }
+
     /// Sends a message to the `Program`'s event loop.
     ///
     /// This is a convenience method that wraps the `sender()` method.
@@ -846,7 +3763,7 @@ impl<M: Model> Program<M> {
     /// # struct MyModel;
     /// # impl Model for MyModel {
     /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
-    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
     /// #     fn view(&self) -> String { String::new() }
     /// # }
     /// # async fn example() -> Result<(), bubbletea_rs::Error> {
@@ -875,7 +3792,7 @@ impl<M: Model> Program<M> {
     /// # struct MyModel;
     /// # impl Model for MyModel {
     /// #     fn init() -> (Self, Option<bubbletea_rs::Cmd>) { (MyModel, None) }
-    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> Option<bubbletea_rs::Cmd> { None }
+    /// #     fn update(&mut self, _: bubbletea_rs::Msg) -> impl bubbletea_rs::IntoCmd {}
     /// #     fn view(&self) -> String { String::new() }
     /// # }
     /// # async fn example() -> Result<(), bubbletea_rs::Error> {
@@ -895,6 +3812,37 @@ impl<M: Model> Program<M> {
         self.memory_monitor.as_ref()
     }
 
+    /// Get the per-type message statistics, if enabled.
+    ///
+    /// The returned handle can be cloned before `run` and read while or
+    /// after the program runs.
+    pub fn message_stats(&self) -> Option<&crate::memory::MessageStats> {
+        self.message_stats.as_ref()
+    }
+
+    /// Get the per-line frame churn records, if enabled.
+    ///
+    /// The returned handle can be cloned before `run` and read while or
+    /// after the program runs.
+    pub fn frame_churn(&self) -> Option<&crate::memory::FrameChurn> {
+        self.frame_churn.as_ref()
+    }
+
+    /// Returns how long `Model::view` has taken so far.
+    ///
+    /// Every `view` call the program makes to draw a frame is timed.
+    pub fn view_stats(&self) -> crate::memory::ViewStats {
+        self.view_timings.stats()
+    }
+
+    /// Returns a handle to the `Model::view` timings.
+    ///
+    /// The handle can be taken before `run` and read while or after the
+    /// program runs, which `view_stats` cannot.
+    pub fn view_timings(&self) -> crate::memory::ViewTimings {
+        self.view_timings.clone()
+    }
+
     /// Get memory usage health information, if monitoring is enabled.
     ///
     /// Returns `None` if memory monitoring is disabled.
@@ -905,9 +3853,12 @@ impl<M: Model> Program<M> {
     /// Sends a `KillMsg` to the `Program`'s event loop, initiating an immediate termination.
     ///
     /// Unlike `quit()`, which performs a graceful shutdown, `kill()` causes the event loop
-    /// to stop as soon as possible and returns `Error::ProgramKilled`.
+    /// to stop as soon as possible and returns `Error::ProgramKilled(1)`. To pass a
+    /// reason or exit code, send a [`KillMsg`] through [`sender`](Self::sender).
     pub fn kill(&self) {
-        let _ = self.event_tx.send(Box::new(KillMsg));
+        let _ = self
+            .event_tx
+            .send(Box::new(KillMsg::by_user("Program::kill".to_string())));
     }
 
     /// Waits for the `Program` to finish execution.
@@ -928,7 +3879,98 @@ impl<M: Model> Program<M> {
         // Since the Program is consumed by run(), we can't really wait for it.
         // This would need a different architecture to implement properly,
         // similar to how Go's context.Context works with goroutines.
-        tokio::task::yield_now().await;
+        crate::runtime::yield_now().await;
+    }
+
+    /// Remembers the `Debug` form of `msg` if it is a decoded input event.
+    fn record_input(&mut self, msg: &Msg) {
+        let input = if let Some(key) = msg.downcast_ref::<KeyMsg>() {
+            format!("{key:?}")
+        } else if let Some(repeat) = msg.downcast_ref::<crate::KeyRepeatMsg>() {
+            format!("{repeat:?}")
+        } else if let Some(mouse) = msg.downcast_ref::<crate::MouseMsg>() {
+            format!("{mouse:?}")
+        } else {
+            return;
+        };
+        self.last_input = Some(input);
+    }
+
+    /// Sends a keyboard enhancement query, unless one is already waiting for
+    /// an answer that will serve both, and starts its timeout.
+    async fn query_keyboard_enhancements(&mut self) {
+        if self.pending_keyboard_enhancement_query.is_some() {
+            return;
+        }
+        self.keyboard_enhancement_query_count += 1;
+        let query = self.keyboard_enhancement_query_count;
+        self.pending_keyboard_enhancement_query = Some(query);
+
+        match &self.keyboard_enhancement_queries {
+            Some(queries) => {
+                let _ = queries.send(());
+            }
+            None => {
+                if let Some(terminal) = &mut self.terminal {
+                    let _ = terminal.query_keyboard_enhancements().await;
+                }
+            }
+        }
+
+        let event_tx = self.event_tx.clone();
+        crate::runtime::spawn(async move {
+            crate::runtime::sleep(KEYBOARD_ENHANCEMENT_QUERY_TIMEOUT).await;
+            let timeout = crate::event::KeyboardEnhancementsTimeoutMsg { query };
+            let _ = event_tx.send(Box::new(timeout) as Msg);
+        });
+    }
+
+    /// Turns the keyboard enhancement protocol off if the program enabled it
+    /// but the terminal reports that its flags were not applied.
+    async fn downgrade_keyboard_enhancement(
+        &mut self,
+        supported_flags: Option<crossterm::event::KeyboardEnhancementFlags>,
+    ) {
+        let applied = supported_flags.is_some_and(|flags| {
+            flags.contains(crossterm::event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        });
+        if self.config.keyboard_enhancement && !applied {
+            // Also keeps `restore_terminal` from asking again
+            self.config.keyboard_enhancement = false;
+            if let Some(terminal) = &mut self.terminal {
+                let _ = terminal.disable_keyboard_enhancement().await;
+            }
+            self.keyboard_enhancement_active
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    /// Hands the terminal to an external process. Frames are dropped and
+    /// recurring timers pause until the matching `end_exec`.
+    async fn begin_exec(&mut self) {
+        self.exec_depth += 1;
+        if self.exec_depth == 1 {
+            let _ = self.release_terminal().await;
+            self.released_terminal = self.terminal.take();
+            self.timers_paused.send_replace(true);
+            self.run_state.send_replace(RunState::ReleasedTerminal);
+        }
+    }
+
+    /// Takes the terminal back after `begin_exec`, returning `true` once the
+    /// last running process has finished and the screen needs a full redraw.
+    async fn end_exec(&mut self) -> bool {
+        self.exec_depth = self.exec_depth.saturating_sub(1);
+        if self.exec_depth > 0 {
+            return false;
+        }
+        if let Some(terminal) = self.released_terminal.take() {
+            self.terminal = Some(terminal);
+            let _ = self.restore_terminal().await;
+        }
+        self.timers_paused.send_replace(false);
+        self.run_state.send_replace(RunState::Running);
+        true
     }
 
     /// Releases control of the terminal.
@@ -941,6 +3983,14 @@ impl<M: Model> Program<M> {
             terminal.exit_alt_screen().await?;
             terminal.disable_mouse().await?;
             terminal.disable_focus_reporting().await?;
+            terminal.disable_keyboard_enhancement().await?;
+            self.keyboard_enhancement_active
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+            if self.config.cursor_key_mode.is_some() {
+                terminal
+                    .set_cursor_key_mode(crate::terminal::CursorKeyMode::Normal)
+                    .await?;
+            }
             terminal.show_cursor().await?;
         }
         Ok(())
@@ -948,26 +3998,35 @@ impl<M: Model> Program<M> {
 
     /// Restores control of the terminal.
     ///
-    /// This method re-initializes the terminal based on the `ProgramConfig`,
-    /// enabling raw mode, entering alternate screen, enabling mouse and focus reporting,
-    /// and hiding the cursor.
+    /// This method re-initializes the terminal based on the `ProgramConfig`
+    /// and the input modes enabled since, enabling raw mode, entering
+    /// alternate screen, enabling mouse and focus reporting, and hiding the
+    /// cursor.
     pub async fn restore_terminal(&mut self) -> Result<(), Error> {
         if let Some(terminal) = &mut self.terminal {
             terminal.enter_raw_mode().await?;
             if self.config.alt_screen {
                 terminal.enter_alt_screen().await?;
             }
-            match self.config.mouse_motion {
+            match self.input_modes.mouse_motion {
                 MouseMotion::Cell => terminal.enable_mouse_cell_motion().await?,
                 MouseMotion::All => terminal.enable_mouse_all_motion().await?,
                 MouseMotion::None => (),
             }
-            if self.config.report_focus {
+            if self.input_modes.focus_reporting {
                 terminal.enable_focus_reporting().await?;
             }
-            if self.config.bracketed_paste {
+            if self.input_modes.bracketed_paste {
                 terminal.enable_bracketed_paste().await?;
             }
+            if self.config.keyboard_enhancement {
+                let active = terminal.enable_keyboard_enhancement().await?;
+                self.keyboard_enhancement_active
+                    .store(active, std::sync::atomic::Ordering::SeqCst);
+            }
+            if let Some(mode) = self.config.cursor_key_mode {
+                terminal.set_cursor_key_mode(mode).await?;
+            }
             terminal.hide_cursor().await?;
         }
         Ok(())
@@ -977,7 +4036,8 @@ impl<M: Model> Program<M> {
     ///
     /// This is useful for debugging or for outputting messages that shouldn't
     /// be part of the managed UI. The output bypasses the normal rendering
-    /// pipeline and goes directly to stdout.
+    /// pipeline and goes directly to the terminal output, with the cursor
+    /// position saved before and restored after.
     ///
     /// # Arguments
     ///
@@ -989,7 +4049,7 @@ impl<M: Model> Program<M> {
     ///
     /// # Errors
     ///
-    /// Returns an `Error` if stdout flushing fails.
+    /// Returns an `Error` if writing to the terminal fails.
     ///
     /// # Warning
     ///
@@ -997,10 +4057,8 @@ impl<M: Model> Program<M> {
     /// the normal UI rendering. It's recommended to use this only for
     /// debugging purposes or when the renderer is disabled.
     pub async fn println(&mut self, s: String) -> Result<(), Error> {
-        if let Some(_terminal) = &mut self.terminal {
-            use std::io::Write;
-            println!("{s}");
-            std::io::stdout().flush()?;
+        if let Some(terminal) = &mut self.terminal {
+            terminal.print_preserving_cursor(&format!("{s}\n")).await?;
         }
         Ok(())
     }
@@ -1009,7 +4067,8 @@ impl<M: Model> Program<M> {
     ///
     /// This is useful for debugging or for outputting messages that shouldn't
     /// be part of the managed UI. The output bypasses the normal rendering
-    /// pipeline and goes directly to stdout without adding a newline.
+    /// pipeline and goes directly to the terminal output without adding a
+    /// newline, with the cursor position saved before and restored after.
     ///
     /// # Arguments
     ///
@@ -1021,7 +4080,7 @@ impl<M: Model> Program<M> {
     ///
     /// # Errors
     ///
-    /// Returns an `Error` if stdout flushing fails.
+    /// Returns an `Error` if writing to the terminal fails.
     ///
     /// # Warning
     ///
@@ -1029,11 +4088,236 @@ impl<M: Model> Program<M> {
     /// the normal UI rendering. It's recommended to use this only for
     /// debugging purposes or when the renderer is disabled.
     pub async fn printf(&mut self, s: String) -> Result<(), Error> {
-        if let Some(_terminal) = &mut self.terminal {
-            use std::io::Write;
-            print!("{s}");
-            std::io::stdout().flush()?;
+        if let Some(terminal) = &mut self.terminal {
+            terminal.print_preserving_cursor(&s).await?;
         }
         Ok(())
     }
 }
+
+/// The outcome of handing one message to a [`HeadlessRunner`].
+pub struct ProcessResult {
+    /// The model's view after the update.
+    pub view: String,
+    /// Commands returned by the update. They have not been run; pass them
+    /// to [`HeadlessRunner::run_cmd`] to run them.
+    pub cmds: Vec<crate::command::Cmd>,
+    /// Whether the message would have ended a running program, as
+    /// `QuitMsg`, `QuitWithMsg`, `InterruptMsg` and `KillMsg` do.
+    pub quit: bool,
+}
+
+impl std::fmt::Debug for ProcessResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessResult")
+            .field("view", &self.view)
+            .field("cmds", &self.cmds.len())
+            .field("quit", &self.quit)
+            .finish()
+    }
+}
+
+/// Drives a model one message at a time, without a terminal or event loop.
+///
+/// Unlike [`Program`], the runner never dispatches commands on its own:
+/// [`process`](Self::process) hands back the commands an update returned,
+/// and the test decides which of them to run with
+/// [`run_cmd`](Self::run_cmd) and which resulting messages to feed back in.
+/// This makes the whole message and command lifecycle deterministic.
+///
+/// Commands are run to completion on a private single-threaded Tokio
+/// runtime with `Runtime::block_on`, or on the built-in executor when only
+/// `sync-runtime` is enabled, so the runner must be used from synchronous
+/// code such as a plain `#[test]`, not from inside another runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg};
+/// use bubbletea_rs::program::HeadlessRunner;
+///
+/// #[derive(Debug)]
+/// struct Loaded(u32);
+///
+/// struct Loader {
+///     value: Option<u32>,
+/// }
+///
+/// impl Model for Loader {
+///     fn init() -> (Self, Option<Cmd>) {
+///         let load: Cmd = Box::pin(async { Some(Box::new(Loaded(42)) as Msg) });
+///         (Self { value: None }, Some(load))
+///     }
+///     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+///         if let Some(Loaded(value)) = msg.downcast_ref::<Loaded>() {
+///             self.value = Some(*value);
+///             return Some(command::quit());
+///         }
+///         None
+///     }
+///     fn view(&self) -> String {
+///         format!("{:?}", self.value)
+///     }
+/// }
+///
+/// let mut runner = HeadlessRunner::<Loader>::new().unwrap();
+/// for cmd in runner.take_init_cmds() {
+///     for msg in runner.run_cmd(cmd) {
+///         let result = runner.process(msg);
+///         assert_eq!(result.view, "Some(42)");
+///         assert_eq!(result.cmds.len(), 1);
+///     }
+/// }
+/// ```
+pub struct HeadlessRunner<M: Model> {
+    model: M,
+    /// The size in the last `WindowSizeMsg` processed, for `Model::view_sized`
+    size: (u16, u16),
+    init_cmds: Vec<crate::command::Cmd>,
+    #[cfg(feature = "tokio-runtime")]
+    runtime: tokio::runtime::Runtime,
+}
+
+impl<M: Model> HeadlessRunner<M> {
+    /// Creates a runner for a freshly initialized model.
+    ///
+    /// The model is created with [`Model::try_init`]; its initial command,
+    /// if any, is kept for [`take_init_cmds`](Self::take_init_cmds).
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `Model::try_init`, or `Error::Io` if the
+    /// runtime cannot be created.
+    pub fn new() -> Result<Self, Error> {
+        let (model, init_cmd) = M::try_init()?;
+        Self::with_model(model, init_cmd.into_iter().collect())
+    }
+
+    /// Creates a runner for an existing model, skipping `init`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Io` if the runtime cannot be created.
+    pub fn from_model(model: M) -> Result<Self, Error> {
+        Self::with_model(model, Vec::new())
+    }
+
+    fn with_model(model: M, init_cmds: Vec<crate::command::Cmd>) -> Result<Self, Error> {
+        Ok(Self {
+            model,
+            size: (0, 0),
+            init_cmds,
+            #[cfg(feature = "tokio-runtime")]
+            runtime: tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?,
+        })
+    }
+
+    /// Takes the commands returned by `init`, leaving none behind.
+    pub fn take_init_cmds(&mut self) -> Vec<crate::command::Cmd> {
+        std::mem::take(&mut self.init_cmds)
+    }
+
+    /// Hands `msg` to the model and returns the view and commands that
+    /// resulted.
+    ///
+    /// `KillMsg` ends a program without reaching the model, so it is only
+    /// reported as `quit`; every other message is passed to
+    /// `Model::update`. Messages the program would handle itself, such as
+    /// `ClearScreenMsg`, reach the model here.
+    pub fn process(&mut self, msg: Msg) -> ProcessResult {
+        let quit = msg.is::<QuitMsg>()
+            || msg.is::<crate::event::QuitWithMsg>()
+            || msg.is::<crate::InterruptMsg>()
+            || msg.is::<KillMsg>();
+        if let Some(size) = msg.downcast_ref::<WindowSizeMsg>() {
+            self.size = (size.width, size.height);
+        }
+        let cmds = if msg.is::<KillMsg>() {
+            Vec::new()
+        } else {
+            self.model.update(msg).into_cmd().into_iter().collect()
+        };
+        ProcessResult {
+            view: self.model.view_sized(self.size.0, self.size.1),
+            cmds,
+            quit,
+        }
+    }
+
+    /// Runs `cmd` to completion and returns the messages it produced.
+    ///
+    /// Batches and sequences are unpacked the way the program would
+    /// deliver them: a `batch` or `batch_limited` yields the messages of
+    /// each of its commands in order, and a `sequence_with_progress` yields
+    /// each progress message followed by that step's messages. Other internal messages,
+    /// such as the one that starts an `every` timer, are returned as is.
+    pub fn run_cmd(&mut self, cmd: crate::command::Cmd) -> Vec<Msg> {
+        let mut msgs = Vec::new();
+        #[cfg(feature = "tokio-runtime")]
+        let msg = self.runtime.block_on(cmd);
+        #[cfg(not(feature = "tokio-runtime"))]
+        let msg = crate::runtime::block_on(cmd);
+        if let Some(msg) = msg {
+            self.unpack(msg, &mut msgs);
+        }
+        msgs
+    }
+
+    fn unpack(&mut self, msg: Msg, msgs: &mut Vec<Msg>) {
+        let msg = match msg.downcast::<crate::event::BatchCmdMsg>() {
+            Ok(batch) => {
+                for cmd in batch.0 {
+                    msgs.extend(self.run_cmd(cmd));
+                }
+                return;
+            }
+            Err(msg) => msg,
+        };
+        let msg = match msg.downcast::<crate::event::BatchLimitedMsg>() {
+            Ok(batch) => {
+                for cmd in batch.cmds {
+                    msgs.extend(self.run_cmd(cmd));
+                }
+                return;
+            }
+            Err(msg) => msg,
+        };
+        let msg = match msg.downcast::<crate::event::BatchMsgInternal>() {
+            Ok(batch) => {
+                for item in batch.messages {
+                    self.unpack(item, msgs);
+                }
+                return;
+            }
+            Err(msg) => msg,
+        };
+        match msg.downcast::<crate::event::SequenceMsgInternal>() {
+            Ok(sequence) => {
+                let crate::event::SequenceMsgInternal { steps, on_step } = *sequence;
+                let total = steps.len();
+                for (index, step) in steps.into_iter().enumerate() {
+                    msgs.push(on_step(index, total));
+                    msgs.extend(self.run_cmd(step));
+                }
+            }
+            Err(msg) => msgs.push(msg),
+        }
+    }
+
+    /// Returns the model.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Returns the model mutably, for setting up state between steps.
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    /// Consumes the runner, returning the model.
+    pub fn into_model(self) -> M {
+        self.model
+    }
+}
@@ -0,0 +1,347 @@
+//! Animated progress bar component.
+//!
+//! [`AnimatedBar`] renders a horizontal bar with a gradient or solid colored
+//! fill and eases between percentages instead of jumping. Setting a new
+//! percentage returns the command that starts the animation; each frame
+//! message is then passed back to [`AnimatedBar::handle_frame_msg`], which
+//! advances the bar and schedules the next frame until it lands on the
+//! target.
+//!
+//! Like [`FrameAnimator`](crate::animation::FrameAnimator), each bar has a
+//! unique id carried by its frame messages, so several bars can animate in
+//! the same model and every message can safely be passed to every bar.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bubbletea_rs::progress::AnimatedBar;
+//! use bubbletea_rs::{Cmd, IntoCmd, Model, Msg};
+//!
+//! #[derive(Debug)]
+//! struct Downloaded(f64);
+//!
+//! struct Download {
+//!     bar: AnimatedBar,
+//! }
+//!
+//! impl Model for Download {
+//!     fn init() -> (Self, Option<Cmd>) {
+//!         (Self { bar: AnimatedBar::new(40) }, None)
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg) -> impl IntoCmd {
+//!         if let Some(Downloaded(fraction)) = msg.downcast_ref::<Downloaded>() {
+//!             return self.bar.set_percent(*fraction);
+//!         }
+//!         self.bar.handle_frame_msg(&msg)
+//!     }
+//!
+//!     fn view(&self) -> String {
+//!         self.bar.view()
+//!     }
+//! }
+//! ```
+
+use crate::command::{tick, Cmd};
+use crate::easing::{ease_out_cubic, EasingFn, Tween};
+use crate::event::{next_timer_id, Msg};
+use crate::gradient::{
+    charm_default_gradient, gradient_filled_segment_with_buffer, gradient_filled_segment_with_spec,
+    GradientSpec,
+};
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// Interval between animation frames (~60fps).
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Default time it takes the bar to ease to a new percentage.
+const DEFAULT_ANIMATION_DURATION: Duration = Duration::from_millis(500);
+
+/// An animation frame message produced by an [`AnimatedBar`].
+///
+/// Models normally don't need to inspect this message; pass it to
+/// [`AnimatedBar::handle_frame_msg`] instead.
+#[derive(Debug, Clone)]
+pub struct ProgressFrameMsg {
+    /// The id of the bar that scheduled this frame.
+    pub id: u64,
+}
+
+/// How the filled part of an [`AnimatedBar`] is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarColor {
+    /// A gradient from the first color at the left edge of the fill to the
+    /// second color at its right edge.
+    Gradient((u8, u8, u8), (u8, u8, u8)),
+    /// A single foreground color.
+    Solid((u8, u8, u8)),
+}
+
+impl Default for BarColor {
+    /// Charm's default pink-to-yellow gradient.
+    fn default() -> Self {
+        let (start, end) = charm_default_gradient();
+        BarColor::Gradient(start, end)
+    }
+}
+
+/// The rendered filled segment of the last frame.
+///
+/// During an animation the filled width only changes every few frames, so
+/// the escape sequences are rebuilt only when it does.
+#[derive(Debug, Default)]
+struct SegmentCache {
+    filled_width: Option<usize>,
+    segment: String,
+}
+
+/// A progress bar that eases between percentages.
+///
+/// The bar is configured with builder methods and defaults to 40 cells of
+/// `█` and `░`, Charm's default gradient, a percentage suffix, and a 500ms
+/// ease-out-cubic animation.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::progress::AnimatedBar;
+///
+/// let mut bar = AnimatedBar::new(10)
+///     .with_chars('#', '-')
+///     .with_solid_color((0, 200, 0));
+///
+/// // Starts the animation; pass the resulting frame messages back to the bar
+/// let cmd = bar.set_percent(0.5);
+/// assert!(cmd.is_some());
+/// assert_eq!(bar.target_percent(), 0.5);
+/// assert_eq!(bar.percent(), 0.0);
+/// assert!(bar.view().ends_with("----------   0.0%"));
+/// ```
+#[derive(Debug)]
+pub struct AnimatedBar {
+    id: u64,
+    width: usize,
+    filled_char: char,
+    empty_char: char,
+    color: BarColor,
+    show_percentage: bool,
+    duration: Duration,
+    easing: EasingFn,
+    tween: Tween,
+    frame_pending: bool,
+    cache: RefCell<SegmentCache>,
+}
+
+impl AnimatedBar {
+    /// Creates an empty bar `width` cells wide.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of cells the bar occupies, excluding the
+    ///   percentage suffix
+    pub fn new(width: usize) -> Self {
+        Self {
+            id: next_timer_id(),
+            width,
+            filled_char: '█',
+            empty_char: '░',
+            color: BarColor::default(),
+            show_percentage: true,
+            duration: DEFAULT_ANIMATION_DURATION,
+            easing: ease_out_cubic,
+            tween: Tween::new(0.0, 0.0, Duration::ZERO),
+            frame_pending: false,
+            cache: RefCell::default(),
+        }
+    }
+
+    /// Sets the characters used for the filled and empty parts of the bar.
+    pub fn with_chars(mut self, filled: char, empty: char) -> Self {
+        self.filled_char = filled;
+        self.empty_char = empty;
+        self.cache = RefCell::default();
+        self
+    }
+
+    /// Colors the filled part with a gradient from `start` to `end`.
+    pub fn with_gradient(mut self, start: (u8, u8, u8), end: (u8, u8, u8)) -> Self {
+        self.color = BarColor::Gradient(start, end);
+        self.cache = RefCell::default();
+        self
+    }
+
+    /// Colors the filled part with a single color.
+    pub fn with_solid_color(mut self, color: (u8, u8, u8)) -> Self {
+        self.color = BarColor::Solid(color);
+        self.cache = RefCell::default();
+        self
+    }
+
+    /// Sets whether [`view`](Self::view) appends the percentage, formatted
+    /// as `" 42.0%"`. Enabled by default.
+    pub fn with_percentage(mut self, show: bool) -> Self {
+        self.show_percentage = show;
+        self
+    }
+
+    /// Sets how long the bar takes to reach a new percentage, and the
+    /// easing function used on the way.
+    ///
+    /// A zero `duration` makes percentage changes take effect immediately,
+    /// without scheduling any frames.
+    pub fn with_animation(mut self, duration: Duration, easing: EasingFn) -> Self {
+        self.duration = duration;
+        self.easing = easing;
+        self
+    }
+
+    /// Returns the unique id carried by this bar's frame messages.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the width of the bar in cells.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Changes the width of the bar, for example after a `WindowSizeMsg`.
+    pub fn set_width(&mut self, width: usize) {
+        self.width = width;
+    }
+
+    /// Returns the percentage currently displayed, from `0.0` to `1.0`.
+    ///
+    /// Easing functions that overshoot, such as elastic ones, are clamped
+    /// to the bar's range.
+    pub fn percent(&self) -> f64 {
+        self.tween.value().clamp(0.0, 1.0)
+    }
+
+    /// Returns the percentage the bar is animating toward.
+    pub fn target_percent(&self) -> f64 {
+        self.tween.target()
+    }
+
+    /// Returns whether the bar is still easing toward its target.
+    pub fn is_animating(&self) -> bool {
+        !self.tween.is_finished()
+    }
+
+    /// Sets the percentage to animate toward.
+    ///
+    /// The animation starts from the percentage currently displayed, so
+    /// calling this mid-animation changes course smoothly.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - The new percentage, clamped to `0.0..=1.0`
+    ///
+    /// # Returns
+    ///
+    /// The command delivering the first frame, or `None` if there is nothing
+    /// to animate or a frame is already scheduled.
+    pub fn set_percent(&mut self, percent: f64) -> Option<Cmd> {
+        let target = percent.clamp(0.0, 1.0);
+        let current = self.percent();
+        if current == target {
+            self.tween = Tween::new(target, target, Duration::ZERO);
+            return None;
+        }
+
+        self.tween = Tween::new(current, target, self.duration).with_easing(self.easing);
+        if self.tween.is_finished() {
+            return None;
+        }
+        self.schedule_frame()
+    }
+
+    /// Moves the target percentage by `amount`, which may be negative.
+    ///
+    /// See [`set_percent`](Self::set_percent) for the returned command.
+    pub fn incr_percent(&mut self, amount: f64) -> Option<Cmd> {
+        self.set_percent(self.target_percent() + amount)
+    }
+
+    /// Advances the animation if `msg` is one of this bar's frame messages.
+    ///
+    /// Messages of any other type, and frames of other bars, are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The command delivering the next frame, or `None` once the bar has
+    /// reached its target or if the message was not for this bar.
+    pub fn handle_frame_msg(&mut self, msg: &Msg) -> Option<Cmd> {
+        match msg.downcast_ref::<ProgressFrameMsg>() {
+            Some(frame) if frame.id == self.id => {}
+            _ => return None,
+        }
+        self.frame_pending = false;
+        self.tween.advance(FRAME_INTERVAL);
+        if self.tween.is_finished() {
+            None
+        } else {
+            self.schedule_frame()
+        }
+    }
+
+    /// Renders the bar at the percentage currently displayed.
+    pub fn view(&self) -> String {
+        let percent = self.percent();
+        let filled_width = (self.width as f64 * percent).round() as usize;
+        let empty_width = self.width.saturating_sub(filled_width);
+
+        let mut cache = self.cache.borrow_mut();
+        if cache.filled_width != Some(filled_width) {
+            self.render_segment(filled_width, &mut cache.segment);
+            cache.filled_width = Some(filled_width);
+        }
+
+        let mut out = String::with_capacity(cache.segment.len() + empty_width * 3 + 7);
+        out.push_str(&cache.segment);
+        out.extend(std::iter::repeat_n(self.empty_char, empty_width));
+        if self.show_percentage {
+            let _ = write!(out, " {:5.1}%", percent * 100.0);
+        }
+        out
+    }
+
+    /// Schedules the next frame unless one is already on its way.
+    fn schedule_frame(&mut self) -> Option<Cmd> {
+        if self.frame_pending {
+            return None;
+        }
+        self.frame_pending = true;
+        let id = self.id;
+        Some(tick(FRAME_INTERVAL, move |_| {
+            Box::new(ProgressFrameMsg { id }) as Msg
+        }))
+    }
+
+    /// Renders `filled_width` colored cells into `buffer`.
+    fn render_segment(&self, filled_width: usize, buffer: &mut String) {
+        match self.color {
+            BarColor::Gradient(start, end) if (start, end) == charm_default_gradient() => {
+                gradient_filled_segment_with_buffer(filled_width, self.filled_char, buffer);
+            }
+            BarColor::Gradient(start, end) => {
+                let stops = [start, end];
+                *buffer = gradient_filled_segment_with_spec(
+                    filled_width,
+                    self.filled_char,
+                    &GradientSpec::new(&stops),
+                );
+            }
+            BarColor::Solid((r, g, b)) => {
+                buffer.clear();
+                if filled_width > 0 {
+                    let _ = write!(buffer, "\x1b[38;2;{r};{g};{b}m");
+                    buffer.extend(std::iter::repeat_n(self.filled_char, filled_width));
+                    buffer.push_str("\x1b[0m");
+                }
+            }
+        }
+    }
+}
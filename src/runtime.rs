@@ -0,0 +1,559 @@
+//! The runtime a program's tasks, timers and blocking work run on.
+//!
+//! [`Program::run`](crate::Program::run) runs on the tokio runtime it is
+//! awaited in. With the `sync-runtime` feature,
+//! [`Program::run_blocking`](crate::Program::run_blocking) needs no tokio
+//! runtime at all: the event loop runs on the calling thread, and commands,
+//! timers and blocking work run on the small executor in this module.
+//!
+//! Every function here uses tokio when it is called inside a tokio runtime
+//! and the built-in executor otherwise. Without `sync-runtime` tokio is the
+//! only choice, and without `tokio-runtime` the built-in executor is.
+//! tokio's channels, `select!` and I/O traits work on either, so only the
+//! parts that need tokio's scheduler or drivers go through this module.
+
+use futures::future::{FutureExt, RemoteHandle};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+#[cfg(feature = "sync-runtime")]
+use std::pin::Pin;
+#[cfg(feature = "sync-runtime")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Which runtime the caller is on.
+enum Flavor {
+    #[cfg(feature = "tokio-runtime")]
+    Tokio,
+    #[cfg(feature = "sync-runtime")]
+    Builtin,
+}
+
+#[cfg(all(feature = "tokio-runtime", feature = "sync-runtime"))]
+fn flavor() -> Flavor {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        Flavor::Tokio
+    } else {
+        Flavor::Builtin
+    }
+}
+
+#[cfg(all(feature = "tokio-runtime", not(feature = "sync-runtime")))]
+fn flavor() -> Flavor {
+    Flavor::Tokio
+}
+
+#[cfg(all(feature = "sync-runtime", not(feature = "tokio-runtime")))]
+fn flavor() -> Flavor {
+    Flavor::Builtin
+}
+
+/// Returns whether the caller is on tokio rather than the built-in
+/// executor.
+#[cfg(feature = "tokio-runtime")]
+pub(crate) fn on_tokio() -> bool {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => true,
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => false,
+    }
+}
+
+/// Runs `future` in the background.
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => {
+            tokio::spawn(future);
+        }
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => executor::spawn(future),
+    }
+}
+
+/// Runs `future` to completion on the calling thread, with tasks it spawns
+/// on the built-in executor.
+#[cfg(feature = "sync-runtime")]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    futures::executor::block_on(future)
+}
+
+/// Runs `f` on a thread where it may block.
+///
+/// Returns `None` if the runtime shut down before `f` ran, and the panic
+/// payload if `f` panicked.
+pub(crate) async fn spawn_blocking<F, T>(f: F) -> Option<std::thread::Result<T>>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => match tokio::task::spawn_blocking(f).await {
+            Ok(value) => Some(Ok(value)),
+            Err(join_error) => join_error.try_into_panic().ok().map(Err),
+        },
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => {
+            let (tx, rx) = futures::channel::oneshot::channel();
+            std::thread::Builder::new()
+                .name("bubbletea-blocking".to_string())
+                .spawn(move || {
+                    let _ = tx.send(std::panic::catch_unwind(AssertUnwindSafe(f)));
+                })
+                .ok()?;
+            rx.await.ok()
+        }
+    }
+}
+
+/// Returns the current time, as tokio sees it when on tokio.
+pub(crate) fn now() -> Instant {
+    // Without a runtime this is the system time, so it suits both
+    tokio::time::Instant::now().into_std()
+}
+
+/// Sleeps for `duration`.
+pub(crate) async fn sleep(duration: Duration) {
+    sleep_until(now() + duration).await;
+}
+
+/// Sleeps until `deadline`.
+pub(crate) async fn sleep_until(deadline: Instant) {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => tokio::time::sleep_until(deadline.into()).await,
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => executor::Sleep::new(deadline).await,
+    }
+}
+
+/// Runs `future` for at most `duration`, returning `None` if it did not
+/// finish in time.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    timeout_at(now() + duration, future).await
+}
+
+/// Runs `future` until `deadline` at the latest, returning `None` if it did
+/// not finish in time.
+pub(crate) async fn timeout_at<F: Future>(deadline: Instant, future: F) -> Option<F::Output> {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => tokio::time::timeout_at(deadline.into(), future).await.ok(),
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => {
+            let future = std::pin::pin!(future);
+            match futures::future::select(future, executor::Sleep::new(deadline)).await {
+                futures::future::Either::Left((output, _)) => Some(output),
+                futures::future::Either::Right(_) => None,
+            }
+        }
+    }
+}
+
+/// Lets other tasks run before continuing.
+pub(crate) async fn yield_now() {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => tokio::task::yield_now().await,
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => YieldNow(false).await,
+    }
+}
+
+/// Returns `Pending` once, waking itself so the task is polled again after
+/// the tasks already queued.
+#[cfg(feature = "sync-runtime")]
+struct YieldNow(bool);
+
+#[cfg(feature = "sync-runtime")]
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if std::mem::replace(&mut self.0, true) {
+            return Poll::Ready(());
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Runs `command` and waits for it to exit.
+pub(crate) async fn status(
+    command: std::process::Command,
+) -> std::io::Result<std::process::ExitStatus> {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => tokio::process::Command::from(command).status().await,
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => {
+            let mut command = command;
+            blocking_io(move || command.status()).await
+        }
+    }
+}
+
+/// Runs `command` and collects its output.
+pub(crate) async fn output(
+    command: std::process::Command,
+) -> std::io::Result<std::process::Output> {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => tokio::process::Command::from(command).output().await,
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => {
+            let mut command = command;
+            blocking_io(move || command.output()).await
+        }
+    }
+}
+
+/// Writes `contents` to the file at `path`, replacing what it held.
+pub(crate) async fn write_file(path: std::path::PathBuf, contents: Vec<u8>) -> std::io::Result<()> {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => tokio::fs::write(path, contents).await,
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => blocking_io(move || std::fs::write(path, contents)).await,
+    }
+}
+
+/// Runs blocking I/O with [`spawn_blocking`], reporting a panic or a lost
+/// thread as an I/O error.
+#[cfg(feature = "sync-runtime")]
+async fn blocking_io<F, T>(f: F) -> std::io::Result<T>
+where
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    match spawn_blocking(f).await {
+        Some(Ok(result)) => result,
+        Some(Err(_)) | None => Err(std::io::Error::other("blocking I/O did not complete")),
+    }
+}
+
+/// Completes when the process receives Ctrl+C, or at once if it cannot
+/// listen for it.
+///
+/// Signals are only handled on tokio; on the built-in executor this never
+/// completes.
+pub(crate) async fn ctrl_c() {
+    match flavor() {
+        #[cfg(feature = "tokio-runtime")]
+        Flavor::Tokio => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+        #[cfg(feature = "sync-runtime")]
+        Flavor::Builtin => std::future::pending().await,
+    }
+}
+
+/// The process's `SIGHUP` signals.
+///
+/// Like [`ctrl_c`], only available on tokio.
+#[cfg(unix)]
+pub(crate) struct Hangup {
+    #[cfg(feature = "tokio-runtime")]
+    signal: tokio::signal::unix::Signal,
+}
+
+#[cfg(unix)]
+impl Hangup {
+    /// Starts listening for `SIGHUP`, if the runtime can.
+    pub(crate) fn new() -> Option<Self> {
+        match flavor() {
+            #[cfg(feature = "tokio-runtime")]
+            Flavor::Tokio => {
+                let kind = tokio::signal::unix::SignalKind::hangup();
+                tokio::signal::unix::signal(kind)
+                    .ok()
+                    .map(|signal| Self { signal })
+            }
+            #[cfg(feature = "sync-runtime")]
+            Flavor::Builtin => None,
+        }
+    }
+
+    /// Waits for the next `SIGHUP`.
+    pub(crate) async fn recv(&mut self) {
+        #[cfg(feature = "tokio-runtime")]
+        self.signal.recv().await;
+        #[cfg(not(feature = "tokio-runtime"))]
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Tasks that are cancelled together, like a tokio `JoinSet` that works on
+/// either runtime.
+pub(crate) struct TaskSet {
+    tasks: FuturesUnordered<RemoteHandle<()>>,
+}
+
+impl TaskSet {
+    pub(crate) fn new() -> Self {
+        Self {
+            tasks: FuturesUnordered::new(),
+        }
+    }
+
+    /// Runs `future` in the background as part of this set.
+    ///
+    /// A panic ends only the task; the set never sees it.
+    pub(crate) fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let (task, handle) = AssertUnwindSafe(future)
+            .catch_unwind()
+            .map(|_| ())
+            .remote_handle();
+        spawn(task);
+        self.tasks.push(handle);
+    }
+
+    /// Waits for one of the tasks to finish, returning `None` if none are
+    /// left.
+    pub(crate) async fn join_next(&mut self) -> Option<()> {
+        self.tasks.next().await
+    }
+
+    /// Cancels every task still running.
+    pub(crate) fn abort_all(&mut self) {
+        self.tasks.clear();
+    }
+}
+
+/// The built-in executor: a fixed pool of worker threads polling tasks, and
+/// a timer thread waking sleeps.
+#[cfg(feature = "sync-runtime")]
+mod executor {
+    use futures::task::{waker_ref, ArcWake};
+    use std::collections::{BTreeMap, VecDeque};
+    use std::future::Future;
+    use std::panic::AssertUnwindSafe;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Condvar, Mutex, OnceLock};
+    use std::task::{Context, Poll, Waker};
+    use std::time::Instant;
+
+    type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// Runs `future` on the worker pool.
+    pub(super) fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+        let task = Arc::new(Spawned {
+            future: Mutex::new(Some(Box::pin(future))),
+            queued: AtomicBool::new(true),
+        });
+        pool().push(task);
+    }
+
+    /// A spawned task, queued again each time it is woken.
+    struct Spawned {
+        /// `None` once the task finished or panicked
+        future: Mutex<Option<Task>>,
+        /// Whether the task is waiting in the queue, so a task woken many
+        /// times is queued once
+        queued: AtomicBool,
+    }
+
+    impl Spawned {
+        fn poll(self: &Arc<Self>) {
+            let mut slot = self.future.lock().unwrap();
+            // Wakes from here on queue the task again
+            self.queued.store(false, Ordering::SeqCst);
+            let Some(future) = slot.as_mut() else {
+                return;
+            };
+            let waker = waker_ref(self);
+            let mut cx = Context::from_waker(&waker);
+            // A panic ends the task; the panic hook has already reported it
+            let result =
+                std::panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx)));
+            if !matches!(result, Ok(Poll::Pending)) {
+                *slot = None;
+            }
+        }
+    }
+
+    impl ArcWake for Spawned {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            if !arc_self.queued.swap(true, Ordering::SeqCst) {
+                pool().push(Arc::clone(arc_self));
+            }
+        }
+    }
+
+    struct Pool {
+        queue: Mutex<VecDeque<Arc<Spawned>>>,
+        ready: Condvar,
+    }
+
+    impl Pool {
+        fn push(&self, task: Arc<Spawned>) {
+            self.queue.lock().unwrap().push_back(task);
+            self.ready.notify_one();
+        }
+
+        fn work(&self) {
+            loop {
+                let task = {
+                    let mut queue = self.queue.lock().unwrap();
+                    loop {
+                        match queue.pop_front() {
+                            Some(task) => break task,
+                            None => queue = self.ready.wait(queue).unwrap(),
+                        }
+                    }
+                };
+                task.poll();
+            }
+        }
+    }
+
+    /// Returns the worker pool, starting its threads on first use.
+    fn pool() -> &'static Pool {
+        static POOL: OnceLock<Pool> = OnceLock::new();
+        static STARTED: std::sync::Once = std::sync::Once::new();
+        let pool = POOL.get_or_init(|| Pool {
+            queue: Mutex::new(VecDeque::new()),
+            ready: Condvar::new(),
+        });
+        STARTED.call_once(|| {
+            let workers = std::thread::available_parallelism().map_or(2, |n| n.get().max(2));
+            for _ in 0..workers {
+                std::thread::Builder::new()
+                    .name("bubbletea-worker".to_string())
+                    .spawn(move || pool.work())
+                    .expect("failed to start an executor thread");
+            }
+        });
+        pool
+    }
+
+    /// A sleep on the timer thread.
+    pub(super) struct Sleep {
+        deadline: Instant,
+        /// Set once the sleep is registered with the timer thread
+        id: Option<u64>,
+    }
+
+    impl Sleep {
+        pub(super) fn new(deadline: Instant) -> Self {
+            Self { deadline, id: None }
+        }
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if Instant::now() >= self.deadline {
+                return Poll::Ready(());
+            }
+            let timers = timers();
+            let mut state = timers.state.lock().unwrap();
+            let id = match self.id {
+                Some(id) => id,
+                None => {
+                    let id = state.next_id;
+                    state.next_id += 1;
+                    self.id = Some(id);
+                    id
+                }
+            };
+            // Only the latest waker is kept, however often the sleep is polled
+            let key = (self.deadline, id);
+            match state.sleepers.get_mut(&key) {
+                Some(waker) => waker.clone_from(cx.waker()),
+                None => {
+                    state.sleepers.insert(key, cx.waker().clone());
+                    if state.sleepers.keys().next() == Some(&key) {
+                        timers.changed.notify_one();
+                    }
+                }
+            }
+            Poll::Pending
+        }
+    }
+
+    impl Drop for Sleep {
+        fn drop(&mut self) {
+            if let Some(id) = self.id {
+                if let Ok(mut state) = timers().state.lock() {
+                    state.sleepers.remove(&(self.deadline, id));
+                }
+            }
+        }
+    }
+
+    struct Timers {
+        state: Mutex<TimerState>,
+        /// Signalled when a sleep becomes the earliest
+        changed: Condvar,
+    }
+
+    struct TimerState {
+        /// The id the next sleep gets
+        next_id: u64,
+        /// The waker of each pending sleep, earliest deadline first
+        sleepers: BTreeMap<(Instant, u64), Waker>,
+    }
+
+    impl Timers {
+        fn run(&self) {
+            let mut state = self.state.lock().unwrap();
+            loop {
+                let now = Instant::now();
+                let mut due = Vec::new();
+                while let Some(entry) = state.sleepers.first_entry() {
+                    if entry.key().0 > now {
+                        break;
+                    }
+                    due.push(entry.remove());
+                }
+                if !due.is_empty() {
+                    drop(state);
+                    for waker in due {
+                        waker.wake();
+                    }
+                    state = self.state.lock().unwrap();
+                    continue;
+                }
+                state = match state.sleepers.keys().next() {
+                    Some(&(deadline, _)) => {
+                        let wait = deadline.saturating_duration_since(now);
+                        self.changed.wait_timeout(state, wait).unwrap().0
+                    }
+                    None => self.changed.wait(state).unwrap(),
+                };
+            }
+        }
+    }
+
+    /// Returns the timer, starting its thread on first use.
+    fn timers() -> &'static Timers {
+        static TIMERS: OnceLock<Timers> = OnceLock::new();
+        static STARTED: std::sync::Once = std::sync::Once::new();
+        let timers = TIMERS.get_or_init(|| Timers {
+            state: Mutex::new(TimerState {
+                next_id: 0,
+                sleepers: BTreeMap::new(),
+            }),
+            changed: Condvar::new(),
+        });
+        STARTED.call_once(|| {
+            std::thread::Builder::new()
+                .name("bubbletea-timer".to_string())
+                .spawn(move || timers.run())
+                .expect("failed to start the timer thread");
+        });
+        timers
+    }
+}
@@ -18,24 +18,367 @@
 //! - Mouse event capture with different motion reporting levels
 //! - Focus change reporting
 //! - Bracketed paste mode for distinguishing pasted vs typed text
-//! - Cursor visibility control
+//! - Cursor visibility control, and saving/restoring the cursor position
+//! - Window titles, with a title stack that falls back to software
+//! - Synchronized output (DEC mode 2026) for flicker-free frames
+//! - Output to stdout or stderr, keeping stdout free for results
+//! - Inline rendering with a live region below scrolling log lines
 //! - Efficient rendering with buffering
 
+use crate::ansi::{self, visible_width};
+use crate::event::{EventSender, Msg};
+use crate::log_region::LogRegion;
 use crate::Error;
 use crossterm::{
     cursor::{Hide, Show},
     event::{
         DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
-        EnableFocusChange, EnableMouseCapture,
+        EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
-    execute,
-    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+    execute, terminal,
 };
 use std::io::{self, Write};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::io::AsyncWrite;
 use tokio::sync::Mutex;
 
+/// Optional terminal features that are not universally supported.
+///
+/// Capabilities are detected once when a [`Terminal`] is created, using
+/// environment variables that identify the terminal emulator. Detection is
+/// conservative: a feature is only reported when the terminal is known to
+/// support it.
+///
+/// # Example
+///
+/// ```rust
+/// use bubbletea_rs::terminal::TerminalCapabilities;
+///
+/// let caps = TerminalCapabilities::from_env(|name| match name {
+///     "TERM" => Some("xterm-kitty".to_string()),
+///     _ => None,
+/// });
+/// assert!(caps.synchronized_output);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    /// Whether the terminal supports synchronized output (DEC private mode
+    /// 2026), which holds back screen refreshes until a frame is complete.
+    pub synchronized_output: bool,
+    /// Whether the terminal keeps a window title stack (`CSI 22;0 t` to push,
+    /// `CSI 23;0 t` to pop), which restores titles the program did not set.
+    pub title_stack: bool,
+    /// Whether the terminal supports bracketed paste (DEC private mode
+    /// 2004), which marks pasted text so it arrives as one `PasteMsg`.
+    pub bracketed_paste: bool,
+    /// Where the terminal's size came from. See [`resolve_size`].
+    pub size_source: SizeSource,
+    /// How many colors the terminal can show.
+    pub color_profile: ColorProfile,
+}
+
+/// How many colors a terminal can show.
+///
+/// Ordered from fewest to most colors, so `profile >= ColorProfile::Ansi256`
+/// asks whether 256 colors are available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorProfile {
+    /// No colors, as on a `dumb` terminal.
+    NoColor,
+    /// The 16 basic ANSI colors, which nearly every terminal supports.
+    #[default]
+    Ansi,
+    /// The 256-color palette.
+    Ansi256,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+impl TerminalCapabilities {
+    /// Detects capabilities from the current process environment.
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Detects capabilities from environment variables provided by `var`.
+    ///
+    /// # Arguments
+    ///
+    /// * `var` - Looks up an environment variable by name, returning `None`
+    ///   if it is unset
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        const SYNC_TERMS: [&str; 5] = [
+            "xterm-kitty",
+            "alacritty",
+            "foot",
+            "contour",
+            "xterm-ghostty",
+        ];
+        const SYNC_PROGRAMS: [&str; 4] = ["WezTerm", "iTerm.app", "ghostty", "contour"];
+        const TITLE_STACK_TERMS: [&str; 3] = ["xterm-kitty", "foot", "alacritty"];
+        const TITLE_STACK_PROGRAMS: [&str; 1] = ["WezTerm"];
+        // The Linux console and `dumb` terminals ignore mode 2004
+        const PASTE_TERMS: [&str; 8] = [
+            "xterm",
+            "screen",
+            "tmux",
+            "rxvt",
+            "alacritty",
+            "foot",
+            "contour",
+            "wezterm",
+        ];
+        const PASTE_PROGRAMS: [&str; 5] = [
+            "WezTerm",
+            "iTerm.app",
+            "Apple_Terminal",
+            "ghostty",
+            "vscode",
+        ];
+        const TRUECOLOR_TERMS: [&str; 3] = ["xterm-kitty", "xterm-ghostty", "alacritty"];
+        const TRUECOLOR_PROGRAMS: [&str; 4] = ["WezTerm", "iTerm.app", "ghostty", "vscode"];
+
+        let term = var("TERM").unwrap_or_default();
+        let term_program = var("TERM_PROGRAM").unwrap_or_default();
+        let synchronized_output = SYNC_TERMS.iter().any(|t| term.starts_with(t))
+            || SYNC_PROGRAMS.contains(&term_program.as_str())
+            || var("WT_SESSION").is_some(); // Windows Terminal
+        let title_stack = TITLE_STACK_TERMS.iter().any(|t| term.starts_with(t))
+            || TITLE_STACK_PROGRAMS.contains(&term_program.as_str())
+            || var("XTERM_VERSION").is_some(); // xterm itself
+        let bracketed_paste = PASTE_TERMS.iter().any(|t| term.starts_with(t))
+            || PASTE_PROGRAMS.contains(&term_program.as_str())
+            || var("WT_SESSION").is_some();
+        let colorterm = var("COLORTERM").unwrap_or_default();
+        let color_profile = if colorterm == "truecolor"
+            || colorterm == "24bit"
+            || term.ends_with("-direct")
+            || TRUECOLOR_TERMS.iter().any(|t| term.starts_with(t))
+            || TRUECOLOR_PROGRAMS.contains(&term_program.as_str())
+            || var("WT_SESSION").is_some()
+        {
+            ColorProfile::TrueColor
+        } else if term.contains("256color") {
+            ColorProfile::Ansi256
+        } else if term == "dumb" {
+            ColorProfile::NoColor
+        } else {
+            ColorProfile::Ansi
+        };
+
+        Self {
+            synchronized_output,
+            title_stack,
+            bracketed_paste,
+            size_source: SizeSource::default(),
+            color_profile,
+        }
+    }
+}
+
+/// The size assumed when neither the terminal nor the environment reports
+/// one: 80 columns by 24 rows.
+pub const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Where a terminal's size came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SizeSource {
+    /// The terminal reported its size.
+    #[default]
+    Terminal,
+    /// The terminal did not answer, so `$COLUMNS` and `$LINES` were used.
+    Environment,
+    /// Neither the terminal nor the environment had a size, so the
+    /// configured default was used.
+    Default,
+}
+
+/// A terminal mode a program can ask for at startup.
+///
+/// Used to report which modes a terminal refused; see
+/// [`ModesDegradedMsg`](crate::ModesDegradedMsg).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerminalMode {
+    /// The alternate screen buffer.
+    AltScreen,
+    /// Mouse reporting, in either motion mode.
+    Mouse,
+    /// Focus change reporting.
+    FocusReporting,
+    /// Bracketed paste.
+    BracketedPaste,
+    /// The keyboard enhancement protocol.
+    KeyboardEnhancement,
+}
+
+/// How the terminal encodes the arrow, Home and End keys (DECCKM).
+///
+/// In normal mode they arrive as CSI sequences such as `ESC [ A`; in
+/// application mode as SS3 sequences such as `ESC O A`. Programs that exit
+/// without resetting the mode leave the terminal in it. See
+/// [`ProgramBuilder::cursor_key_mode`](crate::ProgramBuilder::cursor_key_mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorKeyMode {
+    /// CSI encoding, the mode a terminal starts in.
+    #[default]
+    Normal,
+    /// SS3 encoding.
+    Application,
+}
+
+/// Which part of the screen a frame changes.
+///
+/// Passed to [`TerminalInterface::render`] so a terminal can skip work for
+/// the parts of the screen that did not change. Models supply it with
+/// [`Model::render_hint`](crate::Model::render_hint).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderHint {
+    /// The whole screen is cleared and redrawn.
+    #[default]
+    Full,
+    /// Only the rectangle at column `x`, row `y` (zero-based), `width`
+    /// columns wide and `height` rows tall changed. The matching part of
+    /// the content is written in place, without clearing the screen.
+    Region(u16, u16, u16, u16),
+}
+
+/// Picks a terminal size, never returning a zero dimension.
+///
+/// Some environments (certain containers, `ssh -T`, tmux popups) fail to
+/// report a size or report 0x0. The chain is: `queried`, the size the
+/// terminal reported; then `$COLUMNS` and `$LINES` as looked up by `var`;
+/// then `default`. A stage is only used if both of its dimensions are
+/// nonzero. A zero in `default` is raised to 1.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubbletea_rs::terminal::{resolve_size, SizeSource, DEFAULT_SIZE};
+///
+/// let env = |name: &str| match name {
+///     "COLUMNS" => Some("132".to_string()),
+///     "LINES" => Some("43".to_string()),
+///     _ => None,
+/// };
+/// assert_eq!(
+///     resolve_size(Some((0, 0)), env, DEFAULT_SIZE),
+///     ((132, 43), SizeSource::Environment)
+/// );
+/// ```
+pub fn resolve_size(
+    queried: Option<(u16, u16)>,
+    var: impl Fn(&str) -> Option<String>,
+    default: (u16, u16),
+) -> ((u16, u16), SizeSource) {
+    let nonzero = |&(width, height): &(u16, u16)| width > 0 && height > 0;
+    if let Some(size) = queried.filter(nonzero) {
+        return (size, SizeSource::Terminal);
+    }
+    let dimension = |name| var(name).and_then(|value| value.trim().parse::<u16>().ok());
+    if let Some(size) = dimension("COLUMNS").zip(dimension("LINES")).filter(nonzero) {
+        return (size, SizeSource::Environment);
+    }
+    ((default.0.max(1), default.1.max(1)), SizeSource::Default)
+}
+
+/// Window titles set by the program, backing the software title stack.
+///
+/// Terminals cannot report their current title, so when the terminal has no
+/// title stack of its own, popping can only restore titles the program set
+/// itself.
+#[derive(Debug, Default)]
+struct WindowTitles {
+    current: Option<String>,
+    saved: Vec<Option<String>>,
+}
+
+impl WindowTitles {
+    fn set(&mut self, title: &str) {
+        self.current = Some(title.to_string());
+    }
+
+    fn push(&mut self, title: &str) {
+        self.saved.push(self.current.replace(title.to_string()));
+    }
+
+    /// Returns the title to go back to, or `None` if nothing was pushed.
+    fn pop(&mut self) -> Option<Option<String>> {
+        let previous = self.saved.pop()?;
+        self.current.clone_from(&previous);
+        Some(previous)
+    }
+}
+
+/// The [`device`](TerminalInterface::device) name of the process's
+/// controlling terminal.
+pub(crate) const CONTROLLING_TERMINAL: &str = "tty";
+
+/// Appends `content` to `buffer` as a full render writes it: each `\n`
+/// becomes `\r\n`, since raw mode does not return the cursor to the first
+/// column on a line feed.
+pub(crate) fn push_frame(buffer: &mut String, content: &str) {
+    for ch in content.chars() {
+        if ch == '\n' {
+            buffer.push_str("\r\n");
+        } else {
+            buffer.push(ch);
+        }
+    }
+}
+
+/// Builds the OSC sequence that sets the window title.
+///
+/// Control characters are dropped so a title cannot end the sequence early.
+fn window_title_sequence(title: &str) -> String {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]0;{title}\x07")
+}
+
+/// The standard stream a [`Terminal`] writes to when it has no output writer.
+///
+/// Terminal control sequences and frames go to stdout by default. Writing
+/// them to stderr instead keeps stdout free for the program's result, so a
+/// picker can be used as `FILE=$(my-picker)`. Raw mode and terminal size are
+/// always taken from the controlling terminal, whichever stream is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputStream {
+    /// Write to standard output.
+    #[default]
+    Stdout,
+    /// Write to standard error.
+    Stderr,
+}
+
+impl OutputStream {
+    /// Returns whether this stream is connected to a terminal.
+    pub fn is_terminal(self) -> bool {
+        use std::io::IsTerminal;
+        match self {
+            OutputStream::Stdout => io::stdout().is_terminal(),
+            OutputStream::Stderr => io::stderr().is_terminal(),
+        }
+    }
+}
+
+impl Write for OutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            OutputStream::Stdout => io::stdout().write(buf),
+            OutputStream::Stderr => io::stderr().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            OutputStream::Stdout => io::stdout().flush(),
+            OutputStream::Stderr => io::stderr().flush(),
+        }
+    }
+}
+
 /// A trait for abstracting terminal operations.
 ///
 /// This trait provides a unified interface for terminal management across
@@ -51,14 +394,14 @@ use tokio::sync::Mutex;
 /// # Example
 ///
 /// ```rust
-/// use bubbletea_rs::terminal::{TerminalInterface, Terminal};
+/// use bubbletea_rs::terminal::{RenderHint, TerminalInterface, Terminal};
 /// use bubbletea_rs::Error;
 ///
 /// # async fn example() -> Result<(), Error> {
 /// let mut terminal = Terminal::new(None)?;
 /// terminal.enter_raw_mode().await?;
 /// terminal.hide_cursor().await?;
-/// terminal.render("Hello, world!").await?;
+/// terminal.render("Hello, world!", RenderHint::Full).await?;
 /// terminal.show_cursor().await?;
 /// terminal.exit_raw_mode().await?;
 /// # Ok(())
@@ -127,9 +470,14 @@ pub trait TerminalInterface {
     ///
     /// # Effects
     ///
-    /// - Saves current screen content
+    /// - Saves current screen content and the cursor position
     /// - Switches to alternate buffer
-    /// - Clears the alternate buffer
+    /// - Clears the alternate buffer, unless turned off with
+    ///   [`set_clear_on_alt_screen_enter`](Self::set_clear_on_alt_screen_enter)
+    ///
+    /// Implementations that buffer output may hold the switch back until
+    /// the next flush, so it reaches the terminal together with the first
+    /// frame drawn on the alternate screen.
     ///
     /// # Errors
     ///
@@ -145,6 +493,8 @@ pub trait TerminalInterface {
     ///
     /// - Restores original screen content
     /// - Switches back to main buffer
+    /// - Puts the cursor back where it was when the alternate screen was
+    ///   entered, so inline rendering resumes from there
     ///
     /// # Errors
     ///
@@ -246,6 +596,48 @@ pub trait TerminalInterface {
     ///
     /// Returns an error if bracketed paste mode cannot be disabled.
     async fn disable_bracketed_paste(&mut self) -> Result<(), Error>;
+    /// Enable the keyboard enhancement protocol, if the terminal supports it.
+    ///
+    /// With the protocol active, keys that legacy terminals encode the same
+    /// way are reported distinctly: Ctrl+M is no longer indistinguishable
+    /// from Enter, nor Ctrl+I from Tab. See
+    /// [`input_is_ambiguous`](crate::event::input_is_ambiguous).
+    ///
+    /// Returns whether the protocol is now active. The default
+    /// implementation does nothing and returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the terminal cannot be queried or the control
+    /// sequence cannot be written.
+    async fn enable_keyboard_enhancement(&mut self) -> Result<bool, Error> {
+        Ok(false)
+    }
+    /// Disable the keyboard enhancement protocol if it is active.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn disable_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    /// Ask the terminal which keyboard enhancement flags it supports.
+    ///
+    /// Writes the query followed by a primary device attributes request, so
+    /// terminals without the protocol still answer. The replies arrive on the
+    /// program's input, where a custom input source decodes them into a
+    /// [`KeyboardEnhancementsMsg`](crate::KeyboardEnhancementsMsg).
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn query_keyboard_enhancements(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
     /// Show the cursor if hidden.
     ///
     /// Makes the cursor visible if it was previously hidden. This is typically
@@ -266,6 +658,63 @@ pub trait TerminalInterface {
     ///
     /// Returns an error if the cursor visibility cannot be changed.
     async fn hide_cursor(&mut self) -> Result<(), Error>;
+    /// Save the cursor position.
+    ///
+    /// Writes DECSC (`\x1b7`), which is understood more widely than the
+    /// `CSI s` alternative. The saved position is restored by
+    /// [`restore_cursor`](Self::restore_cursor).
+    ///
+    /// # Nesting
+    ///
+    /// Most terminals keep a single saved position rather than a stack, so
+    /// a second save overwrites the first. Pair every save with a restore
+    /// before saving again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn save_cursor(&mut self) -> Result<(), Error>;
+    /// Restore the cursor position saved by [`save_cursor`](Self::save_cursor).
+    ///
+    /// Writes DECRC (`\x1b8`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn restore_cursor(&mut self) -> Result<(), Error>;
+    /// Set the window title.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn set_window_title(&mut self, title: &str) -> Result<(), Error>;
+    /// Set the window title, remembering the current one so
+    /// [`pop_window_title`](Self::pop_window_title) can restore it.
+    ///
+    /// When [`capabilities`](Self::capabilities) reports a title stack, the
+    /// terminal saves the current title itself (`CSI 22;0 t`), so even a
+    /// title set before the program started is restored. Otherwise the
+    /// implementation keeps its own stack of titles, which can only restore
+    /// titles the program set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequences cannot be written.
+    async fn push_window_title(&mut self, title: &str) -> Result<(), Error>;
+    /// Restore the title saved by the matching
+    /// [`push_window_title`](Self::push_window_title).
+    ///
+    /// Does nothing if no title has been pushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequences cannot be written.
+    async fn pop_window_title(&mut self) -> Result<(), Error>;
+    /// Returns the number of pushed titles not yet popped.
+    ///
+    /// The `Program` pops this many titles on exit, so titles pushed by the
+    /// application never outlive it.
+    fn window_title_depth(&self) -> usize;
     /// Clear the visible screen contents.
     ///
     /// Clears the entire visible screen, typically filling it with the
@@ -276,6 +725,189 @@ pub trait TerminalInterface {
     ///
     /// Returns an error if the screen cannot be cleared.
     async fn clear(&mut self) -> Result<(), Error>;
+    /// Clear from the cursor to the end of the screen.
+    ///
+    /// Writes `\x1b[J`. Rows above the cursor and the text before it on
+    /// its own row are left alone, and the cursor does not move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn clear_to_end_of_screen(&mut self) -> Result<(), Error>;
+    /// Clear from the cursor to the end of its row.
+    ///
+    /// Writes `\x1b[K`. The text before the cursor and every other row are
+    /// left alone, and the cursor does not move. Useful for rewriting a
+    /// status line that may have become shorter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn clear_line(&mut self) -> Result<(), Error>;
+    /// Clear from the top of the screen to the cursor.
+    ///
+    /// Writes `\x1b[1J`. Rows below the cursor and the text after it on its
+    /// own row are left alone, and the cursor does not move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn clear_from_start_of_screen(&mut self) -> Result<(), Error>;
+    /// Ring the terminal bell.
+    ///
+    /// Writes the BEL control character (`\x07`). How the bell is presented
+    /// (sound, flash, notification, or nothing) is up to the terminal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control character cannot be written.
+    async fn bell(&mut self) -> Result<(), Error>;
+    /// Enable or disable reverse video for the whole screen.
+    ///
+    /// Used to implement the visual bell. Terminals that do not support
+    /// screen-wide reverse video ignore the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to swap foreground and background colors,
+    ///   `false` to restore normal video.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn set_reverse_video(&mut self, enabled: bool) -> Result<(), Error>;
+    /// Set how the terminal encodes the cursor keys.
+    ///
+    /// Writes `\x1b[?1l` for [`CursorKeyMode::Normal`] or `\x1b[?1h` for
+    /// [`CursorKeyMode::Application`] (DEC private mode 1, DECCKM). The
+    /// default implementation does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn set_cursor_key_mode(&mut self, _mode: CursorKeyMode) -> Result<(), Error> {
+        Ok(())
+    }
+    /// Begin a synchronized output block.
+    ///
+    /// Writes `\x1b[?2026h` (DEC private mode 2026). Until the matching
+    /// [`exit_synchronized_output`](Self::exit_synchronized_output), the
+    /// terminal keeps showing the previous frame, so a partially written
+    /// frame is never displayed.
+    ///
+    /// Implementations should only emit the sequence when
+    /// [`capabilities`](Self::capabilities) reports support for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn enter_synchronized_output(&mut self) -> Result<(), Error>;
+    /// End a synchronized output block.
+    ///
+    /// Writes `\x1b[?2026l`, letting the terminal display everything written
+    /// since [`enter_synchronized_output`](Self::enter_synchronized_output).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the control sequence cannot be written.
+    async fn exit_synchronized_output(&mut self) -> Result<(), Error>;
+    /// Returns the optional features supported by this terminal.
+    fn capabilities(&self) -> TerminalCapabilities;
+    /// Detects the terminal's capabilities again, for terminals whose
+    /// features can change while the program runs, such as a tmux session
+    /// reattached from a different terminal.
+    ///
+    /// The `Program` calls this on resize when
+    /// [`requery_capabilities_on_resize`](crate::ProgramBuilder::requery_capabilities_on_resize)
+    /// is set, and compares [`capabilities`](Self::capabilities) before and
+    /// after. The default implementation does nothing.
+    fn refresh_capabilities(&mut self) {}
+    /// Sets whether output is flushed after every write.
+    ///
+    /// Terminals flush after every write by default. With auto-flush
+    /// disabled, frames and printed text are buffered until
+    /// [`flush`](Self::flush) is called; the `Program` does this to apply
+    /// its [`FlushStrategy`](crate::FlushStrategy). Mode changes such as
+    /// entering the alternate screen flush buffered output first, so output
+    /// is never reordered.
+    ///
+    /// The default implementation does nothing, for terminals that do not
+    /// buffer.
+    fn set_auto_flush(&mut self, _enabled: bool) {}
+    /// Sets whether [`enter_alt_screen`](Self::enter_alt_screen) clears the
+    /// alternate screen.
+    ///
+    /// Clearing is on by default. A frame drawn in full repaints the whole
+    /// screen anyway, so programs that draw right after switching can turn
+    /// it off. The `Program` applies
+    /// [`ProgramBuilder::clear_on_alt_screen_enter`](crate::ProgramBuilder::clear_on_alt_screen_enter)
+    /// with this.
+    ///
+    /// The default implementation does nothing, for terminals that do not
+    /// clear.
+    fn set_clear_on_alt_screen_enter(&mut self, _enabled: bool) {}
+    /// Sets whether output is printed line after line, as on a line
+    /// printer, for screen readers.
+    ///
+    /// When enabled, [`render`](Self::render) prints each frame that
+    /// differs from the last as plain text below it, and
+    /// [`print`](Self::print) writes plain text. Nothing moves the cursor
+    /// or clears the screen: cursor saves, clears and the alternate screen
+    /// are ignored, and the cursor stays visible. The `Program` enables
+    /// this with
+    /// [`ProgramBuilder::accessible`](crate::ProgramBuilder::accessible).
+    ///
+    /// The default implementation does nothing, for terminals that only
+    /// print lines anyway.
+    fn set_line_printer(&mut self, _enabled: bool) {}
+    /// Writes out any buffered output and flushes the underlying stream.
+    ///
+    /// The default implementation does nothing, for terminals that do not
+    /// buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffered output cannot be written.
+    async fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    /// Write text to the terminal outside of the rendered frame.
+    ///
+    /// Newlines are translated to `\r\n`, since the terminal is usually in
+    /// raw mode. Unlike [`render`](Self::render), the screen is not cleared
+    /// first.
+    ///
+    /// [`Terminal`] wraps long lines to the terminal width, counting only
+    /// visible characters, and closes styling left open at the end of each
+    /// line so it doesn't tint the next frame (see [`ansi::wrap`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write. May contain ANSI escape sequences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text cannot be written.
+    async fn print(&mut self, text: &str) -> Result<(), Error>;
+    /// Write text without disturbing the cursor position of the frame.
+    ///
+    /// Calls [`print`](Self::print), bracketed by
+    /// [`save_cursor`](Self::save_cursor) and
+    /// [`restore_cursor`](Self::restore_cursor), so the next frame is drawn
+    /// from where the renderer left the cursor. The `Program` uses this for
+    /// `PrintMsg` and `PrintfMsg`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text or the control sequences cannot be
+    /// written.
+    async fn print_preserving_cursor(&mut self, text: &str) -> Result<(), Error> {
+        self.save_cursor().await?;
+        let result = self.print(text).await;
+        // Restore even if printing failed, so the renderer's cursor stays put
+        self.restore_cursor().await?;
+        result
+    }
     /// Render the provided content to the terminal.
     ///
     /// Displays the given content on the terminal screen. This typically
@@ -287,6 +919,9 @@ pub trait TerminalInterface {
     ///
     /// * `content` - The text content to display. May contain ANSI escape
     ///   sequences for colors and formatting.
+    /// * `hint` - Which part of the screen changed. With
+    ///   [`RenderHint::Region`], only that rectangle of `content` is
+    ///   written and the rest of the screen keeps the previous frame.
     ///
     /// # Performance
     ///
@@ -297,7 +932,65 @@ pub trait TerminalInterface {
     ///
     /// Returns an error if the content cannot be written to the terminal
     /// or output writer.
-    async fn render(&mut self, content: &str) -> Result<(), Error>;
+    async fn render(&mut self, content: &str, hint: RenderHint) -> Result<(), Error>;
+    /// Render one complete frame.
+    ///
+    /// Calls [`render`](Self::render), bracketed by
+    /// [`enter_synchronized_output`](Self::enter_synchronized_output) and
+    /// [`exit_synchronized_output`](Self::exit_synchronized_output) when the
+    /// terminal supports synchronized output. The `Program` uses this for
+    /// every frame it draws.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content or the control sequences cannot be
+    /// written.
+    async fn render_frame(&mut self, content: &str, hint: RenderHint) -> Result<(), Error> {
+        if !self.capabilities().synchronized_output {
+            return self.render(content, hint).await;
+        }
+        self.enter_synchronized_output().await?;
+        let result = self.render(content, hint).await;
+        // Always end the block, even if rendering failed, so the terminal
+        // doesn't stay frozen on the previous frame
+        self.exit_synchronized_output().await?;
+        result
+    }
+    /// Rewrite only the given rows of the screen with the matching lines of
+    /// `content`.
+    ///
+    /// `rows` are zero-based, end-exclusive row ranges; rows past the last
+    /// line of `content` are cleared. Everything outside `rows` is left as
+    /// it is, so the caller must only use this when the rest of the screen
+    /// still shows the previous frame. The default implementation redraws
+    /// everything with [`render`](Self::render).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content cannot be written.
+    async fn render_rows(&mut self, content: &str, _rows: &[Range<u16>]) -> Result<(), Error> {
+        self.render(content, RenderHint::Full).await
+    }
+    /// Render one frame, rewriting only the given rows.
+    ///
+    /// Like [`render_frame`](Self::render_frame), but calls
+    /// [`render_rows`](Self::render_rows) inside the synchronized output
+    /// block. The `Program` uses this when the model reports damage with
+    /// [`Model::damage`](crate::Model::damage).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content or the control sequences cannot be
+    /// written.
+    async fn render_frame_rows(&mut self, content: &str, rows: &[Range<u16>]) -> Result<(), Error> {
+        if !self.capabilities().synchronized_output {
+            return self.render_rows(content, rows).await;
+        }
+        self.enter_synchronized_output().await?;
+        let result = self.render_rows(content, rows).await;
+        self.exit_synchronized_output().await?;
+        result
+    }
     /// Get the current terminal size as (columns, rows).
     ///
     /// Returns the current dimensions of the terminal in character cells.
@@ -319,6 +1012,18 @@ pub trait TerminalInterface {
     /// Terminal size can change during program execution due to window
     /// resizing. Applications should handle size change events appropriately.
     fn size(&self) -> Result<(u16, u16), Error>;
+    /// Returns the name of the device this terminal drives, if other
+    /// programs in the process could drive it too.
+    ///
+    /// Two programs driving the same device at once fight over raw mode and
+    /// the screen, so a `Program` refuses to start with
+    /// [`Error::AlreadyRunning`] while another one holds the same device.
+    /// The crossterm [`Terminal`] reports the process's controlling
+    /// terminal. Returns `None` by default, for terminals that are not
+    /// shared.
+    fn device(&self) -> Option<&str> {
+        None
+    }
 }
 
 /// Terminal state manager using crossterm for actual terminal control.
@@ -335,6 +1040,8 @@ pub trait TerminalInterface {
 /// - Mouse capture status
 /// - Focus reporting status
 /// - Cursor visibility
+/// - Reverse video (visual bell)
+/// - Synchronized output blocks
 ///
 /// # Performance
 ///
@@ -345,7 +1052,7 @@ pub trait TerminalInterface {
 /// # Example
 ///
 /// ```rust
-/// use bubbletea_rs::terminal::{Terminal, TerminalInterface};
+/// use bubbletea_rs::terminal::{RenderHint, Terminal, TerminalInterface};
 /// use bubbletea_rs::Error;
 ///
 /// # async fn example() -> Result<(), Error> {
@@ -357,7 +1064,7 @@ pub trait TerminalInterface {
 /// terminal.hide_cursor().await?;
 ///
 /// // Render some content
-/// terminal.render("Hello, TUI world!").await?;
+/// terminal.render("Hello, TUI world!", RenderHint::Full).await?;
 ///
 /// // Clean up (or rely on Drop)
 /// terminal.show_cursor().await?;
@@ -371,17 +1078,41 @@ pub struct Terminal {
     alt_screen: bool,
     mouse_enabled: bool,
     focus_reporting: bool,
+    keyboard_enhancement: bool,
     cursor_visible: bool,
+    reverse_video: bool,
+    synchronized_output: bool,
+    capabilities: TerminalCapabilities,
+    /// Whether `capabilities` were detected rather than set by the caller
+    capabilities_detected: bool,
+    window_titles: WindowTitles,
     output_writer: Option<Arc<Mutex<dyn AsyncWrite + Send + Unpin>>>,
+    /// Standard stream used when there is no output writer
+    stream: OutputStream,
     /// Reusable buffer for string operations to minimize allocations
     render_buffer: String,
+    /// Frames and control sequences not yet written out
+    pending: Vec<u8>,
+    /// Whether every write is flushed immediately
+    auto_flush: bool,
+    /// Whether entering the alternate screen clears it
+    clear_on_alt_screen_enter: bool,
+    /// Live region for inline rendering, if enabled
+    log_region: Option<LogRegion>,
+    /// Whether frames and text are printed as plain lines, one after another
+    line_printer: bool,
+    /// The last rendered content, repainted after logging
+    last_view: String,
+    /// The size assumed when neither the terminal nor the environment has one
+    default_size: (u16, u16),
 }
 
 impl Terminal {
     /// Create a new [`Terminal`] instance.
     ///
     /// If an `output_writer` is provided, rendering is performed by writing to
-    /// that asynchronous writer instead of directly to stdout.
+    /// that asynchronous writer instead of directly to stdout (or the stream set
+    /// with [`with_output_stream`](Self::with_output_stream)).
     pub fn new(
         output_writer: Option<Arc<Mutex<dyn AsyncWrite + Send + Unpin>>>,
     ) -> Result<Self, Error> {
@@ -390,11 +1121,133 @@ impl Terminal {
             alt_screen: false,
             mouse_enabled: false,
             focus_reporting: false,
+            keyboard_enhancement: false,
             cursor_visible: true,
+            reverse_video: false,
+            synchronized_output: false,
+            capabilities: TerminalCapabilities::detect(),
+            capabilities_detected: true,
+            window_titles: WindowTitles::default(),
             output_writer,
+            stream: OutputStream::Stdout,
             render_buffer: String::with_capacity(8192), // Pre-allocate 8KB buffer
+            pending: Vec::new(),
+            auto_flush: true,
+            clear_on_alt_screen_enter: true,
+            log_region: None,
+            line_printer: false,
+            last_view: String::new(),
+            default_size: DEFAULT_SIZE,
         })
     }
+
+    /// Overrides the detected terminal capabilities.
+    ///
+    /// Useful when the environment does not identify the terminal, or to
+    /// force features on or off in tests.
+    pub fn with_capabilities(mut self, capabilities: TerminalCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self.capabilities_detected = false;
+        self
+    }
+
+    /// Sets the standard stream to write to when there is no output writer.
+    ///
+    /// Defaults to [`OutputStream::Stdout`].
+    pub fn with_output_stream(mut self, stream: OutputStream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Renders inline, in a live region of at most `live_rows` rows at the
+    /// cursor, instead of clearing the screen for every frame.
+    ///
+    /// [`render`](TerminalInterface::render) repaints the region in place,
+    /// and [`print`](TerminalInterface::print) writes lines above it that
+    /// scroll into the terminal's scrollback, then repaints the region below
+    /// them. See [`LogRegion`] for how the region is clamped on resize.
+    pub fn with_log_region(mut self, live_rows: u16) -> Self {
+        self.log_region = Some(LogRegion::new(live_rows));
+        self
+    }
+
+    /// Prints frames and text as plain lines, one after another, instead
+    /// of redrawing the screen in place.
+    ///
+    /// See [`set_line_printer`](TerminalInterface::set_line_printer).
+    pub fn with_line_printer(mut self) -> Self {
+        self.line_printer = true;
+        self
+    }
+
+    /// Sets the size assumed when neither the terminal nor `$COLUMNS` and
+    /// `$LINES` report one.
+    ///
+    /// Defaults to [`DEFAULT_SIZE`]. See [`resolve_size`].
+    pub fn with_default_size(mut self, width: u16, height: u16) -> Self {
+        self.default_size = (width, height);
+        self
+    }
+
+    /// Returns the terminal's size and where it came from, falling back as
+    /// described in [`resolve_size`].
+    fn resolved_size(default_size: (u16, u16)) -> ((u16, u16), SizeSource) {
+        // The ioctl can briefly report 0x0 while a pty is being set up, so
+        // ask once more before falling back
+        let queried = terminal::size()
+            .ok()
+            .filter(|&(width, height)| width > 0 && height > 0)
+            .or_else(|| terminal::size().ok());
+        resolve_size(queried, |name| std::env::var(name).ok(), default_size)
+    }
+
+    /// Writes a raw control sequence to the output writer, or the output
+    /// stream if none was provided. Buffered until the next flush when
+    /// auto-flush is off.
+    async fn write_control(&mut self, sequence: &str) -> Result<(), Error> {
+        self.pending.extend_from_slice(sequence.as_bytes());
+        if self.auto_flush {
+            self.flush_pending().await?;
+        }
+        Ok(())
+    }
+
+    /// Writes out the buffered output and flushes the output writer or
+    /// stream.
+    async fn flush_pending(&mut self) -> Result<(), Error> {
+        if let Some(writer) = &self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            let mut writer = writer.lock().await;
+            writer.write_all(&self.pending).await?;
+            writer.flush().await?;
+        } else {
+            self.stream.write_all(&self.pending)?;
+            self.stream.flush()?;
+        }
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Writes out buffered output before a mode change that bypasses the
+    /// buffer, so the two are not reordered.
+    async fn flush_before_mode_change(&mut self) -> Result<(), Error> {
+        if !self.pending.is_empty() {
+            self.flush_pending().await?;
+        }
+        Ok(())
+    }
+
+    /// Writes the control sequence that clears the part of the screen
+    /// `clear_type` names, unless printing plain lines.
+    async fn write_clear(&mut self, clear_type: terminal::ClearType) -> Result<(), Error> {
+        if self.line_printer {
+            return Ok(());
+        }
+        let mut sequence = String::new();
+        // Writing to a `String` cannot fail
+        let _ = crossterm::Command::write_ansi(&terminal::Clear(clear_type), &mut sequence);
+        self.write_control(&sequence).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -408,9 +1261,23 @@ impl TerminalInterface for Terminal {
             alt_screen: false,
             mouse_enabled: false,
             focus_reporting: false,
+            keyboard_enhancement: false,
             cursor_visible: true,
+            reverse_video: false,
+            synchronized_output: false,
+            capabilities: TerminalCapabilities::detect(),
+            capabilities_detected: true,
+            window_titles: WindowTitles::default(),
             output_writer,
+            stream: OutputStream::Stdout,
             render_buffer: String::with_capacity(8192),
+            pending: Vec::new(),
+            auto_flush: true,
+            clear_on_alt_screen_enter: true,
+            log_region: None,
+            line_printer: false,
+            last_view: String::new(),
+            default_size: DEFAULT_SIZE,
         })
     }
 
@@ -424,6 +1291,7 @@ impl TerminalInterface for Terminal {
 
     async fn exit_raw_mode(&mut self) -> Result<(), Error> {
         if self.raw_mode {
+            self.flush_before_mode_change().await?;
             terminal::disable_raw_mode()?;
             self.raw_mode = false;
         }
@@ -431,11 +1299,17 @@ impl TerminalInterface for Terminal {
     }
 
     async fn enter_alt_screen(&mut self) -> Result<(), Error> {
-        if !self.alt_screen {
-            execute!(io::stdout(), EnterAlternateScreen)?;
-            // Clear the alternate screen buffer immediately after entering
-            execute!(io::stdout(), terminal::Clear(terminal::ClearType::All))?;
-            io::stdout().flush()?;
+        if !self.alt_screen && !self.line_printer {
+            // Buffered like a frame, so with auto-flush off the switch and
+            // the first frame on the alternate screen go out in one write
+            // and the empty screen is never shown on its own. Mode 1049
+            // also saves the cursor, which leaving restores.
+            let clear = if self.clear_on_alt_screen_enter {
+                "\x1b[2J"
+            } else {
+                ""
+            };
+            self.write_control(&format!("\x1b[?1049h{clear}")).await?;
             self.alt_screen = true;
         }
         Ok(())
@@ -443,8 +1317,22 @@ impl TerminalInterface for Terminal {
 
     async fn exit_alt_screen(&mut self) -> Result<(), Error> {
         if self.alt_screen {
-            execute!(io::stdout(), LeaveAlternateScreen)?;
-            io::stdout().flush()?;
+            // Written right away even with auto-flush off, since the
+            // terminal may be handed to another process next. Mode 1049
+            // puts the cursor back where inline rendering left it.
+            self.pending.extend_from_slice(b"\x1b[?1049l");
+            // The live region is cleared and drawn again in one go, rather
+            // than trusting what the terminal restored: it may have been
+            // re-wrapped by a resize during the fullscreen session
+            if let Some(region) = &mut self.log_region {
+                let size = Self::resolved_size(self.default_size).0;
+                region.reflow(size.0);
+                if !self.last_view.is_empty() {
+                    let sequence = region.repaint(&self.last_view, size);
+                    self.pending.extend_from_slice(sequence.as_bytes());
+                }
+            }
+            self.flush_pending().await?;
             self.alt_screen = false;
         }
         Ok(())
@@ -452,7 +1340,8 @@ impl TerminalInterface for Terminal {
 
     async fn enable_mouse(&mut self) -> Result<(), Error> {
         if !self.mouse_enabled {
-            execute!(io::stdout(), EnableMouseCapture)?;
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, EnableMouseCapture)?;
             self.mouse_enabled = true;
         }
         Ok(())
@@ -468,7 +1357,8 @@ impl TerminalInterface for Terminal {
 
     async fn disable_mouse(&mut self) -> Result<(), Error> {
         if self.mouse_enabled {
-            execute!(io::stdout(), DisableMouseCapture)?;
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, DisableMouseCapture)?;
             self.mouse_enabled = false;
         }
         Ok(())
@@ -476,7 +1366,8 @@ impl TerminalInterface for Terminal {
 
     async fn enable_focus_reporting(&mut self) -> Result<(), Error> {
         if !self.focus_reporting {
-            execute!(io::stdout(), EnableFocusChange)?;
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, EnableFocusChange)?;
             self.focus_reporting = true;
         }
         Ok(())
@@ -484,122 +1375,372 @@ impl TerminalInterface for Terminal {
 
     async fn disable_focus_reporting(&mut self) -> Result<(), Error> {
         if self.focus_reporting {
-            execute!(io::stdout(), DisableFocusChange)?;
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, DisableFocusChange)?;
             self.focus_reporting = false;
         }
         Ok(())
     }
 
     async fn enable_bracketed_paste(&mut self) -> Result<(), Error> {
-        execute!(io::stdout(), EnableBracketedPaste)?;
+        self.flush_before_mode_change().await?;
+        execute!(self.stream, EnableBracketedPaste)?;
         Ok(())
     }
 
     async fn disable_bracketed_paste(&mut self) -> Result<(), Error> {
-        execute!(io::stdout(), DisableBracketedPaste)?;
+        self.flush_before_mode_change().await?;
+        execute!(self.stream, DisableBracketedPaste)?;
+        Ok(())
+    }
+
+    async fn enable_keyboard_enhancement(&mut self) -> Result<bool, Error> {
+        if !self.keyboard_enhancement && terminal::supports_keyboard_enhancement()? {
+            self.flush_before_mode_change().await?;
+            execute!(
+                self.stream,
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+            )?;
+            self.keyboard_enhancement = true;
+        }
+        Ok(self.keyboard_enhancement)
+    }
+
+    async fn disable_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        if self.keyboard_enhancement {
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, PopKeyboardEnhancementFlags)?;
+            self.keyboard_enhancement = false;
+        }
         Ok(())
     }
 
+    async fn query_keyboard_enhancements(&mut self) -> Result<(), Error> {
+        self.write_control("\x1b[?u\x1b[c").await
+    }
+
     async fn show_cursor(&mut self) -> Result<(), Error> {
         if !self.cursor_visible {
-            execute!(io::stdout(), Show)?;
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, Show)?;
             self.cursor_visible = true;
         }
         Ok(())
     }
 
     async fn hide_cursor(&mut self) -> Result<(), Error> {
-        if self.cursor_visible {
-            execute!(io::stdout(), Hide)?;
+        // A screen reader follows the cursor of a line printer
+        if self.cursor_visible && !self.line_printer {
+            self.flush_before_mode_change().await?;
+            execute!(self.stream, Hide)?;
             self.cursor_visible = false;
         }
         Ok(())
     }
 
     async fn clear(&mut self) -> Result<(), Error> {
-        execute!(io::stdout(), terminal::Clear(terminal::ClearType::All))?;
+        if self.line_printer {
+            return Ok(());
+        }
+        self.flush_before_mode_change().await?;
+        execute!(self.stream, terminal::Clear(terminal::ClearType::All))?;
         Ok(())
     }
 
-    async fn render(&mut self, content: &str) -> Result<(), Error> {
-        use crossterm::cursor::MoveTo;
-        use crossterm::terminal::{Clear, ClearType};
-
-        if let Some(writer) = &mut self.output_writer {
-            use tokio::io::AsyncWriteExt;
-
-            // Pre-allocate buffer for efficient rendering
-            self.render_buffer.clear();
+    async fn clear_to_end_of_screen(&mut self) -> Result<(), Error> {
+        self.write_clear(terminal::ClearType::FromCursorDown).await
+    }
 
-            // Reserve space for the clear sequence plus content
-            let estimated_size = 8 + content.len() + content.chars().filter(|&c| c == '\n').count();
-            self.render_buffer.reserve(estimated_size);
+    async fn clear_line(&mut self) -> Result<(), Error> {
+        self.write_clear(terminal::ClearType::UntilNewLine).await
+    }
 
-            // Add clear sequence
-            self.render_buffer.push_str("\x1b[H\x1b[2J");
+    async fn clear_from_start_of_screen(&mut self) -> Result<(), Error> {
+        self.write_clear(terminal::ClearType::FromCursorUp).await
+    }
 
-            // Efficiently replace newlines by iterating through chars
-            for ch in content.chars() {
-                if ch == '\n' {
-                    self.render_buffer.push_str("\r\n");
-                } else {
-                    self.render_buffer.push(ch);
-                }
-            }
+    async fn bell(&mut self) -> Result<(), Error> {
+        self.write_control("\x07").await
+    }
 
-            writer
-                .lock()
-                .await
-                .write_all(self.render_buffer.as_bytes())
+    async fn set_reverse_video(&mut self, enabled: bool) -> Result<(), Error> {
+        if self.reverse_video != enabled {
+            // DECSCNM: screen-wide reverse video
+            self.write_control(if enabled { "\x1b[?5h" } else { "\x1b[?5l" })
                 .await?;
-            writer.lock().await.flush().await?;
-        } else {
-            // Move cursor to top-left and clear entire screen
-            execute!(io::stdout(), MoveTo(0, 0))?;
-            execute!(io::stdout(), Clear(ClearType::All))?;
-
-            // Pre-allocate buffer for efficient rendering
-            self.render_buffer.clear();
-
-            // Reserve space for content plus newline replacements
-            let estimated_size = content.len() + content.chars().filter(|&c| c == '\n').count();
-            self.render_buffer.reserve(estimated_size);
-
-            // Efficiently replace newlines by iterating through chars
-            for ch in content.chars() {
-                if ch == '\n' {
-                    self.render_buffer.push_str("\r\n");
-                } else {
-                    self.render_buffer.push(ch);
-                }
-            }
-
-            print!("{}", self.render_buffer);
-            io::stdout().flush()?;
+            self.reverse_video = enabled;
         }
         Ok(())
     }
 
-    fn size(&self) -> Result<(u16, u16), Error> {
-        let (width, height) = terminal::size()?;
-        Ok((width, height))
+    async fn set_cursor_key_mode(&mut self, mode: CursorKeyMode) -> Result<(), Error> {
+        self.write_control(match mode {
+            CursorKeyMode::Normal => "\x1b[?1l",
+            CursorKeyMode::Application => "\x1b[?1h",
+        })
+        .await
+    }
+
+    async fn enter_synchronized_output(&mut self) -> Result<(), Error> {
+        if self.capabilities.synchronized_output && !self.synchronized_output {
+            self.write_control("\x1b[?2026h").await?;
+            self.synchronized_output = true;
+        }
+        Ok(())
+    }
+
+    async fn exit_synchronized_output(&mut self) -> Result<(), Error> {
+        if self.synchronized_output {
+            self.write_control("\x1b[?2026l").await?;
+            self.synchronized_output = false;
+        }
+        Ok(())
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            size_source: Self::resolved_size(self.default_size).1,
+            ..self.capabilities
+        }
+    }
+
+    /// Detects capabilities from the environment again, unless they were
+    /// set with [`with_capabilities`](Terminal::with_capabilities).
+    fn refresh_capabilities(&mut self) {
+        if self.capabilities_detected {
+            self.capabilities = TerminalCapabilities::detect();
+        }
+    }
+
+    fn set_auto_flush(&mut self, enabled: bool) {
+        self.auto_flush = enabled;
+    }
+
+    fn set_clear_on_alt_screen_enter(&mut self, enabled: bool) {
+        self.clear_on_alt_screen_enter = enabled;
+    }
+
+    fn set_line_printer(&mut self, enabled: bool) {
+        self.line_printer = enabled;
+    }
+
+    async fn flush(&mut self) -> Result<(), Error> {
+        self.flush_pending().await
+    }
+
+    async fn save_cursor(&mut self) -> Result<(), Error> {
+        if self.line_printer {
+            return Ok(());
+        }
+        self.write_control("\x1b7").await
+    }
+
+    async fn restore_cursor(&mut self) -> Result<(), Error> {
+        if self.line_printer {
+            return Ok(());
+        }
+        self.write_control("\x1b8").await
+    }
+
+    async fn print(&mut self, text: &str) -> Result<(), Error> {
+        if self.line_printer {
+            let text = ansi::strip(text).replace('\n', "\r\n");
+            return self.write_control(&text).await;
+        }
+        // The alternate screen is drawn as a whole and must not move the
+        // live region the cursor returns to
+        let alt_screen = self.alt_screen;
+        if let Some(region) = self.log_region.as_mut().filter(|_| !alt_screen) {
+            let size = Self::resolved_size(self.default_size).0;
+            let mut sequence = region.log(text, size);
+            if !self.last_view.is_empty() {
+                sequence.push_str(&region.repaint(&self.last_view, size));
+            }
+            return self.write_control(&sequence).await;
+        }
+        let width = self.size().map_or(0, |(width, _)| usize::from(width));
+        self.write_control(&ansi::wrap(text, width).join("\r\n"))
+            .await
+    }
+
+    async fn set_window_title(&mut self, title: &str) -> Result<(), Error> {
+        self.window_titles.set(title);
+        self.write_control(&window_title_sequence(title)).await
+    }
+
+    async fn push_window_title(&mut self, title: &str) -> Result<(), Error> {
+        self.window_titles.push(title);
+        let mut sequence = window_title_sequence(title);
+        if self.capabilities.title_stack {
+            sequence.insert_str(0, "\x1b[22;0t");
+        }
+        self.write_control(&sequence).await
+    }
+
+    async fn pop_window_title(&mut self) -> Result<(), Error> {
+        match self.window_titles.pop() {
+            None => Ok(()),
+            Some(_) if self.capabilities.title_stack => self.write_control("\x1b[23;0t").await,
+            Some(Some(previous)) => self.write_control(&window_title_sequence(&previous)).await,
+            // The title from before the program started is unknown; leave
+            // the current one in place
+            Some(None) => Ok(()),
+        }
+    }
+
+    fn window_title_depth(&self) -> usize {
+        self.window_titles.saved.len()
+    }
+
+    async fn render(&mut self, content: &str, hint: RenderHint) -> Result<(), Error> {
+        // A line printer can't redraw, so only a changed frame is printed,
+        // in full, below the last
+        if self.line_printer {
+            let text = ansi::strip(content);
+            let text = text.trim_end_matches('\n');
+            if text == self.last_view {
+                return Ok(());
+            }
+            let sequence = format!("{}\r\n", text.replace('\n', "\r\n"));
+            self.last_view = text.to_string();
+            return self.write_control(&sequence).await;
+        }
+
+        // The live region is repainted as a whole, except on the
+        // alternate screen
+        let alt_screen = self.alt_screen;
+        if let Some(region) = self.log_region.as_mut().filter(|_| !alt_screen) {
+            let sequence = region.repaint(content, Self::resolved_size(self.default_size).0);
+            self.last_view.clear();
+            self.last_view.push_str(content);
+            return self.write_control(&sequence).await;
+        }
+
+        // Pre-allocate buffer for efficient rendering
+        self.render_buffer.clear();
+
+        if let RenderHint::Region(x, y, width, height) = hint {
+            let lines: Vec<&str> = content.split('\n').collect();
+            let width = usize::from(width);
+            for row in y..y.saturating_add(height) {
+                // Move to the region's corner on this row and overwrite it,
+                // padding so nothing of the previous frame shows through
+                self.render_buffer.push_str(&format!(
+                    "\x1b[{};{}H",
+                    u32::from(row) + 1,
+                    u32::from(x) + 1
+                ));
+                let line = lines.get(usize::from(row)).copied().unwrap_or_default();
+                let part = ansi::slice(line, usize::from(x), width);
+                let padding = width.saturating_sub(visible_width(&part));
+                self.render_buffer.push_str(&part);
+                self.render_buffer.extend(std::iter::repeat_n(' ', padding));
+            }
+            self.pending
+                .extend_from_slice(self.render_buffer.as_bytes());
+            if self.auto_flush {
+                self.flush_pending().await?;
+            }
+            return Ok(());
+        }
+
+        // Reserve space for the clear sequence plus content
+        let estimated_size = 8 + content.len() + content.chars().filter(|&c| c == '\n').count();
+        self.render_buffer.reserve(estimated_size);
+
+        // Move the cursor to the top-left corner and clear the screen
+        self.render_buffer.push_str("\x1b[H\x1b[2J");
+
+        push_frame(&mut self.render_buffer, content);
+
+        self.pending
+            .extend_from_slice(self.render_buffer.as_bytes());
+        if self.auto_flush {
+            self.flush_pending().await?;
+        }
+        Ok(())
+    }
+
+    async fn render_rows(&mut self, content: &str, rows: &[Range<u16>]) -> Result<(), Error> {
+        // The live region is repainted as a whole, except on the
+        // alternate screen, and a line printer prints whole frames
+        if self.line_printer || (self.log_region.is_some() && !self.alt_screen) {
+            return self.render(content, RenderHint::Full).await;
+        }
+
+        let lines: Vec<&str> = content.split('\n').collect();
+        self.render_buffer.clear();
+        for range in rows {
+            for row in range.clone() {
+                // Move to the start of the row and clear it before writing
+                self.render_buffer
+                    .push_str(&format!("\x1b[{};1H\x1b[2K", u32::from(row) + 1));
+                if let Some(line) = lines.get(usize::from(row)) {
+                    self.render_buffer.push_str(line);
+                }
+            }
+        }
+
+        self.pending
+            .extend_from_slice(self.render_buffer.as_bytes());
+        if self.auto_flush {
+            self.flush_pending().await?;
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> Result<(u16, u16), Error> {
+        let (size, source) = Self::resolved_size(self.default_size);
+        #[cfg(feature = "logging")]
+        if source != SizeSource::Terminal {
+            log::debug!(
+                "terminal size unavailable, using {}x{} from {:?}",
+                size.0,
+                size.1,
+                source
+            );
+        }
+        #[cfg(not(feature = "logging"))]
+        let _ = source;
+        Ok(size)
+    }
+
+    fn device(&self) -> Option<&str> {
+        // Raw mode always applies to the controlling terminal, even when
+        // output goes to a custom writer
+        Some(CONTROLLING_TERMINAL)
     }
 }
 
 impl Drop for Terminal {
     fn drop(&mut self) {
+        if !self.pending.is_empty() && self.output_writer.is_none() {
+            let _ = self.stream.write_all(&self.pending);
+            let _ = self.stream.flush();
+        }
+        if self.synchronized_output && self.output_writer.is_none() {
+            let _ = self.stream.write_all(b"\x1b[?2026l");
+            let _ = self.stream.flush();
+        }
         if !self.cursor_visible {
-            let _ = execute!(io::stdout(), Show);
+            let _ = execute!(self.stream, Show);
+        }
+        if self.reverse_video && self.output_writer.is_none() {
+            let _ = self.stream.write_all(b"\x1b[?5l");
+            let _ = self.stream.flush();
         }
         if self.mouse_enabled {
-            let _ = execute!(io::stdout(), DisableMouseCapture);
+            let _ = execute!(self.stream, DisableMouseCapture);
         }
         if self.focus_reporting {
-            let _ = execute!(io::stdout(), DisableFocusChange);
+            let _ = execute!(self.stream, DisableFocusChange);
         }
-        if self.alt_screen {
-            let _ = execute!(io::stdout(), LeaveAlternateScreen);
-            let _ = io::stdout().flush();
+        if self.keyboard_enhancement {
+            let _ = execute!(self.stream, PopKeyboardEnhancementFlags);
+        }
+        if self.alt_screen && self.output_writer.is_none() {
+            let _ = self.stream.write_all(b"\x1b[?1049l");
+            let _ = self.stream.flush();
         }
         if self.raw_mode {
             let _ = terminal::disable_raw_mode();
@@ -623,13 +1764,19 @@ impl Drop for Terminal {
 /// # Behavior
 ///
 /// - All terminal control methods return success without doing anything
-/// - `render()` writes to the output writer if provided, otherwise does nothing
-/// - `size()` returns `(0, 0)` as a placeholder
+/// - `render()`, `print()`, `bell()` and `clear_to_end_of_screen()` write to the output writer
+///   if provided, otherwise do nothing
+/// - `render_rows()` writes only the rewritten rows, one per line
+/// - Synchronized output, cursor save/restore, render and print calls are recorded in
+///   [`events`](DummyTerminal::events)
+/// - `flush()` only counts calls, see [`flush_count`](DummyTerminal::flush_count)
+/// - `size()` returns the size set with [`with_size`](DummyTerminal::with_size), or `(0, 0)`
+///   (unknown) by default
 ///
 /// # Example
 ///
 /// ```rust
-/// use bubbletea_rs::terminal::{DummyTerminal, TerminalInterface};
+/// use bubbletea_rs::terminal::{DummyTerminal, RenderHint, TerminalInterface};
 /// use bubbletea_rs::Error;
 /// use std::sync::Arc;
 /// use tokio::sync::Mutex;
@@ -641,12 +1788,271 @@ impl Drop for Terminal {
 /// // These all succeed but do nothing
 /// dummy.enter_raw_mode().await?;
 /// dummy.hide_cursor().await?;
-/// dummy.render("This won't be displayed").await?;
+/// dummy.render("This won't be displayed", RenderHint::Full).await?;
 /// # Ok(())
 /// # }
 /// ```
 pub struct DummyTerminal {
     output_writer: Option<Arc<Mutex<dyn AsyncWrite + Send + Unpin>>>,
+    capabilities: TerminalCapabilities,
+    events: Arc<std::sync::Mutex<Vec<DummyTerminalEvent>>>,
+    window_titles: WindowTitles,
+    title_log: Arc<std::sync::Mutex<Vec<String>>>,
+    last_frame: Option<String>,
+    event_sender: Option<EventSender>,
+    flushes: Arc<AtomicUsize>,
+    last_damage: Option<Vec<Range<u16>>>,
+    last_render_hint: RenderHint,
+    device: Option<String>,
+    size: (u16, u16),
+    accepts_keyboard_enhancement: bool,
+    keyboard_enhancement: Arc<AtomicBool>,
+    write_delay: Option<std::time::Duration>,
+    rejected_modes: Vec<TerminalMode>,
+    refreshed_capabilities: Option<TerminalCapabilities>,
+    line_printer: Arc<AtomicBool>,
+}
+
+/// A call recorded by [`DummyTerminal`].
+///
+/// Only calls whose ordering matters to tests are recorded: rendering and
+/// printing, the synchronized output and cursor save/restore brackets
+/// around them, alternate screen switches, focus reporting, cursor key
+/// modes, partial clears, and window title changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DummyTerminalEvent {
+    /// `enter_synchronized_output` was called.
+    EnterSynchronizedOutput,
+    /// `render` was called.
+    Render,
+    /// `render_rows` was called.
+    RenderRows,
+    /// `exit_synchronized_output` was called.
+    ExitSynchronizedOutput,
+    /// `save_cursor` was called.
+    SaveCursor,
+    /// `print` was called.
+    Print,
+    /// `restore_cursor` was called.
+    RestoreCursor,
+    /// `set_window_title` was called.
+    SetWindowTitle,
+    /// `push_window_title` was called.
+    PushWindowTitle,
+    /// `pop_window_title` was called with a pushed title to restore.
+    PopWindowTitle,
+    /// `clear_to_end_of_screen` was called.
+    ClearToEndOfScreen,
+    /// `clear_line` was called.
+    ClearLine,
+    /// `clear_from_start_of_screen` was called.
+    ClearFromStartOfScreen,
+    /// `enter_alt_screen` was called.
+    EnterAltScreen,
+    /// `exit_alt_screen` was called.
+    ExitAltScreen,
+    /// `enable_focus_reporting` was called.
+    EnableFocusReporting,
+    /// `disable_focus_reporting` was called.
+    DisableFocusReporting,
+    /// `set_cursor_key_mode` was called with this mode.
+    SetCursorKeyMode(CursorKeyMode),
+}
+
+impl DummyTerminal {
+    /// Sets the capabilities this terminal reports.
+    ///
+    /// A new `DummyTerminal` reports no optional capabilities.
+    pub fn with_capabilities(mut self, capabilities: TerminalCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Makes the next [`refresh_capabilities`](TerminalInterface::refresh_capabilities)
+    /// call switch this terminal to `capabilities`, as if the session had
+    /// moved to a different terminal.
+    pub fn with_refreshed_capabilities(mut self, capabilities: TerminalCapabilities) -> Self {
+        self.refreshed_capabilities = Some(capabilities);
+        self
+    }
+
+    /// Makes every `render` and `render_rows` call take `delay` before it
+    /// writes, like a terminal behind a slow link.
+    pub fn with_write_delay(mut self, delay: std::time::Duration) -> Self {
+        self.write_delay = Some(delay);
+        self
+    }
+
+    /// Makes this terminal report a size of `width` columns by `height`
+    /// rows, as a real terminal of that size would.
+    pub fn with_size(mut self, width: u16, height: u16) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// Makes this terminal report `name` as its
+    /// [`device`](TerminalInterface::device), as if it drove a shared
+    /// terminal. Programs using dummy terminals with the same device cannot
+    /// run at the same time.
+    pub fn with_device(mut self, name: impl Into<String>) -> Self {
+        self.device = Some(name.into());
+        self
+    }
+
+    /// Makes this terminal accept the keyboard enhancement protocol, as a
+    /// terminal implementing it would.
+    ///
+    /// A new `DummyTerminal` does not support the protocol.
+    pub fn with_keyboard_enhancement(mut self) -> Self {
+        self.accepts_keyboard_enhancement = true;
+        self
+    }
+
+    /// Makes enabling `mode` fail with `Error::Terminal`, as on a console
+    /// that does not support it.
+    pub fn rejecting_mode(mut self, mode: TerminalMode) -> Self {
+        self.rejected_modes.push(mode);
+        self
+    }
+
+    fn check_mode(&self, mode: TerminalMode) -> Result<(), Error> {
+        if self.rejected_modes.contains(&mode) {
+            return Err(Error::Terminal(format!("{mode:?} is not supported")));
+        }
+        Ok(())
+    }
+
+    /// Returns a handle to whether the terminal was set to print lines, as
+    /// the `Program` does in accessible mode, that stays readable after the
+    /// terminal has been handed to a `Program`.
+    pub fn line_printer_state(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.line_printer)
+    }
+
+    /// Returns a handle to whether the keyboard enhancement protocol is
+    /// enabled that stays readable after the terminal has been handed to a
+    /// `Program`.
+    pub fn keyboard_enhancement_state(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.keyboard_enhancement)
+    }
+
+    /// Returns the recorded calls, oldest first.
+    pub fn events(&self) -> Vec<DummyTerminalEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Returns a handle to the recorded calls that stays readable after the
+    /// terminal has been handed to a `Program`.
+    pub fn event_log(&self) -> Arc<std::sync::Mutex<Vec<DummyTerminalEvent>>> {
+        Arc::clone(&self.events)
+    }
+
+    fn record(&self, event: DummyTerminalEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    /// Returns the window title most recently set, pushed, or restored by a
+    /// pop, or `None` if the program has not set one.
+    pub fn window_title(&self) -> Option<&str> {
+        self.window_titles.current.as_deref()
+    }
+
+    /// Returns a handle to every title passed to `set_window_title`, oldest
+    /// first, that stays readable after the terminal has been handed to a
+    /// `Program`.
+    pub fn title_log(&self) -> Arc<std::sync::Mutex<Vec<String>>> {
+        Arc::clone(&self.title_log)
+    }
+
+    /// Connects this terminal to an event queue, so input can be simulated
+    /// with [`send`](Self::send).
+    pub fn with_event_sender(mut self, sender: EventSender) -> Self {
+        self.event_sender = Some(sender);
+        self
+    }
+
+    /// Returns the event queue this terminal is connected to, if any.
+    pub fn event_sender(&self) -> Option<&EventSender> {
+        self.event_sender.as_ref()
+    }
+
+    /// Sends a message to this terminal's event queue, as if it had been
+    /// produced by terminal input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ChannelSend` if the terminal has no event queue or the
+    /// queue has been closed.
+    pub fn send(&self, msg: Msg) -> Result<(), Error> {
+        match &self.event_sender {
+            Some(sender) => sender.send(msg),
+            None => Err(Error::ChannelSend),
+        }
+    }
+
+    /// Returns how many messages are waiting in this terminal's event queue.
+    ///
+    /// A message counts until the program takes it off the queue, so this
+    /// shows whether `update` keeps up with injected input. Only bounded
+    /// queues, such as the one behind a default
+    /// [`Program::sender`](crate::Program::sender), report their length;
+    /// an unbounded queue, or no queue at all, reads as empty.
+    pub fn input_queue_len(&self) -> usize {
+        self.event_sender
+            .as_ref()
+            .and_then(EventSender::queued)
+            .unwrap_or(0)
+    }
+
+    /// Waits until [`input_queue_len`](Self::input_queue_len) drops to zero,
+    /// checking every millisecond.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Elapsed` if messages are still queued after `timeout`.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn wait_until_idle(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Result<(), tokio::time::error::Elapsed> {
+        tokio::time::timeout(timeout, async {
+            while self.input_queue_len() > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+        })
+        .await
+    }
+
+    /// Returns the content of the most recent `render` call.
+    pub fn last_frame(&self) -> Option<&str> {
+        self.last_frame.as_deref()
+    }
+
+    /// Returns the rows passed to the most recent `render_rows` call, or
+    /// `None` if the most recent frame was a full `render`.
+    pub fn last_damage(&self) -> Option<&[Range<u16>]> {
+        self.last_damage.as_deref()
+    }
+
+    /// Returns the hint passed to the most recent `render` call.
+    ///
+    /// This is [`RenderHint::Full`] before the first frame and after a
+    /// `render_rows` call.
+    pub fn last_render_hint(&self) -> RenderHint {
+        self.last_render_hint
+    }
+
+    /// Returns how many times `flush` has been called.
+    pub fn flush_count(&self) -> usize {
+        self.flushes.load(Ordering::SeqCst)
+    }
+
+    /// Returns a handle to the flush count that stays readable after the
+    /// terminal has been handed to a `Program` with
+    /// [`ProgramBuilder::terminal`](crate::ProgramBuilder::terminal).
+    pub fn flush_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.flushes)
+    }
 }
 
 #[async_trait::async_trait]
@@ -654,7 +2060,26 @@ impl TerminalInterface for DummyTerminal {
     fn new(
         output_writer: Option<Arc<Mutex<dyn AsyncWrite + Send + Unpin>>>,
     ) -> Result<Self, Error> {
-        Ok(Self { output_writer })
+        Ok(Self {
+            output_writer,
+            capabilities: TerminalCapabilities::default(),
+            events: Arc::default(),
+            window_titles: WindowTitles::default(),
+            title_log: Arc::default(),
+            last_frame: None,
+            event_sender: None,
+            flushes: Arc::new(AtomicUsize::new(0)),
+            last_damage: None,
+            last_render_hint: RenderHint::Full,
+            device: None,
+            size: (0, 0),
+            accepts_keyboard_enhancement: false,
+            keyboard_enhancement: Arc::new(AtomicBool::new(false)),
+            write_delay: None,
+            rejected_modes: Vec::new(),
+            refreshed_capabilities: None,
+            line_printer: Arc::new(AtomicBool::new(false)),
+        })
     }
     async fn enter_raw_mode(&mut self) -> Result<(), Error> {
         Ok(())
@@ -663,31 +2088,37 @@ impl TerminalInterface for DummyTerminal {
         Ok(())
     }
     async fn enter_alt_screen(&mut self) -> Result<(), Error> {
+        self.check_mode(TerminalMode::AltScreen)?;
+        self.record(DummyTerminalEvent::EnterAltScreen);
         Ok(())
     }
     async fn exit_alt_screen(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::ExitAltScreen);
         Ok(())
     }
     async fn enable_mouse(&mut self) -> Result<(), Error> {
-        Ok(())
+        self.check_mode(TerminalMode::Mouse)
     }
     async fn enable_mouse_cell_motion(&mut self) -> Result<(), Error> {
-        Ok(())
+        self.check_mode(TerminalMode::Mouse)
     }
     async fn enable_mouse_all_motion(&mut self) -> Result<(), Error> {
-        Ok(())
+        self.check_mode(TerminalMode::Mouse)
     }
     async fn disable_mouse(&mut self) -> Result<(), Error> {
         Ok(())
     }
     async fn enable_focus_reporting(&mut self) -> Result<(), Error> {
+        self.check_mode(TerminalMode::FocusReporting)?;
+        self.record(DummyTerminalEvent::EnableFocusReporting);
         Ok(())
     }
     async fn disable_focus_reporting(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::DisableFocusReporting);
         Ok(())
     }
     async fn enable_bracketed_paste(&mut self) -> Result<(), Error> {
-        Ok(())
+        self.check_mode(TerminalMode::BracketedPaste)
     }
     async fn disable_bracketed_paste(&mut self) -> Result<(), Error> {
         Ok(())
@@ -701,15 +2132,322 @@ impl TerminalInterface for DummyTerminal {
     async fn clear(&mut self) -> Result<(), Error> {
         Ok(())
     }
-    async fn render(&mut self, content: &str) -> Result<(), Error> {
+    async fn clear_to_end_of_screen(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::ClearToEndOfScreen);
         if let Some(writer) = &mut self.output_writer {
             use tokio::io::AsyncWriteExt;
-            writer.lock().await.write_all(content.as_bytes()).await?;
+            writer.lock().await.write_all(b"\x1b[J").await?;
+            writer.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+    async fn clear_line(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::ClearLine);
+        if let Some(writer) = &mut self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            writer.lock().await.write_all(b"\x1b[K").await?;
+            writer.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+    async fn clear_from_start_of_screen(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::ClearFromStartOfScreen);
+        if let Some(writer) = &mut self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            writer.lock().await.write_all(b"\x1b[1J").await?;
+            writer.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+    async fn bell(&mut self) -> Result<(), Error> {
+        if let Some(writer) = &mut self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            writer.lock().await.write_all(b"\x07").await?;
+            writer.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+    async fn enable_keyboard_enhancement(&mut self) -> Result<bool, Error> {
+        self.check_mode(TerminalMode::KeyboardEnhancement)?;
+        if self.accepts_keyboard_enhancement {
+            self.keyboard_enhancement.store(true, Ordering::SeqCst);
+        }
+        Ok(self.accepts_keyboard_enhancement)
+    }
+    async fn disable_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        self.keyboard_enhancement.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+    async fn set_reverse_video(&mut self, _enabled: bool) -> Result<(), Error> {
+        Ok(())
+    }
+    async fn set_cursor_key_mode(&mut self, mode: CursorKeyMode) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::SetCursorKeyMode(mode));
+        Ok(())
+    }
+    async fn enter_synchronized_output(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::EnterSynchronizedOutput);
+        Ok(())
+    }
+    async fn exit_synchronized_output(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::ExitSynchronizedOutput);
+        Ok(())
+    }
+    async fn flush(&mut self) -> Result<(), Error> {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+    fn capabilities(&self) -> TerminalCapabilities {
+        self.capabilities
+    }
+    fn refresh_capabilities(&mut self) {
+        if let Some(capabilities) = self.refreshed_capabilities.take() {
+            self.capabilities = capabilities;
+        }
+    }
+    fn set_line_printer(&mut self, enabled: bool) {
+        self.line_printer.store(enabled, Ordering::SeqCst);
+    }
+    async fn save_cursor(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::SaveCursor);
+        Ok(())
+    }
+    async fn restore_cursor(&mut self) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::RestoreCursor);
+        Ok(())
+    }
+    async fn set_window_title(&mut self, title: &str) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::SetWindowTitle);
+        self.window_titles.set(title);
+        if let Ok(mut log) = self.title_log.lock() {
+            log.push(title.to_string());
+        }
+        Ok(())
+    }
+    async fn push_window_title(&mut self, title: &str) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::PushWindowTitle);
+        self.window_titles.push(title);
+        Ok(())
+    }
+    async fn pop_window_title(&mut self) -> Result<(), Error> {
+        if self.window_titles.pop().is_some() {
+            self.record(DummyTerminalEvent::PopWindowTitle);
+        }
+        Ok(())
+    }
+    fn window_title_depth(&self) -> usize {
+        self.window_titles.saved.len()
+    }
+    async fn print(&mut self, text: &str) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::Print);
+        if let Some(writer) = &mut self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            writer.lock().await.write_all(text.as_bytes()).await?;
+            writer.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+    async fn render(&mut self, content: &str, hint: RenderHint) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::Render);
+        self.last_frame = Some(content.to_string());
+        self.last_damage = None;
+        self.last_render_hint = hint;
+        if let Some(delay) = self.write_delay {
+            crate::runtime::sleep(delay).await;
+        }
+        if let Some(writer) = &mut self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            let written = match hint {
+                RenderHint::Full => content.to_string(),
+                // Write only the region, one row per line
+                RenderHint::Region(x, y, width, height) => {
+                    let lines: Vec<&str> = content.split('\n').collect();
+                    let mut region = String::new();
+                    for row in y..y.saturating_add(height) {
+                        let line = lines.get(usize::from(row)).copied().unwrap_or_default();
+                        region.push_str(&ansi::slice(line, usize::from(x), usize::from(width)));
+                        region.push('\n');
+                    }
+                    region
+                }
+            };
+            writer.lock().await.write_all(written.as_bytes()).await?;
+            writer.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+    async fn render_rows(&mut self, content: &str, rows: &[Range<u16>]) -> Result<(), Error> {
+        self.record(DummyTerminalEvent::RenderRows);
+        self.last_frame = Some(content.to_string());
+        self.last_damage = Some(rows.to_vec());
+        self.last_render_hint = RenderHint::Full;
+        if let Some(delay) = self.write_delay {
+            crate::runtime::sleep(delay).await;
+        }
+        if let Some(writer) = &mut self.output_writer {
+            use tokio::io::AsyncWriteExt;
+            // Write only the rewritten rows, one per line
+            let lines: Vec<&str> = content.split('\n').collect();
+            let mut rewritten = String::new();
+            for row in rows.iter().cloned().flatten() {
+                rewritten.push_str(lines.get(usize::from(row)).copied().unwrap_or_default());
+                rewritten.push('\n');
+            }
+            writer.lock().await.write_all(rewritten.as_bytes()).await?;
             writer.lock().await.flush().await?;
         }
         Ok(())
     }
     fn size(&self) -> Result<(u16, u16), Error> {
-        Ok((0, 0))
+        Ok(self.size)
+    }
+    fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+}
+
+/// A group of [`DummyTerminal`]s sharing one event queue, for testing
+/// models that drive several terminal regions, such as split views.
+///
+/// Every terminal in the pool sends to the same queue, so a message sent
+/// through any of them is seen by whoever drains the pool, in the order the
+/// messages were sent. Each terminal still records its own frames, and
+/// [`merge_frames`](Self::merge_frames) lays the last frames out side by
+/// side for snapshot assertions.
+///
+/// # Example
+///
+/// ```rust
+/// use bubbletea_rs::terminal::{DummyTerminalPool, RenderHint, TerminalInterface};
+/// use bubbletea_rs::Msg;
+///
+/// # async fn example() -> Result<(), bubbletea_rs::Error> {
+/// let mut pool = DummyTerminalPool::new(2);
+/// pool.terminal_mut(0).render("left\npanel", RenderHint::Full).await?;
+/// pool.terminal_mut(1).render("right", RenderHint::Full).await?;
+/// assert_eq!(pool.merge_frames(), "left  │ right\npanel │");
+///
+/// pool.terminal(1).send(Box::new("typed") as Msg)?;
+/// assert_eq!(pool.drain_messages().len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct DummyTerminalPool {
+    terminals: Vec<DummyTerminal>,
+    sender: EventSender,
+    receiver: tokio::sync::mpsc::UnboundedReceiver<Msg>,
+}
+
+impl DummyTerminalPool {
+    /// Separator placed between frames by [`merge_frames`](Self::merge_frames).
+    pub const SEPARATOR: &'static str = " │ ";
+
+    /// Creates `count` dummy terminals connected to a shared event queue.
+    pub fn new(count: usize) -> Self {
+        let (tx, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let sender = EventSender::from_unbounded(tx);
+        let terminals = (0..count)
+            .map(|_| {
+                DummyTerminal::new(None)
+                    .expect("creating a dummy terminal cannot fail")
+                    .with_event_sender(sender.clone())
+            })
+            .collect();
+        Self {
+            terminals,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Returns the number of terminals in the pool.
+    pub fn len(&self) -> usize {
+        self.terminals.len()
+    }
+
+    /// Returns whether the pool has no terminals.
+    pub fn is_empty(&self) -> bool {
+        self.terminals.is_empty()
+    }
+
+    /// Returns the terminal at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn terminal(&self, index: usize) -> &DummyTerminal {
+        &self.terminals[index]
+    }
+
+    /// Returns the terminal at `index` mutably, for rendering into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn terminal_mut(&mut self, index: usize) -> &mut DummyTerminal {
+        &mut self.terminals[index]
+    }
+
+    /// Returns all terminals, in creation order.
+    pub fn terminals(&self) -> &[DummyTerminal] {
+        &self.terminals
+    }
+
+    /// Returns all terminals mutably, in creation order.
+    pub fn terminals_mut(&mut self) -> &mut [DummyTerminal] {
+        &mut self.terminals
+    }
+
+    /// Returns a sender for the shared event queue.
+    pub fn sender(&self) -> EventSender {
+        self.sender.clone()
+    }
+
+    /// Removes and returns every message queued so far, oldest first.
+    pub fn drain_messages(&mut self) -> Vec<Msg> {
+        std::iter::from_fn(|| self.receiver.try_recv().ok()).collect()
+    }
+
+    /// Lays out the last frame of every terminal side by side.
+    ///
+    /// Frames are split into lines and each column is padded to the width
+    /// of its widest line, ignoring ANSI escape sequences, so the columns
+    /// line up. Columns are joined with [`SEPARATOR`](Self::SEPARATOR) and
+    /// trailing whitespace is trimmed from each row. Terminals that have not
+    /// rendered yet contribute an empty column.
+    pub fn merge_frames(&self) -> String {
+        let columns: Vec<Vec<&str>> = self
+            .terminals
+            .iter()
+            .map(|t| t.last_frame().unwrap_or_default().lines().collect())
+            .collect();
+        let widths: Vec<usize> = columns
+            .iter()
+            .map(|lines| lines.iter().map(|l| visible_width(l)).max().unwrap_or(0))
+            .collect();
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut merged = Vec::with_capacity(rows);
+        for row in 0..rows {
+            let mut line = String::new();
+            for (i, (lines, width)) in columns.iter().zip(&widths).enumerate() {
+                if i > 0 {
+                    line.push_str(Self::SEPARATOR);
+                }
+                let cell = lines.get(row).copied().unwrap_or_default();
+                line.push_str(cell);
+                line.extend(std::iter::repeat_n(' ', width - visible_width(cell)));
+            }
+            merged.push(line.trim_end().to_string());
+        }
+        merged.join("\n")
+    }
+}
+
+impl std::fmt::Debug for DummyTerminalPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DummyTerminalPool")
+            .field("terminals", &self.terminals.len())
+            .finish()
     }
 }
@@ -0,0 +1,164 @@
+//! File system watching for live-reloading content.
+//!
+//! This module backs the [`watch_path`](crate::command::watch_path) and
+//! [`watch_path_with_id`](crate::command::watch_path_with_id) commands. It is
+//! only available when the `watch` feature is enabled and uses the `notify`
+//! crate to receive file system notifications.
+//!
+//! Raw notifications are debounced before being delivered to the model: a
+//! burst of events (for example, an editor writing a file in several steps)
+//! collapses into a single [`WatchEvent`] per path and kind.
+
+use crate::event::{ErrorMsg, EventSender, Msg};
+use crate::Error;
+use notify::event::EventKind;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// A debounced file system change reported by a path watcher.
+///
+/// Each variant carries the path the change applies to. Access-only
+/// notifications (reads, opens) are not reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// A file or directory was created.
+    Created(PathBuf),
+    /// A file or directory was modified (content, metadata, or rename).
+    Modified(PathBuf),
+    /// A file or directory was removed.
+    Removed(PathBuf),
+}
+
+impl WatchEvent {
+    /// Returns the path this event applies to.
+    pub fn path(&self) -> &std::path::Path {
+        match self {
+            WatchEvent::Created(p) | WatchEvent::Modified(p) | WatchEvent::Removed(p) => p,
+        }
+    }
+}
+
+/// An internal message used to start a path watcher.
+///
+/// This is produced by `watch_path()` and handled by the `Program`, which
+/// registers the watcher under `watch_id` so it can be cancelled later.
+///
+/// # Note
+///
+/// This is not exposed as a public API and should not be used directly
+/// by application code. Use the `watch_path()` command function instead.
+pub struct WatchMsgInternal {
+    /// The path to watch (recursively, if it is a directory).
+    pub path: PathBuf,
+    /// Quiet period used to coalesce bursts of events.
+    pub debounce: Duration,
+    /// Function invoked for each debounced event producing a message.
+    pub func: Box<dyn Fn(WatchEvent) -> Msg + Send>,
+    /// Token used to cancel the running watcher.
+    pub cancellation_token: CancellationToken,
+    /// Unique identifier for this watcher instance.
+    pub watch_id: u64,
+}
+
+impl std::fmt::Debug for WatchMsgInternal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchMsgInternal")
+            .field("path", &self.path)
+            .field("debounce", &self.debounce)
+            .field("watch_id", &self.watch_id)
+            .field("func", &"<closure>")
+            .finish()
+    }
+}
+
+/// Converts a raw `notify` event into zero or more `WatchEvent`s.
+fn convert_event(event: notify::Event) -> Vec<WatchEvent> {
+    let ctor: fn(PathBuf) -> WatchEvent = match event.kind {
+        EventKind::Create(_) => WatchEvent::Created,
+        EventKind::Remove(_) => WatchEvent::Removed,
+        EventKind::Modify(_) => WatchEvent::Modified,
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => return Vec::new(),
+    };
+    event.paths.into_iter().map(ctor).collect()
+}
+
+/// Runs a watcher until `cancellation_token` is cancelled or the event
+/// channel is closed.
+///
+/// Setup failures and runtime watcher errors are delivered as `ErrorMsg`.
+pub(crate) async fn run_watcher(watch: WatchMsgInternal, event_tx: EventSender) {
+    let WatchMsgInternal {
+        path,
+        debounce,
+        func,
+        cancellation_token,
+        ..
+    } = watch;
+
+    let (raw_tx, mut raw_rx) = mpsc::unbounded_channel::<notify::Result<notify::Event>>();
+    let watcher = RecommendedWatcher::new(
+        move |res| {
+            let _ = raw_tx.send(res);
+        },
+        notify::Config::default(),
+    )
+    .and_then(|mut w| w.watch(&path, RecursiveMode::Recursive).map(|_| w));
+
+    // Keep the watcher alive for the duration of the task; dropping it stops notifications.
+    let _watcher = match watcher {
+        Ok(w) => w,
+        Err(e) => {
+            let _ = event_tx.send(Box::new(ErrorMsg(Error::CommandExecution(format!(
+                "failed to watch {}: {e}",
+                path.display()
+            )))) as Msg);
+            return;
+        }
+    };
+
+    let mut pending: Vec<WatchEvent> = Vec::new();
+    loop {
+        let next = if pending.is_empty() {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                res = raw_rx.recv() => res,
+            }
+        } else {
+            // Wait for the burst to settle before flushing pending events
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                res = crate::runtime::timeout(debounce, raw_rx.recv()) => match res {
+                    Some(res) => res,
+                    None => {
+                        for event in pending.drain(..) {
+                            if event_tx.send(func(event)).is_err() {
+                                return; // Receiver dropped
+                            }
+                        }
+                        continue;
+                    }
+                },
+            }
+        };
+
+        match next {
+            Some(Ok(event)) => {
+                for event in convert_event(event) {
+                    if !pending.contains(&event) {
+                        pending.push(event);
+                    }
+                }
+            }
+            Some(Err(e)) => {
+                let err = Error::CommandExecution(format!("file watcher error: {e}"));
+                if event_tx.send(Box::new(ErrorMsg(err)) as Msg).is_err() {
+                    return;
+                }
+            }
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, DummyTerminalEvent, IntoCmd, KeyMsg, Model, MouseMotion, Msg,
+    Program, QuitMsg, RenderHint, Terminal, TerminalCapabilities, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const CITIES: [&str; 3] = ["Tokyo", "Paris", "Lima"];
+
+/// A list of cities; `j` moves the selection down and announces it.
+struct Cities {
+    selected: usize,
+}
+
+impl Model for Cities {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { selected: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let key = msg.downcast_ref::<KeyMsg>()?;
+        if key.key != KeyCode::Char('j') {
+            return None;
+        }
+        self.selected = (self.selected + 1) % CITIES.len();
+        Some(command::announce(format!(
+            "Selected row {}: {}",
+            self.selected + 1,
+            CITIES[self.selected]
+        )))
+    }
+
+    fn view(&self) -> String {
+        CITIES
+            .iter()
+            .enumerate()
+            .map(|(i, city)| {
+                let cursor = if i == self.selected { ">" } else { " " };
+                format!("\x1b[1m{cursor}\x1b[0m {city}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn key(c: char) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[tokio::test]
+async fn test_line_printer_never_moves_the_cursor() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default())
+        .with_line_printer();
+
+    terminal.hide_cursor().await.unwrap();
+    terminal.enter_alt_screen().await.unwrap();
+    terminal.clear_to_end_of_screen().await.unwrap();
+    terminal
+        .render("\x1b[1m>\x1b[0m Tokyo\n  Paris\n", RenderHint::Full)
+        .await
+        .unwrap();
+    // Unchanged, so not printed again
+    terminal
+        .render(
+            "\x1b[1m>\x1b[0m Tokyo\n  Paris",
+            RenderHint::Region(0, 0, 8, 1),
+        )
+        .await
+        .unwrap();
+    terminal
+        .print_preserving_cursor("Selected row 2: \x1b[32mParis\x1b[0m\n")
+        .await
+        .unwrap();
+    terminal
+        .render_rows("  Tokyo\n> Paris", &[0..1, 1..2])
+        .await
+        .unwrap();
+    terminal.exit_alt_screen().await.unwrap();
+
+    let output = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(
+        output,
+        "> Tokyo\r\n  Paris\r\nSelected row 2: Paris\r\n  Tokyo\r\n> Paris\r\n"
+    );
+    assert!(!output.contains('\x1b'));
+}
+
+/// Runs `Cities` on a dummy terminal, sending `keys` and then quitting.
+/// Returns the output and the terminal's recorded calls.
+async fn run(accessible: bool, keys: &str) -> (String, Vec<DummyTerminalEvent>, bool) {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone())).unwrap();
+    let events = terminal.event_log();
+    let line_printer = terminal.line_printer_state();
+    let program = Program::<Cities>::builder()
+        .signal_handler(false)
+        .alt_screen(true)
+        .mouse_motion(MouseMotion::Cell)
+        .accessible(accessible)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    for c in keys.chars() {
+        sender.send(key(c)).unwrap();
+    }
+    let quit = async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), quit)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    let output = String::from_utf8(output.lock().await.clone()).unwrap();
+    let events = events.lock().unwrap().clone();
+    (output, events, line_printer.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn test_announcements_are_printed_in_order_in_accessible_mode() {
+    let (output, events, line_printer) = run(true, "jjj").await;
+
+    assert!(line_printer);
+    assert!(!events.contains(&DummyTerminalEvent::EnterAltScreen));
+    // The dummy terminal writes frames without a final newline, so an
+    // announcement may share a line with the frame before it
+    let positions: Vec<_> = [
+        "Selected row 2: Paris\n",
+        "Selected row 3: Lima\n",
+        "Selected row 1: Tokyo\n",
+    ]
+    .iter()
+    .map(|line| output.find(line).expect("announced"))
+    .collect();
+    assert!(positions.is_sorted());
+}
+
+#[tokio::test]
+async fn test_announcements_are_dropped_otherwise() {
+    let (output, events, line_printer) = run(false, "jj").await;
+
+    assert!(!line_printer);
+    assert!(events.contains(&DummyTerminalEvent::EnterAltScreen));
+    assert!(!output.contains("Selected"));
+}
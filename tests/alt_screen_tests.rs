@@ -0,0 +1,239 @@
+use bubbletea_rs::{
+    enter_alt_screen, exit_alt_screen, Cmd, ColorProfile, DummyTerminal, DummyTerminalEvent,
+    IntoCmd, Model, Msg, Program, RenderHint, SizeSource, Terminal, TerminalCapabilities,
+    TerminalInterface,
+};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+
+const SYNC: TerminalCapabilities = TerminalCapabilities {
+    synchronized_output: true,
+    title_stack: false,
+    bracketed_paste: false,
+    size_source: SizeSource::Terminal,
+    color_profile: ColorProfile::Ansi,
+};
+
+/// Records every write separately, so tests can see what went out together.
+#[derive(Clone, Default)]
+struct Writes(Arc<std::sync::Mutex<Vec<Vec<u8>>>>);
+
+impl Writes {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+            .into_iter()
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+            .collect()
+    }
+}
+
+impl AsyncWrite for Writes {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.0.lock().unwrap().push(buf.to_vec());
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn terminal(writes: &Writes, capabilities: TerminalCapabilities) -> Terminal {
+    Terminal::new(Some(Arc::new(tokio::sync::Mutex::new(writes.clone()))))
+        .unwrap()
+        .with_capabilities(capabilities)
+}
+
+#[tokio::test]
+async fn test_terminal_enters_alt_screen_in_the_same_write_as_the_frame() {
+    let writes = Writes::default();
+    let mut terminal = terminal(&writes, SYNC);
+    terminal.set_auto_flush(false);
+
+    terminal.enter_synchronized_output().await.unwrap();
+    terminal.enter_alt_screen().await.unwrap();
+    terminal
+        .render_frame("frame", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal.flush().await.unwrap();
+
+    assert_eq!(
+        writes.take(),
+        ["\x1b[?2026h\x1b[?1049h\x1b[2J\x1b[H\x1b[2Jframe\x1b[?2026l"]
+    );
+}
+
+#[tokio::test]
+async fn test_terminal_alt_screen_clear_can_be_turned_off() {
+    let writes = Writes::default();
+    let mut terminal = terminal(&writes, TerminalCapabilities::default());
+    terminal.set_auto_flush(false);
+    terminal.set_clear_on_alt_screen_enter(false);
+
+    terminal.enter_alt_screen().await.unwrap();
+    terminal.render("frame", RenderHint::Full).await.unwrap();
+    terminal.flush().await.unwrap();
+    terminal.exit_alt_screen().await.unwrap();
+
+    assert_eq!(
+        writes.take(),
+        ["\x1b[?1049h\x1b[H\x1b[2Jframe", "\x1b[?1049l"]
+    );
+}
+
+#[tokio::test]
+async fn test_inline_frame_resumes_after_leaving_alt_screen() {
+    // The same inline frames, with and without a trip to the alternate
+    // screen in between, must repaint the live region the same way
+    let direct = Writes::default();
+    let mut terminal_direct = terminal(&direct, TerminalCapabilities::default()).with_log_region(3);
+    terminal_direct
+        .render("one\ntwo", RenderHint::Full)
+        .await
+        .unwrap();
+    direct.take();
+    terminal_direct
+        .render("three", RenderHint::Full)
+        .await
+        .unwrap();
+
+    let toggled = Writes::default();
+    let mut terminal_toggled =
+        terminal(&toggled, TerminalCapabilities::default()).with_log_region(3);
+    terminal_toggled
+        .render("one\ntwo", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal_toggled.enter_alt_screen().await.unwrap();
+    terminal_toggled
+        .render("fullscreen", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal_toggled.exit_alt_screen().await.unwrap();
+    toggled.take();
+    terminal_toggled
+        .render("three", RenderHint::Full)
+        .await
+        .unwrap();
+
+    assert_eq!(toggled.take(), direct.take());
+}
+
+#[tokio::test]
+async fn test_leaving_alt_screen_redraws_the_inline_frame_in_place() {
+    let writes = Writes::default();
+    let mut terminal = terminal(&writes, TerminalCapabilities::default()).with_log_region(3);
+
+    let mut log = Vec::new();
+    for round in 0..3 {
+        let inline = format!("status {round}\nprogress");
+        terminal.render(&inline, RenderHint::Full).await.unwrap();
+        terminal.enter_alt_screen().await.unwrap();
+        terminal
+            .render("fullscreen preview", RenderHint::Full)
+            .await
+            .unwrap();
+        writes.take();
+        terminal.exit_alt_screen().await.unwrap();
+        log.extend(writes.take());
+    }
+
+    // Each return clears exactly the two-row region and repaints the frame
+    // that was showing, in the same write as leaving the alternate screen
+    assert_eq!(
+        log,
+        [
+            "\x1b[?1049l\r\x1b[1A\x1b[Jstatus 0\r\nprogress",
+            "\x1b[?1049l\r\x1b[1A\x1b[Jstatus 1\r\nprogress",
+            "\x1b[?1049l\r\x1b[1A\x1b[Jstatus 2\r\nprogress",
+        ]
+    );
+
+    // The next inline frame replaces the repainted one
+    terminal.render("done", RenderHint::Full).await.unwrap();
+    assert_eq!(writes.take(), ["\r\x1b[1A\x1b[Jdone"]);
+}
+
+/// Enters the alternate screen on the first message and quits on the
+/// second.
+struct Preview {
+    steps: usize,
+}
+
+impl Model for Preview {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { steps: 0 }, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {
+        self.steps += 1;
+        if self.steps == 1 {
+            Some(enter_alt_screen())
+        } else if self.steps == 2 {
+            Some(exit_alt_screen())
+        } else {
+            Some(bubbletea_rs::quit())
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("step {}", self.steps)
+    }
+}
+
+#[derive(Debug)]
+struct StepMsg;
+
+#[tokio::test]
+async fn test_program_switches_screens_inside_the_frame_bracket() {
+    let terminal = DummyTerminal::new(None).unwrap().with_capabilities(SYNC);
+    let events = terminal.event_log();
+    let program = Program::<Preview>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let steps = async {
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            sender.send(Box::new(StepMsg) as Msg).unwrap();
+        }
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), steps)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    use DummyTerminalEvent::*;
+    let events = events.lock().unwrap().clone();
+    let entered = events
+        .iter()
+        .position(|event| *event == EnterAltScreen)
+        .expect("alt screen entered");
+    // The block opened before the switch stays open until the frame is in
+    assert_eq!(events[entered - 1], EnterSynchronizedOutput);
+    let closed = entered
+        + events[entered..]
+            .iter()
+            .position(|event| *event == ExitSynchronizedOutput)
+            .unwrap();
+    assert!(events[entered..closed].contains(&Render));
+    assert!(events[closed..].contains(&ExitAltScreen));
+}
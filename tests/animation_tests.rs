@@ -0,0 +1,139 @@
+use bubbletea_rs::animation::{AnimationTickMsg, FrameAnimator};
+use bubbletea_rs::{BlurMsg, Cmd, FocusMsg, Model, Msg, Program};
+use std::time::Duration;
+
+fn frames() -> Vec<String> {
+    ["a", "b", "c"].map(String::from).to_vec()
+}
+
+async fn tick_of(cmd: Cmd) -> Msg {
+    let msg = cmd.await.expect("tick command produces a message");
+    assert!(msg.downcast_ref::<AnimationTickMsg>().is_some());
+    msg
+}
+
+#[test]
+fn test_advance_wraps_around() {
+    let mut animator = FrameAnimator::new(frames(), Duration::from_millis(10));
+    assert_eq!(animator.current(), "a");
+    animator.advance();
+    animator.advance();
+    assert_eq!(animator.current(), "c");
+    animator.advance();
+    assert_eq!(animator.current(), "a");
+
+    let mut empty = FrameAnimator::new(Vec::new(), Duration::from_millis(10));
+    empty.advance();
+    assert_eq!(empty.current(), "");
+}
+
+#[tokio::test]
+async fn test_handle_advances_on_own_ticks_only() {
+    let mut animator = FrameAnimator::new(frames(), Duration::from_millis(1));
+    let mut other = FrameAnimator::new(frames(), Duration::from_millis(1));
+    assert_ne!(animator.id(), other.id());
+
+    let tick = tick_of(animator.start()).await;
+    let other_tick = tick_of(other.start()).await;
+
+    assert!(animator.handle(&other_tick).is_none());
+    assert_eq!(animator.current(), "a");
+
+    let next = animator.handle(&tick).expect("reschedules");
+    assert_eq!(animator.current(), "b");
+    let tick = tick_of(next).await;
+    assert!(animator.handle(&tick).is_some());
+    assert_eq!(animator.current(), "c");
+
+    // Unrelated messages are ignored
+    assert!(animator.handle(&(Box::new(42u32) as Msg)).is_none());
+}
+
+#[tokio::test]
+async fn test_stop_discards_pending_ticks() {
+    let mut animator = FrameAnimator::new(frames(), Duration::from_millis(1));
+    let tick = tick_of(animator.start()).await;
+    animator.stop();
+    assert!(!animator.is_running());
+    assert!(animator.handle(&tick).is_none());
+    assert_eq!(animator.current(), "a");
+
+    // Restarting ignores ticks from the earlier run
+    let new_tick = tick_of(animator.start()).await;
+    assert!(animator.handle(&tick).is_none());
+    assert!(animator.handle(&new_tick).is_some());
+}
+
+#[tokio::test]
+async fn test_pauses_on_blur_and_resumes_on_focus() {
+    let mut animator = FrameAnimator::new(frames(), Duration::from_millis(1));
+    let tick = tick_of(animator.start()).await;
+
+    assert!(animator.handle(&(Box::new(BlurMsg) as Msg)).is_none());
+    assert!(animator.is_paused());
+    assert!(animator.handle(&tick).is_none());
+
+    let resumed = animator
+        .handle(&(Box::new(FocusMsg) as Msg))
+        .expect("focus resumes the animation");
+    assert!(!animator.is_paused());
+    let tick = tick_of(resumed).await;
+    assert!(animator.handle(&tick).is_some());
+    assert_eq!(animator.current(), "b");
+
+    // Focus changes don't start a stopped animator
+    animator.stop();
+    assert!(animator.handle(&(Box::new(BlurMsg) as Msg)).is_none());
+    assert!(animator.handle(&(Box::new(FocusMsg) as Msg)).is_none());
+}
+
+struct SpinnerModel {
+    spinner: FrameAnimator,
+    seen: Vec<String>,
+}
+
+impl Model for SpinnerModel {
+    fn init() -> (Self, Option<Cmd>) {
+        let mut spinner = FrameAnimator::new(frames(), Duration::from_millis(5));
+        let cmd = spinner.start();
+        (
+            Self {
+                spinner,
+                seen: Vec::new(),
+            },
+            Some(cmd),
+        )
+    }
+
+    #[allow(refining_impl_trait)]
+    fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        if !msg.is::<AnimationTickMsg>() {
+            return None;
+        }
+        let cmd = self.spinner.handle(&msg);
+        self.seen.push(self.spinner.current().to_string());
+        if self.seen.len() == 4 {
+            self.spinner.stop();
+            return Some(bubbletea_rs::quit());
+        }
+        cmd
+    }
+
+    fn view(&self) -> String {
+        self.spinner.current().to_string()
+    }
+}
+
+#[tokio::test]
+async fn test_animator_drives_program() {
+    let program = Program::<SpinnerModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("animation should tick")
+        .expect("program run");
+    assert_eq!(model.seen, ["b", "c", "a", "b"]);
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{command, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, TerminalInterface};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Counts how many instrumented commands have started, are running, and
+/// were running at once at most.
+#[derive(Default)]
+struct Gauge {
+    started: AtomicUsize,
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Gauge {
+    /// A command that holds a slot for `millis` and then reports `id`.
+    fn cmd(self: &Arc<Self>, id: usize, millis: u64) -> Cmd {
+        let gauge = Arc::clone(self);
+        Box::pin(async move {
+            gauge.started.fetch_add(1, Ordering::SeqCst);
+            let now = gauge.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            gauge.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(millis)).await;
+            gauge.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Some(Box::new(DoneMsg(id)) as Msg)
+        })
+    }
+}
+
+#[derive(Debug)]
+struct DoneMsg(usize);
+
+const FETCHES: usize = 10;
+const LIMIT: usize = 3;
+
+static FETCH_GAUGE: OnceLock<Arc<Gauge>> = OnceLock::new();
+
+/// Fetches `FETCHES` items, at most `LIMIT` at a time, and quits when all
+/// have arrived.
+struct Fetcher {
+    done: Vec<usize>,
+    started_at_first: usize,
+}
+
+impl Model for Fetcher {
+    fn init() -> (Self, Option<Cmd>) {
+        let gauge = FETCH_GAUGE.get_or_init(Arc::default);
+        // Later commands are quicker, so they finish out of order
+        let cmds = (0..FETCHES)
+            .map(|id| gauge.cmd(id, 10 * (FETCHES - id) as u64))
+            .collect();
+        let model = Self {
+            done: Vec::new(),
+            started_at_first: 0,
+        };
+        (model, Some(command::batch_limited(LIMIT, cmds)))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let DoneMsg(id) = *msg.downcast::<DoneMsg>().ok()?;
+        if self.done.is_empty() {
+            self.started_at_first = FETCH_GAUGE.get()?.started.load(Ordering::SeqCst);
+        }
+        self.done.push(id);
+        (self.done.len() == FETCHES).then(command::quit)
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_batch_limited_never_exceeds_the_limit() {
+    let (input, _keep_open) = tokio::io::duplex(64);
+    let program = Program::<Fetcher>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(input)
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let gauge = FETCH_GAUGE.get().unwrap();
+    assert_eq!(gauge.peak.load(Ordering::SeqCst), LIMIT);
+    assert_eq!(gauge.started.load(Ordering::SeqCst), FETCHES);
+    let mut done = model.done.clone();
+    done.sort_unstable();
+    assert_eq!(done, (0..FETCHES).collect::<Vec<_>>());
+    // Messages arrive as commands complete, not when the whole set is done
+    assert!(model.started_at_first < FETCHES);
+    assert_ne!(model.done, (0..FETCHES).collect::<Vec<_>>());
+}
+
+static SHUTDOWN_GAUGE: OnceLock<Arc<Gauge>> = OnceLock::new();
+
+/// Starts a slow, one-at-a-time batch and quits on the first result.
+struct Impatient;
+
+impl Model for Impatient {
+    fn init() -> (Self, Option<Cmd>) {
+        let gauge = SHUTDOWN_GAUGE.get_or_init(Arc::default);
+        let cmds = (0..5).map(|id| gauge.cmd(id, 100)).collect();
+        (Self, Some(command::batch_limited(1, cmds)))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        msg.is::<DoneMsg>().then(command::quit)
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_batch_limited_drops_queued_commands_on_shutdown() {
+    let (input, _keep_open) = tokio::io::duplex(64);
+    let program = Program::<Impatient>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(input)
+        .build()
+        .expect("program build");
+
+    tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let gauge = SHUTDOWN_GAUGE.get().unwrap();
+    let started = gauge.started.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    // The next command may have started before the quit was handled, but
+    // nothing starts after shutdown
+    assert!(started <= 2, "started {started} commands");
+    assert_eq!(gauge.started.load(Ordering::SeqCst), started);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_after_all_limited_collects_in_order_within_the_limit() {
+    let gauge = Arc::new(Gauge::default());
+    let cmds = (0..6)
+        .map(|id| gauge.cmd(id, 60 - 10 * id as u64))
+        .collect();
+    let cmd = command::after_all_limited(2, cmds, |msgs| Box::new(msgs) as Msg);
+
+    let msgs = cmd.await.unwrap().downcast::<Vec<Msg>>().unwrap();
+    let ids: Vec<usize> = msgs
+        .iter()
+        .map(|msg| msg.downcast_ref::<DoneMsg>().unwrap().0)
+        .collect();
+    assert_eq!(ids, [0, 1, 2, 3, 4, 5]);
+    assert_eq!(gauge.peak.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,165 @@
+use bubbletea_rs::{command, Cmd, Error, ErrorMsg, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct WorkDoneMsg;
+
+struct BlockingModel {
+    keys_before_done: usize,
+    keys_after_done: usize,
+    done: bool,
+}
+
+impl Model for BlockingModel {
+    fn init() -> (Self, Option<Cmd>) {
+        let cmd = command::blocking(|| {
+            // Simulates CPU-bound work that never yields to the runtime
+            std::thread::sleep(Duration::from_millis(500));
+            Box::new(WorkDoneMsg) as Msg
+        });
+        (
+            Self {
+                keys_before_done: 0,
+                keys_after_done: 0,
+                done: false,
+            },
+            Some(cmd),
+        )
+    }
+
+    #[allow(refining_impl_trait)]
+    fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        if msg.downcast_ref::<KeyMsg>().is_some() {
+            if self.done {
+                self.keys_after_done += 1;
+            } else {
+                self.keys_before_done += 1;
+            }
+        } else if msg.downcast_ref::<WorkDoneMsg>().is_some() {
+            self.done = true;
+            return Some(command::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_event_loop_processes_keys_during_blocking_work() {
+    let program = Program::<BlockingModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let key = KeyMsg {
+                key: KeyCode::Char('j'),
+                modifiers: KeyModifiers::NONE,
+            };
+            if sender.send(Box::new(key) as Msg).is_err() {
+                break;
+            }
+        }
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("blocking work should finish")
+        .expect("program run");
+
+    assert!(model.done);
+    assert_eq!(model.keys_before_done, 5);
+    assert_eq!(model.keys_after_done, 0);
+}
+
+#[tokio::test]
+async fn test_try_blocking_delivers_ok_message() {
+    let msg = command::try_blocking(|| Ok::<_, String>(Box::new(QuitMsg) as Msg))
+        .await
+        .unwrap();
+    assert!(msg.downcast_ref::<QuitMsg>().is_some());
+}
+
+#[tokio::test]
+async fn test_try_blocking_converts_error_to_error_msg() {
+    let msg = command::try_blocking(|| Err::<Msg, _>("disk on fire"))
+        .await
+        .unwrap();
+    let ErrorMsg(err) = msg.downcast_ref::<ErrorMsg>().expect("ErrorMsg");
+    assert!(matches!(err, Error::CommandExecution(s) if s == "disk on fire"));
+}
+
+#[tokio::test]
+async fn test_blocking_panics_are_caught() {
+    let msg = command::try_blocking(|| -> Result<Msg, String> { panic!("index out of range") })
+        .await
+        .unwrap();
+    let ErrorMsg(err) = msg.downcast_ref::<ErrorMsg>().expect("ErrorMsg");
+    assert!(matches!(err, Error::ProgramPanic(s) if s == "index out of range"));
+
+    let msg = command::blocking(|| panic!("boom")).await.unwrap();
+    let ErrorMsg(err) = msg.downcast_ref::<ErrorMsg>().expect("ErrorMsg");
+    assert!(matches!(err, Error::ProgramPanic(s) if s == "boom"));
+}
+
+/// Collects every panic report that reaches it.
+struct Panicking {
+    reports: Vec<String>,
+}
+
+impl Model for Panicking {
+    fn init() -> (Self, Option<Cmd>) {
+        let cmd = command::try_blocking(|| -> Result<Msg, String> { panic!("bad row") });
+        (
+            Self {
+                reports: Vec::new(),
+            },
+            Some(cmd),
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(ErrorMsg(err)) = msg.downcast_ref::<ErrorMsg>() {
+            self.reports.push(format!("ErrorMsg: {err}"));
+        } else if let Some(err) = msg.downcast_ref::<Error>() {
+            self.reports.push(format!("Error: {err}"));
+        }
+        if self.reports.is_empty() {
+            return None;
+        }
+        // Leave time for a second report to arrive
+        Some(command::tick(Duration::from_millis(100), |_| {
+            Box::new(QuitMsg) as Msg
+        }))
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_blocking_panic_is_reported_once() {
+    let program = Program::<Panicking>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .catch_panics(true)
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    assert_eq!(model.reports.len(), 1, "got {:?}", model.reports);
+    assert!(model.reports[0].starts_with("ErrorMsg:"));
+}
@@ -0,0 +1,82 @@
+use bubbletea_rs::{
+    CapabilitiesMsg, Cmd, ColorProfile, DummyTerminal, IntoCmd, Model, Msg, Program, QuitMsg,
+    TerminalCapabilities, TerminalInterface, WindowSizeMsg,
+};
+use std::time::Duration;
+
+/// Records every capabilities report it receives.
+struct Recorder {
+    reports: Vec<TerminalCapabilities>,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                reports: Vec::new(),
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(CapabilitiesMsg(capabilities)) = msg.downcast_ref::<CapabilitiesMsg>() {
+            self.reports.push(*capabilities);
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Runs a program whose terminal reports truecolor once it is detected
+/// again, and resizes it twice.
+async fn resize_twice(requery: bool) -> Vec<TerminalCapabilities> {
+    let truecolor = TerminalCapabilities {
+        color_profile: ColorProfile::TrueColor,
+        ..TerminalCapabilities::default()
+    };
+    let terminal = DummyTerminal::new(None)
+        .unwrap()
+        .with_size(80, 24)
+        .with_refreshed_capabilities(truecolor);
+    let program = Program::<Recorder>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .requery_capabilities_on_resize(requery)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        for width in [100, 120] {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let size = WindowSizeMsg { width, height: 30 };
+            sender.send(Box::new(size) as Msg).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run").reports
+}
+
+#[tokio::test]
+async fn test_resize_reports_changed_capabilities() {
+    let reports = resize_twice(true).await;
+
+    // Only the first resize changed anything
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].color_profile, ColorProfile::TrueColor);
+}
+
+#[tokio::test]
+async fn test_capabilities_are_not_requeried_by_default() {
+    assert!(resize_twice(false).await.is_empty());
+}
@@ -0,0 +1,98 @@
+use bubbletea_rs::{ChordMatch, ChordMatcher, KeyMsg};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Top,
+    DeleteLine,
+    WindowDown,
+}
+
+fn key(c: char) -> KeyMsg {
+    KeyMsg {
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+fn matcher() -> ChordMatcher<Action> {
+    ChordMatcher::new(Duration::from_millis(500))
+        .bind("g g", Action::Top)
+        .bind("d d", Action::DeleteLine)
+        .bind("ctrl+w j", Action::WindowDown)
+}
+
+#[test]
+fn test_chord_within_timeout_matches() {
+    let mut chords = matcher();
+    let start = Instant::now();
+
+    assert_eq!(chords.feed_at(&key('g'), start), ChordMatch::Pending);
+    assert!(chords.is_pending());
+    assert_eq!(
+        chords.feed_at(&key('g'), start + Duration::from_millis(400)),
+        ChordMatch::Matched(Action::Top)
+    );
+    assert!(!chords.is_pending());
+}
+
+#[test]
+fn test_mismatched_key_resets() {
+    let mut chords = matcher();
+    let start = Instant::now();
+
+    assert_eq!(chords.feed_at(&key('g'), start), ChordMatch::Pending);
+    assert_eq!(chords.feed_at(&key('x'), start), ChordMatch::NoMatch);
+    assert!(!chords.is_pending());
+    // The earlier `g` is forgotten
+    assert_eq!(chords.feed_at(&key('g'), start), ChordMatch::Pending);
+}
+
+#[test]
+fn test_late_key_does_not_match() {
+    let mut chords = matcher();
+    let start = Instant::now();
+
+    assert_eq!(chords.feed_at(&key('g'), start), ChordMatch::Pending);
+    // Too late to finish the chord, so it starts a new one
+    let late = start + Duration::from_millis(600);
+    assert_eq!(chords.feed_at(&key('g'), late), ChordMatch::Pending);
+    assert_eq!(
+        chords.feed_at(&key('g'), late + Duration::from_millis(100)),
+        ChordMatch::Matched(Action::Top)
+    );
+}
+
+#[test]
+fn test_mismatched_key_can_start_another_chord() {
+    let mut chords = matcher();
+    let start = Instant::now();
+
+    assert_eq!(chords.feed_at(&key('g'), start), ChordMatch::Pending);
+    assert_eq!(chords.feed_at(&key('d'), start), ChordMatch::Pending);
+    assert_eq!(
+        chords.feed_at(&key('d'), start),
+        ChordMatch::Matched(Action::DeleteLine)
+    );
+
+    let ctrl_w = KeyMsg {
+        key: KeyCode::Char('w'),
+        modifiers: KeyModifiers::CONTROL,
+    };
+    assert_eq!(chords.feed_at(&ctrl_w, start), ChordMatch::Pending);
+    assert_eq!(
+        chords.feed_at(&key('j'), start),
+        ChordMatch::Matched(Action::WindowDown)
+    );
+}
+
+#[test]
+fn test_try_bind_rejects_invalid_chords() {
+    assert!(ChordMatcher::new(Duration::from_millis(500))
+        .try_bind("  ", Action::Top)
+        .is_err());
+    assert!(ChordMatcher::new(Duration::from_millis(500))
+        .try_bind("g notakey", Action::Top)
+        .is_err());
+}
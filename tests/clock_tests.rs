@@ -0,0 +1,171 @@
+use bubbletea_rs::{
+    command, Clock, Cmd, DummyTerminal, IdleMsg, IntoCmd, MockClock, Model, Msg, Program, QuitMsg,
+    TerminalInterface,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct TickMsg;
+
+static TICKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Starts one ten-second tick and counts its messages.
+struct Waiting;
+
+impl Model for Waiting {
+    fn init() -> (Self, Option<Cmd>) {
+        let tick = command::tick(Duration::from_secs(10), |_| Box::new(TickMsg) as Msg);
+        (Self, Some(tick))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<TickMsg>() {
+            TICKS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Lets the program handle whatever is ready, in real time.
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(30)).await;
+}
+
+#[tokio::test]
+async fn test_mock_clock_fires_a_tick_once_its_deadline_passes() {
+    let clock = MockClock::new();
+    let program = Program::<Waiting>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .clock(Box::new(clock.clone()))
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        settle().await;
+        let mut seen = Vec::new();
+        for step in [9, 1, 10] {
+            clock.advance(Duration::from_secs(step));
+            settle().await;
+            seen.push(TICKS.load(Ordering::SeqCst));
+        }
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+        seen
+    };
+    let (result, seen) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    // Nothing before the deadline, then exactly one message
+    assert_eq!(seen, [0, 1, 1]);
+}
+
+#[derive(Debug)]
+struct PulseMsg;
+
+/// Runs a one-second `every` timer and counts its pulses and the idle
+/// messages it is sent.
+struct Pulsing {
+    pulses: usize,
+    idles: usize,
+}
+
+impl Model for Pulsing {
+    fn init() -> (Self, Option<Cmd>) {
+        let every = command::every(Duration::from_secs(1), |_| Box::new(PulseMsg) as Msg);
+        (
+            Self {
+                pulses: 0,
+                idles: 0,
+            },
+            Some(every),
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<PulseMsg>() {
+            self.pulses += 1;
+        } else if msg.is::<IdleMsg>() {
+            self.idles += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_mock_clock_drives_recurring_and_program_timers() {
+    let clock = MockClock::new();
+    let program = Program::<Pulsing>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .clock(Box::new(clock.clone()))
+        .idle_after(Duration::from_secs(10))
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        settle().await;
+        for _ in 0..3 {
+            clock.advance(Duration::from_secs(1));
+            settle().await;
+        }
+        // Missed pulses are skipped, and the quiet spell reaches ten seconds
+        clock.advance(Duration::from_secs(7));
+        settle().await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    let model = result.expect("program run");
+
+    assert_eq!(model.pulses, 4);
+    assert_eq!(model.idles, 1);
+}
+
+#[tokio::test]
+async fn test_mock_clock_only_moves_when_advanced() {
+    let clock = MockClock::new();
+    let start = clock.now();
+    let mut sleep = clock.sleep(Duration::from_secs(2));
+
+    clock.advance(Duration::from_secs(1));
+    assert!(futures::poll!(&mut sleep).is_pending());
+    clock.advance(Duration::from_secs(1));
+    assert!(futures::poll!(&mut sleep).is_ready());
+    assert_eq!(clock.now() - start, Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_mock_clock_keeps_sleeps_that_are_not_due() {
+    let clock = MockClock::new();
+    let mut early = clock.sleep(Duration::from_secs(1));
+    let mut late = clock.sleep(Duration::from_secs(3));
+    // Polling again only replaces the waker
+    for _ in 0..3 {
+        assert!(futures::poll!(&mut early).is_pending());
+        assert!(futures::poll!(&mut late).is_pending());
+    }
+
+    clock.advance(Duration::from_secs(2));
+    assert!(futures::poll!(&mut early).is_ready());
+    assert!(futures::poll!(&mut late).is_pending());
+    drop(late);
+    clock.advance(Duration::from_secs(2));
+}
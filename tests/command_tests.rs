@@ -1,7 +1,8 @@
 use bubbletea_rs::{
     event::{BatchCmdMsg, BatchMsgInternal},
-    Cmd, DisableReportFocusMsg, EnableMouseAllMotionMsg, EnableReportFocusMsg, InterruptMsg,
-    KeyMsg, Msg, PrintMsg, PrintfMsg, QuitMsg, SuspendMsg,
+    Cmd, DisableReportFocusMsg, DummyTerminal, EnableMouseAllMotionMsg, EnableReportFocusMsg,
+    InterruptMsg, IntoCmd, KeyMsg, Model, Msg, NoMsg, PrintMsg, PrintfMsg, Program, QuitMsg,
+    SuspendMsg, TerminalInterface,
 };
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::time::Duration;
@@ -105,6 +106,33 @@ async fn test_quit_command() {
     assert!(msg.downcast_ref::<QuitMsg>().is_some());
 }
 
+#[tokio::test]
+async fn test_quit_if_quits_when_check_passes() {
+    let cmd = bubbletea_rs::command::quit_if(async { true }, || Box::new(()) as Msg);
+    let msg = cmd.await.unwrap();
+    assert!(msg.downcast_ref::<QuitMsg>().is_some());
+}
+
+#[tokio::test]
+async fn test_quit_if_delivers_blocked_message_when_check_fails() {
+    #[derive(Debug, PartialEq)]
+    struct UnsavedChangesMsg(usize);
+
+    let cmd = bubbletea_rs::command::quit_if(
+        async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            false
+        },
+        || Box::new(UnsavedChangesMsg(3)) as Msg,
+    );
+    let msg = cmd.await.unwrap();
+    assert!(msg.downcast_ref::<QuitMsg>().is_none());
+    assert_eq!(
+        msg.downcast_ref::<UnsavedChangesMsg>(),
+        Some(&UnsavedChangesMsg(3))
+    );
+}
+
 #[tokio::test]
 async fn test_interrupt_command() {
     let cmd = bubbletea_rs::command::interrupt();
@@ -212,3 +240,207 @@ async fn test_printf_command() {
     let printf_msg = msg.downcast_ref::<PrintfMsg>().unwrap();
     assert_eq!(printf_msg.0, "Formatted: {}");
 }
+
+#[tokio::test]
+async fn test_every_precise_command() {
+    let (cmd, timer_id) = bubbletea_rs::command::every_precise_with_id(60.0, |_at| {
+        Box::new(KeyMsg {
+            key: KeyCode::Char('p'),
+            modifiers: KeyModifiers::NONE,
+        }) as Msg
+    });
+
+    let msg = cmd.await.unwrap();
+    let every_msg = msg
+        .downcast_ref::<bubbletea_rs::event::EveryPreciseMsgInternal>()
+        .unwrap();
+    assert_eq!(every_msg.timer_id, timer_id);
+    assert_eq!(every_msg.interval, Duration::from_secs_f64(1.0 / 60.0));
+}
+
+#[test]
+#[should_panic(expected = "target_fps")]
+fn test_every_precise_rejects_non_positive_fps() {
+    drop(bubbletea_rs::command::every_precise(0.0, |_at| {
+        Box::new(QuitMsg) as Msg
+    }));
+}
+
+/// Quits after five high precision frames.
+struct Frames {
+    frames: usize,
+}
+
+#[derive(Debug)]
+struct FrameMsg;
+
+impl Model for Frames {
+    fn init() -> (Self, Option<Cmd>) {
+        let frames = bubbletea_rs::command::every_precise(500.0, |_at| Box::new(FrameMsg) as Msg);
+        (Self { frames: 0 }, Some(frames))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<FrameMsg>() {
+            self.frames += 1;
+            if self.frames == 5 {
+                return Some(bubbletea_rs::quit());
+            }
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_high_precision_frames_share_a_single_threaded_runtime() {
+    let program = Program::<Frames>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .high_precision_timer(true)
+        .build()
+        .unwrap();
+    // The spin yields, so this task keeps running between frames
+    let yields = tokio::spawn(async {
+        let mut count = 0u64;
+        loop {
+            tokio::time::sleep(Duration::from_micros(100)).await;
+            count += 1;
+            if count == 5 {
+                return count;
+            }
+        }
+    });
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("program should quit")
+        .unwrap();
+    assert_eq!(model.frames, 5);
+    assert_eq!(yields.await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn test_bell_commands() {
+    let msg = bubbletea_rs::command::bell().await.unwrap();
+    assert!(msg.downcast_ref::<bubbletea_rs::BellMsg>().is_some());
+
+    let msg = bubbletea_rs::command::visual_bell().await.unwrap();
+    assert!(msg.downcast_ref::<bubbletea_rs::VisualBellMsg>().is_some());
+}
+
+#[tokio::test]
+async fn test_bell_writes_bel_byte_through_terminal() {
+    use bubbletea_rs::{Terminal, TerminalInterface};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone())).unwrap();
+    terminal.bell().await.unwrap();
+    assert_eq!(output.lock().await.as_slice(), b"\x07");
+
+    // Visual bell toggles reverse video and is idempotent
+    output.lock().await.clear();
+    terminal.set_reverse_video(true).await.unwrap();
+    terminal.set_reverse_video(true).await.unwrap();
+    terminal.set_reverse_video(false).await.unwrap();
+    assert_eq!(output.lock().await.as_slice(), b"\x1b[?5h\x1b[?5l");
+}
+
+#[derive(Debug)]
+struct SavedMsg(std::io::Result<()>);
+
+#[tokio::test]
+async fn test_write_file_reports_success_and_writes_contents() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("buffer.txt");
+
+    let cmd = bubbletea_rs::write_file(path.clone(), b"saved text".to_vec(), |result| {
+        Box::new(SavedMsg(result)) as Msg
+    });
+    let msg = cmd.await.expect("write_file delivers a message");
+
+    let saved = msg.downcast_ref::<SavedMsg>().expect("SavedMsg");
+    assert!(saved.0.is_ok());
+    assert_eq!(std::fs::read(&path).unwrap(), b"saved text");
+}
+
+#[tokio::test]
+async fn test_write_file_reports_errors() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("missing").join("buffer.txt");
+
+    let cmd =
+        bubbletea_rs::write_file(path, Vec::new(), |result| Box::new(SavedMsg(result)) as Msg);
+    let msg = cmd.await.expect("write_file delivers a message");
+
+    let saved = msg.downcast_ref::<SavedMsg>().expect("SavedMsg");
+    let err = saved.0.as_ref().expect_err("the directory does not exist");
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[derive(Debug)]
+struct JoinedMsg(Vec<Msg>);
+
+fn delayed(ms: u64, value: u32) -> Cmd {
+    Box::pin(async move {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        Some(Box::new(value) as Msg)
+    })
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_after_all_joins_in_command_order() {
+    let start = tokio::time::Instant::now();
+    let cmd = bubbletea_rs::command::after_all(
+        vec![delayed(30, 1), create_empty_cmd(), delayed(10, 3)],
+        |msgs| Box::new(JoinedMsg(msgs)) as Msg,
+    );
+
+    let msg = cmd.await.expect("after_all delivers one message");
+    // The commands ran concurrently, so only the slowest one counts
+    assert_eq!(start.elapsed(), Duration::from_millis(30));
+
+    let JoinedMsg(msgs) = *msg.downcast::<JoinedMsg>().unwrap();
+    assert_eq!(msgs.len(), 3);
+    assert_eq!(msgs[0].downcast_ref::<u32>(), Some(&1));
+    assert_eq!(msgs[1].downcast_ref::<NoMsg>(), Some(&NoMsg));
+    assert_eq!(msgs[2].downcast_ref::<u32>(), Some(&3));
+}
+
+#[tokio::test]
+async fn test_after_all_with_no_commands_completes_immediately() {
+    let cmd = bubbletea_rs::command::after_all(Vec::new(), |msgs| Box::new(JoinedMsg(msgs)) as Msg);
+    let msg = cmd.await.unwrap();
+    assert!(msg.downcast_ref::<JoinedMsg>().unwrap().0.is_empty());
+}
+
+#[tokio::test]
+async fn test_lazy_creates_command_when_polled() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    let width = Arc::new(AtomicU32::new(40));
+    let calls = Arc::new(AtomicU32::new(0));
+    let cmd = bubbletea_rs::command::lazy({
+        let width = Arc::clone(&width);
+        let calls = Arc::clone(&calls);
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            create_test_cmd(Box::new(width.load(Ordering::SeqCst)) as Msg)
+        }
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    // The state changes between creating the command and running it
+    width.store(120, Ordering::SeqCst);
+    let msg = cmd
+        .await
+        .expect("lazy delivers the created command's message");
+    assert_eq!(msg.downcast_ref::<u32>(), Some(&120));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,179 @@
+use bubbletea_rs::model::common::{CommonHandlers, Handled, ViewportSize};
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, KeyMsg, KeySpec, Model, Msg, Program, QuitMsg, TerminalInterface,
+    WindowSizeMsg,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+const QUIT_KEYS: &[KeySpec] = &[
+    KeySpec::new(KeyCode::Char('q'), KeyModifiers::NONE),
+    KeySpec::new(KeyCode::Esc, KeyModifiers::NONE),
+];
+
+/// Quits on `q` and `Esc`, optionally on `Ctrl+C`, and records the keys
+/// that reach its own handling.
+struct Viewer {
+    size: ViewportSize,
+    ctrl_c: bool,
+    keys: Vec<KeyCode>,
+}
+
+impl CommonHandlers for Viewer {
+    fn quit_keys(&self) -> &[KeySpec] {
+        QUIT_KEYS
+    }
+
+    fn quit_on_ctrl_c(&self) -> bool {
+        self.ctrl_c
+    }
+
+    fn viewport_size(&mut self) -> Option<&mut ViewportSize> {
+        Some(&mut self.size)
+    }
+}
+
+impl Model for Viewer {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            size: ViewportSize::default(),
+            ctrl_c: true,
+            keys: Vec::new(),
+        };
+        (model, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(handled) = self.handle_common(&msg) {
+            return handled.into_cmd();
+        }
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            self.keys.push(key_msg.key);
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Leaves everything to the defaults.
+struct Plain;
+
+impl CommonHandlers for Plain {}
+
+impl Model for Plain {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        self.handle_common(&msg).and_then(IntoCmd::into_cmd)
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn key(key: KeyCode, modifiers: KeyModifiers) -> Msg {
+    Box::new(KeyMsg { key, modifiers })
+}
+
+fn viewer(ctrl_c: bool) -> Viewer {
+    Viewer {
+        ctrl_c,
+        ..Viewer::init().0
+    }
+}
+
+#[tokio::test]
+async fn test_quit_keys_quit() {
+    for msg in [
+        key(KeyCode::Char('q'), KeyModifiers::NONE),
+        key(KeyCode::Esc, KeyModifiers::NONE),
+        key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+    ] {
+        let mut model = viewer(true);
+        assert_eq!(model.handle_common(&msg), Some(Handled::Quit));
+        let cmd = Handled::Quit.into_cmd().expect("quit command");
+        assert!(cmd.await.unwrap().is::<QuitMsg>());
+    }
+}
+
+#[test]
+fn test_other_keys_are_left_to_update() {
+    let mut model = viewer(true);
+    for msg in [
+        key(KeyCode::Char('x'), KeyModifiers::NONE),
+        key(KeyCode::Char('q'), KeyModifiers::CONTROL),
+        key(KeyCode::Char('c'), KeyModifiers::NONE),
+    ] {
+        assert_eq!(model.handle_common(&msg), None);
+    }
+}
+
+#[test]
+fn test_ctrl_c_can_be_handled_by_update() {
+    let mut model = viewer(false);
+    let ctrl_c = key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert_eq!(model.handle_common(&ctrl_c), None);
+    // The other quit keys still work
+    let q = key(KeyCode::Char('q'), KeyModifiers::NONE);
+    assert_eq!(model.handle_common(&q), Some(Handled::Quit));
+}
+
+#[test]
+fn test_window_size_is_captured() {
+    let mut model = viewer(true);
+    let msg: Msg = Box::new(WindowSizeMsg {
+        width: 120,
+        height: 40,
+    });
+    assert_eq!(model.handle_common(&msg), Some(Handled::Resized));
+    assert_eq!(
+        model.size,
+        ViewportSize {
+            width: 120,
+            height: 40
+        }
+    );
+    assert!(Handled::Resized.into_cmd().is_none());
+
+    // Without a place to store it, the size is left to update
+    assert_eq!(Plain.handle_common(&msg), None);
+}
+
+#[test]
+fn test_defaults_quit_on_ctrl_c_only() {
+    let ctrl_c = key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert_eq!(Plain.handle_common(&ctrl_c), Some(Handled::Quit));
+    let q = key(KeyCode::Char('q'), KeyModifiers::NONE);
+    assert_eq!(Plain.handle_common(&q), None);
+}
+
+#[tokio::test]
+async fn test_quit_key_stops_program() {
+    let (input, mut typed) = tokio::io::duplex(64);
+    let program = Program::<Viewer>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(input)
+        .build()
+        .expect("program build");
+
+    typed.write_all(b"abq\n").await.unwrap();
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("q should quit the program")
+        .expect("program run");
+
+    // `q` never reaches update; the Enter after it was already queued
+    // when the quit command ran
+    assert_eq!(
+        model.keys,
+        [KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Enter]
+    );
+}
@@ -0,0 +1,85 @@
+use bubbletea_rs::{
+    command, Cmd, CursorKeyMode, DummyTerminal, DummyTerminalEvent, ExecRequestMsg, IntoCmd, Model,
+    Msg, Program, QuitMsg, TerminalInterface,
+};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct EditorClosedMsg;
+
+/// Quits once an external "editor" has closed.
+struct Editor;
+
+impl Model for Editor {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        msg.is::<EditorClosedMsg>().then(command::quit)
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Runs a program with `mode` set, handling `msgs`, and returns the cursor
+/// key modes it set on the terminal.
+async fn cursor_key_modes(mode: Option<CursorKeyMode>, msgs: Vec<Msg>) -> Vec<CursorKeyMode> {
+    let terminal = DummyTerminal::new(None).unwrap();
+    let events = terminal.event_log();
+    let mut builder = Program::<Editor>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0);
+    if let Some(mode) = mode {
+        builder = builder.cursor_key_mode(mode);
+    }
+    let program = builder.build().expect("program build");
+    let sender = program.sender();
+    for msg in msgs {
+        sender.send(msg).unwrap();
+    }
+
+    tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let events = events.lock().unwrap();
+    events
+        .iter()
+        .filter_map(|event| match event {
+            DummyTerminalEvent::SetCursorKeyMode(mode) => Some(*mode),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_cursor_key_mode_is_set_at_startup_and_reset_on_exit() {
+    let quit = vec![Box::new(QuitMsg) as Msg];
+    let modes = cursor_key_modes(Some(CursorKeyMode::Application), quit).await;
+
+    assert_eq!(modes, [CursorKeyMode::Application, CursorKeyMode::Normal]);
+}
+
+#[tokio::test]
+async fn test_cursor_key_mode_is_left_alone_by_default() {
+    let quit = vec![Box::new(QuitMsg) as Msg];
+    assert!(cursor_key_modes(None, quit).await.is_empty());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_cursor_key_mode_is_reset_while_a_process_has_the_terminal() {
+    let editor = std::process::Command::new("true");
+    let edit = ExecRequestMsg::new(editor, |_| Box::new(EditorClosedMsg) as Msg);
+    let edit = vec![Box::new(edit) as Msg];
+    let modes = cursor_key_modes(Some(CursorKeyMode::Application), edit).await;
+
+    use CursorKeyMode::*;
+    // Startup, released to the editor, taken back, and exit
+    assert_eq!(modes, [Application, Normal, Application, Normal]);
+}
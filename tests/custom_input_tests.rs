@@ -1,6 +1,11 @@
-use bubbletea_rs::{InputHandler, InputSource, KeyMsg, Msg};
+use bubbletea_rs::{
+    command, event, Cmd, DummyTerminal, InputHandler, InputSource, IntoCmd, KeyMsg, Model, Msg,
+    PasteHeuristic, PasteMsg, Program, TerminalInterface,
+};
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::io::Cursor;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 
 #[tokio::test]
@@ -73,6 +78,36 @@ async fn test_custom_input_processing() {
     handle.abort();
 }
 
+#[tokio::test]
+async fn test_custom_input_decodes_cursor_keys_in_both_modes() {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let test_input = Cursor::new("\x1b[A\x1bOB\x1b[1;5C\x1bOHx");
+    let input_source = InputSource::Custom(Box::pin(test_input));
+    let input_handler = InputHandler::with_source(event_tx, input_source);
+    let handle = tokio::spawn(async move {
+        let _ = input_handler.run().await;
+    });
+
+    let mut keys = Vec::new();
+    for _ in 0..5 {
+        let msg = event_rx.recv().await.unwrap();
+        let key = msg.downcast_ref::<KeyMsg>().unwrap();
+        keys.push((key.key, key.modifiers));
+    }
+
+    assert_eq!(
+        keys,
+        [
+            (KeyCode::Up, KeyModifiers::NONE),
+            (KeyCode::Down, KeyModifiers::NONE),
+            (KeyCode::Right, KeyModifiers::CONTROL),
+            (KeyCode::Home, KeyModifiers::NONE),
+            (KeyCode::Char('x'), KeyModifiers::NONE),
+        ]
+    );
+    handle.abort();
+}
+
 #[tokio::test]
 async fn test_custom_input_empty_line() {
     let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
@@ -178,3 +213,266 @@ async fn test_custom_input_eof() {
     // Wait for the handler to complete
     let _ = handle.await;
 }
+
+#[tokio::test]
+async fn test_custom_input_sgr_mouse_reports() {
+    use bubbletea_rs::MouseMsg;
+    use crossterm::event::MouseEventKind;
+
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let test_input = Cursor::new("a\x1b[<66;3;4M\x1b[<67;3;4Mb\n");
+    let input_source = InputSource::Custom(Box::pin(test_input));
+    let input_handler = InputHandler::with_source(event_tx, input_source);
+    input_handler.run().await.unwrap();
+
+    let mut messages = Vec::new();
+    while let Ok(msg) = event_rx.try_recv() {
+        messages.push(msg);
+    }
+    assert_eq!(messages.len(), 5);
+
+    assert_eq!(
+        messages[0].downcast_ref::<KeyMsg>().unwrap().key,
+        KeyCode::Char('a')
+    );
+    let left = messages[1].downcast_ref::<MouseMsg>().unwrap();
+    assert_eq!(left.button, MouseEventKind::ScrollLeft);
+    assert_eq!((left.x, left.y), (2, 3));
+    let right = messages[2].downcast_ref::<MouseMsg>().unwrap();
+    assert_eq!(right.button, MouseEventKind::ScrollRight);
+    assert_eq!(
+        messages[3].downcast_ref::<KeyMsg>().unwrap().key,
+        KeyCode::Char('b')
+    );
+    assert_eq!(
+        messages[4].downcast_ref::<KeyMsg>().unwrap().key,
+        KeyCode::Enter
+    );
+}
+
+#[tokio::test]
+async fn test_custom_input_mouse_origin_is_zero_based() {
+    use bubbletea_rs::MouseMsg;
+
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let test_input = Cursor::new("\x1b[<0;1;1M\x1b[<0;1;1m");
+    let input_source = InputSource::Custom(Box::pin(test_input));
+    let input_handler = InputHandler::with_source(event_tx, input_source);
+    input_handler.run().await.unwrap();
+
+    for _ in 0..2 {
+        let msg = event_rx.try_recv().unwrap();
+        let mouse = msg.downcast_ref::<MouseMsg>().unwrap();
+        assert_eq!((mouse.column(), mouse.row()), (0, 0));
+    }
+}
+
+#[tokio::test]
+async fn test_paste_heuristic_coalesces_burst() {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let test_input = Cursor::new("pasted text\nsecond line\n");
+    let input_source = InputSource::Custom(Box::pin(test_input));
+    let input_handler = InputHandler::with_source(event_tx, input_source)
+        .with_paste_heuristic(10, Duration::from_millis(50));
+
+    input_handler.run().await.unwrap();
+
+    let msg = event_rx.recv().await.unwrap();
+    let paste = msg.downcast_ref::<PasteMsg>().expect("expected PasteMsg");
+    assert_eq!(paste.text(), "pasted text\nsecond line\n");
+    assert!(!paste.bracketed, "inferred pastes are not bracketed");
+    assert!(event_rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_paste_heuristic_passes_short_bursts_through() {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let (mut writer, reader) = tokio::io::duplex(64);
+    let input_handler = InputHandler::with_source(event_tx, InputSource::Custom(Box::pin(reader)))
+        .with_paste_heuristic(10, Duration::from_millis(50));
+    let handle = tokio::spawn(input_handler.run());
+
+    // Typed keys are held back for the burst window, then sent as keys
+    writer.write_all(b"hi\n").await.unwrap();
+    let mut keys = Vec::new();
+    for _ in 0..3 {
+        let msg = tokio::time::timeout(Duration::from_secs(1), event_rx.recv())
+            .await
+            .expect("keys flushed after the burst window")
+            .unwrap();
+        keys.push(msg.downcast_ref::<KeyMsg>().unwrap().key);
+    }
+    assert_eq!(
+        keys,
+        [KeyCode::Char('h'), KeyCode::Char('i'), KeyCode::Enter]
+    );
+
+    drop(writer);
+    handle.await.unwrap().unwrap();
+    assert!(event_rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_paste_heuristic_threshold_below_two_disables() {
+    let (event_tx, _event_rx) = mpsc::unbounded_channel::<Msg>();
+    let input_handler =
+        InputHandler::new(event_tx).with_paste_heuristic(1, Duration::from_millis(50));
+    assert!(input_handler.paste_heuristic.is_none());
+
+    let default = PasteHeuristic::default();
+    assert_eq!(default.burst_threshold, 10);
+    assert_eq!(default.burst_window, Duration::from_millis(50));
+}
+
+async fn decode_keys(input: &'static str, count: usize) -> Vec<KeyMsg> {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let input_source = InputSource::Custom(Box::pin(Cursor::new(input)));
+    let handle = tokio::spawn(InputHandler::with_source(event_tx, input_source).run());
+
+    let mut keys = Vec::new();
+    for _ in 0..count {
+        let msg = event_rx.recv().await.unwrap();
+        keys.push(msg.downcast_ref::<KeyMsg>().unwrap().clone());
+    }
+    handle.await.unwrap().unwrap();
+    assert!(event_rx.try_recv().is_err());
+    keys
+}
+
+#[tokio::test]
+async fn test_custom_input_legacy_enter_and_tab() {
+    // Legacy bytes: \r is always Enter and \t always Tab
+    let keys = decode_keys("\ta\rb\n", 5).await;
+    let codes: Vec<_> = keys.iter().map(|k| (k.key, k.modifiers)).collect();
+    assert_eq!(
+        codes,
+        [
+            (KeyCode::Tab, KeyModifiers::NONE),
+            (KeyCode::Char('a'), KeyModifiers::NONE),
+            (KeyCode::Enter, KeyModifiers::NONE),
+            (KeyCode::Char('b'), KeyModifiers::NONE),
+            (KeyCode::Enter, KeyModifiers::NONE),
+        ]
+    );
+
+    // A CRLF line ending is a single Enter
+    let keys = decode_keys("x\r\n", 2).await;
+    assert_eq!(keys[1].key, KeyCode::Enter);
+}
+
+#[tokio::test]
+async fn test_custom_input_enhanced_ctrl_m_and_ctrl_i() {
+    // With the keyboard enhancement protocol, Ctrl+M and Ctrl+I are reported
+    // as CSI u sequences and stay distinct from Enter and Tab
+    let keys = decode_keys("\x1b[109;5u\x1b[13u\x1b[105;5u\x1b[9u\n", 5).await;
+    let codes: Vec<_> = keys.iter().map(|k| (k.key, k.modifiers)).collect();
+    assert_eq!(
+        codes,
+        [
+            (KeyCode::Char('m'), KeyModifiers::CONTROL),
+            (KeyCode::Enter, KeyModifiers::NONE),
+            (KeyCode::Char('i'), KeyModifiers::CONTROL),
+            (KeyCode::Tab, KeyModifiers::NONE),
+            (KeyCode::Enter, KeyModifiers::NONE),
+        ]
+    );
+}
+
+/// Records each key with whether the program reported it as ambiguous, up
+/// to a `q`, which quits.
+struct AmbiguityRecorder {
+    keys: Vec<(KeyCode, KeyModifiers, bool)>,
+    quitting: bool,
+}
+
+impl Model for AmbiguityRecorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                keys: Vec::new(),
+                quitting: false,
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let key = msg.downcast_ref::<KeyMsg>().filter(|_| !self.quitting)?;
+        if key.key == KeyCode::Char('q') {
+            self.quitting = true;
+            return Some(command::quit());
+        }
+        self.keys
+            .push((key.key, key.modifiers, event::input_is_ambiguous()));
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Runs `bytes` through a program reading them as its input and returns
+/// the keys its model saw.
+async fn keys_seen(
+    terminal: DummyTerminal,
+    keyboard_enhancement: bool,
+    bytes: &'static [u8],
+) -> Vec<(KeyCode, KeyModifiers, bool)> {
+    let (input, mut typing) = tokio::io::duplex(64);
+    let program = Program::<AmbiguityRecorder>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .keyboard_enhancement(keyboard_enhancement)
+        .input(input)
+        .build()
+        .expect("program build");
+    let driver = tokio::spawn(async move {
+        typing.write_all(bytes).await.unwrap();
+        // Custom input is read a line at a time
+        typing.write_all(b"q\n").await.unwrap();
+        typing
+    });
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    drop(driver.await.unwrap());
+    model.keys
+}
+
+#[tokio::test]
+async fn test_legacy_enter_and_tab_are_ambiguous() {
+    // The terminal does not take the protocol, so \r and \t could have
+    // been Ctrl+M and Ctrl+I
+    let terminal = DummyTerminal::new(None).unwrap();
+    let keys = keys_seen(terminal, true, b"\ta\r\x1b[109;5u").await;
+    assert_eq!(
+        keys,
+        [
+            (KeyCode::Tab, KeyModifiers::NONE, true),
+            (KeyCode::Char('a'), KeyModifiers::NONE, false),
+            (KeyCode::Enter, KeyModifiers::NONE, true),
+            (KeyCode::Char('m'), KeyModifiers::CONTROL, false),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_enhanced_enter_and_tab_are_not_ambiguous() {
+    // With the protocol active, Ctrl+M and Ctrl+I would have arrived as
+    // CSI u sequences, so \r and \t are Enter and Tab
+    let terminal = DummyTerminal::new(None)
+        .unwrap()
+        .with_keyboard_enhancement();
+    let keys = keys_seen(terminal, true, b"\t\r\x1b[109;5u\x1b[105;5u").await;
+    assert_eq!(
+        keys,
+        [
+            (KeyCode::Tab, KeyModifiers::NONE, false),
+            (KeyCode::Enter, KeyModifiers::NONE, false),
+            (KeyCode::Char('m'), KeyModifiers::CONTROL, false),
+            (KeyCode::Char('i'), KeyModifiers::CONTROL, false),
+        ]
+    );
+}
@@ -0,0 +1,252 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg, RenderHint, Terminal,
+    TerminalCapabilities, TerminalInterface, WindowSizeMsg,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// A 100-row table where only the selection marker moves.
+struct Table {
+    selected: u16,
+    previous: Option<u16>,
+}
+
+impl Model for Table {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                selected: 0,
+                previous: None,
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        self.previous = None;
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            if key_msg.key == KeyCode::Down {
+                self.previous = Some(self.selected);
+                self.selected += 1;
+            }
+        }
+    }
+
+    fn view(&self) -> String {
+        (0..100)
+            .map(|row| {
+                let marker = if row == self.selected { '>' } else { ' ' };
+                format!("{marker} row {row}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn damage(&self) -> Option<Vec<Range<u16>>> {
+        self.previous
+            .map(|previous| vec![previous..previous + 1, self.selected..self.selected + 1])
+    }
+}
+
+fn resize() -> Msg {
+    Box::new(WindowSizeMsg {
+        width: 80,
+        height: 24,
+    })
+}
+
+fn down() -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Down,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[tokio::test]
+async fn test_only_damaged_rows_are_rewritten() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone())).unwrap();
+    let program = Program::<Table>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(down());
+        let _ = sender.send(down());
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    assert_eq!(model.selected, 2);
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    // The initial frame is drawn in full, then each move rewrites two rows
+    let damaged = written
+        .strip_prefix(&Table::init().0.view())
+        .expect("full first frame");
+    assert_eq!(damaged, "  row 0\n> row 1\n  row 1\n> row 2\n");
+}
+
+#[tokio::test]
+async fn test_resize_forces_full_redraw_despite_damage() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone())).unwrap();
+    let program = Program::<Table>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(down());
+        let _ = sender.send(resize());
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    // After the two damaged rows, the resize redraws the whole view
+    let full = written
+        .strip_prefix(&Table::init().0.view())
+        .and_then(|rest| rest.strip_prefix("  row 0\n> row 1\n"))
+        .expect("full frame, then damaged rows");
+    assert!(full.starts_with("  row 0\n> row 1\n  row 2"));
+    assert!(full.ends_with("  row 99"));
+}
+
+#[tokio::test]
+async fn test_terminal_render_rows_rewrites_only_given_rows() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal.render("a\nb\nc", RenderHint::Full).await.unwrap();
+    output.lock().await.clear();
+
+    terminal
+        .render_frame_rows("a\nB\nc", &[1..2, 3..4])
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    // Row 3 is past the end of the view, so it is only cleared
+    assert_eq!(written, "\x1b[2;1H\x1b[2KB\x1b[4;1H\x1b[2K");
+}
+
+/// A view with a status line whose clock is the only thing that ticks.
+struct Clock {
+    seconds: u32,
+    ticked: bool,
+}
+
+impl Model for Clock {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                seconds: 0,
+                ticked: false,
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        self.ticked = msg.is::<KeyMsg>();
+        if self.ticked {
+            self.seconds += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("title\nclock {:02} | status", self.seconds)
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        if self.ticked {
+            RenderHint::Region(6, 1, 2, 1)
+        } else {
+            RenderHint::Full
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_render_hint_writes_only_the_region() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone())).unwrap();
+    let program = Program::<Clock>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(down());
+        let _ = sender.send(down());
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    assert_eq!(model.seconds, 2);
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    // The initial frame is drawn in full, then each tick writes the clock
+    assert_eq!(written, "title\nclock 00 | status01\n02\n");
+}
+
+#[tokio::test]
+async fn test_dummy_terminal_records_render_hint() {
+    let mut terminal = DummyTerminal::new(None).unwrap();
+    assert_eq!(terminal.last_render_hint(), RenderHint::Full);
+
+    terminal
+        .render("a\nb", RenderHint::Region(0, 1, 1, 1))
+        .await
+        .unwrap();
+    assert_eq!(terminal.last_render_hint(), RenderHint::Region(0, 1, 1, 1));
+
+    terminal.render_rows("a\nb", &[0..1, 1..2]).await.unwrap();
+    assert_eq!(terminal.last_render_hint(), RenderHint::Full);
+}
+
+#[tokio::test]
+async fn test_terminal_region_render_overwrites_in_place() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal
+        .render_frame(
+            "title\n\x1b[1mclock\x1b[0m 7\nend",
+            RenderHint::Region(4, 1, 4, 2),
+        )
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    // No screen clear; short rows are padded to the region's width
+    assert_eq!(written, "\x1b[2;5H\x1b[1mk\x1b[0m 7 \x1b[3;5H    ");
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A keystroke typed into the search box.
+#[derive(Debug)]
+struct TypedMsg(char);
+
+/// A change to the sort order, debounced separately from the search.
+#[derive(Debug)]
+struct SortMsg;
+
+#[derive(Debug)]
+struct SearchMsg(String);
+
+#[derive(Debug)]
+struct ResortMsg;
+
+const DELAY: Duration = Duration::from_millis(100);
+
+/// Debounces a search on every keystroke and records the searches run.
+struct Search {
+    query: String,
+    searches: Vec<(String, Instant)>,
+    resorts: usize,
+}
+
+impl Model for Search {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            query: String::new(),
+            searches: Vec::new(),
+            resorts: 0,
+        };
+        (model, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(TypedMsg(c)) = msg.downcast_ref::<TypedMsg>() {
+            self.query.push(*c);
+            let search = Box::new(SearchMsg(self.query.clone()));
+            return Some(command::debounce("search", DELAY, search));
+        }
+        if msg.is::<SortMsg>() {
+            return Some(command::debounce("sort", DELAY, Box::new(ResortMsg)));
+        }
+        if let Some(SearchMsg(query)) = msg.downcast_ref::<SearchMsg>() {
+            self.searches.push((query.clone(), Instant::now()));
+        }
+        if msg.is::<ResortMsg>() {
+            self.resorts += 1;
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Sends each message in `script` after its pause, then quits once every
+/// delay has passed. Returns the final model and when the last message was
+/// sent.
+async fn run(script: Vec<(u64, Msg)>) -> (Search, Instant) {
+    let program = Program::<Search>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        let mut last = Instant::now();
+        for (pause, msg) in script {
+            tokio::time::sleep(Duration::from_millis(pause)).await;
+            last = Instant::now();
+            sender.send(msg).unwrap();
+        }
+        tokio::time::sleep(DELAY * 3).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+        last
+    };
+    let (result, last) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    (result.expect("program run"), last)
+}
+
+fn typed(text: &str, pause: u64) -> Vec<(u64, Msg)> {
+    text.chars()
+        .map(|c| (pause, Box::new(TypedMsg(c)) as Msg))
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_only_the_last_of_a_burst_is_delivered() {
+    let (model, last) = run(typed("bubble", 20)).await;
+
+    assert_eq!(model.searches.len(), 1);
+    let (query, at) = &model.searches[0];
+    assert_eq!(query, "bubble");
+    assert!(*at >= last + DELAY);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_a_pause_longer_than_the_delay_delivers_each_burst() {
+    let mut script = typed("ab", 20);
+    script.extend(typed("cd", 0).into_iter().map(|(_, msg)| (150, msg)));
+    let (model, _) = run(script).await;
+
+    let queries: Vec<_> = model.searches.iter().map(|(q, _)| q.as_str()).collect();
+    assert_eq!(queries, ["ab", "abc", "abcd"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_keys_are_debounced_independently() {
+    let mut script = typed("ab", 20);
+    script.insert(1, (20, Box::new(SortMsg) as Msg));
+    let (model, _) = run(script).await;
+
+    assert_eq!(model.searches.len(), 1);
+    assert_eq!(model.searches[0].0, "ab");
+    assert_eq!(model.resorts, 1);
+}
@@ -0,0 +1,62 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+struct Screen;
+
+impl Model for Screen {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {
+        None::<Cmd>
+    }
+
+    fn view(&self) -> String {
+        "title\nstatus".to_string()
+    }
+}
+
+fn key(ch: char) -> KeyMsg {
+    KeyMsg {
+        key: KeyCode::Char(ch),
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+#[tokio::test]
+async fn test_overlay_shows_most_recent_key_over_last_line() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Screen>::builder()
+        .signal_handler(false)
+        .debug_input_overlay(true)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(key('a')) as Msg);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(key('b')) as Msg);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    driver.await.unwrap();
+
+    let output = String::from_utf8(output.lock().await.clone()).unwrap();
+    // The first frame has no input to show
+    let expected = format!("title\nstatustitle\n{:?}title\n{:?}", key('a'), key('b'));
+    assert_eq!(output, expected);
+}
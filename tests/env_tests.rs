@@ -11,6 +11,7 @@ impl Model for DummyModel {
         (Self, None)
     }
 
+    #[allow(refining_impl_trait)]
     fn update(&mut self, _msg: Msg) -> Option<bubbletea_rs::Cmd> {
         None
     }
@@ -1,4 +1,4 @@
-use bubbletea_rs::Error;
+use bubbletea_rs::{command, Cmd, Error, IntoCmd, KillMsg, Model, Msg, Program};
 use std::io;
 use tokio::sync::mpsc;
 
@@ -7,8 +7,8 @@ fn test_error_display() {
     let panic_err = Error::ProgramPanic("test panic".to_string());
     assert_eq!(panic_err.to_string(), "Program panic: test panic");
 
-    let killed_err = Error::ProgramKilled;
-    assert_eq!(killed_err.to_string(), "Program was killed");
+    let killed_err = Error::ProgramKilled(1);
+    assert_eq!(killed_err.to_string(), "Program was killed (exit code 1)");
 
     let interrupted_err = Error::Interrupted;
     assert_eq!(interrupted_err.to_string(), "Program was interrupted");
@@ -137,3 +137,46 @@ fn test_error_variants_are_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Error>();
 }
+
+struct Idle;
+
+impl Model for Idle {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+async fn run_until_killed(kill: Msg) -> Result<Idle, Error> {
+    let program = Program::<Idle>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+    program.sender().send(kill).expect("send kill");
+    tokio::time::timeout(std::time::Duration::from_secs(2), program.run())
+        .await
+        .expect("kill should end the program")
+}
+
+#[tokio::test]
+async fn test_program_killed_carries_exit_code() {
+    let result = run_until_killed(Box::new(KillMsg::by_signal(15))).await;
+    assert!(matches!(result, Err(Error::ProgramKilled(143))));
+
+    let result = run_until_killed(Box::new(KillMsg {
+        reason: "custom".to_string(),
+        exit_code: 42,
+    }))
+    .await;
+    assert!(matches!(result, Err(Error::ProgramKilled(42))));
+
+    let kill = command::kill().await.expect("kill message");
+    let result = run_until_killed(kill).await;
+    assert!(matches!(result, Err(Error::ProgramKilled(1))));
+}
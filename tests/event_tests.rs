@@ -1,6 +1,6 @@
 use bubbletea_rs::{
-    BlurMsg, FocusMsg, InterruptMsg, KeyMsg, MouseMsg, Msg, QuitMsg, ResumeMsg, SuspendMsg,
-    WindowSizeMsg,
+    BlurMsg, FocusMsg, InterruptMsg, KeyMsg, KillMsg, MouseMsg, Msg, PaneFocus, PasteMsg, QuitMsg,
+    ResumeMsg, SuspendMsg, WindowSizeMsg,
 };
 use crossterm::event::{KeyCode, KeyModifiers, MouseEventKind};
 
@@ -16,6 +16,7 @@ fn test_msg_type_alias() {
         y: 20,
         button: MouseEventKind::Down(crossterm::event::MouseButton::Left),
         modifiers: KeyModifiers::CONTROL,
+        scroll_delta: 0,
     });
 
     let quit_msg: Msg = Box::new(QuitMsg);
@@ -47,6 +48,7 @@ fn test_mouse_msg() {
         y: 24,
         button: MouseEventKind::Down(crossterm::event::MouseButton::Right),
         modifiers: KeyModifiers::ALT,
+        scroll_delta: 0,
     };
 
     assert_eq!(mouse_msg.x, 42);
@@ -86,6 +88,36 @@ fn test_lifecycle_messages() {
     let _: BlurMsg = blur_msg;
 }
 
+#[test]
+fn test_focus_messages_can_be_stored_and_compared() {
+    let focus = FocusMsg;
+    let blur = BlurMsg;
+    assert_eq!(focus.clone(), FocusMsg);
+    assert_eq!(blur.clone(), BlurMsg);
+    assert_eq!(format!("{focus:?} {blur:?}"), "FocusMsg BlurMsg");
+
+    // Composed models can keep the last focus event per pane
+    let history = vec![Some(FocusMsg), None];
+    assert_eq!(history, [Some(FocusMsg), None]);
+}
+
+#[test]
+fn test_pane_focus_tracks_focus_and_blur() {
+    let mut left = PaneFocus::default();
+    let mut right = PaneFocus::new(true);
+    assert!(!left.focused);
+
+    // The composed model routes the terminal's focus event to one pane
+    assert!(left.update(&(Box::new(FocusMsg) as Msg)));
+    assert!(right.update(&(Box::new(BlurMsg) as Msg)));
+    assert_eq!(left, PaneFocus::new(true));
+    assert_eq!(right, PaneFocus::new(false));
+
+    // Other messages leave the state alone
+    assert!(!left.update(&(Box::new(QuitMsg) as Msg)));
+    assert!(left.focused);
+}
+
 #[test]
 fn test_message_cloning() {
     let key_msg = KeyMsg {
@@ -101,6 +133,7 @@ fn test_message_cloning() {
         y: 2,
         button: MouseEventKind::Moved,
         modifiers: KeyModifiers::NONE,
+        scroll_delta: 0,
     };
     let cloned = mouse_msg.clone();
     assert_eq!(mouse_msg.x, cloned.x);
@@ -144,3 +177,34 @@ fn test_messages_are_send() {
     assert_send::<BlurMsg>();
     assert_send::<Msg>();
 }
+
+#[test]
+fn test_kill_msg_constructors() {
+    let by_signal = KillMsg::by_signal(2);
+    assert_eq!(by_signal.exit_code, 130);
+    assert!(by_signal.reason.contains('2'));
+
+    let by_user = KillMsg::by_user("aborted".to_string());
+    assert_eq!(
+        by_user,
+        KillMsg {
+            reason: "aborted".to_string(),
+            exit_code: 1,
+        }
+    );
+}
+
+#[test]
+fn test_paste_msg_sanitized_strips_escape_sequences() {
+    let paste = PasteMsg::new("before \x1b[31mred\x1b[0m after", true);
+
+    assert_eq!(paste.text(), "before \x1b[31mred\x1b[0m after");
+    assert_eq!(paste.sanitized(), "before red after");
+}
+
+#[test]
+fn test_paste_msg_sanitized_keeps_tabs_and_newlines() {
+    let paste = PasteMsg::new("a\tb\r\nc\rd\n\x00e\x08\u{9b}f", true);
+
+    assert_eq!(paste.sanitized(), "a\tb\nc\nd\nef");
+}
@@ -0,0 +1,202 @@
+#![cfg(unix)]
+
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, ExecRequestMsg, IntoCmd, KeyMsg, Model, Msg, Program,
+    TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+#[derive(Debug)]
+struct TickMsg;
+
+#[derive(Debug)]
+struct EditorClosedMsg;
+
+/// Animates with a fast timer and opens an "editor" on `e`.
+struct Spinner {
+    ticks: u32,
+    ticks_at_exec: u32,
+    ticks_at_close: Option<u32>,
+}
+
+impl Model for Spinner {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            ticks: 0,
+            ticks_at_exec: 0,
+            ticks_at_close: None,
+        };
+        let spin = command::every_with_id(Duration::from_millis(5), |_| Box::new(TickMsg) as Msg).0;
+        (model, Some(spin))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<TickMsg>() {
+            self.ticks += 1;
+        } else if msg.downcast_ref::<KeyMsg>().is_some() {
+            self.ticks_at_exec = self.ticks;
+            let mut editor = Command::new("sh");
+            editor.args(["-c", "sleep 0.2"]);
+            return Some(command::exec_process(editor, |_| {
+                Box::new(EditorClosedMsg) as Msg
+            }));
+        } else if msg.is::<EditorClosedMsg>() {
+            self.ticks_at_close = Some(self.ticks);
+            return Some(command::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        format!("ticks {}", self.ticks)
+    }
+}
+
+#[tokio::test]
+async fn test_exec_process_leaves_terminal_alone_while_it_runs() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Spinner>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let window = output.clone();
+    let driver = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let _ = sender.send(Box::new(KeyMsg {
+            key: KeyCode::Char('e'),
+            modifiers: KeyModifiers::NONE,
+        }) as Msg);
+        // Sample the output inside the editor's run
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let start = window.lock().await.len();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let end = window.lock().await.len();
+        (start, end)
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    let (start, end) = driver.await.unwrap();
+
+    assert!(model.ticks_at_exec > 0, "the timer ran before the exec");
+    assert_eq!(start, end, "nothing is written while the editor runs");
+    // At most a tick already in flight gets through
+    let ticks_at_close = model.ticks_at_close.expect("completion delivered");
+    assert!(ticks_at_close <= model.ticks_at_exec + 1);
+
+    // The view is redrawn once the terminal is back
+    let output = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert!(output[end..].contains(&format!("ticks {ticks_at_close}")));
+}
+
+#[derive(Debug)]
+struct PingMsg;
+
+#[derive(Debug)]
+struct ChildExitedMsg(Option<i32>);
+
+/// Picks which child to run inside its own command, after an async probe.
+struct Launcher {
+    events: Vec<String>,
+}
+
+fn launch(fail: bool) -> Cmd {
+    Box::pin(async move {
+        // Stands in for async logic such as probing for an editor
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let script = if fail { "sleep 0.1; exit 3" } else { "exit 0" };
+        let mut child = Command::new("sh");
+        child.args(["-c", script]);
+        let request = ExecRequestMsg::new(child, |status| {
+            Box::new(ChildExitedMsg(status.ok().and_then(|status| status.code()))) as Msg
+        });
+        Some(Box::new(request) as Msg)
+    })
+}
+
+impl Model for Launcher {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { events: Vec::new() }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            self.events.push(format!("key {:?}", key_msg.key));
+            return Some(launch(key_msg.key == KeyCode::Char('b')));
+        } else if let Some(ChildExitedMsg(code)) = msg.downcast_ref::<ChildExitedMsg>() {
+            self.events.push(format!("exited {code:?}"));
+            // The failing child is launched last
+            if *code == Some(3) {
+                return Some(command::quit());
+            }
+        } else if msg.is::<PingMsg>() {
+            self.events.push("ping".to_string());
+        } else if msg.is::<ExecRequestMsg>() {
+            self.events.push("request leaked".to_string());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        self.events.join("\n")
+    }
+}
+
+#[tokio::test]
+async fn test_exec_request_runs_the_child_chosen_at_runtime() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Launcher>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let window = output.clone();
+    let driver = tokio::spawn(async move {
+        let key = |c| {
+            Box::new(KeyMsg {
+                key: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE,
+            }) as Msg
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(key('a'));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let _ = sender.send(key('b'));
+        // Delivered while the failing child still runs
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        let start = window.lock().await.len();
+        let _ = sender.send(Box::new(PingMsg) as Msg);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let end = window.lock().await.len();
+        (start, end)
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    let (start, end) = driver.await.unwrap();
+
+    assert_eq!(
+        model.events,
+        [
+            "key Char('a')",
+            "exited Some(0)",
+            "key Char('b')",
+            "ping",
+            "exited Some(3)"
+        ]
+    );
+    assert_eq!(start, end, "nothing is drawn while the child runs");
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "fetch")]
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use bubbletea_rs::{command, Cmd, IntoCmd, Model, Msg, Program};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+static SERVER_URL: OnceLock<String> = OnceLock::new();
+
+#[derive(Debug)]
+struct FetchedMsg(Result<Vec<u8>, String>);
+
+struct FetchModel {
+    result: Option<Result<Vec<u8>, String>>,
+}
+
+impl Model for FetchModel {
+    fn init() -> (Self, Option<Cmd>) {
+        let url = SERVER_URL.get().expect("server url").clone();
+        let cmd = command::fetch(url, |result| Box::new(FetchedMsg(result)) as Msg);
+        (Self { result: None }, Some(cmd))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Ok(fetched) = msg.downcast::<FetchedMsg>() {
+            self.result = Some(fetched.0);
+            return Some(command::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Serves a single connection with a fixed status line and body.
+async fn serve_once(status: &'static str, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 1024];
+        let _ = socket.read(&mut request).await;
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+    });
+    format!("http://{addr}/")
+}
+
+#[tokio::test]
+async fn test_fetch_delivers_body_to_update() {
+    SERVER_URL
+        .set(serve_once("200 OK", "hello from the mock server").await)
+        .unwrap();
+
+    let program = Program::<FetchModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("fetch should complete before the timeout")
+        .expect("program run");
+
+    assert_eq!(
+        model.result,
+        Some(Ok(b"hello from the mock server".to_vec()))
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_reports_error_status() {
+    let url = serve_once("404 Not Found", "missing").await;
+    let msg = command::fetch(url, |result| Box::new(FetchedMsg(result)) as Msg)
+        .await
+        .expect("fetch produces a message");
+    let FetchedMsg(result) = *msg.downcast::<FetchedMsg>().unwrap();
+    assert!(result.unwrap_err().contains("404"));
+}
+
+#[tokio::test]
+async fn test_fetch_with_timeout_cancels_slow_request() {
+    // Accept the connection but never answer
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (_socket, _) = listener.accept().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(10)).await;
+    });
+
+    let cmd = command::fetch_with_timeout(
+        format!("http://{addr}/"),
+        Duration::from_millis(100),
+        |result| Box::new(FetchedMsg(result)) as Msg,
+    );
+    let msg = tokio::time::timeout(Duration::from_secs(5), cmd)
+        .await
+        .expect("timeout should cancel the request")
+        .expect("fetch produces a message");
+    let FetchedMsg(result) = *msg.downcast::<FetchedMsg>().unwrap();
+    assert!(result.unwrap_err().contains("timed out"));
+}
@@ -0,0 +1,144 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, FlushStrategy, IntoCmd, Model, Msg, Program, QuitMsg, RenderHint, Terminal,
+    TerminalCapabilities, TerminalInterface,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+struct Tick;
+
+struct Counter {
+    ticks: usize,
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { ticks: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<Tick>() {
+            self.ticks += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("ticks: {}", self.ticks)
+    }
+}
+
+fn program_with_counter(strategy: FlushStrategy) -> (Program<Counter>, Arc<AtomicUsize>) {
+    let terminal = DummyTerminal::new(None).unwrap();
+    let flushes = terminal.flush_counter();
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .flush_strategy(strategy)
+        .build()
+        .expect("program build");
+    (program, flushes)
+}
+
+#[tokio::test]
+async fn test_every_frame_flushes_after_each_frame() {
+    let (program, flushes) = program_with_counter(FlushStrategy::EveryFrame);
+    let sender = program.sender();
+    for _ in 0..5 {
+        sender.send(Box::new(Tick)).unwrap();
+    }
+    sender.send(Box::new(QuitMsg)).unwrap();
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    assert_eq!(model.ticks, 5);
+    // The initial frame, then one per tick
+    assert_eq!(flushes.load(Ordering::SeqCst), 6);
+}
+
+#[tokio::test]
+async fn test_on_idle_flushes_once_per_burst() {
+    let (program, flushes) = program_with_counter(FlushStrategy::OnIdle);
+    let sender = program.sender();
+    for _ in 0..5 {
+        sender.send(Box::new(Tick)).unwrap();
+    }
+
+    let driver = async {
+        // All five frames were drawn before the loop ran out of messages
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(flushes.load(Ordering::SeqCst), 1);
+
+        sender.send(Box::new(Tick)).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(flushes.load(Ordering::SeqCst), 2);
+
+        sender.send(Box::new(QuitMsg)).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), driver)
+    })
+    .await
+    .expect("program should quit");
+
+    assert_eq!(result.expect("program run").ticks, 6);
+    // The final flush before the terminal is restored
+    assert_eq!(flushes.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_interval_flushes_at_most_once_per_interval() {
+    let (program, flushes) =
+        program_with_counter(FlushStrategy::Interval(Duration::from_millis(100)));
+    let sender = program.sender();
+
+    let driver = async {
+        // 30 frames over about 300 ms
+        for _ in 0..30 {
+            sender.send(Box::new(Tick)).unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // The last frames are flushed once the interval has passed
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let during_run = flushes.load(Ordering::SeqCst);
+        assert!(
+            (2..=5).contains(&during_run),
+            "expected one flush per 100 ms, got {during_run}"
+        );
+
+        sender.send(Box::new(QuitMsg)).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), driver)
+    })
+    .await
+    .expect("program should quit");
+
+    assert_eq!(result.expect("program run").ticks, 30);
+}
+
+#[tokio::test]
+async fn test_terminal_buffers_output_until_flush_without_auto_flush() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+    terminal.set_auto_flush(false);
+
+    terminal.render("first", RenderHint::Full).await.unwrap();
+    terminal.render("second", RenderHint::Full).await.unwrap();
+    assert!(output.lock().await.is_empty());
+
+    terminal.flush().await.unwrap();
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(written, "\x1b[H\x1b[2Jfirst\x1b[H\x1b[2Jsecond");
+
+    // Auto-flush (the default) writes immediately
+    terminal.set_auto_flush(true);
+    terminal.render("third", RenderHint::Full).await.unwrap();
+    assert!(output.lock().await.ends_with(b"third"));
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    event, BlurMsg, Cmd, DummyTerminal, DummyTerminalEvent, FocusMsg, IntoCmd, Model, Msg, Program,
+    QuitMsg, ResumeMsg, TerminalInterface,
+};
+use std::time::Duration;
+use tokio::io::{AsyncWriteExt, DuplexStream};
+
+/// Records the focus changes it sees, and whether each came with the time
+/// it arrived.
+struct Watcher {
+    changes: Vec<(&'static str, bool)>,
+}
+
+impl Model for Watcher {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                changes: Vec::new(),
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let stamped = event::input_timestamp().is_some();
+        if msg.is::<FocusMsg>() {
+            self.changes.push(("focus", stamped));
+        } else if msg.is::<BlurMsg>() {
+            self.changes.push(("blur", stamped));
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Runs a `Watcher` fed by `feed`, which writes to its input, and returns
+/// the focus changes it saw.
+async fn watch<F>(debounce: Duration, feed: impl FnOnce(DuplexStream) -> F) -> Vec<&'static str>
+where
+    F: std::future::Future<Output = DuplexStream>,
+{
+    let (input, writer) = tokio::io::duplex(256);
+    let program = Program::<Watcher>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(input)
+        .report_focus(true)
+        .focus_debounce(debounce)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        let _writer = feed(writer).await;
+        // Let the last change settle
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (model, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+
+    let changes = model.expect("program run").changes;
+    assert!(changes.iter().all(|(_, stamped)| *stamped));
+    changes.into_iter().map(|(change, _)| change).collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_flapping_focus_is_collapsed_into_the_net_transition() {
+    let changes = watch(Duration::from_millis(50), |mut writer| async move {
+        writer.write_all(b"\x1b[I\n").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // Switching tmux panes: pairs that cancel out, then a real blur
+        for report in ["\x1b[O", "\x1b[I", "\x1b[O\x1b[I\n", "\x1b[O\x1b[I\x1b[O\n"] {
+            writer.write_all(report.as_bytes()).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        writer
+    })
+    .await;
+
+    assert_eq!(changes, ["focus", "blur"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_focus_changes_that_cancel_out_are_dropped() {
+    let changes = watch(Duration::from_millis(50), |mut writer| async move {
+        writer.write_all(b"\x1b[O\n").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        writer.write_all(b"\x1b[I\x1b[O\n").await.unwrap();
+        writer
+    })
+    .await;
+
+    assert_eq!(changes, ["blur"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_zero_focus_debounce_delivers_every_change() {
+    let changes = watch(Duration::ZERO, |mut writer| async move {
+        writer.write_all(b"\x1b[I\x1b[O\x1b[I\n").await.unwrap();
+        writer
+    })
+    .await;
+
+    assert_eq!(changes, ["focus", "blur", "focus"]);
+}
+
+#[tokio::test]
+async fn test_resume_reenables_focus_reporting() {
+    let terminal = DummyTerminal::new(None).unwrap();
+    let events = terminal.event_log();
+    let program = Program::<Watcher>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .report_focus(true)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+    sender.send(Box::new(ResumeMsg) as Msg).unwrap();
+    sender.send(Box::new(QuitMsg) as Msg).unwrap();
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    use DummyTerminalEvent::*;
+    let focus_events: Vec<_> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .copied()
+        .filter(|event| matches!(event, EnableFocusReporting | DisableFocusReporting))
+        .collect();
+    // Enabled at startup, sent again on resume, and turned off on exit
+    assert_eq!(
+        focus_events,
+        [
+            EnableFocusReporting,
+            DisableFocusReporting,
+            EnableFocusReporting,
+            DisableFocusReporting
+        ]
+    );
+}
@@ -0,0 +1,92 @@
+use bubbletea_rs::{Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// A clock tick that changes one line of the view.
+struct TickMsg;
+
+/// A status screen with a clock line that changes on every tick.
+struct Clock {
+    ticks: u32,
+}
+
+impl Model for Clock {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { ticks: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<TickMsg>() {
+            self.ticks += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("Status: ok\nUptime: {}s\nPress q to quit\n", self.ticks)
+    }
+}
+
+/// Runs `Clock` through `ticks` frames, returning the churn report and the
+/// output.
+async fn run(ticks: u32, debug_input_overlay: bool) -> (bubbletea_rs::FrameChurn, String) {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Clock>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .frame_churn(true)
+        .debug_input_overlay(debug_input_overlay)
+        .build()
+        .expect("program build");
+    let churn = program.frame_churn().expect("frame churn enabled").clone();
+    let sender = program.sender();
+
+    let drive = async {
+        for _ in 0..ticks {
+            // Long enough for each tick to get its own frame
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            sender.send(Box::new(TickMsg) as Msg).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    let bytes = output.lock().await.clone();
+    (churn, String::from_utf8(bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_churn_report_fingers_the_changing_line() {
+    let (churn, _) = run(5, false).await;
+
+    let report = churn.report();
+    assert_eq!(report.len(), 1, "{}", churn.dump());
+    assert_eq!(report[0].line, 1);
+    assert!(report[0].changes >= 1);
+    assert!(churn.dump().contains("line 1: changed in"));
+}
+
+#[tokio::test]
+async fn test_debug_overlay_names_high_churn_lines() {
+    let (_, output) = run(3, true).await;
+
+    assert!(output.contains("churn: lines 1"));
+}
+
+#[test]
+fn test_frame_churn_is_off_by_default() {
+    let program = Program::<Clock>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    assert!(program.frame_churn().is_none());
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const UPDATES: usize = 30;
+
+#[derive(Debug)]
+struct IncrementMsg;
+
+/// A counter with a large view, so every frame costs real bytes.
+struct Counter {
+    count: usize,
+}
+
+fn frame(count: usize) -> String {
+    format!("count {count:03}\n{}", ".".repeat(1000))
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { count: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<IncrementMsg>() {
+            self.count += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        frame(self.count)
+    }
+}
+
+/// Runs the counter on a terminal that takes 50ms per frame while updates
+/// arrive every 5ms, returning what was written and the skipped frames.
+async fn run_on_slow_link(coalescing: bool) -> (String, u64) {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone()))
+        .unwrap()
+        .with_write_delay(Duration::from_millis(50));
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .frame_coalescing(coalescing)
+        .build()
+        .expect("program build");
+    let timings = program.view_timings();
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        for _ in 0..UPDATES {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            let _ = sender.send(Box::new(IncrementMsg) as Msg);
+        }
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(10), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    assert_eq!(model.count, UPDATES);
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    (written, timings.stats().frames_skipped)
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_slow_terminal_draws_only_the_latest_frame() {
+    let (written, skipped) = run_on_slow_link(true).await;
+
+    let frames = written.matches("count ").count();
+    assert!(frames < 10, "drew {frames} frames");
+    assert!(skipped as usize > UPDATES / 2, "skipped {skipped} frames");
+    // Queued output stays within a handful of frames
+    assert!(written.len() <= frames * frame(0).len());
+    // The final state is on screen
+    assert!(written.ends_with(&frame(UPDATES)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_disabled_coalescing_draws_every_frame() {
+    let (written, skipped) = run_on_slow_link(false).await;
+
+    assert_eq!(written.matches("count ").count(), UPDATES + 1);
+    assert_eq!(skipped, 0);
+    assert!(written.ends_with(&frame(UPDATES)));
+}
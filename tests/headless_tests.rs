@@ -0,0 +1,112 @@
+use bubbletea_rs::program::HeadlessRunner;
+use bubbletea_rs::{command, Cmd, IntoCmd, KeyMsg, Model, Msg, QuitMsg};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[derive(Debug)]
+struct Fetched(&'static str);
+
+/// Fetches a greeting on start and on `r`, and quits on `q`.
+struct Greeter {
+    greeting: Option<&'static str>,
+    fetches: u32,
+}
+
+fn fetch(greeting: &'static str) -> Cmd {
+    Box::pin(async move { Some(Box::new(Fetched(greeting)) as Msg) })
+}
+
+impl Model for Greeter {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                greeting: None,
+                fetches: 0,
+            },
+            Some(fetch("hello")),
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(Fetched(greeting)) = msg.downcast_ref::<Fetched>() {
+            self.greeting = Some(greeting);
+            self.fetches += 1;
+        } else if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            match key_msg.key {
+                KeyCode::Char('r') => {
+                    return vec![
+                        fetch("hi"),
+                        command::sequence(vec![fetch("hey"), fetch("yo")]),
+                    ]
+                }
+                KeyCode::Char('q') => return vec![command::quit()],
+                _ => {}
+            }
+        }
+        Vec::new()
+    }
+
+    fn view(&self) -> String {
+        self.greeting.unwrap_or("loading").to_string()
+    }
+}
+
+fn key(ch: char) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(ch),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+fn fetched(msg: &Msg) -> &'static str {
+    msg.downcast_ref::<Fetched>().expect("a Fetched message").0
+}
+
+#[test]
+fn test_commands_only_run_when_asked() {
+    let mut runner = HeadlessRunner::<Greeter>::new().unwrap();
+    assert_eq!(runner.model().view(), "loading");
+
+    let init_cmds = runner.take_init_cmds();
+    assert_eq!(init_cmds.len(), 1);
+    assert!(runner.take_init_cmds().is_empty());
+
+    // The key press returns commands but does not run them
+    let result = runner.process(key('r'));
+    assert_eq!(result.view, "loading");
+    assert_eq!(result.cmds.len(), 1, "returned commands are batched");
+    assert!(!result.quit);
+    assert_eq!(runner.model().fetches, 0);
+
+    // Batches and sequences unpack into the messages they deliver, in order
+    let msgs = runner.run_cmd(result.cmds.into_iter().next().unwrap());
+    let greetings: Vec<_> = msgs.iter().map(fetched).collect();
+    assert_eq!(greetings, ["hi", "hey", "yo"]);
+
+    // The test picks which messages reach the model
+    let result = runner.process(msgs.into_iter().last().unwrap());
+    assert_eq!(result.view, "yo");
+    assert_eq!(runner.model().fetches, 1);
+}
+
+#[test]
+fn test_quit_is_reported_without_stopping_the_runner() {
+    let mut runner = HeadlessRunner::<Greeter>::new().unwrap();
+
+    let result = runner.process(key('q'));
+    assert!(!result.quit, "quitting takes the command's message");
+    let msgs = runner.run_cmd(result.cmds.into_iter().next().unwrap());
+    assert_eq!(msgs.len(), 1);
+    assert!(msgs[0].is::<QuitMsg>());
+
+    let result = runner.process(msgs.into_iter().next().unwrap());
+    assert!(result.quit);
+    assert!(result.cmds.is_empty());
+
+    // The model is still available after a quit
+    for cmd in runner.take_init_cmds() {
+        for msg in runner.run_cmd(cmd) {
+            runner.process(msg);
+        }
+    }
+    assert_eq!(runner.into_model().greeting, Some("hello"));
+}
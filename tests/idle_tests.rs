@@ -0,0 +1,142 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IdleMsg, IntoCmd, KeyMsg, Model, Msg, PasteChunkMsg, PasteEndMsg, Program,
+    QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A timer firing in the background; not input.
+struct TickMsg;
+
+const PERIOD: Duration = Duration::from_millis(100);
+
+/// Records when keys, streamed paste pieces and idle reports arrive.
+#[derive(Default)]
+struct Recorder {
+    keys: Vec<Instant>,
+    pastes: Vec<Instant>,
+    idles: Vec<(IdleMsg, Instant)>,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self::default(), None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<KeyMsg>() {
+            self.keys.push(Instant::now());
+        }
+        if msg.is::<PasteChunkMsg>() || msg.is::<PasteEndMsg>() {
+            self.pastes.push(Instant::now());
+        }
+        if let Some(idle) = msg.downcast_ref::<IdleMsg>() {
+            self.idles.push((*idle, Instant::now()));
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn key() -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char('j'),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+/// Sends each message in `script` after its pause in milliseconds, then
+/// quits after `quiet`.
+async fn run(idle_after: Option<Duration>, script: Vec<(u64, Msg)>, quiet: Duration) -> Recorder {
+    let mut builder = Program::<Recorder>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0);
+    if let Some(period) = idle_after {
+        builder = builder.idle_after(period);
+    }
+    let program = builder.build().expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        for (pause, msg) in script {
+            tokio::time::sleep(Duration::from_millis(pause)).await;
+            sender.send(msg).unwrap();
+        }
+        tokio::time::sleep(quiet).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run")
+}
+
+fn burst(keys: usize, pause: u64) -> Vec<(u64, Msg)> {
+    (0..keys).map(|_| (pause, key())).collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_a_burst_then_silence_sends_one_idle_msg() {
+    let model = run(Some(PERIOD), burst(5, 20), PERIOD * 5).await;
+
+    assert_eq!(model.idles.len(), 1);
+    let (idle, at) = model.idles[0];
+    let last_key = *model.keys.last().unwrap();
+    assert_eq!(at, last_key + PERIOD);
+    assert_eq!(idle.idle_for, PERIOD);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_input_starts_a_new_idle_period() {
+    let mut script = burst(3, 20);
+    script.extend(burst(1, 250));
+    script.extend(burst(2, 20));
+    let model = run(Some(PERIOD), script, PERIOD * 3).await;
+
+    assert_eq!(model.idles.len(), 2);
+    assert_eq!(model.idles[0].1, model.keys[2] + PERIOD);
+    assert_eq!(model.idles[1].1, model.keys[5] + PERIOD);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_other_messages_do_not_count_as_input() {
+    let mut script = burst(1, 20);
+    script.extend((0..10).map(|_| (30, Box::new(TickMsg) as Msg)));
+    let model = run(Some(PERIOD), script, PERIOD).await;
+
+    assert_eq!(model.idles.len(), 1);
+    assert_eq!(model.idles[0].1, model.keys[0] + PERIOD);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_streamed_paste_counts_as_input() {
+    let mut script = burst(1, 20);
+    let chunk = || {
+        Box::new(PasteChunkMsg {
+            text: "pasted".to_string(),
+        }) as Msg
+    };
+    script.extend([
+        (60, chunk()),
+        (60, chunk()),
+        (60, Box::new(PasteEndMsg) as Msg),
+    ]);
+    let model = run(Some(PERIOD), script, PERIOD * 3).await;
+
+    assert_eq!(model.idles.len(), 1);
+    assert_eq!(model.idles[0].1, *model.pastes.last().unwrap() + PERIOD);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_no_idle_msg_unless_enabled() {
+    let model = run(None, burst(2, 20), PERIOD * 5).await;
+    assert!(model.idles.is_empty());
+}
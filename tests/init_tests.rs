@@ -0,0 +1,195 @@
+use bubbletea_rs::{Cmd, DummyTerminal, Error, IntoCmd, Model, Msg, Program, TerminalInterface};
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::AsyncWrite;
+
+/// Records everything the program writes to its output.
+#[derive(Clone, Default)]
+struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl AsyncWrite for SharedOutput {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct FailingModel;
+
+impl Model for FailingModel {
+    fn init() -> (Self, Option<Cmd>) {
+        unreachable!("the program should call try_init")
+    }
+
+    fn try_init() -> Result<(Self, Option<Cmd>), Error> {
+        Err(Error::Configuration("missing document".to_string()))
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        "should never render".to_string()
+    }
+}
+
+struct PlainModel {
+    initialized: bool,
+}
+
+impl Model for PlainModel {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { initialized: true }, Some(bubbletea_rs::quit()))
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_try_init_error_is_returned_without_touching_terminal() {
+    let output = SharedOutput::default();
+    let program = Program::<FailingModel>::builder()
+        .output(output.clone())
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+
+    let result = program.run().await;
+
+    match result {
+        Err(Error::Configuration(message)) => assert_eq!(message, "missing document"),
+        Err(other) => panic!("expected the try_init error, got {other}"),
+        Ok(_) => panic!("expected the try_init error, got a model"),
+    }
+    assert!(output.0.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_default_try_init_calls_init() {
+    let program = Program::<PlainModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(std::time::Duration::from_secs(2), program.run())
+        .await
+        .expect("init command should quit the program")
+        .expect("program run");
+
+    assert!(model.initialized);
+}
+
+/// Counts the messages it has handled.
+struct Idle {
+    handled: usize,
+}
+
+impl Model for Idle {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { handled: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        self.handled += 1;
+        if msg.is::<&str>() {
+            return Some(bubbletea_rs::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        format!("handled {}|", self.handled)
+    }
+}
+
+#[tokio::test]
+async fn test_initial_view_is_rendered_before_any_message() {
+    let output = Arc::new(tokio::sync::Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Idle>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let driver = async {
+        // Nothing has been sent yet, but the first frame is already there
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(output.lock().await.as_slice(), b"handled 0|");
+        sender.send(Box::new("quit")).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+        tokio::join!(program.run(), driver)
+    })
+    .await
+    .expect("program should quit");
+
+    result.expect("program run");
+}
+
+/// Records the startup messages it receives and quits once it has all of
+/// them.
+struct Startup {
+    seen: Vec<&'static str>,
+}
+
+/// A command that delivers `name` as its message.
+fn announce(name: &'static str) -> Cmd {
+    Box::pin(async move { Some(Box::new(name) as Msg) })
+}
+
+impl Model for Startup {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { seen: Vec::new() }, Some(announce("init")))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(&name) = msg.downcast_ref::<&'static str>() {
+            self.seen.push(name);
+        }
+        (self.seen.len() == 3).then(bubbletea_rs::quit)
+    }
+
+    fn view(&self) -> String {
+        format!("seen {}|", self.seen.len())
+    }
+}
+
+#[tokio::test]
+async fn test_initial_commands_run_with_init_command() {
+    let output = Arc::new(tokio::sync::Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Startup>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .initial_commands(vec![announce("fetch")])
+        .initial_commands(vec![announce("config")])
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(std::time::Duration::from_secs(2), program.run())
+        .await
+        .expect("startup commands should quit the program")
+        .expect("program run");
+
+    assert_eq!(model.seen, ["init", "fetch", "config"]);
+    // The initial frame is drawn before any startup message is handled
+    assert!(output.lock().await.starts_with(b"seen 0|"));
+}
@@ -45,6 +45,7 @@ async fn test_mouse_message_conversion() {
         y: mouse_event.row,
         button: mouse_event.kind,
         modifiers: mouse_event.modifiers,
+        scroll_delta: 0,
     };
 
     assert_eq!(mouse_msg.x, 10);
@@ -177,6 +178,7 @@ async fn test_mouse_button_types() {
             y: 0,
             button: button_kind,
             modifiers: KeyModifiers::NONE,
+            scroll_delta: 0,
         };
 
         assert_eq!(mouse_msg.button, button_kind);
@@ -0,0 +1,72 @@
+use bubbletea_rs::{
+    command, Cmd, DisableBracketedPasteMsg, DummyTerminal, EnableBracketedPasteMsg,
+    EnableMouseCellMotionMsg, InputModes, IntoCmd, Model, ModesMsg, MouseMotion, Msg, Program,
+    QuitMsg, TerminalInterface,
+};
+use std::time::Duration;
+
+/// Enables bracketed paste on start and records every mode change.
+struct Recorder {
+    modes: Vec<InputModes>,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self { modes: Vec::new() };
+        (model, Some(command::enable_bracketed_paste()))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(&ModesMsg(modes)) = msg.downcast_ref::<ModesMsg>() {
+            self.modes.push(modes);
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_enabled_modes_are_tracked() {
+    let program = Program::<Recorder>::builder()
+        .signal_handler(false)
+        .report_focus(true)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        let pause = Duration::from_millis(10);
+        tokio::time::sleep(pause).await;
+        // Already enabled by init, so nothing changes
+        let _ = sender.send(Box::new(EnableBracketedPasteMsg) as Msg);
+        tokio::time::sleep(pause).await;
+        let _ = sender.send(Box::new(EnableMouseCellMotionMsg) as Msg);
+        tokio::time::sleep(pause).await;
+        let _ = sender.send(Box::new(DisableBracketedPasteMsg) as Msg);
+        tokio::time::sleep(pause).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    driver.await.unwrap();
+
+    let modes = |bracketed_paste, mouse_motion| InputModes {
+        bracketed_paste,
+        mouse_motion,
+        focus_reporting: true,
+    };
+    assert_eq!(
+        model.modes,
+        [
+            modes(true, MouseMotion::None),
+            modes(true, MouseMotion::Cell),
+            modes(false, MouseMotion::Cell),
+        ]
+    );
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+/// Counts key presses, taking a little while over each one.
+struct SlowCounter {
+    keys: usize,
+}
+
+impl Model for SlowCounter {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { keys: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<KeyMsg>() {
+            self.keys += 1;
+            std::thread::sleep(Duration::from_micros(200));
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("keys {}", self.keys)
+    }
+}
+
+fn key() -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char('x'),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+fn build(buffer: Option<usize>) -> Program<SlowCounter> {
+    Program::<SlowCounter>::builder()
+        .signal_handler(false)
+        .event_channel_buffer(buffer)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build")
+}
+
+#[tokio::test]
+async fn test_input_queue_drains_while_program_runs() {
+    let program = build(Some(1000));
+    let input = DummyTerminal::new(None)
+        .unwrap()
+        .with_event_sender(program.sender());
+
+    for _ in 0..100 {
+        input.send(key()).unwrap();
+    }
+    assert_eq!(input.input_queue_len(), 100);
+
+    let driver = tokio::spawn(async move {
+        input
+            .wait_until_idle(Duration::from_secs(2))
+            .await
+            .expect("queue should drain");
+        let drained = input.input_queue_len();
+        input.send(Box::new(QuitMsg) as Msg).unwrap();
+        drained
+    });
+
+    let model = program.run().await.expect("program run");
+    assert_eq!(driver.await.unwrap(), 0);
+    assert_eq!(model.keys, 100);
+}
+
+#[tokio::test]
+async fn test_wait_until_idle_times_out_when_nothing_reads() {
+    let program = build(Some(1000));
+    let input = DummyTerminal::new(None)
+        .unwrap()
+        .with_event_sender(program.sender());
+
+    for _ in 0..3 {
+        input.send(key()).unwrap();
+    }
+
+    assert!(input
+        .wait_until_idle(Duration::from_millis(20))
+        .await
+        .is_err());
+    assert_eq!(input.input_queue_len(), 3);
+}
+
+#[tokio::test]
+async fn test_unconnected_and_unbounded_queues_read_as_empty() {
+    assert_eq!(DummyTerminal::new(None).unwrap().input_queue_len(), 0);
+
+    let program = build(None);
+    let input = DummyTerminal::new(None)
+        .unwrap()
+        .with_event_sender(program.sender());
+    input.send(key()).unwrap();
+    assert_eq!(input.input_queue_len(), 0);
+}
@@ -164,6 +164,7 @@ async fn test_input_handler_mouse_event() -> Result<(), Error> {
                     y: mouse_event.row,
                     button: mouse_event.kind,
                     modifiers: mouse_event.modifiers,
+                    scroll_delta: 0,
                 };
                 let _ = input_handler.event_tx.send(Box::new(msg));
             }
@@ -269,3 +270,150 @@ async fn test_input_handler_focus_events() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_parse_sgr_horizontal_wheel() {
+    use bubbletea_rs::parse_sgr_mouse;
+
+    // Buttons 6/7 are encoded as 64 + 2 and 64 + 3
+    let left = parse_sgr_mouse("\x1b[<66;12;3M").unwrap();
+    assert_eq!(left.button, MouseEventKind::ScrollLeft);
+    assert_eq!((left.x, left.y), (11, 2));
+    assert_eq!(left.modifiers, KeyModifiers::NONE);
+
+    let right = parse_sgr_mouse("\x1b[<67;1;1M").unwrap();
+    assert_eq!(right.button, MouseEventKind::ScrollRight);
+    assert_eq!((right.x, right.y), (0, 0));
+
+    // Shift + horizontal wheel
+    let shifted = parse_sgr_mouse("\x1b[<71;5;5M").unwrap();
+    assert_eq!(shifted.button, MouseEventKind::ScrollRight);
+    assert_eq!(shifted.modifiers, KeyModifiers::SHIFT);
+}
+
+#[test]
+fn test_parse_sgr_mouse_coordinates_are_zero_based() {
+    use bubbletea_rs::parse_sgr_mouse;
+
+    // The terminal's top-left cell is (1,1) on the wire
+    let origin = parse_sgr_mouse("\x1b[<0;1;1M").unwrap();
+    assert_eq!((origin.x, origin.y), (0, 0));
+    assert_eq!((origin.column(), origin.row()), (0, 0));
+
+    let cell = parse_sgr_mouse("\x1b[<0;80;24m").unwrap();
+    assert_eq!((cell.column(), cell.row()), (79, 23));
+
+    // Out-of-spec zero coordinates saturate instead of wrapping
+    let zero = parse_sgr_mouse("\x1b[<0;0;0M").unwrap();
+    assert_eq!((zero.x, zero.y), (0, 0));
+}
+
+#[test]
+fn test_parse_sgr_buttons_and_motion() {
+    use bubbletea_rs::parse_sgr_mouse;
+
+    let cases = [
+        ("\x1b[<64;1;1M", MouseEventKind::ScrollUp),
+        ("\x1b[<65;1;1M", MouseEventKind::ScrollDown),
+        ("\x1b[<0;1;1M", MouseEventKind::Down(MouseButton::Left)),
+        ("\x1b[<2;1;1m", MouseEventKind::Up(MouseButton::Right)),
+        ("\x1b[<33;1;1M", MouseEventKind::Drag(MouseButton::Middle)),
+        ("\x1b[<35;1;1M", MouseEventKind::Moved),
+    ];
+    for (seq, kind) in cases {
+        assert_eq!(parse_sgr_mouse(seq).unwrap().button, kind, "{seq:?}");
+    }
+
+    let ctrl_alt = parse_sgr_mouse("\x1b[<24;1;1M").unwrap();
+    assert_eq!(
+        ctrl_alt.modifiers,
+        KeyModifiers::CONTROL | KeyModifiers::ALT
+    );
+
+    // Malformed or unsupported reports
+    assert!(parse_sgr_mouse("\x1b[<66;12M").is_none());
+    assert!(parse_sgr_mouse("\x1b[<66;12;3").is_none());
+    assert!(parse_sgr_mouse("\x1b[<66;12;3Mx").is_none());
+    assert!(parse_sgr_mouse("\x1b[<128;1;1M").is_none());
+    assert!(parse_sgr_mouse("hello").is_none());
+}
+
+#[test]
+fn test_parse_csi_u_enter_tab_and_ctrl_letters() {
+    use bubbletea_rs::parse_csi_u_key;
+
+    let cases = [
+        ("\x1b[13u", KeyCode::Enter, KeyModifiers::NONE),
+        ("\x1b[9u", KeyCode::Tab, KeyModifiers::NONE),
+        ("\x1b[9;2u", KeyCode::BackTab, KeyModifiers::SHIFT),
+        ("\x1b[109;5u", KeyCode::Char('m'), KeyModifiers::CONTROL),
+        ("\x1b[105;5u", KeyCode::Char('i'), KeyModifiers::CONTROL),
+        ("\x1b[27u", KeyCode::Esc, KeyModifiers::NONE),
+        ("\x1b[127;3u", KeyCode::Backspace, KeyModifiers::ALT),
+        // Alternate key codes and explicit press events are accepted
+        (
+            "\x1b[109:77;6:1u",
+            KeyCode::Char('m'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        ),
+    ];
+    for (seq, key, modifiers) in cases {
+        let msg = parse_csi_u_key(seq).unwrap();
+        assert_eq!((msg.key, msg.modifiers), (key, modifiers), "{seq:?}");
+    }
+
+    // Releases, functional keys and malformed reports
+    assert!(parse_csi_u_key("\x1b[13;1:3u").is_none());
+    assert!(parse_csi_u_key("\x1b[57399u").is_none());
+    assert!(parse_csi_u_key("\x1b[13").is_none());
+    assert!(parse_csi_u_key("\x1b[13ux").is_none());
+    assert!(parse_csi_u_key("\x1b[1;5A").is_none());
+    assert!(parse_csi_u_key("\r").is_none());
+}
+
+#[test]
+fn test_parse_cursor_keys_in_both_encodings() {
+    use bubbletea_rs::parse_cursor_key;
+
+    let keys = [
+        ('A', KeyCode::Up),
+        ('B', KeyCode::Down),
+        ('C', KeyCode::Right),
+        ('D', KeyCode::Left),
+        ('H', KeyCode::Home),
+        ('F', KeyCode::End),
+    ];
+    for (end, key) in keys {
+        // Normal mode (CSI) and application mode (SS3)
+        for seq in [format!("\x1b[{end}"), format!("\x1bO{end}")] {
+            let msg = parse_cursor_key(&seq).unwrap();
+            assert_eq!(
+                (msg.key, msg.modifiers),
+                (key, KeyModifiers::NONE),
+                "{seq:?}"
+            );
+        }
+    }
+
+    let cases = [
+        ("\x1b[1;5A", KeyCode::Up, KeyModifiers::CONTROL),
+        ("\x1b[1;2D", KeyCode::Left, KeyModifiers::SHIFT),
+        ("\x1b[1;3F", KeyCode::End, KeyModifiers::ALT),
+        ("\x1b[1~", KeyCode::Home, KeyModifiers::NONE),
+        ("\x1b[7~", KeyCode::Home, KeyModifiers::NONE),
+        ("\x1b[4~", KeyCode::End, KeyModifiers::NONE),
+        ("\x1b[8;5~", KeyCode::End, KeyModifiers::CONTROL),
+    ];
+    for (seq, key, modifiers) in cases {
+        let msg = parse_cursor_key(seq).unwrap();
+        assert_eq!((msg.key, msg.modifiers), (key, modifiers), "{seq:?}");
+    }
+
+    // Other keys, focus reports and incomplete sequences
+    assert!(parse_cursor_key("\x1b[2~").is_none());
+    assert!(parse_cursor_key("\x1b[O").is_none());
+    assert!(parse_cursor_key("\x1bOP").is_none());
+    assert!(parse_cursor_key("\x1b[1;5").is_none());
+    assert!(parse_cursor_key("\x1bO").is_none());
+    assert!(parse_cursor_key("\x1b[Ax").is_none());
+}
@@ -0,0 +1,92 @@
+use bubbletea_rs::{
+    event, Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, MouseMsg, Msg, Program, QuitMsg,
+    TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+/// Records each key or mouse message with its input timestamp.
+struct Recorder {
+    stamps: Vec<(String, Option<Instant>)>,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { stamps: Vec::new() }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let name = if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            format!("{:?}", key_msg.key)
+        } else if msg.is::<MouseMsg>() {
+            "mouse".to_string()
+        } else {
+            return;
+        };
+        self.stamps.push((name, event::input_timestamp()));
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_decoded_input_carries_increasing_timestamps() {
+    let (input, mut typing) = tokio::io::duplex(64);
+    let program = Program::<Recorder>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(input)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        let before = Instant::now();
+        typing.write_all(b"a\n").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        typing.write_all(b"\x1b[<0;3;4Mb\n").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // Sent rather than decoded, so it is stamped when received
+        let injected = Instant::now();
+        let _ = sender.send(Box::new(KeyMsg {
+            key: KeyCode::Char('z'),
+            modifiers: KeyModifiers::NONE,
+        }) as Msg);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+        (before, injected)
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    let (before, injected) = driver.await.unwrap();
+
+    let names: Vec<&str> = model.stamps.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "Char('a')",
+            "Enter",
+            "mouse",
+            "Char('b')",
+            "Enter",
+            "Char('z')"
+        ]
+    );
+    let stamps: Vec<Instant> = model
+        .stamps
+        .iter()
+        .map(|(name, at)| at.unwrap_or_else(|| panic!("{name} has no timestamp")))
+        .collect();
+    assert!(stamps[0] >= before);
+    assert!(stamps.windows(2).all(|pair| pair[0] <= pair[1]));
+    // The second line was typed 20ms after the first
+    assert!(stamps[2] - stamps[1] >= Duration::from_millis(15));
+    assert!(stamps[5] >= injected);
+    assert_eq!(event::input_timestamp(), None);
+}
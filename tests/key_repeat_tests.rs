@@ -0,0 +1,122 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, KeyMsg, KeyRepeatMsg, Model, Msg, Program, QuitMsg,
+    TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+/// Records how many presses each delivered key message stood for.
+struct Cursor {
+    updates: Vec<u16>,
+    row: u32,
+}
+
+impl Model for Cursor {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                updates: Vec::new(),
+                row: 0,
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some((key, presses)) = KeyMsg::presses(&msg) {
+            if key.key == KeyCode::Down {
+                self.updates.push(presses);
+                self.row += u32::from(presses);
+            }
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("row {}", self.row)
+    }
+}
+
+fn key(code: KeyCode) -> Msg {
+    Box::new(KeyMsg {
+        key: code,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+/// Queues `msgs` before the program starts and returns the final model.
+async fn run_with_queue(coalesce: bool, msgs: Vec<Msg>) -> Cursor {
+    let program = Program::<Cursor>::builder()
+        .signal_handler(false)
+        .coalesce_key_repeats(coalesce)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    for msg in msgs {
+        sender.send(msg).unwrap();
+    }
+    sender.send(Box::new(QuitMsg) as Msg).unwrap();
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run")
+}
+
+#[tokio::test]
+async fn test_queued_repeats_are_coalesced() {
+    let downs = (0..200).map(|_| key(KeyCode::Down)).collect();
+    let model = run_with_queue(true, downs).await;
+
+    assert!(
+        model.updates.len() <= 2,
+        "got {} updates",
+        model.updates.len()
+    );
+    assert_eq!(
+        model.updates.iter().map(|&n| u32::from(n)).sum::<u32>(),
+        200
+    );
+    assert_eq!(model.row, 200);
+}
+
+#[tokio::test]
+async fn test_repeats_are_delivered_one_by_one_by_default() {
+    let downs = (0..200).map(|_| key(KeyCode::Down)).collect();
+    let model = run_with_queue(false, downs).await;
+
+    assert_eq!(model.updates, vec![1; 200]);
+}
+
+#[tokio::test]
+async fn test_different_keys_end_a_run() {
+    let msgs = vec![
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Up),
+        key(KeyCode::Down),
+    ];
+    let model = run_with_queue(true, msgs).await;
+
+    // The Up key is kept for the next update rather than dropped
+    assert_eq!(model.updates, [3, 1]);
+}
+
+#[test]
+fn test_presses_reads_both_key_messages() {
+    let single = key(KeyCode::Down);
+    assert_eq!(KeyMsg::presses(&single).map(|(_, n)| n), Some(1));
+
+    let repeated: Msg = Box::new(KeyRepeatMsg {
+        key: KeyMsg {
+            key: KeyCode::Down,
+            modifiers: KeyModifiers::NONE,
+        },
+        repeat_count: 7,
+    });
+    assert_eq!(KeyMsg::presses(&repeated).map(|(_, n)| n), Some(7));
+
+    assert!(KeyMsg::presses(&(Box::new(QuitMsg) as Msg)).is_none());
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, KeyMsg, KeyboardEnhancementsMsg, Model, Msg, Program,
+    QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyboardEnhancementFlags};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Queries keyboard enhancements on start and records what it receives.
+struct Prober {
+    replies: Vec<KeyboardEnhancementsMsg>,
+    keys: Vec<KeyCode>,
+}
+
+impl Model for Prober {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            replies: Vec::new(),
+            keys: Vec::new(),
+        };
+        (model, Some(command::query_keyboard_enhancements()))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(reply) = msg.downcast_ref::<KeyboardEnhancementsMsg>() {
+            self.replies.push(*reply);
+        } else if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            self.keys.push(key_msg.key);
+        }
+        None::<Cmd>
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn build(terminal: DummyTerminal, input: tokio::io::DuplexStream) -> Program<Prober> {
+    Program::<Prober>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(input)
+        .build()
+        .expect("program build")
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_supporting_terminal_reply_is_reported_not_typed() {
+    let (input, mut replies) = tokio::io::duplex(64);
+    let program = build(DummyTerminal::new(None).unwrap(), input);
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        replies.write_all(b"\x1b[?1u\x1b[?62;22cx").await.unwrap();
+        drop(replies);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = program.run().await.expect("program run");
+    driver.await.unwrap();
+
+    assert_eq!(
+        model.replies,
+        [KeyboardEnhancementsMsg {
+            supported_flags: Some(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES),
+        }]
+    );
+    assert!(model.replies[0].is_supported());
+    // Only the key typed after the reply reaches the model
+    assert_eq!(model.keys, [KeyCode::Char('x')]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_device_attributes_alone_report_unsupported() {
+    let (input, mut replies) = tokio::io::duplex(64);
+    let program = build(DummyTerminal::new(None).unwrap(), input);
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        let start = tokio::time::Instant::now();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        replies.write_all(b"\x1b[?62;22c").await.unwrap();
+        drop(replies);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+        start.elapsed()
+    });
+
+    let model = program.run().await.expect("program run");
+    let elapsed = driver.await.unwrap();
+
+    // Answered without waiting for the timeout
+    assert!(elapsed < Duration::from_secs(1));
+    assert_eq!(
+        model.replies,
+        [KeyboardEnhancementsMsg {
+            supported_flags: None
+        }]
+    );
+    assert!(model.keys.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_silent_terminal_times_out_as_unsupported() {
+    let (input, _replies) = tokio::io::duplex(64);
+    let program = build(DummyTerminal::new(None).unwrap(), input);
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = program.run().await.expect("program run");
+    driver.await.unwrap();
+
+    assert_eq!(
+        model.replies,
+        [KeyboardEnhancementsMsg {
+            supported_flags: None
+        }]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rejected_flags_turn_keyboard_enhancement_off() {
+    let (input, mut replies) = tokio::io::duplex(64);
+    let terminal = DummyTerminal::new(None)
+        .unwrap()
+        .with_keyboard_enhancement();
+    let enabled = terminal.keyboard_enhancement_state();
+    let program = Program::<Prober>::builder()
+        .signal_handler(false)
+        .keyboard_enhancement(true)
+        .terminal(terminal)
+        .input(input)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let before = enabled.load(Ordering::SeqCst);
+        // The terminal knows the protocol but did not apply our flags
+        replies.write_all(b"\x1b[?0u\x1b[?62c").await.unwrap();
+        drop(replies);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let after = enabled.load(Ordering::SeqCst);
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+        (before, after)
+    });
+
+    let model = program.run().await.expect("program run");
+    let (before, after) = driver.await.unwrap();
+
+    assert!(before, "the protocol is enabled on start");
+    assert!(!after, "rejected flags turn the protocol off");
+    assert_eq!(
+        model.replies,
+        [KeyboardEnhancementsMsg {
+            supported_flags: Some(KeyboardEnhancementFlags::empty()),
+        }]
+    );
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "logging")]
+
+use bubbletea_rs::logging::{self, LogConfig};
+use bubbletea_rs::Error;
+
+#[tokio::test]
+async fn test_log_config_installs_logger_and_flush_writes_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logs").join("app.log");
+    LogConfig::new(&path)
+        .max_size(1024)
+        .max_files(3)
+        .build()
+        .expect("logger installs");
+
+    log::info!(target: "app", "started");
+    log::debug!("below the default level");
+    assert!(logging::flush().await.is_none());
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(written.ends_with(" INFO  app: started\n"), "{written:?}");
+    assert_eq!(written.lines().count(), 1);
+
+    // Only one logger can be installed per process
+    let again = LogConfig::new(dir.path().join("other.log")).build();
+    assert!(matches!(again, Err(Error::Configuration(_))));
+}
@@ -0,0 +1,110 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface, WindowSizeMsg,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Fills its width with `x` and records every width it is told.
+struct Ruler {
+    widths: Vec<u16>,
+}
+
+impl Model for Ruler {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { widths: Vec::new() }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(size) = msg.downcast_ref::<WindowSizeMsg>() {
+            self.widths.push(size.width);
+        }
+    }
+
+    fn view(&self) -> String {
+        let width = self.widths.last().copied().unwrap_or_default();
+        format!("{}\n\n{}", "x".repeat(usize::from(width)), "y")
+    }
+}
+
+/// Ignores its width and draws a bold line of 150 columns.
+struct Overflowing;
+
+impl Model for Overflowing {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        format!("\x1b[1m{}\x1b[0m\ny", "x".repeat(150))
+    }
+}
+
+async fn run<M: Model>(terminal_width: u16, max_width: u16, messages: Vec<Msg>) -> (M, String) {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone()))
+        .unwrap()
+        .with_size(terminal_width, 40);
+    let program = Program::<M>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .max_width(max_width)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        for msg in messages {
+            let _ = sender.send(msg);
+        }
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    (model, written)
+}
+
+#[tokio::test]
+async fn test_wide_terminal_is_capped_and_letterboxed() {
+    let (model, written) = run::<Ruler>(200, 100, Vec::new()).await;
+
+    assert_eq!(model.widths, [100]);
+    let margin = " ".repeat(50);
+    let frame = format!("{margin}{}\n\n{margin}y", "x".repeat(100));
+    assert!(written.starts_with(&frame), "{written:?}");
+}
+
+#[tokio::test]
+async fn test_resize_reports_capped_width() {
+    let resize = WindowSizeMsg {
+        width: 300,
+        height: 40,
+    };
+    let (model, _) = run::<Ruler>(200, 100, vec![Box::new(resize) as Msg]).await;
+
+    assert_eq!(model.widths, [100, 100]);
+}
+
+#[tokio::test]
+async fn test_narrow_terminal_is_unchanged() {
+    let (model, written) = run::<Ruler>(80, 100, Vec::new()).await;
+
+    assert_eq!(model.widths, [80]);
+    assert!(written.starts_with(&format!("{}\n\ny", "x".repeat(80))));
+}
+
+#[tokio::test]
+async fn test_lines_wider_than_max_width_are_cut_off() {
+    let (_, written) = run::<Overflowing>(200, 100, Vec::new()).await;
+
+    let margin = " ".repeat(50);
+    let frame = format!("{margin}\x1b[1m{}\x1b[0m\n{margin}y", "x".repeat(100));
+    assert!(written.starts_with(&frame), "{written:?}");
+}
@@ -0,0 +1,136 @@
+use bubbletea_rs::{command, Cmd, IntoCmd, MessageRateExceededMsg, Model, Msg, Program, QuitMsg};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct TickMsg;
+
+#[derive(Debug)]
+struct NoiseMsg;
+
+/// A tick loop that reschedules itself immediately, producing ticks as fast
+/// as the event loop can handle them.
+struct Runaway {
+    ticks: u64,
+    warning: Option<MessageRateExceededMsg>,
+}
+
+impl Model for Runaway {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                ticks: 0,
+                warning: None,
+            },
+            Some(Box::pin(async { Some(Box::new(TickMsg) as Msg) })),
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(warning) = msg.downcast_ref::<MessageRateExceededMsg>() {
+            self.warning = Some(warning.clone());
+            return Some(command::quit());
+        }
+        if !msg.is::<TickMsg>() {
+            return None;
+        }
+        self.ticks += 1;
+        Some(Box::pin(async { Some(Box::new(TickMsg) as Msg) }) as Cmd)
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_runaway_tick_loop_is_detected() {
+    let program = Program::<Runaway>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .warn_on_message_rate(1000, Duration::from_millis(300))
+        .build()
+        .expect("program build");
+    let stats = program.message_stats().expect("stats enabled").clone();
+    stats.register::<TickMsg>();
+
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("runaway loop should be reported")
+        .expect("program run");
+
+    let warning = model.warning.expect("rate warning delivered to the model");
+    assert!(warning.type_name.ends_with("TickMsg"));
+    assert!(warning.rate_per_sec > 1000.0);
+
+    let busiest = &stats.snapshot()[0];
+    assert!(busiest.type_name.ends_with("TickMsg"));
+    assert!(busiest.processed >= model.ticks);
+    assert!(stats.dump().contains("TickMsg"));
+}
+
+/// Receives a slow trickle of ticks and some messages that are filtered out.
+struct Steady;
+
+impl Model for Steady {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        assert!(!msg.is::<MessageRateExceededMsg>(), "slow ticks reported");
+        assert!(!msg.is::<NoiseMsg>(), "filtered messages reach the model");
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_steady_rate_is_not_reported_and_drops_are_counted() {
+    let program = Program::<Steady>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .memory_monitoring(true)
+        .warn_on_message_rate(1000, Duration::from_millis(100))
+        .filter(|_, msg| {
+            if msg.is::<NoiseMsg>() {
+                None
+            } else {
+                Some(msg)
+            }
+        })
+        .build()
+        .expect("program build");
+    let monitor = program
+        .memory_monitor()
+        .expect("monitoring enabled")
+        .clone();
+    monitor
+        .message_stats
+        .as_ref()
+        .expect("stats in monitor")
+        .register::<NoiseMsg>();
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        for _ in 0..20 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = sender.send(Box::new(TickMsg) as Msg);
+            let _ = sender.send(Box::new(NoiseMsg) as Msg);
+        }
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let types = monitor.snapshot().message_types;
+    let noise = types
+        .iter()
+        .find(|stats| stats.type_name.ends_with("NoiseMsg"))
+        .expect("noise counted");
+    assert_eq!((noise.processed, noise.dropped), (20, 20));
+}
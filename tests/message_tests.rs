@@ -0,0 +1,80 @@
+use bubbletea_rs::memory::MessageStats;
+use bubbletea_rs::message::{self, Message, MsgExt};
+use bubbletea_rs::{KeyMsg, Msg, QuitMsg};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[derive(Debug, PartialEq)]
+struct Loaded(Vec<u32>);
+
+#[test]
+fn test_take_moves_the_message_out() {
+    let msg: Msg = Box::new(Loaded(vec![1, 2, 3]));
+    assert!(MsgExt::is::<Loaded>(&msg));
+    assert_eq!(msg.take::<Loaded>(), Some(Loaded(vec![1, 2, 3])));
+}
+
+#[test]
+fn test_take_of_another_type_is_none() {
+    let msg: Msg = Box::new(QuitMsg);
+    assert!(!MsgExt::is::<Loaded>(&msg));
+    assert_eq!(msg.take::<Loaded>(), None);
+}
+
+#[test]
+fn test_builtin_messages_have_names() {
+    let key: Msg = Box::new(KeyMsg {
+        key: KeyCode::Char('a'),
+        modifiers: KeyModifiers::NONE,
+    });
+    assert_eq!(key.message_name(), Some("KeyMsg"));
+    let debug = message::describe(&key).and_then(Message::as_debug).unwrap();
+    assert!(format!("{debug:?}").contains("Char('a')"));
+
+    // Unregistered types have no name
+    let loaded: Msg = Box::new(Loaded(Vec::new()));
+    assert_eq!(loaded.message_name(), None);
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Debug, Message)]
+    struct TickMsg(u32);
+
+    #[derive(Message)]
+    #[message(name = "fetch", no_debug)]
+    struct FetchMsg<T: Send + 'static> {
+        _body: T,
+    }
+
+    #[test]
+    fn test_derive_names_the_type() {
+        let tick = TickMsg(7);
+        assert_eq!(tick.name(), "TickMsg");
+        assert_eq!(format!("{:?}", tick.as_debug().unwrap()), "TickMsg(7)");
+
+        let fetch = FetchMsg { _body: "page" };
+        assert_eq!(fetch.name(), "fetch");
+        assert!(fetch.as_debug().is_none());
+    }
+
+    #[test]
+    fn test_registered_names_are_found_and_counted() {
+        message::register::<TickMsg>();
+        let msg: Msg = Box::new(TickMsg(1));
+        assert_eq!(msg.message_name(), Some("TickMsg"));
+
+        let stats = MessageStats::new();
+        stats.record_processed(&msg);
+        assert_eq!(stats.snapshot()[0].type_name, "TickMsg");
+        assert_eq!(msg.take::<TickMsg>().map(|tick| tick.0), Some(1));
+    }
+}
+
+#[test]
+fn test_stats_prefer_message_names() {
+    let stats = MessageStats::new();
+    stats.record_processed(&(Box::new(QuitMsg) as Msg));
+    assert_eq!(stats.snapshot()[0].type_name, "QuitMsg");
+}
@@ -0,0 +1,151 @@
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, Model, ModesMsg, MouseMotion, MouseMsg, Msg, Program,
+    QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+use std::time::Duration;
+
+/// Starts a capture on every press and records the mouse events and mouse
+/// modes it sees.
+struct Dragger {
+    events: Vec<MouseEventKind>,
+    modes: Vec<MouseMotion>,
+}
+
+impl Model for Dragger {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            events: Vec::new(),
+            modes: Vec::new(),
+        };
+        (model, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(ModesMsg(modes)) = msg.downcast_ref::<ModesMsg>() {
+            self.modes.push(modes.mouse_motion);
+        }
+        let mouse = msg.downcast_ref::<MouseMsg>()?;
+        self.events.push(mouse.button);
+        match mouse.button {
+            MouseEventKind::Down(_) => Some(command::begin_mouse_capture()),
+            _ => None,
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn mouse(button: MouseEventKind) -> Msg {
+    Box::new(MouseMsg {
+        x: 3,
+        y: 4,
+        button,
+        modifiers: KeyModifiers::NONE,
+        scroll_delta: 0,
+    })
+}
+
+async fn run(mouse_motion: MouseMotion, msgs: Vec<Msg>) -> Dragger {
+    let program = Program::<Dragger>::builder()
+        .signal_handler(false)
+        .mouse_motion(mouse_motion)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let driver = async move {
+        for msg in msgs {
+            // Let the program act on each one, captures included
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = sender.send(msg);
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    };
+
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), driver)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run")
+}
+
+#[tokio::test]
+async fn test_motion_is_delivered_only_between_press_and_release() {
+    let left = MouseButton::Left;
+    let model = run(
+        MouseMotion::Cell,
+        vec![
+            mouse(MouseEventKind::Moved),
+            mouse(MouseEventKind::Down(left)),
+            mouse(MouseEventKind::Moved),
+            mouse(MouseEventKind::Drag(left)),
+            mouse(MouseEventKind::Moved),
+            mouse(MouseEventKind::Up(left)),
+            mouse(MouseEventKind::Moved),
+            mouse(MouseEventKind::Moved),
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        model.events,
+        [
+            MouseEventKind::Down(left),
+            MouseEventKind::Moved,
+            MouseEventKind::Drag(left),
+            MouseEventKind::Moved,
+            MouseEventKind::Up(left),
+        ]
+    );
+    // All motion for the drag, then back to cell motion
+    assert_eq!(model.modes, [MouseMotion::All, MouseMotion::Cell]);
+}
+
+#[tokio::test]
+async fn test_ending_a_capture_early_restores_the_prior_mode() {
+    let model = run(
+        MouseMotion::None,
+        vec![
+            Box::new(bubbletea_rs::BeginMouseCaptureMsg) as Msg,
+            mouse(MouseEventKind::Moved),
+            Box::new(bubbletea_rs::EndMouseCaptureMsg) as Msg,
+            mouse(MouseEventKind::Moved),
+        ],
+    )
+    .await;
+
+    assert_eq!(model.events, [MouseEventKind::Moved]);
+    assert_eq!(model.modes, [MouseMotion::All, MouseMotion::None]);
+}
+
+#[tokio::test]
+async fn test_a_mode_set_during_a_capture_outlasts_it() {
+    let left = MouseButton::Left;
+    let model = run(
+        MouseMotion::None,
+        vec![
+            mouse(MouseEventKind::Down(left)),
+            Box::new(bubbletea_rs::EnableMouseCellMotionMsg) as Msg,
+            mouse(MouseEventKind::Up(left)),
+            mouse(MouseEventKind::Drag(left)),
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        model.events,
+        [
+            MouseEventKind::Down(left),
+            MouseEventKind::Up(left),
+            MouseEventKind::Drag(left),
+        ]
+    );
+    assert_eq!(model.modes, [MouseMotion::All, MouseMotion::Cell]);
+}
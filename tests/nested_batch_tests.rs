@@ -0,0 +1,108 @@
+use bubbletea_rs::event::BatchCmdMsg;
+use bubbletea_rs::{
+    command, BatchMsgInternal, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, TerminalInterface,
+};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Named(&'static str);
+
+/// Asks the model to run the command built by the given function.
+struct Start(fn() -> Cmd);
+
+/// Records the messages it is updated with and quits after three.
+struct Recorder {
+    seen: Vec<&'static str>,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { seen: Vec::new() }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(Start(build)) = msg.downcast_ref::<Start>() {
+            return Some(build());
+        }
+        if let Some(Named(name)) = msg.downcast_ref::<Named>() {
+            self.seen.push(name);
+        } else if msg.is::<BatchMsgInternal>() || msg.is::<BatchCmdMsg>() {
+            self.seen.push("internal");
+        }
+        if self.seen.len() == 3 {
+            return Some(command::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        self.seen.join(",")
+    }
+}
+
+fn named(name: &'static str) -> Cmd {
+    Box::pin(async move { Some(Box::new(Named(name)) as Msg) })
+}
+
+/// Runs the command built by `build` and returns the messages the model saw.
+async fn updates(build: fn() -> Cmd) -> Vec<&'static str> {
+    let program = Program::<Recorder>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+    program.sender().send(Box::new(Start(build))).unwrap();
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    model.seen
+}
+
+#[tokio::test]
+async fn test_nested_sequences_update_like_a_flat_sequence() {
+    let flat = updates(|| command::sequence(vec![named("a"), named("b"), named("c")])).await;
+    let nested = updates(|| {
+        command::sequence(vec![
+            command::sequence(vec![named("a"), command::sequence(vec![named("b")])]),
+            named("c"),
+        ])
+    })
+    .await;
+
+    assert_eq!(flat, ["a", "b", "c"]);
+    assert_eq!(nested, flat);
+}
+
+#[tokio::test]
+async fn test_nested_batches_deliver_the_same_messages_as_a_flat_batch() {
+    // Batched commands run concurrently, so only the set of updates is fixed
+    let sorted = |mut seen: Vec<&'static str>| {
+        seen.sort_unstable();
+        seen
+    };
+    let flat = sorted(updates(|| command::batch(vec![named("a"), named("b"), named("c")])).await);
+    let nested = sorted(
+        updates(|| {
+            command::batch(vec![
+                command::batch(vec![named("a"), named("b")]),
+                named("c"),
+            ])
+        })
+        .await,
+    );
+    let in_sequence = sorted(
+        updates(|| {
+            command::sequence(vec![
+                command::batch(vec![named("a"), named("b")]),
+                named("c"),
+            ])
+        })
+        .await,
+    );
+
+    assert_eq!(flat, ["a", "b", "c"]);
+    assert_eq!(nested, flat);
+    assert_eq!(in_sequence, flat);
+}
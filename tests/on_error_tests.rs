@@ -0,0 +1,107 @@
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, DummyTerminalEvent, Error, IntoCmd, Model, Msg, Program, QuitMsg,
+    TerminalInterface, TerminalMode,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Interrupts itself on the first message.
+struct Interrupting;
+
+impl Model for Interrupting {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<QuitMsg>() {
+            return None;
+        }
+        Some(command::interrupt())
+    }
+
+    fn view(&self) -> String {
+        "frame".to_string()
+    }
+}
+
+/// What the error handler saw: each error, with the terminal calls made
+/// before it was called.
+type Reports = Arc<Mutex<Vec<(String, Vec<DummyTerminalEvent>)>>>;
+
+fn program(terminal: DummyTerminal, reports: &Reports) -> Program<Interrupting> {
+    let events = terminal.event_log();
+    let reports = Arc::clone(reports);
+    Program::<Interrupting>::builder()
+        .signal_handler(false)
+        .alt_screen(true)
+        .report_focus(true)
+        .strict_modes(true)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .on_error(move |err| {
+            let events = events.lock().unwrap().clone();
+            reports.lock().unwrap().push((err.to_string(), events));
+        })
+        .build()
+        .expect("program build")
+}
+
+#[tokio::test]
+async fn test_error_is_reported_after_the_terminal_is_restored() {
+    let reports = Reports::default();
+    let program = program(DummyTerminal::new(None).unwrap(), &reports);
+    program.sender().send(Box::new(()) as Msg).unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should stop");
+    assert!(matches!(result, Err(Error::Interrupted)));
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    let (error, events) = &reports[0];
+    assert_eq!(*error, Error::Interrupted.to_string());
+    assert_eq!(
+        events[events.len() - 2..],
+        [
+            DummyTerminalEvent::DisableFocusReporting,
+            DummyTerminalEvent::ExitAltScreen,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_a_failed_setup_is_undone_before_the_error_is_reported() {
+    let reports = Reports::default();
+    let terminal = DummyTerminal::new(None)
+        .unwrap()
+        .rejecting_mode(TerminalMode::FocusReporting);
+    let program = program(terminal, &reports);
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should stop");
+    assert!(matches!(result, Err(Error::Terminal(_))));
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    let (error, events) = &reports[0];
+    assert!(error.contains("FocusReporting"));
+    // The alternate screen was entered before focus reporting failed
+    assert_eq!(events.first(), Some(&DummyTerminalEvent::EnterAltScreen));
+    assert_eq!(events.last(), Some(&DummyTerminalEvent::ExitAltScreen));
+}
+
+#[tokio::test]
+async fn test_a_clean_exit_reports_nothing() {
+    let reports = Reports::default();
+    let program = program(DummyTerminal::new(None).unwrap(), &reports);
+    program.sender().send(Box::new(QuitMsg) as Msg).unwrap();
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    assert!(reports.lock().unwrap().is_empty());
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    batch, quit, Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+/// How many times each scenario is repeated to shake out races.
+const RUNS: usize = 50;
+
+#[derive(Debug)]
+struct Echo(char);
+
+/// Logs every key and echo it sees. `q` quits, `b` answers with a batch of
+/// echoes followed by a quit, `p` with a command that panics, and any other
+/// key is echoed in upper case.
+struct Log {
+    seen: String,
+}
+
+fn echo(c: char) -> Cmd {
+    Box::pin(async move { Some(Box::new(Echo(c)) as Msg) })
+}
+
+/// A command with a bug before its first `.await`.
+fn out_of_bounds() -> Cmd {
+    Box::pin(async move {
+        let echoes: Vec<char> = Vec::new();
+        Some(Box::new(Echo(echoes[0])) as Msg)
+    })
+}
+
+impl Model for Log {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                seen: String::new(),
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(Echo(c)) = msg.downcast_ref::<Echo>() {
+            self.seen.push(*c);
+        } else if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            let KeyCode::Char(c) = key_msg.key else {
+                return None;
+            };
+            self.seen.push(c);
+            return Some(match c {
+                'q' => quit(),
+                'b' => batch(vec![echo('1'), echo('2'), echo('3'), echo('4'), quit()]),
+                'p' => out_of_bounds(),
+                _ => echo(c.to_ascii_uppercase()),
+            });
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        self.seen.clone()
+    }
+}
+
+fn key(c: char) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+/// Queues `keys` before the program starts and returns what it saw.
+async fn run_with_keys(keys: &str) -> String {
+    let program = Program::<Log>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    for c in keys.chars() {
+        sender.send(key(c)).unwrap();
+    }
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run")
+        .seen
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_quit_waits_for_input_received_before_it() {
+    for _ in 0..RUNS {
+        // The echo of `a` and the quit queue behind `x` and `y`
+        assert_eq!(run_with_keys("aqxy").await, "aqxyA");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_immediate_batch_messages_keep_their_order() {
+    for _ in 0..RUNS {
+        assert_eq!(run_with_keys("b").await, "b1234");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_command_panicking_on_first_poll_does_not_end_the_program() {
+    // The panic is caught on the event loop, and the keys after it are
+    // still handled
+    assert_eq!(run_with_keys("pxq").await, "pxqX");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_immediate_messages_land_ahead_of_later_input() {
+    for _ in 0..RUNS {
+        let program = Program::<Log>::builder()
+            .signal_handler(false)
+            .terminal(DummyTerminal::new(None).unwrap())
+            .build()
+            .expect("program build");
+
+        let input = DummyTerminal::new(None)
+            .unwrap()
+            .with_event_sender(program.sender());
+        let driver = tokio::spawn(async move {
+            input.send(key('a')).unwrap();
+            // Once `a` has been taken off the queue, its echo is queued
+            // before anything typed afterwards
+            input.wait_until_idle(Duration::from_secs(1)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            input.send(key('q')).unwrap();
+        });
+
+        let seen = tokio::time::timeout(Duration::from_secs(2), program.run())
+            .await
+            .expect("program should quit")
+            .expect("program run")
+            .seen;
+        driver.await.unwrap();
+        assert_eq!(seen, "aAq");
+    }
+}
@@ -0,0 +1,188 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, InputHandler, InputSource, IntoCmd, KeyMsg, Model, Msg, PasteChunkMsg,
+    PasteEndMsg, PasteMsg, Program, TerminalInterface,
+};
+use crossterm::event::KeyCode;
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+
+/// Decodes `input` from a custom source, returning every message sent.
+async fn decode(input: &str, chunk_size: usize) -> Vec<Msg> {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Msg>();
+    let source = InputSource::Custom(Box::pin(Cursor::new(input.to_string())));
+    InputHandler::with_source(event_tx, source)
+        .with_paste_chunks(chunk_size)
+        .run()
+        .await
+        .unwrap();
+    let mut msgs = Vec::new();
+    while let Ok(msg) = event_rx.try_recv() {
+        msgs.push(msg);
+    }
+    msgs
+}
+
+fn key(msg: &Msg) -> Option<KeyCode> {
+    msg.downcast_ref::<KeyMsg>().map(|key| key.key)
+}
+
+/// A paste of many lines, with multi-byte characters to split.
+fn large_paste() -> String {
+    (0..20_000)
+        .map(|i| format!("line {i}: héllo wörld ✓\n"))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_bracketed_paste_spanning_lines_is_one_paste_msg() {
+    let msgs = decode("a\x1b[200~first\nsecond\x1b[201~b\n", 0).await;
+
+    assert_eq!(msgs.len(), 4);
+    assert_eq!(key(&msgs[0]), Some(KeyCode::Char('a')));
+    let paste = msgs[1]
+        .downcast_ref::<PasteMsg>()
+        .expect("expected PasteMsg");
+    assert_eq!(paste.text, "first\nsecond");
+    assert!(paste.bracketed);
+    assert_eq!(key(&msgs[2]), Some(KeyCode::Char('b')));
+    assert_eq!(key(&msgs[3]), Some(KeyCode::Enter));
+}
+
+#[tokio::test]
+async fn test_large_paste_without_chunks_is_one_paste_msg() {
+    let text = large_paste();
+    let msgs = decode(&format!("\x1b[200~{text}\x1b[201~"), 0).await;
+
+    assert_eq!(msgs.len(), 1);
+    assert_eq!(msgs[0].downcast_ref::<PasteMsg>().unwrap().text, text);
+}
+
+#[tokio::test]
+async fn test_large_paste_is_streamed_in_chunks() {
+    let text = large_paste();
+    let msgs = decode(&format!("\x1b[200~{text}\x1b[201~x"), 1000).await;
+
+    let (end, chunks) = msgs
+        .iter()
+        .position(|msg| msg.is::<PasteEndMsg>())
+        .map(|end| (end, &msgs[..end]))
+        .expect("expected PasteEndMsg");
+    let chunks: Vec<&str> = chunks
+        .iter()
+        .map(|msg| msg.downcast_ref::<PasteChunkMsg>().unwrap().text.as_str())
+        .collect();
+    assert!(chunks.len() > 1);
+    assert!(chunks
+        .iter()
+        .all(|chunk| !chunk.is_empty() && chunk.len() <= 1000));
+    assert_eq!(chunks.concat(), text);
+    assert_eq!(key(&msgs[end + 1]), Some(KeyCode::Char('x')));
+    assert_eq!(msgs.len(), end + 2);
+}
+
+#[tokio::test]
+async fn test_paste_without_line_breaks_is_streamed_in_chunks() {
+    // Terminals send pasted line breaks as carriage returns
+    let text = "pasted line\r".repeat(10_000);
+    let msgs = decode(&format!("a\x1b[200~{text}\x1b[201~b\n"), 4096).await;
+
+    assert_eq!(key(&msgs[0]), Some(KeyCode::Char('a')));
+    let chunks: String = msgs
+        .iter()
+        .filter_map(|msg| msg.downcast_ref::<PasteChunkMsg>())
+        .map(|chunk| chunk.text.as_str())
+        .collect();
+    assert_eq!(chunks, text);
+    let end = msgs.iter().position(|msg| msg.is::<PasteEndMsg>()).unwrap();
+    assert_eq!(key(&msgs[end + 1]), Some(KeyCode::Char('b')));
+    assert_eq!(key(&msgs[end + 2]), Some(KeyCode::Enter));
+}
+
+#[tokio::test]
+async fn test_a_paste_cut_short_by_eof_is_still_delivered() {
+    let msgs = decode("\x1b[200~partial\n", 0).await;
+
+    assert_eq!(msgs.len(), 1);
+    assert_eq!(
+        msgs[0].downcast_ref::<PasteMsg>().unwrap().text,
+        "partial\n"
+    );
+}
+
+/// A message sent while a paste is still arriving.
+struct PingMsg;
+
+/// Chunks the model has received so far, for the driver to wait on.
+static CHUNKS_SEEN: AtomicUsize = AtomicUsize::new(0);
+
+/// Records the paste chunks and other messages in the order they arrive.
+#[derive(Default)]
+struct Recorder {
+    events: Vec<&'static str>,
+    pasted: String,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self::default(), None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(chunk) = msg.downcast_ref::<PasteChunkMsg>() {
+            self.pasted.push_str(&chunk.text);
+            self.events.push("chunk");
+            CHUNKS_SEEN.fetch_add(1, Ordering::SeqCst);
+        } else if msg.is::<PingMsg>() {
+            self.events.push("ping");
+        } else if msg.is::<PasteEndMsg>() {
+            self.events.push("end");
+            return Some(bubbletea_rs::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_program_handles_other_messages_while_a_paste_streams() {
+    let (mut writer, reader) = tokio::io::duplex(64 * 1024);
+    let program = Program::<Recorder>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(reader)
+        .paste_chunk_size(4096)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+    let text = large_paste();
+
+    let drive = async {
+        writer.write_all(b"\x1b[200~").await.unwrap();
+        writer.write_all(text.as_bytes()).await.unwrap();
+        // The paste is not over, yet the model already has some of it
+        while CHUNKS_SEEN.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        sender.send(Box::new(PingMsg) as Msg).unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        writer.write_all(b"\x1b[201~").await.unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(10), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    let model = result.expect("program run");
+
+    assert_eq!(model.pasted, text);
+    let ping = model.events.iter().position(|&event| event == "ping");
+    let ping = ping.expect("the ping should be handled");
+    assert!(model.events[..ping].contains(&"chunk"));
+    assert_eq!(model.events.last(), Some(&"end"));
+}
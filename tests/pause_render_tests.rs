@@ -0,0 +1,96 @@
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// A counter whose updates only ever damage the first row.
+struct Counter {
+    count: u32,
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { count: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            match key_msg.key {
+                KeyCode::Char('p') => return Some(command::pause_render()),
+                KeyCode::Char('r') => return Some(command::resume_render()),
+                KeyCode::Char('+') => self.count += 1,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        format!("count {}\nfooter", self.count)
+    }
+
+    fn damage(&self) -> Option<Vec<Range<u16>>> {
+        let first_row = 0..1;
+        Some(vec![first_row])
+    }
+}
+
+fn key(ch: char) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(ch),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[tokio::test]
+async fn test_paused_render_writes_nothing_and_resume_redraws_in_full() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let terminal = DummyTerminal::new(Some(output.clone())).unwrap();
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let paused_output = output.clone();
+    let driver = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(key('+'));
+        let _ = sender.send(key('p'));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let before = paused_output.lock().await.clone();
+        let _ = sender.send(key('+'));
+        let _ = sender.send(key('+'));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let after = paused_output.lock().await.clone();
+        let _ = sender.send(key('r'));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+        (before, after)
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+    // Messages were still processed while rendering was paused
+    assert_eq!(model.count, 3);
+
+    let (before, after) = driver.await.unwrap();
+    assert_eq!(before, after, "nothing is written while paused");
+    // The key that asked for the pause is still drawn; the pause starts
+    // once its command's message arrives
+    assert_eq!(
+        String::from_utf8(before).unwrap(),
+        "count 0\nfootercount 1\ncount 1\n"
+    );
+
+    // Resuming draws the whole view rather than the damaged row
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(written, "count 0\nfootercount 1\ncount 1\ncount 3\nfooter");
+}
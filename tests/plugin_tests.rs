@@ -0,0 +1,179 @@
+use bubbletea_rs::plugin::{
+    BracketedPastePlugin, FocusPlugin, MousePlugin, Plugin, PluginRegistry, ResizeDebouncePlugin,
+};
+use bubbletea_rs::{
+    command, Cmd, DisableMouseMsg, EnableBracketedPasteMsg, EnableMouseAllMotionMsg,
+    EnableReportFocusMsg, IntoCmd, KeyMsg, Model, MouseMotion, Msg, Program, WindowSizeMsg,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct StartedMsg;
+
+/// Announces startup and swallows the `x` key.
+struct TestPlugin;
+
+impl Plugin for TestPlugin {
+    fn name(&self) -> &str {
+        "test"
+    }
+
+    fn on_startup(&mut self) -> Vec<Cmd> {
+        vec![Box::pin(async { Some(Box::new(StartedMsg) as Msg) })]
+    }
+
+    fn intercept(&mut self, msg: Msg, _cmds: &mut Vec<Cmd>) -> Option<Msg> {
+        match msg.downcast_ref::<KeyMsg>() {
+            Some(key) if key.key == KeyCode::Char('x') => None,
+            _ => Some(msg),
+        }
+    }
+}
+
+struct PluginModel {
+    started: bool,
+    keys: Vec<KeyCode>,
+    sizes: Vec<(u16, u16)>,
+}
+
+impl Model for PluginModel {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                started: false,
+                keys: Vec::new(),
+                sizes: Vec::new(),
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<StartedMsg>() {
+            self.started = true;
+        } else if let Some(key) = msg.downcast_ref::<KeyMsg>() {
+            self.keys.push(key.key);
+            if key.key == KeyCode::Char('q') {
+                return Some(command::quit());
+            }
+        } else if let Some(size) = msg.downcast_ref::<WindowSizeMsg>() {
+            self.sizes.push((size.width, size.height));
+            return Some(command::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn key(c: char) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[tokio::test]
+async fn test_plugin_startup_and_intercept() {
+    let program = Program::<PluginModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .with_plugin(Box::new(TestPlugin))
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        for c in ['a', 'x', 'b', 'q'] {
+            let _ = sender.send(key(c));
+        }
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    assert!(model.started);
+    assert_eq!(
+        model.keys,
+        vec![KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Char('q')]
+    );
+}
+
+#[tokio::test]
+async fn test_resize_debounce_delivers_only_latest_size() {
+    let program = Program::<PluginModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .with_plugin(Box::new(ResizeDebouncePlugin::new(Duration::from_millis(
+            80,
+        ))))
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        for width in 80..90 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = sender.send(Box::new(WindowSizeMsg { width, height: 24 }) as Msg);
+        }
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("debounced resize should arrive")
+        .expect("program run");
+
+    assert_eq!(model.sizes, vec![(89, 24)]);
+}
+
+#[tokio::test]
+async fn test_builtin_plugins_issue_mode_commands() {
+    let mut registry = PluginRegistry::new();
+    registry.register(Box::new(FocusPlugin));
+    registry.register(Box::new(BracketedPastePlugin));
+    registry.register(Box::new(MousePlugin(MouseMotion::All)));
+    assert_eq!(registry.names(), ["focus", "bracketed-paste", "mouse"]);
+
+    let mut startup = Vec::new();
+    for cmd in registry.startup() {
+        startup.push(cmd.await.expect("startup message"));
+    }
+    assert_eq!(startup.len(), 3);
+    assert!(startup[0].is::<EnableReportFocusMsg>());
+    assert!(startup[1].is::<EnableBracketedPasteMsg>());
+    assert!(startup[2].is::<EnableMouseAllMotionMsg>());
+
+    // Shutdown runs in reverse registration order
+    let shutdown = registry.shutdown();
+    assert_eq!(shutdown.len(), 3);
+    let first = shutdown.into_iter().next().unwrap().await.unwrap();
+    assert!(first.is::<DisableMouseMsg>());
+}
+
+#[test]
+fn test_registry_stops_at_swallowing_plugin() {
+    let mut registry = PluginRegistry::new();
+    registry.register(Box::new(TestPlugin));
+    registry.register(Box::new(ResizeDebouncePlugin::new(Duration::from_millis(
+        10,
+    ))));
+
+    let mut cmds = Vec::new();
+    assert!(registry.intercept(key('x'), &mut cmds).is_none());
+    assert!(registry.intercept(key('y'), &mut cmds).is_some());
+    assert!(cmds.is_empty());
+
+    // The debounce plugin swallows resizes and schedules its own tick
+    let resize = Box::new(WindowSizeMsg {
+        width: 10,
+        height: 10,
+    }) as Msg;
+    assert!(registry.intercept(resize, &mut cmds).is_none());
+    assert_eq!(cmds.len(), 1);
+}
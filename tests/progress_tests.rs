@@ -0,0 +1,252 @@
+use bubbletea_rs::easing::linear;
+use bubbletea_rs::gradient::gradient_filled_segment;
+use bubbletea_rs::progress::{AnimatedBar, ProgressFrameMsg};
+use bubbletea_rs::Msg;
+use std::time::Duration;
+
+/// A bar that jumps straight to each new percentage.
+fn instant_bar(width: usize) -> AnimatedBar {
+    AnimatedBar::new(width).with_animation(Duration::ZERO, linear)
+}
+
+/// The rendering the examples used before sharing `AnimatedBar`.
+fn legacy_view(width: usize, percent: f64, with_percentage: bool) -> String {
+    let filled_width = (width as f64 * percent).round() as usize;
+    let empty_width = width.saturating_sub(filled_width);
+    let bar = format!(
+        "{}{}",
+        gradient_filled_segment(filled_width, '█'),
+        "░".repeat(empty_width)
+    );
+    if with_percentage {
+        format!("{} {:5.1}%", bar, percent * 100.0)
+    } else {
+        bar
+    }
+}
+
+#[test]
+fn test_view_snapshots() {
+    let snapshots = [
+        (
+            4,
+            0.5,
+            "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m░░  50.0%",
+        ),
+        (
+            5,
+            0.6,
+            "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;254;190;172m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m░░  60.0%",
+        ),
+        (
+            8,
+            0.25,
+            "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m░░░░░░  25.0%",
+        ),
+        (
+            6,
+            1.0,
+            "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;255;150;190m█\x1b[0m\x1b[38;2;254;176;178m█\x1b[0m\x1b[38;2;254;203;165m█\x1b[0m\x1b[38;2;253;229;153m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m 100.0%",
+        ),
+        (3, 0.0, "░░░   0.0%"),
+    ];
+
+    for (width, percent, expected) in snapshots {
+        let mut bar = instant_bar(width);
+        assert!(bar.set_percent(percent).is_none());
+        assert_eq!(bar.view(), expected, "width {width} at {percent}");
+    }
+}
+
+#[test]
+fn test_view_matches_legacy_rendering() {
+    for width in [0, 1, 2, 7, 40, 80] {
+        for step in 0..=20 {
+            let percent = step as f64 / 20.0;
+            let mut bar = instant_bar(width);
+            bar.set_percent(percent);
+            assert_eq!(bar.view(), legacy_view(width, percent, true));
+
+            let mut plain = instant_bar(width).with_percentage(false);
+            plain.set_percent(percent);
+            assert_eq!(plain.view(), legacy_view(width, percent, false));
+        }
+    }
+}
+
+/// `Progress::view` from `examples/package-manager` before it switched to
+/// `AnimatedBar`: the bar alone.
+fn package_manager_view(width: usize, current_percent: f64) -> String {
+    let percent = current_percent.clamp(0.0, 1.0);
+    let filled_width = (width as f64 * percent).round() as usize;
+    let empty_width = width.saturating_sub(filled_width);
+    let filled_str = gradient_filled_segment(filled_width, '█');
+    let empty_str = '░'.to_string().repeat(empty_width);
+    format!("{}{}", filled_str, empty_str)
+}
+
+/// `AnimatedProgressBar::view` from `examples/progress-download` before it
+/// switched to `AnimatedBar`: the bar and the percentage.
+fn progress_download_view(width: usize, current_percent: f64) -> String {
+    let percent = current_percent.clamp(0.0, 1.0);
+    let filled_width = (width as f64 * percent).round() as usize;
+    let empty_width = width.saturating_sub(filled_width);
+    let filled_str = gradient_filled_segment(filled_width, '█');
+    let empty_str = '░'.to_string().repeat(empty_width);
+    let bar = format!("{}{}", filled_str, empty_str);
+    format!("{} {:5.1}%", bar, percent * 100.0)
+}
+
+#[test]
+fn test_package_manager_bar_snapshots() {
+    // The example's bar: 40 cells, no percentage
+    let new_bar = || instant_bar(40).with_percentage(false);
+
+    let snapshots = [
+        (0.0, "░".repeat(40)),
+        (
+            0.05,
+            format!(
+                "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m{}",
+                "░".repeat(38)
+            ),
+        ),
+        (
+            0.1,
+            format!(
+                "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;254;168;182m█\x1b[0m\x1b[38;2;254;211;161m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m{}",
+                "░".repeat(36)
+            ),
+        ),
+    ];
+    for (percent, expected) in snapshots {
+        let mut bar = new_bar();
+        bar.set_percent(percent);
+        assert_eq!(bar.view(), expected, "at {percent}");
+    }
+
+    // Every package count the example steps through
+    for total in [1, 3, 7, 12] {
+        for installed in 0..=total {
+            let percent = installed as f64 / total as f64;
+            let mut bar = new_bar();
+            bar.set_percent(percent);
+            assert_eq!(bar.view(), package_manager_view(40, percent));
+        }
+    }
+}
+
+#[test]
+fn test_progress_download_bar_snapshots() {
+    // The example's bar: 40 cells until the first resize, with the percentage
+    let mut bar = instant_bar(40);
+    bar.set_percent(0.05);
+    assert_eq!(
+        bar.view(),
+        format!(
+            "\x1b[38;2;255;124;203m█\x1b[0m\x1b[38;2;253;255;140m█\x1b[0m{}   5.0%",
+            "░".repeat(38)
+        )
+    );
+    bar.set_percent(0.0);
+    assert_eq!(bar.view(), format!("{}   0.0%", "░".repeat(40)));
+
+    // Resizes set the width to the window less padding, at most 80 cells
+    for columns in [0u16, 10, 44, 60, 84, 120] {
+        let width = (columns.saturating_sub(4).saturating_sub(4) as usize).min(80);
+        for step in 0..=40 {
+            let percent = step as f64 / 40.0;
+            let mut bar = instant_bar(40);
+            bar.set_width(width);
+            bar.set_percent(percent);
+            assert_eq!(bar.view(), progress_download_view(width, percent));
+        }
+    }
+}
+
+#[test]
+fn test_view_cache_follows_width_changes() {
+    let mut bar = instant_bar(10);
+    bar.set_percent(0.5);
+    assert_eq!(bar.view(), legacy_view(10, 0.5, true));
+
+    bar.set_width(20);
+    assert_eq!(bar.view(), legacy_view(20, 0.5, true));
+    bar.set_percent(0.25);
+    assert_eq!(bar.view(), legacy_view(20, 0.25, true));
+}
+
+#[test]
+fn test_solid_color_and_custom_chars() {
+    let mut bar = instant_bar(4)
+        .with_chars('#', '.')
+        .with_solid_color((1, 2, 3))
+        .with_percentage(false);
+    bar.set_percent(0.5);
+    assert_eq!(bar.view(), "\x1b[38;2;1;2;3m##\x1b[0m..");
+
+    bar.set_percent(0.0);
+    assert_eq!(bar.view(), "....");
+}
+
+#[test]
+fn test_custom_gradient_endpoints() {
+    let mut bar = instant_bar(2)
+        .with_gradient((255, 0, 0), (0, 0, 255))
+        .with_percentage(false);
+    bar.set_percent(1.0);
+    assert_eq!(
+        bar.view(),
+        "\x1b[38;2;255;0;0m█\x1b[0m\x1b[38;2;0;0;255m█\x1b[0m"
+    );
+}
+
+#[tokio::test]
+async fn test_frames_animate_to_target() {
+    let mut bar = AnimatedBar::new(10).with_animation(Duration::from_millis(100), linear);
+
+    let mut cmd = bar.set_percent(0.8);
+    assert!(bar.is_animating());
+    // A frame is already on its way, so retargeting does not start a second chain
+    assert!(bar.set_percent(1.0).is_none());
+
+    let mut frames = 0;
+    let mut last = bar.percent();
+    while let Some(frame) = cmd {
+        let msg = frame.await.expect("frame message");
+        cmd = bar.handle_frame_msg(&msg);
+        assert!(bar.percent() >= last);
+        last = bar.percent();
+        frames += 1;
+    }
+
+    assert_eq!(frames, 7); // 100ms at 16ms per frame
+    assert!(!bar.is_animating());
+    assert_eq!(bar.percent(), 1.0);
+    assert!(bar.set_percent(1.0).is_none());
+}
+
+#[test]
+fn test_ignores_other_messages() {
+    let mut bar = AnimatedBar::new(10);
+    let other = AnimatedBar::new(10);
+    assert!(bar.set_percent(0.5).is_some());
+
+    let foreign = Box::new(ProgressFrameMsg { id: other.id() }) as Msg;
+    assert!(bar.handle_frame_msg(&foreign).is_none());
+    assert!(bar.handle_frame_msg(&(Box::new(42u8) as Msg)).is_none());
+    assert_eq!(bar.percent(), 0.0);
+
+    let own = Box::new(ProgressFrameMsg { id: bar.id() }) as Msg;
+    assert!(bar.handle_frame_msg(&own).is_some());
+    assert!(bar.percent() > 0.0);
+}
+
+#[test]
+fn test_percent_is_clamped() {
+    let mut bar = instant_bar(10);
+    bar.set_percent(1.5);
+    assert_eq!(bar.target_percent(), 1.0);
+    bar.incr_percent(-3.0);
+    assert_eq!(bar.percent(), 0.0);
+}
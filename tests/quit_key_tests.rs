@@ -0,0 +1,217 @@
+use bubbletea_rs::{Cmd, IntoCmd, KeyMsg, KeySpec, Model, Msg, Program, QuitMsg};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+struct KeyRecorder {
+    keys: Vec<KeyCode>,
+    saw_quit: bool,
+}
+
+impl Model for KeyRecorder {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            keys: Vec::new(),
+            saw_quit: false,
+        };
+        (model, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            self.keys.push(key_msg.key);
+        }
+        self.saw_quit |= msg.is::<QuitMsg>();
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyMsg {
+    KeyMsg {
+        key: code,
+        modifiers,
+    }
+}
+
+#[tokio::test]
+async fn test_quit_on_key_reaches_the_model_as_a_quit() {
+    let program = Program::<KeyRecorder>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .quit_on("q")
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        for code in [KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Char('q')] {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let _ = sender.send(Box::new(key(code, KeyModifiers::NONE)) as Msg);
+        }
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("quit key should end the program")
+        .expect("program run");
+
+    // The quit key itself is not delivered; the model sees a QuitMsg instead
+    assert_eq!(model.keys, vec![KeyCode::Char('a'), KeyCode::Char('b')]);
+    assert!(model.saw_quit);
+}
+
+/// Sends `keys` to a program quitting on q, esc and ctrl+c, and returns the
+/// keys its model saw.
+async fn run_quit_keys(program: Program<KeyRecorder>, keys: Vec<KeyMsg>) -> Vec<KeyCode> {
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        for key_msg in keys {
+            let _ = sender.send(Box::new(key_msg) as Msg);
+        }
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("quit key should end the program")
+        .expect("program run");
+    model.keys
+}
+
+#[tokio::test]
+async fn test_quit_keys_quit_on_each_configured_key() {
+    for quit in [
+        key(KeyCode::Char('q'), KeyModifiers::NONE),
+        key(KeyCode::Esc, KeyModifiers::NONE),
+        key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+    ] {
+        let program = Program::<KeyRecorder>::builder()
+            .without_renderer()
+            .signal_handler(false)
+            .quit_keys(&["q", "esc", "ctrl+c"])
+            .build()
+            .expect("program build");
+        let keys = vec![key(KeyCode::Char('x'), KeyModifiers::NONE), quit];
+
+        // Keys that are not configured still reach the model
+        assert_eq!(run_quit_keys(program, keys).await, vec![KeyCode::Char('x')]);
+    }
+}
+
+#[tokio::test]
+async fn test_ctrl_c_follows_quit_on_ctrl_c() {
+    let ctrl_c = || key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+    // Off by default: Ctrl+C is an ordinary key until q quits
+    let program = Program::<KeyRecorder>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .quit_keys(&["q"])
+        .build()
+        .expect("program build");
+    let keys = vec![ctrl_c(), key(KeyCode::Char('q'), KeyModifiers::NONE)];
+    assert_eq!(run_quit_keys(program, keys).await, vec![KeyCode::Char('c')]);
+
+    let program = Program::<KeyRecorder>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .quit_on_ctrl_c(true)
+        .build()
+        .expect("program build");
+    let keys = vec![key(KeyCode::Char('x'), KeyModifiers::NONE), ctrl_c()];
+    assert_eq!(run_quit_keys(program, keys).await, vec![KeyCode::Char('x')]);
+}
+
+#[tokio::test]
+async fn test_filter_can_veto_a_quit_key() {
+    // The first q is swallowed, as a model with unsaved changes might
+    let program = Program::<KeyRecorder>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .quit_keys(&["q"])
+        .filter(|model: &KeyRecorder, msg| {
+            let first_q = model.keys.is_empty()
+                && msg
+                    .downcast_ref::<KeyMsg>()
+                    .is_some_and(|key_msg| key_msg.key == KeyCode::Char('q'));
+            if first_q {
+                Some(Box::new(key(KeyCode::Char('v'), KeyModifiers::NONE)) as Msg)
+            } else {
+                Some(msg)
+            }
+        })
+        .build()
+        .expect("program build");
+    let keys = vec![
+        key(KeyCode::Char('q'), KeyModifiers::NONE),
+        key(KeyCode::Char('q'), KeyModifiers::NONE),
+    ];
+
+    assert_eq!(run_quit_keys(program, keys).await, vec![KeyCode::Char('v')]);
+}
+
+#[tokio::test]
+async fn test_quit_on_ignores_keys_with_other_modifiers() {
+    let program = Program::<KeyRecorder>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .quit_on("ctrl+c")
+        .quit_on(KeyCode::Esc)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(key(KeyCode::Char('c'), KeyModifiers::NONE)) as Msg);
+        let _ = sender.send(Box::new(key(KeyCode::Esc, KeyModifiers::ALT)) as Msg);
+        let _ = sender.send(Box::new(key(KeyCode::Esc, KeyModifiers::NONE)) as Msg);
+    });
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("quit key should end the program")
+        .expect("program run");
+
+    assert_eq!(model.keys, vec![KeyCode::Char('c'), KeyCode::Esc]);
+}
+
+#[test]
+fn test_key_spec_parse() {
+    let spec = KeySpec::parse("ctrl+alt+Delete").unwrap();
+    assert_eq!(spec.key, KeyCode::Delete);
+    assert_eq!(spec.modifiers, KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+    assert_eq!(KeySpec::parse("f12").unwrap().key, KeyCode::F(12));
+    assert_eq!(KeySpec::parse("space").unwrap().key, KeyCode::Char(' '));
+    assert_eq!(KeySpec::parse("ctrl++").unwrap().key, KeyCode::Char('+'));
+    assert_eq!(KeySpec::parse("+").unwrap().key, KeyCode::Char('+'));
+
+    assert!(KeySpec::parse("").is_err());
+    assert!(KeySpec::parse("ctrl+").is_err());
+    assert!(KeySpec::parse("hyper+q").is_err());
+    assert!(KeySpec::parse("f99").is_err());
+    assert!(KeySpec::parse("qq").is_err());
+}
+
+#[test]
+fn test_key_spec_display_round_trips() {
+    for s in ["q", "ctrl+c", "alt+shift+enter", "f5", "space", "ctrl++"] {
+        let spec = KeySpec::parse(s).unwrap();
+        assert_eq!(spec.to_string(), s);
+        assert_eq!(KeySpec::parse(&spec.to_string()).unwrap(), spec);
+    }
+}
+
+#[test]
+fn test_key_spec_char_matching_ignores_shift() {
+    let spec = KeySpec::from('Q');
+    assert!(spec.matches(&key(KeyCode::Char('Q'), KeyModifiers::SHIFT)));
+    assert!(spec.matches(&key(KeyCode::Char('Q'), KeyModifiers::NONE)));
+    assert!(!spec.matches(&key(KeyCode::Char('q'), KeyModifiers::NONE)));
+
+    let up = KeySpec::parse("up").unwrap();
+    assert!(!up.matches(&key(KeyCode::Up, KeyModifiers::SHIFT)));
+}
@@ -0,0 +1,170 @@
+use bubbletea_rs::event::BatchMsgInternal;
+use bubbletea_rs::{
+    command, Cmd, Error, InterruptMsg, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg, QuitWithMsg,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Open,
+    Delete,
+}
+
+struct Menu {
+    quits_seen: usize,
+}
+
+impl Model for Menu {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { quits_seen: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<QuitMsg>() {
+            self.quits_seen += 1;
+        }
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            return match key_msg.key {
+                KeyCode::Char('o') => Some(command::quit_with(Action::Open)),
+                KeyCode::Char('d') => Some(command::quit_with(Action::Delete)),
+                KeyCode::Char('q') => Some(command::quit()),
+                _ => None,
+            };
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn build() -> Program<Menu> {
+    Program::<Menu>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build")
+}
+
+fn press(program: &Program<Menu>, code: KeyCode) {
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(KeyMsg {
+            key: code,
+            modifiers: KeyModifiers::NONE,
+        }) as Msg);
+    });
+}
+
+#[tokio::test]
+async fn test_quit_with_value_is_returned_typed() {
+    let program = build();
+    press(&program, KeyCode::Char('d'));
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run_with_result())
+        .await
+        .expect("quit_with should end the program")
+        .expect("program run");
+
+    assert!(result.has_exit_value());
+    assert_eq!(result.exit_value::<Action>(), Some(&Action::Delete));
+    // The model sees quit_with as an ordinary quit
+    assert_eq!(result.model.quits_seen, 1);
+
+    let (model, action) = result.into_parts::<Action>();
+    assert_eq!(model.quits_seen, 1);
+    assert_eq!(action, Some(Action::Delete));
+}
+
+#[tokio::test]
+async fn test_exit_value_of_wrong_type_is_none() {
+    let program = build();
+    press(&program, KeyCode::Char('o'));
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run_with_result())
+        .await
+        .expect("quit_with should end the program")
+        .expect("program run");
+
+    assert!(result.has_exit_value());
+    assert_eq!(result.exit_value::<String>(), None);
+    assert_eq!(result.exit_value::<Action>(), Some(&Action::Open));
+    assert_eq!(result.into_parts::<&str>().1, None);
+}
+
+#[tokio::test]
+async fn test_plain_quit_leaves_exit_value_unset() {
+    let program = build();
+    press(&program, KeyCode::Char('q'));
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run_with_result())
+        .await
+        .expect("quit should end the program")
+        .expect("program run");
+
+    assert!(!result.has_exit_value());
+    assert_eq!(result.exit_value::<Action>(), None);
+}
+
+#[tokio::test]
+async fn test_last_quit_with_value_wins() {
+    let program = build();
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(BatchMsgInternal {
+            messages: vec![
+                Box::new(QuitWithMsg {
+                    value: Box::new(Action::Open),
+                }),
+                Box::new(QuitWithMsg {
+                    value: Box::new(Action::Delete),
+                }),
+            ],
+        }) as Msg);
+    });
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run_with_result())
+        .await
+        .expect("quit_with should end the program")
+        .expect("program run");
+
+    assert_eq!(result.exit_value::<Action>(), Some(&Action::Delete));
+    assert_eq!(result.model.quits_seen, 2);
+}
+
+#[tokio::test]
+async fn test_interrupt_returns_error_instead_of_exit_value() {
+    let program = build();
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // The interrupt arrives before the quit_with is handled
+        let _ = sender.send(Box::new(InterruptMsg) as Msg);
+        let _ = sender.send(Box::new(QuitWithMsg {
+            value: Box::new(Action::Open),
+        }) as Msg);
+    });
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run_with_result())
+        .await
+        .expect("interrupt should end the program");
+
+    assert!(matches!(result, Err(Error::Interrupted)));
+}
+
+#[tokio::test]
+async fn test_run_still_returns_model_after_quit_with() {
+    let program = build();
+    press(&program, KeyCode::Char('o'));
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("quit_with should end the program")
+        .expect("program run");
+
+    assert_eq!(model.quits_seen, 1);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "testing")]
+
+use bubbletea_rs::{Cmd, IntoCmd, Model, Msg, Program, RenderHint, Terminal, TerminalInterface};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+struct List {
+    items: Vec<&'static str>,
+}
+
+impl Model for List {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                items: vec!["apples", "\x1b[1mpears\x1b[0m"],
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        self.items.join("\n")
+    }
+}
+
+#[test]
+fn test_render_to_string_without_a_program() {
+    let (model, _) = List::init();
+    assert_eq!(
+        Program::<List>::render_to_string(&model),
+        "apples\r\n\x1b[1mpears\x1b[0m"
+    );
+}
+
+#[tokio::test]
+async fn test_render_to_string_matches_terminal_frame() {
+    let (model, _) = List::init();
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone())).unwrap();
+    terminal
+        .render(&model.view(), RenderHint::Full)
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    let frame = written.strip_prefix("\x1b[H\x1b[2J").unwrap();
+    assert_eq!(Program::<List>::render_to_string(&model), frame);
+}
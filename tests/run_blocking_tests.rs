@@ -0,0 +1,103 @@
+#![cfg(feature = "sync-runtime")]
+
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct LoadedMsg(u32);
+
+#[derive(Debug)]
+struct RuntimeMsg {
+    on_tokio: bool,
+}
+
+/// Loads a starting value with a timer and a blocking command, then counts
+/// typed keys.
+struct Counter {
+    value: u32,
+    /// Whether any command ran on a tokio runtime
+    on_tokio: bool,
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        let load = command::tick(Duration::from_millis(5), |_| Box::new(LoadedMsg(10)) as Msg);
+        let blocking = command::blocking(|| Box::new(LoadedMsg(2)) as Msg);
+        let probe: Cmd = Box::pin(async {
+            let on_tokio = tokio::runtime::Handle::try_current().is_ok();
+            Some(Box::new(RuntimeMsg { on_tokio }) as Msg)
+        });
+        let model = Self {
+            value: 0,
+            on_tokio: false,
+        };
+        (model, Some(command::batch(vec![load, blocking, probe])))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(LoadedMsg(value)) = msg.downcast_ref::<LoadedMsg>() {
+            self.value += value;
+        } else if let Some(runtime) = msg.downcast_ref::<RuntimeMsg>() {
+            self.on_tokio |= runtime.on_tokio;
+        } else if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            match key_msg.key {
+                KeyCode::Char('q') => return Some(command::quit()),
+                _ => self.value += 1,
+            }
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        format!("value {}", self.value)
+    }
+}
+
+fn key(c: char) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[test]
+fn test_run_blocking_drives_a_scripted_program() {
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    let script = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        for c in ['a', 'b', 'q'] {
+            sender.send(key(c)).unwrap();
+        }
+    });
+
+    let model = program.run_blocking().expect("program run");
+    script.join().unwrap();
+
+    // The timer and the blocking command ran without a tokio runtime
+    assert_eq!(model.value, 14);
+    assert!(!model.on_tokio);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test]
+async fn test_run_blocking_refuses_to_block_an_async_runtime() {
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    assert!(matches!(
+        program.run_blocking(),
+        Err(bubbletea_rs::Error::Configuration(_))
+    ));
+}
@@ -0,0 +1,153 @@
+use bubbletea_rs::{
+    command, plugin::Plugin, Cmd, DummyTerminal, Error, ExecRequestMsg, IntoCmd, Model, Msg,
+    Program, QuitMsg, ResumeMsg, RunState, SuspendMsg, TerminalInterface,
+};
+use std::time::Duration;
+
+struct EditorClosedMsg;
+
+/// Quits once the external editor has closed.
+struct Session;
+
+impl Model for Session {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        msg.is::<EditorClosedMsg>().then(command::quit)
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Fails before the terminal is set up.
+struct Broken;
+
+impl Model for Broken {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn try_init() -> Result<(Self, Option<Cmd>), Error> {
+        Err(Error::Configuration("no config file".to_string()))
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Takes a moment to shut down, so the shutdown can be observed.
+struct SlowShutdown;
+
+impl Plugin for SlowShutdown {
+    fn name(&self) -> &str {
+        "slow-shutdown"
+    }
+
+    fn on_shutdown(&mut self) -> Vec<Cmd> {
+        vec![Box::pin(async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            None
+        })]
+    }
+}
+
+fn program<M: Model>() -> Program<M> {
+    Program::<M>::builder()
+        .signal_handler(false)
+        .with_plugin(Box::new(SlowShutdown))
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build")
+}
+
+/// Collects every state the program reports until it finishes.
+fn record(program: &Program<impl Model>) -> tokio::task::JoinHandle<Vec<RunState>> {
+    let mut state = program.state();
+    let mut states = vec![*state.borrow_and_update()];
+    tokio::spawn(async move {
+        while state.changed().await.is_ok() {
+            let current = *state.borrow_and_update();
+            states.push(current);
+            if current == RunState::Finished {
+                break;
+            }
+        }
+        states
+    })
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_states_follow_a_session_through_suspend_and_exec() {
+    let program = program::<Session>();
+    let states = record(&program);
+
+    let sender = program.sender();
+    let driver = async move {
+        let editor = std::process::Command::new("true");
+        let edit = ExecRequestMsg::new(editor, |_| Box::new(EditorClosedMsg) as Msg);
+        let script = [
+            Box::new(SuspendMsg) as Msg,
+            Box::new(ResumeMsg) as Msg,
+            Box::new(edit) as Msg,
+        ];
+        for msg in script {
+            // Let the observer see each state before the next
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let _ = sender.send(msg);
+        }
+    };
+
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), driver)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    use RunState::*;
+    assert_eq!(
+        states.await.unwrap(),
+        [
+            Initializing,
+            Running,
+            Suspended,
+            Running,
+            ReleasedTerminal,
+            Running,
+            ShuttingDown,
+            Finished,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_a_failed_init_still_reports_finishing() {
+    let program = program::<Broken>();
+    let states = record(&program);
+
+    assert!(program.run().await.is_err());
+
+    use RunState::*;
+    let states = states.await.unwrap();
+    assert_eq!(states.first(), Some(&Initializing));
+    assert_eq!(states.last(), Some(&Finished));
+}
+
+#[tokio::test]
+async fn test_quitting_ends_in_finished() {
+    let program = program::<Session>();
+    let mut state = program.state();
+    program.sender().send(Box::new(QuitMsg) as Msg).unwrap();
+
+    program.run().await.expect("program run");
+    assert_eq!(*state.borrow_and_update(), RunState::Finished);
+}
@@ -0,0 +1,96 @@
+use bubbletea_rs::{command, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, TerminalInterface};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct StepMsg;
+
+#[derive(Debug)]
+struct ShotMsg(String);
+
+/// Counts steps, and after each one asks for a screenshot; quits with the
+/// second.
+struct Counter {
+    steps: usize,
+    shots: Vec<(String, String)>,
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            steps: 0,
+            shots: Vec::new(),
+        };
+        (model, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(ShotMsg(frame)) = msg.downcast_ref::<ShotMsg>() {
+            // Keep the view the frame should match
+            self.shots.push((frame.clone(), self.view()));
+            return (self.shots.len() == 2).then(command::quit);
+        }
+        if !msg.is::<StepMsg>() {
+            return None;
+        }
+        self.steps += 1;
+        Some(command::screenshot(|frame| Box::new(ShotMsg(frame)) as Msg))
+    }
+
+    fn view(&self) -> String {
+        format!(
+            "\x1b[1mSteps\x1b[0m\n\x1b[38;2;255;0;0m{}\x1b[0m",
+            self.steps
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_screenshot_is_the_last_rendered_view() {
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+    sender.send(Box::new(StepMsg) as Msg).unwrap();
+    sender.send(Box::new(StepMsg) as Msg).unwrap();
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    assert_eq!(model.shots.len(), 2);
+    for (frame, view) in &model.shots {
+        assert_eq!(frame, view);
+        // Styling is kept
+        assert!(frame.contains("\x1b[38;2;255;0;0m"));
+    }
+}
+
+#[tokio::test]
+async fn test_screenshot_includes_the_letterbox_margin() {
+    let terminal = DummyTerminal::new(None).unwrap().with_size(20, 5);
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .max_width(10)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+    sender.send(Box::new(StepMsg) as Msg).unwrap();
+    sender.send(Box::new(StepMsg) as Msg).unwrap();
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let (frame, _) = &model.shots[0];
+    assert_eq!(
+        frame,
+        "     \x1b[1mSteps\x1b[0m\n     \x1b[38;2;255;0;0m2\x1b[0m"
+    );
+}
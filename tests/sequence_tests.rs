@@ -0,0 +1,134 @@
+use bubbletea_rs::{
+    command, BatchMsgInternal, CancelledMsg, Cmd, IntoCmd, Model, Msg, Program, SequenceMsgInternal,
+};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct ProgressMsg(usize, usize);
+
+#[derive(Debug)]
+struct StepDoneMsg(usize);
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Progress(usize, usize),
+    Done(usize),
+}
+
+struct WizardModel {
+    events: Vec<Event>,
+}
+
+fn step(index: usize, delay_ms: u64) -> Cmd {
+    command::tick(Duration::from_millis(delay_ms), move |_| {
+        Box::new(StepDoneMsg(index)) as Msg
+    })
+}
+
+impl Model for WizardModel {
+    fn init() -> (Self, Option<Cmd>) {
+        // The slowest step comes first: results must still arrive in order
+        let cmd = command::sequence_with_progress(
+            vec![step(0, 60), step(1, 10), step(2, 30)],
+            |index, total| Box::new(ProgressMsg(index, total)) as Msg,
+        );
+        (Self { events: Vec::new() }, Some(cmd))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(ProgressMsg(index, total)) = msg.downcast_ref::<ProgressMsg>() {
+            self.events.push(Event::Progress(*index, *total));
+        } else if let Some(StepDoneMsg(index)) = msg.downcast_ref::<StepDoneMsg>() {
+            self.events.push(Event::Done(*index));
+            if *index == 2 {
+                return Some(command::quit());
+            }
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_sequence_with_progress_interleaves_progress_and_results() {
+    let program = Program::<WizardModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("sequence should finish")
+        .expect("program run");
+
+    assert_eq!(
+        model.events,
+        vec![
+            Event::Progress(0, 3),
+            Event::Done(0),
+            Event::Progress(1, 3),
+            Event::Done(1),
+            Event::Progress(2, 3),
+            Event::Done(2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_sequence_with_progress_returns_internal_message() {
+    let cmd = command::sequence_with_progress(vec![command::quit(), command::quit()], |i, n| {
+        Box::new(ProgressMsg(i, n)) as Msg
+    });
+    let msg = cmd.await.expect("sequence message");
+    let sequence = msg
+        .downcast_ref::<SequenceMsgInternal>()
+        .expect("SequenceMsgInternal");
+    assert_eq!(sequence.steps.len(), 2);
+
+    let progress = (sequence.on_step)(1, 2);
+    let ProgressMsg(index, total) = progress.downcast_ref::<ProgressMsg>().unwrap();
+    assert_eq!((*index, *total), (1, 2));
+}
+
+#[derive(Debug)]
+struct AbortMsg;
+
+fn abort_step() -> Cmd {
+    Box::pin(async { Some(Box::new(AbortMsg) as Msg) })
+}
+
+fn done_indexes(batch: &BatchMsgInternal) -> Vec<usize> {
+    batch
+        .messages
+        .iter()
+        .filter_map(|msg| msg.downcast_ref::<StepDoneMsg>().map(|done| done.0))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sequence_until_msg_skips_remaining_steps() {
+    let cmd = command::sequence_until_msg::<AbortMsg>(vec![step(0, 1), abort_step(), step(2, 1)]);
+    let msg = cmd.await.expect("sequence message");
+    let batch = msg.downcast_ref::<BatchMsgInternal>().expect("batch");
+
+    assert_eq!(batch.messages.len(), 2);
+    assert_eq!(done_indexes(batch), vec![0]);
+    let cancelled = batch.messages[1]
+        .downcast_ref::<CancelledMsg>()
+        .expect("CancelledMsg");
+    assert_eq!(cancelled.step, 1);
+}
+
+#[tokio::test]
+async fn test_sequence_until_runs_every_step_without_cancellation() {
+    let cmd = command::sequence_until(vec![step(0, 1), step(1, 1)], |msg| msg.is::<AbortMsg>());
+    let msg = cmd.await.expect("sequence message");
+    let batch = msg.downcast_ref::<BatchMsgInternal>().expect("batch");
+
+    assert_eq!(done_indexes(batch), vec![0, 1]);
+    assert!(!batch.messages.iter().any(|m| m.is::<CancelledMsg>()));
+}
@@ -0,0 +1,130 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, Error, IntoCmd, KeyMsg, Model, Msg, Program, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Quits on any key.
+struct App;
+
+impl Model for App {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<KeyMsg>() {
+            return Some(bubbletea_rs::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        "app".to_string()
+    }
+}
+
+fn key() -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char('q'),
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+fn program_on(device: &str, output: Arc<Mutex<Vec<u8>>>) -> Program<App> {
+    let terminal = DummyTerminal::new(Some(output))
+        .unwrap()
+        .with_device(device);
+    Program::<App>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .expect("program build")
+}
+
+#[tokio::test]
+async fn test_sequential_programs_share_a_terminal() {
+    for _ in 0..2 {
+        let program = program_on("seq-tty", Arc::new(Mutex::new(Vec::new())));
+        let sender = program.sender();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = sender.send(key());
+        });
+        tokio::time::timeout(Duration::from_secs(2), program.run())
+            .await
+            .expect("program timed out")
+            .expect("program run");
+    }
+}
+
+#[tokio::test]
+async fn test_second_concurrent_program_fails_without_touching_terminal() {
+    let first_output = Arc::new(Mutex::new(Vec::new()));
+    let second_output = Arc::new(Mutex::new(Vec::new()));
+    let first = program_on("shared-tty", first_output.clone());
+    let second = program_on("shared-tty", second_output.clone());
+
+    let sender = first.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let _ = sender.send(key());
+    });
+
+    let (first, second) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(first.run(), async {
+            // Let the first program claim the terminal
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            second.run().await
+        })
+    })
+    .await
+    .expect("programs timed out");
+
+    assert!(matches!(second, Err(Error::AlreadyRunning)));
+    assert!(second_output.lock().await.is_empty());
+
+    // The first program ran to completion undisturbed
+    first.expect("first program run");
+    // The initial frame and the one drawn after the key
+    assert_eq!(first_output.lock().await.as_slice(), b"appapp");
+}
+
+#[tokio::test]
+async fn test_terminals_without_device_run_concurrently() {
+    let terminal = DummyTerminal::new(None).unwrap();
+    assert_eq!(terminal.device(), None);
+    assert_eq!(
+        DummyTerminal::new(None)
+            .unwrap()
+            .with_device("pty")
+            .device(),
+        Some("pty")
+    );
+
+    let a = Program::<App>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .unwrap();
+    let b = Program::<App>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .unwrap();
+    let (a_tx, b_tx) = (a.sender(), b.sender());
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = a_tx.send(key());
+        let _ = b_tx.send(key());
+    });
+    let (a, b) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(a.run(), b.run())
+    })
+    .await
+    .expect("programs timed out");
+    assert!(a.is_ok() && b.is_ok());
+    assert!(!Program::<App>::is_running());
+}
@@ -0,0 +1,91 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, Error, IntoCmd, Model, ModesDegradedMsg, MouseMotion, Msg, Program,
+    QuitMsg, TerminalInterface, TerminalMode,
+};
+use std::time::Duration;
+
+/// Keeps the degraded modes report, if any, and quits on a `QuitMsg`.
+struct Recorder {
+    degraded: Option<ModesDegradedMsg>,
+}
+
+impl Model for Recorder {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { degraded: None }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(degraded) = msg.downcast_ref::<ModesDegradedMsg>() {
+            self.degraded = Some(degraded.clone());
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+fn program(terminal: DummyTerminal, strict: bool) -> Program<Recorder> {
+    Program::<Recorder>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .input(tokio::io::duplex(64).0)
+        .mouse_motion(MouseMotion::Cell)
+        .bracketed_paste(true)
+        .strict_modes(strict)
+        .build()
+        .expect("program build")
+}
+
+/// Runs `program` until it has handled its startup messages.
+async fn run(program: Program<Recorder>) -> Recorder {
+    let sender = program.sender();
+    let quit = async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), quit)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run")
+}
+
+#[tokio::test]
+async fn test_program_runs_without_a_rejected_mode() {
+    let terminal = DummyTerminal::new(None)
+        .unwrap()
+        .rejecting_mode(TerminalMode::Mouse);
+    let program = program(terminal, false);
+    let model = run(program).await;
+
+    let degraded = model.degraded.expect("degraded modes reported");
+    assert_eq!(degraded.modes.mouse_motion, MouseMotion::None);
+    // The modes that worked are still on
+    assert!(degraded.modes.bracketed_paste);
+    assert_eq!(degraded.failures.len(), 1);
+    assert_eq!(degraded.failures[0].0, TerminalMode::Mouse);
+}
+
+#[tokio::test]
+async fn test_no_report_when_every_mode_applies() {
+    let program = program(DummyTerminal::new(None).unwrap(), false);
+    let model = run(program).await;
+
+    assert!(model.degraded.is_none());
+}
+
+#[tokio::test]
+async fn test_strict_modes_fail_on_a_rejected_mode() {
+    let terminal = DummyTerminal::new(None)
+        .unwrap()
+        .rejecting_mode(TerminalMode::Mouse);
+    let program = program(terminal, true);
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should stop");
+
+    assert!(matches!(result, Err(Error::Terminal(_))));
+}
@@ -0,0 +1,72 @@
+use bubbletea_rs::{
+    ColorProfile, OutputStream, RenderHint, SizeSource, Terminal, TerminalCapabilities,
+    TerminalInterface,
+};
+use std::process::Command;
+
+/// Set when this test binary is re-run as the child process.
+const CHILD_ENV: &str = "BUBBLETEA_STDERR_OUTPUT_CHILD";
+
+/// Drives a stderr terminal the way the program does. Only does anything in
+/// the child process started by the test below.
+#[tokio::test]
+async fn child_renders_to_stderr() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    let mut terminal = Terminal::new(None)
+        .unwrap()
+        .with_capabilities(TerminalCapabilities {
+            synchronized_output: true,
+            title_stack: false,
+            bracketed_paste: false,
+            size_source: SizeSource::Terminal,
+            color_profile: ColorProfile::Ansi,
+        })
+        .with_output_stream(OutputStream::Stderr);
+
+    terminal.hide_cursor().await.unwrap();
+    terminal.set_window_title("picker").await.unwrap();
+    terminal
+        .render_frame("\x1b[1m> first\x1b[0m", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal.print_preserving_cursor("printed\n").await.unwrap();
+    terminal.show_cursor().await.unwrap();
+}
+
+#[test]
+fn test_stderr_output_writes_no_bytes_to_stdout() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "child_renders_to_stderr",
+            "--exact",
+            "--nocapture",
+            "--color",
+            "never",
+        ])
+        .env(CHILD_ENV, "1")
+        .output()
+        .expect("run child test");
+    assert!(output.status.success(), "child failed: {output:?}");
+
+    // The test harness reports on stdout, but no terminal output may end up there
+    assert!(
+        !output.stdout.contains(&0x1b),
+        "escape bytes on stdout: {:?}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for expected in [
+        "\x1b[?25l",
+        "\x1b]0;picker\x07",
+        "\x1b[?2026h",
+        "> first",
+        "printed",
+    ] {
+        assert!(
+            stderr.contains(expected),
+            "missing {expected:?} in {stderr:?}"
+        );
+    }
+}
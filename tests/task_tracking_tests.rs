@@ -0,0 +1,92 @@
+use bubbletea_rs::{command, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, TerminalInterface};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct TickMsg;
+
+fn tick() -> Cmd {
+    command::tick(Duration::from_millis(10), |_| Box::new(TickMsg) as Msg)
+}
+
+/// Re-arms its timer twice on every tick, so the pending ticks double.
+struct Leaky;
+
+impl Model for Leaky {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, Some(tick()))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        msg.is::<TickMsg>()
+            .then(|| command::batch(vec![tick(), tick()]))
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[tokio::test(start_paused = true)]
+async fn test_double_armed_timer_grows_the_in_flight_count() {
+    let program = Program::<Leaky>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .track_tasks(true)
+        .build()
+        .expect("program build");
+    let monitor = program.memory_monitor().expect("monitor").clone();
+    let sender = program.sender();
+
+    let sample = async {
+        let mut counts = Vec::new();
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_millis(25)).await;
+            counts.push(monitor.snapshot().in_flight_commands);
+        }
+        sender.send(Box::new(bubbletea_rs::QuitMsg) as Msg).unwrap();
+        counts
+    };
+    let (result, counts) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), sample)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    assert!(
+        counts.windows(2).all(|pair| pair[0] < pair[1]),
+        "in-flight counts {counts:?}"
+    );
+    // Ticks dropped on shutdown are counted out
+    assert_eq!(monitor.get_in_flight_commands(), 0);
+}
+
+#[tokio::test]
+async fn test_in_flight_commands_are_not_counted_by_default() {
+    let program = Program::<Leaky>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .memory_monitoring(true)
+        .build()
+        .expect("program build");
+    let monitor = program.memory_monitor().expect("monitor").clone();
+    let sender = program.sender();
+
+    let sample = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let count = monitor.get_in_flight_commands();
+        sender.send(Box::new(bubbletea_rs::QuitMsg) as Msg).unwrap();
+        count
+    };
+    let (result, count) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), sample)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    assert_eq!(count, 0);
+}
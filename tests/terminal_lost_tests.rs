@@ -0,0 +1,155 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, Error, IntoCmd, Model, Msg, Program, TerminalInterface, TerminalLostMsg,
+};
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+use tokio::sync::Mutex;
+
+/// An output whose every write fails with the same error kind.
+struct FailingWriter(io::ErrorKind);
+
+impl AsyncWrite for FailingWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        _buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Err(io::Error::from(self.0)))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn failing_terminal(kind: io::ErrorKind) -> DummyTerminal {
+    let writer: Arc<Mutex<dyn AsyncWrite + Send + Unpin>> =
+        Arc::new(Mutex::new(FailingWriter(kind)));
+    DummyTerminal::new(Some(writer)).unwrap()
+}
+
+struct Ping;
+
+static BROKEN_PIPE_LOST: AtomicBool = AtomicBool::new(false);
+static BROKEN_PIPE_SAVED: AtomicBool = AtomicBool::new(false);
+
+/// Saves its state from a command when the terminal is lost.
+struct Saver;
+
+impl Model for Saver {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let lost = msg.downcast_ref::<TerminalLostMsg>()?;
+        assert_eq!(lost.kind, io::ErrorKind::BrokenPipe);
+        BROKEN_PIPE_LOST.store(true, Ordering::SeqCst);
+        Some(Box::pin(async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            BROKEN_PIPE_SAVED.store(true, Ordering::SeqCst);
+            None
+        }) as Cmd)
+    }
+
+    fn view(&self) -> String {
+        "saver".to_string()
+    }
+}
+
+#[tokio::test]
+async fn test_broken_pipe_shuts_down_with_terminal_lost() {
+    let program = Program::<Saver>::builder()
+        .signal_handler(false)
+        .terminal(failing_terminal(io::ErrorKind::BrokenPipe))
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(Ping));
+    });
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should shut down on its own");
+
+    match result {
+        Err(Error::TerminalLost(source)) => {
+            assert_eq!(source.kind(), io::ErrorKind::BrokenPipe);
+        }
+        other => panic!("expected TerminalLost, got {:?}", other.err()),
+    }
+    assert!(BROKEN_PIPE_LOST.load(Ordering::SeqCst));
+    assert!(BROKEN_PIPE_SAVED.load(Ordering::SeqCst));
+}
+
+static HANDLED_BEFORE_LOSS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts the messages it handled before the terminal was lost.
+struct Counter {
+    handled: usize,
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { handled: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<TerminalLostMsg>() {
+            HANDLED_BEFORE_LOSS.store(self.handled, Ordering::SeqCst);
+        } else {
+            self.handled += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("{}", self.handled)
+    }
+}
+
+#[tokio::test]
+async fn test_transient_write_errors_are_retried_before_giving_up() {
+    let program = Program::<Counter>::builder()
+        .signal_handler(false)
+        .terminal(failing_terminal(io::ErrorKind::Other))
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = sender.send(Box::new(Ping));
+        }
+    });
+
+    let result = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should shut down on its own");
+
+    // The initial frame and the first ping's frame fail and are skipped,
+    // the second ping's frame gives up
+    assert!(matches!(result, Err(Error::TerminalLost(_))));
+    assert_eq!(HANDLED_BEFORE_LOSS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_terminal_lost_error_keeps_io_source() {
+    let err = Error::TerminalLost(io::Error::from(io::ErrorKind::BrokenPipe));
+    assert!(err.to_string().starts_with("Terminal lost: "));
+    let source = std::error::Error::source(&err).expect("io error source");
+    let io_err = source.downcast_ref::<io::Error>().unwrap();
+    assert_eq!(io_err.kind(), io::ErrorKind::BrokenPipe);
+}
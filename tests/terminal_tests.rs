@@ -0,0 +1,714 @@
+use bubbletea_rs::{
+    resolve_size, Cmd, ColorProfile, CursorKeyMode, DummyTerminal, DummyTerminalEvent,
+    DummyTerminalPool, IntoCmd, KeyMsg, Model, Msg, Program, RenderHint, SizeSource, Terminal,
+    TerminalCapabilities, TerminalInterface, DEFAULT_SIZE,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+const SYNC: TerminalCapabilities = TerminalCapabilities {
+    synchronized_output: true,
+    title_stack: false,
+    bracketed_paste: false,
+    size_source: SizeSource::Terminal,
+    color_profile: ColorProfile::Ansi,
+};
+
+const TITLE_STACK: TerminalCapabilities = TerminalCapabilities {
+    synchronized_output: false,
+    title_stack: true,
+    bracketed_paste: false,
+    size_source: SizeSource::Terminal,
+    color_profile: ColorProfile::Ansi,
+};
+
+#[tokio::test]
+async fn test_terminal_brackets_frames_with_synchronized_output() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(SYNC);
+
+    terminal
+        .render_frame("hello", RenderHint::Full)
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert!(written.starts_with("\x1b[?2026h"), "got {written:?}");
+    assert!(written.ends_with("\x1b[?2026l"), "got {written:?}");
+    assert!(written.contains("hello"));
+}
+
+#[tokio::test]
+async fn test_terminal_skips_synchronized_output_without_capability() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal.enter_synchronized_output().await.unwrap();
+    terminal
+        .render_frame("hello", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal.exit_synchronized_output().await.unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert!(!written.contains("2026"), "got {written:?}");
+}
+
+#[tokio::test]
+async fn test_terminal_synchronized_output_is_idempotent() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(SYNC);
+
+    terminal.exit_synchronized_output().await.unwrap();
+    terminal.enter_synchronized_output().await.unwrap();
+    terminal.enter_synchronized_output().await.unwrap();
+    terminal.exit_synchronized_output().await.unwrap();
+    terminal.exit_synchronized_output().await.unwrap();
+
+    assert_eq!(output.lock().await.as_slice(), b"\x1b[?2026h\x1b[?2026l");
+}
+
+#[tokio::test]
+async fn test_dummy_terminal_records_synchronized_frames() {
+    let mut terminal = DummyTerminal::new(None).unwrap().with_capabilities(SYNC);
+    terminal
+        .render_frame("one", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal
+        .render_frame("two", RenderHint::Full)
+        .await
+        .unwrap();
+
+    use DummyTerminalEvent::*;
+    assert_eq!(
+        terminal.events(),
+        [
+            EnterSynchronizedOutput,
+            Render,
+            ExitSynchronizedOutput,
+            EnterSynchronizedOutput,
+            Render,
+            ExitSynchronizedOutput,
+        ]
+    );
+
+    let mut plain = DummyTerminal::new(None).unwrap();
+    plain.render_frame("one", RenderHint::Full).await.unwrap();
+    assert_eq!(plain.events(), [Render]);
+}
+
+#[test]
+fn test_capabilities_from_env() {
+    let detect = |vars: &'static [(&'static str, &'static str)]| {
+        TerminalCapabilities::from_env(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    };
+
+    assert!(detect(&[("TERM", "xterm-kitty")]).synchronized_output);
+    assert!(detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]).synchronized_output);
+    assert!(detect(&[("WT_SESSION", "1234")]).synchronized_output);
+    assert!(!detect(&[("TERM", "xterm-256color")]).synchronized_output);
+    assert!(!detect(&[]).synchronized_output);
+
+    assert!(detect(&[("TERM", "foot")]).title_stack);
+    assert!(detect(&[("XTERM_VERSION", "XTerm(390)")]).title_stack);
+    assert!(!detect(&[("TERM", "xterm-256color")]).title_stack);
+
+    assert!(detect(&[("TERM", "xterm-256color")]).bracketed_paste);
+    assert!(detect(&[("TERM", "tmux-256color")]).bracketed_paste);
+    assert!(detect(&[("TERM_PROGRAM", "iTerm.app")]).bracketed_paste);
+    assert!(!detect(&[("TERM", "linux")]).bracketed_paste);
+    assert!(!detect(&[("TERM", "dumb")]).bracketed_paste);
+
+    let profile = |vars| detect(vars).color_profile;
+    assert_eq!(
+        profile(&[("COLORTERM", "truecolor")]),
+        ColorProfile::TrueColor
+    );
+    assert_eq!(profile(&[("TERM", "xterm-kitty")]), ColorProfile::TrueColor);
+    assert_eq!(
+        profile(&[("TERM", "xterm-256color")]),
+        ColorProfile::Ansi256
+    );
+    assert_eq!(profile(&[("TERM", "xterm")]), ColorProfile::Ansi);
+    assert_eq!(profile(&[("TERM", "dumb")]), ColorProfile::NoColor);
+}
+
+#[tokio::test]
+async fn test_terminal_print_is_bracketed_by_cursor_save_and_restore() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal
+        .print_preserving_cursor("line one\nline two")
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(written, "\x1b7line one\r\nline two\x1b8");
+}
+
+#[tokio::test]
+async fn test_dummy_terminal_records_cursor_brackets_around_print() {
+    let mut terminal = DummyTerminal::new(None).unwrap();
+    terminal.print_preserving_cursor("hello").await.unwrap();
+    terminal
+        .render_frame("view", RenderHint::Full)
+        .await
+        .unwrap();
+
+    use DummyTerminalEvent::*;
+    assert_eq!(
+        terminal.events(),
+        [SaveCursor, Print, RestoreCursor, Render]
+    );
+}
+
+#[tokio::test]
+async fn test_printf_command_produces_print_message() {
+    let msg = bubbletea_rs::printf("\x1b[1;1Hstatus".to_string())
+        .await
+        .unwrap();
+    let printf = msg.downcast_ref::<bubbletea_rs::PrintfMsg>().unwrap();
+    assert_eq!(printf.0, "\x1b[1;1Hstatus");
+
+    assert!(bubbletea_rs::save_cursor()
+        .await
+        .unwrap()
+        .is::<bubbletea_rs::SaveCursorMsg>());
+    assert!(bubbletea_rs::restore_cursor()
+        .await
+        .unwrap()
+        .is::<bubbletea_rs::RestoreCursorMsg>());
+}
+
+/// The checkmark lipgloss renders for the package-manager example.
+const STYLED_CHECK: &str = "\x1b[38;5;42m✓\x1b[0m";
+
+#[tokio::test]
+async fn test_terminal_prints_styled_line_above_frame() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal
+        .print_preserving_cursor(&format!("{STYLED_CHECK} left-pad\n"))
+        .await
+        .unwrap();
+    terminal
+        .render_frame("Installing 2/5", RenderHint::Full)
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(
+        written,
+        "\x1b7\x1b[38;5;42m✓\x1b[0m left-pad\r\n\x1b8\x1b[H\x1b[2JInstalling 2/5"
+    );
+}
+
+#[tokio::test]
+async fn test_terminal_print_closes_open_styles_before_next_frame() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal
+        .print_preserving_cursor("\x1b[31mfailed:\nretrying")
+        .await
+        .unwrap();
+    terminal
+        .render_frame("frame", RenderHint::Full)
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(
+        written,
+        "\x1b7\x1b[31mfailed:\x1b[0m\r\n\x1b[31mretrying\x1b[0m\x1b8\x1b[H\x1b[2Jframe"
+    );
+}
+
+#[tokio::test]
+async fn test_terminal_log_region_keeps_logged_lines_above_live_region() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default())
+        .with_log_region(2);
+
+    terminal
+        .render_frame("header\nBuilding\n[==  ]", RenderHint::Full)
+        .await
+        .unwrap();
+    terminal
+        .print(&format!("{STYLED_CHECK} foo\n"))
+        .await
+        .unwrap();
+    terminal.print("bar\n").await.unwrap();
+    terminal
+        .render_frame("header\nBuilding\n[=== ]", RenderHint::Full)
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(
+        written,
+        concat!(
+            // Only the last two lines of the view are live
+            "Building\r\n[==  ]",
+            // Each log erases the region from its top row, then repaints it
+            "\r\x1b[1A\x1b[J\x1b[38;5;42m✓\x1b[0m foo\r\nBuilding\r\n[==  ]",
+            "\r\x1b[1A\x1b[Jbar\r\nBuilding\r\n[==  ]",
+            "\r\x1b[1A\x1b[JBuilding\r\n[=== ]",
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_print_lines_command_produces_single_print_message() {
+    let msg = bubbletea_rs::print_lines(vec![
+        format!("{STYLED_CHECK} one"),
+        format!("{STYLED_CHECK} two"),
+    ])
+    .await
+    .unwrap();
+    let print = msg.downcast_ref::<bubbletea_rs::PrintMsg>().unwrap();
+    assert_eq!(print.0, format!("{STYLED_CHECK} one\n{STYLED_CHECK} two"));
+}
+
+#[tokio::test]
+async fn test_dummy_terminal_window_title_stack_discipline() {
+    let mut terminal = DummyTerminal::new(None).unwrap();
+    assert_eq!(terminal.window_title(), None);
+
+    terminal.set_window_title("editor").await.unwrap();
+    terminal.push_window_title("saving…").await.unwrap();
+    terminal.push_window_title("saving… 42%").await.unwrap();
+    assert_eq!(terminal.window_title(), Some("saving… 42%"));
+    assert_eq!(terminal.window_title_depth(), 2);
+
+    terminal.pop_window_title().await.unwrap();
+    assert_eq!(terminal.window_title(), Some("saving…"));
+    terminal.pop_window_title().await.unwrap();
+    assert_eq!(terminal.window_title(), Some("editor"));
+    assert_eq!(terminal.window_title_depth(), 0);
+
+    // Popping an empty stack is a no-op
+    terminal.pop_window_title().await.unwrap();
+    assert_eq!(terminal.window_title(), Some("editor"));
+
+    use DummyTerminalEvent::*;
+    assert_eq!(
+        terminal.events(),
+        [
+            SetWindowTitle,
+            PushWindowTitle,
+            PushWindowTitle,
+            PopWindowTitle,
+            PopWindowTitle
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_terminal_title_stack_sequences() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TITLE_STACK);
+
+    terminal.push_window_title("busy").await.unwrap();
+    terminal.pop_window_title().await.unwrap();
+    terminal.pop_window_title().await.unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(written, "\x1b[22;0t\x1b]0;busy\x07\x1b[23;0t");
+}
+
+#[tokio::test]
+async fn test_terminal_cursor_key_mode_sequences() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal
+        .set_cursor_key_mode(CursorKeyMode::Application)
+        .await
+        .unwrap();
+    terminal
+        .set_cursor_key_mode(CursorKeyMode::Normal)
+        .await
+        .unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(written, "\x1b[?1h\x1b[?1l");
+}
+
+#[tokio::test]
+async fn test_terminal_software_title_stack_fallback() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone()))
+        .unwrap()
+        .with_capabilities(TerminalCapabilities::default());
+
+    terminal.push_window_title("outer").await.unwrap();
+    terminal.push_window_title("inner\x07").await.unwrap();
+    terminal.pop_window_title().await.unwrap();
+    // The title from before the first push is unknown, so it stays
+    terminal.pop_window_title().await.unwrap();
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    assert_eq!(written, "\x1b]0;outer\x07\x1b]0;inner\x07\x1b]0;outer\x07");
+    assert_eq!(terminal.window_title_depth(), 0);
+}
+
+/// A two-panel model where each panel renders to its own terminal region.
+struct SplitPanels {
+    panels: [String; 2],
+    focused: usize,
+}
+
+impl SplitPanels {
+    fn update(&mut self, msg: &Msg) {
+        if let Some(key) = msg.downcast_ref::<KeyMsg>() {
+            match key.key {
+                KeyCode::Tab => self.focused = 1 - self.focused,
+                KeyCode::Char(c) => self.panels[self.focused].push(c),
+                _ => {}
+            }
+        }
+    }
+
+    async fn render(&self, pool: &mut DummyTerminalPool) {
+        for (i, text) in self.panels.iter().enumerate() {
+            let marker = if i == self.focused {
+                "\x1b[1m*\x1b[0m"
+            } else {
+                " "
+            };
+            let frame = format!("{marker} panel {i}\n{text}");
+            pool.terminal_mut(i)
+                .render(&frame, RenderHint::Full)
+                .await
+                .unwrap();
+        }
+    }
+}
+
+fn key(code: KeyCode) -> Msg {
+    Box::new(KeyMsg {
+        key: code,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[tokio::test]
+async fn test_terminal_pool_shares_event_queue_and_merges_frames() {
+    let mut pool = DummyTerminalPool::new(2);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.merge_frames(), "");
+
+    // Input arriving through either terminal lands in the same queue, in order
+    pool.terminal(0).send(key(KeyCode::Char('a'))).unwrap();
+    pool.terminal(1).send(key(KeyCode::Tab)).unwrap();
+    pool.sender().send(key(KeyCode::Char('b'))).unwrap();
+    pool.terminal(1).send(key(KeyCode::Char('c'))).unwrap();
+
+    let mut model = SplitPanels {
+        panels: [String::new(), String::new()],
+        focused: 0,
+    };
+    let messages = pool.drain_messages();
+    assert_eq!(messages.len(), 4);
+    for msg in &messages {
+        model.update(msg);
+    }
+    assert!(pool.drain_messages().is_empty());
+
+    model.render(&mut pool).await;
+    assert_eq!(pool.terminal(0).last_frame(), Some("  panel 0\na"));
+    assert_eq!(
+        pool.merge_frames(),
+        "  panel 0 │ \x1b[1m*\x1b[0m panel 1\na         │ bc"
+    );
+}
+
+#[tokio::test]
+async fn test_terminal_pool_pads_ragged_frames() {
+    let mut pool = DummyTerminalPool::new(3);
+    pool.terminal_mut(0)
+        .render("one\ntwo\nthree", RenderHint::Full)
+        .await
+        .unwrap();
+    pool.terminal_mut(2)
+        .render("x", RenderHint::Full)
+        .await
+        .unwrap();
+
+    assert_eq!(pool.merge_frames(), "one   │  │ x\ntwo   │  │\nthree │  │");
+
+    // A standalone dummy terminal has no event queue
+    let standalone = DummyTerminal::new(None).unwrap();
+    assert!(standalone.event_sender().is_none());
+    assert!(standalone.send(key(KeyCode::Enter)).is_err());
+}
+
+/// Draws one line, then quits.
+struct Prompt;
+
+impl Model for Prompt {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<KeyMsg>() {
+            return Some(bubbletea_rs::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        "> ".to_string()
+    }
+}
+
+/// Runs `Prompt` on a dummy terminal and returns everything it wrote.
+async fn run_prompt(clear_on_start: bool, alt_screen: bool) -> String {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Prompt>::builder()
+        .signal_handler(false)
+        .alt_screen(alt_screen)
+        .clear_on_start(clear_on_start)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new(KeyMsg {
+            key: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+        }));
+    });
+    tokio::time::timeout(std::time::Duration::from_secs(2), program.run())
+        .await
+        .expect("program timed out")
+        .expect("program run");
+
+    let written = output.lock().await.clone();
+    String::from_utf8(written).unwrap()
+}
+
+#[tokio::test]
+async fn test_clear_on_start_clears_before_first_inline_frame() {
+    // The initial frame, then the frame drawn after the key
+    assert_eq!(run_prompt(true, false).await, "\x1b[J> > ");
+    assert_eq!(run_prompt(false, false).await, "> > ");
+    // The alternate screen starts empty, so there is nothing to clear
+    assert_eq!(run_prompt(true, true).await, "> > ");
+}
+
+#[tokio::test]
+async fn test_terminal_clear_to_end_of_screen_sequence() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone())).unwrap();
+    terminal.clear_to_end_of_screen().await.unwrap();
+    assert_eq!(output.lock().await.as_slice(), b"\x1b[J");
+}
+
+#[tokio::test]
+async fn test_terminal_partial_clear_sequences() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut terminal = Terminal::new(Some(output.clone())).unwrap();
+    terminal.clear_line().await.unwrap();
+    terminal.clear_from_start_of_screen().await.unwrap();
+    assert_eq!(output.lock().await.as_slice(), b"\x1b[K\x1b[1J");
+}
+
+#[tokio::test]
+async fn test_dummy_terminal_records_each_clear() {
+    let mut terminal = DummyTerminal::new(None).unwrap();
+    terminal.clear_line().await.unwrap();
+    terminal.clear_to_end_of_screen().await.unwrap();
+    terminal.clear_from_start_of_screen().await.unwrap();
+    assert_eq!(
+        terminal.events(),
+        [
+            DummyTerminalEvent::ClearLine,
+            DummyTerminalEvent::ClearToEndOfScreen,
+            DummyTerminalEvent::ClearFromStartOfScreen,
+        ]
+    );
+}
+
+/// Counts the messages that reach it.
+struct Clearing {
+    /// Messages that reached `update`
+    seen: usize,
+}
+
+impl Model for Clearing {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { seen: 0 }, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {
+        self.seen += 1;
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_partial_clear_commands_reach_the_terminal() {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let program = Program::<Clearing>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .unwrap();
+    let sender = program.sender();
+    let clears = [
+        bubbletea_rs::clear_line(),
+        bubbletea_rs::clear_to_end(),
+        bubbletea_rs::clear_from_start(),
+        bubbletea_rs::quit(),
+    ];
+    for cmd in clears {
+        sender.send(cmd.await.unwrap()).unwrap();
+    }
+    let model = tokio::time::timeout(std::time::Duration::from_secs(2), program.run())
+        .await
+        .expect("program timed out")
+        .expect("program run");
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    let line = written.find("\x1b[K").expect("clear_line written");
+    let start = written.find("\x1b[1J").expect("clear_from_start written");
+    assert!(line < start, "got {written:?}");
+    // Only the quit message reaches the model
+    assert_eq!(model.seen, 1);
+}
+
+#[test]
+fn test_paste_supported_follows_terminal_capabilities() {
+    let program =
+        |bracketed_paste: bool| {
+            Program::<Prompt>::builder()
+                .signal_handler(false)
+                .terminal(DummyTerminal::new(None).unwrap().with_capabilities(
+                    TerminalCapabilities {
+                        bracketed_paste,
+                        ..TerminalCapabilities::default()
+                    },
+                ))
+                .build()
+                .expect("program build")
+        };
+    assert!(program(true).paste_supported());
+    assert!(!program(false).paste_supported());
+
+    let headless = Program::<Prompt>::builder()
+        .without_renderer()
+        .build()
+        .expect("program build");
+    assert!(!headless.paste_supported());
+}
+
+/// Looks variables up in a fixed list instead of the process environment.
+fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+    move |name| {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+#[test]
+fn test_resolve_size_prefers_the_terminal() {
+    let vars = env(&[("COLUMNS", "100"), ("LINES", "40")]);
+    assert_eq!(
+        resolve_size(Some((120, 50)), vars, DEFAULT_SIZE),
+        ((120, 50), SizeSource::Terminal)
+    );
+}
+
+#[test]
+fn test_resolve_size_falls_back_to_environment() {
+    let vars = env(&[("COLUMNS", "100"), ("LINES", "40")]);
+    assert_eq!(
+        resolve_size(None, &vars, DEFAULT_SIZE),
+        ((100, 40), SizeSource::Environment)
+    );
+    // A terminal that reports 0x0 does not know its size either
+    assert_eq!(
+        resolve_size(Some((0, 0)), &vars, DEFAULT_SIZE),
+        ((100, 40), SizeSource::Environment)
+    );
+    assert_eq!(
+        resolve_size(Some((120, 0)), &vars, DEFAULT_SIZE),
+        ((100, 40), SizeSource::Environment)
+    );
+}
+
+#[test]
+fn test_resolve_size_falls_back_to_default() {
+    assert_eq!(
+        resolve_size(None, env(&[]), DEFAULT_SIZE),
+        ((80, 24), SizeSource::Default)
+    );
+    // Both variables are needed, and both must be positive numbers
+    for vars in [
+        env(&[("COLUMNS", "100")]),
+        env(&[("COLUMNS", "100"), ("LINES", "0")]),
+        env(&[("COLUMNS", "wide"), ("LINES", "40")]),
+    ] {
+        assert_eq!(
+            resolve_size(Some((0, 0)), vars, (132, 43)),
+            ((132, 43), SizeSource::Default)
+        );
+    }
+}
+
+#[test]
+fn test_resolve_size_never_returns_zero() {
+    assert_eq!(
+        resolve_size(None, env(&[]), (0, 0)),
+        ((1, 1), SizeSource::Default)
+    );
+}
+
+#[test]
+fn test_terminal_capabilities_report_size_source() {
+    let terminal = Terminal::new(None).unwrap();
+    let (width, height) = terminal.size().unwrap();
+    assert!(width > 0 && height > 0);
+    let expected = resolve_size(
+        crossterm::terminal::size().ok(),
+        |name| std::env::var(name).ok(),
+        DEFAULT_SIZE,
+    );
+    assert_eq!(terminal.capabilities().size_source, expected.1);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "tokio-runtime")]
+
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, Model, Msg, PauseTimerGroupMsg, Program, QuitMsg,
+    ResumeTimerGroupMsg, TerminalInterface,
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+const ANIMATIONS: u64 = 1;
+const CLOCK: u64 = 2;
+
+#[derive(Debug)]
+struct TickMsg(&'static str);
+
+/// Runs two grouped animation timers, a timer in another group and a
+/// one-shot grouped tick, and records when each fires.
+struct Animated {
+    ticks: Vec<(&'static str, Instant)>,
+}
+
+impl Model for Animated {
+    fn init() -> (Self, Option<Cmd>) {
+        let every = |group, name| {
+            command::every_in_group(group, Duration::from_millis(10), move |_| {
+                Box::new(TickMsg(name)) as Msg
+            })
+            .0
+        };
+        let once = command::tick_in_group(ANIMATIONS, Duration::from_millis(150), |_| {
+            Box::new(TickMsg("once")) as Msg
+        });
+        let cmds = vec![
+            every(ANIMATIONS, "spinner"),
+            every(ANIMATIONS, "progress"),
+            every(CLOCK, "clock"),
+            once,
+        ];
+        (Self { ticks: Vec::new() }, Some(command::batch(cmds)))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(TickMsg(name)) = msg.downcast_ref::<TickMsg>() {
+            self.ticks.push((name, Instant::now()));
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_paused_group_holds_its_timers_until_resumed() {
+    let program = Program::<Animated>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        tokio::time::sleep(Duration::from_millis(55)).await;
+        let paused = Instant::now();
+        sender
+            .send(Box::new(PauseTimerGroupMsg { group: ANIMATIONS }) as Msg)
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let resumed = Instant::now();
+        sender
+            .send(Box::new(ResumeTimerGroupMsg { group: ANIMATIONS }) as Msg)
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(55)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+        (paused, resumed)
+    };
+    let (model, (paused, resumed)) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    let ticks = model.expect("program run").ticks;
+
+    let fired = |name, from: Instant, to: Instant| {
+        ticks
+            .iter()
+            .filter(|(tick, at)| *tick == name && from < *at && *at < to)
+            .count()
+    };
+    // A tick already on its way may land just after the pause
+    let settled = paused + Duration::from_millis(5);
+    for name in ["spinner", "progress"] {
+        assert!(fired(name, paused - Duration::from_millis(55), paused) >= 4);
+        assert_eq!(
+            fired(name, settled, resumed),
+            0,
+            "{name} fired while paused"
+        );
+        assert!(fired(name, resumed, Instant::now()) >= 4);
+    }
+    // Other groups keep running
+    assert!(fired("clock", settled, resumed) >= 15);
+    // The one-shot tick fired during the pause and was delivered on resume
+    let (_, once) = ticks.iter().find(|(tick, _)| *tick == "once").unwrap();
+    assert!(*once >= resumed);
+}
@@ -0,0 +1,93 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, FocusMsg, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use std::time::Duration;
+
+/// Leaves the window title to the program.
+struct Untitled;
+
+impl Model for Untitled {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// Titles the window after the number of focus changes it has seen.
+struct Counter {
+    focused: usize,
+}
+
+impl Model for Counter {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { focused: 0 }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<FocusMsg>() {
+            self.focused += 1;
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(format!("focused {}", self.focused))
+    }
+}
+
+/// Runs a program that gains focus twice, and returns the titles it set.
+async fn titles<M: Model>(title: Option<&str>) -> Vec<String> {
+    let terminal = DummyTerminal::new(None).unwrap();
+    let log = terminal.title_log();
+    let mut builder = Program::<M>::builder()
+        .signal_handler(false)
+        .terminal(terminal);
+    if let Some(title) = title {
+        builder = builder.with_title(title);
+    }
+    let program = builder.build().expect("program build");
+
+    let sender = program.sender();
+    let driver = tokio::spawn(async move {
+        for _ in 0..2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = sender.send(Box::new(FocusMsg) as Msg);
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = sender.send(Box::new(QuitMsg) as Msg);
+    });
+
+    program.run().await.expect("program run");
+    driver.await.unwrap();
+    let titles = log.lock().unwrap();
+    titles.clone()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_title_is_set_on_start_and_on_focus() {
+    assert_eq!(
+        titles::<Untitled>(Some("notes")).await,
+        ["notes", "notes", "notes"]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_no_title_leaves_window_title_alone() {
+    assert!(titles::<Untitled>(None).await.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_model_title_takes_precedence() {
+    assert_eq!(
+        titles::<Counter>(Some("notes")).await,
+        ["focused 0", "focused 1", "focused 2"]
+    );
+}
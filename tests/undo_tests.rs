@@ -0,0 +1,132 @@
+use bubbletea_rs::{Cmd, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct TickMsg;
+
+#[derive(Clone)]
+struct Editor {
+    text: String,
+    ticks: usize,
+    undo_keys_seen: usize,
+}
+
+impl Model for Editor {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self {
+                text: String::new(),
+                ticks: 0,
+                undo_keys_seen: 0,
+            },
+            None,
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if msg.is::<TickMsg>() {
+            self.ticks += 1;
+        }
+        if let Some(key_msg) = msg.downcast_ref::<KeyMsg>() {
+            match key_msg.key {
+                KeyCode::Char('z' | 'y') if key_msg.modifiers == KeyModifiers::CONTROL => {
+                    self.undo_keys_seen += 1;
+                }
+                KeyCode::Char(ch) => self.text.push(ch),
+                _ => {}
+            }
+        }
+    }
+
+    fn view(&self) -> String {
+        self.text.clone()
+    }
+}
+
+fn key(ch: char, modifiers: KeyModifiers) -> Msg {
+    Box::new(KeyMsg {
+        key: KeyCode::Char(ch),
+        modifiers,
+    })
+}
+
+async fn run_with(msgs: Vec<Msg>) -> Editor {
+    let program = Program::<Editor>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .with_undo(50)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    for msg in msgs {
+        sender.send(msg).unwrap();
+    }
+    sender.send(Box::new(QuitMsg)).unwrap();
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run")
+}
+
+#[tokio::test]
+async fn test_ctrl_z_and_ctrl_y_undo_and_redo_edits() {
+    let model = run_with(vec![
+        key('a', KeyModifiers::NONE),
+        key('b', KeyModifiers::NONE),
+        key('c', KeyModifiers::NONE),
+        key('z', KeyModifiers::CONTROL),
+        key('z', KeyModifiers::CONTROL),
+        key('y', KeyModifiers::CONTROL),
+    ])
+    .await;
+
+    assert_eq!(model.text, "ab");
+    // Undo keys are handled by the program, never by the model
+    assert_eq!(model.undo_keys_seen, 0);
+}
+
+#[tokio::test]
+async fn test_undo_past_first_edit_restores_initial_state() {
+    let model = run_with(vec![
+        key('a', KeyModifiers::NONE),
+        key('z', KeyModifiers::CONTROL),
+        key('z', KeyModifiers::CONTROL),
+    ])
+    .await;
+
+    assert_eq!(model.text, "");
+}
+
+#[tokio::test]
+async fn test_non_input_messages_are_not_recorded() {
+    let model = run_with(vec![
+        key('a', KeyModifiers::NONE),
+        Box::new(TickMsg),
+        Box::new(TickMsg),
+        key('b', KeyModifiers::NONE),
+        key('z', KeyModifiers::CONTROL),
+    ])
+    .await;
+
+    // A single undo steps back over the last key press, not the ticks, and
+    // restores the whole snapshot taken after 'a'
+    assert_eq!(model.text, "a");
+    assert_eq!(model.ticks, 0);
+}
+
+#[tokio::test]
+async fn test_new_edit_after_undo_discards_redo() {
+    let model = run_with(vec![
+        key('a', KeyModifiers::NONE),
+        key('b', KeyModifiers::NONE),
+        key('z', KeyModifiers::CONTROL),
+        key('c', KeyModifiers::NONE),
+        key('y', KeyModifiers::CONTROL),
+    ])
+    .await;
+
+    assert_eq!(model.text, "ac");
+}
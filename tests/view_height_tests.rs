@@ -0,0 +1,65 @@
+use bubbletea_rs::{
+    ansi, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct ShowMsg(&'static str);
+
+/// Shows whatever it was last told to.
+struct Display(&'static str);
+
+impl Model for Display {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self(""), None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(ShowMsg(view)) = msg.downcast_ref::<ShowMsg>() {
+            self.0 = view;
+        }
+    }
+
+    fn view(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Shows `view` on a terminal `width` columns wide and returns the height
+/// the program measured for it.
+async fn view_height(view: &'static str, width: u16) -> usize {
+    let program = Program::<Display>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap().with_size(width, 24))
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+    let drive = async {
+        sender.send(Box::new(ShowMsg(view)) as Msg).unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run_with_result(), drive)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run").view_height()
+}
+
+#[tokio::test]
+async fn test_view_height_counts_lines_that_wrap() {
+    // The first line is 15 columns wide and wraps onto a second row
+    assert_eq!(view_height("0123456789abcde\nxy", 10).await, 3);
+    // The same view fits on a wider terminal
+    assert_eq!(view_height("0123456789abcde\nxy", 20).await, 2);
+}
+
+#[tokio::test]
+async fn test_view_height_ignores_escape_sequences() {
+    let styled = "\x1b[1;32m0123456789\x1b[0m\nxy";
+    assert_eq!(ansi::visible_width(styled.lines().next().unwrap()), 10);
+    assert_eq!(view_height(styled, 10).await, 2);
+}
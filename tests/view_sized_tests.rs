@@ -0,0 +1,87 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, HeadlessRunner, IntoCmd, Model, Msg, Program, QuitMsg, TerminalInterface,
+    WindowSizeMsg,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Lays out its view from the size it is given, without storing it.
+struct Sized;
+
+impl Model for Sized {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {}
+
+    fn view_sized(&self, width: u16, height: u16) -> String {
+        format!("[{width}x{height}]")
+    }
+}
+
+fn resize(width: u16, height: u16) -> Msg {
+    Box::new(WindowSizeMsg { width, height })
+}
+
+/// Runs `Sized` on a dummy terminal, resizing to each of `sizes` in turn,
+/// and returns the output.
+async fn run(max_width: Option<u16>, sizes: &[(u16, u16)]) -> String {
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let mut builder = Program::<Sized>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(Some(output.clone())).unwrap())
+        .input(tokio::io::duplex(64).0);
+    if let Some(width) = max_width {
+        builder = builder.max_width(width);
+    }
+    let program = builder.build().expect("program build");
+    let sender = program.sender();
+
+    let drive = async {
+        for &(width, height) in sizes {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sender.send(resize(width, height)).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        sender.send(Box::new(QuitMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(program.run(), drive)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run");
+
+    let bytes = output.lock().await.clone();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_view_sized_receives_the_size_after_each_resize() {
+    let output = run(None, &[(100, 30), (60, 20)]).await;
+
+    let positions: Vec<_> = ["[0x0]", "[100x30]", "[60x20]"]
+        .iter()
+        .map(|frame| output.find(frame).expect("frame drawn"))
+        .collect();
+    assert!(positions.is_sorted());
+}
+
+#[tokio::test]
+async fn test_view_sized_receives_the_capped_width() {
+    let output = run(Some(50), &[(100, 30)]).await;
+
+    assert!(output.contains("[50x30]"));
+    assert!(!output.contains("[100x30]"));
+}
+
+#[test]
+fn test_headless_runner_passes_the_last_size() {
+    let mut runner = HeadlessRunner::<Sized>::new().unwrap();
+
+    assert_eq!(runner.process(Box::new(())).view, "[0x0]");
+    assert_eq!(runner.process(resize(120, 40)).view, "[120x40]");
+    assert_eq!(runner.process(Box::new(())).view, "[120x40]");
+}
@@ -0,0 +1,57 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, KeyMsg, Model, Msg, Program, QuitMsg, TerminalInterface,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+/// A model whose view takes at least 2 ms to draw.
+struct SlowView;
+
+impl Model for SlowView {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self, None)
+    }
+
+    fn update(&mut self, _msg: Msg) -> impl IntoCmd {
+        None::<Cmd>
+    }
+
+    fn view(&self) -> String {
+        std::thread::sleep(Duration::from_millis(2));
+        "slow".to_string()
+    }
+}
+
+#[tokio::test]
+async fn test_view_stats_time_every_frame() {
+    let program = Program::<SlowView>::builder()
+        .signal_handler(false)
+        .with_view_warning_threshold(Duration::from_millis(1))
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+    assert_eq!(program.view_stats().call_count, 0);
+
+    let timings = program.view_timings();
+    let sender = program.sender();
+    for _ in 0..3 {
+        let _ = sender.send(Box::new(KeyMsg {
+            key: KeyCode::Char('a'),
+            modifiers: KeyModifiers::NONE,
+        }) as Msg);
+    }
+    let _ = sender.send(Box::new(QuitMsg) as Msg);
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run");
+
+    let stats = timings.stats();
+    // The initial frame plus one per key
+    assert_eq!(stats.call_count, 4);
+    assert!(stats.slowest_duration >= Duration::from_millis(2));
+    assert!(stats.p95_duration >= Duration::from_millis(2));
+    assert!(stats.p95_duration <= stats.slowest_duration);
+    assert!(stats.total_duration >= Duration::from_millis(8));
+}
@@ -0,0 +1,145 @@
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, TerminalInterface, ViewportSizeMsg,
+};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct StepMsg;
+
+/// Asks for the viewport size, then turns the debug input overlay on and
+/// off again, one step at a time; quits on the third step.
+struct Pager {
+    steps: usize,
+    sizes: Vec<ViewportSizeMsg>,
+}
+
+impl Model for Pager {
+    fn init() -> (Self, Option<Cmd>) {
+        let model = Self {
+            steps: 0,
+            sizes: Vec::new(),
+        };
+        (model, Some(command::viewport_size()))
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(size) = msg.downcast_ref::<ViewportSizeMsg>() {
+            self.sizes.push(*size);
+            return None;
+        }
+        if !msg.is::<StepMsg>() {
+            return None;
+        }
+        self.steps += 1;
+        Some(match self.steps {
+            1 => command::set_debug_input_overlay(true),
+            2 => command::set_debug_input_overlay(false),
+            _ => command::quit(),
+        })
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+async fn run(program: Program<Pager>) -> Pager {
+    let sender = program.sender();
+    let steps = async {
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            sender.send(Box::new(StepMsg) as Msg).unwrap();
+        }
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), steps)
+    })
+    .await
+    .expect("program should quit");
+    result.expect("program run")
+}
+
+fn size(content_width: u16, content_height: u16) -> ViewportSizeMsg {
+    ViewportSizeMsg {
+        terminal_width: 80,
+        terminal_height: 24,
+        content_width,
+        content_height,
+    }
+}
+
+#[tokio::test]
+async fn test_viewport_size_follows_the_debug_overlay() {
+    let program = Program::<Pager>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap().with_size(80, 24))
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+
+    let model = run(program).await;
+
+    assert_eq!(model.sizes, [size(80, 24), size(80, 23), size(80, 24)]);
+}
+
+#[tokio::test]
+async fn test_viewport_size_leaves_out_framework_chrome() {
+    let program = Program::<Pager>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap().with_size(80, 24))
+        .input(tokio::io::duplex(64).0)
+        .inline_with_log_region(5)
+        .max_width(60)
+        .build()
+        .expect("program build");
+
+    let model = run(program).await;
+
+    assert_eq!(model.sizes, [size(60, 5), size(60, 4), size(60, 5)]);
+}
+
+#[tokio::test]
+async fn test_viewport_size_is_not_sent_unless_asked() {
+    struct Quiet {
+        sizes: usize,
+    }
+
+    impl Model for Quiet {
+        fn init() -> (Self, Option<Cmd>) {
+            (
+                Self { sizes: 0 },
+                Some(command::set_debug_input_overlay(true)),
+            )
+        }
+
+        fn update(&mut self, msg: Msg) -> impl IntoCmd {
+            if msg.is::<ViewportSizeMsg>() {
+                self.sizes += 1;
+            }
+            msg.is::<StepMsg>().then(command::quit)
+        }
+
+        fn view(&self) -> String {
+            String::new()
+        }
+    }
+
+    let program = Program::<Quiet>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap().with_size(80, 24))
+        .input(tokio::io::duplex(64).0)
+        .build()
+        .expect("program build");
+    let sender = program.sender();
+    let quit = async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sender.send(Box::new(StepMsg) as Msg).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+        tokio::join!(program.run(), quit)
+    })
+    .await
+    .expect("program should quit");
+
+    assert_eq!(result.expect("program run").sizes, 0);
+}
@@ -0,0 +1,120 @@
+use bubbletea_rs::{
+    command, Cmd, DummyTerminal, IntoCmd, Model, Msg, Program, TerminalInterface, WindowSizeMsg,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Records every size it is told about, asks for the size once more, then
+/// quits.
+struct Embedded {
+    sizes: Vec<(u16, u16)>,
+}
+
+impl Model for Embedded {
+    fn init() -> (Self, Option<Cmd>) {
+        (
+            Self { sizes: Vec::new() },
+            Some(command::set_viewport_size(100, 30)),
+        )
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        let size = msg.downcast_ref::<WindowSizeMsg>()?;
+        self.sizes.push((size.width, size.height));
+        match self.sizes.len() {
+            1 => Some(command::window_size()),
+            _ => Some(command::quit()),
+        }
+    }
+
+    fn view(&self) -> String {
+        format!("{:?}", self.sizes.last())
+    }
+}
+
+#[tokio::test]
+async fn test_set_viewport_size_overrides_terminal_size() {
+    // The dummy terminal itself reports a size of 0x0
+    let program = Program::<Embedded>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program timed out")
+        .expect("program run");
+
+    assert_eq!(model.sizes, [(100, 30), (100, 30)]);
+}
+
+/// Shows the size it was last told about.
+struct Sized {
+    size: Option<(u16, u16)>,
+}
+
+impl Model for Sized {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { size: None }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(size) = msg.downcast_ref::<WindowSizeMsg>() {
+            self.size = Some((size.width, size.height));
+        } else if msg.is::<&str>() {
+            return Some(command::quit());
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        match self.size {
+            Some((width, height)) => format!("{width}x{height}|"),
+            None => "unsized|".to_string(),
+        }
+    }
+}
+
+async fn first_frame(terminal: DummyTerminal, output: Arc<Mutex<Vec<u8>>>) -> String {
+    let program = Program::<Sized>::builder()
+        .signal_handler(false)
+        .terminal(terminal)
+        .build()
+        .expect("program build");
+
+    let sender = program.sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = sender.send(Box::new("done"));
+    });
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program timed out")
+        .expect("program run");
+
+    let written = String::from_utf8(output.lock().await.clone()).unwrap();
+    // Every frame ends with '|'
+    written
+        .split_inclusive('|')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_window_size_is_delivered_before_first_frame() {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let terminal = DummyTerminal::new(Some(output.clone()))
+        .unwrap()
+        .with_size(120, 40);
+    assert_eq!(first_frame(terminal, output).await, "120x40|");
+}
+
+#[tokio::test]
+async fn test_unknown_terminal_size_is_not_reported() {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let terminal = DummyTerminal::new(Some(output.clone())).unwrap();
+    assert_eq!(first_frame(terminal, output).await, "unsized|");
+}
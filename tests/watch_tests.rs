@@ -0,0 +1,115 @@
+#![cfg(feature = "watch")]
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use bubbletea_rs::{command, Cmd, ErrorMsg, Model, Msg, Program, WatchEvent};
+
+static WATCH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug)]
+struct WriteFileMsg;
+
+#[derive(Debug)]
+struct FileChangedMsg(WatchEvent);
+
+struct WatchModel {
+    events: Vec<WatchEvent>,
+    errors: usize,
+}
+
+impl Model for WatchModel {
+    fn init() -> (Self, Option<Cmd>) {
+        let dir = WATCH_DIR.get().expect("watch dir").clone();
+        let cmd = command::batch(vec![
+            command::watch_path(dir, Duration::from_millis(50), |event| {
+                Box::new(FileChangedMsg(event)) as Msg
+            }),
+            // Give the watcher time to register before touching the file system
+            command::tick(Duration::from_millis(200), |_| {
+                Box::new(WriteFileMsg) as Msg
+            }),
+        ]);
+        (
+            Self {
+                events: Vec::new(),
+                errors: 0,
+            },
+            Some(cmd),
+        )
+    }
+
+    #[allow(refining_impl_trait)]
+    fn update(&mut self, msg: Msg) -> Option<Cmd> {
+        if msg.downcast_ref::<WriteFileMsg>().is_some() {
+            let file = WATCH_DIR.get().unwrap().join("watched.txt");
+            // Several writes in quick succession should be debounced
+            for i in 0..5 {
+                std::fs::write(&file, format!("revision {i}")).unwrap();
+            }
+            return None;
+        }
+        if let Some(FileChangedMsg(event)) = msg.downcast_ref::<FileChangedMsg>() {
+            self.events.push(event.clone());
+            return Some(command::tick(Duration::from_millis(300), |_| {
+                Box::new(bubbletea_rs::QuitMsg) as Msg
+            }));
+        }
+        if msg.downcast_ref::<ErrorMsg>().is_some() {
+            self.errors += 1;
+        }
+        None
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+#[tokio::test]
+async fn test_watch_path_reports_debounced_changes() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let dir_path = dir.path().canonicalize().unwrap();
+    WATCH_DIR.set(dir_path.clone()).unwrap();
+
+    let program = Program::<WatchModel>::builder()
+        .without_renderer()
+        .signal_handler(false)
+        .build()
+        .expect("program build");
+
+    let model = tokio::time::timeout(Duration::from_secs(5), program.run())
+        .await
+        .expect("watcher should report a change before the timeout")
+        .expect("program run");
+
+    assert_eq!(model.errors, 0);
+    assert!(!model.events.is_empty());
+    assert!(model.events.iter().all(|e| e.path().starts_with(&dir_path)));
+    // Five rapid writes must not produce five separate modify notifications
+    let modified = model
+        .events
+        .iter()
+        .filter(|e| matches!(e, WatchEvent::Modified(_)))
+        .count();
+    assert!(modified <= 2, "expected debounced events, got {modified}");
+}
+
+#[tokio::test]
+async fn test_watch_path_with_id_returns_internal_message() {
+    let (cmd, id) =
+        command::watch_path_with_id("/tmp", Duration::from_millis(10), |_| Box::new(()) as Msg);
+    let msg = cmd.await.expect("watch command produces a message");
+    let watch_msg = msg
+        .downcast_ref::<bubbletea_rs::watch::WatchMsgInternal>()
+        .expect("WatchMsgInternal");
+    assert_eq!(watch_msg.watch_id, id);
+    assert_eq!(watch_msg.path, PathBuf::from("/tmp"));
+
+    let cancel = command::cancel_watch(id).await.unwrap();
+    let cancel_msg = cancel
+        .downcast_ref::<bubbletea_rs::CancelWatchMsg>()
+        .expect("CancelWatchMsg");
+    assert_eq!(cancel_msg.watch_id, id);
+}
@@ -0,0 +1,100 @@
+use bubbletea_rs::{
+    Cmd, DummyTerminal, IntoCmd, Model, MouseMsg, Msg, Program, ProgramBuilder, QuitMsg,
+    TerminalInterface,
+};
+use crossterm::event::{KeyModifiers, MouseEventKind};
+use std::time::Duration;
+
+/// Records the scroll delta of each delivered wheel message.
+struct Pager {
+    deltas: Vec<u16>,
+}
+
+impl Model for Pager {
+    fn init() -> (Self, Option<Cmd>) {
+        (Self { deltas: Vec::new() }, None)
+    }
+
+    fn update(&mut self, msg: Msg) -> impl IntoCmd {
+        if let Some(mouse) = msg.downcast_ref::<MouseMsg>() {
+            if mouse.button == MouseEventKind::ScrollUp {
+                self.deltas.push(mouse.scroll_delta);
+            }
+        }
+    }
+
+    fn view(&self) -> String {
+        String::new()
+    }
+}
+
+/// A wheel event as the input decoder delivers it.
+fn wheel(button: MouseEventKind) -> Msg {
+    Box::new(MouseMsg {
+        x: 10,
+        y: 5,
+        button,
+        modifiers: KeyModifiers::NONE,
+        scroll_delta: 1,
+    })
+}
+
+/// Queues `msgs` before the program starts and returns the deltas seen.
+async fn run_with_queue(
+    configure: impl FnOnce(ProgramBuilder<Pager>) -> ProgramBuilder<Pager>,
+    msgs: Vec<Msg>,
+) -> Vec<u16> {
+    let builder = Program::<Pager>::builder()
+        .signal_handler(false)
+        .terminal(DummyTerminal::new(None).unwrap())
+        .input(tokio::io::duplex(64).0);
+    let program = configure(builder).build().expect("program build");
+
+    let sender = program.sender();
+    for msg in msgs {
+        sender.send(msg).unwrap();
+    }
+    sender.send(Box::new(QuitMsg) as Msg).unwrap();
+
+    tokio::time::timeout(Duration::from_secs(2), program.run())
+        .await
+        .expect("program should quit")
+        .expect("program run")
+        .deltas
+}
+
+fn burst() -> Vec<Msg> {
+    (0..30).map(|_| wheel(MouseEventKind::ScrollUp)).collect()
+}
+
+#[tokio::test]
+async fn test_queued_wheel_events_are_coalesced() {
+    let deltas = run_with_queue(|b| b.coalesce_wheel_events(true), burst()).await;
+
+    assert!(deltas.len() <= 2, "got {} messages", deltas.len());
+    assert_eq!(deltas.iter().map(|&d| u32::from(d)).sum::<u32>(), 30);
+}
+
+#[tokio::test]
+async fn test_wheel_events_arrive_one_by_one_by_default() {
+    let deltas = run_with_queue(|b| b, burst()).await;
+
+    assert_eq!(deltas, vec![1; 30]);
+}
+
+#[tokio::test]
+async fn test_coalescing_stops_at_a_change_of_direction() {
+    let mut msgs = burst();
+    msgs.insert(10, wheel(MouseEventKind::ScrollDown));
+    let deltas = run_with_queue(|b| b.coalesce_wheel_events(true), msgs).await;
+
+    assert_eq!(deltas, [10, 20]);
+}
+
+#[tokio::test]
+async fn test_wheel_scroll_lines_scales_each_notch() {
+    let msgs = (0..3).map(|_| wheel(MouseEventKind::ScrollUp)).collect();
+    let deltas = run_with_queue(|b| b.wheel_scroll_lines(3), msgs).await;
+
+    assert_eq!(deltas, [3, 3, 3]);
+}